@@ -30,6 +30,11 @@ struct EnvironmentConfig {
         if components.port == nil && components.scheme == "http" {
             components.port = 3414
         }
+        // API paths resolve against the base URL's directory, so a server mounted under a --base-path
+        // prefix such as /agentrix needs the trailing slash to keep it
+        if !components.path.hasSuffix("/") {
+            components.path += "/"
+        }
         return components.url ?? baseURL
     }
 
@@ -48,17 +53,16 @@ struct EnvironmentConfig {
     }
 
     func terminalWebSocketURL(sessionId: String) -> URL {
-        var components = URLComponents(url: baseURL, resolvingAgainstBaseURL: false) ?? URLComponents()
-        components.scheme = components.scheme == "https" ? "wss" : "ws"
-        components.path = "/api/terminal/socket"
-        components.queryItems = [URLQueryItem(name: "sessionId", value: sessionId)]
-        return components.url ?? baseURL
+        webSocketURL(for: "/api/terminal/socket", sessionId: sessionId)
     }
 
     func codexSdkWebSocketURL(sessionId: String) -> URL {
-        var components = URLComponents(url: baseURL, resolvingAgainstBaseURL: false) ?? URLComponents()
+        webSocketURL(for: "/api/codex-sdk/socket", sessionId: sessionId)
+    }
+
+    private func webSocketURL(for path: String, sessionId: String) -> URL {
+        var components = URLComponents(url: url(for: path), resolvingAgainstBaseURL: false) ?? URLComponents()
         components.scheme = components.scheme == "https" ? "wss" : "ws"
-        components.path = "/api/codex-sdk/socket"
         components.queryItems = [URLQueryItem(name: "sessionId", value: sessionId)]
         return components.url ?? baseURL
     }
@@ -345,6 +345,27 @@ final class AgentrixMobileTests: XCTestCase {
         XCTAssertNil(store.openingActionIdentifier)
     }
 
+    func testEnvironmentConfigKeepsBasePathForRequestsAndSockets() {
+        let config = EnvironmentConfig(baseURL: URL(string: "https://example.com/agentrix")!)
+
+        XCTAssertEqual(config.url(for: "/api/repos").absoluteString, "https://example.com/agentrix/api/repos")
+        XCTAssertEqual(
+            config.terminalWebSocketURL(sessionId: "abc").absoluteString,
+            "wss://example.com/agentrix/api/terminal/socket?sessionId=abc"
+        )
+        XCTAssertEqual(
+            config.codexSdkWebSocketURL(sessionId: "abc").absoluteString,
+            "wss://example.com/agentrix/api/codex-sdk/socket?sessionId=abc"
+        )
+
+        let root = EnvironmentConfig(baseURL: URL(string: "http://127.0.0.1:3414")!)
+        XCTAssertEqual(root.url(for: "/api/repos").absoluteString, "http://127.0.0.1:3414/api/repos")
+        XCTAssertEqual(
+            root.terminalWebSocketURL(sessionId: "abc").absoluteString,
+            "ws://127.0.0.1:3414/api/terminal/socket?sessionId=abc"
+        )
+    }
+
     // MARK: - Helpers
 
     private func makeMockServices() throws -> ServiceRegistry {
@@ -170,6 +170,7 @@ describe('createAuthHandlers', () => {
     const rateLimiter: RateLimiter = {
       check: mock.fn(() => ({ limited: true, retryAfterMs: 1000, attempts: 5 })),
       recordFailure: mock.fn(() => ({ limited: true, retryAfterMs: 1000, attempts: 5 })),
+      consume: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       reset: mock.fn(() => {}),
     };
     const logger: Logger = { info: mock.fn(), error: mock.fn(), warn: mock.fn(), debug: mock.fn() };
@@ -199,6 +200,7 @@ describe('createAuthHandlers', () => {
     const rateLimiter: RateLimiter = {
       check: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       recordFailure: mock.fn(() => ({ limited: true, retryAfterMs: 5000, attempts: 5 })),
+      consume: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       reset: mock.fn(() => {}),
     };
     const logger: Logger = { info: mock.fn(), error: mock.fn(), warn: mock.fn(), debug: mock.fn() };
@@ -222,6 +224,7 @@ describe('createAuthHandlers', () => {
     const rateLimiter: RateLimiter = {
       check: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       recordFailure: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
+      consume: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       reset: mock.fn(() => {}),
     };
 
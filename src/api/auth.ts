@@ -35,6 +35,10 @@ interface AuthHandlersOptions {
   cookieManager?: CookieManager;
   rateLimiter?: RateLimiter;
   logger?: Logger;
+  /**
+   * Takes the client address from `X-Forwarded-For` when throttling logins
+   */
+  trustProxy?: boolean;
 }
 
 /**
@@ -49,7 +53,7 @@ export function __setAuthTestOverrides(overrides?: Partial<AuthDependencies>): v
 }
 
 export function createAuthHandlers(authManager: AuthManager, options: AuthHandlersOptions = {}) {
-  const { cookieManager, rateLimiter, logger } = options;
+  const { cookieManager, rateLimiter, logger, trustProxy = false } = options;
   const authService = activeDependencies.createAuthService(authManager, cookieManager);
   const log = activeDependencies.createLogger(logger);
   const loginRateLimiter =
@@ -64,7 +68,7 @@ export function createAuthHandlers(authManager: AuthManager, options: AuthHandle
     const payload = await context.readJsonBody();
     const password = typeof payload['password'] === 'string' ? payload['password'].trim() : '';
     const username = typeof payload['username'] === 'string' ? payload['username'].trim() : '';
-    const clientIp = getClientIp(context.req);
+    const clientIp = getClientIp(context.req, { trustProxy });
     const limiterKey = clientIp;
 
     const limiterStatus = loginRateLimiter.check(limiterKey);
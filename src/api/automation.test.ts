@@ -77,7 +77,7 @@ describe('ensureRepositoryExists', () => {
       });
       assert.equal(clone.mock.calls.length, 1);
       const cloneArgs = clone.mock.calls[0]?.arguments ?? [];
-      assert.deepEqual(cloneArgs, ['/repos', 'git@github.com:org/repo.git']);
+      assert.deepEqual(cloneArgs, ['/repos', 'git@github.com:org/repo.git', {}]);
       assert.equal(ensure.mock.calls.length, 2);
     } finally {
       __setAutomationTestOverrides();
@@ -237,6 +237,7 @@ describe('createAutomationHandlers', () => {
     const rateLimiter: RateLimiter = {
       check: mock.fn(() => ({ limited: true, retryAfterMs: 2000, attempts: 5 })),
       recordFailure: mock.fn(() => ({ limited: true, retryAfterMs: 0, attempts: 0 })),
+      consume: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       reset: mock.fn(() => {}),
     };
     const logger: Logger = { info: mock.fn(), error: mock.fn(), warn: mock.fn(), debug: mock.fn() };
@@ -271,6 +272,7 @@ describe('createAutomationHandlers', () => {
     const rateLimiter: RateLimiter = {
       check: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       recordFailure: mock.fn(() => ({ limited: true, retryAfterMs: 4000, attempts: 5 })),
+      consume: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       reset: mock.fn(() => {}),
     };
     const logger: Logger = { info: mock.fn(), error: mock.fn(), warn: mock.fn(), debug: mock.fn() };
@@ -312,6 +314,7 @@ describe('createAutomationHandlers', () => {
     const rateLimiter: RateLimiter = {
       check: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       recordFailure: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
+      consume: mock.fn(() => ({ limited: false, retryAfterMs: 0, attempts: 0 })),
       reset: mock.fn(() => {}),
     };
 
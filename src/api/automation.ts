@@ -5,10 +5,13 @@ import {
   createWorktree,
   ensureRepository,
   getWorktreePath,
+  type CloneSettings,
+  type WorktreeSettings,
 } from '../core/git.js';
 import { launchAgentProcess } from '../core/agents.js';
 import { runTask } from '../core/tasks.js';
 import { getClientIp, sendJson } from '../utils/http.js';
+import { refreshRepositoryCache, type RepositoryCacheOptions } from '../utils/repository-cache.js';
 import {
   validateAutomationRequest,
   AutomationRequestError,
@@ -80,7 +83,8 @@ export function __setAutomationTestOverrides(overrides?: Partial<AutomationModul
 export async function ensureRepositoryExists(
   workdir: string,
   org: string,
-  repo: string
+  repo: string,
+  cloneSettings: CloneSettings = {}
 ): Promise<{ repositoryPath: string; cloned: boolean }> {
   try {
     const { repositoryPath } = await activeAutomationDependencies.ensureRepository(workdir, org, repo);
@@ -89,7 +93,7 @@ export async function ensureRepositoryExists(
     const err = error as { message?: string };
     if (err && /Repository not found/i.test(err.message || '')) {
       const remote = `git@github.com:${org}/${repo}.git`;
-      await activeAutomationDependencies.cloneRepository(workdir, remote);
+      await activeAutomationDependencies.cloneRepository(workdir, remote, cloneSettings);
       const { repositoryPath } = await activeAutomationDependencies.ensureRepository(workdir, org, repo);
       return { repositoryPath, cloned: true };
     }
@@ -163,6 +167,23 @@ export interface AutomationHandlersConfig {
   planService: unknown;
   logger?: Logger;
   defaultBranches: unknown;
+  /**
+   * Takes the client address from `X-Forwarded-For` when throttling launches
+   */
+  trustProxy?: boolean;
+  /**
+   * Default org, git config and queue applied when a missing repository is cloned
+   */
+  clone?: CloneSettings;
+  /**
+   * Identity, default base, limit and git config applied to the worktrees it creates
+   */
+  worktree?: WorktreeSettings;
+  /**
+   * Extra environment variables for the agent terminals it launches
+   */
+  terminalEnv?: Record<string, string> | null;
+  repositoryCache?: RepositoryCacheOptions;
 }
 
 export interface AutomationHandlersDependencies {
@@ -184,6 +205,11 @@ export function createAutomationHandlers(
     planService,
     logger,
     defaultBranches,
+    trustProxy = false,
+    clone = {},
+    worktree = {},
+    terminalEnv,
+    repositoryCache,
   }: AutomationHandlersConfig,
   {
     ensureRepositoryExists: ensureRepoExists = ensureRepositoryExists,
@@ -196,9 +222,12 @@ export function createAutomationHandlers(
   }: AutomationHandlersDependencies = {}
 ) {
   const gitOrchestrator = activeAutomationDependencies.createGitOrchestrator({
-    ensureRepositoryExists: ensureRepoExists,
-    ensureWorktreeExists: ensureWorktree,
+    ensureRepositoryExists: (dir, org, repo) => ensureRepoExists(dir, org, repo, clone),
+    ensureWorktreeExists: (dir, org, repo, branch, options) =>
+      ensureWorktree(dir, org, repo, branch, { ...worktree, ...(options as object) }),
+    refreshCache: (dir) => refreshRepositoryCache(dir, repositoryCache),
   });
+  const launchInTerminal: typeof launchAgentProcess = (params) => launchAgent({ ...params, env: terminalEnv });
 
   const log = activeAutomationDependencies.createLogger(logger);
   const automationRateLimiter =
@@ -216,7 +245,7 @@ export function createAutomationHandlers(
       readJsonBody: () => Promise<unknown>;
     };
     const providedApiKey = extractApiKey(req);
-    const clientIp = getClientIp(req);
+    const clientIp = getClientIp(req, { trustProxy });
     const limiterKey = clientIp;
 
     const limiterStatus = automationRateLimiter.check(limiterKey);
@@ -328,7 +357,7 @@ export function createAutomationHandlers(
         resolveBranchName: activeAutomationDependencies.resolveBranchName as never,
         generatePlanText: activeAutomationDependencies.generatePlanText as never,
         gitOrchestrator,
-        launchAgent: launchAgent as never,
+        launchAgent: launchInTerminal as never,
         workdir,
         planEnabled,
         routeLabel,
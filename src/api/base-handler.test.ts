@@ -8,6 +8,7 @@ import {
     createQueryHandler,
     createSimpleHandler,
 } from './base-handler.js';
+import { assignRequestId } from '../utils/request-id.js';
 
 function createContext(overrides: Partial<RequestContext> = {}): RequestContext {
   return {
@@ -134,3 +135,84 @@ describe('createQueryHandler', () => {
   });
 });
 
+describe('response meta', () => {
+  it('adds meta to the data envelope only when the request opts in', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson, now: () => new Date('2024-05-01T10:00:00.000Z') });
+
+    const handlerFn = createQueryHandler(async () => ({ data: { count: 1 } }));
+    await handlerFn(createContext({ method: 'GET', req: { headers: { 'x-response-meta': 'true' } } as unknown as RequestContext['req'] }));
+    await handlerFn(createContext({ method: 'GET' }));
+    await createQueryHandler(async () => ({ mergeBase: 'abc' }))(
+      createContext({ method: 'GET', req: { headers: { 'x-response-meta': '1' } } as unknown as RequestContext['req'] })
+    );
+    __setBaseHandlerTestOverrides();
+
+    const bodies = sendJson.mock.calls.map((call) => call.arguments[2]);
+    assert.deepEqual(bodies[0], {
+      data: { count: 1 },
+      meta: { servedAt: '2024-05-01T10:00:00.000Z', apiVersion: '1' },
+    });
+    assert.equal((bodies[0] as { meta: { apiVersion?: string } }).meta.apiVersion, '1');
+    assert.deepEqual(bodies[1], { data: { count: 1 } });
+    assert.deepEqual(bodies[2], { mergeBase: 'abc' });
+  });
+});
+
+describe('time format', () => {
+  it('writes timestamps as epoch seconds when the request asks for unix time', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const handler = mock.fn(async () => ({ data: { lastCommit: { hash: 'abc', committedAt: '2024-05-01T10:00:00+02:00' } } }));
+    const handlerFn = createQueryHandler(handler);
+
+    await handlerFn(createContext({ method: 'GET', url: new URL('http://localhost/test?timeFormat=unix') }));
+    await handlerFn(createContext({ method: 'GET' }));
+    const invalid = createContext({
+      method: 'GET',
+      url: new URL('http://localhost/test?timeFormat=epoch'),
+      res: { statusCode: 0, setHeader: mock.fn(), end: mock.fn() } as unknown as RequestContext['res'],
+    });
+    await handlerFn(invalid);
+    __setBaseHandlerTestOverrides();
+
+    const bodies = sendJson.mock.calls.map((call) => call.arguments[2]);
+    assert.deepEqual(bodies, [
+      { data: { lastCommit: { hash: 'abc', committedAt: 1714550400 } } },
+      { data: { lastCommit: { hash: 'abc', committedAt: '2024-05-01T10:00:00+02:00' } } },
+    ]);
+    assert.equal(invalid.res.statusCode, 400);
+    assert.equal(handler.mock.callCount(), 2);
+  });
+});
+
+describe('error responses', () => {
+  it('tags 500 responses with the request id', async () => {
+    const consoleError = mock.method(console, 'error', () => {});
+    const res = {
+      statusCode: 0,
+      setHeader: mock.fn(),
+      end: mock.fn(),
+    } as unknown as RequestContext['res'];
+    const requestId = assignRequestId(res, () => 'req-123');
+
+    const handlerFn = createHandler({
+      handler: async () => {
+        throw new Error('disk on fire');
+      },
+      readBody: false,
+    });
+
+    await handlerFn(createContext({ res }));
+    consoleError.mock.restore();
+
+    assert.equal(requestId, 'req-123');
+    assert.equal(res.statusCode, 500);
+    const body = JSON.parse((res.end as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0] as string);
+    assert.deepEqual(body, { error: 'disk on fire', requestId: 'req-123' });
+    const headerCalls = (res.setHeader as ReturnType<typeof mock.fn>).mock.calls;
+    assert.ok(headerCalls.some((call) => call.arguments[0] === 'X-Request-Id' && call.arguments[1] === 'req-123'));
+    assert.match(String(consoleError.mock.calls[0]?.arguments[0]), /Request req-123 failed with 500/);
+  });
+});
+
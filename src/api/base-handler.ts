@@ -1,7 +1,22 @@
 import { sendJson } from '../utils/http.js';
 import { asyncHandler } from '../infrastructure/errors/index.js';
+import { API_VERSION } from '../config/constants.js';
+import { applyTimeFormat, parseTimeFormat, TIME_FORMAT_PARAM } from '../utils/time-format.js';
 import type { RequestContext } from '../types/http.js';
 
+/**
+ * Request header that opts in to a `meta` object alongside `data` in the response envelope
+ */
+export const RESPONSE_META_HEADER = 'X-Response-Meta';
+
+export interface ResponseMeta {
+  /**
+   * RFC 3339 timestamp of when the response was produced
+   */
+  servedAt: string;
+  apiVersion: string;
+}
+
 /**
  * Generic handler function type
  */
@@ -30,9 +45,9 @@ export interface HandlerOptions<TInput, TOutput> {
   validator?: ValidationFunction<TInput>;
   
   /**
-   * HTTP status code for successful response (default: 200)
+   * HTTP status code for successful response (default: 200), or a function choosing it from the result
    */
-  successCode?: number;
+  successCode?: number | ((result: TOutput) => number);
   
   /**
    * Whether to read JSON body from request (default: true if validator is provided)
@@ -59,10 +74,12 @@ export interface HandlerOptions<TInput, TOutput> {
  */
 interface HandlerDependencies {
   sendJson: typeof sendJson;
+  now: () => Date;
 }
 
 const defaultDependencies: HandlerDependencies = {
   sendJson,
+  now: () => new Date(),
 };
 
 let activeDependencies: HandlerDependencies = { ...defaultDependencies };
@@ -78,6 +95,25 @@ export function __setBaseHandlerTestOverrides(overrides?: Partial<HandlerDepende
   activeDependencies = { ...activeDependencies, ...overrides } as HandlerDependencies;
 }
 
+/**
+ * Adds `meta` to a `{ data }` envelope when the request opted in with the X-Response-Meta header. Other
+ * responses, and requests without the header, are sent unchanged so existing clients see no difference.
+ */
+function withResponseMeta(context: RequestContext, response: unknown): unknown {
+  const requested = context.req?.headers?.[RESPONSE_META_HEADER.toLowerCase()];
+  if (
+    (requested !== 'true' && requested !== '1') ||
+    !response ||
+    typeof response !== 'object' ||
+    Array.isArray(response) ||
+    !Object.prototype.hasOwnProperty.call(response, 'data')
+  ) {
+    return response;
+  }
+  const meta: ResponseMeta = { servedAt: activeDependencies.now().toISOString(), apiVersion: API_VERSION };
+  return { ...response, meta };
+}
+
 export function createHandler<TInput = void, TOutput = unknown>(
   options: HandlerOptions<TInput, TOutput>
 ): (context: RequestContext) => Promise<void> {
@@ -90,6 +126,8 @@ export function createHandler<TInput = void, TOutput = unknown>(
   } = options;
 
   return asyncHandler(async (context: RequestContext) => {
+    // Read up front so an unknown format is refused before the handler does any work
+    const timeFormat = parseTimeFormat(context.url.searchParams.get(TIME_FORMAT_PARAM));
     let input: TInput;
 
     if (readBody) {
@@ -100,12 +138,21 @@ export function createHandler<TInput = void, TOutput = unknown>(
     }
 
     const result = await handler(input, context);
-    
+    // Handlers that already answered (event streams) skip the envelope
+    if (context.res.writableEnded) {
+      return;
+    }
+
     const response = responseTransformer 
       ? responseTransformer(result)
       : result;
 
-    activeDependencies.sendJson(context.res, successCode, response);
+    const statusCode = typeof successCode === 'function' ? successCode(result) : successCode;
+    activeDependencies.sendJson(
+      context.res,
+      statusCode,
+      applyTimeFormat(withResponseMeta(context, response), timeFormat)
+    );
   });
 }
 
@@ -114,7 +161,7 @@ export function createHandler<TInput = void, TOutput = unknown>(
  */
 export function createSimpleHandler<TOutput = unknown>(
   handler: (context: RequestContext) => Promise<TOutput>,
-  options: { successCode?: number; responseTransformer?: (result: TOutput) => unknown } = {}
+  options: { successCode?: number | ((result: TOutput) => number); responseTransformer?: (result: TOutput) => unknown } = {}
 ): (context: RequestContext) => Promise<void> {
   return createHandler({
     handler: async (_input: void, context: RequestContext) => handler(context),
@@ -131,11 +178,20 @@ export function createQueryHandler<TOutput = unknown>(
   options: { successCode?: number; responseTransformer?: (result: TOutput) => unknown } = {}
 ): (context: RequestContext) => Promise<void> {
   return asyncHandler(async (context: RequestContext) => {
+    const timeFormat = parseTimeFormat(context.url.searchParams.get(TIME_FORMAT_PARAM));
     const result = await handler(context);
+    // Handlers that already answered (HEAD requests, non-JSON bodies) skip the envelope
+    if (context.res.writableEnded) {
+      return;
+    }
     const response = options.responseTransformer 
       ? options.responseTransformer(result)
       : result;
-    activeDependencies.sendJson(context.res, options.successCode || 200, response);
+    activeDependencies.sendJson(
+      context.res,
+      options.successCode || 200,
+      applyTimeFormat(withResponseMeta(context, response), timeFormat)
+    );
   });
 }
 
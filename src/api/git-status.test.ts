@@ -1,4 +1,6 @@
 import assert from 'node:assert/strict';
+import { EventEmitter } from 'node:events';
+import { Readable } from 'node:stream';
 import { describe, it, mock } from 'node:test';
 
 import {
@@ -7,15 +9,22 @@ import {
 } from './git-status.js';
 import { __setBaseHandlerTestOverrides } from './base-handler.js';
 import { ValidationError } from '../infrastructure/errors/index.js';
+import {
+  DEFAULT_COMPARE_DIFF_LIMIT_BYTES,
+  DEFAULT_GIT_OUTPUT_LIMIT_BYTES,
+  UnrelatedHistoriesError,
+} from '../core/git.js';
 import type { RequestContext } from '../types/http.js';
 
 function setupOverrides(deps?: {
   getWorktreeStatus?: typeof import('../core/git.js').getWorktreeStatus;
+  getWorktreeSummary?: typeof import('../core/git.js').getWorktreeSummary;
   getWorktreeFileDiff?: typeof import('../core/git.js').getWorktreeFileDiff;
   extractWorktreeParams?: typeof import('../validation/index.js').extractWorktreeParams;
 }) {
   // getWorktreeStatus returns a WorktreeStatus object, which is then wrapped in { status: ... } by the handler
   const getStatus = mock.fn(async () => ({ clean: true, modified: [], staged: [] }));
+  const getSummary = mock.fn(async () => ({ branch: 'main', dirty: false, ahead: 0, behind: 0 }));
   const getDiff = mock.fn(async () => ({ diff: 'content' }));
   const extractParams = mock.fn((params: URLSearchParams) => ({
     org: params.get('org') || '',
@@ -25,11 +34,17 @@ function setupOverrides(deps?: {
 
   __setGitStatusTestOverrides({
     getWorktreeStatus: deps?.getWorktreeStatus ?? getStatus,
+    getWorktreeSummary: deps?.getWorktreeSummary ?? (getSummary as never),
     getWorktreeFileDiff: deps?.getWorktreeFileDiff ?? getDiff,
     extractWorktreeParams: deps?.extractWorktreeParams ?? extractParams,
   });
 
-  return { getWorktreeStatus: getStatus, getWorktreeFileDiff: getDiff, extractWorktreeParams: extractParams };
+  return {
+    getWorktreeStatus: getStatus,
+    getWorktreeSummary: getSummary,
+    getWorktreeFileDiff: getDiff,
+    extractWorktreeParams: extractParams,
+  };
 }
 
 function createContext(overrides: Partial<RequestContext> = {}): RequestContext {
@@ -134,6 +149,222 @@ describe('createGitStatusHandlers', () => {
     assert.equal(headerCall.arguments[1], 'no-store');
   });
 
+  it('summary handler returns a single worktree summary', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const { getWorktreeSummary } = setupOverrides();
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const url = new URL('http://localhost/api/worktrees/status?org=vultuk&repo=agentrix&branch=main');
+    const context = createContext({ url });
+
+    await handlers.summary(context);
+    __setBaseHandlerTestOverrides();
+    __setGitStatusTestOverrides();
+
+    const summaryCall = getWorktreeSummary.mock.calls[0];
+    assert.ok(summaryCall);
+    assert.deepEqual(summaryCall.arguments, ['/workdir', 'vultuk', 'agentrix', 'main']);
+
+    const call = sendJson.mock.calls[0];
+    assert.ok(call);
+    assert.equal(call.arguments[1], 200);
+    assert.deepEqual(call.arguments[2], {
+      worktree: { branch: 'main', dirty: false, ahead: 0, behind: 0 },
+    });
+  });
+
+  it('file handler returns raw bytes with sniffed content type and safety headers', async () => {
+    setupOverrides();
+    const content = Buffer.from('hello\n');
+    const readWorktreeFile = mock.fn(async () => ({
+      path: 'src/notes.txt',
+      size: content.length,
+      contentType: 'text/plain; charset=utf-8',
+      content,
+    }));
+    __setGitStatusTestOverrides({ readWorktreeFile });
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const url = new URL('http://localhost/api/worktrees/file?org=vultuk&repo=agentrix&branch=main&path=src/notes.txt');
+    const context = createContext({ url });
+
+    await handlers.file(context);
+    __setGitStatusTestOverrides();
+
+    assert.deepEqual(readWorktreeFile.mock.calls[0]?.arguments, [
+      '/workdir',
+      'vultuk',
+      'agentrix',
+      'main',
+      'src/notes.txt',
+    ]);
+    assert.equal(context.res.statusCode, 200);
+    const headers = new Map(
+      (context.res.setHeader as ReturnType<typeof mock.fn>).mock.calls.map((call) => [call.arguments[0], call.arguments[1]])
+    );
+    assert.equal(headers.get('Content-Type'), 'text/plain; charset=utf-8');
+    assert.equal(headers.get('Content-Length'), String(content.length));
+    assert.equal(headers.get('X-Content-Type-Options'), 'nosniff');
+    assert.match(String(headers.get('Content-Security-Policy')), /sandbox/);
+    assert.equal((context.res.end as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0], content);
+  });
+
+  it('file handler requires a path', async () => {
+    setupOverrides();
+    const readWorktreeFile = mock.fn();
+    __setGitStatusTestOverrides({ readWorktreeFile: readWorktreeFile as never });
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const context = createContext({
+      url: new URL('http://localhost/api/worktrees/file?org=vultuk&repo=agentrix&branch=main'),
+    });
+
+    await handlers.file(context);
+    __setGitStatusTestOverrides();
+
+    assert.equal(readWorktreeFile.mock.calls.length, 0);
+    assert.equal(context.res.statusCode, 400);
+  });
+
+  it('blame handler passes the line range and rejects inverted ranges', async () => {
+    setupOverrides();
+    const blame = { path: 'notes.md', lines: [], truncated: false, limitBytes: 4096 };
+    const blameWorktreeFile = mock.fn(async () => blame);
+    __setGitStatusTestOverrides({ blameWorktreeFile });
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const base = 'http://localhost/api/worktrees/blame?org=vultuk&repo=agentrix&branch=main&path=notes.md';
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    await handlers.blame(createContext({ url: new URL(`${base}&start=2&end=4`) }));
+    __setBaseHandlerTestOverrides();
+
+    const inverted = createContext({ url: new URL(`${base}&start=5&end=4`) });
+    await handlers.blame(inverted);
+    __setGitStatusTestOverrides();
+
+    assert.deepEqual(blameWorktreeFile.mock.calls[0]?.arguments, [
+      '/workdir',
+      'vultuk',
+      'agentrix',
+      'main',
+      'notes.md',
+      { start: 2, end: 4, maxBytes: DEFAULT_GIT_OUTPUT_LIMIT_BYTES },
+    ]);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { blame });
+    assert.equal(blameWorktreeFile.mock.calls.length, 1);
+    assert.equal(inverted.res.statusCode, 400);
+  });
+
+  it('compare handler passes the refs and path scope and requires both refs', async () => {
+    const comparison = { diff: '', truncated: false, commits: { items: [], total: 0, truncated: false } };
+    const compareRefs = mock.fn(async () => comparison);
+    __setGitStatusTestOverrides({ compareRefs: compareRefs as never });
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    await handlers.compare(
+      createContext({ url: new URL('http://localhost/api/git/compare?org=vultuk&repo=agentrix&base=main&head=feature&path=src') })
+    );
+    __setBaseHandlerTestOverrides();
+
+    const missingHead = createContext({ url: new URL('http://localhost/api/git/compare?org=vultuk&repo=agentrix&base=main') });
+    await handlers.compare(missingHead);
+    __setGitStatusTestOverrides();
+
+    assert.deepEqual(compareRefs.mock.calls[0]?.arguments, [
+      '/workdir',
+      'vultuk',
+      'agentrix',
+      { base: 'main', head: 'feature', path: 'src', maxDiffBytes: DEFAULT_COMPARE_DIFF_LIMIT_BYTES },
+    ]);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { comparison });
+    assert.equal(compareRefs.mock.calls.length, 1);
+    assert.equal(missingHead.res.statusCode, 400);
+  });
+
+  it('compare handler holds maxBytes to the git output limit and rejects invalid values', async () => {
+    const comparison = { diff: '', truncated: false, limitBytes: 512, commits: { items: [], total: 0, truncated: false } };
+    const compareRefs = mock.fn(async () => comparison);
+    __setGitStatusTestOverrides({ compareRefs: compareRefs as never });
+
+    const handlers = createGitStatusHandlers('/workdir', { outputLimitBytes: 2048 });
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const base = 'http://localhost/api/git/compare?org=vultuk&repo=agentrix&base=main&head=feature';
+    await handlers.compare(createContext({ url: new URL(`${base}&maxBytes=512`) }));
+    await handlers.compare(createContext({ url: new URL(`${base}&maxBytes=999999`) }));
+    const invalid = createContext({ url: new URL(`${base}&maxBytes=-1`) });
+    await handlers.compare(invalid);
+    __setBaseHandlerTestOverrides();
+    __setGitStatusTestOverrides();
+
+    assert.deepEqual(
+      compareRefs.mock.calls.map((call) => (call.arguments as unknown[])[3]),
+      [
+        { base: 'main', head: 'feature', path: undefined, maxDiffBytes: 512 },
+        { base: 'main', head: 'feature', path: undefined, maxDiffBytes: 2048 },
+      ]
+    );
+    assert.equal(invalid.res.statusCode, 400);
+  });
+
+  it('merge-base handler passes the against ref and answers 409 for unrelated histories', async () => {
+    const mergeBase = { branch: 'feature', against: 'main', headCommit: 'b', againstCommit: 'c', mergeBase: 'a' };
+    const findWorktreeMergeBase = mock.fn(async (_workdir: string, _org: string, _repo: string, branch: string) => {
+      if (branch === 'orphan') {
+        throw new UnrelatedHistoriesError('orphan', 'main');
+      }
+      return mergeBase;
+    });
+    __setGitStatusTestOverrides({ findWorktreeMergeBase: findWorktreeMergeBase as never });
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    await handlers.mergeBase(
+      createContext({
+        url: new URL('http://localhost/api/worktrees/merge-base?org=vultuk&repo=agentrix&branch=feature&against=main'),
+      })
+    );
+    __setBaseHandlerTestOverrides();
+
+    const unrelated = createContext({
+      url: new URL('http://localhost/api/worktrees/merge-base?org=vultuk&repo=agentrix&branch=orphan'),
+    });
+    await handlers.mergeBase(unrelated);
+    __setGitStatusTestOverrides();
+
+    assert.deepEqual(findWorktreeMergeBase.mock.calls[0]?.arguments, [
+      '/workdir',
+      'vultuk',
+      'agentrix',
+      'feature',
+      { against: 'main' },
+    ]);
+    assert.deepEqual(findWorktreeMergeBase.mock.calls[1]?.arguments[4], { against: undefined });
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { mergeBase });
+    assert.equal(unrelated.res.statusCode, 409);
+  });
+
+  it('tags handler lists the repository tags', async () => {
+    const tags = [{ name: 'v1.0.0', commit: 'abc', annotated: false, createdAt: null, subject: 'Seed' }];
+    const listRepositoryTags = mock.fn(async () => tags);
+    __setGitStatusTestOverrides({ listRepositoryTags: listRepositoryTags as never });
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    await handlers.tags(createContext({ url: new URL('http://localhost/api/git/tags?org=vultuk&repo=agentrix') }));
+    __setBaseHandlerTestOverrides();
+    __setGitStatusTestOverrides();
+
+    assert.deepEqual(listRepositoryTags.mock.calls[0]?.arguments, ['/workdir', 'vultuk', 'agentrix']);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { tags });
+  });
+
   it('diff handler validates path parameter', async () => {
     __setBaseHandlerTestOverrides();
     const { getWorktreeFileDiff } = setupOverrides();
@@ -228,7 +459,225 @@ describe('createGitStatusHandlers', () => {
       previousPath: undefined,
       mode: undefined,
       status: undefined,
+      maxBytes: DEFAULT_GIT_OUTPUT_LIMIT_BYTES,
     });
   });
+
+  it('diff handler returns a files-changed summary when stat is true', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const { getWorktreeFileDiff } = setupOverrides();
+    const stat = {
+      branch: 'branch',
+      mode: 'staged' as const,
+      files: [],
+      totals: { files: 0, additions: 0, deletions: 0 },
+    };
+    const getWorktreeDiffStat = mock.fn(async () => stat);
+    __setGitStatusTestOverrides({ getWorktreeDiffStat });
+
+    const handlers = createGitStatusHandlers('/workdir');
+    await handlers.diff(
+      createContext({
+        url: new URL('http://localhost/api/git/diff?stat=true'),
+        readJsonBody: async () => ({ org: 'org', repo: 'repo', branch: 'branch', mode: 'staged' }),
+      })
+    );
+    const invalid = createContext({
+      url: new URL('http://localhost/api/git/diff?stat=true'),
+      readJsonBody: async () => ({ org: 'org', repo: 'repo', branch: 'branch', mode: 'untracked' }),
+    });
+    await handlers.diff(invalid);
+    __setBaseHandlerTestOverrides();
+    __setGitStatusTestOverrides();
+
+    assert.deepEqual(getWorktreeDiffStat.mock.calls[0]?.arguments, ['/workdir', 'org', 'repo', 'branch', 'staged']);
+    assert.equal(getWorktreeDiffStat.mock.callCount(), 1);
+    assert.equal(getWorktreeFileDiff.mock.callCount(), 0);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { stat });
+    assert.equal(invalid.res.statusCode, 400);
+  });
+
+  describe('diff content negotiation', () => {
+    function createDiffContext(accept?: string) {
+      const res = {
+        statusCode: 0,
+        writableEnded: false,
+        setHeader: mock.fn(),
+        getHeader: mock.fn(),
+        end: mock.fn(function (this: { writableEnded: boolean }) {
+          this.writableEnded = true;
+        }),
+      };
+      return createContext({
+        req: { headers: accept === undefined ? {} : { accept } } as unknown as RequestContext['req'],
+        res: res as unknown as RequestContext['res'],
+        readJsonBody: async () => ({ org: 'org', repo: 'repo', branch: 'branch', path: 'file.ts' }),
+      });
+    }
+
+    async function requestDiff(accept: string | undefined, diff = '--- a/file.ts\n+++ b/file.ts\n@@ -1 +1 @@\n-a\n+b') {
+      const sendJson = mock.fn();
+      __setBaseHandlerTestOverrides({ sendJson });
+      setupOverrides({
+        getWorktreeFileDiff: mock.fn(async () => ({ path: 'file.ts', previousPath: null, mode: 'unstaged', diff })),
+      });
+      const context = createDiffContext(accept);
+      await createGitStatusHandlers('/workdir').diff(context);
+      __setBaseHandlerTestOverrides();
+      __setGitStatusTestOverrides();
+      const headers = new Map(
+        (context.res.setHeader as ReturnType<typeof mock.fn>).mock.calls.map((call) => [call.arguments[0], call.arguments[1]])
+      );
+      const end = (context.res.end as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0];
+      return { sendJson, headers, end };
+    }
+
+    it('returns the bare patch when text/plain is preferred', async () => {
+      const { sendJson, headers, end } = await requestDiff('text/plain');
+
+      assert.equal(sendJson.mock.calls.length, 0);
+      assert.equal(headers.get('Content-Type'), 'text/plain; charset=utf-8');
+      assert.equal(headers.get('Vary'), 'Accept');
+      assert.equal(end, '--- a/file.ts\n+++ b/file.ts\n@@ -1 +1 @@\n-a\n+b\n');
+    });
+
+    it('returns an empty patch when the file has no changes', async () => {
+      const { end } = await requestDiff('text/plain, */*;q=0.1', 'No differences to display.');
+      assert.equal(end, '');
+    });
+
+    it('keeps the JSON envelope for JSON and ambiguous Accept headers', async () => {
+      for (const accept of [undefined, 'application/json', '*/*', 'application/json, text/plain']) {
+        const { sendJson, end } = await requestDiff(accept);
+
+        assert.equal(sendJson.mock.calls.length, 1, `Accept: ${accept}`);
+        assert.equal((sendJson.mock.calls[0]?.arguments[2] as { path: string }).path, 'file.ts');
+        assert.equal(end, undefined);
+      }
+    });
+  });
+
+  function createStreamingResponse() {
+    const emitter = new EventEmitter();
+    return Object.assign(emitter, {
+      statusCode: 0,
+      writableFinished: false,
+      setHeader: mock.fn(),
+      getHeader: mock.fn(),
+      write: mock.fn((_chunk: unknown) => true),
+      end: mock.fn(),
+      destroy: mock.fn(),
+    });
+  }
+
+  it('archive handler streams the tarball as a download', async () => {
+    setupOverrides();
+    const abort = mock.fn();
+    const createWorktreeArchive = mock.fn(async () => ({
+      fileName: 'agentrix-feature-login.tar.gz',
+      stream: Readable.from([Buffer.from([0x1f, 0x8b]), Buffer.from('rest')]),
+      completed: Promise.resolve(),
+      abort,
+    }));
+    __setGitStatusTestOverrides({ createWorktreeArchive });
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const res = createStreamingResponse();
+    const context = createContext({
+      url: new URL('http://localhost/api/worktrees/archive?org=vultuk&repo=agentrix&branch=feature/login&includeIgnored=true'),
+      res: res as unknown as RequestContext['res'],
+    });
+
+    await handlers.archive(context);
+    __setGitStatusTestOverrides();
+
+    assert.deepEqual(createWorktreeArchive.mock.calls[0]?.arguments, [
+      '/workdir',
+      'vultuk',
+      'agentrix',
+      'feature/login',
+      { includeIgnored: true },
+    ]);
+    assert.equal(res.statusCode, 200);
+    const headers = new Map(res.setHeader.mock.calls.map((call) => [call.arguments[0], call.arguments[1]]));
+    assert.equal(headers.get('Content-Type'), 'application/gzip');
+    assert.equal(headers.get('Content-Disposition'), 'attachment; filename="agentrix-feature-login.tar.gz"');
+    assert.deepEqual(
+      Buffer.concat(res.write.mock.calls.map((call) => call.arguments[0] as Buffer)),
+      Buffer.concat([Buffer.from([0x1f, 0x8b]), Buffer.from('rest')])
+    );
+    assert.equal(res.end.mock.calls.length, 1);
+    assert.equal(res.destroy.mock.calls.length, 0);
+    assert.equal(abort.mock.calls.length, 0);
+  });
+
+  it('archive handler stops waiting for drain when the client disconnects mid-stream', async () => {
+    setupOverrides();
+    const abort = mock.fn();
+    const chunks = Array.from({ length: 8 }, (_, index) => Buffer.from(`chunk-${index}`));
+    __setGitStatusTestOverrides({
+      createWorktreeArchive: async () => ({
+        fileName: 'agentrix-main.tar.gz',
+        stream: Readable.from(chunks),
+        completed: Promise.resolve(),
+        abort,
+      }),
+    });
+    const consoleError = mock.method(console, 'error', () => {});
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const res = createStreamingResponse();
+    // The socket's buffer is full and the client then goes away, so 'drain' is never emitted
+    res.write = mock.fn((_chunk: unknown) => {
+      setImmediate(() => res.emit('close'));
+      return false;
+    });
+    const context = createContext({
+      url: new URL('http://localhost/api/worktrees/archive?org=vultuk&repo=agentrix&branch=main'),
+      res: res as unknown as RequestContext['res'],
+    });
+
+    await handlers.archive(context);
+    __setGitStatusTestOverrides();
+    const errorsLogged = consoleError.mock.calls.length;
+    consoleError.mock.restore();
+
+    assert.equal(res.write.mock.calls.length, 1);
+    assert.equal(res.end.mock.calls.length, 0);
+    assert.ok(abort.mock.calls.length >= 1);
+    assert.equal(errorsLogged, 0);
+  });
+
+  it('archive handler cuts the download short when archiving fails midway', async () => {
+    setupOverrides();
+    const abort = mock.fn();
+    const completed = Promise.reject(new Error('tar: read error'));
+    completed.catch(() => {});
+    __setGitStatusTestOverrides({
+      createWorktreeArchive: async () => ({
+        fileName: 'agentrix-main.tar.gz',
+        stream: Readable.from([Buffer.from('partial')]),
+        completed,
+        abort,
+      }),
+    });
+    const consoleError = mock.method(console, 'error', () => {});
+
+    const handlers = createGitStatusHandlers('/workdir');
+    const res = createStreamingResponse();
+    const context = createContext({
+      url: new URL('http://localhost/api/worktrees/archive?org=vultuk&repo=agentrix&branch=main'),
+      res: res as unknown as RequestContext['res'],
+    });
+
+    await handlers.archive(context);
+    __setGitStatusTestOverrides();
+    consoleError.mock.restore();
+
+    assert.equal(res.destroy.mock.calls.length, 1);
+    assert.equal(res.end.mock.calls.length, 0);
+    assert.equal(abort.mock.calls.length, 1);
+  });
 });
 
@@ -1,18 +1,51 @@
-import { getWorktreeStatus, getWorktreeFileDiff } from '../core/git.js';
+import { once } from 'node:events';
+import {
+  blameWorktreeFile,
+  compareRefs,
+  createWorktreeArchive,
+  findWorktreeMergeBase,
+  getWorktreeStatus,
+  getWorktreeSummary,
+  getWorktreeFileDiff,
+  getWorktreeDiffStat,
+  listRepositoryTags,
+  readWorktreeFile,
+  NO_DIFF_PLACEHOLDER,
+  DEFAULT_COMPARE_DIFF_LIMIT_BYTES,
+  resolveGitOutputLimit,
+} from '../core/git.js';
+import { negotiateMediaType } from '../utils/http.js';
 import { createQueryHandler } from './base-handler.js';
-import { extractWorktreeParams } from '../validation/index.js';
-import { ValidationError } from '../infrastructure/errors/index.js';
+import { extractRepositoryParams, extractWorktreeParams } from '../validation/index.js';
+import { asyncHandler, ValidationError } from '../infrastructure/errors/index.js';
 import type { RequestContext } from '../types/http.js';
+import type { DiffStatMode } from '../core/git.js';
 
 interface GitStatusDependencies {
   getWorktreeStatus: typeof getWorktreeStatus;
+  getWorktreeSummary: typeof getWorktreeSummary;
   getWorktreeFileDiff: typeof getWorktreeFileDiff;
+  getWorktreeDiffStat: typeof getWorktreeDiffStat;
+  readWorktreeFile: typeof readWorktreeFile;
+  createWorktreeArchive: typeof createWorktreeArchive;
+  blameWorktreeFile: typeof blameWorktreeFile;
+  compareRefs: typeof compareRefs;
+  findWorktreeMergeBase: typeof findWorktreeMergeBase;
+  listRepositoryTags: typeof listRepositoryTags;
   extractWorktreeParams: typeof extractWorktreeParams;
 }
 
 const defaultDependencies: GitStatusDependencies = {
   getWorktreeStatus,
+  getWorktreeSummary,
   getWorktreeFileDiff,
+  getWorktreeDiffStat,
+  readWorktreeFile,
+  createWorktreeArchive,
+  blameWorktreeFile,
+  compareRefs,
+  findWorktreeMergeBase,
+  listRepositoryTags,
   extractWorktreeParams,
 };
 
@@ -44,10 +77,50 @@ function parseOptionalInteger(value: string | null, fallback: number | undefined
   return parsed;
 }
 
-export function createGitStatusHandlers(workdir: string) {
+const DIFF_STAT_MODES: readonly DiffStatMode[] = ['all', 'staged', 'unstaged'];
+
+function isDiffStatMode(value: string): value is DiffStatMode {
+  return (DIFF_STAT_MODES as readonly string[]).includes(value);
+}
+
+function parseLineNumber(value: string | null, name: string): number | undefined {
+  if (value === null || !value.trim()) {
+    return undefined;
+  }
+  const trimmed = value.trim();
+  if (!/^\d+$/.test(trimmed) || Number(trimmed) < 1) {
+    throw new ValidationError(`${name} must be a positive integer`);
+  }
+  return Number(trimmed);
+}
+
+/**
+ * Reads the `maxBytes` query parameter, which lowers the git output limit for one request. Values above
+ * the server's limit are held to it.
+ */
+function parseMaxBytes(value: string | null, limitBytes: number): number | undefined {
+  if (value === null || !value.trim()) {
+    return undefined;
+  }
+  const trimmed = value.trim();
+  if (!/^\d+$/.test(trimmed) || Number(trimmed) < 1) {
+    throw new ValidationError('maxBytes must be a positive integer');
+  }
+  return Math.min(Number(trimmed), limitBytes);
+}
+
+export interface GitStatusHandlerOptions {
+  /**
+   * Largest git output kept for diffs, blame and comparisons (DEFAULT_GIT_OUTPUT_LIMIT_BYTES when unset)
+   */
+  outputLimitBytes?: number | null;
+}
+
+export function createGitStatusHandlers(workdir: string, options: GitStatusHandlerOptions = {}) {
   if (!workdir) {
     throw new Error('workdir is required');
   }
+  const outputLimitBytes = resolveGitOutputLimit(options.outputLimitBytes);
 
   const read = createQueryHandler(async (context: RequestContext) => {
     const { org, repo, branch } = activeDependencies.extractWorktreeParams(context.url.searchParams);
@@ -63,6 +136,14 @@ export function createGitStatusHandlers(workdir: string) {
     return { status };
   });
 
+  const summary = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo, branch } = activeDependencies.extractWorktreeParams(context.url.searchParams);
+    const worktree = await activeDependencies.getWorktreeSummary(workdir, org, repo, branch);
+
+    context.res.setHeader('Cache-Control', 'no-store');
+    return { worktree };
+  });
+
   const diff = createQueryHandler(async (context: RequestContext) => {
     const payload = await context.readJsonBody();
     const { org, repo, branch } = activeDependencies.extractWorktreeParams(
@@ -73,22 +154,187 @@ export function createGitStatusHandlers(workdir: string) {
       })
     );
 
+    const mode = typeof payload['mode'] === 'string' ? payload['mode'] : undefined;
+
+    // The files-changed summary covers the whole worktree; the patch stays available without `stat`
+    if (context.url.searchParams.get('stat') === 'true') {
+      const statMode = mode ?? 'all';
+      if (!isDiffStatMode(statMode)) {
+        throw new ValidationError(`mode must be one of ${DIFF_STAT_MODES.join(', ')} when stat is true`);
+      }
+      const stat = await activeDependencies.getWorktreeDiffStat(workdir, org, repo, branch, statMode);
+      context.res.setHeader('Cache-Control', 'no-store');
+      return { stat };
+    }
+
     const filePath = typeof payload['path'] === 'string' ? payload['path'] : '';
     if (!filePath) {
       throw new ValidationError('path is required');
     }
 
     const previousPath = typeof payload['previousPath'] === 'string' ? payload['previousPath'] : undefined;
-    const mode = typeof payload['mode'] === 'string' ? payload['mode'] : undefined;
     const status = typeof payload['status'] === 'string' ? payload['status'] : undefined;
+    const maxBytes = parseMaxBytes(context.url.searchParams.get('maxBytes'), outputLimitBytes) ?? outputLimitBytes;
 
-    return await activeDependencies.getWorktreeFileDiff(workdir, org, repo, branch, {
+    const result = await activeDependencies.getWorktreeFileDiff(workdir, org, repo, branch, {
       path: filePath,
       previousPath,
       mode,
       status,
+      maxBytes,
+    });
+
+    const { res } = context;
+    res.setHeader('Vary', 'Accept');
+    if (result.truncated) {
+      // The bare patch has nowhere else to say it was cut short
+      res.setHeader('X-Output-Truncated', String(result.limitBytes));
+    }
+    // JSON stays the default, so only clients that rank text/plain above JSON get the bare patch
+    if (negotiateMediaType(context.req.headers.accept, ['application/json', 'text/plain']) === 'text/plain') {
+      const patch = result.diff === NO_DIFF_PLACEHOLDER ? '' : result.diff;
+      res.statusCode = 200;
+      res.setHeader('Content-Type', 'text/plain; charset=utf-8');
+      res.setHeader('Cache-Control', 'no-store');
+      res.end(patch && !patch.endsWith('\n') ? `${patch}\n` : patch);
+      return;
+    }
+    return result;
+  });
+
+  const file = asyncHandler(async (context: RequestContext) => {
+    const { org, repo, branch } = activeDependencies.extractWorktreeParams(context.url.searchParams);
+    const filePath = context.url.searchParams.get('path') || '';
+    if (!filePath.trim()) {
+      throw new ValidationError('path is required');
+    }
+
+    const result = await activeDependencies.readWorktreeFile(workdir, org, repo, branch, filePath);
+
+    const { res } = context;
+    res.statusCode = 200;
+    res.setHeader('Content-Type', result.contentType);
+    res.setHeader('Content-Length', String(result.size));
+    res.setHeader('Cache-Control', 'no-store');
+    // Worktree files are untrusted content served from our origin; never let them render as active documents
+    res.setHeader('X-Content-Type-Options', 'nosniff');
+    res.setHeader('Content-Security-Policy', "default-src 'none'; sandbox");
+    res.end(result.content);
+  });
+
+  const blame = createQueryHandler(async (context: RequestContext) => {
+    const { searchParams } = context.url;
+    const { org, repo, branch } = activeDependencies.extractWorktreeParams(searchParams);
+    const filePath = searchParams.get('path') || '';
+    if (!filePath.trim()) {
+      throw new ValidationError('path is required');
+    }
+    const start = parseLineNumber(searchParams.get('start'), 'start');
+    const end = parseLineNumber(searchParams.get('end'), 'end');
+    if (start !== undefined && end !== undefined && end < start) {
+      throw new ValidationError('end must not be before start');
+    }
+
+    const maxBytes = parseMaxBytes(searchParams.get('maxBytes'), outputLimitBytes) ?? outputLimitBytes;
+
+    const result = await activeDependencies.blameWorktreeFile(workdir, org, repo, branch, filePath, {
+      start,
+      end,
+      maxBytes,
     });
+
+    context.res.setHeader('Cache-Control', 'no-store');
+    return { blame: result };
+  });
+
+  const compare = createQueryHandler(async (context: RequestContext) => {
+    const { searchParams } = context.url;
+    const { org, repo } = extractRepositoryParams(searchParams);
+    const base = searchParams.get('base') || '';
+    const head = searchParams.get('head') || '';
+    if (!base.trim() || !head.trim()) {
+      throw new ValidationError('base and head are required');
+    }
+    const scope = searchParams.get('path') || undefined;
+    const maxBytes =
+      parseMaxBytes(searchParams.get('maxBytes'), outputLimitBytes) ??
+      Math.min(DEFAULT_COMPARE_DIFF_LIMIT_BYTES, outputLimitBytes);
+
+    const comparison = await activeDependencies.compareRefs(workdir, org, repo, {
+      base,
+      head,
+      path: scope,
+      maxDiffBytes: maxBytes,
+    });
+
+    context.res.setHeader('Cache-Control', 'no-store');
+    return { comparison };
+  });
+
+  const mergeBase = createQueryHandler(async (context: RequestContext) => {
+    const { searchParams } = context.url;
+    const { org, repo, branch } = activeDependencies.extractWorktreeParams(searchParams);
+    const against = searchParams.get('against') || undefined;
+
+    const result = await activeDependencies.findWorktreeMergeBase(workdir, org, repo, branch, { against });
+
+    context.res.setHeader('Cache-Control', 'no-store');
+    return { mergeBase: result };
+  });
+
+  const tags = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+    const result = await activeDependencies.listRepositoryTags(workdir, org, repo);
+
+    context.res.setHeader('Cache-Control', 'no-store');
+    return { tags: result };
+  });
+
+  const archive = asyncHandler(async (context: RequestContext) => {
+    const { org, repo, branch } = activeDependencies.extractWorktreeParams(context.url.searchParams);
+    const includeIgnored = context.url.searchParams.get('includeIgnored')?.trim().toLowerCase() === 'true';
+
+    const result = await activeDependencies.createWorktreeArchive(workdir, org, repo, branch, { includeIgnored });
+
+    const { res } = context;
+    res.statusCode = 200;
+    res.setHeader('Content-Type', 'application/gzip');
+    res.setHeader('Content-Disposition', `attachment; filename="${result.fileName}"`);
+    res.setHeader('Cache-Control', 'no-store');
+    res.setHeader('X-Content-Type-Options', 'nosniff');
+
+    let clientGone = false;
+    // Aborts a pending wait for 'drain', which never comes once the client has gone
+    const closed = new AbortController();
+    res.once('close', () => {
+      if (!res.writableFinished) {
+        clientGone = true;
+        result.abort();
+      }
+      closed.abort();
+    });
+
+    try {
+      for await (const chunk of result.stream) {
+        if (clientGone) {
+          break;
+        }
+        if (!res.write(chunk)) {
+          await once(res, 'drain', { signal: closed.signal });
+        }
+      }
+      await result.completed;
+    } catch (error: unknown) {
+      result.abort();
+      if (!clientGone) {
+        console.error('[agentrix] Worktree archive failed:', error);
+      }
+      // The status line is already sent, so the only way to signal failure is to cut the download short
+      res.destroy();
+      return;
+    }
+    res.end();
   });
 
-  return { read, diff };
+  return { read, summary, diff, file, blame, compare, mergeBase, tags, archive };
 }
@@ -0,0 +1,59 @@
+import assert from 'node:assert/strict';
+import { describe, it, mock } from 'node:test';
+
+import { createGithubNotificationHandlers } from './github-notifications.js';
+import { __setBaseHandlerTestOverrides } from './base-handler.js';
+import type { RequestContext } from '../types/http.js';
+
+function createContext(search: string): RequestContext {
+  return {
+    req: { headers: {} } as unknown as RequestContext['req'],
+    res: {
+      statusCode: 0,
+      setHeader: mock.fn(),
+      getHeader: mock.fn(),
+      end: mock.fn(),
+    } as unknown as RequestContext['res'],
+    url: new URL(`http://localhost/api/github/notifications${search}`),
+    method: 'GET',
+    workdir: '/workdir',
+    readJsonBody: async () => ({}),
+  };
+}
+
+describe('createGithubNotificationHandlers', () => {
+  it('forwards the filters and returns the page of notifications', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const page = { page: 2, perPage: 10, hasMore: false, items: [] };
+    const listNotifications = mock.fn(async () => page);
+    const handlers = createGithubNotificationHandlers({
+      githubClient: { listNotifications } as never,
+      now: () => new Date('2024-05-01T00:00:00Z'),
+    });
+
+    await handlers.list(createContext('?all=true&since=2024-04-30T12:00:00%2B02:00&page=2&perPage=10'));
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual(listNotifications.mock.calls[0]?.arguments, [
+      { all: true, since: '2024-04-30T10:00:00.000Z', page: 2, perPage: 10 },
+    ]);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], {
+      data: { ...page, fetchedAt: '2024-05-01T00:00:00.000Z' },
+    });
+  });
+
+  it('rejects malformed filters before calling GitHub', async () => {
+    const listNotifications = mock.fn(async () => ({ page: 1, perPage: 30, hasMore: false, items: [] }));
+    const handlers = createGithubNotificationHandlers({ githubClient: { listNotifications } as never });
+
+    const badSince = createContext('?since=yesterday');
+    await handlers.list(badSince);
+    const badAll = createContext('?all=maybe');
+    await handlers.list(badAll);
+
+    assert.equal(badSince.res.statusCode, 400);
+    assert.equal(badAll.res.statusCode, 400);
+    assert.equal(listNotifications.mock.calls.length, 0);
+  });
+});
@@ -0,0 +1,74 @@
+import { createGithubClient } from '../core/github.js';
+import { handleHeadRequest } from '../utils/http.js';
+import { ValidationError } from '../infrastructure/errors/index.js';
+import { createQueryHandler } from './base-handler.js';
+import type { RequestContext } from '../types/http.js';
+
+export interface GithubNotificationOverrides {
+  githubClient?: ReturnType<typeof createGithubClient>;
+  now?: () => Date;
+}
+
+function parseBooleanParam(value: string | null, name: string): boolean {
+  const trimmed = value?.trim().toLowerCase() || '';
+  if (!trimmed || trimmed === 'false') {
+    return false;
+  }
+  if (trimmed === 'true') {
+    return true;
+  }
+  throw new ValidationError(`${name} query parameter must be true or false`);
+}
+
+function parseSinceParam(value: string | null): string | null {
+  const trimmed = value?.trim() || '';
+  if (!trimmed) {
+    return null;
+  }
+  const parsed = new Date(trimmed);
+  if (Number.isNaN(parsed.getTime())) {
+    throw new ValidationError('since query parameter must be an ISO 8601 timestamp');
+  }
+  return parsed.toISOString();
+}
+
+function parsePositiveIntegerParam(value: string | null, name: string): number | undefined {
+  const trimmed = value?.trim() || '';
+  if (!trimmed) {
+    return undefined;
+  }
+  const parsed = Number(trimmed);
+  if (!Number.isInteger(parsed) || parsed <= 0) {
+    throw new ValidationError(`${name} query parameter must be a positive integer`);
+  }
+  return parsed;
+}
+
+export function createGithubNotificationHandlers(overrides: GithubNotificationOverrides = {}) {
+  const { githubClient = createGithubClient(), now = () => new Date() } = overrides;
+
+  const list = createQueryHandler(async (context: RequestContext) => {
+    const { searchParams } = context.url;
+    const all = parseBooleanParam(searchParams.get('all'), 'all');
+    const since = parseSinceParam(searchParams.get('since'));
+    const page = parsePositiveIntegerParam(searchParams.get('page'), 'page');
+    const perPage = parsePositiveIntegerParam(searchParams.get('perPage'), 'perPage');
+
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const notifications = await githubClient.listNotifications({ all, since, page, perPage });
+    context.res.setHeader('Cache-Control', 'no-store');
+
+    return {
+      data: {
+        ...notifications,
+        fetchedAt: now().toISOString(),
+      },
+    };
+  });
+
+  return { list };
+}
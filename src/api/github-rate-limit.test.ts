@@ -0,0 +1,61 @@
+import assert from 'node:assert/strict';
+import { describe, it, mock } from 'node:test';
+
+import { createGithubRateLimitHandlers } from './github-rate-limit.js';
+import { __setBaseHandlerTestOverrides } from './base-handler.js';
+import { GitHubTokenRequiredError } from '../core/github.js';
+import type { RequestContext } from '../types/http.js';
+
+function createContext(search: string): RequestContext {
+  return {
+    req: { headers: {} } as unknown as RequestContext['req'],
+    res: {
+      statusCode: 0,
+      setHeader: mock.fn(),
+      getHeader: mock.fn(),
+      end: mock.fn(),
+    } as unknown as RequestContext['res'],
+    url: new URL(`http://localhost/api/github/rate-limit${search}`),
+    method: 'GET',
+    workdir: '/workdir',
+    readJsonBody: async () => ({}),
+  };
+}
+
+const rateLimit = {
+  core: { limit: 5000, remaining: 4990, used: 10, resetAt: '2024-05-01T01:00:00.000Z' },
+  search: { limit: 30, remaining: 28, used: 2, resetAt: '2024-05-01T00:01:00.000Z' },
+};
+
+describe('createGithubRateLimitHandlers', () => {
+  it("returns the core and search limits of the organisation's token", async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const getRateLimit = mock.fn(async () => rateLimit);
+    const handlers = createGithubRateLimitHandlers({
+      githubClient: { getRateLimit },
+      now: () => new Date('2024-05-01T00:00:00Z'),
+    });
+
+    await handlers.read(createContext('?org=acme'));
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual(getRateLimit.mock.calls[0]?.arguments, ['acme']);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], {
+      data: { org: 'acme', ...rateLimit, fetchedAt: '2024-05-01T00:00:00.000Z' },
+    });
+  });
+
+  it('answers 501 when there is no token to ask GitHub with', async () => {
+    const getRateLimit = mock.fn(async () => {
+      throw new GitHubTokenRequiredError();
+    });
+    const handlers = createGithubRateLimitHandlers({ githubClient: { getRateLimit } });
+
+    const context = createContext('');
+    await handlers.read(context);
+
+    assert.deepEqual(getRateLimit.mock.calls[0]?.arguments, [null]);
+    assert.equal(context.res.statusCode, 501);
+  });
+});
@@ -0,0 +1,35 @@
+import { createGithubClient } from '../core/github.js';
+import { handleHeadRequest } from '../utils/http.js';
+import { createQueryHandler } from './base-handler.js';
+import type { RequestContext } from '../types/http.js';
+
+export interface GithubRateLimitOverrides {
+  githubClient?: Pick<ReturnType<typeof createGithubClient>, 'getRateLimit'>;
+  now?: () => Date;
+}
+
+export function createGithubRateLimitHandlers(overrides: GithubRateLimitOverrides = {}) {
+  const { githubClient = createGithubClient(), now = () => new Date() } = overrides;
+
+  // `org` reports on that organisation's token instead of the CLI's own login
+  const read = createQueryHandler(async (context: RequestContext) => {
+    const org = context.url.searchParams.get('org')?.trim() || null;
+
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const rateLimit = await githubClient.getRateLimit(org);
+
+    return {
+      data: {
+        org,
+        ...rateLimit,
+        fetchedAt: now().toISOString(),
+      },
+    };
+  });
+
+  return { read };
+}
@@ -0,0 +1,53 @@
+import assert from 'node:assert/strict';
+import { describe, it, mock } from 'node:test';
+
+import { createGithubReadmeHandlers } from './github-readme.js';
+import { __setBaseHandlerTestOverrides } from './base-handler.js';
+import type { RequestContext } from '../types/http.js';
+
+function createContext(search: string): RequestContext {
+  return {
+    req: { headers: {} } as unknown as RequestContext['req'],
+    res: {
+      statusCode: 0,
+      setHeader: mock.fn(),
+      getHeader: mock.fn(),
+      end: mock.fn(),
+    } as unknown as RequestContext['res'],
+    url: new URL(`http://localhost/api/github/readme${search}`),
+    method: 'GET',
+    workdir: '/workdir',
+    readJsonBody: async () => ({}),
+  };
+}
+
+describe('createGithubReadmeHandlers', () => {
+  it('returns the README fetched from GitHub', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const getReadme = mock.fn(async () => ({ found: true, content: '# Demo\n' }));
+    const handlers = createGithubReadmeHandlers({
+      githubClient: { getReadme },
+      now: () => new Date('2024-05-01T00:00:00Z'),
+    });
+
+    await handlers.read(createContext('?org=acme&repo=demo'));
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual(getReadme.mock.calls[0]?.arguments, ['acme', 'demo']);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], {
+      data: { org: 'acme', repo: 'demo', found: true, content: '# Demo\n', fetchedAt: '2024-05-01T00:00:00.000Z' },
+    });
+  });
+
+  it('requires org and repo before calling GitHub', async () => {
+    const getReadme = mock.fn(async () => ({ found: false, content: '' }));
+    const handlers = createGithubReadmeHandlers({ githubClient: { getReadme } });
+
+    const context = createContext('?org=acme');
+    await handlers.read(context);
+
+    assert.equal(context.res.statusCode, 400);
+    assert.equal(getReadme.mock.calls.length, 0);
+  });
+});
@@ -0,0 +1,37 @@
+import { createGithubClient } from '../core/github.js';
+import { handleHeadRequest } from '../utils/http.js';
+import { extractRepositoryParams } from '../validation/index.js';
+import { createQueryHandler } from './base-handler.js';
+import type { RequestContext } from '../types/http.js';
+
+export interface GithubReadmeOverrides {
+  githubClient?: Pick<ReturnType<typeof createGithubClient>, 'getReadme'>;
+  now?: () => Date;
+}
+
+export function createGithubReadmeHandlers(overrides: GithubReadmeOverrides = {}) {
+  const { githubClient = createGithubClient(), now = () => new Date() } = overrides;
+
+  // Reads straight from GitHub, so dashboards can preview repositories that have not been cloned
+  const read = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const readme = await githubClient.getReadme(org, repo);
+
+    return {
+      data: {
+        org,
+        repo,
+        ...readme,
+        fetchedAt: now().toISOString(),
+      },
+    };
+  });
+
+  return { read };
+}
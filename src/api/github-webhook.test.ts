@@ -0,0 +1,95 @@
+import assert from 'node:assert/strict';
+import { createHmac } from 'node:crypto';
+import { afterEach, describe, it, mock } from 'node:test';
+
+import { createGithubWebhookHandlers } from './github-webhook.js';
+import { createRepoDashboardHandlers, type GithubSummarySections } from './repo-dashboard.js';
+import { __setBaseHandlerTestOverrides } from './base-handler.js';
+import { GITHUB_SUMMARY_CACHE_TTL_MS, RepositorySummaryCache } from '../core/github-summary-cache.js';
+import type { RequestContext } from '../types/http.js';
+
+const SECRET = 'webhook-secret';
+
+function sign(body: Buffer, secret: string = SECRET): string {
+  return `sha256=${createHmac('sha256', secret).update(body).digest('hex')}`;
+}
+
+function createContext(headers: Record<string, string> = {}): RequestContext {
+  return {
+    req: { headers } as unknown as RequestContext['req'],
+    res: {
+      statusCode: 0,
+      headersSent: false,
+      setHeader: mock.fn(),
+      getHeader: mock.fn(),
+      end: mock.fn(),
+    } as unknown as RequestContext['res'],
+    url: new URL('http://localhost/api/github/webhook'),
+    method: 'POST',
+    workdir: '/workdir',
+    readJsonBody: async () => ({}),
+  };
+}
+
+describe('createGithubWebhookHandlers', () => {
+  afterEach(() => {
+    __setBaseHandlerTestOverrides();
+  });
+
+  it('drops the cached dashboard summary when a signed issues event arrives', async () => {
+    const summaryCache = new RepositorySummaryCache<GithubSummarySections>(GITHUB_SUMMARY_CACHE_TTL_MS);
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const githubClient = {
+      countOpenPullRequests: mock.fn(async () => 1),
+      countOpenIssues: mock.fn(async () => 4),
+      listIssues: mock.fn(async () => []),
+      countRunningWorkflows: mock.fn(async () => 0),
+    };
+    const dashboard = createRepoDashboardHandlers('/workdir', {
+      githubClient,
+      ensureRepo: async () => ({ repoRoot: '/workdir/acme/web', repositoryPath: '/workdir/acme/web/repository' }),
+      listRepositories: async () => [{ org: 'acme', repos: ['web'] }],
+      resolveSlug: async (_path: string, org: string, repo: string) => ({ org, repo }),
+      summaryCache,
+    });
+
+    await dashboard.summary(createContext());
+    await dashboard.summary(createContext());
+    assert.equal(githubClient.countOpenIssues.mock.calls.length, 1);
+
+    const body = Buffer.from(JSON.stringify({ action: 'opened', repository: { full_name: 'Acme/Web' } }));
+    const handlers = createGithubWebhookHandlers({ secret: SECRET, summaryCache, readBody: async () => body });
+    await handlers.receive(createContext({ 'x-hub-signature-256': sign(body), 'x-github-event': 'issues' }));
+
+    assert.deepEqual(sendJson.mock.calls[2]?.arguments.slice(1), [
+      200,
+      { event: 'issues', repository: 'Acme/Web', invalidated: true },
+    ]);
+    await dashboard.summary(createContext());
+    assert.equal(githubClient.countOpenIssues.mock.calls.length, 2);
+  });
+
+  it('rejects unsigned and mismatched deliveries with 401', async () => {
+    const body = Buffer.from(JSON.stringify({ repository: { full_name: 'acme/web' } }));
+    const handlers = createGithubWebhookHandlers({ secret: SECRET, readBody: async () => body });
+
+    const unsigned = createContext({ 'x-github-event': 'issues' });
+    await handlers.receive(unsigned);
+    const mismatched = createContext({ 'x-hub-signature-256': sign(body, 'other-secret'), 'x-github-event': 'issues' });
+    await handlers.receive(mismatched);
+
+    assert.equal(unsigned.res.statusCode, 401);
+    assert.equal(mismatched.res.statusCode, 401);
+  });
+
+  it('responds with 404 while no secret is configured', async () => {
+    const readBody = mock.fn(async () => Buffer.from('{}'));
+    const context = createContext({ 'x-github-event': 'ping' });
+
+    await createGithubWebhookHandlers({ readBody }).receive(context);
+
+    assert.equal(context.res.statusCode, 404);
+    assert.equal(readBody.mock.calls.length, 0);
+  });
+});
@@ -0,0 +1,52 @@
+import { applyGithubWebhookEvent, GITHUB_SIGNATURE_HEADER, verifyGithubSignature } from '../core/github-webhooks.js';
+import { RepositorySummaryCache } from '../core/github-summary-cache.js';
+import { parseJsonBody, readRequestBody } from '../utils/http.js';
+import { createSimpleHandler } from './base-handler.js';
+import { HttpError, InvalidBodyError } from '../infrastructure/errors/index.js';
+import type { RequestContext } from '../types/http.js';
+
+export interface GithubWebhookOverrides {
+  /**
+   * Secret deliveries are signed with; missing or blank disables the receiver
+   */
+  secret?: string | null;
+  /**
+   * Dashboard summaries that deliveries invalidate
+   */
+  summaryCache?: Pick<RepositorySummaryCache<unknown>, 'invalidate'>;
+  readBody?: typeof readRequestBody;
+}
+
+function headerValue(value: string | string[] | undefined): string | null {
+  return (Array.isArray(value) ? value[0] : value) ?? null;
+}
+
+export function createGithubWebhookHandlers(overrides: GithubWebhookOverrides = {}) {
+  const { summaryCache = new RepositorySummaryCache<unknown>(), readBody = readRequestBody } = overrides;
+  const secret = overrides.secret?.trim() || null;
+
+  // Authenticated by the delivery's signature rather than a session, so GitHub can call it directly
+  const receive = createSimpleHandler(async (context: RequestContext) => {
+    if (!secret) {
+      throw new HttpError('GitHub webhooks are not configured', 404, null, 'webhooks_disabled');
+    }
+
+    const body = await readBody(context.req);
+    if (!verifyGithubSignature(secret, body, headerValue(context.req.headers[GITHUB_SIGNATURE_HEADER]))) {
+      throw new HttpError('Webhook signature is missing or does not match', 401, null, 'invalid_signature');
+    }
+
+    const event = headerValue(context.req.headers['x-github-event'])?.trim() || '';
+    if (!event) {
+      throw new HttpError('X-GitHub-Event header is required', 400, null, 'missing_event');
+    }
+    const payload = parseJsonBody(body.toString('utf8'));
+    if (!payload || typeof payload !== 'object' || Array.isArray(payload)) {
+      throw new InvalidBodyError('Webhook payload must be a JSON object');
+    }
+
+    return applyGithubWebhookEvent(event, payload as Record<string, unknown>, summaryCache);
+  });
+
+  return { receive };
+}
@@ -0,0 +1,145 @@
+import assert from 'node:assert/strict';
+import { describe, it, mock } from 'node:test';
+
+import { createHealthHandlers, type HealthOverrides } from './health.js';
+import { __setBaseHandlerTestOverrides } from './base-handler.js';
+import type { RequestContext } from '../types/http.js';
+
+function createContext(): RequestContext {
+  return {
+    req: { headers: {} } as unknown as RequestContext['req'],
+    res: {
+      statusCode: 0,
+      setHeader: mock.fn(),
+      getHeader: mock.fn(),
+      end: mock.fn(),
+    } as unknown as RequestContext['res'],
+    url: new URL('http://localhost/api/health/detail'),
+    method: 'GET',
+    workdir: '/tmp/workdir',
+    readJsonBody: async () => ({}),
+  };
+}
+
+const GIB = 1024 * 1024 * 1024;
+
+function healthyOverrides(): HealthOverrides {
+  return {
+    probeGit: async () => 'git version 2.43.0',
+    statfs: async () => ({ bavail: 10 * GIB / 4096, bsize: 4096 }),
+    githubClient: {
+      getAuthStatus: async () => ({ configured: true, valid: true, message: null }),
+      getTokenVerification: () => null,
+    },
+    cloneQueueStats: () => ({ maxConcurrent: 2, running: 2, queued: 1 }),
+    now: () => new Date('2024-01-01T00:00:00Z'),
+  };
+}
+
+async function runDetail(overrides: HealthOverrides): Promise<{ statusCode: number; body: any }> {
+  const sendJson = mock.fn();
+  __setBaseHandlerTestOverrides({ sendJson });
+  try {
+    await createHealthHandlers('/workdir', overrides).detail(createContext());
+  } finally {
+    __setBaseHandlerTestOverrides();
+  }
+  const call = sendJson.mock.calls[0];
+  assert.ok(call);
+  return { statusCode: call.arguments[1] as number, body: call.arguments[2] };
+}
+
+describe('createHealthHandlers', () => {
+  it('basic handler answers without running any checks', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const probeGit = mock.fn(async () => 'git version 2.43.0');
+
+    await createHealthHandlers('/workdir', { ...healthyOverrides(), probeGit }).basic(createContext());
+    __setBaseHandlerTestOverrides();
+
+    assert.equal(sendJson.mock.calls[0]?.arguments[1], 200);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { status: 'ok' });
+    assert.equal(probeGit.mock.calls.length, 0);
+  });
+
+  it('reports every check as ok with 200 when healthy', async () => {
+    const { statusCode, body } = await runDetail(healthyOverrides());
+
+    assert.equal(statusCode, 200);
+    assert.equal(body.status, 'ok');
+    assert.equal(body.checkedAt, '2024-01-01T00:00:00.000Z');
+    assert.deepEqual(body.checks.git, { status: 'ok', critical: true, message: null, version: '2.43.0' });
+    assert.equal(body.checks.disk.status, 'ok');
+    assert.equal(body.checks.disk.freeBytes, 10 * GIB);
+    assert.equal(body.checks.disk.path, '/workdir');
+    assert.equal(body.checks.github.valid, true);
+    assert.deepEqual(body.queues, { clones: { maxConcurrent: 2, running: 2, queued: 1 } });
+  });
+
+  it('returns 503 when git is missing', async () => {
+    const { statusCode, body } = await runDetail({ ...healthyOverrides(), probeGit: async () => null });
+
+    assert.equal(statusCode, 503);
+    assert.equal(body.status, 'fail');
+    assert.equal(body.checks.git.status, 'fail');
+    assert.equal(body.checks.disk.status, 'ok');
+  });
+
+  it('returns 503 when free disk space is low or cannot be read', async () => {
+    const low = await runDetail({ ...healthyOverrides(), statfs: async () => ({ bavail: 10, bsize: 4096 }) });
+    assert.equal(low.statusCode, 503);
+    assert.equal(low.body.checks.disk.status, 'fail');
+
+    const unreadable = await runDetail({
+      ...healthyOverrides(),
+      statfs: async () => {
+        throw new Error('ENOENT: no such file or directory');
+      },
+    });
+    assert.equal(unreadable.statusCode, 503);
+    assert.match(unreadable.body.checks.disk.message, /ENOENT/);
+  });
+
+  it('only degrades the report when GitHub is not configured', async () => {
+    const { statusCode, body } = await runDetail({
+      ...healthyOverrides(),
+      githubClient: {
+        getAuthStatus: async () => ({
+          configured: false,
+          valid: false,
+          message: 'You are not logged into any GitHub hosts.',
+        }),
+        getTokenVerification: () => null,
+      },
+    });
+
+    assert.equal(statusCode, 200);
+    assert.equal(body.status, 'degraded');
+    assert.equal(body.checks.github.status, 'warn');
+    assert.equal(body.checks.github.critical, false);
+    assert.equal(body.checks.github.configured, false);
+  });
+
+  it('surfaces the startup token verification in the GitHub check', async () => {
+    const verification = {
+      valid: false,
+      login: null,
+      message: 'gh: Bad credentials (HTTP 401)',
+      checkedAt: '2024-01-01T00:00:00.000Z',
+    };
+    const { statusCode, body } = await runDetail({
+      ...healthyOverrides(),
+      githubClient: {
+        getAuthStatus: async () => ({ configured: true, valid: true, message: null }),
+        getTokenVerification: () => verification,
+      },
+    });
+
+    assert.equal(statusCode, 200);
+    assert.equal(body.status, 'degraded');
+    assert.equal(body.checks.github.status, 'warn');
+    assert.equal(body.checks.github.message, 'gh: Bad credentials (HTTP 401)');
+    assert.deepEqual(body.checks.github.tokenVerification, verification);
+  });
+});
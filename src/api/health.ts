@@ -0,0 +1,127 @@
+import fs from 'node:fs/promises';
+import { getCloneQueueStats, probeGitVersion, type CloneQueueStats } from '../core/git.js';
+import { createGithubClient, type GithubClient } from '../core/github.js';
+import { createSimpleHandler } from './base-handler.js';
+
+/**
+ * Free space below which the workdir check fails; cloning and creating worktrees start failing around here
+ */
+export const DEFAULT_MIN_FREE_DISK_BYTES = 1024 * 1024 * 1024;
+
+export type HealthCheckStatus = 'ok' | 'warn' | 'fail';
+
+export interface HealthCheckResult {
+  status: HealthCheckStatus;
+  /**
+   * Critical checks that fail turn the whole report into a 503
+   */
+  critical: boolean;
+  message: string | null;
+  [key: string]: unknown;
+}
+
+export interface HealthReport {
+  status: 'ok' | 'degraded' | 'fail';
+  checks: {
+    git: HealthCheckResult;
+    disk: HealthCheckResult;
+    github: HealthCheckResult;
+  };
+  /**
+   * Work waiting for a slot, reported for visibility rather than judged healthy or not
+   */
+  queues: {
+    clones: CloneQueueStats;
+  };
+  checkedAt: string;
+}
+
+export interface HealthOverrides {
+  probeGit?: typeof probeGitVersion;
+  statfs?: (path: string) => Promise<{ bavail: number | bigint; bsize: number | bigint }>;
+  githubClient?: Pick<GithubClient, 'getAuthStatus' | 'getTokenVerification'>;
+  minFreeDiskBytes?: number;
+  cloneQueueStats?: typeof getCloneQueueStats;
+  now?: () => Date;
+}
+
+function describeError(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+export function createHealthHandlers(workdir: string, overrides: HealthOverrides = {}) {
+  const {
+    probeGit = probeGitVersion,
+    statfs = (target: string) => fs.statfs(target),
+    githubClient = createGithubClient(),
+    minFreeDiskBytes = DEFAULT_MIN_FREE_DISK_BYTES,
+    cloneQueueStats = getCloneQueueStats,
+    now = () => new Date(),
+  } = overrides;
+
+  async function checkGit(): Promise<HealthCheckResult> {
+    const version = await probeGit();
+    if (!version) {
+      return { status: 'fail', critical: true, message: 'git is not installed or not available on PATH', version: null };
+    }
+    return { status: 'ok', critical: true, message: null, version: version.replace(/^git version\s+/i, '') };
+  }
+
+  async function checkDisk(): Promise<HealthCheckResult> {
+    const stats = await statfs(workdir);
+    const freeBytes = Number(stats.bavail) * Number(stats.bsize);
+    const details = { path: workdir, freeBytes, minFreeBytes: minFreeDiskBytes };
+    if (freeBytes < minFreeDiskBytes) {
+      return { status: 'fail', critical: true, message: 'Free disk space under the workdir is below the minimum', ...details };
+    }
+    return { status: 'ok', critical: true, message: null, ...details };
+  }
+
+  // GitHub features are optional, so a missing or rejected token only degrades the report
+  async function checkGithub(): Promise<HealthCheckResult> {
+    const { configured, valid, message } = await githubClient.getAuthStatus();
+    // Startup verification only runs with --verify-github-token; null means it was skipped
+    const tokenCheck = githubClient.getTokenVerification();
+    const verified = tokenCheck ? tokenCheck.valid : true;
+    return {
+      status: valid && verified ? 'ok' : 'warn',
+      critical: false,
+      message: message ?? (verified ? null : tokenCheck?.message ?? null),
+      configured,
+      valid,
+      tokenVerification: tokenCheck,
+    };
+  }
+
+  async function runCheck(check: () => Promise<HealthCheckResult>, critical: boolean): Promise<HealthCheckResult> {
+    try {
+      return await check();
+    } catch (error: unknown) {
+      return { status: critical ? 'fail' : 'warn', critical, message: describeError(error) };
+    }
+  }
+
+  const basic = createSimpleHandler(async () => ({ status: 'ok' }));
+
+  const detail = createSimpleHandler(
+    async (): Promise<HealthReport> => {
+      const [git, disk, github] = await Promise.all([
+        runCheck(checkGit, true),
+        runCheck(checkDisk, true),
+        runCheck(checkGithub, false),
+      ]);
+      const checks = { git, disk, github };
+      const results = Object.values(checks);
+      const status = results.some((check) => check.critical && check.status === 'fail')
+        ? 'fail'
+        : results.some((check) => check.status !== 'ok')
+          ? 'degraded'
+          : 'ok';
+
+      return { status, checks, queues: { clones: cloneQueueStats() }, checkedAt: now().toISOString() };
+    },
+    { successCode: (report) => (report.status === 'fail' ? 503 : 200) }
+  );
+
+  return { basic, detail };
+}
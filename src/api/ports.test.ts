@@ -82,6 +82,35 @@ describe('createPortHandlers', () => {
     assert.deepEqual(sendCall.arguments[2], { ports: [3000, 5000] });
   });
 
+  it('lists ports opened by the worktree terminals', async () => {
+    const sendJson = mock.fn();
+    const listPids = mock.fn(async () => [100, 240]);
+    const listPorts = mock.fn(async () => [{ port: 5173, pid: 101, command: 'node' }]);
+
+    __setBaseHandlerTestOverrides({ sendJson });
+    __setPortsApiTestOverrides({ listWorktreeSessionPids: listPids, listProcessListeningPorts: listPorts });
+
+    const handlers = createPortHandlers({
+      portManager: {
+        open: async () => ({ port: 0, url: '', createdAt: 0 }),
+        close: async () => {},
+        closeAll: async () => {},
+        list: () => [],
+      },
+    });
+
+    await handlers.listForWorktree(
+      createContext({ url: new URL('http://localhost/api/worktrees/ports?org=acme&repo=web&branch=feature/login') }),
+    );
+
+    assert.deepEqual(listPids.mock.calls[0]?.arguments, ['acme', 'web', 'feature/login']);
+    assert.deepEqual(listPorts.mock.calls[0]?.arguments, [[100, 240]]);
+    assert.equal(sendJson.mock.calls[0]?.arguments[1], 200);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], {
+      ports: [{ port: 5173, pid: 101, command: 'node' }],
+    });
+  });
+
   it('validates tunnel creation payload', async () => {
     const endMock = mock.fn();
     const context = createContext({
@@ -1,15 +1,21 @@
-import { listActivePorts } from '../core/ports.js';
+import { listActivePorts, listProcessListeningPorts } from '../core/ports.js';
+import { listWorktreeSessionPids } from '../core/terminal-sessions.js';
 import { ValidationError } from '../infrastructure/errors/index.js';
+import { extractWorktreeParams } from '../validation/index.js';
 import { createHandler, createQueryHandler } from './base-handler.js';
 import type { PortTunnelManager, PortTunnel } from '../core/ports.js';
 import type { RequestContext } from '../types/http.js';
 
 interface PortApiDependencies {
   listActivePorts: typeof listActivePorts;
+  listWorktreeSessionPids: typeof listWorktreeSessionPids;
+  listProcessListeningPorts: typeof listProcessListeningPorts;
 }
 
 const defaultDependencies: PortApiDependencies = {
   listActivePorts,
+  listWorktreeSessionPids,
+  listProcessListeningPorts,
 };
 
 let activeDependencies: PortApiDependencies = { ...defaultDependencies };
@@ -64,6 +70,7 @@ function validateOpenTunnelPayload(payload: unknown): OpenTunnelValidatorInput {
 
 export interface PortHandlers {
   list: (context: RequestContext) => Promise<void>;
+  listForWorktree: (context: RequestContext) => Promise<void>;
   openTunnel: (context: RequestContext) => Promise<void>;
 }
 
@@ -78,6 +85,15 @@ export function createPortHandlers({ portManager }: CreatePortHandlersOptions):
     return { ports };
   });
 
+  // Only processes started from the worktree's terminals count; other listeners on the host are ignored
+  const listForWorktree = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo, branch } = extractWorktreeParams(context.url.searchParams);
+    const pids = await activeDependencies.listWorktreeSessionPids(org, repo, branch);
+    const ports = await activeDependencies.listProcessListeningPorts(pids);
+    context.res.setHeader('Cache-Control', 'no-store');
+    return { ports };
+  });
+
   const openTunnel = createHandler<OpenTunnelInput, PortTunnel>({
     validator: validateOpenTunnelPayload,
     successCode: 201,
@@ -90,6 +106,7 @@ export function createPortHandlers({ portManager }: CreatePortHandlersOptions):
 
   return {
     list,
+    listForWorktree,
     openTunnel,
   };
 }
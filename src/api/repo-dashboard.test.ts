@@ -1,8 +1,14 @@
 import assert from 'node:assert/strict';
-import { describe, it, mock } from 'node:test';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { after, before, describe, it, mock } from 'node:test';
 
 import { createRepoDashboardHandlers } from './repo-dashboard.js';
 import { __setBaseHandlerTestOverrides } from './base-handler.js';
+import { savePlanToWorktree } from '../core/plan-storage.js';
 import type { RequestContext } from '../types/http.js';
 
 function createContext(overrides: Partial<RequestContext> = {}): RequestContext {
@@ -36,7 +42,7 @@ describe('createRepoDashboardHandlers', () => {
     const githubClient = {
       countOpenPullRequests: mock.fn(async () => 5),
       countOpenIssues: mock.fn(async () => 10),
-      listOpenIssues: mock.fn(async () => [{ number: 1, title: 'Issue 1' }]),
+      listIssues: mock.fn(async () => [{ number: 1, title: 'Issue 1' }]),
       countRunningWorkflows: mock.fn(async () => 2),
     };
     const now = mock.fn(() => new Date('2024-01-01T00:00:00Z'));
@@ -58,7 +64,7 @@ describe('createRepoDashboardHandlers', () => {
 
     assert.equal(githubClient.countOpenPullRequests.mock.calls.length, 1);
     assert.equal(githubClient.countOpenIssues.mock.calls.length, 1);
-    assert.equal(githubClient.listOpenIssues.mock.calls.length, 1);
+    assert.equal(githubClient.listIssues.mock.calls.length, 1);
     assert.equal(githubClient.countRunningWorkflows.mock.calls.length, 1);
     assert.equal(worktreeCounter.mock.calls.length, 1);
 
@@ -77,6 +83,322 @@ describe('createRepoDashboardHandlers', () => {
     assert.equal((response.data as { worktrees: { local: number } }).worktrees.local, 3);
   });
 
+  it('queries GitHub with the slug from the origin remote', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const githubClient = {
+      countOpenPullRequests: mock.fn(async () => 0),
+      countOpenIssues: mock.fn(async () => 0),
+      listIssues: mock.fn(async () => []),
+      countRunningWorkflows: mock.fn(async () => 0),
+    };
+    const resolveSlug = mock.fn(async () => ({ org: 'upstream-org', repo: 'real-name' }));
+
+    const handlers = createRepoDashboardHandlers('/workdir', {
+      ensureRepo: mock.fn(async () => ({ repoRoot: '/repo', repositoryPath: '/repo/path' })),
+      worktreeCounter: mock.fn(async () => 0),
+      githubClient,
+      resolveSlug,
+    });
+
+    await handlers.read(createContext());
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual(resolveSlug.mock.calls[0]?.arguments, ['/repo/path', 'vultuk', 'agentrix']);
+    assert.deepEqual(githubClient.countOpenPullRequests.mock.calls[0]?.arguments, ['upstream-org', 'real-name']);
+    assert.deepEqual(githubClient.listIssues.mock.calls[0]?.arguments, ['upstream-org', 'real-name', { state: 'open' }]);
+    const response = sendJson.mock.calls[0]?.arguments[2] as { data: { org: string; repo: string } };
+    assert.equal(response.data.org, 'vultuk');
+    assert.equal(response.data.repo, 'agentrix');
+  });
+
+  it('forwards the requested issue state and rejects unknown ones', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const githubClient = {
+      countOpenPullRequests: mock.fn(async () => 0),
+      countOpenIssues: mock.fn(async () => 0),
+      listIssues: mock.fn(async () => [{ number: 3, title: 'Fixed', state: 'closed' }]),
+      countRunningWorkflows: mock.fn(async () => 0),
+    };
+    const handlers = createRepoDashboardHandlers('/workdir', {
+      ensureRepo: mock.fn(async () => ({ repoRoot: '/repo', repositoryPath: '/repo/path' })),
+      worktreeCounter: mock.fn(async () => 0),
+      resolveSlug: async (_path: string, org: string, repo: string) => ({ org, repo }),
+      githubClient,
+    });
+
+    await handlers.read(
+      createContext({ url: new URL('http://localhost/api/repos/dashboard?org=vultuk&repo=agentrix&state=Closed') })
+    );
+    const invalid = createContext({
+      url: new URL('http://localhost/api/repos/dashboard?org=vultuk&repo=agentrix&state=merged'),
+    });
+    await handlers.read(invalid);
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual(githubClient.listIssues.mock.calls[0]?.arguments, ['vultuk', 'agentrix', { state: 'closed' }]);
+    const { data } = sendJson.mock.calls[0]?.arguments[2] as { data: { issues: Record<string, unknown> } };
+    assert.equal(data.issues['state'], 'closed');
+    assert.equal(githubClient.listIssues.mock.calls.length, 1);
+    assert.equal(invalid.res.statusCode, 400);
+  });
+
+  describe('partial GitHub failures', () => {
+    function createGithubClient(failing: string[]) {
+      const respond = <T>(name: string, value: T) =>
+        mock.fn(async () => {
+          if (failing.includes(name)) {
+            throw new Error(`${name} unavailable`);
+          }
+          return value;
+        });
+      return {
+        countOpenPullRequests: respond('pulls', 4),
+        countOpenIssues: respond('issueCount', 2),
+        listIssues: respond('issueList', [{ number: 7, title: 'Bug' }]),
+        countRunningWorkflows: respond('workflows', 1),
+      };
+    }
+
+    async function readDashboard(failing: string[]) {
+      const sendJson = mock.fn();
+      __setBaseHandlerTestOverrides({ sendJson });
+      const handlers = createRepoDashboardHandlers('/workdir', {
+        ensureRepo: mock.fn(async () => ({ repoRoot: '/repo', repositoryPath: '/repo/path' })),
+        worktreeCounter: mock.fn(async () => 0),
+        resolveSlug: async (_path: string, org: string, repo: string) => ({ org, repo }),
+        githubClient: createGithubClient(failing),
+      });
+      const context = createContext();
+      await handlers.read(context);
+      __setBaseHandlerTestOverrides();
+      return { sendJson, context };
+    }
+
+    it('returns issues when pull requests fail', async () => {
+      const { sendJson } = await readDashboard(['pulls']);
+      const call = sendJson.mock.calls[0];
+      assert.ok(call);
+      assert.equal(call.arguments[1], 200);
+      const { data } = call.arguments[2] as { data: Record<string, unknown> };
+      assert.deepEqual(data['pullRequests'], { open: null, error: 'pulls unavailable' });
+      assert.deepEqual(data['issues'], { open: 2, state: 'open', items: [{ number: 7, title: 'Bug' }] });
+      assert.deepEqual(data['workflows'], { running: 1 });
+    });
+
+    it('returns pull requests when issues fail', async () => {
+      const { sendJson } = await readDashboard(['issueList']);
+      const { data } = sendJson.mock.calls[0]?.arguments[2] as { data: Record<string, unknown> };
+      assert.deepEqual(data['pullRequests'], { open: 4 });
+      assert.deepEqual(data['issues'], { open: 2, state: 'open', items: [], error: 'issueList unavailable' });
+    });
+
+    it('returns the remaining sections when workflows fail', async () => {
+      const { sendJson } = await readDashboard(['workflows']);
+      const { data } = sendJson.mock.calls[0]?.arguments[2] as { data: Record<string, unknown> };
+      assert.deepEqual(data['workflows'], { running: null, error: 'workflows unavailable' });
+      assert.deepEqual(data['pullRequests'], { open: 4 });
+    });
+
+    it('fails the request when every section fails', async () => {
+      mock.method(console, 'error', () => {});
+      const { sendJson, context } = await readDashboard(['pulls', 'issueCount', 'issueList', 'workflows']);
+      mock.restoreAll();
+      assert.equal(sendJson.mock.calls.length, 0);
+      assert.equal(context.res.statusCode, 500);
+      const body = JSON.parse((context.res.end as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0] as string);
+      assert.equal(body.error, 'pulls unavailable');
+    });
+  });
+
+  describe('summary', () => {
+    it('summarises every repository in the workdir, keeping per-repo failures', async () => {
+      const sendJson = mock.fn();
+      __setBaseHandlerTestOverrides({ sendJson });
+
+      const githubClient = {
+        countOpenPullRequests: mock.fn(async (org: string) => (org === 'upstream' ? 3 : 1)),
+        countOpenIssues: mock.fn(async () => 2),
+        listIssues: mock.fn(async () => []),
+        countRunningWorkflows: mock.fn(async (org: string) => {
+          if (org === 'acme') {
+            throw new Error('workflows unavailable');
+          }
+          return 0;
+        }),
+      };
+      const ensureRepo = mock.fn(async (_workdir: string, org: string, repo: string) => {
+        if (repo === 'broken') {
+          throw new Error('Repository not found');
+        }
+        return { repoRoot: `/workdir/${org}/${repo}`, repositoryPath: `/workdir/${org}/${repo}/repository` };
+      });
+
+      const handlers = createRepoDashboardHandlers('/workdir', {
+        ensureRepo,
+        githubClient,
+        listRepositories: async () => [
+          { org: 'acme', repos: ['broken', 'web'] },
+          { org: 'vultuk', repos: ['agentrix'] },
+        ],
+        resolveSlug: async (_path: string, org: string, repo: string) =>
+          org === 'vultuk' ? { org: 'upstream', repo: 'agentrix' } : { org, repo },
+        summaryConcurrency: 2,
+        now: () => new Date('2024-01-01T00:00:00Z'),
+      });
+
+      await handlers.summary(createContext());
+      __setBaseHandlerTestOverrides();
+
+      assert.equal(sendJson.mock.calls[0]?.arguments[1], 200);
+      const { data } = sendJson.mock.calls[0]?.arguments[2] as { data: Record<string, unknown> };
+      assert.equal(data['fetchedAt'], '2024-01-01T00:00:00.000Z');
+      assert.deepEqual(data['repositories'], {
+        'acme/broken': {
+          org: 'acme',
+          repo: 'broken',
+          slug: null,
+          pullRequests: { open: null },
+          issues: { open: null },
+          workflows: { running: null },
+          error: 'Repository not found',
+        },
+        'acme/web': {
+          org: 'acme',
+          repo: 'web',
+          slug: 'acme/web',
+          pullRequests: { open: 1 },
+          issues: { open: 2 },
+          workflows: { running: null, error: 'workflows unavailable' },
+        },
+        'vultuk/agentrix': {
+          org: 'vultuk',
+          repo: 'agentrix',
+          slug: 'upstream/agentrix',
+          pullRequests: { open: 3 },
+          issues: { open: 2 },
+          workflows: { running: 0 },
+        },
+      });
+      assert.equal(githubClient.listIssues.mock.calls.length, 0);
+    });
+
+    it('reports an error for repositories where every GitHub call fails', async () => {
+      const sendJson = mock.fn();
+      __setBaseHandlerTestOverrides({ sendJson });
+      const unavailable = async (): Promise<number> => {
+        throw new Error('gh: API rate limit exceeded');
+      };
+
+      const handlers = createRepoDashboardHandlers('/workdir', {
+        ensureRepo: mock.fn(async () => ({ repoRoot: '/repo', repositoryPath: '/repo/path' })),
+        githubClient: {
+          countOpenPullRequests: unavailable,
+          countOpenIssues: unavailable,
+          listIssues: async () => [],
+          countRunningWorkflows: unavailable,
+        },
+        listRepositories: async () => [{ org: 'acme', repos: ['web'] }],
+        resolveSlug: async (_path: string, org: string, repo: string) => ({ org, repo }),
+      });
+
+      await handlers.summary(createContext());
+      __setBaseHandlerTestOverrides();
+
+      assert.equal(sendJson.mock.calls[0]?.arguments[1], 200);
+      const { data } = sendJson.mock.calls[0]?.arguments[2] as {
+        data: { repositories: Record<string, { error?: string; pullRequests: unknown }> };
+      };
+      assert.equal(data.repositories['acme/web']?.error, 'gh: API rate limit exceeded');
+      assert.deepEqual(data.repositories['acme/web']?.pullRequests, { open: null });
+    });
+  });
+
+  describe('detail handler', () => {
+    const execFileAsync = promisify(execFile);
+    const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+    let workdir: string;
+    let repositoryPath: string;
+    let featurePath: string;
+
+    before(async () => {
+      workdir = await fs.realpath(await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-detail-')));
+      repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+      featurePath = path.join(workdir, 'acme', 'demo', 'login');
+      await fs.mkdir(repositoryPath, { recursive: true });
+      await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+      await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+      await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', featurePath]);
+      await savePlanToWorktree({
+        worktreePath: featurePath,
+        branch: 'feature/login',
+        planText: 'Build the login form',
+        clock: () => new Date('2024-03-01T10:00:00Z'),
+        gitAdd: async () => {},
+      });
+    });
+
+    after(async () => {
+      await fs.rm(workdir, { recursive: true, force: true });
+    });
+
+    it('returns the worktrees, their plans and the GitHub summary of one repository', async () => {
+      const sendJson = mock.fn();
+      __setBaseHandlerTestOverrides({ sendJson });
+      const githubClient = {
+        countOpenPullRequests: mock.fn(async () => 4),
+        countOpenIssues: mock.fn(async () => 7),
+        countRunningWorkflows: mock.fn(async () => 1),
+      };
+      const handlers = createRepoDashboardHandlers(workdir, {
+        githubClient: githubClient as never,
+        resolveSlug: async () => ({ org: 'acme', repo: 'demo' }),
+        now: () => new Date('2024-03-02T00:00:00Z'),
+      });
+
+      await handlers.detail(createContext({ url: new URL('http://localhost/api/repos/detail?org=acme&repo=demo') }));
+      __setBaseHandlerTestOverrides();
+
+      const [, status, body] = sendJson.mock.calls[0]?.arguments ?? [];
+      assert.equal(status, 200);
+      const { data } = body as { data: Record<string, any> };
+      assert.equal(data['fetchedAt'], '2024-03-02T00:00:00.000Z');
+      assert.deepEqual(
+        data['worktrees'].map((worktree: any) => [worktree.path, worktree.branch, worktree.primary, worktree.plans.length]),
+        [
+          [repositoryPath, 'main', true, 0],
+          [featurePath, 'feature/login', false, 1],
+        ]
+      );
+      assert.deepEqual(data['worktrees'][1].plans[0], {
+        id: '20240301_100000-feature_login.md',
+        branch: 'feature_login',
+        createdAt: '2024-03-01T10:00:00.000Z',
+      });
+      assert.deepEqual(data['github'], {
+        org: 'acme',
+        repo: 'demo',
+        slug: 'acme/demo',
+        pullRequests: { open: 4 },
+        issues: { open: 7 },
+        workflows: { running: 1 },
+      });
+    });
+
+    it('answers 404 without calling GitHub when the repository is not cloned', async () => {
+      const githubClient = { countOpenPullRequests: mock.fn(async () => 0) };
+      const handlers = createRepoDashboardHandlers(workdir, { githubClient: githubClient as never });
+      const context = createContext({ url: new URL('http://localhost/api/repos/detail?org=acme&repo=missing') });
+
+      await handlers.detail(context);
+
+      assert.equal(context.res.statusCode, 404);
+      assert.equal(githubClient.countOpenPullRequests.mock.calls.length, 0);
+    });
+  });
+
   it('handles repository not found errors', async () => {
     __setBaseHandlerTestOverrides();
 
@@ -1,49 +1,140 @@
-import { ensureRepository, countLocalWorktrees } from '../core/git.js';
-import { createGithubClient } from '../core/github.js';
+import {
+  ensureRepository,
+  countLocalWorktrees,
+  discoverWorktrees,
+  listRepositoryTree,
+  resolveRepositorySlug,
+  type DiscoveredWorktree,
+} from '../core/git.js';
+import { createGithubClient, GITHUB_ISSUE_STATES, isGithubIssueState, type GithubIssueState } from '../core/github.js';
+import { RepositorySummaryCache } from '../core/github-summary-cache.js';
+import { listPlansForWorktree, type PlanEntry } from '../core/plan-storage.js';
 import { handleHeadRequest } from '../utils/http.js';
 import { extractRepositoryParams } from '../validation/index.js';
+import { mapWithConcurrency } from '../utils/concurrency.js';
 import { createQueryHandler } from './base-handler.js';
-import { HttpError } from '../infrastructure/errors/index.js';
+import { HttpError, ValidationError } from '../infrastructure/errors/index.js';
 import type { RequestContext } from '../types/http.js';
 
 export interface RepoDashboardOverrides {
   githubClient?: ReturnType<typeof createGithubClient>;
   ensureRepo?: typeof ensureRepository;
   worktreeCounter?: typeof countLocalWorktrees;
+  resolveSlug?: typeof resolveRepositorySlug;
+  listRepositories?: typeof listRepositoryTree;
+  discover?: typeof discoverWorktrees;
+  listPlans?: typeof listPlansForWorktree;
+  summaryConcurrency?: number;
+  summaryCache?: RepositorySummaryCache<GithubSummarySections>;
   now?: () => Date;
 }
 
+/**
+ * Number of repositories whose GitHub summary is fetched at once by the workdir summary
+ */
+export const DEFAULT_SUMMARY_CONCURRENCY = 4;
+
+export interface RepositorySummary {
+  org: string;
+  repo: string;
+  /**
+   * `owner/repo` queried on GitHub, or null when the repository could not be resolved
+   */
+  slug: string | null;
+  pullRequests: { open: number | null; error?: string };
+  issues: { open: number | null; error?: string };
+  workflows: { running: number | null; error?: string };
+  /**
+   * Set when nothing could be fetched for the repository
+   */
+  error?: string;
+}
+
+export type GithubSummarySections = Pick<RepositorySummary, 'pullRequests' | 'issues' | 'workflows'>;
+
+export interface RepositoryDetailWorktree extends DiscoveredWorktree {
+  /**
+   * Plans saved in the worktree for its branch, newest first; empty when the directory is gone
+   */
+  plans: PlanEntry[];
+}
+
+function settledValue<T>(result: PromiseSettledResult<T>): T | null {
+  return result.status === 'fulfilled' ? result.value : null;
+}
+
+function describeError(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+function parseIssueState(value: string | null): GithubIssueState {
+  const state = value?.trim().toLowerCase() || 'open';
+  if (!isGithubIssueState(state)) {
+    throw new ValidationError(`state query parameter must be one of ${GITHUB_ISSUE_STATES.join(', ')}`);
+  }
+  return state;
+}
+
+function settledError(...results: PromiseSettledResult<unknown>[]): string | null {
+  const failure = results.find((result): result is PromiseRejectedResult => result.status === 'rejected');
+  if (!failure) {
+    return null;
+  }
+  return describeError(failure.reason);
+}
+
 export function createRepoDashboardHandlers(workdir: string, overrides: RepoDashboardOverrides = {}) {
   const {
     githubClient = createGithubClient(),
     ensureRepo = ensureRepository,
     worktreeCounter = countLocalWorktrees,
+    resolveSlug = resolveRepositorySlug,
+    listRepositories = listRepositoryTree,
+    discover = discoverWorktrees,
+    listPlans = listPlansForWorktree,
+    summaryConcurrency = DEFAULT_SUMMARY_CONCURRENCY,
+    summaryCache = new RepositorySummaryCache<GithubSummarySections>(),
     now = () => new Date(),
   } = overrides;
 
-  const read = createQueryHandler(async (context: RequestContext) => {
-    const { org, repo } = extractRepositoryParams(context.url.searchParams);
-
-    let repositoryPath;
+  async function requireRepository(org: string, repo: string): Promise<string> {
     try {
-      ({ repositoryPath } = await ensureRepo(workdir, org, repo));
+      return (await ensureRepo(workdir, org, repo)).repositoryPath;
     } catch (error: unknown) {
       const message = error instanceof Error ? error.message : String(error);
       const statusCode = message.includes('not found') ? 404 : 500;
       throw new HttpError(message, statusCode);
     }
+  }
+
+  const read = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+    const issueState = parseIssueState(context.url.searchParams.get('state'));
+    const repositoryPath = await requireRepository(org, repo);
 
     if (context.method === 'HEAD') {
       handleHeadRequest(context.res);
       return;
     }
 
-    const [openPullRequests, openIssues, openIssueDetails, runningWorkflows] = await Promise.all([
-      githubClient.countOpenPullRequests(org, repo),
-      githubClient.countOpenIssues(org, repo),
-      githubClient.listOpenIssues(org, repo),
-      githubClient.countRunningWorkflows(org, repo),
-    ]);
+    const slug = await resolveSlug(repositoryPath, org, repo);
+    // Each GitHub section is fetched independently so a partial outage still yields the rest
+    const results = await Promise.allSettled([
+      githubClient.countOpenPullRequests(slug.org, slug.repo),
+      githubClient.countOpenIssues(slug.org, slug.repo),
+      githubClient.listIssues(slug.org, slug.repo, { state: issueState }),
+      githubClient.countRunningWorkflows(slug.org, slug.repo),
+    ] as const);
+    const [pullRequestsResult, issueCountResult, issueListResult, workflowsResult] = results;
+
+    // Only fail outright when GitHub gave us nothing at all
+    if (results.every((result) => result.status === 'rejected')) {
+      throw (pullRequestsResult as PromiseRejectedResult).reason;
+    }
+
+    const pullRequestsError = settledError(pullRequestsResult);
+    const issuesError = settledError(issueCountResult, issueListResult);
+    const workflowsError = settledError(workflowsResult);
 
     const worktreeCount = await worktreeCounter(repositoryPath, { includeMain: false });
     const fetchedAt = now().toISOString();
@@ -53,13 +144,146 @@ export function createRepoDashboardHandlers(workdir: string, overrides: RepoDash
         org,
         repo,
         fetchedAt,
-        pullRequests: { open: openPullRequests },
-        issues: { open: openIssues, items: openIssueDetails },
-        workflows: { running: runningWorkflows },
+        pullRequests: {
+          open: settledValue(pullRequestsResult),
+          ...(pullRequestsError ? { error: pullRequestsError } : {}),
+        },
+        issues: {
+          open: settledValue(issueCountResult),
+          state: issueState,
+          items: settledValue(issueListResult) ?? [],
+          ...(issuesError ? { error: issuesError } : {}),
+        },
+        workflows: {
+          running: settledValue(workflowsResult),
+          ...(workflowsError ? { error: workflowsError } : {}),
+        },
         worktrees: { local: worktreeCount },
       },
     };
   });
 
-  return { read };
+  async function summariseRepository(org: string, repo: string): Promise<RepositorySummary> {
+    const empty = {
+      pullRequests: { open: null },
+      issues: { open: null },
+      workflows: { running: null },
+    };
+
+    let slug;
+    try {
+      const { repositoryPath } = await ensureRepo(workdir, org, repo);
+      slug = await resolveSlug(repositoryPath, org, repo);
+    } catch (error: unknown) {
+      return { org, repo, slug: null, ...empty, error: describeError(error) };
+    }
+
+    const slugName = `${slug.org}/${slug.repo}`;
+    try {
+      const sections = await summaryCache.get(slugName, () => fetchSummarySections(slug.org, slug.repo));
+      return { org, repo, slug: slugName, ...sections };
+    } catch (error: unknown) {
+      return { org, repo, slug: slugName, ...empty, error: describeError(error) };
+    }
+  }
+
+  // Rejects only when every section failed, so total outages are never cached
+  async function fetchSummarySections(owner: string, name: string): Promise<GithubSummarySections> {
+    const results = await Promise.allSettled([
+      githubClient.countOpenPullRequests(owner, name),
+      githubClient.countOpenIssues(owner, name),
+      githubClient.countRunningWorkflows(owner, name),
+    ] as const);
+    const [pullRequestsResult, issuesResult, workflowsResult] = results;
+
+    if (results.every((result) => result.status === 'rejected')) {
+      throw new Error(settledError(pullRequestsResult) ?? 'GitHub unavailable');
+    }
+
+    const pullRequestsError = settledError(pullRequestsResult);
+    const issuesError = settledError(issuesResult);
+    const workflowsError = settledError(workflowsResult);
+
+    return {
+      pullRequests: {
+        open: settledValue(pullRequestsResult),
+        ...(pullRequestsError ? { error: pullRequestsError } : {}),
+      },
+      issues: {
+        open: settledValue(issuesResult),
+        ...(issuesError ? { error: issuesError } : {}),
+      },
+      workflows: {
+        running: settledValue(workflowsResult),
+        ...(workflowsError ? { error: workflowsError } : {}),
+      },
+    };
+  }
+
+  // Repositories are summarised independently so one failing clone never fails the whole workdir
+  const summary = createQueryHandler(async (context: RequestContext) => {
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const tree = await listRepositories(workdir);
+    const targets = tree.flatMap(({ org, repos }) => repos.map((repo) => ({ org, repo })));
+    const summaries = await mapWithConcurrency(targets, summaryConcurrency, ({ org, repo }) =>
+      summariseRepository(org, repo)
+    );
+
+    const repositories: Record<string, RepositorySummary> = {};
+    summaries.forEach((entry) => {
+      repositories[`${entry.org}/${entry.repo}`] = entry;
+    });
+
+    return {
+      data: {
+        fetchedAt: now().toISOString(),
+        repositories,
+      },
+    };
+  });
+
+  async function listWorktreesWithPlans(org: string, repo: string): Promise<RepositoryDetailWorktree[]> {
+    const { worktrees } = await discover(workdir, org, repo);
+    return await Promise.all(
+      worktrees.map(async (worktree) => ({
+        ...worktree,
+        plans:
+          worktree.onDisk && worktree.branch
+            ? await listPlans({ worktreePath: worktree.path, branch: worktree.branch })
+            : [],
+      }))
+    );
+  }
+
+  // Everything one repository page needs, so it does not have to load the whole sessions tree
+  const detail = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+    await requireRepository(org, repo);
+
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const [worktrees, github] = await Promise.all([
+      listWorktreesWithPlans(org, repo),
+      summariseRepository(org, repo),
+    ]);
+
+    return {
+      data: {
+        org,
+        repo,
+        fetchedAt: now().toISOString(),
+        worktrees,
+        github,
+      },
+    };
+  });
+
+  return { read, summary, detail };
 }
@@ -2,6 +2,7 @@ import assert from 'node:assert/strict';
 import { describe, it, mock } from 'node:test';
 
 import { createRepoIssueHandlers } from './repo-issue.js';
+import { GitHubSearchRateLimitError } from '../core/github.js';
 import { __setBaseHandlerTestOverrides } from './base-handler.js';
 import type { RequestContext } from '../types/http.js';
 
@@ -56,7 +57,7 @@ describe('createRepoIssueHandlers', () => {
     assert.deepEqual(ensureRepo.mock.calls[0]?.arguments, ['/workdir', 'vultuk', 'agentrix']);
 
     assert.equal(githubClient.getIssue.mock.calls.length, 1);
-    assert.deepEqual(githubClient.getIssue.mock.calls[0]?.arguments, ['vultuk', 'agentrix', 123]);
+    assert.deepEqual(githubClient.getIssue.mock.calls[0]?.arguments, ['vultuk', 'agentrix', 123, { renderHtml: false }]);
 
     assert.equal(sendJson.mock.calls.length, 1);
     const call = sendJson.mock.calls[0];
@@ -70,6 +71,32 @@ describe('createRepoIssueHandlers', () => {
     assert.equal((response.data as { issue: { number: number } }).issue.number, 123);
   });
 
+  it('read handler asks for rendered HTML only when render=html', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const githubClient = {
+      getIssue: mock.fn(async () => ({ number: 123, body: '**hi**', bodyHtml: '<p><strong>hi</strong></p>' })),
+    };
+    const handlers = createRepoIssueHandlers('/workdir', {
+      ensureRepo: mock.fn(async () => ({ repositoryPath: '/repo/path' })),
+      resolveSlug: async (_path, org, repo) => ({ org, repo }),
+      githubClient: githubClient as never,
+    });
+
+    await handlers.read(
+      createContext({ url: new URL('http://localhost/api/repos/issue?org=vultuk&repo=agentrix&issue=123&render=html') })
+    );
+    const invalid = createContext({
+      url: new URL('http://localhost/api/repos/issue?org=vultuk&repo=agentrix&issue=123&render=pdf'),
+    });
+    await handlers.read(invalid);
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual(githubClient.getIssue.mock.calls[0]?.arguments, ['vultuk', 'agentrix', 123, { renderHtml: true }]);
+    assert.equal(githubClient.getIssue.mock.calls.length, 1);
+    assert.equal(invalid.res.statusCode, 400);
+  });
+
   it('requires issue parameter', async () => {
     __setBaseHandlerTestOverrides();
 
@@ -193,5 +220,84 @@ describe('createRepoIssueHandlers', () => {
     // Should have called end (either from handleHeadRequest or createQueryHandler)
     assert.equal(end.mock.calls.length, 1);
   });
+
+  it('search handler forwards the query and pagination', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const ensureRepo = mock.fn(async () => ({ repositoryPath: '/repo/path' }));
+    const resolveSlug = mock.fn(async () => ({ org: 'vultuk', repo: 'agentrix' }));
+    const searchResult = {
+      query: 'crash',
+      totalCount: 1,
+      incomplete: false,
+      page: 2,
+      perPage: 10,
+      hasMore: false,
+      items: [],
+    };
+    const githubClient = {
+      searchIssues: mock.fn(async () => searchResult),
+    };
+    const now = mock.fn(() => new Date('2024-01-01T00:00:00Z'));
+
+    const handlers = createRepoIssueHandlers('/workdir', { ensureRepo, resolveSlug, githubClient, now });
+
+    const context = createContext({
+      url: new URL('http://localhost/api/repos/search?org=vultuk&repo=agentrix&q=crash&page=2&perPage=10'),
+    });
+
+    await handlers.search(context);
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual(githubClient.searchIssues.mock.calls[0]?.arguments, [
+      'vultuk',
+      'agentrix',
+      'crash',
+      { page: 2, perPage: 10 },
+    ]);
+    const call = sendJson.mock.calls[0];
+    assert.ok(call);
+    assert.equal(call.arguments[1], 200);
+    assert.deepEqual(call.arguments[2], {
+      data: { org: 'vultuk', repo: 'agentrix', ...searchResult, fetchedAt: '2024-01-01T00:00:00.000Z' },
+    });
+  });
+
+  it('search handler requires a query', async () => {
+    __setBaseHandlerTestOverrides();
+
+    const handlers = createRepoIssueHandlers('/workdir', {});
+    const context = createContext({
+      url: new URL('http://localhost/api/repos/search?org=vultuk&repo=agentrix'),
+    });
+
+    await handlers.search(context);
+
+    assert.equal(context.res.statusCode, 400);
+  });
+
+  it('search handler sets Retry-After when the search rate limit is hit', async () => {
+    __setBaseHandlerTestOverrides();
+
+    const ensureRepo = mock.fn(async () => ({ repositoryPath: '/repo/path' }));
+    const resolveSlug = mock.fn(async () => ({ org: 'vultuk', repo: 'agentrix' }));
+    const githubClient = {
+      searchIssues: mock.fn(async () => {
+        throw new GitHubSearchRateLimitError(42);
+      }),
+    };
+
+    const handlers = createRepoIssueHandlers('/workdir', { ensureRepo, resolveSlug, githubClient });
+    const context = createContext({
+      url: new URL('http://localhost/api/repos/search?org=vultuk&repo=agentrix&q=crash'),
+    });
+    const setHeader = context.res.setHeader as ReturnType<typeof mock.fn>;
+
+    await handlers.search(context);
+
+    assert.equal(context.res.statusCode, 429);
+    assert.ok(setHeader.mock.calls.some((call) => call.arguments[0] === 'Retry-After' && call.arguments[1] === '42'));
+  });
 });
 
@@ -1,5 +1,5 @@
-import { ensureRepository } from '../core/git.js';
-import { createGithubClient } from '../core/github.js';
+import { ensureRepository, resolveRepositorySlug } from '../core/git.js';
+import { createGithubClient, GitHubSearchRateLimitError } from '../core/github.js';
 import { handleHeadRequest } from '../utils/http.js';
 import { extractRepositoryParams } from '../validation/index.js';
 import { HttpError, ValidationError } from '../infrastructure/errors/index.js';
@@ -9,6 +9,7 @@ import type { RequestContext } from '../types/http.js';
 export interface RepoIssueOverrides {
   githubClient?: ReturnType<typeof createGithubClient>;
   ensureRepo?: typeof ensureRepository;
+  resolveSlug?: typeof resolveRepositorySlug;
   now?: () => Date;
 }
 
@@ -16,6 +17,7 @@ export function createRepoIssueHandlers(workdir: string, overrides: RepoIssueOve
   const {
     githubClient = createGithubClient(),
     ensureRepo = ensureRepository,
+    resolveSlug = resolveRepositorySlug,
     now = () => new Date(),
   } = overrides;
 
@@ -32,8 +34,14 @@ export function createRepoIssueHandlers(workdir: string, overrides: RepoIssueOve
       throw new ValidationError('issue query parameter must be a positive integer');
     }
 
+    const render = context.url.searchParams.get('render')?.trim().toLowerCase() || 'markdown';
+    if (render !== 'markdown' && render !== 'html') {
+      throw new ValidationError('render query parameter must be markdown or html');
+    }
+
+    let repositoryPath;
     try {
-      await ensureRepo(workdir, org, repo);
+      ({ repositoryPath } = await ensureRepo(workdir, org, repo));
     } catch (error: unknown) {
       const message = error instanceof Error ? error.message : String(error);
       const statusCode = message.includes('not found') ? 404 : 500;
@@ -45,7 +53,8 @@ export function createRepoIssueHandlers(workdir: string, overrides: RepoIssueOve
       return;
     }
 
-    const issue = await githubClient.getIssue(org, repo, issueNumber);
+    const slug = await resolveSlug(repositoryPath, org, repo);
+    const issue = await githubClient.getIssue(slug.org, slug.repo, issueNumber, { renderHtml: render === 'html' });
     const fetchedAt = now().toISOString();
     
     return {
@@ -58,5 +67,64 @@ export function createRepoIssueHandlers(workdir: string, overrides: RepoIssueOve
     };
   });
 
-  return { read };
+  const search = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+    const query = context.url.searchParams.get('q')?.trim() || '';
+
+    if (!query) {
+      throw new ValidationError('q query parameter is required');
+    }
+
+    const page = parsePositiveIntegerParam(context.url.searchParams.get('page'), 'page');
+    const perPage = parsePositiveIntegerParam(context.url.searchParams.get('perPage'), 'perPage');
+
+    let repositoryPath;
+    try {
+      ({ repositoryPath } = await ensureRepo(workdir, org, repo));
+    } catch (error: unknown) {
+      const message = error instanceof Error ? error.message : String(error);
+      const statusCode = message.includes('not found') ? 404 : 500;
+      throw new HttpError(message, statusCode);
+    }
+
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const slug = await resolveSlug(repositoryPath, org, repo);
+    let results;
+    try {
+      results = await githubClient.searchIssues(slug.org, slug.repo, query, { page, perPage });
+    } catch (error: unknown) {
+      if (error instanceof GitHubSearchRateLimitError && error.retryAfterSeconds) {
+        context.res.setHeader('Retry-After', String(error.retryAfterSeconds));
+      }
+      throw error;
+    }
+    const fetchedAt = now().toISOString();
+
+    return {
+      data: {
+        org,
+        repo,
+        ...results,
+        fetchedAt,
+      },
+    };
+  });
+
+  return { read, search };
+}
+
+function parsePositiveIntegerParam(value: string | null, name: string): number | undefined {
+  const trimmed = value?.trim() || '';
+  if (!trimmed) {
+    return undefined;
+  }
+  const parsed = Number(trimmed);
+  if (!Number.isInteger(parsed) || parsed <= 0) {
+    throw new ValidationError(`${name} query parameter must be a positive integer`);
+  }
+  return parsed;
 }
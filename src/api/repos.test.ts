@@ -7,6 +7,7 @@ import { __setBaseHandlerTestOverrides } from './base-handler.js';
 import type { RequestContext } from '../types/http.js';
 import type { RepositoryService } from '../services/index.js';
 import { createRepositoryService, __setRepositoryServiceTestOverrides } from '../services/repository-service.js';
+import { __setGitRepositoryTestOverrides } from '../repositories/git-repository.js';
 
 function createContext(overrides: Partial<RequestContext> = {}): RequestContext {
   const url = new URL('http://localhost/api/repos');
@@ -25,6 +26,8 @@ function createContext(overrides: Partial<RequestContext> = {}): RequestContext
       setHeader,
       getHeader,
       end: mock.fn(),
+      once: mock.fn(),
+      off: mock.fn(),
     } as unknown as RequestContext['res'],
     url,
     method: 'GET',
@@ -39,6 +42,7 @@ describe('createRepoHandlers', () => {
     mock.restoreAll();
     __setBaseHandlerTestOverrides();
     __setRepositoryServiceTestOverrides();
+    __setGitRepositoryTestOverrides();
   });
 
   it('list handler returns repository data', async () => {
@@ -117,16 +121,19 @@ describe('createRepoHandlers', () => {
       readJsonBody: async () => ({
         url: 'git@github.com:vultuk/agentrix.git',
         initCommand: 'npm install',
+        cloneId: 'clone-1',
       }),
     });
 
     await handlers.create(context);
 
     assert.equal(repositoryService.addRepository.mock.calls.length, 1);
-    assert.deepEqual(repositoryService.addRepository.mock.calls[0]?.arguments, [
+    assert.deepEqual(repositoryService.addRepository.mock.calls[0]?.arguments.slice(0, 3), [
       'git@github.com:vultuk/agentrix.git',
       'npm install',
+      'clone-1',
     ]);
+    assert.ok(repositoryService.addRepository.mock.calls[0]?.arguments[3] instanceof AbortSignal);
 
     assert.equal(sendJson.mock.calls.length, 1);
     const call = sendJson.mock.calls[0];
@@ -134,6 +141,71 @@ describe('createRepoHandlers', () => {
     assert.equal(call.arguments[1], 200);
   });
 
+  it('create handler replays the first clone to retries with the same Idempotency-Key', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const repositoryService = {
+      addRepository: mock.fn(async () => ({ data: {}, repo: { org: 'vultuk', repo: 'agentrix' }, cloneId: 'clone-1' })),
+    } as unknown as RepositoryService;
+    const handlers = createRepoHandlers('/workdir', { repositoryService });
+    const request = (url: string) =>
+      createContext({
+        method: 'POST',
+        req: { headers: { 'idempotency-key': 'retry-1' } } as unknown as RequestContext['req'],
+        readJsonBody: async () => ({ url }),
+      });
+
+    const first = request('git@github.com:vultuk/agentrix.git');
+    const retry = request('git@github.com:vultuk/agentrix.git');
+    await handlers.create(first);
+    await handlers.create(retry);
+
+    assert.equal(repositoryService.addRepository.mock.calls.length, 1);
+    assert.deepEqual(
+      sendJson.mock.calls.map((call) => call.arguments.slice(1)),
+      [
+        [200, { data: {}, repo: { org: 'vultuk', repo: 'agentrix' }, cloneId: 'clone-1' }],
+        [200, { data: {}, repo: { org: 'vultuk', repo: 'agentrix' }, cloneId: 'clone-1' }],
+      ]
+    );
+    assert.equal(first.res.getHeader('Idempotent-Replayed'), undefined);
+    assert.equal(retry.res.getHeader('Idempotent-Replayed'), 'true');
+
+    __setBaseHandlerTestOverrides();
+    const reused = request('git@github.com:vultuk/other.git');
+    await handlers.create(reused);
+
+    assert.equal(reused.res.statusCode, 422);
+    assert.equal(repositoryService.addRepository.mock.calls.length, 1);
+  });
+
+  it('cancelClone handler cancels a running clone by id', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const repositoryService = {
+      cancelClone: mock.fn((cloneId: string) => ({ cloneId, cancelled: true })),
+    } as unknown as RepositoryService;
+
+    const handlers = createRepoHandlers('/workdir', { repositoryService });
+    await handlers.cancelClone(
+      createContext({ method: 'POST', readJsonBody: async () => ({ cloneId: 'clone-1' }) })
+    );
+
+    assert.deepEqual(repositoryService.cancelClone.mock.calls[0]?.arguments, ['clone-1']);
+    assert.equal(sendJson.mock.calls[0]?.arguments[1], 200);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { cloneId: 'clone-1', cancelled: true });
+  });
+
+  it('cancelClone handler returns 404 when no clone is running under the id', async () => {
+    const handlers = createRepoHandlers('/workdir', { repositoryService: createRepositoryService('/workdir') });
+    const context = createContext({ method: 'POST', readJsonBody: async () => ({ cloneId: 'missing-clone' }) });
+
+    await handlers.cancelClone(context);
+
+    assert.equal(context.res.statusCode, 404);
+  });
+
   it('delete handler removes repository', async () => {
     const sendJson = mock.fn();
     __setBaseHandlerTestOverrides({ sendJson });
@@ -264,6 +336,28 @@ describe('createRepoHandlers', () => {
     assert.equal(mkdirMock.mock.callCount(), 0);
   });
 
+  it('create handler returns a machine-readable code for malformed repository URLs', async () => {
+    __setRepositoryServiceTestOverrides({
+      refreshRepositoryCache: async () => ({}),
+    });
+
+    const repositoryService = createRepositoryService('/tmp/workdir');
+    const handlers = createRepoHandlers('/tmp/workdir', { repositoryService });
+    const context = createContext({
+      method: 'POST',
+      readJsonBody: async () => ({ url: 'git@github.com:agentrix' }),
+    });
+
+    await handlers.create(context);
+
+    assert.equal(context.res.statusCode, 400);
+    const payloadCall = (context.res.end as ReturnType<typeof mock.fn>).mock.calls[0];
+    assert.ok(payloadCall);
+    const payload = JSON.parse(payloadCall.arguments[0] as string);
+    assert.equal(payload.code, 'invalid_ssh_url');
+    assert.match(payload.error as string, /git@host:org\/repo\.git/);
+  });
+
   it('delete handler rejects traversal identifiers before repository lookup', async () => {
     const statMock = mock.method(fs, 'stat', async () => {
       throw new Error('stat should not run for invalid identifiers');
@@ -295,4 +389,164 @@ describe('createRepoHandlers', () => {
     );
     assert.equal(statMock.mock.callCount(), 0);
   });
+
+  it('switch branch handler returns 409 listing dirty files', async () => {
+    mock.method(fs, 'stat', async () => ({ isDirectory: () => true }));
+    const gitCalls: string[][] = [];
+    __setGitRepositoryTestOverrides({
+      execFileAsync: async (_command, args) => {
+        gitCalls.push(args as string[]);
+        if ((args as string[]).includes('status')) {
+          return { stdout: ' M src/index.ts\n?? notes.md\n', stderr: '' };
+        }
+        return { stdout: '', stderr: '' };
+      },
+    });
+
+    const repositoryService = createRepositoryService('/tmp/workdir');
+    const handlers = createRepoHandlers('/tmp/workdir', { repositoryService });
+    const context = createContext({
+      method: 'POST',
+      readJsonBody: async () => ({ org: 'vultuk', repo: 'agentrix', branch: 'develop' }),
+    });
+
+    await handlers.switchBranch(context);
+
+    assert.equal(context.res.statusCode, 409);
+    const payloadCall = (context.res.end as ReturnType<typeof mock.fn>).mock.calls[0];
+    assert.ok(payloadCall);
+    const payload = JSON.parse(payloadCall.arguments[0] as string);
+    assert.equal(payload.code, 'working_tree_dirty');
+    assert.deepEqual(payload.details, { files: ['src/index.ts', 'notes.md'] });
+    assert.ok(!gitCalls.some((args) => args.includes('switch')));
+  });
+
+  it('read branch handler reports the checked out branch', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const branchInfo = { org: 'vultuk', repo: 'agentrix', branch: 'main', detached: false, head: 'abc123' };
+    const repositoryService = {
+      getCurrentBranch: mock.fn(async () => branchInfo),
+    } as unknown as RepositoryService;
+
+    const handlers = createRepoHandlers('/workdir', { repositoryService });
+    const context = createContext({
+      url: new URL('http://localhost/api/repos/branch?org=vultuk&repo=agentrix'),
+    });
+
+    await handlers.readBranch(context);
+
+    const call = sendJson.mock.calls[0];
+    assert.ok(call);
+    assert.equal(call.arguments[1], 200);
+    assert.deepEqual(call.arguments[2], { data: branchInfo });
+  });
+
+  it('remote handlers list remotes and add a validated one', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const fork = { name: 'fork', fetchUrl: 'https://github.com/me/agentrix.git', pushUrl: 'https://github.com/me/agentrix.git' };
+    const repositoryService = {
+      listRemotes: mock.fn(async (org: string, repo: string) => ({ org, repo, remotes: [fork] })),
+      addRemote: mock.fn(async (org: string, repo: string) => ({ org, repo, remote: fork })),
+    } as unknown as RepositoryService;
+
+    const handlers = createRepoHandlers('/workdir', { repositoryService });
+    await handlers.listRemotes(
+      createContext({ url: new URL('http://localhost/api/repos/remotes?org=vultuk&repo=agentrix') })
+    );
+    await handlers.addRemote(
+      createContext({
+        method: 'POST',
+        readJsonBody: async () => ({ org: 'vultuk', repo: 'agentrix', name: ' fork ', url: fork.fetchUrl }),
+      })
+    );
+    const invalid = createContext({
+      method: 'POST',
+      readJsonBody: async () => ({ org: 'vultuk', repo: 'agentrix', name: '-fork', url: fork.fetchUrl }),
+    });
+    await handlers.addRemote(invalid);
+
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { data: { org: 'vultuk', repo: 'agentrix', remotes: [fork] } });
+    assert.deepEqual((repositoryService.addRemote as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments, [
+      'vultuk',
+      'agentrix',
+      'fork',
+      fork.fetchUrl,
+    ]);
+    assert.deepEqual(sendJson.mock.calls[1]?.arguments[2], { data: { org: 'vultuk', repo: 'agentrix', remote: fork } });
+    assert.equal((repositoryService.addRemote as ReturnType<typeof mock.fn>).mock.callCount(), 1);
+    assert.equal(invalid.res.statusCode, 400);
+  });
+
+  it('config handler returns an allowed key and rejects others', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const readRepositoryConfig = mock.fn(async (_workdir: string, _org: string, _repo: string, key: string) => ({
+      key,
+      value: 'https://github.com/vultuk/agentrix.git',
+    }));
+    __setRepositoryServiceTestOverrides({ readRepositoryConfig });
+
+    const handlers = createRepoHandlers('/workdir');
+    await handlers.readConfig(
+      createContext({ url: new URL('http://localhost/api/repos/config?org=vultuk&repo=agentrix&key=remote.origin.url') })
+    );
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], {
+      data: { org: 'vultuk', repo: 'agentrix', key: 'remote.origin.url', value: 'https://github.com/vultuk/agentrix.git' },
+    });
+
+    // The real repository check runs before git is asked for the value
+    __setRepositoryServiceTestOverrides();
+    const rejected = createContext({
+      url: new URL('http://localhost/api/repos/config?org=vultuk&repo=agentrix&key=http.https://github.com/.extraheader'),
+    });
+    await handlers.readConfig(rejected);
+    assert.equal(rejected.res.statusCode, 400);
+
+    const missingKey = createContext({ url: new URL('http://localhost/api/repos/config?org=vultuk&repo=agentrix') });
+    await handlers.readConfig(missingKey);
+    assert.equal(missingKey.res.statusCode, 400);
+    assert.equal(readRepositoryConfig.mock.callCount(), 1);
+  });
+
+  it('metadata handlers read and replace repository metadata', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+    const repositoryService = {
+      getMetadata: mock.fn(async (org: string, repo: string) => ({ org, repo, metadata: { team: 'web' } })),
+      updateMetadata: mock.fn(async (org: string, repo: string, metadata: Record<string, string>) => ({
+        org,
+        repo,
+        metadata,
+      })),
+    } as unknown as RepositoryService;
+
+    const handlers = createRepoHandlers('/workdir', { repositoryService });
+    await handlers.readMetadata(
+      createContext({ url: new URL('http://localhost/api/repos/meta?org=vultuk&repo=agentrix') })
+    );
+    await handlers.updateMetadata(
+      createContext({
+        method: 'PUT',
+        readJsonBody: async () => ({ org: 'vultuk', repo: 'agentrix', metadata: { team: 'platform', priority: 'high' } }),
+      })
+    );
+
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], {
+      data: { org: 'vultuk', repo: 'agentrix', metadata: { team: 'web' } },
+    });
+    assert.deepEqual(sendJson.mock.calls[1]?.arguments[2], {
+      data: { org: 'vultuk', repo: 'agentrix', metadata: { team: 'platform', priority: 'high' } },
+    });
+
+    const invalid = createContext({
+      method: 'PUT',
+      readJsonBody: async () => ({ org: 'vultuk', repo: 'agentrix', metadata: { team: { name: 'web' } } }),
+    });
+    await handlers.updateMetadata(invalid);
+
+    assert.equal(invalid.res.statusCode, 400);
+    assert.equal((repositoryService.updateMetadata as ReturnType<typeof mock.fn>).mock.callCount(), 1);
+  });
 });
@@ -1,20 +1,54 @@
 import { createRepositoryService, type RepositoryService } from '../services/index.js';
+import type { RepositoryServiceOptions } from '../services/repository-service.js';
 import { handleHeadRequest, sendJson } from '../utils/http.js';
-import { asyncHandler } from '../infrastructure/errors/index.js';
-import { createHandler } from './base-handler.js';
+import { asyncHandler, ValidationError } from '../infrastructure/errors/index.js';
+import { IDEMPOTENCY_KEY_HEADER, IdempotencyCache, MAX_IDEMPOTENCY_KEY_LENGTH } from '../utils/idempotency.js';
+import { createHandler, createQueryHandler } from './base-handler.js';
 import type { RequestContext } from '../types/http.js';
+import type {
+  RepositoryCreateInput,
+  RepositoryMetadataUpdateInput,
+  RepositoryRemoteAddInput,
+} from '../validation/index.js';
 import {
   validateRepositoryCreate,
   validateRepositoryDelete,
+  validateRepositoryMove,
   validateInitCommandUpdate,
+  validateRepositoryMetadataUpdate,
+  validateRepositoryBranchSwitch,
+  validateRepositoryRemoteAdd,
+  validateCloneCancel,
+  extractRepositoryParams,
 } from '../validation/index.js';
 
-export interface RepoHandlersOverrides {
+export interface RepoHandlersOverrides extends RepositoryServiceOptions {
   repositoryService?: RepositoryService;
 }
 
+/**
+ * How long the outcome of a clone made with an `Idempotency-Key` is replayed to retries
+ */
+export const CLONE_IDEMPOTENCY_TTL_MS = 10 * 60_000;
+
+function readIdempotencyKey(context: RequestContext): string | null {
+  const header = context.req.headers[IDEMPOTENCY_KEY_HEADER];
+  const value = (Array.isArray(header) ? header[0] : header)?.trim();
+  if (value === undefined) {
+    return null;
+  }
+  if (!value || value.length > MAX_IDEMPOTENCY_KEY_LENGTH) {
+    throw new ValidationError(`Idempotency-Key must be 1 to ${MAX_IDEMPOTENCY_KEY_LENGTH} characters`);
+  }
+  return value;
+}
+
 export function createRepoHandlers(workdir: string, overrides: RepoHandlersOverrides = {}) {
-  const repositoryService = overrides.repositoryService ?? createRepositoryService(workdir);
+  const { repositoryService: providedRepositoryService, ...serviceOptions } = overrides;
+  const repositoryService = providedRepositoryService ?? createRepositoryService(workdir, serviceOptions);
+  const cloneRequests = new IdempotencyCache<Awaited<ReturnType<RepositoryService['addRepository']>>>(
+    CLONE_IDEMPOTENCY_TTL_MS
+  );
 
   const list = asyncHandler(async (context: RequestContext) => {
     if (context.method === 'HEAD') {
@@ -28,8 +62,46 @@ export function createRepoHandlers(workdir: string, overrides: RepoHandlersOverr
 
   const create = createHandler({
     validator: validateRepositoryCreate,
-    handler: async (input: { url: string; initCommand: string }) => 
-      repositoryService.addRepository(input.url, input.initCommand),
+    handler: async (input: RepositoryCreateInput, context: RequestContext) => {
+      // A client that goes away while its clone is still queued no longer needs it; once git is
+      // running the clone finishes regardless, so a dropped connection never discards a long download
+      const controller = new AbortController();
+      const { res } = context;
+      const onClose = (): void => {
+        if (!res.writableFinished) {
+          controller.abort();
+        }
+      };
+      res.once('close', onClose);
+      try {
+        const key = readIdempotencyKey(context);
+        const addRepository = (signal: AbortSignal) =>
+          repositoryService.addRepository(input.url, input.initCommand, input.cloneId, signal, input.filter);
+        if (!key) {
+          return await addRepository(controller.signal);
+        }
+        // Retries with the key share the first request's clone, and get its result once it has finished
+        const { value, replayed } = await cloneRequests.run(
+          key,
+          JSON.stringify([input.url, input.initCommand, input.cloneId ?? null, input.filter ?? null]),
+          addRepository,
+          controller.signal
+        );
+        if (replayed) {
+          res.setHeader('Idempotent-Replayed', 'true');
+        }
+        return value;
+      } finally {
+        res.off('close', onClose);
+      }
+    },
+  });
+
+  const listClones = createQueryHandler(async () => ({ data: repositoryService.listClones() }));
+
+  const cancelClone = createHandler({
+    validator: validateCloneCancel,
+    handler: async (input: { cloneId: string }) => repositoryService.cancelClone(input.cloneId),
   });
 
   const deleteRepo = createHandler({
@@ -40,6 +112,12 @@ export function createRepoHandlers(workdir: string, overrides: RepoHandlersOverr
     },
   });
 
+  const move = createHandler({
+    validator: validateRepositoryMove,
+    handler: async (input: { org: string; repo: string; newOrg: string; newRepo: string }) =>
+      repositoryService.moveRepository(input.org, input.repo, input.newOrg, input.newRepo),
+  });
+
   const updateInitCommand = createHandler({
     validator: validateInitCommandUpdate,
     handler: async (input: { org: string; repo: string; initCommand: string }) => {
@@ -48,12 +126,75 @@ export function createRepoHandlers(workdir: string, overrides: RepoHandlersOverr
     },
   });
 
+  const readMetadata = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+    const data = await repositoryService.getMetadata(org, repo);
+    return { data };
+  });
+
+  const updateMetadata = createHandler({
+    validator: validateRepositoryMetadataUpdate,
+    handler: async (input: RepositoryMetadataUpdateInput) => {
+      const data = await repositoryService.updateMetadata(input.org, input.repo, input.metadata);
+      return { data };
+    },
+  });
+
+  const readBranch = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+    const data = await repositoryService.getCurrentBranch(org, repo);
+    return { data };
+  });
+
+  const switchBranch = createHandler({
+    validator: validateRepositoryBranchSwitch,
+    handler: async (input: { org: string; repo: string; branch: string }) => {
+      const data = await repositoryService.switchBranch(input.org, input.repo, input.branch);
+      return { data };
+    },
+  });
+
+  const listRemotes = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+    const data = await repositoryService.listRemotes(org, repo);
+    return { data };
+  });
+
+  const readConfig = createQueryHandler(async (context: RequestContext) => {
+    const { searchParams } = context.url;
+    const { org, repo } = extractRepositoryParams(searchParams);
+    const key = searchParams.get('key')?.trim() || '';
+    if (!key) {
+      throw new ValidationError('key is required');
+    }
+    const data = await repositoryService.readConfig(org, repo, key);
+    return { data };
+  });
+
+  const addRemote = createHandler({
+    validator: validateRepositoryRemoteAdd,
+    handler: async (input: RepositoryRemoteAddInput) => {
+      const data = await repositoryService.addRemote(input.org, input.repo, input.name, input.url);
+      return { data };
+    },
+  });
+
   return { 
     list, 
     create, 
     delete: deleteRepo,
     // Deprecated alias for backward compatibility
     destroy: deleteRepo,
-    updateInitCommand 
+    move,
+    updateInitCommand,
+    readMetadata,
+    updateMetadata,
+    readBranch,
+    switchBranch,
+    listRemotes,
+    addRemote,
+    readConfig,
+    listClones,
+    cancelClone,
   };
 }
@@ -4,6 +4,7 @@ import { describe, it, mock } from 'node:test';
 import { createSessionHandlers } from './sessions.js';
 import type { RequestContext } from '../types/http.js';
 import type { SessionService } from '../services/session-service.js';
+import type { ManifestService } from '../services/manifest-service.js';
 
 function createContext(overrides: Partial<RequestContext> = {}): RequestContext {
   const url = new URL('http://localhost/api/sessions');
@@ -119,4 +120,40 @@ describe('createSessionHandlers', () => {
     );
     assert.equal(end.mock.calls.length, 1);
   });
+
+  it('tree handler returns the organisation and repository tree', async () => {
+    const tree = [{ org: 'vultuk', repos: ['agentrix'] }];
+    const sessionService = {
+      listSessions: mock.fn(async () => []),
+      listSessionTree: mock.fn(async () => tree),
+    } as unknown as SessionService;
+
+    const handlers = createSessionHandlers('/workdir', { sessionService });
+    const context = createContext({ url: new URL('http://localhost/api/sessions/tree') });
+    const end = context.res.end as ReturnType<typeof mock.fn>;
+
+    await handlers.tree(context);
+
+    assert.equal(sessionService.listSessions.mock.calls.length, 0);
+    assert.equal(context.res.statusCode, 200);
+    const payload = end.mock.calls[0]?.arguments[0];
+    assert.deepEqual(JSON.parse(payload as string), { tree });
+  });
+
+  it('manifest handler sends the bare manifest as a download', async () => {
+    const manifest = { version: 1, exportedAt: '2024-01-01T00:00:00.000Z', repositories: [] };
+    const manifestService = {
+      exportManifest: mock.fn(async () => manifest),
+    } as unknown as ManifestService;
+
+    const handlers = createSessionHandlers('/workdir', { manifestService });
+    const context = createContext({ url: new URL('http://localhost/api/sessions/manifest') });
+    const end = context.res.end as ReturnType<typeof mock.fn>;
+
+    await handlers.exportManifest(context);
+
+    assert.equal(context.res.statusCode, 200);
+    assert.equal(context.res.getHeader('Content-Disposition'), 'attachment; filename="agentrix-manifest.json"');
+    assert.deepEqual(JSON.parse(end.mock.calls[0]?.arguments[0] as string), manifest);
+  });
 });
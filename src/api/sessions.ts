@@ -1,14 +1,25 @@
 import { createSessionService, type SessionService } from '../services/session-service.js';
+import {
+  createManifestService,
+  type ManifestService,
+  type ManifestServiceOptions,
+} from '../services/manifest-service.js';
 import { handleHeadRequest } from '../utils/http.js';
 import { asyncHandler } from '../infrastructure/errors/index.js';
+import { validateSessionManifest, type SessionManifestInput } from '../validation/index.js';
+import { createHandler } from './base-handler.js';
 import type { RequestContext } from '../types/http.js';
 
-export interface SessionHandlersOverrides {
+export interface SessionHandlersOverrides extends ManifestServiceOptions {
   sessionService?: SessionService;
+  manifestService?: ManifestService;
 }
 
 export function createSessionHandlers(workdir: string, overrides: SessionHandlersOverrides = {}) {
-  const sessionService = overrides.sessionService ?? createSessionService(workdir);
+  const { sessionService: providedSessionService, manifestService: providedManifestService, ...serviceOptions } =
+    overrides;
+  const sessionService = providedSessionService ?? createSessionService(workdir, serviceOptions.repositoryCache);
+  const manifestService = providedManifestService ?? createManifestService(workdir, serviceOptions);
 
   const list = asyncHandler(async (context: RequestContext) => {
     if (context.method === 'HEAD') {
@@ -23,5 +34,40 @@ export function createSessionHandlers(workdir: string, overrides: SessionHandler
     context.res.end(JSON.stringify({ sessions }));
   });
 
-  return { list };
+  const tree = asyncHandler(async (context: RequestContext) => {
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const tree = await sessionService.listSessionTree();
+    context.res.setHeader('Cache-Control', 'no-store');
+    context.res.statusCode = 200;
+    context.res.setHeader('Content-Type', 'application/json; charset=utf-8');
+    context.res.end(JSON.stringify({ tree }));
+  });
+
+  const exportManifest = asyncHandler(async (context: RequestContext) => {
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const manifest = await manifestService.exportManifest();
+    context.res.setHeader('Cache-Control', 'no-store');
+    context.res.setHeader('Content-Disposition', 'attachment; filename="agentrix-manifest.json"');
+    context.res.statusCode = 200;
+    context.res.setHeader('Content-Type', 'application/json; charset=utf-8');
+    context.res.end(JSON.stringify(manifest, null, 2));
+  });
+
+  const importManifest = createHandler({
+    validator: validateSessionManifest,
+    handler: async (input: SessionManifestInput) => {
+      const data = await manifestService.importManifest(input);
+      return { data };
+    },
+  });
+
+  return { list, tree, exportManifest, importManifest };
 }
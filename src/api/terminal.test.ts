@@ -199,4 +199,32 @@ describe('createTerminalHandlers', () => {
     assert.ok(errorCall);
     assert.match(errorCall.arguments[0] as string, /sessionId is required/i);
   });
+
+  it('signal handler maps signal names and rejects unknown ones', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const terminalService = {
+      signalSession: mock.fn(async () => ({ sessionId: 'session-42', signal: 'SIGINT', processGroup: 812 })),
+    } as unknown as TerminalService;
+
+    const handlers = createTerminalHandlers('/workdir', { terminalService });
+
+    await handlers.signal(createContext({ readJsonBody: async () => ({ sessionId: 'session-42', signal: 'INT' }) }));
+    const rejected = createContext({ readJsonBody: async () => ({ sessionId: 'session-42', signal: 'hup' }) });
+    await handlers.signal(rejected);
+    __setBaseHandlerTestOverrides();
+
+    assert.equal(terminalService.signalSession.mock.calls.length, 1);
+    assert.deepEqual(terminalService.signalSession.mock.calls[0]?.arguments[0], {
+      sessionId: 'session-42',
+      signal: 'int',
+    });
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], {
+      sessionId: 'session-42',
+      signal: 'SIGINT',
+      processGroup: 812,
+    });
+    assert.equal(rejected.res.statusCode, 400);
+  });
 });
@@ -1,16 +1,29 @@
 import { createTerminalService, type TerminalService } from '../services/index.js';
-import { createHandler } from './base-handler.js';
-import { validateTerminalOpen, validateTerminalSend, validateTerminalClose } from '../validation/index.js';
-import type { TerminalOpenInput, TerminalSendInput, TerminalCloseInput } from '../validation/index.js';
+import type { TerminalServiceOptions } from '../services/terminal-service.js';
+import { createHandler, createQueryHandler } from './base-handler.js';
+import { handleHeadRequest } from '../utils/http.js';
+import {
+  validateTerminalOpen,
+  validateTerminalSend,
+  validateTerminalClose,
+  validateTerminalHistory,
+  validateTerminalSignal,
+} from '../validation/index.js';
+import type {
+  TerminalOpenInput,
+  TerminalSendInput,
+  TerminalCloseInput,
+  TerminalSignalInput,
+} from '../validation/index.js';
+import type { RequestContext } from '../types/http.js';
 
-export interface TerminalHandlerOptions {
-  mode?: string;
+export interface TerminalHandlerOptions extends TerminalServiceOptions {
   terminalService?: TerminalService;
 }
 
 export function createTerminalHandlers(workdir: string, options: TerminalHandlerOptions = {}) {
-  const { mode, terminalService: providedTerminalService } = options;
-  const terminalService = providedTerminalService ?? createTerminalService(workdir, mode ? { mode } : {});
+  const { terminalService: providedTerminalService, ...serviceOptions } = options;
+  const terminalService = providedTerminalService ?? createTerminalService(workdir, serviceOptions);
 
   const open = createHandler({
     validator: validateTerminalOpen,
@@ -27,5 +40,20 @@ export function createTerminalHandlers(workdir: string, options: TerminalHandler
     handler: async (input: TerminalCloseInput) => terminalService.closeSession(input),
   });
 
-  return { open, send, close };
+  const signal = createHandler({
+    validator: validateTerminalSignal,
+    handler: async (input: TerminalSignalInput) => terminalService.signalSession(input),
+  });
+
+  const history = createQueryHandler(async (context: RequestContext) => {
+    const input = validateTerminalHistory(context.url.searchParams);
+    const result = await terminalService.readHistory(input);
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+    return result;
+  });
+
+  return { open, send, close, signal, history };
 }
@@ -1,10 +1,13 @@
 import assert from 'node:assert/strict';
+import type { IncomingMessage } from 'node:http';
+import { Readable } from 'node:stream';
 import { describe, it, mock } from 'node:test';
 
 import { createWorktreeHandlers } from './worktrees.js';
 import { __setBaseHandlerTestOverrides } from './base-handler.js';
 import type { RequestContext } from '../types/http.js';
 import type { WorktreeService } from '../services/worktree-service.js';
+import { readJsonBody } from '../utils/http.js';
 
 function createContext(overrides: Partial<RequestContext> = {}): RequestContext {
   const url = new URL('http://localhost/api/worktrees');
@@ -79,6 +82,41 @@ describe('createWorktreeHandlers', () => {
     });
   });
 
+  it('create handler answers 200 when an existing worktree is reused', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const reused = {
+      org: 'vultuk',
+      repo: 'agentrix',
+      branch: 'feature/test',
+      worktreePath: '/workdir/vultuk/agentrix/test',
+      created: false,
+    };
+    const worktreeService = {
+      createWorktree: mock.fn(async () => reused),
+    } as unknown as WorktreeService;
+
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+    await handlers.create(
+      createContext({
+        readJsonBody: async () => ({ org: 'vultuk', repo: 'agentrix', branch: 'feature/test', ifExists: 'reuse' }),
+      })
+    );
+    const invalid = createContext({
+      readJsonBody: async () => ({ org: 'vultuk', repo: 'agentrix', branch: 'feature/test', ifExists: 'replace' }),
+    });
+    await handlers.create(invalid);
+    __setBaseHandlerTestOverrides();
+
+    assert.equal(worktreeService.createWorktree.mock.calls.length, 1);
+    assert.equal(worktreeService.createWorktree.mock.calls[0]?.arguments[0]?.ifExists, 'reuse');
+    assert.equal(sendJson.mock.calls[0]?.arguments[1], 200);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], reused);
+    assert.equal(sendJson.mock.calls.length, 1);
+    assert.equal(invalid.res.statusCode, 400);
+  });
+
   it('delete handler validates payload and returns result', async () => {
     const sendJson = mock.fn();
     __setBaseHandlerTestOverrides({ sendJson });
@@ -115,6 +153,155 @@ describe('createWorktreeHandlers', () => {
     assert.deepEqual(call.arguments[2], { data: { removed: true } });
   });
 
+  it('rename handler validates payload and returns renamed worktree', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const renamed = {
+      org: 'vultuk',
+      repo: 'agentrix',
+      branch: 'feature/renamed',
+      previousBranch: 'feature/test',
+      worktreePath: '/workdir/vultuk/agentrix/renamed',
+    };
+    const worktreeService = {
+      createWorktree: mock.fn(),
+      deleteWorktree: mock.fn(),
+      renameWorktree: mock.fn(async () => renamed),
+    } as unknown as WorktreeService;
+
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+
+    const context = createContext({
+      readJsonBody: async () => ({
+        org: 'vultuk',
+        repo: 'agentrix',
+        branch: 'feature/test',
+        newBranch: 'feature/renamed',
+      }),
+    });
+
+    await handlers.rename(context);
+    __setBaseHandlerTestOverrides();
+
+    assert.equal(worktreeService.renameWorktree.mock.calls.length, 1);
+    assert.deepEqual(worktreeService.renameWorktree.mock.calls[0]?.arguments[0], {
+      org: 'vultuk',
+      repo: 'agentrix',
+      branch: 'feature/test',
+      newBranch: 'feature/renamed',
+    });
+
+    const call = sendJson.mock.calls[0];
+    assert.ok(call);
+    assert.equal(call.arguments[1], 200);
+    assert.deepEqual(call.arguments[2], { data: renamed });
+  });
+
+  it('rename handler rejects new branch names git would refuse', async () => {
+    const worktreeService = {
+      renameWorktree: mock.fn(),
+    } as unknown as WorktreeService;
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+
+    for (const newBranch of ['--force', 'feature..x', 'feature/lock.lock']) {
+      const context = createContext({
+        readJsonBody: async () => ({ org: 'vultuk', repo: 'agentrix', branch: 'feature/test', newBranch }),
+      });
+
+      await handlers.rename(context);
+
+      assert.equal(context.res.statusCode, 400, newBranch);
+    }
+    assert.equal((worktreeService.renameWorktree as ReturnType<typeof mock.fn>).mock.calls.length, 0);
+  });
+
+  it('commit handler passes message, paths and signing to the service', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const committed = {
+      org: 'vultuk',
+      repo: 'agentrix',
+      branch: 'feature/test',
+      sha: 'abc123',
+      files: ['src/index.ts'],
+    };
+    const worktreeService = {
+      commitWorktree: mock.fn(async () => committed),
+    } as unknown as WorktreeService;
+
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+
+    const context = createContext({
+      readJsonBody: async () => ({
+        org: 'vultuk',
+        repo: 'agentrix',
+        branch: 'feature/test',
+        message: ' Update index ',
+        paths: ['src/index.ts'],
+        sign: true,
+      }),
+    });
+
+    await handlers.commit(context);
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual((worktreeService.commitWorktree as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0], {
+      org: 'vultuk',
+      repo: 'agentrix',
+      branch: 'feature/test',
+      message: 'Update index',
+      paths: ['src/index.ts'],
+      sign: true,
+    });
+
+    const call = sendJson.mock.calls[0];
+    assert.ok(call);
+    assert.equal(call.arguments[1], 200);
+    assert.deepEqual(call.arguments[2], { data: committed });
+  });
+
+  it('push handler forwards the force-with-lease flag', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const pushed = {
+      org: 'vultuk',
+      repo: 'agentrix',
+      branch: 'feature/test',
+      remote: 'origin',
+      upstream: 'origin/feature/test',
+      sha: 'abc123',
+      forced: true,
+    };
+    const worktreeService = {
+      pushWorktree: mock.fn(async () => pushed),
+    } as unknown as WorktreeService;
+
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+
+    await handlers.push(
+      createContext({
+        readJsonBody: async () => ({
+          org: 'vultuk',
+          repo: 'agentrix',
+          branch: 'feature/test',
+          forceWithLease: true,
+        }),
+      })
+    );
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual((worktreeService.pushWorktree as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0], {
+      org: 'vultuk',
+      repo: 'agentrix',
+      branch: 'feature/test',
+      forceWithLease: true,
+    });
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { data: pushed });
+  });
+
   it('create handler returns validation errors', async () => {
     const worktreeService = {
       createWorktree: mock.fn(),
@@ -144,6 +331,242 @@ describe('createWorktreeHandlers', () => {
     assert.match(errorCall.arguments[0] as string, /Missing required field/i);
   });
 
+  it('create handler previews the worktree when dryRun is set', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const preview = {
+      org: 'vultuk',
+      repo: 'agentrix',
+      branch: 'feature/test',
+      worktreePath: '/workdir/vultuk/agentrix/test',
+      branchExists: false,
+      created: false,
+    };
+    const worktreeService = {
+      createWorktree: mock.fn(),
+      previewWorktree: mock.fn(async () => preview),
+    } as unknown as WorktreeService;
+
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+    const context = createContext({
+      readJsonBody: async () => ({
+        org: 'vultuk',
+        repo: 'agentrix',
+        branch: 'feature/test',
+        dryRun: true,
+      }),
+    });
+
+    await handlers.create(context);
+    __setBaseHandlerTestOverrides();
+
+    assert.equal(worktreeService.createWorktree.mock.calls.length, 0);
+    assert.equal(worktreeService.previewWorktree.mock.calls.length, 1);
+    const call = sendJson.mock.calls[0];
+    assert.ok(call);
+    assert.equal(call.arguments[1], 200);
+    assert.deepEqual(call.arguments[2], preview);
+  });
+
+  it('usage handler forwards refresh and disables caching', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const usage = {
+      org: 'acme',
+      repo: 'demo',
+      worktrees: [{ branch: 'main', path: '/workdir/acme/demo/repository', primary: true, bytes: 4096, files: 3 }],
+      totalBytes: 4096,
+      measuredAt: '2024-05-01T12:00:00.000Z',
+    };
+    const worktreeService = {
+      getWorktreeUsage: mock.fn(async () => usage),
+    } as unknown as WorktreeService;
+
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+    const context = createContext({
+      method: 'GET',
+      url: new URL('http://localhost/api/worktrees/usage?org=acme&repo=demo&refresh=true'),
+    });
+
+    await handlers.usage(context);
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual((worktreeService.getWorktreeUsage as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments, [
+      'acme',
+      'demo',
+      { refresh: true },
+    ]);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { data: usage });
+    assert.deepEqual((context.res.setHeader as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments, [
+      'Cache-Control',
+      'no-store',
+    ]);
+  });
+
+  it('activity handler forwards the caps and rejects invalid ones', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const activity = { org: 'acme', repo: 'demo', commits: [] };
+    const worktreeService = {
+      getWorktreeActivity: mock.fn(async () => activity),
+    } as unknown as WorktreeService;
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+
+    await handlers.activity(
+      createContext({
+        method: 'GET',
+        url: new URL('http://localhost/api/worktrees/activity?org=acme&repo=demo&perWorktree=3&limit=20'),
+      })
+    );
+    const invalid = createContext({
+      method: 'GET',
+      url: new URL('http://localhost/api/worktrees/activity?org=acme&repo=demo&limit=0'),
+    });
+    await handlers.activity(invalid);
+    __setBaseHandlerTestOverrides();
+
+    const getWorktreeActivity = worktreeService.getWorktreeActivity as ReturnType<typeof mock.fn>;
+    assert.equal(getWorktreeActivity.mock.calls.length, 1);
+    assert.deepEqual(getWorktreeActivity.mock.calls[0]?.arguments, ['acme', 'demo', { perWorktree: 3, limit: 20 }]);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { data: activity });
+    assert.equal(sendJson.mock.calls.length, 1);
+    assert.equal(invalid.res.statusCode, 400);
+  });
+
+  it('upstream handlers read and set the tracked branch', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const upstream = {
+      org: 'acme',
+      repo: 'demo',
+      branch: 'feature/login',
+      upstream: 'origin/feature/login',
+      remote: 'origin',
+      remoteBranch: 'feature/login',
+    };
+    const worktreeService = {
+      getUpstream: mock.fn(async () => upstream),
+      setUpstream: mock.fn(async () => upstream),
+    } as unknown as WorktreeService;
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+
+    await handlers.readUpstream(
+      createContext({
+        method: 'GET',
+        url: new URL('http://localhost/api/worktrees/upstream?org=acme&repo=demo&branch=feature/login'),
+      })
+    );
+    await handlers.setUpstream(
+      createContext({
+        method: 'PUT',
+        readJsonBody: async () => ({ org: 'acme', repo: 'demo', branch: 'feature/login', remote: 'origin' }),
+      })
+    );
+    const invalid = createContext({
+      method: 'PUT',
+      readJsonBody: async () => ({ org: 'acme', repo: 'demo', branch: 'feature/login', remote: 'origin', remoteBranch: 'a..b' }),
+    });
+    await handlers.setUpstream(invalid);
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual((worktreeService.getUpstream as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments, [
+      { org: 'acme', repo: 'demo', branch: 'feature/login' },
+    ]);
+    const setUpstream = worktreeService.setUpstream as ReturnType<typeof mock.fn>;
+    assert.equal(setUpstream.mock.calls.length, 1);
+    assert.deepEqual(setUpstream.mock.calls[0]?.arguments, [
+      { org: 'acme', repo: 'demo', branch: 'feature/login', remote: 'origin' },
+    ]);
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { data: upstream });
+    assert.deepEqual(sendJson.mock.calls[1]?.arguments[2], { data: upstream });
+    assert.equal(invalid.res.statusCode, 400);
+  });
+
+  it('setup handler validates the worktree and returns the run result', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const run = { org: 'acme', repo: 'demo', branch: 'feature/login', exitCode: 0, output: 'ok\n' };
+    const worktreeService = {
+      setupWorktree: mock.fn(async () => run),
+    } as unknown as WorktreeService;
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+
+    await handlers.setup(
+      createContext({ readJsonBody: async () => ({ org: 'acme', repo: 'demo', branch: 'feature/login' }) })
+    );
+    const invalid = createContext({ readJsonBody: async () => ({ org: 'acme', repo: 'demo' }) });
+    await handlers.setup(invalid);
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual((worktreeService.setupWorktree as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0], {
+      org: 'acme',
+      repo: 'demo',
+      branch: 'feature/login',
+    });
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { data: run });
+    assert.equal(invalid.res.statusCode, 400);
+    assert.equal((worktreeService.setupWorktree as ReturnType<typeof mock.fn>).mock.callCount(), 1);
+  });
+
+  it('reset handler requires explicit confirmation', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const reset = { org: 'acme', repo: 'demo', branch: 'feature/login', target: 'origin/main' };
+    const worktreeService = {
+      resetWorktree: mock.fn(async () => reset),
+    } as unknown as WorktreeService;
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+    const body = { org: 'acme', repo: 'demo', branch: 'feature/login', ref: ' origin/main ' };
+
+    await handlers.reset(createContext({ readJsonBody: async () => ({ ...body, confirm: true }) }));
+    const unconfirmed = createContext({ readJsonBody: async () => ({ ...body, confirm: 'yes' }) });
+    await handlers.reset(unconfirmed);
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual((worktreeService.resetWorktree as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0], {
+      org: 'acme',
+      repo: 'demo',
+      branch: 'feature/login',
+      ref: 'origin/main',
+    });
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { data: reset });
+    assert.equal(unconfirmed.res.statusCode, 400);
+    assert.equal((worktreeService.resetWorktree as ReturnType<typeof mock.fn>).mock.callCount(), 1);
+  });
+
+  it('replace handler requires explicit confirmation', async () => {
+    const sendJson = mock.fn();
+    __setBaseHandlerTestOverrides({ sendJson });
+
+    const replaced = { org: 'acme', repo: 'demo', branch: 'feature/login', replaced: true, sha: 'abc1234' };
+    const worktreeService = {
+      replaceWorktree: mock.fn(async () => replaced),
+    } as unknown as WorktreeService;
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+    const body = { org: 'acme', repo: 'demo', branch: 'feature/login', base: ' main ' };
+
+    await handlers.replace(createContext({ method: 'PUT', readJsonBody: async () => ({ ...body, confirm: true }) }));
+    const unconfirmed = createContext({ method: 'PUT', readJsonBody: async () => body });
+    await handlers.replace(unconfirmed);
+    __setBaseHandlerTestOverrides();
+
+    assert.deepEqual((worktreeService.replaceWorktree as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0], {
+      org: 'acme',
+      repo: 'demo',
+      branch: 'feature/login',
+      base: 'main',
+    });
+    assert.deepEqual(sendJson.mock.calls[0]?.arguments[2], { data: replaced });
+    assert.equal(unconfirmed.res.statusCode, 400);
+    assert.equal((worktreeService.replaceWorktree as ReturnType<typeof mock.fn>).mock.callCount(), 1);
+  });
+
   it('aliases upsert and destroy map to create/delete', () => {
     const worktreeService = {
       createWorktree: mock.fn(),
@@ -155,5 +578,22 @@ describe('createWorktreeHandlers', () => {
     assert.equal(handlers.upsert, handlers.create);
     assert.equal(handlers.destroy, handlers.delete);
   });
+
+  it('create handler answers malformed JSON with the standard error envelope', async () => {
+    const worktreeService = { createWorktree: mock.fn() } as unknown as WorktreeService;
+    const handlers = createWorktreeHandlers('/workdir', {}, {}, { worktreeService });
+    const req = Readable.from([Buffer.from('{"org": "acme",\n  "repo" "demo"}')]) as unknown as IncomingMessage;
+    const context = createContext({ readJsonBody: () => readJsonBody(req) });
+
+    await handlers.create(context);
+
+    assert.equal(context.res.statusCode, 400);
+    const endCall = (context.res.end as ReturnType<typeof mock.fn>).mock.calls[0];
+    assert.ok(endCall);
+    const body = JSON.parse(endCall.arguments[0] as string);
+    assert.equal(body.code, 'invalid_body');
+    assert.match(body.error, /^Invalid JSON payload at line 2, column 10/);
+    assert.equal(worktreeService.createWorktree.mock.calls.length, 0);
+  });
 });
 
@@ -1,12 +1,61 @@
 import { createWorktreeService, type WorktreeService } from '../services/index.js';
-import { createHandler } from './base-handler.js';
-import { validateWorktreeCreate, validateWorktreeDelete } from '../validation/index.js';
-import type { WorktreeCreateInput, WorktreeDeleteInput } from '../validation/index.js';
+import { createHandler, createQueryHandler } from './base-handler.js';
+import { handleHeadRequest } from '../utils/http.js';
+import { streamProcessOutput, wantsOutputStream } from '../utils/process-output.js';
+import {
+  extractRepositoryParams,
+  extractWorktreeParams,
+  validateWorktreeCreate,
+  validateWorktreeBatchCreate,
+  validateWorktreeDelete,
+  validateWorktreeReplace,
+  validateWorktreeRename,
+  validateWorktreeCommit,
+  validateWorktreePush,
+  validateWorktreePullRequestDraft,
+  validateWorktreeSetup,
+  validateWorktreeReset,
+  validateWorktreeCherryPick,
+  validateWorktreeCherryPickAbort,
+  validateWorktreeStash,
+  validateWorktreeUpstream,
+} from '../validation/index.js';
+import type {
+  WorktreeCreateInput,
+  WorktreeBatchCreateInput,
+  WorktreeDeleteInput,
+  WorktreeReplaceInput,
+  WorktreeRenameInput,
+  WorktreeCommitInput,
+  WorktreePushInput,
+  WorktreePullRequestDraftInput,
+  WorktreeSetupInput,
+  WorktreeResetInput,
+  WorktreeCherryPickInput,
+  WorktreeCherryPickAbortInput,
+  WorktreeStashInput,
+  WorktreeUpstreamInput,
+} from '../validation/index.js';
+import type { RequestContext } from '../types/http.js';
+import { ValidationError } from '../infrastructure/errors/index.js';
+import { MAX_ACTIVITY_LIMIT, MAX_ACTIVITY_PER_WORKTREE } from '../core/git.js';
 
 export interface WorktreeHandlerOverrides {
   worktreeService?: WorktreeService;
 }
 
+function parseActivityCount(value: string | null, name: string, max: number): number | undefined {
+  const trimmed = value?.trim() || '';
+  if (!trimmed) {
+    return undefined;
+  }
+  const parsed = Number(trimmed);
+  if (!Number.isInteger(parsed) || parsed <= 0 || parsed > max) {
+    throw new ValidationError(`${name} query parameter must be an integer from 1 to ${max}`);
+  }
+  return parsed;
+}
+
 export function createWorktreeHandlers(
   workdir: string,
   branchNameGenerator: unknown,
@@ -18,8 +67,46 @@ export function createWorktreeHandlers(
 
   const createWorktree = createHandler({
     validator: validateWorktreeCreate,
-    handler: async (input: WorktreeCreateInput) => worktreeService.createWorktree(input),
-    successCode: 202,
+    handler: async (input: WorktreeCreateInput) =>
+      input.dryRun ? worktreeService.previewWorktree(input) : worktreeService.createWorktree(input),
+    successCode: (result) => ('created' in result ? 200 : 202),
+  });
+
+  const createWorktreeBatch = createHandler({
+    validator: validateWorktreeBatchCreate,
+    handler: async (input: WorktreeBatchCreateInput) => {
+      const data = await worktreeService.createWorktreeBatch(input);
+      return { data };
+    },
+  });
+
+  const listWorktrees = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+    const data = await worktreeService.listWorktrees(org, repo);
+    return { data };
+  });
+
+  const worktreeUsage = createQueryHandler(async (context: RequestContext) => {
+    const { org, repo } = extractRepositoryParams(context.url.searchParams);
+    const refresh = context.url.searchParams.get('refresh')?.trim().toLowerCase() === 'true';
+    const data = await worktreeService.getWorktreeUsage(org, repo, { refresh });
+
+    context.res.setHeader('Cache-Control', 'no-store');
+    return { data };
+  });
+
+  const worktreeActivity = createQueryHandler(async (context: RequestContext) => {
+    const { searchParams } = context.url;
+    const { org, repo } = extractRepositoryParams(searchParams);
+    const perWorktree = parseActivityCount(searchParams.get('perWorktree'), 'perWorktree', MAX_ACTIVITY_PER_WORKTREE);
+    const limit = parseActivityCount(searchParams.get('limit'), 'limit', MAX_ACTIVITY_LIMIT);
+    const data = await worktreeService.getWorktreeActivity(org, repo, {
+      ...(perWorktree !== undefined ? { perWorktree } : {}),
+      ...(limit !== undefined ? { limit } : {}),
+    });
+
+    context.res.setHeader('Cache-Control', 'no-store');
+    return { data };
   });
 
   const deleteWorktree = createHandler({
@@ -30,9 +117,162 @@ export function createWorktreeHandlers(
     },
   });
 
+  const renameWorktree = createHandler({
+    validator: validateWorktreeRename,
+    handler: async (input: WorktreeRenameInput) => {
+      const data = await worktreeService.renameWorktree(input);
+      return { data };
+    },
+  });
+
+  const commitWorktree = createHandler({
+    validator: validateWorktreeCommit,
+    handler: async (input: WorktreeCommitInput, context: RequestContext) => {
+      if (wantsOutputStream(context.req)) {
+        await streamProcessOutput(context.res, (output) => worktreeService.commitWorktree(input, output));
+        return undefined;
+      }
+      const data = await worktreeService.commitWorktree(input);
+      return { data };
+    },
+  });
+
+  const pushWorktree = createHandler({
+    validator: validateWorktreePush,
+    handler: async (input: WorktreePushInput, context: RequestContext) => {
+      if (wantsOutputStream(context.req)) {
+        await streamProcessOutput(context.res, (output) => worktreeService.pushWorktree(input, output));
+        return undefined;
+      }
+      const data = await worktreeService.pushWorktree(input);
+      return { data };
+    },
+  });
+
+  const createPullRequestDraft = createHandler({
+    validator: validateWorktreePullRequestDraft,
+    handler: async (input: WorktreePullRequestDraftInput) => {
+      const data = await worktreeService.createPullRequestDraft(input);
+      return { data };
+    },
+    successCode: 201,
+  });
+
+  const getPullRequest = createQueryHandler(async (context: RequestContext) => {
+    const params = extractWorktreeParams(context.url.searchParams);
+
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const data = await worktreeService.getPullRequest(params);
+    return { data };
+  });
+
+  const checkBranch = createQueryHandler(async (context: RequestContext) => {
+    const params = extractWorktreeParams(context.url.searchParams);
+
+    if (context.method === 'HEAD') {
+      handleHeadRequest(context.res);
+      return;
+    }
+
+    const data = await worktreeService.checkBranch(params);
+    return { data };
+  });
+
+  const setupWorktree = createHandler({
+    validator: validateWorktreeSetup,
+    handler: async (input: WorktreeSetupInput, context: RequestContext) => {
+      if (wantsOutputStream(context.req)) {
+        await streamProcessOutput(
+          context.res,
+          (output) => worktreeService.setupWorktree(input, output),
+          (result) => result.exitCode
+        );
+        return undefined;
+      }
+      const data = await worktreeService.setupWorktree(input);
+      return { data };
+    },
+  });
+
+  const replaceWorktree = createHandler({
+    validator: validateWorktreeReplace,
+    handler: async (input: WorktreeReplaceInput) => {
+      const data = await worktreeService.replaceWorktree(input);
+      return { data };
+    },
+  });
+
+  const resetWorktree = createHandler({
+    validator: validateWorktreeReset,
+    handler: async (input: WorktreeResetInput) => {
+      const data = await worktreeService.resetWorktree(input);
+      return { data };
+    },
+  });
+
+  const cherryPickWorktree = createHandler({
+    validator: validateWorktreeCherryPick,
+    handler: async (input: WorktreeCherryPickInput) => {
+      const data = await worktreeService.cherryPickWorktree(input);
+      return { data };
+    },
+  });
+
+  const abortCherryPick = createHandler({
+    validator: validateWorktreeCherryPickAbort,
+    handler: async (input: WorktreeCherryPickAbortInput) => {
+      const data = await worktreeService.abortCherryPick(input);
+      return { data };
+    },
+  });
+
+  const stashWorktree = createHandler({
+    validator: validateWorktreeStash,
+    handler: async (input: WorktreeStashInput) => {
+      const data = await worktreeService.stashWorktree(input);
+      return { data };
+    },
+  });
+
+  const readUpstream = createQueryHandler(async (context: RequestContext) => {
+    const params = extractWorktreeParams(context.url.searchParams);
+    const data = await worktreeService.getUpstream(params);
+    return { data };
+  });
+
+  const setUpstream = createHandler({
+    validator: validateWorktreeUpstream,
+    handler: async (input: WorktreeUpstreamInput) => {
+      const data = await worktreeService.setUpstream(input);
+      return { data };
+    },
+  });
+
   return { 
+    list: listWorktrees,
     create: createWorktree,
+    createBatch: createWorktreeBatch,
+    usage: worktreeUsage,
+    activity: worktreeActivity,
     delete: deleteWorktree,
+    replace: replaceWorktree,
+    rename: renameWorktree,
+    commit: commitWorktree,
+    push: pushWorktree,
+    pullRequestDraft: createPullRequestDraft,
+    pullRequest: getPullRequest,
+    checkBranch,
+    setup: setupWorktree,
+    reset: resetWorktree,
+    cherryPick: cherryPickWorktree,
+    cherryPickAbort: abortCherryPick,
+    stash: stashWorktree,
+    readUpstream,
+    setUpstream,
     // Deprecated aliases for backward compatibility
     upsert: createWorktree,
     destroy: deleteWorktree,
@@ -2,10 +2,11 @@
 
 import { parseArgs } from './cli/arg-parser.js';
 import { loadConfig, saveConfig } from './cli/config.js';
-import { resolveConfig, validateNgrokConfig, buildConfigToSave } from './cli/config-resolver.js';
+import { resolveConfig, validateNgrokConfig, validateCorsConfig, buildConfigToSave } from './cli/config-resolver.js';
 import { printHelp, printVersion } from './cli/help.js';
 import { handlePlansCommand } from './cli/plans-command.js';
 import { startAppServer } from './cli/server-starter.js';
+import { initLogging, resolveLogLevel } from './infrastructure/logging/index.js';
 
 async function main(argv: string[] = process.argv.slice(2)): Promise<void> {
   // Handle plans subcommand
@@ -26,6 +27,9 @@ async function main(argv: string[] = process.argv.slice(2)): Promise<void> {
     return;
   }
 
+  // Apply the log level before anything else writes to the console
+  initLogging(resolveLogLevel(args));
+
   // Handle help and version flags
   if (args.help) {
     printHelp();
@@ -44,6 +48,7 @@ async function main(argv: string[] = process.argv.slice(2)): Promise<void> {
   // Validate configuration
   try {
     validateNgrokConfig(config);
+    validateCorsConfig(config);
   } catch (err) {
     const error = err as { message?: string };
     process.stderr.write(`${error?.message || String(err)}\n`);
@@ -18,12 +18,16 @@ describe('parseArgs', () => {
     assert.deepEqual(parsed._provided, {
       port: false,
       host: false,
+      local: false,
       ui: false,
       workdir: false,
       password: false,
       cookieSecure: false,
       defaultBranch: false,
       showPassword: false,
+      printStartupJson: false,
+      quiet: false,
+      verbose: false,
       codexCommand: false,
       claudeCommand: false,
       cursorCommand: false,
@@ -33,6 +37,39 @@ describe('parseArgs', () => {
       ngrokDomain: false,
       openaiApiKey: false,
       terminalSessionMode: false,
+      terminalEnv: false,
+      gitConfig: false,
+      allowedUrlSchemes: false,
+      gitConcurrency: false,
+      maxConcurrentClones: false,
+      gitBackend: false,
+      repoCacheTtlMs: false,
+      trustProxy: false,
+      githubTimeoutMs: false,
+      githubApiVersion: false,
+      githubWebhookSecret: false,
+      gitAuthorName: false,
+      gitAuthorEmail: false,
+      cloneLayout: false,
+      defaultOrg: false,
+      defaultBase: false,
+      maxWorktreesPerRepo: false,
+      gitOutputLimitBytes: false,
+      setupCommand: false,
+      rateLimit: false,
+      maxConnections: false,
+      readOnly: false,
+      verifyGithubToken: false,
+      traceGit: false,
+      signCommits: false,
+      confirmDangerousTerminals: false,
+      slowRequestMs: false,
+      corsOrigins: false,
+      corsAllowCredentials: false,
+      corsMaxAgeSeconds: false,
+      landingPage: false,
+      basePath: false,
+      features: false,
       save: false,
     });
   });
@@ -54,6 +91,7 @@ describe('parseArgs', () => {
       '--terminal-session-mode',
       'tmux',
       '--show-password',
+      '--print-startup-json',
     ]);
 
     assert.equal(parsed.port, 8080);
@@ -72,6 +110,34 @@ describe('parseArgs', () => {
     assert.equal(parsed._provided.cookieSecure, true);
     assert.equal(parsed._provided.terminalSessionMode, true);
     assert.equal(parsed._provided.showPassword, true);
+    assert.equal(parsed.printStartupJson, true);
+  });
+
+  it('collects repeated terminal environment assignments', () => {
+    const parsed = parseArgs(['--terminal-env', 'API_KEY=a=b', '--terminal-env', 'EMPTY=']);
+    assert.deepEqual(parsed.terminalEnv, { API_KEY: 'a=b', EMPTY: '' });
+    assert.equal(parsed._provided.terminalEnv, true);
+
+    assert.throws(() => parseArgs(['--terminal-env', 'NO_EQUALS']), /KEY=VALUE/);
+    assert.throws(() => parseArgs(['--terminal-env', '1BAD=value']), /KEY=VALUE/);
+  });
+
+  it('collects repeated git config assignments', () => {
+    assert.equal(parseArgs([]).gitConfig, null);
+    const parsed = parseArgs([
+      '--git-config',
+      'http.proxy=http://proxy.internal:3128',
+      '--git-config',
+      'http.https://git.corp.example.sslVerify=false',
+    ]);
+    assert.deepEqual(parsed.gitConfig, {
+      'http.proxy': 'http://proxy.internal:3128',
+      'http.https://git.corp.example.sslVerify': 'false',
+    });
+    assert.equal(parsed._provided.gitConfig, true);
+
+    assert.throws(() => parseArgs(['--git-config', 'proxy=value']), /KEY=VALUE/);
+    assert.throws(() => parseArgs(['--git-config', 'http.proxy']), /KEY=VALUE/);
   });
 
   it('supports force-tmux and no-tmux shortcuts', () => {
@@ -82,6 +148,12 @@ describe('parseArgs', () => {
     assert.equal(ptyArgs.terminalSessionMode, 'pty');
   });
 
+  it('accepts --frontend-dir as an alias for --ui', () => {
+    const args = parseArgs(['--frontend-dir', './custom-ui']);
+    assert.equal(args.ui, './custom-ui');
+    assert.equal(args._provided['ui'], true);
+  });
+
   it('throws when conflicting terminal session modes are provided', () => {
     assert.throws(() => parseArgs(['--terminal-session-mode', 'tmux', '--no-tmux']));
   });
@@ -90,6 +162,7 @@ describe('parseArgs', () => {
     assert.throws(() => parseArgs(['--port', 'not-a-number']));
     assert.throws(() => parseArgs(['--port', '-1']));
     assert.throws(() => parseArgs(['--port', '70000']));
+    assert.equal(parseArgs(['--port', '0']).port, 0);
   });
 
   it('rejects invalid cookie secure values', () => {
@@ -101,6 +174,246 @@ describe('parseArgs', () => {
     assert.equal(parsed.terminalSessionMode, 'pty');
   });
 
+  it('parses comma separated URL scheme allowlists', () => {
+    const parsed = parseArgs(['--allowed-url-schemes', 'https, ssh,file']);
+    assert.deepEqual(parsed.allowedUrlSchemes, ['https', 'ssh', 'file']);
+    assert.equal(parsed._provided.allowedUrlSchemes, true);
+    assert.throws(() => parseArgs(['--allowed-url-schemes', ' , ']));
+  });
+
+  it('parses the git concurrency bound', () => {
+    assert.equal(parseArgs(['--git-concurrency', '4']).gitConcurrency, 4);
+    assert.throws(() => parseArgs(['--git-concurrency', '0']));
+    assert.throws(() => parseArgs(['--git-concurrency', 'many']));
+  });
+
+  it('parses the clone concurrency limit', () => {
+    assert.equal(parseArgs([]).maxConcurrentClones, null);
+    assert.equal(parseArgs(['--max-concurrent-clones', '3']).maxConcurrentClones, 3);
+    assert.throws(() => parseArgs(['--max-concurrent-clones', '0']));
+  });
+
+  it('parses the clone backend', () => {
+    assert.equal(parseArgs([]).gitBackend, null);
+    assert.equal(parseArgs(['--git-backend', 'LibGit2']).gitBackend, 'libgit2');
+    assert.throws(() => parseArgs(['--git-backend', 'jgit']));
+  });
+
+  it('parses the repository cache ttl, allowing zero', () => {
+    assert.equal(parseArgs([]).repoCacheTtlMs, null);
+    assert.equal(parseArgs(['--repo-cache-ttl', '5000']).repoCacheTtlMs, 5000);
+    assert.equal(parseArgs(['--repo-cache-ttl', '0']).repoCacheTtlMs, 0);
+    assert.throws(() => parseArgs(['--repo-cache-ttl', '-1']));
+    assert.throws(() => parseArgs(['--repo-cache-ttl', 'soon']));
+  });
+
+  it('enables proxy trust only when requested', () => {
+    assert.equal(parseArgs([]).trustProxy, false);
+    const parsed = parseArgs(['--trust-proxy']);
+    assert.equal(parsed.trustProxy, true);
+    assert.equal(parsed._provided.trustProxy, true);
+  });
+
+  it('parses the GitHub CLI timeout in milliseconds', () => {
+    const parsed = parseArgs(['--github-timeout', '2500']);
+    assert.equal(parsed.githubTimeoutMs, 2500);
+    assert.equal(parsed._provided.githubTimeoutMs, true);
+    assert.throws(() => parseArgs(['--github-timeout', '-1']));
+  });
+
+  it('parses the git author identity for new worktrees', () => {
+    const parsed = parseArgs(['--git-author-name', 'Agent Bot', '--git-author-email', 'agent@example.com']);
+    assert.equal(parsed.gitAuthorName, 'Agent Bot');
+    assert.equal(parsed.gitAuthorEmail, 'agent@example.com');
+    assert.equal(parsed._provided.gitAuthorName, true);
+    assert.equal(parsed._provided.gitAuthorEmail, true);
+  });
+
+  it('parses and validates the clone layout template', () => {
+    assert.equal(parseArgs(['--clone-layout', 'repos/{org}/{repo}']).cloneLayout, 'repos/{org}/{repo}');
+    assert.equal(parseArgs(['--clone-layout', ' {workspace}/{repository} ']).cloneLayout, '{workspace}/{repository}');
+    assert.throws(() => parseArgs(['--clone-layout', '{org}']), /must contain \{repo\}/);
+    assert.throws(() => parseArgs(['--clone-layout', '{repo}']), /must contain \{org\}/);
+    assert.throws(() => parseArgs(['--clone-layout', '../{repo}']));
+  });
+
+  it('parses and validates the default organization', () => {
+    assert.equal(parseArgs([]).defaultOrg, null);
+    const parsed = parseArgs(['--default-org', ' platform ']);
+    assert.equal(parsed.defaultOrg, 'platform');
+    assert.equal(parsed._provided.defaultOrg, true);
+    assert.throws(() => parseArgs(['--default-org', '..']), /Invalid value for --default-org/);
+    assert.throws(() => parseArgs(['--default-org', 'a/b']), /path separators/);
+  });
+
+  it('parses the per-client API rate limit', () => {
+    assert.equal(parseArgs([]).rateLimit, null);
+    const parsed = parseArgs(['--rate-limit', '120']);
+    assert.equal(parsed.rateLimit, 120);
+    assert.equal(parsed._provided.rateLimit, true);
+    assert.throws(() => parseArgs(['--rate-limit', '0']));
+  });
+
+  it('parses and normalises the base path', () => {
+    assert.equal(parseArgs([]).basePath, null);
+    const parsed = parseArgs(['--base-path', 'agentrix/']);
+    assert.equal(parsed.basePath, '/agentrix');
+    assert.equal(parsed._provided.basePath, true);
+    assert.equal(parseArgs(['--base-path', '/']).basePath, null);
+    assert.throws(() => parseArgs(['--base-path', '/a/../b']), /Invalid value for --base-path/);
+  });
+
+  it('collects features from repeated and comma-separated flags', () => {
+    assert.equal(parseArgs([]).features, null);
+    const parsed = parseArgs(['--feature', 'github', '--feature', 'Terminals,git-write']);
+    assert.deepEqual(parsed.features, ['github', 'terminals', 'git-write']);
+    assert.equal(parsed._provided.features, true);
+    assert.throws(() => parseArgs(['--feature', 'email']), /Invalid value for --feature: Unknown feature email/);
+  });
+
+  it('parses the connection limit', () => {
+    assert.equal(parseArgs([]).maxConnections, null);
+    const parsed = parseArgs(['--max-connections', '64']);
+    assert.equal(parsed.maxConnections, 64);
+    assert.equal(parsed._provided.maxConnections, true);
+    assert.throws(() => parseArgs(['--max-connections', '0']));
+  });
+
+  it('parses the GitHub API version', () => {
+    assert.equal(parseArgs([]).githubApiVersion, null);
+    const parsed = parseArgs(['--github-api-version', '2022-11-28']);
+    assert.equal(parsed.githubApiVersion, '2022-11-28');
+    assert.equal(parsed._provided.githubApiVersion, true);
+    assert.throws(() => parseArgs(['--github-api-version', 'latest']));
+  });
+
+  it('parses the GitHub webhook secret', () => {
+    assert.equal(parseArgs([]).githubWebhookSecret, null);
+    assert.equal(parseArgs(['--github-webhook-secret', 's3cret']).githubWebhookSecret, 's3cret');
+    assert.throws(() => parseArgs(['--github-webhook-secret', '  ']));
+  });
+
+  it('parses the read-only flag', () => {
+    assert.equal(parseArgs([]).readOnly, false);
+    const parsed = parseArgs(['--read-only']);
+    assert.equal(parsed.readOnly, true);
+    assert.equal(parsed._provided.readOnly, true);
+  });
+
+  it('parses the verify-github-token flag', () => {
+    assert.equal(parseArgs([]).verifyGithubToken, false);
+    const parsed = parseArgs(['--verify-github-token']);
+    assert.equal(parsed.verifyGithubToken, true);
+    assert.equal(parsed._provided.verifyGithubToken, true);
+  });
+
+  it('parses the trace-git flag', () => {
+    assert.equal(parseArgs([]).traceGit, false);
+    const parsed = parseArgs(['--trace-git']);
+    assert.equal(parsed.traceGit, true);
+    assert.equal(parsed._provided.traceGit, true);
+  });
+
+  it('counts verbosity flags and rejects combining them with quiet', () => {
+    assert.equal(parseArgs([]).verbose, 0);
+    assert.equal(parseArgs(['-v']).verbose, 1);
+    assert.equal(parseArgs(['-vv', '--verbose']).verbose, 3);
+    const quiet = parseArgs(['-q']);
+    assert.equal(quiet.quiet, true);
+    assert.equal(quiet._provided.quiet, true);
+    assert.throws(() => parseArgs(['--quiet', '-v']), /cannot be combined/);
+    assert.equal(parseArgs(['-V']).version, true);
+  });
+
+  it('parses the sign-commits flag', () => {
+    assert.equal(parseArgs([]).signCommits, false);
+    const parsed = parseArgs(['--sign-commits']);
+    assert.equal(parsed.signCommits, true);
+    assert.equal(parsed._provided.signCommits, true);
+  });
+
+  it('parses the confirm-dangerous-terminals flag', () => {
+    assert.equal(parseArgs([]).confirmDangerousTerminals, false);
+    const parsed = parseArgs(['--confirm-dangerous-terminals']);
+    assert.equal(parsed.confirmDangerousTerminals, true);
+    assert.equal(parsed._provided.confirmDangerousTerminals, true);
+  });
+
+  it('parses the local flag', () => {
+    assert.equal(parseArgs([]).local, false);
+    const parsed = parseArgs(['--local', '--host', '192.168.1.10']);
+    assert.equal(parsed.local, true);
+    assert.equal(parsed.host, '192.168.1.10');
+    assert.equal(parsed._provided.local, true);
+  });
+
+  it('parses the slow request threshold', () => {
+    assert.equal(parseArgs([]).slowRequestMs, null);
+    const parsed = parseArgs(['--slow-request-ms', '750']);
+    assert.equal(parsed.slowRequestMs, 750);
+    assert.equal(parsed._provided.slowRequestMs, true);
+    assert.equal(parseArgs(['--slow-request-ms', '0']).slowRequestMs, 0);
+    assert.throws(() => parseArgs(['--slow-request-ms', '-5']));
+  });
+
+  it('parses the CORS flags', () => {
+    const defaults = parseArgs([]);
+    assert.equal(defaults.corsOrigins, null);
+    assert.equal(defaults.corsAllowCredentials, false);
+    assert.equal(defaults.corsMaxAgeSeconds, null);
+    const parsed = parseArgs([
+      '--cors-origin',
+      'https://app.example.com, http://localhost:5173',
+      '--cors-origin',
+      'https://admin.example.com',
+      '--cors-allow-credentials',
+      '--cors-max-age',
+      '600',
+    ]);
+    assert.deepEqual(parsed.corsOrigins, ['https://app.example.com', 'http://localhost:5173', 'https://admin.example.com']);
+    assert.equal(parsed.corsAllowCredentials, true);
+    assert.equal(parsed.corsMaxAgeSeconds, 600);
+    assert.equal(parsed._provided.corsOrigins, true);
+    assert.throws(() => parseArgs(['--cors-origin', 'https://app.example.com/path']), /Invalid value for --cors-origin/);
+    assert.throws(() => parseArgs(['--cors-max-age', '-1']));
+  });
+
+  it('parses the landing page path', () => {
+    assert.equal(parseArgs([]).landingPage, null);
+    const parsed = parseArgs(['--landing-page', './landing.html']);
+    assert.equal(parsed.landingPage, './landing.html');
+    assert.equal(parsed._provided.landingPage, true);
+    assert.throws(() => parseArgs(['--landing-page']));
+  });
+
+  it('parses the setup command', () => {
+    assert.equal(parseArgs([]).setupCommand, null);
+    const parsed = parseArgs(['--setup-command', ' npm ci ']);
+    assert.equal(parsed.setupCommand, 'npm ci');
+    assert.equal(parsed._provided.setupCommand, true);
+    assert.throws(() => parseArgs(['--setup-command', '  ']), /cannot be empty/);
+  });
+
+  it('parses the default base ref', () => {
+    assert.equal(parseArgs([]).defaultBase, null);
+    const parsed = parseArgs(['--default-base', ' develop ']);
+    assert.equal(parsed.defaultBase, 'develop');
+    assert.equal(parsed._provided.defaultBase, true);
+    assert.throws(() => parseArgs(['--default-base', '--upload-pack=evil']));
+  });
+
+  it('parses the per-repository worktree limit', () => {
+    assert.equal(parseArgs([]).maxWorktreesPerRepo, null);
+    assert.equal(parseArgs(['--max-worktrees-per-repo', '5']).maxWorktreesPerRepo, 5);
+    assert.throws(() => parseArgs(['--max-worktrees-per-repo', '0']));
+  });
+
+  it('parses the git output limit', () => {
+    assert.equal(parseArgs([]).gitOutputLimitBytes, null);
+    assert.equal(parseArgs(['--git-output-limit', '1048576']).gitOutputLimitBytes, 1048576);
+    assert.throws(() => parseArgs(['--git-output-limit', '0']));
+  });
+
   it('throws on unknown flags and unexpected positional arguments', () => {
     assert.throws(() => parseArgs(['--no-such-flag']));
     assert.throws(() => parseArgs(['positional']));
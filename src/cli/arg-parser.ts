@@ -1,6 +1,13 @@
 import { DEFAULT_HOST, DEFAULT_PORT } from '../server/index.js';
-import { VALID_TERMINAL_SESSION_MODES } from './constants.js';
+import { VALID_GIT_BACKENDS, VALID_TERMINAL_SESSION_MODES } from './constants.js';
 import type { ParsedArgs } from './types.js';
+import { parseCloneLayout, validateRepositorySegment } from '../domain/index.js';
+import { normalizeBasePath } from '../utils/base-path.js';
+import { isGithubApiVersion } from '../core/github.js';
+import { isValidEnvName } from '../core/terminal-env.js';
+import { isValidGitConfigEntry } from '../core/git.js';
+import { isCorsOrigin } from '../infrastructure/cors/index.js';
+import { parseFeatureList } from '../config/features.js';
 
 class ArgumentParser {
   private args: Omit<ParsedArgs, '_provided'>;
@@ -16,12 +23,16 @@ class ArgumentParser {
     return {
       port: DEFAULT_PORT,
       host: DEFAULT_HOST,
+      local: false,
       ui: null,
       workdir: null,
       password: null,
       cookieSecure: null,
       defaultBranch: null,
       showPassword: false,
+      printStartupJson: false,
+      quiet: false,
+      verbose: 0,
       codexCommand: null,
       claudeCommand: null,
       cursorCommand: null,
@@ -31,6 +42,39 @@ class ArgumentParser {
       ngrokDomain: null,
       openaiApiKey: null,
       terminalSessionMode: null,
+      terminalEnv: null,
+      gitConfig: null,
+      allowedUrlSchemes: null,
+      gitConcurrency: null,
+      maxConcurrentClones: null,
+      gitBackend: null,
+      repoCacheTtlMs: null,
+      trustProxy: false,
+      githubTimeoutMs: null,
+      githubApiVersion: null,
+      githubWebhookSecret: null,
+      gitAuthorName: null,
+      gitAuthorEmail: null,
+      cloneLayout: null,
+      defaultOrg: null,
+      defaultBase: null,
+      maxWorktreesPerRepo: null,
+      gitOutputLimitBytes: null,
+      setupCommand: null,
+      rateLimit: null,
+      maxConnections: null,
+      readOnly: false,
+      verifyGithubToken: false,
+      traceGit: false,
+      signCommits: false,
+      confirmDangerousTerminals: false,
+      slowRequestMs: null,
+      corsOrigins: null,
+      corsAllowCredentials: false,
+      corsMaxAgeSeconds: null,
+      landingPage: null,
+      basePath: null,
+      features: null,
       save: false,
       help: false,
       version: false,
@@ -41,12 +85,16 @@ class ArgumentParser {
     return {
       port: false,
       host: false,
+      local: false,
       ui: false,
       workdir: false,
       password: false,
       cookieSecure: false,
       defaultBranch: false,
       showPassword: false,
+      printStartupJson: false,
+      quiet: false,
+      verbose: false,
       codexCommand: false,
       claudeCommand: false,
       cursorCommand: false,
@@ -56,6 +104,39 @@ class ArgumentParser {
       ngrokDomain: false,
       openaiApiKey: false,
       terminalSessionMode: false,
+      terminalEnv: false,
+      gitConfig: false,
+      allowedUrlSchemes: false,
+      gitConcurrency: false,
+      maxConcurrentClones: false,
+      gitBackend: false,
+      repoCacheTtlMs: false,
+      trustProxy: false,
+      githubTimeoutMs: false,
+      githubApiVersion: false,
+      githubWebhookSecret: false,
+      gitAuthorName: false,
+      gitAuthorEmail: false,
+      cloneLayout: false,
+      defaultOrg: false,
+      defaultBase: false,
+      maxWorktreesPerRepo: false,
+      gitOutputLimitBytes: false,
+      setupCommand: false,
+      rateLimit: false,
+      maxConnections: false,
+      readOnly: false,
+      verifyGithubToken: false,
+      traceGit: false,
+      signCommits: false,
+      confirmDangerousTerminals: false,
+      slowRequestMs: false,
+      corsOrigins: false,
+      corsAllowCredentials: false,
+      corsMaxAgeSeconds: false,
+      landingPage: false,
+      basePath: false,
+      features: false,
       save: false,
     };
   }
@@ -103,12 +184,29 @@ class ArgumentParser {
 
   private parsePort(_token: string, value: string): number {
     const parsed = Number.parseInt(value, 10);
-    if (!Number.isInteger(parsed) || parsed < 1 || parsed > 65535) {
+    // 0 lets the OS pick a free port; --print-startup-json reports which one
+    if (!Number.isInteger(parsed) || parsed < 0 || parsed > 65535) {
       throw new Error(`Invalid port: ${value}`);
     }
     return parsed;
   }
 
+  private parsePositiveInteger(token: string, value: string): number {
+    const parsed = Number.parseInt(value, 10);
+    if (!Number.isInteger(parsed) || parsed < 1 || String(parsed) !== value.trim()) {
+      throw new Error(`Invalid value for ${token}: ${value} (expected a positive integer)`);
+    }
+    return parsed;
+  }
+
+  private parseNonNegativeInteger(token: string, value: string): number {
+    const parsed = Number.parseInt(value, 10);
+    if (!Number.isInteger(parsed) || parsed < 0 || String(parsed) !== value.trim()) {
+      throw new Error(`Invalid value for ${token}: ${value} (expected a non-negative integer)`);
+    }
+    return parsed;
+  }
+
   private parseCookieSecure(_token: string, value: string): string {
     const trimmed = value.trim().toLowerCase();
     if (!['true', 'false', 'auto'].includes(trimmed)) {
@@ -117,10 +215,55 @@ class ArgumentParser {
     return trimmed;
   }
 
+  private parseGitBackend(token: string, value: string): string {
+    const trimmed = value.trim().toLowerCase();
+    if (!VALID_GIT_BACKENDS.has(trimmed)) {
+      throw new Error(`Invalid value for ${token}: ${value} (expected one of: ${[...VALID_GIT_BACKENDS].join(', ')})`);
+    }
+    return trimmed;
+  }
+
+  private parseList(token: string, value: string, fieldName: string): string[] {
+    const items = value
+      .split(',')
+      .map((item) => item.trim())
+      .filter(Boolean);
+    if (items.length === 0) {
+      throw new Error(`${fieldName} cannot be empty (${token})`);
+    }
+    return items;
+  }
+
+  private parseEnvAssignment(token: string, value: string): [string, string] {
+    const separator = value.indexOf('=');
+    const name = separator === -1 ? '' : value.slice(0, separator).trim();
+    if (!isValidEnvName(name)) {
+      throw new Error(`Invalid value for ${token}: expected KEY=VALUE with a valid variable name`);
+    }
+    return [name, value.slice(separator + 1)];
+  }
+
+  private parseGitConfigAssignment(token: string, value: string): [string, string] {
+    const separator = value.indexOf('=');
+    const key = separator === -1 ? '' : value.slice(0, separator).trim();
+    const configValue = value.slice(separator + 1);
+    if (!isValidGitConfigEntry(key, configValue)) {
+      throw new Error(`Invalid value for ${token}: expected KEY=VALUE with a git config key such as http.proxy`);
+    }
+    return [key, configValue];
+  }
+
   parse(argv: string[]): ParsedArgs {
     for (let i = 0; i < argv.length; i += 1) {
       const token = argv[i];
 
+      // Stacked short flags such as -vv raise the verbosity once per v
+      if (token && /^-v{2,}$/.test(token)) {
+        this.args.verbose += token.length - 1;
+        this.provided['verbose'] = true;
+        continue;
+      }
+
       switch (token) {
         case '--port':
         case '-p': {
@@ -135,7 +278,13 @@ class ArgumentParser {
           this.provided['host'] = true;
           break;
         }
+        case '--local': {
+          this.args.local = true;
+          this.provided['local'] = true;
+          break;
+        }
         case '--ui':
+        case '--frontend-dir':
         case '-u': {
           this.args.ui = this.requireValue(token, argv[++i]);
           this.provided['ui'] = true;
@@ -184,6 +333,11 @@ class ArgumentParser {
           this.provided['showPassword'] = true;
           break;
         }
+        case '--print-startup-json': {
+          this.args.printStartupJson = true;
+          this.provided['printStartupJson'] = true;
+          break;
+        }
         case '--codex-command': {
           this.args.codexCommand = this.requireValue(token, argv[++i]);
           this.provided['codexCommand'] = true;
@@ -227,17 +381,244 @@ class ArgumentParser {
           this.provided['openaiApiKey'] = true;
           break;
         }
+        case '--terminal-env': {
+          const [name, envValue] = this.parseEnvAssignment(token, this.requireValue(token, argv[++i]));
+          this.args.terminalEnv = { ...(this.args.terminalEnv ?? {}), [name]: envValue };
+          this.provided['terminalEnv'] = true;
+          break;
+        }
+        case '--git-config': {
+          const [key, configValue] = this.parseGitConfigAssignment(token, this.requireValue(token, argv[++i]));
+          this.args.gitConfig = { ...(this.args.gitConfig ?? {}), [key]: configValue };
+          this.provided['gitConfig'] = true;
+          break;
+        }
+        case '--allowed-url-schemes': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.allowedUrlSchemes = this.parseList(token, value, 'Allowed URL schemes');
+          this.provided['allowedUrlSchemes'] = true;
+          break;
+        }
+        case '--git-concurrency': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.gitConcurrency = this.parsePositiveInteger(token, value);
+          this.provided['gitConcurrency'] = true;
+          break;
+        }
+        case '--max-concurrent-clones': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.maxConcurrentClones = this.parsePositiveInteger(token, value);
+          this.provided['maxConcurrentClones'] = true;
+          break;
+        }
+        case '--git-backend': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.gitBackend = this.parseGitBackend(token, value);
+          this.provided['gitBackend'] = true;
+          break;
+        }
+        case '--repo-cache-ttl': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.repoCacheTtlMs = this.parseNonNegativeInteger(token, value);
+          this.provided['repoCacheTtlMs'] = true;
+          break;
+        }
+        case '--trust-proxy': {
+          this.args.trustProxy = true;
+          this.provided['trustProxy'] = true;
+          break;
+        }
+        case '--github-timeout': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.githubTimeoutMs = this.parsePositiveInteger(token, value);
+          this.provided['githubTimeoutMs'] = true;
+          break;
+        }
+        case '--github-api-version': {
+          const value = this.requireValue(token, argv[++i]).trim();
+          if (!isGithubApiVersion(value)) {
+            throw new Error(`Invalid value for ${token}: expected a YYYY-MM-DD version such as 2022-11-28`);
+          }
+          this.args.githubApiVersion = value;
+          this.provided['githubApiVersion'] = true;
+          break;
+        }
+        case '--github-webhook-secret': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.githubWebhookSecret = this.requireNonEmpty(token, value, 'GitHub webhook secret');
+          this.provided['githubWebhookSecret'] = true;
+          break;
+        }
+        case '--git-author-name': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.gitAuthorName = this.requireNonEmpty(token, value, 'Git author name');
+          this.provided['gitAuthorName'] = true;
+          break;
+        }
+        case '--git-author-email': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.gitAuthorEmail = this.requireNonEmpty(token, value, 'Git author email');
+          this.provided['gitAuthorEmail'] = true;
+          break;
+        }
+        case '--clone-layout': {
+          const value = this.requireValue(token, argv[++i]);
+          try {
+            this.args.cloneLayout = parseCloneLayout(value).template;
+          } catch (error: unknown) {
+            throw new Error(`Invalid value for ${token}: ${(error as Error).message}`);
+          }
+          this.provided['cloneLayout'] = true;
+          break;
+        }
+        case '--default-org': {
+          const value = this.requireValue(token, argv[++i]);
+          try {
+            this.args.defaultOrg = validateRepositorySegment(value, 'organization');
+          } catch (error: unknown) {
+            throw new Error(`Invalid value for ${token}: ${(error as Error).message}`);
+          }
+          this.provided['defaultOrg'] = true;
+          break;
+        }
+        case '--default-base': {
+          const value = this.requireNonEmpty(token, this.requireValue(token, argv[++i]), 'Default base');
+          if (value.startsWith('-')) {
+            throw new Error(`Invalid value for ${token}: expected a branch or ref name`);
+          }
+          this.args.defaultBase = value;
+          this.provided['defaultBase'] = true;
+          break;
+        }
+        case '--max-worktrees-per-repo': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.maxWorktreesPerRepo = this.parsePositiveInteger(token, value);
+          this.provided['maxWorktreesPerRepo'] = true;
+          break;
+        }
+        case '--git-output-limit': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.gitOutputLimitBytes = this.parsePositiveInteger(token, value);
+          this.provided['gitOutputLimitBytes'] = true;
+          break;
+        }
+        case '--setup-command': {
+          this.args.setupCommand = this.requireNonEmpty(token, this.requireValue(token, argv[++i]), 'Setup command');
+          this.provided['setupCommand'] = true;
+          break;
+        }
+        case '--rate-limit': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.rateLimit = this.parsePositiveInteger(token, value);
+          this.provided['rateLimit'] = true;
+          break;
+        }
+        case '--max-connections': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.maxConnections = this.parsePositiveInteger(token, value);
+          this.provided['maxConnections'] = true;
+          break;
+        }
+        case '--read-only': {
+          this.args.readOnly = true;
+          this.provided['readOnly'] = true;
+          break;
+        }
+        case '--verify-github-token': {
+          this.args.verifyGithubToken = true;
+          this.provided['verifyGithubToken'] = true;
+          break;
+        }
+        case '--trace-git': {
+          this.args.traceGit = true;
+          this.provided['traceGit'] = true;
+          break;
+        }
+        case '--sign-commits': {
+          this.args.signCommits = true;
+          this.provided['signCommits'] = true;
+          break;
+        }
+        case '--confirm-dangerous-terminals': {
+          this.args.confirmDangerousTerminals = true;
+          this.provided['confirmDangerousTerminals'] = true;
+          break;
+        }
+        case '--slow-request-ms': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.slowRequestMs = this.parseNonNegativeInteger(token, value);
+          this.provided['slowRequestMs'] = true;
+          break;
+        }
+        case '--cors-origin': {
+          const origins = this.parseList(token, this.requireValue(token, argv[++i]), 'CORS origin');
+          const invalid = origins.find((origin) => !isCorsOrigin(origin));
+          if (invalid !== undefined) {
+            throw new Error(`Invalid value for ${token}: ${invalid} (expected * or an origin such as https://app.example.com)`);
+          }
+          this.args.corsOrigins = [...(this.args.corsOrigins ?? []), ...origins];
+          this.provided['corsOrigins'] = true;
+          break;
+        }
+        case '--cors-allow-credentials': {
+          this.args.corsAllowCredentials = true;
+          this.provided['corsAllowCredentials'] = true;
+          break;
+        }
+        case '--cors-max-age': {
+          const value = this.requireValue(token, argv[++i]);
+          this.args.corsMaxAgeSeconds = this.parseNonNegativeInteger(token, value);
+          this.provided['corsMaxAgeSeconds'] = true;
+          break;
+        }
+        case '--landing-page': {
+          this.args.landingPage = this.requireValue(token, argv[++i]);
+          this.provided['landingPage'] = true;
+          break;
+        }
+        case '--base-path': {
+          const value = this.requireValue(token, argv[++i]);
+          try {
+            this.args.basePath = normalizeBasePath(value) || null;
+          } catch (error: unknown) {
+            throw new Error(`Invalid value for ${token}: ${(error as Error).message}`);
+          }
+          this.provided['basePath'] = true;
+          break;
+        }
+        case '--feature': {
+          const value = this.requireValue(token, argv[++i]);
+          try {
+            this.args.features = [...(this.args.features ?? []), ...parseFeatureList(value)];
+          } catch (error: unknown) {
+            throw new Error(`Invalid value for ${token}: ${(error as Error).message}`);
+          }
+          this.provided['features'] = true;
+          break;
+        }
         case '--save': {
           this.args.save = true;
           this.provided['save'] = true;
           break;
         }
+        case '--quiet':
+        case '-q': {
+          this.args.quiet = true;
+          this.provided['quiet'] = true;
+          break;
+        }
+        case '--verbose':
+        case '-v': {
+          this.args.verbose += 1;
+          this.provided['verbose'] = true;
+          break;
+        }
         case '--help':
         case '-h':
           this.args.help = true;
           break;
         case '--version':
-        case '-v':
+        case '-V':
           this.args.version = true;
           break;
         default:
@@ -249,6 +630,10 @@ class ArgumentParser {
       }
     }
 
+    if (this.args.quiet && this.args.verbose > 0) {
+      throw new Error('--quiet cannot be combined with --verbose');
+    }
+
     return {
       ...this.args,
       _provided: this.provided,
@@ -3,7 +3,7 @@ import path from 'node:path';
 import { describe, it, mock } from 'node:test';
 
 import { parseArgs } from './arg-parser.js';
-import { resolveConfig, validateNgrokConfig, buildConfigToSave } from './config-resolver.js';
+import { resolveConfig, validateNgrokConfig, validateCorsConfig, buildConfigToSave, FRONTEND_DIR_ENV } from './config-resolver.js';
 
 describe('config-resolver', () => {
   it('prefers CLI provided values over file configuration', () => {
@@ -37,6 +37,32 @@ describe('config-resolver', () => {
     }
   });
 
+  it('resolves the UI directory from the flag, then the environment, then the config file', () => {
+    const fileConfig = { ui: './file-ui' };
+    const previousEnv = process.env[FRONTEND_DIR_ENV];
+    const cwdMock = mock.method(process, 'cwd', () => '/tmp/project');
+    try {
+      process.env[FRONTEND_DIR_ENV] = '/opt/agentrix/ui';
+
+      const fromFlag = resolveConfig(parseArgs(['--frontend-dir', './flag-ui']), fileConfig);
+      assert.equal(fromFlag.uiPath, path.resolve('/tmp/project', './flag-ui'));
+
+      const fromEnv = resolveConfig(parseArgs([]), fileConfig);
+      assert.equal(fromEnv.uiPath, '/opt/agentrix/ui');
+
+      delete process.env[FRONTEND_DIR_ENV];
+      const fromFile = resolveConfig(parseArgs([]), fileConfig);
+      assert.equal(fromFile.uiPath, path.resolve('/tmp/project', './file-ui'));
+    } finally {
+      cwdMock.mock.restore();
+      if (previousEnv === undefined) {
+        delete process.env[FRONTEND_DIR_ENV];
+      } else {
+        process.env[FRONTEND_DIR_ENV] = previousEnv;
+      }
+    }
+  });
+
   it('falls back to configuration values and defaults', () => {
     const args = parseArgs([]);
     const fileConfig = {
@@ -48,6 +74,7 @@ describe('config-resolver', () => {
       cursorCommand: 'cursor-cli',
       ideCommand: 'deprecated-ide',
       terminalSessionMode: 'tmux',
+      githubTokens: { acme: 'ghp_acme' },
     };
 
     const cwdMock = mock.method(process, 'cwd', () => '/tmp/project');
@@ -61,11 +88,29 @@ describe('config-resolver', () => {
       assert.equal(resolved.cursorCommand, 'cursor-cli');
       assert.equal(resolved.ideCommand, 'deprecated-ide');
       assert.equal(resolved.terminalSessionMode, 'tmux');
+      assert.deepEqual(resolved.githubTokens, { acme: 'ghp_acme' });
     } finally {
       cwdMock.mock.restore();
     }
   });
 
+  it('binds loopback with --local, overriding --host and the config file', () => {
+    const stderr = mock.method(process.stderr, 'write', () => true);
+    try {
+      assert.equal(resolveConfig(parseArgs([]), {}).host, '0.0.0.0');
+      assert.equal(resolveConfig(parseArgs(['--local']), {}).host, '127.0.0.1');
+      assert.equal(stderr.mock.callCount(), 0);
+
+      const resolved = resolveConfig(parseArgs(['--local', '--host', '0.0.0.0']), { host: '10.0.0.5' });
+      assert.equal(resolved.host, '127.0.0.1');
+      assert.equal(resolved.local, true);
+      assert.equal(resolveConfig(parseArgs([]), { local: true, host: '10.0.0.5' }).host, '127.0.0.1');
+      assert.match(String(stderr.mock.calls[0]?.arguments[0]), /--local binds 127\.0\.0\.1; ignoring host 0\.0\.0\.0/);
+    } finally {
+      stderr.mock.restore();
+    }
+  });
+
   it('uses IDE command as fallback for cursor command when CLI did not provide one', () => {
     const args = parseArgs([]);
     const fileConfig = {
@@ -84,6 +129,17 @@ describe('config-resolver', () => {
     assert.doesNotThrow(() => validateNgrokConfig(okConfig));
   });
 
+  it('refuses CORS credentials with a wildcard origin', () => {
+    const config = resolveConfig(parseArgs(['--cors-allow-credentials']), { corsOrigins: ['*'] });
+    assert.throws(() => validateCorsConfig(config), /cannot be combined with --cors-origin \*/);
+
+    const okConfig = resolveConfig(parseArgs(['--cors-origin', 'https://app.example.com', '--cors-allow-credentials']), {
+      corsOrigins: ['*'],
+    });
+    assert.deepEqual(okConfig.corsOrigins, ['https://app.example.com']);
+    assert.doesNotThrow(() => validateCorsConfig(okConfig));
+  });
+
   it('builds configuration save payload based on provided fields', () => {
     const args = parseArgs([
       '--port',
@@ -1,17 +1,26 @@
 import path from 'node:path';
 import { fileURLToPath } from 'node:url';
-import { DEFAULT_HOST, DEFAULT_PORT } from '../server/index.js';
+import { DEFAULT_HOST, DEFAULT_PORT, LOOPBACK_HOST } from '../server/index.js';
+import { FEATURES_ENV, parseFeatureList, type Feature } from '../config/features.js';
 import type { ParsedArgs } from './types.js';
+import { warnConfig } from './validation.js';
 
 const BUNDLED_UI_PATH = fileURLToPath(new URL('../../ui/dist', import.meta.url));
 
+/**
+ * Environment variable naming the UI directory, consulted after the CLI flag and before the config file
+ */
+export const FRONTEND_DIR_ENV = 'AGENTRIX_FRONTEND_DIR';
+
 interface ResolvedConfig {
   port: number;
   host: string;
+  local: boolean;
   uiPath: string;
   workdir: string;
   password: string | null;
   showPassword: boolean;
+  printStartupJson: boolean;
   defaultBranch: string | null;
   defaultBranches: Record<string, string> | null;
   cookieSecure: string;
@@ -27,6 +36,40 @@ interface ResolvedConfig {
   branchNameLlm: string | null;
   planLlm: string | null;
   terminalSessionMode: string;
+  terminalEnv: Record<string, string> | null;
+  gitConfig: Record<string, string> | null;
+  allowedUrlSchemes: string[] | null;
+  gitConcurrency: number | null;
+  maxConcurrentClones: number | null;
+  gitBackend: string | null;
+  repoCacheTtlMs: number | null;
+  trustProxy: boolean;
+  githubTimeoutMs: number | null;
+  githubApiVersion: string | null;
+  githubWebhookSecret: string | null;
+  githubTokens: Record<string, string> | null;
+  gitAuthorName: string | null;
+  gitAuthorEmail: string | null;
+  cloneLayout: string | null;
+  defaultOrg: string | null;
+  defaultBase: string | null;
+  maxWorktreesPerRepo: number | null;
+  gitOutputLimitBytes: number | null;
+  setupCommand: string | null;
+  rateLimit: number | null;
+  maxConnections: number | null;
+  readOnly: boolean;
+  verifyGithubToken: boolean;
+  traceGit: boolean;
+  signCommits: boolean;
+  confirmDangerousTerminals: boolean;
+  slowRequestMs: number | null;
+  corsOrigins: string[] | null;
+  corsAllowCredentials: boolean;
+  corsMaxAgeSeconds: number | null;
+  landingPage: string | null;
+  basePath: string | null;
+  features: Feature[] | null;
 }
 
 function resolveValue<T>(
@@ -49,8 +92,15 @@ export function resolveConfig(
   const fc = fileConfig;
 
   const port = resolveValue(provided['port'] ?? false, args.port, fc['port'] as number | undefined, DEFAULT_PORT);
-  const host = resolveValue(provided['host'] ?? false, args.host, fc['host'] as string | undefined, DEFAULT_HOST);
-  const uiInput = resolveValue(provided['ui'] ?? false, args.ui, fc['ui'] as string | undefined, null);
+  const requestedHost = resolveValue(provided['host'] ?? false, args.host, fc['host'] as string | undefined, DEFAULT_HOST);
+  const local = resolveValue(provided['local'] ?? false, args.local, fc['local'] as boolean | undefined, false);
+  // --local wins over any host, so a stray --host or config entry cannot expose the server
+  const host = local ? LOOPBACK_HOST : requestedHost;
+  if (local && requestedHost !== LOOPBACK_HOST && ((provided['host'] ?? false) || fc['host'] !== undefined)) {
+    warnConfig(`--local binds ${LOOPBACK_HOST}; ignoring host ${requestedHost}.`);
+  }
+  const envUi = process.env[FRONTEND_DIR_ENV]?.trim() || undefined;
+  const uiInput = resolveValue(provided['ui'] ?? false, args.ui, envUi ?? (fc['ui'] as string | undefined), null);
   const workdirInput = resolveValue(provided['workdir'] ?? false, args.workdir, fc['workdir'] as string | undefined, null);
   const password = resolveValue(provided['password'] ?? false, args.password, fc['password'] as string | undefined, null);
   const defaultBranch = resolveValue(provided['defaultBranch'] ?? false, args.defaultBranch, fc['defaultBranch'] as string | undefined, null);
@@ -61,6 +111,11 @@ export function resolveConfig(
       ? (fc['defaultBranches'] as Record<string, string>)
       : null;
 
+  const githubTokens =
+    fc['githubTokens'] && typeof fc['githubTokens'] === 'object' && Object.keys(fc['githubTokens'] as object).length > 0
+      ? (fc['githubTokens'] as Record<string, string>)
+      : null;
+
   const codexCommand = resolveValue(provided['codexCommand'] ?? false, args.codexCommand, fc['codexCommand'] as string | undefined, null);
   const claudeCommand = resolveValue(provided['claudeCommand'] ?? false, args.claudeCommand, fc['claudeCommand'] as string | undefined, null);
   const ideCommand = resolveValue(provided['ideCommand'] ?? false, args.ideCommand, fc['ideCommand'] as string | undefined, null);
@@ -77,20 +132,93 @@ export function resolveConfig(
   const openaiApiKey = resolveValue(provided['openaiApiKey'] ?? false, args.openaiApiKey, fc['openaiApiKey'] as string | undefined, null);
   const terminalSessionMode = resolveValue(provided['terminalSessionMode'] ?? false, args.terminalSessionMode, fc['terminalSessionMode'] as string | undefined, 'auto');
 
+  // Flags add to the config file's variables rather than replacing them
+  const mergedTerminalEnv = {
+    ...((fc['terminalEnv'] as Record<string, string> | undefined) ?? {}),
+    ...(args.terminalEnv ?? {}),
+  };
+  const terminalEnv = Object.keys(mergedTerminalEnv).length > 0 ? mergedTerminalEnv : null;
+  const mergedGitConfig = {
+    ...((fc['gitConfig'] as Record<string, string> | undefined) ?? {}),
+    ...(args.gitConfig ?? {}),
+  };
+  const gitConfig = Object.keys(mergedGitConfig).length > 0 ? mergedGitConfig : null;
+
+  const allowedUrlSchemes = resolveValue(provided['allowedUrlSchemes'] ?? false, args.allowedUrlSchemes, fc['allowedUrlSchemes'] as string[] | undefined, null);
+  const gitConcurrency = resolveValue(provided['gitConcurrency'] ?? false, args.gitConcurrency, fc['gitConcurrency'] as number | undefined, null);
+  const maxConcurrentClones = resolveValue(provided['maxConcurrentClones'] ?? false, args.maxConcurrentClones, fc['maxConcurrentClones'] as number | undefined, null);
+  const gitBackend = resolveValue(provided['gitBackend'] ?? false, args.gitBackend, fc['gitBackend'] as string | undefined, null);
+  const repoCacheTtlMs = resolveValue(provided['repoCacheTtlMs'] ?? false, args.repoCacheTtlMs, fc['repoCacheTtlMs'] as number | undefined, null);
+  const trustProxy = resolveValue(provided['trustProxy'] ?? false, args.trustProxy, fc['trustProxy'] as boolean | undefined, false);
+  const githubTimeoutMs = resolveValue(provided['githubTimeoutMs'] ?? false, args.githubTimeoutMs, fc['githubTimeoutMs'] as number | undefined, null);
+  const githubApiVersion = resolveValue(provided['githubApiVersion'] ?? false, args.githubApiVersion, fc['githubApiVersion'] as string | undefined, null);
+  const githubWebhookSecret = resolveValue(provided['githubWebhookSecret'] ?? false, args.githubWebhookSecret, fc['githubWebhookSecret'] as string | undefined, null);
+  const gitAuthorName = resolveValue(provided['gitAuthorName'] ?? false, args.gitAuthorName, fc['gitAuthorName'] as string | undefined, null);
+  const gitAuthorEmail = resolveValue(provided['gitAuthorEmail'] ?? false, args.gitAuthorEmail, fc['gitAuthorEmail'] as string | undefined, null);
+  const cloneLayout = resolveValue(provided['cloneLayout'] ?? false, args.cloneLayout, fc['cloneLayout'] as string | undefined, null);
+  const defaultOrg = resolveValue(provided['defaultOrg'] ?? false, args.defaultOrg, fc['defaultOrg'] as string | undefined, null);
+  const defaultBase = resolveValue(provided['defaultBase'] ?? false, args.defaultBase, fc['defaultBase'] as string | undefined, null);
+  const maxWorktreesPerRepo = resolveValue(provided['maxWorktreesPerRepo'] ?? false, args.maxWorktreesPerRepo, fc['maxWorktreesPerRepo'] as number | undefined, null);
+  const gitOutputLimitBytes = resolveValue(provided['gitOutputLimitBytes'] ?? false, args.gitOutputLimitBytes, fc['gitOutputLimitBytes'] as number | undefined, null);
+  const setupCommand = resolveValue(provided['setupCommand'] ?? false, args.setupCommand, fc['setupCommand'] as string | undefined, null);
+  const rateLimit = resolveValue(provided['rateLimit'] ?? false, args.rateLimit, fc['rateLimit'] as number | undefined, null);
+  const maxConnections = resolveValue(provided['maxConnections'] ?? false, args.maxConnections, fc['maxConnections'] as number | undefined, null);
+  const readOnly = resolveValue(provided['readOnly'] ?? false, args.readOnly, fc['readOnly'] as boolean | undefined, false);
+  const verifyGithubToken = resolveValue(
+    provided['verifyGithubToken'] ?? false,
+    args.verifyGithubToken,
+    fc['verifyGithubToken'] as boolean | undefined,
+    false,
+  );
+  const traceGit = resolveValue(provided['traceGit'] ?? false, args.traceGit, fc['traceGit'] as boolean | undefined, false);
+  const signCommits = resolveValue(
+    provided['signCommits'] ?? false,
+    args.signCommits,
+    fc['signCommits'] as boolean | undefined,
+    false,
+  );
+  const confirmDangerousTerminals = resolveValue(
+    provided['confirmDangerousTerminals'] ?? false,
+    args.confirmDangerousTerminals,
+    fc['confirmDangerousTerminals'] as boolean | undefined,
+    false,
+  );
+  const slowRequestMs = resolveValue(provided['slowRequestMs'] ?? false, args.slowRequestMs, fc['slowRequestMs'] as number | undefined, null);
+  const corsOrigins = resolveValue(provided['corsOrigins'] ?? false, args.corsOrigins, fc['corsOrigins'] as string[] | undefined, null);
+  const corsAllowCredentials = resolveValue(
+    provided['corsAllowCredentials'] ?? false,
+    args.corsAllowCredentials,
+    fc['corsAllowCredentials'] as boolean | undefined,
+    false,
+  );
+  const corsMaxAgeSeconds = resolveValue(provided['corsMaxAgeSeconds'] ?? false, args.corsMaxAgeSeconds, fc['corsMaxAgeSeconds'] as number | undefined, null);
+  const landingPageInput = resolveValue(provided['landingPage'] ?? false, args.landingPage, fc['landingPage'] as string | undefined, null);
+  const basePath = resolveValue(provided['basePath'] ?? false, args.basePath, fc['basePath'] as string | undefined, null);
+  const envFeatures = process.env[FEATURES_ENV]?.trim() || undefined;
+  const features = resolveValue(
+    provided['features'] ?? false,
+    args.features,
+    envFeatures !== undefined ? parseFeatureList(envFeatures) : (fc['features'] as Feature[] | undefined),
+    null,
+  );
+
   const automationApiKey = (fc['automationApiKey'] as string | undefined) ?? null;
   const branchNameLlm = (fc['branchNameLlm'] as string | undefined) ?? null;
   const planLlm = (fc['planLlm'] as string | undefined) ?? null;
 
   const uiPath = uiInput ? path.resolve(process.cwd(), uiInput) : BUNDLED_UI_PATH;
   const workdir = workdirInput ? path.resolve(process.cwd(), workdirInput) : process.cwd();
+  const landingPage = landingPageInput ? path.resolve(process.cwd(), landingPageInput) : null;
 
   return {
     port,
     host,
+    local,
     uiPath,
     workdir,
     password,
     showPassword: args.showPassword,
+    printStartupJson: args.printStartupJson,
     defaultBranch,
     defaultBranches,
     cookieSecure: cookieSecure ?? 'auto',
@@ -106,6 +234,40 @@ export function resolveConfig(
     branchNameLlm,
     planLlm,
     terminalSessionMode: terminalSessionMode ?? 'auto',
+    terminalEnv,
+    gitConfig,
+    allowedUrlSchemes,
+    gitConcurrency,
+    maxConcurrentClones,
+    gitBackend,
+    repoCacheTtlMs,
+    trustProxy,
+    githubTimeoutMs,
+    githubApiVersion,
+    githubWebhookSecret,
+    githubTokens,
+    gitAuthorName,
+    gitAuthorEmail,
+    cloneLayout,
+    defaultOrg,
+    defaultBase,
+    maxWorktreesPerRepo,
+    gitOutputLimitBytes,
+    setupCommand,
+    rateLimit,
+    maxConnections,
+    readOnly,
+    verifyGithubToken,
+    traceGit,
+    signCommits,
+    confirmDangerousTerminals,
+    slowRequestMs,
+    corsOrigins,
+    corsAllowCredentials,
+    corsMaxAgeSeconds,
+    landingPage,
+    basePath,
+    features,
   };
 }
 
@@ -118,6 +280,13 @@ export function validateNgrokConfig(config: ResolvedConfig): void {
   }
 }
 
+export function validateCorsConfig(config: ResolvedConfig): void {
+  // Browsers refuse credentialed responses that allow every origin
+  if (config.corsAllowCredentials && config.corsOrigins?.includes('*')) {
+    throw new Error('--cors-allow-credentials cannot be combined with --cors-origin *; list the allowed origins instead.');
+  }
+}
+
 export function buildConfigToSave(
   config: ResolvedConfig,
   args: ParsedArgs,
@@ -141,6 +310,21 @@ export function buildConfigToSave(
     configToSave['workdir'] = workdirInput;
   }
 
+  const landingPageInput =
+    (provided['landingPage'] ?? false) ? args.landingPage : (fc['landingPage'] as string | undefined) ?? null;
+  if (landingPageInput) {
+    configToSave['landingPage'] = landingPageInput;
+  }
+
+  if (config.basePath) {
+    configToSave['basePath'] = config.basePath;
+  }
+
+  const featuresInput = (provided['features'] ?? false) ? args.features : (fc['features'] as Feature[] | undefined) ?? null;
+  if (featuresInput) {
+    configToSave['features'] = featuresInput;
+  }
+
   if (config.password) {
     configToSave['password'] = config.password;
   }
@@ -169,6 +353,134 @@ export function buildConfigToSave(
     configToSave['terminalSessionMode'] = config.terminalSessionMode;
   }
 
+  if (config.terminalEnv) {
+    configToSave['terminalEnv'] = config.terminalEnv;
+  }
+
+  if (config.gitConfig) {
+    configToSave['gitConfig'] = config.gitConfig;
+  }
+
+  if (config.allowedUrlSchemes) {
+    configToSave['allowedUrlSchemes'] = config.allowedUrlSchemes;
+  }
+
+  if (config.gitConcurrency) {
+    configToSave['gitConcurrency'] = config.gitConcurrency;
+  }
+
+  if (config.maxConcurrentClones) {
+    configToSave['maxConcurrentClones'] = config.maxConcurrentClones;
+  }
+
+  if (config.gitBackend) {
+    configToSave['gitBackend'] = config.gitBackend;
+  }
+
+  if (config.repoCacheTtlMs !== null) {
+    configToSave['repoCacheTtlMs'] = config.repoCacheTtlMs;
+  }
+
+  if (config.trustProxy) {
+    configToSave['trustProxy'] = true;
+  }
+
+  if (config.githubTimeoutMs) {
+    configToSave['githubTimeoutMs'] = config.githubTimeoutMs;
+  }
+
+  if (config.githubApiVersion) {
+    configToSave['githubApiVersion'] = config.githubApiVersion;
+  }
+
+  if (config.githubWebhookSecret) {
+    configToSave['githubWebhookSecret'] = config.githubWebhookSecret;
+  }
+
+  if (config.githubTokens) {
+    configToSave['githubTokens'] = config.githubTokens;
+  }
+
+  if (config.gitAuthorName) {
+    configToSave['gitAuthorName'] = config.gitAuthorName;
+  }
+
+  if (config.gitAuthorEmail) {
+    configToSave['gitAuthorEmail'] = config.gitAuthorEmail;
+  }
+
+  if (config.cloneLayout) {
+    configToSave['cloneLayout'] = config.cloneLayout;
+  }
+
+  if (config.defaultOrg) {
+    configToSave['defaultOrg'] = config.defaultOrg;
+  }
+
+  if (config.defaultBase) {
+    configToSave['defaultBase'] = config.defaultBase;
+  }
+
+  if (config.maxWorktreesPerRepo) {
+    configToSave['maxWorktreesPerRepo'] = config.maxWorktreesPerRepo;
+  }
+
+  if (config.gitOutputLimitBytes) {
+    configToSave['gitOutputLimitBytes'] = config.gitOutputLimitBytes;
+  }
+
+  if (config.setupCommand) {
+    configToSave['setupCommand'] = config.setupCommand;
+  }
+
+  if (config.rateLimit) {
+    configToSave['rateLimit'] = config.rateLimit;
+  }
+
+  if (config.maxConnections) {
+    configToSave['maxConnections'] = config.maxConnections;
+  }
+
+  if (config.readOnly) {
+    configToSave['readOnly'] = true;
+  }
+
+  if (config.verifyGithubToken) {
+    configToSave['verifyGithubToken'] = true;
+  }
+
+  if (config.traceGit) {
+    configToSave['traceGit'] = true;
+  }
+
+  if (config.signCommits) {
+    configToSave['signCommits'] = true;
+  }
+
+  if (config.confirmDangerousTerminals) {
+    configToSave['confirmDangerousTerminals'] = true;
+  }
+
+  if (config.local) {
+    configToSave['local'] = true;
+  }
+
+  if (config.slowRequestMs) {
+    configToSave['slowRequestMs'] = config.slowRequestMs;
+  }
+
+  if (config.corsOrigins) {
+    configToSave['corsOrigins'] = config.corsOrigins;
+  }
+
+  if (config.corsAllowCredentials) {
+    configToSave['corsAllowCredentials'] = true;
+  }
+
+  if (config.corsMaxAgeSeconds !== null) {
+    configToSave['corsMaxAgeSeconds'] = config.corsMaxAgeSeconds;
+  }
+
   const commandsConfig: Record<string, string> = {};
   if (config.codexCommand) commandsConfig['codex'] = config.codexCommand;
   if (config.claudeCommand) commandsConfig['claude'] = config.claudeCommand;
@@ -23,6 +23,7 @@ describe('cli config utilities', () => {
       automation: { apiKey: 'auto' },
       openai: { apiKey: 'openai' },
       terminalSessionMode: 'tmux',
+      githubTokens: { ' acme ': ' ghp_acme ' },
     };
 
     const normalized = normalizeConfig(raw, 'config.json');
@@ -42,6 +43,7 @@ describe('cli config utilities', () => {
     assert.equal(normalized.automationApiKey, 'auto');
     assert.equal(normalized.openaiApiKey, 'openai');
     assert.equal(normalized.terminalSessionMode, 'tmux');
+    assert.deepEqual(normalized.githubTokens, { acme: 'ghp_acme' });
   });
 
   it('loads configuration from disk and normalizes it', async () => {
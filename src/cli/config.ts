@@ -6,10 +6,22 @@ import {
   validateString,
   validateBranchLlm,
   validateTerminalSessionMode,
+  validateGitBackend,
   validateCookieSecure,
+  validateStringList,
+  validatePositiveInteger,
+  validateNonNegativeInteger,
+  validateCloneLayout,
+  validateDefaultOrg,
+  validateBasePath,
+  validateGithubApiVersion,
   pickFirst,
   warnConfig,
 } from './validation.js';
+import { isValidEnvName } from '../core/terminal-env.js';
+import { isValidGitConfigEntry } from '../core/git.js';
+import { isCorsOrigin } from '../infrastructure/cors/index.js';
+import { isFeature } from '../config/features.js';
 import type { NormalizedConfig } from './types.js';
 
 interface ConfigSource {
@@ -25,28 +37,29 @@ function pickLlm(sources: ConfigSource[], configPath: string): string | undefine
   return pickFirst(sources, validateBranchLlm, configPath);
 }
 
-function normalizeDefaultBranches(
+function normalizeStringMap(
   config: Record<string, unknown>,
+  name: string,
   configPath: string,
 ): Record<string, string> | undefined {
-  const defaultBranchesOverride =
-    config['defaultBranches'] && typeof config['defaultBranches'] === 'object'
-      ? (config['defaultBranches'] as Record<string, unknown>)
+  const mapping =
+    config[name] && typeof config[name] === 'object'
+      ? (config[name] as Record<string, unknown>)
       : null;
 
-  if (!defaultBranchesOverride) {
+  if (!mapping) {
     return undefined;
   }
 
   const overrides: Record<string, string> = {};
-  for (const [key, value] of Object.entries(defaultBranchesOverride)) {
+  for (const [key, value] of Object.entries(mapping)) {
     if (typeof value === 'string') {
       const trimmed = value.trim();
       if (trimmed) {
         overrides[key.trim()] = trimmed;
       }
     } else {
-      warnConfig(`Ignoring non-string defaultBranches entry for ${key} in ${configPath || 'config'}.`);
+      warnConfig(`Ignoring non-string ${name} entry for ${key} in ${configPath || 'config'}.`);
     }
   }
 
@@ -86,6 +99,12 @@ export function normalizeConfig(rawConfig: unknown, configPath: string): Record<
   const host = validateString(config['host'], 'host', configPath);
   if (host !== undefined) normalized['host'] = host;
 
+  if (typeof config['local'] === 'boolean') {
+    normalized['local'] = config['local'];
+  } else if (config['local'] !== undefined && config['local'] !== null) {
+    warnConfig(`Ignoring non-boolean local in ${configPath || 'config'}.`);
+  }
+
   // UI path
   const ui = pickString(
     [
@@ -96,6 +115,24 @@ export function normalizeConfig(rawConfig: unknown, configPath: string): Record<
   );
   if (ui !== undefined) normalized['ui'] = ui;
 
+  const landingPage = validateString(config['landingPage'], 'landingPage', configPath);
+  if (landingPage !== undefined) normalized['landingPage'] = landingPage;
+
+  const basePath = validateBasePath(config['basePath'], 'basePath', configPath);
+  if (basePath !== undefined) normalized['basePath'] = basePath;
+
+  // Feature flags
+  const features = validateStringList(config['features'], 'features', configPath)
+    ?.map((feature) => feature.toLowerCase())
+    .filter((feature) => {
+      if (!isFeature(feature)) {
+        warnConfig(`Ignoring unknown features entry ${feature} in ${configPath || 'config'}.`);
+        return false;
+      }
+      return true;
+    });
+  if (features && features.length > 0) normalized['features'] = features;
+
   // Working directory
   const workdir = pickString(
     [
@@ -111,7 +148,7 @@ export function normalizeConfig(rawConfig: unknown, configPath: string): Record<
   if (defaultBranch !== undefined) normalized['defaultBranch'] = defaultBranch;
 
   // Default branches overrides
-  const defaultBranches = normalizeDefaultBranches(config, configPath);
+  const defaultBranches = normalizeStringMap(config, 'defaultBranches', configPath);
   if (defaultBranches) normalized['defaultBranches'] = defaultBranches;
 
   // Cookie secure
@@ -210,6 +247,171 @@ export function normalizeConfig(rawConfig: unknown, configPath: string): Record<
   );
   if (terminalSessionMode !== undefined) normalized['terminalSessionMode'] = terminalSessionMode;
 
+  // Extra terminal environment variables
+  const terminalEnv = normalizeStringMap(config, 'terminalEnv', configPath);
+  if (terminalEnv) {
+    const valid = Object.entries(terminalEnv).filter(([name]) => {
+      if (!isValidEnvName(name)) {
+        warnConfig(`Ignoring terminalEnv entry with invalid variable name ${name} in ${configPath || 'config'}.`);
+        return false;
+      }
+      return true;
+    });
+    if (valid.length > 0) normalized['terminalEnv'] = Object.fromEntries(valid);
+  }
+
+  // git config passed to clones and worktree creation
+  const gitConfig = normalizeStringMap(config, 'gitConfig', configPath);
+  if (gitConfig) {
+    const valid = Object.entries(gitConfig).filter(([key, value]) => {
+      if (!isValidGitConfigEntry(key, value)) {
+        warnConfig(`Ignoring invalid gitConfig entry ${key} in ${configPath || 'config'}.`);
+        return false;
+      }
+      return true;
+    });
+    if (valid.length > 0) normalized['gitConfig'] = Object.fromEntries(valid);
+  }
+
+  // Repository URL scheme allowlist
+  const allowedUrlSchemes = validateStringList(
+    config['allowedUrlSchemes'],
+    'allowedUrlSchemes',
+    configPath,
+  );
+  if (allowedUrlSchemes !== undefined) normalized['allowedUrlSchemes'] = allowedUrlSchemes;
+
+  // Git lookup concurrency
+  const gitConcurrency = validatePositiveInteger(config['gitConcurrency'], 'gitConcurrency', configPath);
+  if (gitConcurrency !== undefined) normalized['gitConcurrency'] = gitConcurrency;
+
+  // Clone queue parallelism
+  const maxConcurrentClones = validatePositiveInteger(config['maxConcurrentClones'], 'maxConcurrentClones', configPath);
+  if (maxConcurrentClones !== undefined) normalized['maxConcurrentClones'] = maxConcurrentClones;
+
+  // Clone backend
+  const gitBackend = validateGitBackend(config['gitBackend'], 'gitBackend', configPath);
+  if (gitBackend !== undefined) normalized['gitBackend'] = gitBackend;
+
+  // Repository listing cache lifetime
+  const repoCacheTtlMs = validateNonNegativeInteger(config['repoCacheTtlMs'], 'repoCacheTtlMs', configPath);
+  if (repoCacheTtlMs !== undefined) normalized['repoCacheTtlMs'] = repoCacheTtlMs;
+
+  // Reverse proxy trust
+  if (typeof config['trustProxy'] === 'boolean') {
+    normalized['trustProxy'] = config['trustProxy'];
+  } else if (config['trustProxy'] !== undefined && config['trustProxy'] !== null) {
+    warnConfig(`Ignoring non-boolean trustProxy in ${configPath || 'config'}.`);
+  }
+
+  // GitHub CLI timeout
+  const githubTimeoutMs = validatePositiveInteger(config['githubTimeoutMs'], 'githubTimeoutMs', configPath);
+  if (githubTimeoutMs !== undefined) normalized['githubTimeoutMs'] = githubTimeoutMs;
+  const githubApiVersion = validateGithubApiVersion(config['githubApiVersion'], 'githubApiVersion', configPath);
+  if (githubApiVersion !== undefined) normalized['githubApiVersion'] = githubApiVersion;
+
+  // GitHub webhook signing secret
+  const githubWebhookSecret = validateString(config['githubWebhookSecret'], 'githubWebhookSecret', configPath);
+  if (githubWebhookSecret !== undefined) normalized['githubWebhookSecret'] = githubWebhookSecret;
+
+  // Per-organisation GitHub tokens
+  const githubTokens = normalizeStringMap(config, 'githubTokens', configPath);
+  if (githubTokens) normalized['githubTokens'] = githubTokens;
+
+  // Git identity for new worktrees
+  const gitAuthorName = pickString([{ value: config['gitAuthorName'], name: 'gitAuthorName' }], configPath);
+  if (gitAuthorName !== undefined) normalized['gitAuthorName'] = gitAuthorName;
+  const gitAuthorEmail = pickString([{ value: config['gitAuthorEmail'], name: 'gitAuthorEmail' }], configPath);
+  if (gitAuthorEmail !== undefined) normalized['gitAuthorEmail'] = gitAuthorEmail;
+
+  // Clone directory layout
+  const cloneLayout = validateCloneLayout(config['cloneLayout'], 'cloneLayout', configPath);
+  if (cloneLayout !== undefined) normalized['cloneLayout'] = cloneLayout;
+
+  // Organization for clone URLs that name only a repository
+  const defaultOrg = validateDefaultOrg(config['defaultOrg'], 'defaultOrg', configPath);
+  if (defaultOrg !== undefined) normalized['defaultOrg'] = defaultOrg;
+
+  // Base ref for new worktree branches
+  const defaultBase = pickString([{ value: config['defaultBase'], name: 'defaultBase' }], configPath);
+  if (defaultBase !== undefined && defaultBase.startsWith('-')) {
+    warnConfig(`Ignoring defaultBase starting with "-" in ${configPath || 'config'}.`);
+  } else if (defaultBase !== undefined) {
+    normalized['defaultBase'] = defaultBase;
+  }
+
+  const maxWorktreesPerRepo = validatePositiveInteger(config['maxWorktreesPerRepo'], 'maxWorktreesPerRepo', configPath);
+  if (maxWorktreesPerRepo !== undefined) normalized['maxWorktreesPerRepo'] = maxWorktreesPerRepo;
+
+  const gitOutputLimitBytes = validatePositiveInteger(config['gitOutputLimitBytes'], 'gitOutputLimitBytes', configPath);
+  if (gitOutputLimitBytes !== undefined) normalized['gitOutputLimitBytes'] = gitOutputLimitBytes;
+
+  // Worktree setup command
+  const setupCommand = pickString([{ value: config['setupCommand'], name: 'setupCommand' }], configPath);
+  if (setupCommand !== undefined) normalized['setupCommand'] = setupCommand;
+
+  // Per-client API rate limit (requests per minute)
+  const rateLimit = validatePositiveInteger(config['rateLimit'], 'rateLimit', configPath);
+  if (rateLimit !== undefined) normalized['rateLimit'] = rateLimit;
+
+  // Requests served at once before answering 503
+  const maxConnections = validatePositiveInteger(config['maxConnections'], 'maxConnections', configPath);
+  if (maxConnections !== undefined) normalized['maxConnections'] = maxConnections;
+
+  // Read-only mode
+  if (typeof config['readOnly'] === 'boolean') {
+    normalized['readOnly'] = config['readOnly'];
+  } else if (config['readOnly'] !== undefined && config['readOnly'] !== null) {
+    warnConfig(`Ignoring non-boolean readOnly in ${configPath || 'config'}.`);
+  }
+
+  // Startup GitHub token verification
+  if (typeof config['verifyGithubToken'] === 'boolean') {
+    normalized['verifyGithubToken'] = config['verifyGithubToken'];
+  } else if (config['verifyGithubToken'] !== undefined && config['verifyGithubToken'] !== null) {
+    warnConfig(`Ignoring non-boolean verifyGithubToken in ${configPath || 'config'}.`);
+  }
+
+  if (typeof config['traceGit'] === 'boolean') {
+    normalized['traceGit'] = config['traceGit'];
+  } else if (config['traceGit'] !== undefined && config['traceGit'] !== null) {
+    warnConfig(`Ignoring non-boolean traceGit in ${configPath || 'config'}.`);
+  }
+
+  if (typeof config['signCommits'] === 'boolean') {
+    normalized['signCommits'] = config['signCommits'];
+  } else if (config['signCommits'] !== undefined && config['signCommits'] !== null) {
+    warnConfig(`Ignoring non-boolean signCommits in ${configPath || 'config'}.`);
+  }
+
+  if (typeof config['confirmDangerousTerminals'] === 'boolean') {
+    normalized['confirmDangerousTerminals'] = config['confirmDangerousTerminals'];
+  } else if (config['confirmDangerousTerminals'] !== undefined && config['confirmDangerousTerminals'] !== null) {
+    warnConfig(`Ignoring non-boolean confirmDangerousTerminals in ${configPath || 'config'}.`);
+  }
+
+  const slowRequestMs = validateNonNegativeInteger(config['slowRequestMs'], 'slowRequestMs', configPath);
+  if (slowRequestMs !== undefined) normalized['slowRequestMs'] = slowRequestMs;
+
+  // Cross-origin access to the API
+  const corsOrigins = validateStringList(config['corsOrigins'], 'corsOrigins', configPath)?.filter((origin) => {
+    if (!isCorsOrigin(origin)) {
+      warnConfig(`Ignoring invalid corsOrigins entry ${origin} in ${configPath || 'config'}.`);
+      return false;
+    }
+    return true;
+  });
+  if (corsOrigins && corsOrigins.length > 0) normalized['corsOrigins'] = corsOrigins;
+
+  if (typeof config['corsAllowCredentials'] === 'boolean') {
+    normalized['corsAllowCredentials'] = config['corsAllowCredentials'];
+  } else if (config['corsAllowCredentials'] !== undefined && config['corsAllowCredentials'] !== null) {
+    warnConfig(`Ignoring non-boolean corsAllowCredentials in ${configPath || 'config'}.`);
+  }
+
+  const corsMaxAgeSeconds = validateNonNegativeInteger(config['corsMaxAgeSeconds'], 'corsMaxAgeSeconds', configPath);
+  if (corsMaxAgeSeconds !== undefined) normalized['corsMaxAgeSeconds'] = corsMaxAgeSeconds;
+
   // ngrok
   const ngrokApiKey = pickString(
     [
@@ -7,6 +7,7 @@ export const CONFIG_FILE_NAME = 'config.json';
 export const VALID_BRANCH_LLMS = new Set(['codex', 'claude', 'cursor']);
 export const VALID_TERMINAL_SESSION_MODES = new Set(['auto', 'tmux', 'pty']);
 export const VALID_COOKIE_SECURE_MODES = new Set(['true', 'false', 'auto']);
+export const VALID_GIT_BACKENDS = new Set(['cli', 'libgit2']);
 
 export function getConfigFilePath(): string | null {
   const homeDir = os.homedir();
@@ -1,12 +1,14 @@
-import { DEFAULT_HOST, DEFAULT_PORT } from '../server/index.js';
+import { DEFAULT_HOST, DEFAULT_PORT, LOOPBACK_HOST } from '../server/index.js';
 
 export function printHelp(): void {
   const helpText = `Usage: agentrix [options]
 
 Options:
-  -p, --port <number>    Port to bind the HTTP server (default: ${DEFAULT_PORT})
-  -H, --host <host>      Host interface to bind (default: ${DEFAULT_HOST})
-  -u, --ui <path>        Path to the UI directory or entry file (default: bundled build)
+  -p, --port <number>    Port to bind the HTTP server (default: ${DEFAULT_PORT}; 0 picks a free port)
+  -H, --host <host>      Host interface to bind (default: ${DEFAULT_HOST}, every interface)
+      --local            Bind ${LOOPBACK_HOST} only, overriding --host
+  -u, --ui <path>        Path to the UI directory or entry file (default: $AGENTRIX_FRONTEND_DIR, then bundled build)
+      --frontend-dir <path>  Alias for --ui
   -w, --workdir <path>   Working directory root (default: current directory)
   -P, --password <string>  Password for login (default: randomly generated)
       --default-branch <name>  Override default branch used when syncing repositories
@@ -14,7 +16,9 @@ Options:
       --terminal-session-mode <mode>  Terminal backend preference: auto, tmux, pty (default: auto)
       --force-tmux         Shortcut for --terminal-session-mode tmux (fail if tmux unavailable)
       --no-tmux            Shortcut for --terminal-session-mode pty (disable tmux usage)
+      --terminal-env <KEY=VALUE>  Add a variable to spawned terminals' environment (repeatable)
       --show-password     Print the resolved password even if provided via config or flag
+      --print-startup-json  Print the bound address as one JSON line on stdout once listening
       --codex-command <cmd>   Command executed when launching Codex (default: codex)
       --claude-command <cmd>  Command executed when launching Claude (default: claude)
       --cursor-command <cmd>  Command executed when launching Cursor (default: cursor-agent)
@@ -23,9 +27,44 @@ Options:
       --ngrok-api-key <token> Authtoken used when establishing an ngrok tunnel
       --ngrok-domain <domain> Reserved ngrok domain to expose the server publicly
       --openai-api-key <token> OpenAI API key forwarded to local LLM commands
+      --trust-proxy        Honour X-Forwarded-For/-Proto/-Host from a reverse proxy (first hop only)
+      --git-concurrency <n>   Max repositories inspected concurrently when scanning the workdir (default: 8)
+      --max-concurrent-clones <n>  Max clones running at once; further clones wait in a queue (default: 2)
+      --git-backend <cli|libgit2>  Clone with the git CLI or in-process via libgit2 (optional nodegit) (default: cli)
+      --repo-cache-ttl <ms>   Max age of the cached repository listing; 0 rescans on every request (default: 30000)
+      --allowed-url-schemes <list>  Comma-separated clone URL schemes (default: https,ssh,git@; add file for local paths)
+      --github-timeout <ms>   Timeout for each GitHub CLI call before returning 504 (default: 10000)
+      --github-api-version <date>  GitHub REST API version sent with gh api requests (default: 2022-11-28)
+      --github-webhook-secret <secret>  Secret for signed GitHub webhooks at /api/github/webhook; caches dashboard summaries
+      --git-config <KEY=VALUE>  git config (e.g. http.proxy) passed with -c to clones and worktree creation; repeatable
+      --git-author-name <name>    Commit author name written to new worktrees' git config
+      --git-author-email <email>  Commit author email written to new worktrees' git config
+      --clone-layout <template>   Repository directory layout under the workdir (default: {org}/{repo})
+      --default-org <name>    Organization for clone URLs that name only a repository (https://host/repo.git)
+      --default-base <ref>    Ref new worktree branches start from when a request names no base
+      --max-worktrees-per-repo <n>  Max worktrees per repository besides the primary clone; more are refused with 409 (default: unlimited)
+      --git-output-limit <bytes>  Bytes kept from a diff or blame before it is cut short and flagged truncated (default: 4194304)
+      --setup-command <cmd>   Command run by POST /api/worktrees/setup when a worktree has no .agentrix/setup
+      --rate-limit <n>        Max /api requests per minute per client IP, answered with 429 beyond it (default: off)
+      --max-connections <n>   Max requests served at once, answered with 503 beyond it; health checks exempt (default: 1024)
+      --read-only          Reject every mutating API request with 403 (viewing only)
+      --verify-github-token  Check the GitHub CLI token against /user at startup and log the result
+      --trace-git          Log every git command line (credentials in URLs masked)
+      --sign-commits       Sign every commit made through the API (requires user.signingKey)
+      --confirm-dangerous-terminals  Refuse dangerous agent terminals with 412 unless the request confirms them
+      --slow-request-ms <ms>  Warn about requests slower than this, with route, latency and request id (default: 0, off)
+      --cors-origin <origins>  Origins allowed to call the API from a browser, comma-separated or repeated; * for any
+      --cors-allow-credentials  Let allowed origins send the session cookie (not with *)
+      --cors-max-age <seconds>  How long browsers may cache a CORS preflight answer
+      --landing-page <path>  Page served instead of the UI when no frontend is built (default: built-in placeholder)
+      --base-path <path>     Serve the API and UI under this path prefix, e.g. /agentrix (default: /)
+      --feature <names>      Only serve these features, comma-separated or repeated: github, terminals, agents,
+                             ports, git-write (default: $AGENTRIX_FEATURES, then all)
       --save               Persist the effective configuration and exit
+  -q, --quiet            Only log errors
+  -v, --verbose          Log more; repeat for more detail (-v info, -vv debug, -vvv trace). $AGENTRIX_LOG wins
   -h, --help             Display this help message
-  -v, --version          Output the version number
+  -V, --version          Output the version number
 `;
   process.stdout.write(helpText);
 }
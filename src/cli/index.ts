@@ -15,7 +15,7 @@
 
 export { parseArgs } from './arg-parser.js';
 export { loadConfig, saveConfig, normalizeConfig } from './config.js';
-export { resolveConfig, validateNgrokConfig, buildConfigToSave } from './config-resolver.js';
+export { resolveConfig, validateNgrokConfig, validateCorsConfig, buildConfigToSave } from './config-resolver.js';
 export { printHelp, printVersion } from './help.js';
 export { handlePlansCommand } from './plans-command.js';
 export { startAppServer } from './server-starter.js';
@@ -134,5 +134,75 @@ describe('server starter', () => {
     assert.ok(!output.includes('operator-pass'));
     assert.ok(!output.includes('should-not-be-used'));
   });
+
+  it('prints a single JSON startup line on stdout when requested', async () => {
+    const startServerMock = mock.fn(async () => ({
+      server: {},
+      host: '127.0.0.1',
+      port: 41234,
+      uiPath: '/tmp/ui',
+      close: mock.fn(async () => {}),
+      password: null,
+      publicUrl: null,
+    }));
+
+    const capture = (stream: NodeJS.WriteStream, sink: string[]) =>
+      mock.method(stream, 'write', (chunk: string | Uint8Array) => {
+        sink.push(typeof chunk === 'string' ? chunk : Buffer.from(chunk).toString('utf8'));
+        return true;
+      });
+    const stdout: string[] = [];
+    const stderr: string[] = [];
+    const stdoutMock = capture(process.stdout, stdout);
+    const stderrMock = capture(process.stderr, stderr);
+    const onMock = mock.method(process, 'on', () => process);
+
+    __setServerStarterTestOverrides({
+      startServer: startServerMock,
+      generateRandomPassword: () => 'generated-pass',
+    });
+
+    try {
+      await startAppServer({
+        uiPath: './ui',
+        port: 0,
+        host: '127.0.0.1',
+        workdir: '/repo',
+        password: null,
+        showPassword: false,
+        defaultBranch: null,
+        defaultBranches: null,
+        cookieSecure: 'auto',
+        codexCommand: null,
+        claudeCommand: null,
+        cursorCommand: null,
+        ideCommand: null,
+        vscodeCommand: null,
+        ngrokApiKey: null,
+        ngrokDomain: null,
+        automationApiKey: null,
+        openaiApiKey: null,
+        branchNameLlm: null,
+        planLlm: null,
+        terminalSessionMode: 'auto',
+        printStartupJson: true,
+      });
+    } finally {
+      __setServerStarterTestOverrides();
+      stdoutMock.mock.restore();
+      stderrMock.mock.restore();
+      onMock.mock.restore();
+    }
+
+    const lines = stdout.join('').split('\n').filter(Boolean);
+    assert.equal(lines.length, 1);
+    assert.deepEqual(JSON.parse(lines[0] ?? ''), {
+      address: '127.0.0.1',
+      port: 41234,
+      workdir: '/repo',
+      worktrees_root: '/repo',
+    });
+    assert.ok(stderr.join('').includes('Password: generated-pass'));
+  });
 });
 
@@ -1,4 +1,6 @@
-import { startServer, generateRandomPassword } from '../server/index.js';
+import path from 'node:path';
+import { startServer, generateRandomPassword, DEFAULT_HOST } from '../server/index.js';
+import type { Feature } from '../config/features.js';
 
 interface ServerConfig {
   uiPath: string;
@@ -22,6 +24,48 @@ interface ServerConfig {
   branchNameLlm: string | null;
   planLlm: string | null;
   terminalSessionMode: string;
+  terminalEnv?: Record<string, string> | null;
+  gitConfig?: Record<string, string> | null;
+  allowedUrlSchemes?: string[] | null;
+  gitConcurrency?: number | null;
+  maxConcurrentClones?: number | null;
+  gitBackend?: string | null;
+  repoCacheTtlMs?: number | null;
+  trustProxy?: boolean;
+  githubTimeoutMs?: number | null;
+  githubApiVersion?: string | null;
+  githubWebhookSecret?: string | null;
+  githubTokens?: Record<string, string> | null;
+  gitAuthorName?: string | null;
+  gitAuthorEmail?: string | null;
+  cloneLayout?: string | null;
+  defaultOrg?: string | null;
+  defaultBase?: string | null;
+  maxWorktreesPerRepo?: number | null;
+  gitOutputLimitBytes?: number | null;
+  setupCommand?: string | null;
+  rateLimit?: number | null;
+  maxConnections?: number | null;
+  readOnly?: boolean;
+  verifyGithubToken?: boolean;
+  traceGit?: boolean;
+  signCommits?: boolean;
+  confirmDangerousTerminals?: boolean;
+  slowRequestMs?: number | null;
+  corsOrigins?: string[] | null;
+  corsAllowCredentials?: boolean;
+  corsMaxAgeSeconds?: number | null;
+  landingPage?: string | null;
+  basePath?: string | null;
+  features?: Feature[] | null;
+  printStartupJson?: boolean;
+}
+
+export interface StartupSummary {
+  address: string;
+  port: number;
+  workdir: string;
+  worktrees_root: string;
 }
 
 interface ServerStarterDependencies {
@@ -44,6 +88,18 @@ export function __setServerStarterTestOverrides(overrides?: Partial<ServerStarte
   activeDependencies = { ...activeDependencies, ...overrides };
 }
 
+/**
+ * Builds the machine-readable startup line printed by `--print-startup-json`
+ * @param host - Address the server is bound to
+ * @param port - Port the server is bound to, as assigned by the OS when 0 was requested
+ * @param workdir - Configured work directory
+ */
+export function buildStartupSummary(host: string, port: number, workdir: string): StartupSummary {
+  const resolvedWorkdir = path.resolve(workdir);
+  // Clone layouts are relative to the workdir, so every worktree lives beneath it
+  return { address: host, port, workdir: resolvedWorkdir, worktrees_root: resolvedWorkdir };
+}
+
 export async function startAppServer(config: ServerConfig): Promise<void> {
   const chosenPassword = config.password || activeDependencies.generateRandomPassword();
   const passwordWasProvided = config.password !== null;
@@ -93,24 +149,67 @@ export async function startAppServer(config: ServerConfig): Promise<void> {
     defaultBranches: defaultBranchConfig as never,
     cookieSecure: (config.cookieSecure ?? undefined) as string | boolean | undefined,
     terminalSessionMode: (config.terminalSessionMode ?? undefined) as 'auto' | 'tmux' | 'pty' | undefined,
+    terminalEnv: config.terminalEnv ?? undefined,
+    gitConfig: config.gitConfig ?? undefined,
+    allowedUrlSchemes: config.allowedUrlSchemes ?? undefined,
+    gitConcurrency: config.gitConcurrency ?? undefined,
+    maxConcurrentClones: config.maxConcurrentClones ?? undefined,
+    gitBackend: (config.gitBackend ?? undefined) as 'cli' | 'libgit2' | undefined,
+    repoCacheTtlMs: config.repoCacheTtlMs ?? undefined,
+    trustProxy: config.trustProxy ?? false,
+    githubTimeoutMs: config.githubTimeoutMs ?? undefined,
+    githubApiVersion: config.githubApiVersion ?? undefined,
+    githubWebhookSecret: config.githubWebhookSecret ?? undefined,
+    githubTokens: config.githubTokens ?? undefined,
+    gitAuthorName: config.gitAuthorName ?? undefined,
+    gitAuthorEmail: config.gitAuthorEmail ?? undefined,
+    cloneLayout: config.cloneLayout ?? undefined,
+    defaultOrg: config.defaultOrg ?? undefined,
+    defaultBase: config.defaultBase ?? undefined,
+    maxWorktreesPerRepo: config.maxWorktreesPerRepo ?? undefined,
+    gitOutputLimitBytes: config.gitOutputLimitBytes ?? undefined,
+    setupCommand: config.setupCommand ?? undefined,
+    rateLimit: config.rateLimit ?? undefined,
+    maxConnections: config.maxConnections ?? undefined,
+    readOnly: config.readOnly ?? false,
+    verifyGithubToken: config.verifyGithubToken ?? false,
+    traceGit: config.traceGit ?? false,
+    signCommits: config.signCommits ?? false,
+    confirmDangerousTerminals: config.confirmDangerousTerminals ?? false,
+    slowRequestMs: config.slowRequestMs ?? undefined,
+    corsOrigins: config.corsOrigins ?? undefined,
+    corsAllowCredentials: config.corsAllowCredentials ?? false,
+    corsMaxAgeSeconds: config.corsMaxAgeSeconds ?? undefined,
+    landingPage: config.landingPage ?? undefined,
+    basePath: config.basePath ?? undefined,
+    features: config.features ?? undefined,
   });
 
+  // With --print-startup-json stdout carries only the JSON line, so the human messages go to stderr
+  const messages = config.printStartupJson ? process.stderr : process.stdout;
+  if (config.printStartupJson) {
+    process.stdout.write(`${JSON.stringify(buildStartupSummary(host, port, config.workdir))}\n`);
+  }
+
   const localAddress = host === '0.0.0.0' ? 'localhost' : host;
-  process.stdout.write(`Serving UI from ${resolvedUi}\n`);
-  process.stdout.write(`Working directory set to ${config.workdir}\n`);
-  process.stdout.write(`Listening on http://${localAddress}:${port}\n`);
+  messages.write(`Serving UI from ${resolvedUi || 'the built-in placeholder page'}\n`);
+  messages.write(`Working directory set to ${config.workdir}\n`);
+  messages.write(`Listening on http://${localAddress}:${port}${config.basePath ? `${config.basePath}/` : ''}\n`);
+  if (host === DEFAULT_HOST) {
+    messages.write('Reachable from the network on every interface; pass --local to accept local connections only\n');
+  }
 
   const effectivePassword = serverPassword || chosenPassword;
   if (shouldPrintPassword) {
-    process.stdout.write(`Password: ${effectivePassword}\n`);
+    messages.write(`Password: ${effectivePassword}\n`);
   } else {
-    process.stdout.write(
+    messages.write(
       'Password logging suppressed (operator-provided password). Use --show-password to print.\n',
     );
   }
 
   if (publicUrl) {
-    process.stdout.write(`Public URL (ngrok): ${publicUrl}\n`);
+    messages.write(`Public URL (ngrok): ${publicUrl}\n`);
   }
 
   setupShutdownHandlers(close);
@@ -1,6 +1,9 @@
+import type { Feature } from '../config/features.js';
+
 export interface CliConfig {
   port: number;
   host: string;
+  local: boolean;
   ui: string | null;
   workdir: string | null;
   password: string | null;
@@ -8,6 +11,9 @@ export interface CliConfig {
   defaultBranch: string | null;
   defaultBranches?: Record<string, string>;
   showPassword: boolean;
+  printStartupJson: boolean;
+  quiet: boolean;
+  verbose: number;
   codexCommand: string | null;
   claudeCommand: string | null;
   cursorCommand: string | null;
@@ -20,6 +26,40 @@ export interface CliConfig {
   branchNameLlm?: string | null;
   planLlm?: string | null;
   terminalSessionMode: string | null;
+  terminalEnv: Record<string, string> | null;
+  gitConfig: Record<string, string> | null;
+  allowedUrlSchemes: string[] | null;
+  gitConcurrency: number | null;
+  maxConcurrentClones: number | null;
+  gitBackend: string | null;
+  repoCacheTtlMs: number | null;
+  trustProxy: boolean;
+  githubTimeoutMs: number | null;
+  githubApiVersion: string | null;
+  githubWebhookSecret: string | null;
+  githubTokens?: Record<string, string>;
+  gitAuthorName: string | null;
+  gitAuthorEmail: string | null;
+  cloneLayout: string | null;
+  defaultOrg: string | null;
+  defaultBase: string | null;
+  maxWorktreesPerRepo: number | null;
+  gitOutputLimitBytes: number | null;
+  setupCommand: string | null;
+  rateLimit: number | null;
+  maxConnections: number | null;
+  readOnly: boolean;
+  verifyGithubToken: boolean;
+  traceGit: boolean;
+  signCommits: boolean;
+  confirmDangerousTerminals: boolean;
+  slowRequestMs: number | null;
+  corsOrigins: string[] | null;
+  corsAllowCredentials: boolean;
+  corsMaxAgeSeconds: number | null;
+  landingPage: string | null;
+  basePath: string | null;
+  features: Feature[] | null;
   save: boolean;
   help: boolean;
   version: boolean;
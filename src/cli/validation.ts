@@ -2,7 +2,11 @@ import {
   VALID_BRANCH_LLMS,
   VALID_TERMINAL_SESSION_MODES,
   VALID_COOKIE_SECURE_MODES,
+  VALID_GIT_BACKENDS,
 } from './constants.js';
+import { parseCloneLayout, validateRepositorySegment } from '../domain/index.js';
+import { isGithubApiVersion } from '../core/github.js';
+import { normalizeBasePath } from '../utils/base-path.js';
 
 export class ValidationError extends Error {
   constructor(message: string) {
@@ -95,6 +99,21 @@ export function validateTerminalSessionMode(value: unknown, name: string, config
   return lower;
 }
 
+export function validateGitBackend(value: unknown, name: string, configPath: string): string | undefined {
+  const stringValue = validateString(value, name, configPath);
+  if (stringValue === undefined) {
+    return undefined;
+  }
+
+  const lower = stringValue.toLowerCase();
+  if (!VALID_GIT_BACKENDS.has(lower)) {
+    warnConfig(`Ignoring invalid ${name} in ${configPath || 'config'}; expected one of ${[...VALID_GIT_BACKENDS].join(', ')}.`);
+    return undefined;
+  }
+
+  return lower;
+}
+
 export function validateCookieSecure(value: unknown, name: string, configPath: string): string | undefined {
   if (typeof value === 'string') {
     const trimmed = value.trim().toLowerCase();
@@ -112,6 +131,133 @@ export function validateCookieSecure(value: unknown, name: string, configPath: s
   return undefined;
 }
 
+export function validatePositiveInteger(value: unknown, name: string, configPath: string): number | undefined {
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+
+  const parsed = typeof value === 'string' ? Number.parseInt(value.trim(), 10) : value;
+  if (typeof parsed !== 'number' || !Number.isInteger(parsed) || parsed < 1) {
+    warnConfig(`Ignoring invalid ${name} in ${configPath || 'config'}; expected a positive integer.`);
+    return undefined;
+  }
+
+  return parsed;
+}
+
+export function validateNonNegativeInteger(value: unknown, name: string, configPath: string): number | undefined {
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+
+  const parsed = typeof value === 'string' ? Number.parseInt(value.trim(), 10) : value;
+  if (typeof parsed !== 'number' || !Number.isInteger(parsed) || parsed < 0) {
+    warnConfig(`Ignoring invalid ${name} in ${configPath || 'config'}; expected a non-negative integer.`);
+    return undefined;
+  }
+
+  return parsed;
+}
+
+export function validateStringList(value: unknown, name: string, configPath: string): string[] | undefined {
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+
+  const rawItems = typeof value === 'string' ? value.split(',') : Array.isArray(value) ? value : null;
+  if (!rawItems) {
+    warnConfig(`Ignoring ${name} in ${configPath || 'config'}; expected an array or comma-separated string.`);
+    return undefined;
+  }
+
+  const items: string[] = [];
+  for (const item of rawItems) {
+    if (typeof item !== 'string') {
+      warnConfig(`Ignoring non-string ${name} entry in ${configPath || 'config'}.`);
+      continue;
+    }
+    const trimmed = item.trim();
+    if (trimmed) {
+      items.push(trimmed);
+    }
+  }
+
+  if (items.length === 0) {
+    warnConfig(`Ignoring empty ${name} in ${configPath || 'config'}.`);
+    return undefined;
+  }
+
+  return items;
+}
+
+export function validateCloneLayout(value: unknown, name: string, configPath: string): string | undefined {
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+
+  if (typeof value !== 'string') {
+    warnConfig(`Ignoring non-string ${name} in ${configPath || 'config'}.`);
+    return undefined;
+  }
+
+  try {
+    return parseCloneLayout(value).template;
+  } catch (error: unknown) {
+    warnConfig(`Ignoring invalid ${name} in ${configPath || 'config'}: ${(error as Error).message}.`);
+    return undefined;
+  }
+}
+
+export function validateDefaultOrg(value: unknown, name: string, configPath: string): string | undefined {
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+
+  if (typeof value !== 'string') {
+    warnConfig(`Ignoring non-string ${name} in ${configPath || 'config'}.`);
+    return undefined;
+  }
+
+  try {
+    return validateRepositorySegment(value, 'organization');
+  } catch (error: unknown) {
+    warnConfig(`Ignoring invalid ${name} in ${configPath || 'config'}: ${(error as Error).message}.`);
+    return undefined;
+  }
+}
+
+export function validateBasePath(value: unknown, name: string, configPath: string): string | undefined {
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+
+  if (typeof value !== 'string') {
+    warnConfig(`Ignoring non-string ${name} in ${configPath || 'config'}.`);
+    return undefined;
+  }
+
+  try {
+    return normalizeBasePath(value) || undefined;
+  } catch (error: unknown) {
+    warnConfig(`Ignoring invalid ${name} in ${configPath || 'config'}: ${(error as Error).message}.`);
+    return undefined;
+  }
+}
+
+export function validateGithubApiVersion(value: unknown, name: string, configPath: string): string | undefined {
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+
+  const trimmed = typeof value === 'string' ? value.trim() : value;
+  if (!isGithubApiVersion(trimmed)) {
+    warnConfig(`Ignoring invalid ${name} in ${configPath || 'config'}: expected a YYYY-MM-DD version.`);
+    return undefined;
+  }
+
+  return trimmed;
+}
+
 export function pickFirst<T>(
   sources: Array<{ value: unknown; name: string }>,
   validator: (value: unknown, name: string, configPath: string) => T | undefined,
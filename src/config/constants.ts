@@ -1,4 +1,5 @@
 export const DEFAULT_HOST = '0.0.0.0';
+export const LOOPBACK_HOST = '127.0.0.1';
 export const DEFAULT_PORT = 3414;
 export const MAX_TERMINAL_BUFFER = 200000;
 export const PASSWORD_LENGTH = 12;
@@ -7,6 +8,10 @@ export const SESSION_MAX_AGE_SECONDS = 60 * 60 * 8;
 export const TMUX_BIN = 'tmux';
 export const TMUX_SESSION_PREFIX = 'tw-';
 export const MAX_REQUEST_BODY_SIZE = 1024 * 1024;
+/**
+ * Version of the JSON API, raised when a response shape changes incompatibly
+ */
+export const API_VERSION = '1';
 
 export const AUTH_RATE_LIMIT_WINDOW_MS = 5 * 60 * 1000;
 export const AUTH_RATE_LIMIT_MAX_ATTEMPTS = 5;
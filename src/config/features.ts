@@ -0,0 +1,49 @@
+/**
+ * Groups of API routes an operator can switch off, each named by the `--feature` flag
+ *
+ * - `github`: routes that call GitHub (notifications, READMEs, rate limits, issues, pull request lookups, webhooks)
+ * - `terminals`: terminal sessions, over HTTP and the terminal WebSocket
+ * - `agents`: Codex SDK sessions and automation launches
+ * - `ports`: listing listening ports and opening tunnels to them
+ * - `git-write`: operations that change a repository or worktree beyond creating and removing them (commit,
+ *   push, reset, cherry-pick, stash, rename, upstream, setup runs, repository moves, init commands, metadata)
+ */
+export const FEATURES = ['github', 'terminals', 'agents', 'ports', 'git-write'] as const;
+
+export type Feature = (typeof FEATURES)[number];
+
+/**
+ * Environment variable listing the enabled features, consulted after the CLI flag and before the config file
+ */
+export const FEATURES_ENV = 'AGENTRIX_FEATURES';
+
+export function isFeature(value: string): value is Feature {
+  return (FEATURES as readonly string[]).includes(value);
+}
+
+/**
+ * Splits a comma-separated feature list, as given to `--feature` or `AGENTRIX_FEATURES`
+ * @param value - Feature names, e.g. `github,terminals`
+ * @returns The named features
+ * @throws {Error} If a name is not a known feature
+ */
+export function parseFeatureList(value: string): Feature[] {
+  const names = value
+    .split(',')
+    .map((entry) => entry.trim().toLowerCase())
+    .filter(Boolean);
+  const unknown = names.find((name) => !isFeature(name));
+  if (unknown !== undefined) {
+    throw new Error(`Unknown feature ${unknown} (expected one of ${FEATURES.join(', ')})`);
+  }
+  return names as Feature[];
+}
+
+/**
+ * Works out which features are on
+ * @param features - Features the operator enabled; every feature is on when none are named
+ * @returns The enabled features
+ */
+export function resolveEnabledFeatures(features?: readonly Feature[] | null): ReadonlySet<Feature> {
+  return new Set(features && features.length > 0 ? features : FEATURES);
+}
@@ -83,6 +83,10 @@ export interface LaunchAgentParams {
   repo: string;
   branch: string;
   prompt: string;
+  /**
+   * Extra environment variables for the agent's terminal
+   */
+  env?: Record<string, string> | null;
 }
 
 export interface LaunchAgentResult {
@@ -106,6 +110,7 @@ export async function launchAgentProcess({
   repo,
   branch,
   prompt,
+  env,
 }: LaunchAgentParams): Promise<LaunchAgentResult> {
   const deps = resolveDependencies();
   if (!command || typeof command !== 'string' || !command.trim()) {
@@ -117,7 +122,7 @@ export async function launchAgentProcess({
 
   const executable = command.trim();
   const promptValue = typeof prompt === 'string' ? prompt : '';
-  const session = await deps.createIsolatedTerminalSession(workdir, org, repo, branch);
+  const session = await deps.createIsolatedTerminalSession(workdir, org, repo, branch, { env });
 
   if (session.worktreePath) {
     try {
@@ -3,6 +3,7 @@ import path from 'node:path';
 import os from 'node:os';
 import { createHash } from 'node:crypto';
 import type { CodexSdkEvent } from '../types/codex-sdk.js';
+import { isValidSessionId } from '../domain/index.js';
 
 const HOME_STORAGE_DIR = '.codex';
 const NAMESPACE_DIR = 'agentrix';
@@ -31,7 +32,7 @@ function getSessionDirectory(worktreePath: string): string | null {
 
 function getSessionPath(worktreePath: string, sessionId: string): string | null {
   const directory = getSessionDirectory(worktreePath);
-  if (!directory) {
+  if (!directory || !isValidSessionId(sessionId)) {
     return null;
   }
   return path.join(directory, `${sessionId}.json`);
@@ -46,7 +47,7 @@ export async function listStoredSessions(worktreePath: string): Promise<CodexSdk
     const files = await readdir(directory);
     const results: CodexSdkStoredSession[] = [];
     for (const file of files) {
-      if (!file.endsWith('.json')) {
+      if (!file.endsWith('.json') || !isValidSessionId(file.slice(0, -'.json'.length))) {
         continue;
       }
       const filePath = path.join(directory, file);
@@ -0,0 +1,87 @@
+/**
+ * Matches the flags codex and claude use to skip their approval prompts and sandbox
+ */
+const DANGEROUS_FLAG_PATTERN = /(?:^|\s)--dangerously-/;
+
+/**
+ * Error raised when a dangerous terminal is requested without `confirmDangerous: true`
+ */
+export class DangerousTerminalConfirmationRequiredError extends Error {
+  public readonly statusCode: number = 412;
+  public readonly code = 'dangerous_confirmation_required';
+
+  constructor() {
+    super('This terminal runs an agent without its safeguards; resend the request with confirmDangerous: true');
+    this.name = 'DangerousTerminalConfirmationRequiredError';
+  }
+}
+
+export interface DangerousTerminalOptions {
+  /**
+   * Refuse dangerous terminals unless the request confirms them
+   */
+  requireConfirmation?: boolean;
+  /**
+   * Commands that count as dangerous, such as the configured `codexDangerous` and `claudeDangerous`
+   */
+  commands?: readonly string[];
+}
+
+export interface DangerousTerminalRequest {
+  org: string;
+  repo: string;
+  branch: string;
+  command?: string;
+  /**
+   * The client marked the terminal as dangerous
+   */
+  dangerous?: boolean;
+  confirmDangerous?: boolean;
+}
+
+function normaliseCommand(command: string): string {
+  return command.trim().replace(/\s+/g, ' ');
+}
+
+/**
+ * Checks whether a terminal request is dangerous: marked so by the client, or running one of the
+ * given dangerous commands or a command that passes a `--dangerously-…` flag
+ * @param request - Command and client flag from the open request
+ * @param commands - Commands that count as dangerous
+ */
+export function isDangerousTerminal(
+  { command = '', dangerous = false }: Pick<DangerousTerminalRequest, 'command' | 'dangerous'>,
+  commands: readonly string[] = []
+): boolean {
+  if (dangerous) {
+    return true;
+  }
+  const normalised = normaliseCommand(command);
+  return (
+    Boolean(normalised) &&
+    (commands.some((candidate) => normaliseCommand(candidate) === normalised) || DANGEROUS_FLAG_PATTERN.test(normalised))
+  );
+}
+
+/**
+ * Lets a terminal request through the dangerous-terminal gate, logging every dangerous terminal that is
+ * allowed to start
+ * @param request - Worktree, command and flags from the open request
+ * @param options - Gate settings; the gate is off and only flagged commands are dangerous when omitted
+ * @throws {DangerousTerminalConfirmationRequiredError} If the gate is on and a dangerous terminal is not confirmed
+ */
+export function assertDangerousTerminalAllowed(
+  request: DangerousTerminalRequest,
+  { requireConfirmation = false, commands = [] }: DangerousTerminalOptions = {}
+): void {
+  if (!isDangerousTerminal(request, commands)) {
+    return;
+  }
+  if (requireConfirmation && request.confirmDangerous !== true) {
+    throw new DangerousTerminalConfirmationRequiredError();
+  }
+  const command = request.command?.trim() || '(no command)';
+  console.warn(
+    `[agentrix] Starting dangerous terminal for ${request.org}/${request.repo} branch ${request.branch}: ${command}`
+  );
+}
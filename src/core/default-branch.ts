@@ -1,7 +1,4 @@
-import { promisify } from 'node:util';
-import * as childProcess from 'node:child_process';
-
-const execFileAsync = promisify(childProcess.execFile);
+import { executeGitCommand, executeGitCommandInRepo } from '../repositories/git-repository.js';
 
 export interface DefaultBranchOptions {
   remote?: string;
@@ -32,7 +29,7 @@ export async function resolveDefaultBranch(
   }
 
   try {
-    const { stdout } = await execFileAsync('git', ['-C', repositoryPath, 'symbolic-ref', 'HEAD'], {
+    const { stdout } = await executeGitCommandInRepo(repositoryPath, ['symbolic-ref', 'HEAD'], {
       maxBuffer: 1024 * 1024,
     });
     const headBranch = parseRef(stdout);
@@ -44,7 +41,7 @@ export async function resolveDefaultBranch(
   }
 
   try {
-    const { stdout } = await execFileAsync('git', ['remote', 'show', remote], {
+    const { stdout } = await executeGitCommand(['remote', 'show', remote], {
       cwd: repositoryPath,
       maxBuffer: 1024 * 1024,
     });
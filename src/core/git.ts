@@ -11,40 +11,253 @@ export {
 } from '../domain/index.js';
 export type { GitUrlParts } from '../domain/index.js';
 
+// Re-export from git repository
+export {
+  GitNotFoundError,
+  DEFAULT_GIT_OUTPUT_LIMIT_BYTES,
+  configureGitTracing,
+  isValidGitConfigEntry,
+  resolveGitOutputLimit,
+  probeGitVersion,
+  stripUrlCredentials,
+} from '../repositories/git-repository.js';
+
 // Re-export from worktree repository
 export {
   GitWorktreeError,
+  WorktreeNotFoundError,
+  WorktreeConflictError,
+  InvalidBranchNameError,
+  InvalidRepositoryError,
+  RepositoryNotFoundError,
+  assertGitWorkTree,
   listWorktrees,
   countLocalWorktrees,
   createWorktree,
+  assertWorktreeCapacity,
+  WorktreeLimitReachedError,
+  verifyWorktreeBase,
+  InvalidBaseRefError,
+  planWorktree,
+  checkWorktreeBranch,
+  findExistingWorktree,
+  clearWorktreeTarget,
+  syncDefaultBranch,
+  createWorktrees,
   getWorktreePath,
   removeWorktree,
+  renameWorktree,
+  planWorktreeRename,
 } from '../repositories/worktree-repository.js';
 export type {
   WorktreeEntry,
   CreateWorktreeOptions,
   WorktreePathResult,
   InitCommandResult,
+  RenameWorktreeResult,
+  WorktreeRenamePlan,
+  WorktreePlan,
+  WorktreeBranchCheck,
+  WorktreeBranchReason,
+  ExistingWorktree,
+  WorktreeBatchItemResult,
+  GitIdentity,
+  WorktreeSettings,
 } from '../repositories/worktree-repository.js';
 
 // Re-export from repository repository
 export {
   ensureRepository,
   cloneRepository,
+  cancelClone,
+  listActiveClones,
+  createCloneQueue,
+  getCloneQueueStats,
+  DEFAULT_MAX_CONCURRENT_CLONES,
+  CloneCancelledError,
+  CloneInProgressError,
   discoverRepositories,
+  listRepositoryTree,
+  getRepositoryBranch,
+  switchRepositoryBranch,
+  getRemoteUrl,
+  getRemoteSlug,
+  resolveRepositorySlug,
+  RepositoryDirtyError,
 } from '../repositories/repository-repository.js';
 export type {
+  RepositoryBranchInfo,
+  RepositorySlug,
   RepositoryPaths,
   CloneResult,
   CloneOptions,
+  CloneSettings,
+  ActiveClone,
+  CloneQueueStats,
   RepositoriesMap,
+  RepositoryTreeEntry,
 } from '../repositories/repository-repository.js';
 
+// Re-export from clone backend
+export { CLONE_BACKENDS, CloneBackendUnavailableError, isCloneBackendName } from '../repositories/clone-backend.js';
+export type { CloneBackendName, CloneProgress } from '../repositories/clone-backend.js';
+
 // Re-export from git status repository
 export {
   getWorktreeStatus,
+  getWorktreeSummary,
   getWorktreeFileDiff,
+  NO_DIFF_PLACEHOLDER,
 } from '../repositories/git-status-repository.js';
 
+// Re-export from git diff stat repository
+export { getWorktreeDiffStat, parseDiffStat } from '../repositories/git-diff-stat-repository.js';
+export type {
+  DiffStat,
+  DiffStatFile,
+  DiffStatFileStatus,
+  DiffStatMode,
+  WorktreeDiffStat,
+} from '../repositories/git-diff-stat-repository.js';
+
+// Re-export from worktree file repository
+export {
+  readWorktreeFile,
+  resolveWorktreeFile,
+  sniffContentType,
+  DEFAULT_MAX_WORKTREE_FILE_BYTES,
+  WorktreeFilePathError,
+  WorktreeFileNotFoundError,
+  WorktreeFileTooLargeError,
+} from '../repositories/worktree-file-repository.js';
+export type { WorktreeFile, ReadWorktreeFileOptions, ResolvedWorktreeFile } from '../repositories/worktree-file-repository.js';
+
+// Re-export from worktree blame repository
+export {
+  blameWorktreeFile,
+  parseBlamePorcelain,
+  UNCOMMITTED_BLAME_COMMIT,
+  WorktreeFileUntrackedError,
+  BlameRangeError,
+} from '../repositories/worktree-blame-repository.js';
+export type { BlameLine, WorktreeBlame, BlameWorktreeFileOptions } from '../repositories/worktree-blame-repository.js';
+
+// Re-export from worktree commit repository
+export {
+  commitWorktree,
+  NothingToCommitError,
+  SigningKeyNotConfiguredError,
+} from '../repositories/worktree-commit-repository.js';
+export type { CommitWorktreeOptions, CommitWorktreeResult } from '../repositories/worktree-commit-repository.js';
+
+// Re-export from worktree push repository
+export { pushWorktree, PushRejectedError, RemoteNotFoundError } from '../repositories/worktree-push-repository.js';
+export type { PushWorktreeOptions, PushWorktreeResult } from '../repositories/worktree-push-repository.js';
+
+// Re-export from worktree reset repository
+export { resetWorktree, NoUpstreamError, InvalidResetRefError } from '../repositories/worktree-reset-repository.js';
+export type { ResetWorktreeOptions, ResetWorktreeResult } from '../repositories/worktree-reset-repository.js';
+
+// Re-export from worktree cherry-pick repository
+export {
+  cherryPickWorktree,
+  abortCherryPick,
+  InvalidCherryPickCommitError,
+  CherryPickConflictError,
+  CherryPickStateError,
+} from '../repositories/worktree-cherry-pick-repository.js';
+export type { CherryPickWorktreeOptions, CherryPickWorktreeResult } from '../repositories/worktree-cherry-pick-repository.js';
+
+// Re-export from worktree stash repository
+export {
+  listWorktreeStashes,
+  pushWorktreeStash,
+  popWorktreeStash,
+  DEFAULT_STASH_MESSAGE,
+  StashStateError,
+  StashConflictError,
+} from '../repositories/worktree-stash-repository.js';
+export type { WorktreeStash, PushWorktreeStashOptions } from '../repositories/worktree-stash-repository.js';
+
+// Re-export from worktree upstream repository
+export {
+  getWorktreeUpstream,
+  setWorktreeUpstream,
+  UpstreamBranchNotFoundError,
+} from '../repositories/worktree-upstream-repository.js';
+export type { WorktreeUpstream, SetWorktreeUpstreamOptions } from '../repositories/worktree-upstream-repository.js';
+
+// Re-export from worktree replace repository
+export { replaceWorktree } from '../repositories/worktree-replace-repository.js';
+export type { ReplaceWorktreeOptions, ReplaceWorktreeResult } from '../repositories/worktree-replace-repository.js';
+
+// Re-export from worktree discovery repository
+export { discoverWorktrees } from '../repositories/worktree-discovery-repository.js';
+export type { DiscoveredWorktree, WorktreeListing } from '../repositories/worktree-discovery-repository.js';
+
+// Re-export from git compare repository
+export {
+  compareRefs,
+  CompareRefError,
+  DEFAULT_COMPARE_DIFF_LIMIT_BYTES,
+  DEFAULT_COMPARE_COMMIT_LIMIT,
+  findWorktreeMergeBase,
+  UnrelatedHistoriesError,
+} from '../repositories/git-compare-repository.js';
+export type { RefComparison, CompareRefsOptions, WorktreeMergeBase } from '../repositories/git-compare-repository.js';
+
+// Re-export from git tags repository
+export { listRepositoryTags, parseTagList } from '../repositories/git-tags-repository.js';
+export type { RepositoryTag } from '../repositories/git-tags-repository.js';
+
+// Re-export from git remote repository
+export {
+  listRepositoryRemotes,
+  addRepositoryRemote,
+  parseRemoteList,
+  RemoteExistsError,
+} from '../repositories/git-remote-repository.js';
+export type { RepositoryRemote, AddRepositoryRemoteOptions } from '../repositories/git-remote-repository.js';
+
+// Re-export from git config repository
+export {
+  readRepositoryConfig,
+  isReadableGitConfigKey,
+  GitConfigKeyNotAllowedError,
+  GitConfigKeyUnsetError,
+} from '../repositories/git-config-repository.js';
+export type { RepositoryConfigValue } from '../repositories/git-config-repository.js';
+
+// Re-export from worktree usage repository
+export { getWorktreeUsage, measureDirectory, WORKTREE_USAGE_CACHE_TTL_MS } from '../repositories/worktree-usage-repository.js';
+export type { WorktreeUsage, WorktreeUsageEntry, WorktreeUsageOptions, DirectoryUsage } from '../repositories/worktree-usage-repository.js';
+
+// Re-export from worktree activity repository
+export {
+  getWorktreeActivity,
+  DEFAULT_ACTIVITY_LIMIT,
+  DEFAULT_ACTIVITY_PER_WORKTREE,
+  MAX_ACTIVITY_LIMIT,
+  MAX_ACTIVITY_PER_WORKTREE,
+} from '../repositories/worktree-activity-repository.js';
+export type {
+  WorktreeActivity,
+  WorktreeActivityCommit,
+  WorktreeActivityOptions,
+} from '../repositories/worktree-activity-repository.js';
+
+// Re-export from worktree setup repository
+export {
+  runWorktreeSetup,
+  SetupCommandNotConfiguredError,
+  SETUP_SCRIPT_FILE,
+  SETUP_COMMAND_TIMEOUT_MS,
+} from '../repositories/worktree-setup-repository.js';
+export type { RunWorktreeSetupOptions, WorktreeSetupResult } from '../repositories/worktree-setup-repository.js';
+
+// Re-export from worktree archive repository
+export { createWorktreeArchive, createArchiveFileName } from '../repositories/worktree-archive-repository.js';
+export type { WorktreeArchive, WorktreeArchiveOptions } from '../repositories/worktree-archive-repository.js';
+
 // Legacy alias for backward compatibility
 export { normalizeBranchName as normaliseBranchName } from '../domain/index.js';
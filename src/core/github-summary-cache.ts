@@ -0,0 +1,65 @@
+/**
+ * Longest a repository's GitHub summary is served from memory while webhooks keep it fresh; bounds how
+ * stale it can get when a delivery is missed
+ */
+export const GITHUB_SUMMARY_CACHE_TTL_MS = 10 * 60_000;
+
+interface CacheEntry<T> {
+  expiresAt: number;
+  promise: Promise<T>;
+}
+
+/**
+ * Caches per-repository GitHub summaries keyed by `owner/repo`. Give it a TTL only when webhooks are
+ * configured, since without them nothing would tell the cache that a summary changed.
+ */
+export class RepositorySummaryCache<T> {
+  private readonly entries = new Map<string, CacheEntry<T>>();
+
+  constructor(
+    private readonly ttlMs: number = 0,
+    private readonly now: () => number = () => Date.now()
+  ) {}
+
+  get enabled(): boolean {
+    return this.ttlMs > 0;
+  }
+
+  /**
+   * Returns the cached summary for a repository, loading it when missing or expired. Concurrent callers
+   * share one load, and failed loads are not cached.
+   * @param slug - `owner/repo` on GitHub
+   * @param load - Fetches a fresh summary
+   */
+  async get(slug: string, load: () => Promise<T>): Promise<T> {
+    if (!this.enabled) {
+      return await load();
+    }
+    const key = slug.toLowerCase();
+    const now = this.now();
+    const cached = this.entries.get(key);
+    if (cached && cached.expiresAt > now) {
+      return await cached.promise;
+    }
+
+    const entry = { expiresAt: now + this.ttlMs, promise: load() };
+    this.entries.set(key, entry);
+    try {
+      return await entry.promise;
+    } catch (error) {
+      if (this.entries.get(key) === entry) {
+        this.entries.delete(key);
+      }
+      throw error;
+    }
+  }
+
+  /**
+   * Forgets a repository's summary so the next read fetches it again
+   * @param slug - `owner/repo` on GitHub; matched case-insensitively like GitHub does
+   * @returns Whether a summary was cached
+   */
+  invalidate(slug: string): boolean {
+    return this.entries.delete(slug.toLowerCase());
+  }
+}
@@ -0,0 +1,58 @@
+import { createHmac, timingSafeEqual } from 'node:crypto';
+import type { RepositorySummaryCache } from './github-summary-cache.js';
+
+/**
+ * Header GitHub signs deliveries with: `sha256=` followed by the hex HMAC of the raw body
+ */
+export const GITHUB_SIGNATURE_HEADER = 'x-hub-signature-256';
+
+/**
+ * Events whose deliveries change a repository's dashboard summary
+ */
+export const SUMMARY_EVENTS: ReadonlySet<string> = new Set(['issues', 'pull_request', 'workflow_run']);
+
+/**
+ * Checks a delivery's `X-Hub-Signature-256` header against the HMAC of its raw body
+ * @param secret - Webhook secret
+ * @param payload - Body exactly as received
+ * @param signature - Header value, e.g. `sha256=5d61…`
+ * @returns True only when the header is present and matches
+ */
+export function verifyGithubSignature(secret: string, payload: Buffer, signature: string | null | undefined): boolean {
+  const match = /^sha256=([0-9a-f]{64})$/i.exec(signature?.trim() ?? '');
+  if (!match?.[1]) {
+    return false;
+  }
+  const expected = createHmac('sha256', secret).update(payload).digest();
+  return timingSafeEqual(expected, Buffer.from(match[1], 'hex'));
+}
+
+export interface GithubWebhookOutcome {
+  event: string;
+  /**
+   * `owner/repo` named by the delivery, when it has one
+   */
+  repository: string | null;
+  /**
+   * Whether a cached summary was dropped
+   */
+  invalidated: boolean;
+}
+
+/**
+ * Applies a verified delivery: events that change a repository's open issues, pull requests or workflow
+ * runs drop its cached summary; everything else is acknowledged and ignored
+ * @param event - `X-GitHub-Event` header
+ * @param payload - Parsed delivery body
+ * @param summaryCache - Dashboard summaries the delivery may invalidate
+ */
+export function applyGithubWebhookEvent(
+  event: string,
+  payload: Record<string, unknown>,
+  summaryCache: Pick<RepositorySummaryCache<unknown>, 'invalidate'>
+): GithubWebhookOutcome {
+  const repository = payload['repository'] as { full_name?: unknown } | undefined;
+  const slug = typeof repository?.full_name === 'string' ? repository.full_name : null;
+  const invalidated = Boolean(slug && SUMMARY_EVENTS.has(event) && summaryCache.invalidate(slug));
+  return { event, repository: slug, invalidated };
+}
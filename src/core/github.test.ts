@@ -0,0 +1,783 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import { promisify } from 'node:util';
+import { afterEach, describe, it } from 'node:test';
+
+import {
+  DEFAULT_GITHUB_API_VERSION,
+  DEFAULT_GITHUB_TIMEOUT_MS,
+  GITHUB_HTML_MEDIA_TYPE,
+  GITHUB_RETRY_DELAYS_MS,
+  GITHUB_USER_AGENT,
+  GitHubSearchRateLimitError,
+  GitHubTimeoutError,
+  GitHubTokenRequiredError,
+  createGithubClient,
+  githubTokenEnvName,
+  resolveGithubToken,
+  __setGithubTestOverrides,
+} from './github.js';
+
+const execFileAsync = promisify(execFile);
+
+describe('createGithubClient', () => {
+  afterEach(() => {
+    __setGithubTestOverrides();
+  });
+
+  it('kills a hung gh call once the timeout elapses', async () => {
+    __setGithubTestOverrides({
+      execFileAsync: (async (_command: string, _args: readonly string[], options: { timeout?: number }) =>
+        await execFileAsync(process.execPath, ['-e', 'setTimeout(() => {}, 30000)'], {
+          timeout: options.timeout,
+        })) as unknown as typeof execFileAsync,
+    });
+
+    const client = createGithubClient({ timeoutMs: 100 });
+    const startedAt = Date.now();
+
+    await assert.rejects(
+      () => client.countOpenIssues('acme', 'demo'),
+      (error: unknown) => {
+        assert.ok(error instanceof GitHubTimeoutError);
+        assert.equal(error.statusCode, 504);
+        assert.equal(error.code, 'github_timeout');
+        assert.equal(error.timeoutMs, 100);
+        return true;
+      },
+    );
+    assert.ok(Date.now() - startedAt < 5000, 'timeout should fire promptly');
+  });
+
+  it('keeps GitHub CLI failures distinct from timeouts', async () => {
+    __setGithubTestOverrides({
+      execFileAsync: (async () => {
+        throw Object.assign(new Error('Command failed'), {
+          code: 1,
+          stderr: 'HTTP 404: Not Found',
+        });
+      }) as unknown as typeof execFileAsync,
+    });
+
+    const client = createGithubClient();
+
+    await assert.rejects(
+      () => client.countOpenPullRequests('acme', 'demo'),
+      (error: unknown) => {
+        assert.ok(!(error instanceof GitHubTimeoutError));
+        assert.equal((error as Error).message, 'HTTP 404: Not Found');
+        return true;
+      },
+    );
+  });
+
+  it('maps a timed-out call to a 504 github_timeout without retrying it', async () => {
+    let calls = 0;
+    __setGithubTestOverrides({
+      execFileAsync: (async () => {
+        calls += 1;
+        throw Object.assign(new Error('Command failed'), { killed: true, signal: 'SIGTERM' });
+      }) as unknown as typeof execFileAsync,
+      sleep: async () => {},
+    });
+
+    await assert.rejects(
+      () => createGithubClient({ timeoutMs: 2500 }).countOpenIssues('acme', 'demo'),
+      (error: unknown) => {
+        assert.ok(error instanceof GitHubTimeoutError);
+        assert.equal(error.statusCode, 504);
+        assert.equal(error.code, 'github_timeout');
+        assert.equal(error.timeoutMs, 2500);
+        return true;
+      },
+    );
+    assert.equal(calls, 1);
+  });
+
+  it('retries transient failures with a growing delay', async () => {
+    const delays: number[] = [];
+    let calls = 0;
+    __setGithubTestOverrides({
+      execFileAsync: (async () => {
+        calls += 1;
+        if (calls < 3) {
+          throw Object.assign(new Error('Command failed'), { code: 1, stderr: 'HTTP 502: Bad Gateway' });
+        }
+        return { stdout: '[{"number":1}]', stderr: '' };
+      }) as unknown as typeof execFileAsync,
+      sleep: async (ms) => {
+        delays.push(ms);
+      },
+    });
+
+    assert.equal(await createGithubClient().countOpenIssues('acme', 'demo'), 1);
+    assert.equal(calls, 3);
+    assert.deepEqual(delays, [...GITHUB_RETRY_DELAYS_MS]);
+  });
+
+  it('gives up once the retries are spent and never retries a pull request create', async () => {
+    const commands: string[][] = [];
+    __setGithubTestOverrides({
+      execFileAsync: (async (_command: string, args: readonly string[]) => {
+        commands.push([...args]);
+        throw Object.assign(new Error('Command failed'), { code: 1, stderr: 'HTTP 503: Service Unavailable' });
+      }) as unknown as typeof execFileAsync,
+      sleep: async () => {},
+    });
+    const client = createGithubClient();
+
+    await assert.rejects(() => client.countOpenIssues('acme', 'demo'), { message: 'HTTP 503: Service Unavailable' });
+    assert.equal(commands.length, GITHUB_RETRY_DELAYS_MS.length + 1);
+
+    commands.length = 0;
+    await assert.rejects(() =>
+      client.createPullRequest('acme', 'demo', { head: 'feature', base: 'main', title: 'Feature' }),
+    );
+    assert.equal(commands.length, 1);
+    assert.deepEqual(commands[0]?.slice(0, 2), ['pr', 'create']);
+  });
+
+  it('applies the configured timeout, falling back to the default', async () => {
+    const timeouts: Array<number | undefined> = [];
+    __setGithubTestOverrides({
+      execFileAsync: (async (_command: string, _args: readonly string[], options: { timeout?: number }) => {
+        timeouts.push(options.timeout);
+        return { stdout: '[]', stderr: '' };
+      }) as unknown as typeof execFileAsync,
+    });
+
+    await createGithubClient({ timeoutMs: 2500 }).countOpenIssues('acme', 'demo');
+    await createGithubClient({ timeoutMs: null }).countOpenIssues('acme', 'demo');
+
+    assert.deepEqual(timeouts, [2500, DEFAULT_GITHUB_TIMEOUT_MS]);
+  });
+
+  describe('getIssue', () => {
+    const issueView = JSON.stringify({
+      number: 7,
+      title: 'Render me',
+      body: '**bold**',
+      state: 'OPEN',
+      url: 'https://github.com/acme/demo/issues/7',
+    });
+
+    function recordCalls(): string[][] {
+      const calls: string[][] = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[]) => {
+          calls.push([...args]);
+          const stdout = args[0] === 'api' ? JSON.stringify({ body: '**bold**', body_html: '<p><strong>bold</strong></p>' }) : issueView;
+          return { stdout, stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+      return calls;
+    }
+
+    it('returns the raw markdown body without a rendering request by default', async () => {
+      const calls = recordCalls();
+
+      const issue = await createGithubClient().getIssue('acme', 'demo', 7);
+
+      assert.equal(calls.length, 1);
+      assert.deepEqual(calls[0]?.slice(0, 3), ['issue', 'view', '7']);
+      assert.equal(issue['body'], '**bold**');
+      assert.equal('bodyHtml' in issue, false);
+    });
+
+    it('requests the HTML media type and returns bodyHtml alongside the markdown', async () => {
+      const calls = recordCalls();
+
+      const issue = await createGithubClient().getIssue('acme', 'demo', 7, { renderHtml: true });
+
+      const apiCall = calls.find((args) => args[0] === 'api') ?? [];
+      assert.ok(apiCall.includes('repos/acme/demo/issues/7'));
+      assert.ok(apiCall.includes(`Accept: ${GITHUB_HTML_MEDIA_TYPE}`));
+      assert.equal(issue['body'], '**bold**');
+      assert.equal(issue['bodyHtml'], '<p><strong>bold</strong></p>');
+    });
+  });
+
+  describe('listIssues', () => {
+    function recordCalls(): string[][] {
+      const calls: string[][] = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[]) => {
+          calls.push([...args]);
+          const stdout = JSON.stringify([
+            {
+              number: 4,
+              title: 'Crash on start',
+              state: 'CLOSED',
+              labels: [{ name: 'bug' }],
+              createdAt: '2024-03-01T10:00:00Z',
+              closedAt: '2024-03-02T12:00:00Z',
+              url: 'https://github.com/acme/demo/issues/4',
+            },
+          ]);
+          return { stdout, stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+      return calls;
+    }
+
+    it('lists open issues unless another state is requested', async () => {
+      const calls = recordCalls();
+      const client = createGithubClient();
+
+      await client.listIssues('acme', 'demo');
+      const closed = await client.listIssues('acme', 'demo', { state: 'closed' });
+
+      assert.equal(calls[0]?.[calls[0].indexOf('--state') + 1], 'open');
+      assert.equal(calls[1]?.[calls[1].indexOf('--state') + 1], 'closed');
+      assert.deepEqual(closed, [
+        {
+          number: 4,
+          title: 'Crash on start',
+          state: 'closed',
+          createdAt: '2024-03-01T10:00:00.000Z',
+          closedAt: '2024-03-02T12:00:00.000Z',
+          labels: ['bug'],
+          url: 'https://github.com/acme/demo/issues/4',
+        },
+      ]);
+    });
+
+    it('refuses states GitHub does not understand', async () => {
+      const calls = recordCalls();
+
+      await assert.rejects(
+        () => createGithubClient().listIssues('acme', 'demo', { state: '--web' as 'open' }),
+        /Unsupported issue state/,
+      );
+      assert.equal(calls.length, 0);
+    });
+  });
+
+  describe('searchIssues', () => {
+    const searchResponse = [
+      'HTTP/2.0 200 OK',
+      'Content-Type: application/json; charset=utf-8',
+      'X-Ratelimit-Limit: 30',
+      'X-Ratelimit-Remaining: 29',
+      'X-Ratelimit-Reset: 1700000060',
+      '',
+      JSON.stringify({
+        total_count: 75,
+        incomplete_results: false,
+        items: [
+          {
+            number: 12,
+            title: 'Crash on start',
+            state: 'open',
+            created_at: '2024-05-01T10:00:00Z',
+            html_url: 'https://github.com/acme/demo/issues/12',
+            labels: [{ name: 'bug' }, { name: 'p1' }],
+          },
+          {
+            number: 13,
+            title: 'Fix crash on start',
+            state: 'closed',
+            created_at: '2024-05-02T10:00:00Z',
+            html_url: 'https://github.com/acme/demo/pull/13',
+            labels: [],
+            pull_request: { url: 'https://api.github.com/repos/acme/demo/pulls/13' },
+          },
+        ],
+      }),
+    ].join('\r\n');
+
+    it('scopes the query to the repository and maps issues and pull requests', async () => {
+      const calls: string[][] = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[]) => {
+          calls.push([...args]);
+          return { stdout: searchResponse, stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const client = createGithubClient();
+      const result = await client.searchIssues('acme', 'demo', ' crash ', { page: 2, perPage: 25 });
+
+      assert.equal(calls.length, 1);
+      const args = calls[0] ?? [];
+      assert.ok(args.includes('search/issues'));
+      assert.ok(args.includes('--include'));
+      assert.ok(args.includes('q=repo:acme/demo crash'));
+      assert.ok(args.includes('page=2'));
+      assert.ok(args.includes('per_page=25'));
+
+      assert.deepEqual(result, {
+        query: 'crash',
+        totalCount: 75,
+        incomplete: false,
+        page: 2,
+        perPage: 25,
+        hasMore: true,
+        items: [
+          {
+            type: 'issue',
+            number: 12,
+            title: 'Crash on start',
+            state: 'open',
+            createdAt: '2024-05-01T10:00:00.000Z',
+            labels: ['bug', 'p1'],
+            url: 'https://github.com/acme/demo/issues/12',
+          },
+          {
+            type: 'pull_request',
+            number: 13,
+            title: 'Fix crash on start',
+            state: 'closed',
+            createdAt: '2024-05-02T10:00:00.000Z',
+            labels: [],
+            url: 'https://github.com/acme/demo/pull/13',
+          },
+        ],
+      });
+    });
+
+    it('sends the GitHub media type, API version and user agent headers', async () => {
+      const calls: string[][] = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[]) => {
+          calls.push([...args]);
+          return { stdout: searchResponse, stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      function sentHeaders(): string[] {
+        const args = calls.at(-1) ?? [];
+        return args.flatMap((arg, index) => (args[index - 1] === '--header' ? [arg] : []));
+      }
+
+      const pkg = (await import('../../package.json', { with: { type: 'json' } })).default;
+      assert.equal(GITHUB_USER_AGENT, `agentrix/${pkg.version}`);
+
+      await createGithubClient().searchIssues('acme', 'demo', 'crash');
+      assert.deepEqual(sentHeaders(), [
+        'Accept: application/vnd.github+json',
+        `X-GitHub-Api-Version: ${DEFAULT_GITHUB_API_VERSION}`,
+        `User-Agent: ${GITHUB_USER_AGENT}`,
+      ]);
+
+      await createGithubClient({ apiVersion: '2026-03-10' }).searchIssues('acme', 'demo', 'crash');
+      assert.ok(sentHeaders().includes('X-GitHub-Api-Version: 2026-03-10'));
+
+      await createGithubClient({ apiVersion: 'latest' }).searchIssues('acme', 'demo', 'crash');
+      assert.ok(sentHeaders().includes(`X-GitHub-Api-Version: ${DEFAULT_GITHUB_API_VERSION}`));
+    });
+
+    it('clamps page size and reports the last page', async () => {
+      const calls: string[][] = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[]) => {
+          calls.push([...args]);
+          return { stdout: searchResponse, stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const client = createGithubClient();
+      const result = await client.searchIssues('acme', 'demo', 'crash', { perPage: 500 });
+
+      assert.ok(calls[0]?.includes('per_page=100'));
+      assert.equal(result.page, 1);
+      assert.equal(result.perPage, 100);
+      assert.equal(result.hasMore, false);
+    });
+
+    it('raises a search rate limit error and backs off until the reset', async () => {
+      let nowMs = 1_700_000_000_000;
+      let callCount = 0;
+      __setGithubTestOverrides({
+        execFileAsync: (async () => {
+          callCount += 1;
+          throw Object.assign(new Error('Command failed'), {
+            code: 1,
+            stdout: 'HTTP/2.0 403 Forbidden\r\nX-Ratelimit-Remaining: 0\r\nX-Ratelimit-Reset: 1700000045\r\n\r\n{}',
+            stderr: 'gh: API rate limit exceeded for user ID 1. (HTTP 403)',
+          });
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const client = createGithubClient({ now: () => nowMs });
+
+      await assert.rejects(
+        () => client.searchIssues('acme', 'demo', 'crash'),
+        (error: unknown) => {
+          assert.ok(error instanceof GitHubSearchRateLimitError);
+          assert.equal(error.statusCode, 429);
+          assert.equal(error.code, 'github_search_rate_limited');
+          assert.equal(error.retryAfterSeconds, 45);
+          return true;
+        },
+      );
+
+      nowMs += 15_000;
+      await assert.rejects(
+        () => client.searchIssues('acme', 'demo', 'crash'),
+        (error: unknown) => {
+          assert.ok(error instanceof GitHubSearchRateLimitError);
+          assert.equal(error.retryAfterSeconds, 30);
+          return true;
+        },
+      );
+      assert.equal(callCount, 1, 'blocked searches should not reach GitHub');
+
+      nowMs += 30_000;
+      await assert.rejects(() => client.searchIssues('acme', 'demo', 'crash'), GitHubSearchRateLimitError);
+      assert.equal(callCount, 2);
+    });
+  });
+
+  describe('listNotifications', () => {
+    const notificationsResponse = [
+      'HTTP/2.0 200 OK',
+      'Content-Type: application/json; charset=utf-8',
+      'Link: <https://api.github.com/notifications?all=true&page=3&per_page=2>; rel="next"',
+      '',
+      JSON.stringify([
+        {
+          id: '101',
+          unread: true,
+          reason: 'review_requested',
+          updated_at: '2024-05-03T09:00:00Z',
+          subject: {
+            title: 'Add login',
+            type: 'PullRequest',
+            url: 'https://api.github.com/repos/acme/demo/pulls/7',
+          },
+          repository: { full_name: 'acme/demo', html_url: 'https://github.com/acme/demo' },
+        },
+        {
+          id: '102',
+          unread: false,
+          reason: 'subscribed',
+          updated_at: '2024-05-02T09:00:00Z',
+          subject: { title: 'Nightly failed', type: 'CheckSuite', url: null },
+          repository: { full_name: 'acme/api', html_url: 'https://github.com/acme/api' },
+        },
+      ]),
+    ].join('\r\n');
+
+    it('passes the filters and pagination and maps subjects to web links', async () => {
+      const calls: string[][] = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[]) => {
+          calls.push([...args]);
+          return { stdout: notificationsResponse, stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const result = await createGithubClient().listNotifications({
+        all: true,
+        since: '2024-05-01T00:00:00.000Z',
+        page: 2,
+        perPage: 200,
+      });
+
+      const args = calls[0] ?? [];
+      assert.ok(args.includes('notifications'));
+      assert.ok(args.includes('all=true'));
+      assert.ok(args.includes('since=2024-05-01T00:00:00.000Z'));
+      assert.ok(args.includes('page=2'));
+      assert.ok(args.includes('per_page=50'));
+      assert.deepEqual(result, {
+        page: 2,
+        perPage: 50,
+        hasMore: true,
+        items: [
+          {
+            id: '101',
+            repository: 'acme/demo',
+            title: 'Add login',
+            type: 'PullRequest',
+            reason: 'review_requested',
+            unread: true,
+            updatedAt: '2024-05-03T09:00:00.000Z',
+            url: 'https://github.com/acme/demo/pull/7',
+          },
+          {
+            id: '102',
+            repository: 'acme/api',
+            title: 'Nightly failed',
+            type: 'CheckSuite',
+            reason: 'subscribed',
+            unread: false,
+            updatedAt: '2024-05-02T09:00:00.000Z',
+            url: 'https://github.com/acme/api',
+          },
+        ],
+      });
+    });
+
+    it('reports a missing token as a 501', async () => {
+      __setGithubTestOverrides({
+        execFileAsync: (async () => {
+          throw Object.assign(new Error('Command failed: gh api notifications'), {
+            stderr: 'To get started with GitHub CLI, please run:  gh auth login',
+          });
+        }) as unknown as typeof execFileAsync,
+      });
+
+      await assert.rejects(createGithubClient().listNotifications(), (error: unknown) => {
+        assert.ok(error instanceof GitHubTokenRequiredError);
+        assert.equal(error.statusCode, 501);
+        assert.equal(error.code, 'github_token_required');
+        return true;
+      });
+    });
+  });
+
+  describe('findOpenPullRequest', () => {
+    it("asks for open pull requests headed by the owner's branch and maps the match", async () => {
+      const calls: string[][] = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[]) => {
+          calls.push([...args]);
+          return {
+            stdout: JSON.stringify([
+              {
+                number: 42,
+                html_url: 'https://github.com/acme/demo/pull/42',
+                title: 'Add login',
+                draft: true,
+                created_at: '2024-05-01T10:00:00Z',
+                base: { ref: 'main' },
+                head: { ref: 'feature/login' },
+                user: { login: 'octocat' },
+              },
+            ]),
+            stderr: '',
+          };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const result = await createGithubClient().findOpenPullRequest('acme', 'demo', 'feature/login');
+
+      const args = calls[0] ?? [];
+      assert.ok(args.includes('repos/acme/demo/pulls'));
+      assert.ok(args.includes('state=open'));
+      assert.ok(args.includes('head=acme:feature/login'));
+      assert.deepEqual(result, {
+        number: 42,
+        url: 'https://github.com/acme/demo/pull/42',
+        title: 'Add login',
+        draft: true,
+        base: 'main',
+        head: 'feature/login',
+        author: 'octocat',
+        createdAt: '2024-05-01T10:00:00.000Z',
+      });
+    });
+
+    it('returns null when the branch has no open pull request', async () => {
+      __setGithubTestOverrides({
+        execFileAsync: (async () => ({ stdout: '[]', stderr: '' })) as unknown as typeof execFileAsync,
+      });
+
+      assert.equal(await createGithubClient().findOpenPullRequest('acme', 'demo', 'feature/none'), null);
+    });
+  });
+
+  describe('getReadme', () => {
+    it('asks the readme endpoint for the raw media type and returns the decoded text', async () => {
+      const calls: string[][] = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[]) => {
+          calls.push([...args]);
+          return { stdout: '# Demo\n\nA small demo.\n', stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const result = await createGithubClient().getReadme('acme', 'demo');
+
+      assert.deepEqual(result, { found: true, content: '# Demo\n\nA small demo.\n' });
+      const args = calls[0] ?? [];
+      assert.equal(args.at(-1), 'repos/acme/demo/readme');
+      assert.ok(args.includes('Accept: application/vnd.github.raw'));
+    });
+
+    it('treats a repository without a README as an empty result', async () => {
+      __setGithubTestOverrides({
+        execFileAsync: (async () => {
+          throw Object.assign(new Error('Command failed: gh api repos/acme/demo/readme'), {
+            code: 1,
+            stderr: 'gh: Not Found (HTTP 404)',
+          });
+        }) as unknown as typeof execFileAsync,
+      });
+
+      assert.deepEqual(await createGithubClient().getReadme('acme', 'demo'), { found: false, content: '' });
+    });
+
+    it('reports a missing token as a 501', async () => {
+      __setGithubTestOverrides({
+        execFileAsync: (async () => {
+          throw Object.assign(new Error('Command failed: gh api repos/acme/private/readme'), {
+            stderr: 'To get started with GitHub CLI, please run:  gh auth login',
+          });
+        }) as unknown as typeof execFileAsync,
+      });
+
+      await assert.rejects(createGithubClient().getReadme('acme', 'private'), GitHubTokenRequiredError);
+    });
+  });
+
+  describe('getRateLimit', () => {
+    it('reads the core and search limits from the rate_limit endpoint', async () => {
+      const calls: Array<{ args: string[]; env?: NodeJS.ProcessEnv }> = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[], options: { env?: NodeJS.ProcessEnv }) => {
+          calls.push({ args: [...args], env: options.env });
+          return {
+            stdout: JSON.stringify({
+              resources: {
+                core: { limit: 5000, used: 12, remaining: 4988, reset: 1714525200 },
+                search: { limit: 30, used: 30, remaining: 0, reset: 1714521660 },
+                graphql: { limit: 5000, used: 0, remaining: 5000, reset: 1714525200 },
+              },
+              rate: { limit: 5000, used: 12, remaining: 4988, reset: 1714525200 },
+            }),
+            stderr: '',
+          };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const client = createGithubClient({ resolveToken: (org) => (org === 'acme' ? 'acme-token' : null) });
+      const result = await client.getRateLimit('acme');
+
+      assert.deepEqual(result, {
+        core: { limit: 5000, remaining: 4988, used: 12, resetAt: '2024-05-01T01:00:00.000Z' },
+        search: { limit: 30, remaining: 0, used: 30, resetAt: '2024-05-01T00:01:00.000Z' },
+      });
+      assert.equal(calls[0]?.args.at(-1), 'rate_limit');
+      assert.equal(calls[0]?.env?.['GH_TOKEN'], 'acme-token');
+
+      await client.getRateLimit();
+      assert.equal(calls[1]?.env, undefined);
+    });
+
+    it('reports a missing token as a 501', async () => {
+      __setGithubTestOverrides({
+        execFileAsync: (async () => {
+          throw Object.assign(new Error('Command failed: gh api rate_limit'), {
+            stderr: 'To get started with GitHub CLI, please run:  gh auth login',
+          });
+        }) as unknown as typeof execFileAsync,
+      });
+
+      await assert.rejects(createGithubClient().getRateLimit(), (error: unknown) => {
+        assert.ok(error instanceof GitHubTokenRequiredError);
+        assert.equal(error.statusCode, 501);
+        return true;
+      });
+    });
+  });
+
+  describe('verifyToken', () => {
+    it('reports the authenticated login from the user endpoint and caches it', async () => {
+      const calls: string[][] = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, args: readonly string[]) => {
+          calls.push([...args]);
+          return { stdout: JSON.stringify({ login: 'octocat', id: 1 }), stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const client = createGithubClient({ now: () => Date.UTC(2026, 0, 2) });
+      assert.equal(client.getTokenVerification(), null);
+      const result = await client.verifyToken();
+
+      assert.deepEqual(result, {
+        valid: true,
+        login: 'octocat',
+        message: null,
+        checkedAt: '2026-01-02T00:00:00.000Z',
+      });
+      assert.deepEqual(client.getTokenVerification(), result);
+      assert.equal(calls.length, 1);
+      assert.equal(calls[0]?.[0], 'api');
+      assert.equal(calls[0]?.at(-1), 'user');
+    });
+
+    it('reports a rejected token without throwing', async () => {
+      __setGithubTestOverrides({
+        execFileAsync: (async () => {
+          throw Object.assign(new Error('Command failed'), {
+            code: 1,
+            stderr: 'gh: Bad credentials (HTTP 401)',
+          });
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const client = createGithubClient();
+      const result = await client.verifyToken();
+
+      assert.equal(result.valid, false);
+      assert.equal(result.login, null);
+      assert.equal(result.message, 'gh: Bad credentials (HTTP 401)');
+      assert.equal(client.getTokenVerification()?.valid, false);
+    });
+  });
+
+  describe('per-organisation tokens', () => {
+    it('passes the owner token to gh as GH_TOKEN and leaves other owners on the global login', async () => {
+      const envs: Array<NodeJS.ProcessEnv | undefined> = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, _args: readonly string[], options: { env?: NodeJS.ProcessEnv }) => {
+          envs.push(options.env);
+          return { stdout: '[]', stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      const client = createGithubClient({ resolveToken: (org) => (org === 'acme' ? 'acme-token' : null) });
+      await client.countOpenPullRequests('acme', 'demo');
+      await client.countOpenPullRequests('other', 'demo');
+
+      assert.equal(envs[0]?.['GH_TOKEN'], 'acme-token');
+      assert.equal(envs[1], undefined);
+    });
+
+    it('looks up the tokens the client was created with', async () => {
+      const envs: Array<NodeJS.ProcessEnv | undefined> = [];
+      __setGithubTestOverrides({
+        execFileAsync: (async (_command: string, _args: readonly string[], options: { env?: NodeJS.ProcessEnv }) => {
+          envs.push(options.env);
+          return { stdout: '[]', stderr: '' };
+        }) as unknown as typeof execFileAsync,
+      });
+
+      await createGithubClient({ orgTokens: { Acme: 'configured-token' } }).countOpenIssues('acme', 'demo');
+      await createGithubClient().countOpenIssues('acme', 'demo');
+
+      assert.equal(envs[0]?.['GH_TOKEN'], 'configured-token');
+      assert.equal(envs[1], undefined);
+    });
+  });
+});
+
+describe('resolveGithubToken', () => {
+  it('derives the environment variable name from the organisation', () => {
+    assert.equal(githubTokenEnvName('acme'), 'AGENTRIX_GITHUB_TOKEN_ACME');
+    assert.equal(githubTokenEnvName('Acme-Corp.io'), 'AGENTRIX_GITHUB_TOKEN_ACME_CORP_IO');
+  });
+
+  it('prefers the environment variable over the configured mapping', () => {
+    const orgTokens = { acme: 'from-config' };
+
+    assert.equal(resolveGithubToken('acme', { orgTokens, env: { AGENTRIX_GITHUB_TOKEN_ACME: 'from-env' } }), 'from-env');
+    assert.equal(resolveGithubToken('acme', { orgTokens, env: {} }), 'from-config');
+  });
+
+  it('matches configured organisations case-insensitively', () => {
+    assert.equal(resolveGithubToken('acme-corp', { orgTokens: { 'Acme-Corp': 'corp-token' }, env: {} }), 'corp-token');
+  });
+
+  it('falls back to the global token when no organisation token exists', () => {
+    const orgTokens = { acme: 'acme-token', empty: '  ' };
+
+    assert.equal(resolveGithubToken('other', { orgTokens, env: { AGENTRIX_GITHUB_TOKEN_ACME: 'unrelated' } }), null);
+    assert.equal(resolveGithubToken('empty', { orgTokens, env: { AGENTRIX_GITHUB_TOKEN_EMPTY: '' } }), null);
+    assert.equal(resolveGithubToken('', { orgTokens, env: {} }), null);
+  });
+});
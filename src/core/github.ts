@@ -1,10 +1,346 @@
 import { execFile } from 'node:child_process';
+import { createRequire } from 'node:module';
 import { promisify } from 'node:util';
 
 const execFileAsync = promisify(execFile);
+const require = createRequire(import.meta.url);
 
-const DEFAULT_TIMEOUT_MS = 10_000;
+export const DEFAULT_GITHUB_TIMEOUT_MS = 10_000;
+/**
+ * REST API version pinned on `gh api` requests so responses do not drift when GitHub changes its default
+ */
+export const DEFAULT_GITHUB_API_VERSION = '2022-11-28';
+const GITHUB_API_VERSION_PATTERN = /^\d{4}-\d{2}-\d{2}$/;
 const DEFAULT_MAX_BUFFER = 1024 * 1024;
+/**
+ * Waits before each retry of a GitHub CLI call that failed transiently; one retry per entry
+ */
+export const GITHUB_RETRY_DELAYS_MS = [250, 1000] as const;
+// Server errors and dropped connections that usually clear up on their own
+const TRANSIENT_GH_ERROR_PATTERN =
+  /HTTP 5\d\d|ECONNRESET|ECONNREFUSED|EAI_AGAIN|connection reset|i\/o timeout|TLS handshake timeout|unexpected EOF/i;
+const SEARCH_MAX_PER_PAGE = 100;
+const SEARCH_DEFAULT_PER_PAGE = 30;
+// GitHub never returns more than the first 1000 matches of a search
+const SEARCH_RESULT_CAP = 1000;
+const NOTIFICATIONS_MAX_PER_PAGE = 50;
+const NOTIFICATIONS_DEFAULT_PER_PAGE = 30;
+/**
+ * Prefix of the environment variables holding per-organisation tokens, e.g. `AGENTRIX_GITHUB_TOKEN_ACME_CORP`
+ */
+export const GITHUB_TOKEN_ENV_PREFIX = 'AGENTRIX_GITHUB_TOKEN_';
+
+function readPackageVersion(): string {
+  try {
+    const pkg = require('../../package.json') as { version?: unknown };
+    return typeof pkg.version === 'string' && pkg.version ? pkg.version : '0.0.0';
+  } catch {
+    return '0.0.0';
+  }
+}
+
+/**
+ * User-Agent sent on `gh api` requests, derived from the installed package version
+ */
+export const GITHUB_USER_AGENT = `agentrix/${readPackageVersion()}`;
+
+type GithubTestOverrides = {
+  execFileAsync?: typeof execFileAsync;
+  sleep?: (ms: number) => Promise<void>;
+} | null;
+
+let githubTestOverrides: GithubTestOverrides = null;
+
+function resolveExecFileAsync(): typeof execFileAsync {
+  return githubTestOverrides?.execFileAsync ?? execFileAsync;
+}
+
+function sleep(ms: number): Promise<void> {
+  return githubTestOverrides?.sleep?.(ms) ?? new Promise<void>((resolve) => setTimeout(resolve, ms));
+}
+
+export function __setGithubTestOverrides(overrides?: GithubTestOverrides): void {
+  githubTestOverrides = overrides ?? null;
+}
+
+/**
+ * Raised when a GitHub CLI call exceeds its time budget, so callers can tell a slow GitHub apart
+ * from one that answered with an error
+ */
+export class GitHubTimeoutError extends Error {
+  readonly statusCode = 504;
+  readonly code = 'github_timeout';
+
+  constructor(public readonly timeoutMs: number, cause?: unknown) {
+    super(`GitHub CLI command timed out after ${timeoutMs}ms`);
+    this.name = 'GitHubTimeoutError';
+    if (cause) {
+      (this as { cause?: unknown }).cause = cause;
+    }
+  }
+}
+
+/**
+ * Raised when GitHub's search rate limit (far stricter than the core API limit) is exhausted
+ */
+export class GitHubSearchRateLimitError extends Error {
+  readonly statusCode = 429;
+  readonly code = 'github_search_rate_limited';
+  readonly details: { retryAfterSeconds: number | null };
+
+  constructor(public readonly retryAfterSeconds: number | null, cause?: unknown) {
+    super(
+      retryAfterSeconds
+        ? `GitHub search rate limit exceeded; retry in ${retryAfterSeconds}s`
+        : 'GitHub search rate limit exceeded',
+    );
+    this.name = 'GitHubSearchRateLimitError';
+    this.details = { retryAfterSeconds };
+    if (cause) {
+      (this as { cause?: unknown }).cause = cause;
+    }
+  }
+}
+
+/**
+ * Raised when a request needs the user's own GitHub identity but the CLI has no token to act with
+ */
+export class GitHubTokenRequiredError extends Error {
+  readonly statusCode = 501;
+  readonly code = 'github_token_required';
+
+  constructor(cause?: unknown) {
+    super('GitHub CLI is not authenticated; run `gh auth login` or set GH_TOKEN');
+    this.name = 'GitHubTokenRequiredError';
+    if (cause) {
+      (this as { cause?: unknown }).cause = cause;
+    }
+  }
+}
+
+export interface GithubSearchResultItem {
+  type: 'issue' | 'pull_request';
+  number: number;
+  title: string;
+  state: string | null;
+  createdAt: string | null;
+  labels: string[];
+  url: string;
+}
+
+export interface GithubSearchResult {
+  query: string;
+  totalCount: number;
+  incomplete: boolean;
+  page: number;
+  perPage: number;
+  hasMore: boolean;
+  items: GithubSearchResultItem[];
+}
+
+export interface GithubNotification {
+  id: string;
+  /**
+   * `owner/repo` the notification belongs to
+   */
+  repository: string | null;
+  title: string;
+  /**
+   * Subject type as GitHub reports it, e.g. `Issue`, `PullRequest`, `Release` or `CheckSuite`
+   */
+  type: string | null;
+  /**
+   * Why the user was notified, e.g. `mention`, `review_requested` or `subscribed`
+   */
+  reason: string | null;
+  unread: boolean;
+  updatedAt: string | null;
+  /**
+   * Web page for the subject, falling back to the repository when the subject has none
+   */
+  url: string | null;
+}
+
+export interface GithubNotificationOptions {
+  /**
+   * Include notifications already marked as read
+   */
+  all?: boolean;
+  /**
+   * Only notifications updated after this ISO 8601 time
+   */
+  since?: string | null;
+  page?: number;
+  perPage?: number;
+}
+
+export interface GithubNotificationPage {
+  page: number;
+  perPage: number;
+  hasMore: boolean;
+  items: GithubNotification[];
+}
+
+export interface GithubAuthStatus {
+  configured: boolean;
+  valid: boolean;
+  message: string | null;
+}
+
+export interface GithubTokenVerification {
+  valid: boolean;
+  /**
+   * Login the token authenticates as, when GitHub accepted it
+   */
+  login: string | null;
+  message: string | null;
+  checkedAt: string;
+}
+
+export interface GithubSearchOptions {
+  page?: number;
+  perPage?: number;
+}
+
+export interface GithubIssueOptions {
+  /**
+   * Also fetch the body as HTML rendered by GitHub, returned as `bodyHtml`
+   */
+  renderHtml?: boolean;
+}
+
+/**
+ * Issue states GitHub can filter issue listings by
+ */
+export const GITHUB_ISSUE_STATES = ['open', 'closed', 'all'] as const;
+
+export type GithubIssueState = (typeof GITHUB_ISSUE_STATES)[number];
+
+export function isGithubIssueState(value: unknown): value is GithubIssueState {
+  return typeof value === 'string' && (GITHUB_ISSUE_STATES as readonly string[]).includes(value);
+}
+
+export interface GithubIssueListOptions {
+  /**
+   * Which issues to list; defaults to open ones
+   */
+  state?: GithubIssueState;
+}
+
+export interface GithubPullRequestOptions {
+  /**
+   * Branch with the changes, already pushed to the repository
+   */
+  head: string;
+  /**
+   * Branch the pull request merges into
+   */
+  base: string;
+  title: string;
+  body?: string;
+  draft?: boolean;
+}
+
+export interface GithubPullRequest {
+  number: number | null;
+  url: string;
+  draft: boolean;
+}
+
+export interface GithubOpenPullRequest {
+  number: number;
+  url: string;
+  title: string;
+  draft: boolean;
+  /**
+   * Branch the pull request targets
+   */
+  base: string;
+  head: string;
+  author: string | null;
+  createdAt: string | null;
+}
+
+export interface GithubReadme {
+  /**
+   * False when the repository has no README; `content` is then empty
+   */
+  found: boolean;
+  content: string;
+}
+
+export interface GithubRateLimitBucket {
+  limit: number;
+  remaining: number;
+  used: number;
+  /**
+   * ISO timestamp at which the window resets and `remaining` returns to `limit`
+   */
+  resetAt: string | null;
+}
+
+export interface GithubRateLimit {
+  core: GithubRateLimitBucket;
+  search: GithubRateLimitBucket;
+}
+
+/**
+ * Media type that makes the REST API return rendered `body_html` alongside the markdown body
+ */
+export const GITHUB_HTML_MEDIA_TYPE = 'application/vnd.github.html+json';
+
+/**
+ * Media type that makes the REST API return a file's decoded contents instead of base64 JSON
+ */
+export const GITHUB_RAW_MEDIA_TYPE = 'application/vnd.github.raw';
+
+/**
+ * Checks that a GitHub REST API version looks like the date-based versions GitHub publishes
+ * @param value - Candidate version, e.g. `2022-11-28`
+ * @returns True when the value is a YYYY-MM-DD date string
+ */
+export function isGithubApiVersion(value: unknown): value is string {
+  return typeof value === 'string' && GITHUB_API_VERSION_PATTERN.test(value);
+}
+
+/**
+ * Environment variable consulted for an organisation's token; characters outside A-Z and 0-9 become `_`
+ * @param org - GitHub organisation or user, e.g. `acme-corp`
+ * @returns Variable name, e.g. `AGENTRIX_GITHUB_TOKEN_ACME_CORP`
+ */
+export function githubTokenEnvName(org: string): string {
+  return `${GITHUB_TOKEN_ENV_PREFIX}${org.trim().toUpperCase().replace(/[^A-Z0-9]/g, '_')}`;
+}
+
+/**
+ * Picks the token used for requests against an organisation's repositories. The environment variable wins
+ * over the configured mapping; null means the GitHub CLI's own (global) credentials are used.
+ * @param org - GitHub organisation or user that owns the repository
+ * @param options - Tokens configured per organisation (matched case-insensitively), and the environment to
+ * read `AGENTRIX_GITHUB_TOKEN_<ORG>` from
+ * @returns Organisation-specific token, or null to fall back to the global token
+ */
+export function resolveGithubToken(
+  org: string,
+  { orgTokens, env = process.env }: { orgTokens?: Record<string, string> | null; env?: NodeJS.ProcessEnv } = {},
+): string | null {
+  const trimmed = typeof org === 'string' ? org.trim() : '';
+  if (!trimmed) {
+    return null;
+  }
+  const fromEnv = env[githubTokenEnvName(trimmed)]?.trim();
+  if (fromEnv) {
+    return fromEnv;
+  }
+  const key = trimmed.toLowerCase();
+  for (const [configuredOrg, token] of Object.entries(orgTokens ?? {})) {
+    const value = typeof token === 'string' ? token.trim() : '';
+    if (configuredOrg.trim().toLowerCase() === key && value) {
+      return value;
+    }
+  }
+  return null;
+}
 
 function createGithubError(message: string, cause?: unknown): Error {
   const error = new Error(message);
@@ -55,11 +391,50 @@ function parseJsonObject(payload: string, contextMessage: string): Record<string
   throw createGithubError(contextMessage);
 }
 
-async function runGh(args: string[], { timeoutMs = DEFAULT_TIMEOUT_MS }: { timeoutMs?: number } = {}): Promise<string> {
+/**
+ * Runs a GitHub CLI command, retrying server errors and dropped connections with a growing delay.
+ * Timeouts are not retried, so a hung GitHub costs one time budget rather than several.
+ * @param args - Arguments after `gh`
+ * @param options - Time budget, token, and `retry: false` for commands that must not run twice
+ * @returns The command's stdout
+ */
+async function runGh(
+  args: string[],
+  {
+    timeoutMs = DEFAULT_GITHUB_TIMEOUT_MS,
+    token = null,
+    retry = true,
+  }: { timeoutMs?: number; token?: string | null; retry?: boolean } = {},
+): Promise<string> {
+  const delays: readonly number[] = retry ? GITHUB_RETRY_DELAYS_MS : [];
+  for (let attempt = 0; ; attempt += 1) {
+    try {
+      return await runGhOnce(args, { timeoutMs, token });
+    } catch (error: unknown) {
+      const delay = delays[attempt];
+      if (delay === undefined || error instanceof GitHubTimeoutError || !isTransientGhError(error)) {
+        throw error;
+      }
+      await sleep(delay);
+    }
+  }
+}
+
+function isTransientGhError(error: unknown): boolean {
+  return error instanceof Error && TRANSIENT_GH_ERROR_PATTERN.test(error.message);
+}
+
+async function runGhOnce(
+  args: string[],
+  { timeoutMs, token }: { timeoutMs: number; token: string | null },
+): Promise<string> {
+  const run = resolveExecFileAsync();
   try {
-    const { stdout } = await execFileAsync('gh', args, {
+    const { stdout } = await run('gh', args, {
       timeout: timeoutMs,
       maxBuffer: DEFAULT_MAX_BUFFER,
+      // GH_TOKEN takes precedence over gh's stored credentials for this call only
+      ...(token ? { env: { ...process.env, GH_TOKEN: token } } : {}),
     });
     return typeof stdout === 'string' ? stdout : '';
   } catch (error: unknown) {
@@ -68,7 +443,7 @@ async function runGh(args: string[], { timeoutMs = DEFAULT_TIMEOUT_MS }: { timeo
       throw createGithubError('GitHub CLI (gh) is not installed or not available on PATH', error);
     }
     if (err?.code === 'ETIMEDOUT' || err?.signal === 'SIGTERM' || err?.killed) {
-      throw createGithubError('GitHub CLI command timed out', error);
+      throw new GitHubTimeoutError(timeoutMs, error);
     }
     const stderr = typeof err?.stderr === 'string' ? err.stderr.trim() : '';
     const stdout = typeof err?.stdout === 'string' ? err.stdout.trim() : '';
@@ -77,27 +452,219 @@ async function runGh(args: string[], { timeoutMs = DEFAULT_TIMEOUT_MS }: { timeo
   }
 }
 
-export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeoutMs?: number } = {}) {
+/**
+ * Splits `gh api --include` output into lower-cased response headers and the body
+ */
+function splitIncludedResponse(output: string): { headers: Map<string, string>; body: string } {
+  const headers = new Map<string, string>();
+  const separator = output.match(/\r?\n\r?\n/);
+  if (!separator || separator.index === undefined || !/^HTTP\//.test(output)) {
+    return { headers, body: output };
+  }
+  const head = output.slice(0, separator.index);
+  for (const line of head.split(/\r?\n/).slice(1)) {
+    const colon = line.indexOf(':');
+    if (colon > 0) {
+      headers.set(line.slice(0, colon).trim().toLowerCase(), line.slice(colon + 1).trim());
+    }
+  }
+  return { headers, body: output.slice(separator.index + separator[0].length) };
+}
+
+/**
+ * Seconds until GitHub will accept another search, from Retry-After or the rate limit reset time
+ */
+function resolveRetryAfterSeconds(headers: Map<string, string>, nowMs: number): number | null {
+  const retryAfter = Number.parseInt(headers.get('retry-after') ?? '', 10);
+  if (Number.isFinite(retryAfter) && retryAfter >= 0) {
+    return retryAfter;
+  }
+  const reset = Number.parseInt(headers.get('x-ratelimit-reset') ?? '', 10);
+  if (Number.isFinite(reset)) {
+    return Math.max(1, Math.ceil(reset - nowMs / 1000));
+  }
+  return null;
+}
+
+function normaliseSearchDate(value: unknown): string | null {
+  if (typeof value !== 'string' || !value) {
+    return null;
+  }
+  const parsed = new Date(value);
+  return Number.isNaN(parsed.getTime()) ? null : parsed.toISOString();
+}
+
+/**
+ * Maps a subject's REST API URL to its web page, e.g. `…/repos/o/r/pulls/1` to `https://github.com/o/r/pull/1`
+ */
+function toSubjectWebUrl(apiUrl: unknown): string | null {
+  if (typeof apiUrl !== 'string') {
+    return null;
+  }
+  const match = /^https:\/\/api\.github\.com\/repos\/([^/]+\/[^/]+)\/(issues|pulls|commits|releases)\/([^/?#]+)$/.exec(apiUrl);
+  if (!match) {
+    return null;
+  }
+  const [, repoSlug, kind, id] = match;
+  // Releases are addressed by numeric id in the API but only by tag on the web, so link the list instead
+  if (kind === 'releases') {
+    return `https://github.com/${repoSlug}/releases`;
+  }
+  return `https://github.com/${repoSlug}/${kind === 'pulls' ? 'pull' : kind === 'commits' ? 'commit' : 'issues'}/${id}`;
+}
+
+function toNotification(entry: unknown): GithubNotification | null {
+  const record = entry as Record<string, unknown>;
+  const id = record && (typeof record['id'] === 'string' || typeof record['id'] === 'number') ? String(record['id']) : null;
+  if (id === null) {
+    return null;
+  }
+  const subject = (record['subject'] ?? {}) as Record<string, unknown>;
+  const repository = (record['repository'] ?? {}) as Record<string, unknown>;
+  const repoUrl = typeof repository['html_url'] === 'string' ? repository['html_url'] : null;
+  return {
+    id,
+    repository: typeof repository['full_name'] === 'string' ? repository['full_name'] : null,
+    title: typeof subject['title'] === 'string' ? subject['title'] : '',
+    type: typeof subject['type'] === 'string' ? subject['type'] : null,
+    reason: typeof record['reason'] === 'string' ? record['reason'] : null,
+    unread: record['unread'] === true,
+    updatedAt: normaliseSearchDate(record['updated_at']),
+    url: toSubjectWebUrl(subject['url']) ?? repoUrl,
+  };
+}
+
+function toSearchResultItem(entry: unknown, repoSlug: string): GithubSearchResultItem | null {
+  const record = entry as Record<string, unknown>;
+  const number = record && typeof record['number'] === 'number' ? record['number'] : null;
+  if (number === null) {
+    return null;
+  }
+  const type = record['pull_request'] ? 'pull_request' : 'issue';
+  const labels = Array.isArray(record['labels'])
+    ? (record['labels'] as unknown[])
+        .map((label) => {
+          const labelRecord = label as Record<string, unknown>;
+          return labelRecord && typeof labelRecord['name'] === 'string' ? labelRecord['name'] : null;
+        })
+        .filter((label): label is string => Boolean(label))
+    : [];
+  const url =
+    typeof record['html_url'] === 'string' && record['html_url']
+      ? record['html_url']
+      : `https://github.com/${repoSlug}/${type === 'pull_request' ? 'pull' : 'issues'}/${number}`;
+  return {
+    type,
+    number,
+    title: typeof record['title'] === 'string' ? record['title'] : '',
+    state: typeof record['state'] === 'string' && record['state'] ? record['state'].toLowerCase() : null,
+    createdAt: normaliseSearchDate(record['created_at']),
+    labels,
+    url,
+  };
+}
+
+function toRateLimitBucket(entry: unknown): GithubRateLimitBucket {
+  const record = (entry ?? {}) as Record<string, unknown>;
+  const readCount = (value: unknown): number =>
+    typeof value === 'number' && Number.isFinite(value) && value >= 0 ? value : 0;
+  const reset = record['reset'];
+  return {
+    limit: readCount(record['limit']),
+    remaining: readCount(record['remaining']),
+    used: readCount(record['used']),
+    resetAt: typeof reset === 'number' && Number.isFinite(reset) ? new Date(reset * 1000).toISOString() : null,
+  };
+}
+
+/**
+ * Fields read from a REST API pull request
+ */
+interface RestPullRequest {
+  number?: unknown;
+  html_url?: unknown;
+  title?: unknown;
+  draft?: unknown;
+  created_at?: unknown;
+  base?: { ref?: unknown };
+  head?: { ref?: unknown };
+  user?: { login?: unknown };
+}
+
+export interface GithubClientOptions {
+  /**
+   * Time budget of each GitHub CLI call in milliseconds; missing or invalid values use the default
+   */
+  timeoutMs?: number | null;
+  /**
+   * REST API version sent on `gh api` requests; missing or invalid values use the default
+   */
+  apiVersion?: string | null;
+  /**
+   * Tokens used for the repositories of particular organisations, keyed by organisation
+   */
+  orgTokens?: Record<string, string> | null;
+  now?: () => number;
+  /**
+   * Picks the token for an organisation (defaults to resolveGithubToken with the configured tokens)
+   */
+  resolveToken?: (org: string) => string | null;
+}
+
+export type GithubClient = ReturnType<typeof createGithubClient>;
+
+export function createGithubClient({
+  timeoutMs: requestedTimeoutMs,
+  apiVersion: requestedApiVersion,
+  orgTokens,
+  now = Date.now,
+  resolveToken = (org: string) => resolveGithubToken(org, { orgTokens }),
+}: GithubClientOptions = {}) {
+  const timeoutMs =
+    typeof requestedTimeoutMs === 'number' && Number.isFinite(requestedTimeoutMs) && requestedTimeoutMs > 0
+      ? Math.floor(requestedTimeoutMs)
+      : DEFAULT_GITHUB_TIMEOUT_MS;
+  const apiVersion = isGithubApiVersion(requestedApiVersion) ? requestedApiVersion : DEFAULT_GITHUB_API_VERSION;
+  // Only `gh api` accepts custom headers; the higher-level gh subcommands manage their own
+  const buildApiHeaderArgs = (accept: string): string[] => [
+    '--header',
+    `Accept: ${accept}`,
+    '--header',
+    `X-GitHub-Api-Version: ${apiVersion}`,
+    '--header',
+    `User-Agent: ${GITHUB_USER_AGENT}`,
+  ];
+  const apiHeaderArgs = buildApiHeaderArgs('application/vnd.github+json');
+
+  // Once GitHub reports the search budget is spent, answer locally until it resets instead of
+  // burning further requests that would only add to the penalty
+  let searchBlockedUntilMs = 0;
+
+  let lastTokenVerification: GithubTokenVerification | null = null;
+
   async function countOpenPullRequests(org: string, repo: string): Promise<number> {
-    const { repoSlug } = normaliseRepo(org, repo);
+    const { repoSlug, org: owner } = normaliseRepo(org, repo);
+    const token = resolveToken(owner);
     const stdout = await runGh(
       ['pr', 'list', '--repo', repoSlug, '--state', 'open', '--json', 'number', '--limit', '100'],
-      { timeoutMs },
+      { timeoutMs, token },
     );
     return parseJsonArray(stdout, 'Unexpected response when listing pull requests').length;
   }
 
   async function countOpenIssues(org: string, repo: string): Promise<number> {
-    const { repoSlug } = normaliseRepo(org, repo);
+    const { repoSlug, org: owner } = normaliseRepo(org, repo);
+    const token = resolveToken(owner);
     const stdout = await runGh(
       ['issue', 'list', '--repo', repoSlug, '--state', 'open', '--json', 'number', '--limit', '100'],
-      { timeoutMs },
+      { timeoutMs, token },
     );
     return parseJsonArray(stdout, 'Unexpected response when listing issues').length;
   }
 
   async function countRunningWorkflows(org: string, repo: string): Promise<number> {
-    const { repoSlug } = normaliseRepo(org, repo);
+    const { repoSlug, org: owner } = normaliseRepo(org, repo);
+    const token = resolveToken(owner);
     const statuses = ['in_progress', 'queued'];
     const results = await Promise.all(
       statuses.map(async (status) => {
@@ -114,7 +681,7 @@ export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeout
             '--limit',
             '100',
           ],
-          { timeoutMs },
+          { timeoutMs, token },
         );
         return parseJsonArray(stdout, 'Unexpected response when listing workflow runs').length;
       }),
@@ -122,8 +689,22 @@ export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeout
     return results.reduce((total, value) => total + value, 0);
   }
 
-  async function listOpenIssues(org: string, repo: string): Promise<unknown[]> {
-    const { repoSlug } = normaliseRepo(org, repo);
+  /**
+   * Lists a repository's issues, newest first
+   * @param options - Issue state to list; open issues by default
+   * @throws {Error} If the state is not one GitHub understands
+   */
+  async function listIssues(
+    org: string,
+    repo: string,
+    { state = 'open' }: GithubIssueListOptions = {},
+  ): Promise<unknown[]> {
+    // Checked here too since the value ends up on the gh command line
+    if (!isGithubIssueState(state)) {
+      throw new Error(`Unsupported issue state: ${String(state)}`);
+    }
+    const { repoSlug, org: owner } = normaliseRepo(org, repo);
+    const token = resolveToken(owner);
     const stdout = await runGh(
       [
         'issue',
@@ -131,13 +712,13 @@ export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeout
         '--repo',
         repoSlug,
         '--state',
-        'open',
+        state,
         '--json',
-        'number,title,labels,createdAt,url',
+        'number,title,state,labels,createdAt,closedAt,url',
         '--limit',
         '200',
       ],
-      { timeoutMs },
+      { timeoutMs, token },
     );
     const issues = parseJsonArray(stdout, 'Unexpected response when listing issues');
     return issues
@@ -148,14 +729,9 @@ export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeout
           return null;
         }
         const title = typeof issueRecord['title'] === 'string' ? issueRecord['title'] : '';
-        const createdAtValue = typeof issueRecord['createdAt'] === 'string' ? issueRecord['createdAt'] : null;
-        let createdAt = null;
-        if (createdAtValue) {
-          const parsedDate = new Date(createdAtValue);
-          if (!Number.isNaN(parsedDate.getTime())) {
-            createdAt = parsedDate.toISOString();
-          }
-        }
+        const issueState = typeof issueRecord['state'] === 'string' ? issueRecord['state'].toLowerCase() : 'open';
+        const createdAt = normaliseSearchDate(issueRecord['createdAt']);
+        const closedAt = normaliseSearchDate(issueRecord['closedAt']);
         const labels = Array.isArray(issueRecord['labels'])
           ? (issueRecord['labels'] as unknown[])
               .map((label) => {
@@ -170,7 +746,9 @@ export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeout
         return {
           number,
           title,
+          state: issueState,
           createdAt,
+          closedAt,
           labels,
           url,
         };
@@ -178,8 +756,110 @@ export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeout
       .filter(Boolean);
   }
 
-  async function getIssue(org: string, repo: string, issueNumber: number | string): Promise<Record<string, unknown>> {
-    const { repoSlug } = normaliseRepo(org, repo);
+  /**
+   * Opens a pull request from a pushed branch
+   * @param options - Head and base branches, title, body and whether to open it as a draft
+   * @returns The new pull request's number and web URL
+   */
+  async function createPullRequest(
+    org: string,
+    repo: string,
+    { head, base, title, body = '', draft = false }: GithubPullRequestOptions,
+  ): Promise<GithubPullRequest> {
+    const { repoSlug, org: owner } = normaliseRepo(org, repo);
+    const token = resolveToken(owner);
+    const stdout = await runGh(
+      [
+        'pr',
+        'create',
+        '--repo',
+        repoSlug,
+        '--head',
+        head,
+        '--base',
+        base,
+        '--title',
+        title,
+        '--body',
+        body,
+        ...(draft ? ['--draft'] : []),
+      ],
+      // A create that failed after reaching GitHub may still have opened the pull request
+      { timeoutMs, token, retry: false },
+    );
+    // gh prints the new pull request's URL as its last line of output
+    const url = stdout.trim().split('\n').pop()?.trim() || '';
+    if (!/^https?:\/\//.test(url)) {
+      throw createGithubError('Unexpected response when creating pull request');
+    }
+    const number = /\/pull\/(\d+)/.exec(url)?.[1];
+    return { number: number ? Number(number) : null, url, draft };
+  }
+
+  /**
+   * Reports whether the GitHub CLI has credentials for github.com and whether GitHub accepts them
+   * @returns `configured` when gh has a token at all, `valid` when `gh auth status` succeeds
+   * @throws {GitHubTimeoutError} If GitHub does not answer in time
+   */
+  async function getAuthStatus(): Promise<GithubAuthStatus> {
+    try {
+      await runGh(['auth', 'status', '--hostname', 'github.com'], { timeoutMs });
+      return { configured: true, valid: true, message: null };
+    } catch (error: unknown) {
+      if (error instanceof GitHubTimeoutError) {
+        throw error;
+      }
+      const message = error instanceof Error ? error.message : String(error);
+      const configured = !/not logged in|not installed/i.test(message);
+      return { configured, valid: false, message };
+    }
+  }
+
+  /**
+   * Confirms GitHub accepts the CLI's token by fetching the authenticated user. Never throws; the
+   * outcome is cached for getTokenVerification.
+   * @returns Whether the token is valid and, if so, the login it belongs to
+   */
+  async function verifyToken(): Promise<GithubTokenVerification> {
+    let result: Omit<GithubTokenVerification, 'checkedAt'>;
+    try {
+      const stdout = await runGh(['api', ...apiHeaderArgs, 'user'], { timeoutMs });
+      const user = parseJsonObject(stdout, 'Unexpected response when reading the authenticated user');
+      const login = typeof user['login'] === 'string' && user['login'] ? user['login'] : null;
+      result = login
+        ? { valid: true, login, message: null }
+        : { valid: false, login: null, message: 'GitHub did not report a login for the token' };
+    } catch (error: unknown) {
+      result = { valid: false, login: null, message: error instanceof Error ? error.message : String(error) };
+    }
+    lastTokenVerification = { ...result, checkedAt: new Date(now()).toISOString() };
+    return lastTokenVerification;
+  }
+
+  /**
+   * Result of this client's most recent token verification, or null when none has run
+   */
+  function getTokenVerification(): GithubTokenVerification | null {
+    return lastTokenVerification;
+  }
+
+  async function fetchIssueBodyHtml(repoSlug: string, issueNumber: number, token: string | null): Promise<string> {
+    const stdout = await runGh(
+      ['api', '--method', 'GET', ...buildApiHeaderArgs(GITHUB_HTML_MEDIA_TYPE), `repos/${repoSlug}/issues/${issueNumber}`],
+      { timeoutMs, token },
+    );
+    const issue = parseJsonObject(stdout, 'Unexpected response when rendering issue body');
+    return typeof issue['body_html'] === 'string' ? issue['body_html'] : '';
+  }
+
+  async function getIssue(
+    org: string,
+    repo: string,
+    issueNumber: number | string,
+    { renderHtml = false }: GithubIssueOptions = {},
+  ): Promise<Record<string, unknown>> {
+    const { repoSlug, org: owner } = normaliseRepo(org, repo);
+    const token = resolveToken(owner);
     const parsedNumber =
       typeof issueNumber === 'number'
         ? issueNumber
@@ -189,18 +869,22 @@ export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeout
       throw createGithubError('Issue number must be a positive integer');
     }
 
-    const stdout = await runGh(
-      [
-        'issue',
-        'view',
-        String(parsedNumber),
-        '--repo',
-        repoSlug,
-        '--json',
-        'number,title,body,author,createdAt,updatedAt,labels,url,state',
-      ],
-      { timeoutMs },
-    );
+    // gh issue view cannot ask for rendered bodies, so HTML comes from a parallel REST call
+    const [stdout, bodyHtml] = await Promise.all([
+      runGh(
+        [
+          'issue',
+          'view',
+          String(parsedNumber),
+          '--repo',
+          repoSlug,
+          '--json',
+          'number,title,body,author,createdAt,updatedAt,labels,url,state',
+        ],
+        { timeoutMs, token },
+      ),
+      renderHtml ? fetchIssueBodyHtml(repoSlug, parsedNumber, token) : Promise.resolve(null),
+    ]);
 
     const issue = parseJsonObject(stdout, 'Unexpected response when reading issue details');
     const title = typeof issue?.["title"] === 'string' ? issue["title"] : '';
@@ -268,6 +952,7 @@ export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeout
       number: parsedNumber,
       title,
       body,
+      ...(bodyHtml !== null ? { bodyHtml } : {}),
       author,
       createdAt: normaliseDate(createdAtValue),
       updatedAt: normaliseDate(updatedAtValue),
@@ -277,11 +962,272 @@ export function createGithubClient({ timeoutMs = DEFAULT_TIMEOUT_MS }: { timeout
     };
   }
 
+  /**
+   * Searches a repository's issues and pull requests through GitHub's search API
+   * @param org - Repository owner
+   * @param repo - Repository name
+   * @param query - Search terms using GitHub search syntax (e.g. `is:open label:bug crash`)
+   * @param options - Pagination (1-based `page`, `perPage` up to 100)
+   * @returns One page of matches in the issue summary shape, tagged with their type
+   * @throws {GitHubSearchRateLimitError} If the search rate limit is exhausted
+   */
+  async function searchIssues(
+    org: string,
+    repo: string,
+    query: string,
+    { page = 1, perPage = SEARCH_DEFAULT_PER_PAGE }: GithubSearchOptions = {},
+  ): Promise<GithubSearchResult> {
+    const { repoSlug, org: owner } = normaliseRepo(org, repo);
+    const token = resolveToken(owner);
+    const terms = typeof query === 'string' ? query.trim() : '';
+    if (!terms) {
+      throw createGithubError('Search query is required');
+    }
+    const resolvedPage = Number.isInteger(page) && page > 0 ? page : 1;
+    const resolvedPerPage =
+      Number.isInteger(perPage) && perPage > 0 ? Math.min(perPage, SEARCH_MAX_PER_PAGE) : SEARCH_DEFAULT_PER_PAGE;
+
+    const waitMs = searchBlockedUntilMs - now();
+    if (waitMs > 0) {
+      throw new GitHubSearchRateLimitError(Math.ceil(waitMs / 1000));
+    }
+
+    const searchQuery = `repo:${repoSlug} ${terms}`;
+    let output: string;
+    try {
+      output = await runGh(
+        [
+          'api',
+          '--method',
+          'GET',
+          '--include',
+          ...apiHeaderArgs,
+          'search/issues',
+          '-f',
+          `q=${searchQuery}`,
+          '-f',
+          `page=${resolvedPage}`,
+          '-f',
+          `per_page=${resolvedPerPage}`,
+        ],
+        { timeoutMs, token },
+      );
+    } catch (error: unknown) {
+      const message = error instanceof Error ? error.message : '';
+      if (/rate limit/i.test(message)) {
+        const cause = (error as { cause?: { stdout?: unknown } }).cause;
+        const { headers } = splitIncludedResponse(typeof cause?.stdout === 'string' ? cause.stdout : '');
+        const retryAfterSeconds = resolveRetryAfterSeconds(headers, now());
+        if (retryAfterSeconds) {
+          searchBlockedUntilMs = now() + retryAfterSeconds * 1000;
+        }
+        throw new GitHubSearchRateLimitError(retryAfterSeconds, error);
+      }
+      throw error;
+    }
+
+    const { headers, body } = splitIncludedResponse(output);
+    if (headers.get('x-ratelimit-remaining') === '0') {
+      const retryAfterSeconds = resolveRetryAfterSeconds(headers, now());
+      if (retryAfterSeconds) {
+        searchBlockedUntilMs = now() + retryAfterSeconds * 1000;
+      }
+    }
+
+    const data = parseJsonObject(body, 'Unexpected response when searching issues');
+    const totalCount = typeof data['total_count'] === 'number' ? data['total_count'] : 0;
+    const items = Array.isArray(data['items'])
+      ? (data['items'] as unknown[])
+          .map((entry) => toSearchResultItem(entry, repoSlug))
+          .filter((item): item is GithubSearchResultItem => item !== null)
+      : [];
+
+    return {
+      query: terms,
+      totalCount,
+      incomplete: data['incomplete_results'] === true,
+      page: resolvedPage,
+      perPage: resolvedPerPage,
+      hasMore: resolvedPage * resolvedPerPage < Math.min(totalCount, SEARCH_RESULT_CAP),
+      items,
+    };
+  }
+
+  /**
+   * Lists the authenticated user's notifications, newest first. Notifications belong to the user rather
+   * than an organisation, so the CLI's own login is always used.
+   * @param options - `all` to include read notifications, `since` to limit by update time, and pagination
+   *   (1-based `page`, `perPage` up to 50)
+   * @returns One page of notifications
+   * @throws {GitHubTokenRequiredError} If the GitHub CLI has no token
+   */
+  async function listNotifications({
+    all = false,
+    since = null,
+    page = 1,
+    perPage = NOTIFICATIONS_DEFAULT_PER_PAGE,
+  }: GithubNotificationOptions = {}): Promise<GithubNotificationPage> {
+    const resolvedPage = Number.isInteger(page) && page > 0 ? page : 1;
+    const resolvedPerPage =
+      Number.isInteger(perPage) && perPage > 0
+        ? Math.min(perPage, NOTIFICATIONS_MAX_PER_PAGE)
+        : NOTIFICATIONS_DEFAULT_PER_PAGE;
+    const args = [
+      'api',
+      '--method',
+      'GET',
+      '--include',
+      ...apiHeaderArgs,
+      'notifications',
+      '-f',
+      `all=${all}`,
+      '-f',
+      `page=${resolvedPage}`,
+      '-f',
+      `per_page=${resolvedPerPage}`,
+    ];
+    if (since) {
+      args.push('-f', `since=${since}`);
+    }
+
+    let output: string;
+    try {
+      output = await runGh(args, { timeoutMs });
+    } catch (error: unknown) {
+      const message = error instanceof Error ? error.message : '';
+      if (/gh auth login|GH_TOKEN|not logged in/i.test(message)) {
+        throw new GitHubTokenRequiredError(error);
+      }
+      throw error;
+    }
+
+    const { headers, body } = splitIncludedResponse(output);
+    const items = parseJsonArray(body, 'Unexpected response when listing notifications')
+      .map((entry) => toNotification(entry))
+      .filter((item): item is GithubNotification => item !== null);
+
+    return {
+      page: resolvedPage,
+      perPage: resolvedPerPage,
+      hasMore: /rel="next"/.test(headers.get('link') ?? ''),
+      items,
+    };
+  }
+
+  /**
+   * Finds the open pull request whose head is a branch of the repository itself (`head=<owner>:<branch>`),
+   * so a branch that already has one is not given a duplicate
+   * @param org - Repository owner
+   * @param repo - Repository name
+   * @param branch - Head branch
+   * @returns The open pull request, or null when the branch has none
+   */
+  async function findOpenPullRequest(org: string, repo: string, branch: string): Promise<GithubOpenPullRequest | null> {
+    const { repoSlug, org: owner } = normaliseRepo(org, repo);
+    const token = resolveToken(owner);
+    const stdout = await runGh(
+      [
+        'api',
+        '--method',
+        'GET',
+        ...apiHeaderArgs,
+        `repos/${repoSlug}/pulls`,
+        '-f',
+        'state=open',
+        '-f',
+        `head=${owner}:${branch}`,
+        '-f',
+        'per_page=1',
+      ],
+      { timeoutMs, token },
+    );
+    const [entry] = parseJsonArray(stdout, 'Unexpected response when looking up pull requests');
+    const pull = entry as RestPullRequest | undefined;
+    if (!pull || typeof pull.number !== 'number' || typeof pull.html_url !== 'string') {
+      return null;
+    }
+    return {
+      number: pull.number,
+      url: pull.html_url,
+      title: typeof pull.title === 'string' ? pull.title : '',
+      draft: pull.draft === true,
+      base: typeof pull.base?.ref === 'string' ? pull.base.ref : '',
+      head: typeof pull.head?.ref === 'string' ? pull.head.ref : branch,
+      author: typeof pull.user?.login === 'string' ? pull.user.login : null,
+      createdAt: normaliseSearchDate(pull.created_at),
+    };
+  }
+
+  /**
+   * Reads a repository's README from GitHub without needing a clone
+   * @param org - Repository owner
+   * @param repo - Repository name
+   * @returns The decoded README text, or an empty result when the repository has none
+   * @throws {GitHubTokenRequiredError} If there is no token to call GitHub with
+   */
+  async function getReadme(org: string, repo: string): Promise<GithubReadme> {
+    const { repoSlug, org: owner } = normaliseRepo(org, repo);
+    const token = resolveToken(owner);
+    try {
+      const content = await runGh(
+        ['api', '--method', 'GET', ...buildApiHeaderArgs(GITHUB_RAW_MEDIA_TYPE), `repos/${repoSlug}/readme`],
+        { timeoutMs, token },
+      );
+      return { found: true, content };
+    } catch (error: unknown) {
+      const message = error instanceof Error ? error.message : '';
+      if (/HTTP 404/.test(message)) {
+        return { found: false, content: '' };
+      }
+      if (/gh auth login|GH_TOKEN|not logged in/i.test(message)) {
+        throw new GitHubTokenRequiredError(error);
+      }
+      throw error;
+    }
+  }
+
+  /**
+   * Reads the core and search rate limits of the token used for an organisation, or of the CLI's own
+   * login when none is given. GitHub does not count this call against either limit.
+   * @param org - Organisation whose token to inspect
+   * @returns Remaining requests and reset time of each limit
+   * @throws {GitHubTokenRequiredError} If there is no token to call GitHub with
+   */
+  async function getRateLimit(org: string | null = null): Promise<GithubRateLimit> {
+    const token = org ? resolveToken(org.trim()) : null;
+    let stdout: string;
+    try {
+      stdout = await runGh(['api', '--method', 'GET', ...apiHeaderArgs, 'rate_limit'], { timeoutMs, token });
+    } catch (error: unknown) {
+      const message = error instanceof Error ? error.message : '';
+      if (/gh auth login|GH_TOKEN|not logged in/i.test(message)) {
+        throw new GitHubTokenRequiredError(error);
+      }
+      throw error;
+    }
+
+    const data = parseJsonObject(stdout, 'Unexpected response when reading the rate limit');
+    const resources = (data['resources'] ?? {}) as Record<string, unknown>;
+    return {
+      core: toRateLimitBucket(resources['core']),
+      search: toRateLimitBucket(resources['search']),
+    };
+  }
+
   return {
     countOpenPullRequests,
     countOpenIssues,
     countRunningWorkflows,
-    listOpenIssues,
+    listIssues,
+    findOpenPullRequest,
+    getReadme,
+    getRateLimit,
+    createPullRequest,
     getIssue,
+    getAuthStatus,
+    verifyToken,
+    getTokenVerification,
+    searchIssues,
+    listNotifications,
   };
 }
@@ -65,6 +65,11 @@ interface CreatePlanInput {
   codexSessionId?: string | null;
 }
 
+export type ImportPlanInput = Pick<
+  PlanRecord,
+  'id' | 'title' | 'markdown' | 'status' | 'source' | 'createdAt' | 'updatedAt' | 'defaultBranch' | 'worktreeBranch'
+>;
+
 interface UpdatePlanInput {
   markdown?: string;
   status?: PlanStatus;
@@ -410,6 +415,42 @@ export async function createPlan(options: PlanStoreOptions, input: CreatePlanInp
   return record;
 }
 
+/**
+ * Adds a plan exported from another install, keeping its id so importing it again is a no-op. The Codex
+ * session and last change belong to the original install and are not carried over.
+ * @returns The stored plan, and whether it was added rather than already present
+ */
+export async function importPlan(
+  options: PlanStoreOptions,
+  input: ImportPlanInput,
+): Promise<{ plan: PlanRecord; created: boolean }> {
+  const paths = resolvePlanPaths(options);
+  const current = await loadPlansFile(paths);
+  const existing = current.plans.find((entry) => entry.id === input.id);
+  if (existing) {
+    return { plan: clonePlan(existing), created: false };
+  }
+  const record: PlanRecord = {
+    id: requireValue(input.id, 'id'),
+    org: requireValue(options.org, 'org'),
+    repo: requireValue(options.repo, 'repo'),
+    title: input.title?.trim() || 'Untitled Plan',
+    markdown: normaliseMarkdown(input.markdown),
+    status: input.status,
+    source: input.source || { type: 'manual' },
+    createdAt: input.createdAt,
+    updatedAt: input.updatedAt,
+    codexSessionId: null,
+    defaultBranch: input.defaultBranch || null,
+    worktreeBranch: input.worktreeBranch || null,
+    lastChange: null,
+    slug: slugify(input.title || 'Plan'),
+  };
+  current.plans.push(record);
+  await persistPlansFile(paths, current);
+  return { plan: record, created: true };
+}
+
 export async function readPlan(options: PlanStoreOptions & { id: string }): Promise<PlanRecord | null> {
   const paths = resolvePlanPaths(options);
   const current = await loadPlansFile(paths);
@@ -1,9 +1,6 @@
 import { mkdir, readFile, readdir, unlink, writeFile } from 'node:fs/promises';
 import { join } from 'node:path';
-import { execFile } from 'node:child_process';
-import { promisify } from 'node:util';
-
-const execFileAsync = promisify(execFile);
+import { executeGitCommand } from '../repositories/git-repository.js';
 
 export interface PlanEntry {
   id: string;
@@ -68,7 +65,7 @@ function ensureTrailingNewline(text: string): string {
 const DEFAULT_MAX_PLANS_PER_BRANCH = 20;
 
 async function gitAddPlans(cwd: string): Promise<void> {
-  await execFileAsync('git', ['add', '-A', '.plans'], { cwd });
+  await executeGitCommand(['add', '-A', '.plans'], { cwd });
 }
 
 function parsePlanFilename(filename: string): ParsedPlanFilename | null {
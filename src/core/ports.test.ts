@@ -3,6 +3,7 @@ import { describe, it, mock, beforeEach, afterEach } from 'node:test';
 
 import {
   listActivePorts,
+  listProcessListeningPorts,
   createPortTunnelManager,
   __setPortsTestOverrides,
 } from './ports.js';
@@ -97,6 +98,109 @@ describe('ports core utilities', () => {
     });
   });
 
+  describe('listProcessListeningPorts', () => {
+    function useProc(files: Record<string, string>, links: Record<string, string>) {
+      const directories = new Map<string, Set<string>>();
+      for (const filePath of [...Object.keys(files), ...Object.keys(links)]) {
+        const parts = filePath.split('/');
+        for (let index = 2; index < parts.length; index += 1) {
+          const parent = parts.slice(0, index).join('/');
+          const children = directories.get(parent) ?? new Set<string>();
+          children.add(parts[index]!);
+          directories.set(parent, children);
+        }
+      }
+      const missing = (target: string) => Object.assign(new Error(`ENOENT: ${target}`), { code: 'ENOENT' });
+      __setPortsTestOverrides({
+        platform: 'linux',
+        readFile: async (target) => {
+          if (!(target in files)) throw missing(target);
+          return files[target]!;
+        },
+        readdir: async (target) => {
+          const children = directories.get(target);
+          if (!children) throw missing(target);
+          return Array.from(children);
+        },
+        readlink: async (target) => {
+          if (!(target in links)) throw missing(target);
+          return links[target]!;
+        },
+      });
+    }
+
+    const tcpHeader = '  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n';
+
+    it('reports listening sockets owned by the root processes and their descendants on Linux', async () => {
+      useProc(
+        {
+          '/proc/100/stat': '100 (bash) S 1 100 100 0',
+          '/proc/101/stat': '101 (node (vite)) S 100 100 100 0',
+          '/proc/200/stat': '200 (postgres) S 1 200 200 0',
+          '/proc/101/comm': 'node\n',
+          '/proc/200/comm': 'postgres\n',
+          '/proc/net/tcp':
+            tcpHeader +
+            '   0: 00000000:0BB8 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 555 1\n' +
+            '   1: 0100007F:1538 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 777 1\n' +
+            '   2: 0100007F:C350 0100007F:0BB8 01 00000000:00000000 00:00000000 00000000  1000        0 888 1\n',
+          '/proc/net/tcp6':
+            tcpHeader +
+            '   0: 00000000000000000000000000000000:0BB8 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 556 1\n',
+        },
+        {
+          '/proc/100/fd/0': '/dev/pts/1',
+          '/proc/101/fd/0': '/dev/null',
+          '/proc/101/fd/20': 'socket:[555]',
+          '/proc/101/fd/21': 'socket:[556]',
+          '/proc/101/fd/22': 'socket:[888]',
+          '/proc/200/fd/5': 'socket:[777]',
+        },
+      );
+
+      const ports = await listProcessListeningPorts([100]);
+
+      assert.deepEqual(ports, [{ port: 3000, pid: 101, command: 'node' }]);
+    });
+
+    it('parses lsof output on other Unix platforms', async () => {
+      const commands: string[] = [];
+      __setPortsTestOverrides({
+        platform: 'darwin',
+        execCommand: async (command) => {
+          commands.push(command);
+          if (command.startsWith('ps ')) {
+            return { stdout: '  100     1\n  101   100\n  200     1\n', stderr: '' };
+          }
+          return { stdout: 'p101\ncnode\nf20\nn*:5173\nf21\nn[::1]:5173\nf22\nn127.0.0.1:9229\n', stderr: '' };
+        },
+      });
+
+      const ports = await listProcessListeningPorts([100]);
+
+      assert.deepEqual(ports, [
+        { port: 5173, pid: 101, command: 'node' },
+        { port: 9229, pid: 101, command: 'node' },
+      ]);
+      assert.match(commands[1] ?? '', /-p 100,101 /);
+    });
+
+    it('returns an empty list when detection is unsupported or unavailable', async () => {
+      __setPortsTestOverrides({ platform: 'win32' });
+      assert.deepEqual(await listProcessListeningPorts([100]), []);
+
+      __setPortsTestOverrides({
+        platform: 'darwin',
+        execCommand: async () => {
+          throw new Error('ps: command not found');
+        },
+      });
+      assert.deepEqual(await listProcessListeningPorts([100]), []);
+
+      assert.deepEqual(await listProcessListeningPorts([]), []);
+    });
+  });
+
   describe('createPortTunnelManager', () => {
     it('requires an ngrok API key to open tunnels', async () => {
       const forwardMock = mock.fn(async () => ({
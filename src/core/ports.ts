@@ -1,5 +1,6 @@
 import { exec as execCallback } from 'node:child_process';
 import type { ExecOptions } from 'node:child_process';
+import fs from 'node:fs/promises';
 import { promisify } from 'node:util';
 
 const execAsync = promisify(execCallback);
@@ -20,6 +21,9 @@ interface PortsDependencies {
   loadForward: ForwardLoader;
   now: Clock;
   platform: NodeJS.Platform;
+  readFile: (path: string) => Promise<string>;
+  readdir: (path: string) => Promise<string[]>;
+  readlink: (path: string) => Promise<string>;
 }
 
 interface PortListCommandSpec {
@@ -60,6 +64,9 @@ const defaultDependencies: PortsDependencies = {
   },
   now: () => Date.now(),
   platform: process.platform,
+  readFile: (path) => fs.readFile(path, 'utf8'),
+  readdir: (path) => fs.readdir(path),
+  readlink: (path) => fs.readlink(path),
 };
 
 let activeDependencies: PortsDependencies = { ...defaultDependencies };
@@ -115,6 +122,213 @@ export async function listActivePorts(): Promise<number[]> {
   }
 }
 
+export interface ProcessListeningPort {
+  port: number;
+  pid: number;
+  command: string;
+}
+
+// State column value for LISTEN sockets in /proc/net/tcp{,6}
+const PROC_TCP_LISTEN_STATE = '0A';
+
+function collectDescendants(rootPids: number[], parents: Map<number, number>): Set<number> {
+  const children = new Map<number, number[]>();
+  parents.forEach((ppid, pid) => {
+    const siblings = children.get(ppid) ?? [];
+    siblings.push(pid);
+    children.set(ppid, siblings);
+  });
+
+  const result = new Set<number>();
+  const queue = rootPids.filter((pid) => Number.isInteger(pid) && pid > 0);
+  while (queue.length > 0) {
+    const pid = queue.shift()!;
+    if (result.has(pid)) {
+      continue;
+    }
+    result.add(pid);
+    queue.push(...(children.get(pid) ?? []));
+  }
+  return result;
+}
+
+function sortPorts(entries: ProcessListeningPort[]): ProcessListeningPort[] {
+  const unique = new Map<string, ProcessListeningPort>();
+  entries.forEach((entry) => unique.set(`${entry.pid}:${entry.port}`, entry));
+  return Array.from(unique.values()).sort((a, b) => a.port - b.port || a.pid - b.pid);
+}
+
+async function readProcParents(): Promise<Map<number, number>> {
+  const parents = new Map<number, number>();
+  const entries = await activeDependencies.readdir('/proc');
+  await Promise.all(
+    entries
+      .filter((entry) => /^\d+$/.test(entry))
+      .map(async (entry) => {
+        try {
+          const stat = await activeDependencies.readFile(`/proc/${entry}/stat`);
+          // The command name may contain spaces or parentheses, so fields are counted from the last ')'
+          const fields = stat.slice(stat.lastIndexOf(')') + 1).trim().split(/\s+/);
+          const ppid = Number.parseInt(fields[1] ?? '', 10);
+          if (Number.isInteger(ppid)) {
+            parents.set(Number(entry), ppid);
+          }
+        } catch {
+          // The process exited while we were scanning
+        }
+      })
+  );
+  return parents;
+}
+
+async function readProcListeningInodes(): Promise<Map<string, number>> {
+  const inodes = new Map<string, number>();
+  for (const table of ['/proc/net/tcp', '/proc/net/tcp6']) {
+    let content: string;
+    try {
+      // eslint-disable-next-line no-await-in-loop
+      content = await activeDependencies.readFile(table);
+    } catch {
+      continue;
+    }
+    content
+      .split('\n')
+      .slice(1)
+      .forEach((line) => {
+        const columns = line.trim().split(/\s+/);
+        const localAddress = columns[1];
+        const inode = columns[9];
+        if (!localAddress || !inode || columns[3] !== PROC_TCP_LISTEN_STATE) {
+          return;
+        }
+        const port = Number.parseInt(localAddress.slice(localAddress.lastIndexOf(':') + 1), 16);
+        if (Number.isInteger(port) && port > 0) {
+          inodes.set(inode, port);
+        }
+      });
+  }
+  return inodes;
+}
+
+async function listProcListeningPorts(rootPids: number[]): Promise<ProcessListeningPort[]> {
+  const pids = collectDescendants(rootPids, await readProcParents());
+  const listening = await readProcListeningInodes();
+  if (listening.size === 0) {
+    return [];
+  }
+
+  const results: ProcessListeningPort[] = [];
+  await Promise.all(
+    Array.from(pids).map(async (pid) => {
+      let descriptors: string[];
+      try {
+        descriptors = await activeDependencies.readdir(`/proc/${pid}/fd`);
+      } catch {
+        // Exited, or owned by another user
+        return;
+      }
+      const ports = new Set<number>();
+      for (const descriptor of descriptors) {
+        try {
+          // eslint-disable-next-line no-await-in-loop
+          const target = await activeDependencies.readlink(`/proc/${pid}/fd/${descriptor}`);
+          const inode = target.match(/^socket:\[(\d+)\]$/)?.[1];
+          const port = inode ? listening.get(inode) : undefined;
+          if (port) {
+            ports.add(port);
+          }
+        } catch {
+          // Descriptor closed while we were reading
+        }
+      }
+      if (ports.size === 0) {
+        return;
+      }
+      const command = (await activeDependencies.readFile(`/proc/${pid}/comm`).catch(() => '')).trim();
+      ports.forEach((port) => results.push({ port, pid, command }));
+    })
+  );
+  return results;
+}
+
+async function listLsofListeningPorts(rootPids: number[]): Promise<ProcessListeningPort[]> {
+  const { stdout: psOutput } = await activeDependencies.execCommand('ps -Ao pid=,ppid=');
+  const parents = new Map<number, number>();
+  String(psOutput)
+    .split('\n')
+    .forEach((line) => {
+      const [pid = Number.NaN, ppid = Number.NaN] = line.trim().split(/\s+/).map((value) => Number.parseInt(value, 10));
+      if (Number.isInteger(pid) && Number.isInteger(ppid)) {
+        parents.set(pid, ppid);
+      }
+    });
+  const pids = collectDescendants(rootPids, parents);
+  if (pids.size === 0) {
+    return [];
+  }
+
+  let output: string;
+  try {
+    const { stdout } = await activeDependencies.execCommand(
+      `lsof -nP -a -iTCP -sTCP:LISTEN -p ${Array.from(pids).join(',')} -Fpcn`
+    );
+    output = String(stdout);
+  } catch (error: unknown) {
+    // lsof exits 1 when none of the processes has a matching socket
+    if ((error as { code?: unknown })?.code === 1) {
+      return [];
+    }
+    throw error;
+  }
+
+  const results: ProcessListeningPort[] = [];
+  let pid = 0;
+  let command = '';
+  output.split('\n').forEach((line) => {
+    const field = line.charAt(0);
+    const value = line.slice(1).trim();
+    if (field === 'p') {
+      pid = Number.parseInt(value, 10);
+      command = '';
+    } else if (field === 'c') {
+      command = value;
+    } else if (field === 'n') {
+      const port = Number.parseInt(value.slice(value.lastIndexOf(':') + 1), 10);
+      if (pid > 0 && Number.isInteger(port) && port > 0) {
+        results.push({ port, pid, command });
+      }
+    }
+  });
+  return results;
+}
+
+/**
+ * Lists TCP ports being listened on by the given processes or any of their descendants. Linux reads
+ * `/proc` directly; other Unix platforms use `ps` and `lsof`. Platforms without either (Windows)
+ * report no ports rather than failing.
+ * @param rootPids - Process ids to start from, e.g. a terminal's shell
+ * @returns Listening ports with the owning pid and command, sorted by port
+ */
+export async function listProcessListeningPorts(rootPids: number[]): Promise<ProcessListeningPort[]> {
+  if (rootPids.length === 0) {
+    return [];
+  }
+  const { platform } = activeDependencies;
+  if (platform === 'win32') {
+    return [];
+  }
+  try {
+    const ports =
+      platform === 'linux' || platform === 'android'
+        ? await listProcListeningPorts(rootPids)
+        : await listLsofListeningPorts(rootPids);
+    return sortPorts(ports);
+  } catch {
+    // /proc, ps or lsof is missing or unusable; detection is best effort
+    return [];
+  }
+}
+
 export interface PortTunnel {
   port: number;
   url: string;
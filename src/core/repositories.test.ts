@@ -0,0 +1,66 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, beforeEach, describe, it } from 'node:test';
+
+import { moveRepository, RepositoryExistsError } from './repositories.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+
+describe('moveRepository', () => {
+  let workdir: string;
+
+  beforeEach(async () => {
+    workdir = await fs.realpath(await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-move-')));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await fs.writeFile(path.join(repositoryPath, 'README.md'), 'demo\n');
+    await git(repositoryPath, ['add', '-A']);
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', path.join(workdir, 'acme', 'demo', 'login')]);
+  });
+
+  afterEach(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('moves the clone and its worktrees and relinks them', async () => {
+    const result = await moveRepository(workdir, 'acme', 'demo', 'widgets', 'demo-app');
+
+    assert.deepEqual(result, { org: 'widgets', repo: 'demo-app' });
+    await assert.rejects(fs.stat(path.join(workdir, 'acme')), { code: 'ENOENT' });
+
+    const repositoryPath = path.join(workdir, 'widgets', 'demo-app', 'repository');
+    const worktreePath = path.join(workdir, 'widgets', 'demo-app', 'login');
+    const listing = await git(repositoryPath, ['worktree', 'list', '--porcelain']);
+    assert.match(listing, new RegExp(`^worktree ${worktreePath}$`, 'm'));
+    assert.doesNotMatch(listing, /prunable/);
+    assert.equal(await git(worktreePath, ['rev-parse', '--abbrev-ref', 'HEAD']), 'feature/login');
+  });
+
+  it('refuses to overwrite an existing destination', async () => {
+    await fs.mkdir(path.join(workdir, 'widgets', 'demo'), { recursive: true });
+
+    await assert.rejects(moveRepository(workdir, 'acme', 'demo', 'widgets', 'demo'), (error: unknown) => {
+      assert.ok(error instanceof RepositoryExistsError);
+      assert.equal(error.statusCode, 409);
+      return true;
+    });
+    await fs.stat(path.join(workdir, 'acme', 'demo', 'repository'));
+  });
+
+  it('rejects destinations that escape the workdir', async () => {
+    for (const [newOrg, newRepo] of [['..', 'demo'], ['acme', '../../outside'], ['a/b', 'demo']] as const) {
+      await assert.rejects(moveRepository(workdir, 'acme', 'demo', newOrg, newRepo), (error: unknown) => {
+        assert.equal((error as { statusCode?: number }).statusCode, 400);
+        return true;
+      });
+    }
+    await fs.stat(path.join(workdir, 'acme', 'demo', 'login'));
+  });
+});
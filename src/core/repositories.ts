@@ -1,19 +1,56 @@
 import fs from 'node:fs/promises';
 import path from 'node:path';
-import { promisify } from 'node:util';
-import { execFile } from 'node:child_process';
 
 import { ensureRepository, listWorktrees } from './git.js';
-import { disposeSessionsForRepository } from './terminal-sessions.js';
+import { executeGitCommandInRepo, extractGitErrorMessage } from '../repositories/git-repository.js';
+import { resolveRepositoryPaths } from '../repositories/repository-paths.js';
+import { disposeSessionsForRepository, listActiveSessions } from './terminal-sessions.js';
 import { tmuxKillSessionsForRepository } from './tmux.js';
 
-const execFileAsync = promisify(execFile);
 const GIT_MAX_BUFFER = 1024 * 1024;
 
 function normalise(value: unknown): string {
   return typeof value === 'string' ? value.trim() : '';
 }
 
+/**
+ * Error raised when a repository would be moved onto a path that already exists
+ */
+export class RepositoryExistsError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'repository_exists';
+
+  constructor(org: string, repo: string) {
+    super(`Repository ${org}/${repo} already exists`);
+    this.name = 'RepositoryExistsError';
+  }
+}
+
+/**
+ * Error raised when a repository cannot be moved because terminal sessions are running in it
+ */
+export class RepositoryInUseError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'repository_in_use';
+
+  constructor(org: string, repo: string) {
+    super(`Repository ${org}/${repo} has running terminal sessions; close them before moving it`);
+    this.name = 'RepositoryInUseError';
+  }
+}
+
+async function removeEmptyOrgDirectory(repoRoot: string): Promise<void> {
+  const orgPath = path.dirname(repoRoot);
+  try {
+    const remaining = await fs.readdir(orgPath);
+    if (remaining.length === 0) {
+      await fs.rmdir(orgPath);
+    }
+  } catch {
+    // ignore cleanup errors
+  }
+}
+
 export async function removeRepository(workdir: string, orgInput: string, repoInput: string): Promise<void> {
   const org = normalise(orgInput);
   const repo = normalise(repoInput);
@@ -46,28 +83,92 @@ export async function removeRepository(workdir: string, orgInput: string, repoIn
     }
     try {
       // eslint-disable-next-line no-await-in-loop
-      await execFileAsync(
-        'git',
-        ['-C', repositoryPath, 'worktree', 'remove', '--force', targetPath],
-        { maxBuffer: GIT_MAX_BUFFER }
-      );
+      await executeGitCommandInRepo(repositoryPath, ['worktree', 'remove', '--force', targetPath], {
+        maxBuffer: GIT_MAX_BUFFER,
+      });
     } catch (error: unknown) {
-      const err = error as { stderr?: Buffer | string; message?: string };
-      const stderr = err && err.stderr ? err.stderr.toString() : '';
-      const message = stderr || err.message || 'Unknown git error';
-      throw new Error(`Failed to remove worktree ${targetPath}: ${message.trim()}`);
+      throw new Error(`Failed to remove worktree ${targetPath}: ${extractGitErrorMessage(error)}`);
     }
   }
 
   await fs.rm(repoRoot, { recursive: true, force: true });
+  await removeEmptyOrgDirectory(repoRoot);
+}
 
-  const orgPath = path.dirname(repoRoot);
-  try {
-    const remaining = await fs.readdir(orgPath);
-    if (remaining.length === 0) {
-      await fs.rmdir(orgPath);
+/**
+ * Renames a repository by moving its directory, worktrees included, to a new org/repo under the workdir.
+ * Worktrees are re-linked with `git worktree repair`; if that fails the directory is moved back.
+ * @param workdir - Work directory root
+ * @param orgInput - Current organization name
+ * @param repoInput - Current repository name
+ * @param newOrgInput - Destination organization name
+ * @param newRepoInput - Destination repository name
+ * @returns The destination org and repo
+ * @throws {RepositoryExistsError} If the destination already exists
+ * @throws {RepositoryInUseError} If terminal sessions are running in the repository
+ */
+export async function moveRepository(
+  workdir: string,
+  orgInput: string,
+  repoInput: string,
+  newOrgInput: string,
+  newRepoInput: string
+): Promise<{ org: string; repo: string }> {
+  const org = normalise(orgInput);
+  const repo = normalise(repoInput);
+  const newOrg = normalise(newOrgInput);
+  const newRepo = normalise(newRepoInput);
+
+  if (!org || !repo || !newOrg || !newRepo) {
+    throw new Error('org, repo, newOrg and newRepo are required');
+  }
+
+  const source = await ensureRepository(workdir, org, repo);
+  const destination = resolveRepositoryPaths(workdir, newOrg, newRepo);
+
+  const destinationExists = await fs.lstat(destination.repoRoot).then(
+    () => true,
+    (error: unknown) => {
+      if ((error as { code?: string })?.code === 'ENOENT') {
+        return false;
+      }
+      throw error;
     }
-  } catch {
-    // ignore cleanup errors
+  );
+  if (destinationExists) {
+    throw new RepositoryExistsError(newOrg, newRepo);
+  }
+
+  if (listActiveSessions().some((session) => session.org === org && session.repo === repo)) {
+    throw new RepositoryInUseError(org, repo);
+  }
+
+  // Worktrees inside the repository directory travel with it; any elsewhere stay put but still need repairing
+  const sourceRoot = await fs.realpath(source.repoRoot);
+  const repositoryRealPath = await fs.realpath(source.repositoryPath);
+  const worktreePaths = (await listWorktrees(source.repositoryPath))
+    .map((entry) => (entry?.path ? path.resolve(entry.path) : null))
+    .filter((entryPath): entryPath is string => entryPath !== null && entryPath !== repositoryRealPath)
+    .map((entryPath) => {
+      const relative = path.relative(sourceRoot, entryPath);
+      return relative && !relative.startsWith('..') && !path.isAbsolute(relative)
+        ? path.join(destination.repoRoot, relative)
+        : entryPath;
+    });
+
+  await fs.mkdir(path.dirname(destination.repoRoot), { recursive: true });
+  await fs.rename(source.repoRoot, destination.repoRoot);
+
+  try {
+    await executeGitCommandInRepo(destination.repositoryPath, ['worktree', 'repair', ...worktreePaths], {
+      maxBuffer: GIT_MAX_BUFFER,
+    });
+  } catch (error: unknown) {
+    await fs.rename(destination.repoRoot, source.repoRoot);
+    await removeEmptyOrgDirectory(destination.repoRoot);
+    throw new Error(`Failed to relink worktrees after moving ${org}/${repo}: ${extractGitErrorMessage(error)}`);
   }
+
+  await removeEmptyOrgDirectory(source.repoRoot);
+  return { org: newOrg, repo: newRepo };
 }
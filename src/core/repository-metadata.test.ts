@@ -0,0 +1,72 @@
+import assert from 'node:assert/strict';
+import { afterEach, beforeEach, describe, it, mock } from 'node:test';
+import { mkdir, mkdtemp, readdir, rm, stat, writeFile } from 'node:fs/promises';
+import { dirname, join } from 'node:path';
+import { tmpdir } from 'node:os';
+
+import {
+  __setRepositoryMetadataTestOverrides,
+  getRepositoryMetadataPath,
+  loadRepositoryMetadata,
+  saveRepositoryMetadata,
+} from './repository-metadata.js';
+import { MAX_REPOSITORY_METADATA_ENTRIES, RepositoryMetadataError } from '../domain/index.js';
+
+describe('repository-metadata', () => {
+  let homeDir: string;
+
+  beforeEach(async () => {
+    homeDir = await mkdtemp(join(tmpdir(), 'agentrix-repo-meta-'));
+    __setRepositoryMetadataTestOverrides({ homedir: () => homeDir });
+  });
+
+  afterEach(async () => {
+    __setRepositoryMetadataTestOverrides();
+    mock.restoreAll();
+    await rm(homeDir, { recursive: true, force: true }).catch(() => {});
+  });
+
+  it('round-trips metadata through ~/.agentrix/meta/<org>/<repo>.json', async () => {
+    assert.deepEqual(await loadRepositoryMetadata('acme', 'demo'), {});
+
+    const saved = await saveRepositoryMetadata('acme', 'demo', { ' team ': 'platform', priority: 'high' });
+    assert.deepEqual(saved, { team: 'platform', priority: 'high' });
+    assert.equal(getRepositoryMetadataPath('acme', 'demo'), join(homeDir, '.agentrix', 'meta', 'acme', 'demo.json'));
+    assert.deepEqual(await loadRepositoryMetadata('acme', 'demo'), saved);
+    assert.deepEqual(await readdir(join(homeDir, '.agentrix', 'meta', 'acme')), ['demo.json']);
+
+    await saveRepositoryMetadata('acme', 'demo', { team: 'web' });
+    assert.deepEqual(await loadRepositoryMetadata('acme', 'demo'), { team: 'web' });
+
+    await saveRepositoryMetadata('acme', 'demo', {});
+    assert.deepEqual(await loadRepositoryMetadata('acme', 'demo'), {});
+    await assert.rejects(stat(getRepositoryMetadataPath('acme', 'demo')), { code: 'ENOENT' });
+  });
+
+  it('rejects nested values, oversized maps and path segments', async () => {
+    await assert.rejects(saveRepositoryMetadata('acme', 'demo', { team: { name: 'web' } }), RepositoryMetadataError);
+    await assert.rejects(saveRepositoryMetadata('acme', 'demo', ['team']), RepositoryMetadataError);
+    await assert.rejects(saveRepositoryMetadata('acme', 'demo', { note: 'x'.repeat(257) }), RepositoryMetadataError);
+    const tooMany = Object.fromEntries(
+      Array.from({ length: MAX_REPOSITORY_METADATA_ENTRIES + 1 }, (_, index) => [`key${index}`, 'v'])
+    );
+    await assert.rejects(saveRepositoryMetadata('acme', 'demo', tooMany), RepositoryMetadataError);
+    await assert.rejects(saveRepositoryMetadata('..', 'demo', { team: 'web' }), { statusCode: 400 });
+    await assert.rejects(stat(join(homeDir, '.agentrix', 'meta')), { code: 'ENOENT' });
+  });
+
+  it('skips malformed metadata files with a warning', async () => {
+    const warn = mock.method(console, 'warn', () => {});
+    const filePath = getRepositoryMetadataPath('acme', 'demo');
+    await mkdir(dirname(filePath), { recursive: true });
+
+    await writeFile(filePath, '{not json', 'utf8');
+    assert.deepEqual(await loadRepositoryMetadata('acme', 'demo'), {});
+
+    await writeFile(filePath, JSON.stringify({ team: ['web'] }), 'utf8');
+    assert.deepEqual(await loadRepositoryMetadata('acme', 'demo'), {});
+
+    assert.equal(warn.mock.callCount(), 2);
+    assert.match(String(warn.mock.calls[0]?.arguments[0]), /Ignoring malformed repository metadata/);
+  });
+});
@@ -0,0 +1,106 @@
+import { randomUUID } from 'node:crypto';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import {
+  validateRepositoryMetadata,
+  validateRepositorySegment,
+  type RepositoryMetadata,
+} from '../domain/index.js';
+
+const CONFIG_DIR_NAME = '.agentrix';
+const METADATA_DIR_NAME = 'meta';
+
+interface RepositoryMetadataDependencies {
+  homedir: () => string;
+}
+
+let activeDependencies: RepositoryMetadataDependencies = { homedir: os.homedir };
+
+/**
+ * @internal Utility for tests to point the store at a temporary home directory
+ */
+export function __setRepositoryMetadataTestOverrides(overrides?: Partial<RepositoryMetadataDependencies>): void {
+  activeDependencies = { homedir: os.homedir, ...overrides };
+}
+
+/**
+ * Resolves the file holding a repository's metadata: `~/.agentrix/meta/<org>/<repo>.json`
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @returns Absolute file path
+ * @throws {RepositoryIdentifierError} If org or repo is not a single path segment
+ */
+export function getRepositoryMetadataPath(org: string, repo: string): string {
+  return path.join(
+    activeDependencies.homedir(),
+    CONFIG_DIR_NAME,
+    METADATA_DIR_NAME,
+    validateRepositorySegment(org, 'organization'),
+    `${validateRepositorySegment(repo, 'repository')}.json`
+  );
+}
+
+/**
+ * Reads a repository's metadata. Missing files yield an empty map; malformed ones are skipped with a
+ * warning so one bad file never breaks the repository listing.
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @returns Stored metadata
+ */
+export async function loadRepositoryMetadata(org: string, repo: string): Promise<RepositoryMetadata> {
+  const filePath = getRepositoryMetadataPath(org, repo);
+  let raw: string;
+  try {
+    raw = await fs.readFile(filePath, 'utf8');
+  } catch (error: unknown) {
+    if ((error as { code?: string })?.code === 'ENOENT') {
+      return {};
+    }
+    throw error;
+  }
+
+  try {
+    return validateRepositoryMetadata(JSON.parse(raw));
+  } catch (error: unknown) {
+    console.warn(
+      `[agentrix] Ignoring malformed repository metadata at ${filePath}:`,
+      (error as Error)?.message || error
+    );
+    return {};
+  }
+}
+
+/**
+ * Replaces a repository's metadata. The file is written to a temporary name and renamed into place so
+ * readers never see a partial write; an empty map removes the file.
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param metadata - New metadata
+ * @returns The stored metadata
+ * @throws {RepositoryMetadataError} If the metadata is not a flat string map within the limits
+ */
+export async function saveRepositoryMetadata(
+  org: string,
+  repo: string,
+  metadata: unknown
+): Promise<RepositoryMetadata> {
+  const validated = validateRepositoryMetadata(metadata);
+  const filePath = getRepositoryMetadataPath(org, repo);
+
+  if (Object.keys(validated).length === 0) {
+    await fs.rm(filePath, { force: true });
+    return validated;
+  }
+
+  await fs.mkdir(path.dirname(filePath), { recursive: true });
+  const tempPath = `${filePath}.${randomUUID()}.tmp`;
+  try {
+    await fs.writeFile(tempPath, `${JSON.stringify(validated, null, 2)}\n`, 'utf8');
+    await fs.rename(tempPath, filePath);
+  } catch (error) {
+    await fs.rm(tempPath, { force: true });
+    throw error;
+  }
+  return validated;
+}
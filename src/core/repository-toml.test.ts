@@ -0,0 +1,146 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { afterEach, beforeEach, describe, it, mock } from 'node:test';
+
+import { loadRepositoryToml, parseRepositoryToml, REPOSITORY_TOML_FILENAME } from './repository-toml.js';
+
+describe('parseRepositoryToml', () => {
+  it('parses basic and literal strings with their escapes', () => {
+    const parsed = parseRepositoryToml(
+      [
+        'basic = "tab\\there \\"quoted\\" \\\\ \\u00e9\\U0001F600"',
+        "literal = 'C:\\path\\no\\escapes'",
+        'multi = """',
+        'first \\',
+        '    second"""',
+        "raw = '''",
+        "keeps \\n as is'''",
+      ].join('\n')
+    );
+
+    assert.deepEqual(parsed, {
+      basic: 'tab\there "quoted" \\ é😀',
+      literal: 'C:\\path\\no\\escapes',
+      multi: 'first second',
+      raw: 'keeps \\n as is',
+    });
+  });
+
+  it('rejects invalid escapes and unterminated strings', () => {
+    assert.throws(() => parseRepositoryToml('value = "\\q"'), /Invalid escape on line 1/);
+    assert.throws(() => parseRepositoryToml('value = "\\u12"'), /Invalid unicode escape/);
+    assert.throws(() => parseRepositoryToml('ok = 1\nvalue = "open\n"'), /Unterminated string on line 2/);
+    assert.throws(() => parseRepositoryToml("value = 'open"), /Unterminated string/);
+  });
+
+  it('parses arrays, inline tables, booleans and numbers', () => {
+    const parsed = parseRepositoryToml(
+      [
+        'seed_paths = [',
+        '  ".env", # local secrets',
+        "  'config/dev.json',",
+        ']',
+        'nested = [[1, 2], ["a"]]',
+        'inline = { name = "demo", "quoted key" = true }',
+        'enabled = false',
+        'count = 1_000',
+        'hex = 0xff',
+      ].join('\n')
+    );
+
+    assert.deepEqual(parsed, {
+      seed_paths: ['.env', 'config/dev.json'],
+      nested: [[1, 2], ['a']],
+      inline: { name: 'demo', 'quoted key': true },
+      enabled: false,
+      count: 1000,
+      hex: 255,
+    });
+  });
+
+  it('keeps only top-level keys, leaving dotted keys and tables out', () => {
+    const parsed = parseRepositoryToml(
+      [
+        'setup_command = "npm ci"',
+        'tool.name = "ignored"',
+        '',
+        '[section]',
+        'setup_command = "from a table"',
+        '[[entries]]',
+        'default_base = "from an array table"',
+      ].join('\n')
+    );
+
+    assert.deepEqual(parsed, { setup_command: 'npm ci' });
+  });
+
+  it('rejects duplicate keys', () => {
+    assert.throws(
+      () => parseRepositoryToml('setup_command = "a"\nsetup_command = "b"'),
+      /Duplicate key setup_command on line 2/
+    );
+  });
+
+  it('rejects malformed lines', () => {
+    assert.throws(() => parseRepositoryToml('setup_command "npm ci"'), /Expected = after key/);
+    assert.throws(() => parseRepositoryToml('value = [1, 2'), /Expected , or \] in array/);
+    assert.throws(() => parseRepositoryToml('[section'), /Unterminated table header/);
+    assert.throws(() => parseRepositoryToml('when = 2024-01-01'), /Unsupported value/);
+    assert.throws(() => parseRepositoryToml('value = "a" trailing'), /Expected end of line/);
+  });
+});
+
+describe('loadRepositoryToml', () => {
+  let checkout: string;
+
+  beforeEach(async () => {
+    checkout = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-toml-'));
+  });
+
+  afterEach(async () => {
+    mock.restoreAll();
+    await fs.rm(checkout, { recursive: true, force: true });
+  });
+
+  const writeToml = (contents: string) => fs.writeFile(path.join(checkout, REPOSITORY_TOML_FILENAME), contents);
+
+  it('reads the known keys and ignores the rest', async () => {
+    await writeToml(
+      [
+        'setup_command = "  npm ci  "',
+        'default_base = "origin/develop"',
+        'seed_paths = [".env", "config/"]',
+        'unknown = 1',
+      ].join('\n')
+    );
+
+    assert.deepEqual(await loadRepositoryToml(checkout), {
+      setupCommand: 'npm ci',
+      seedPaths: ['.env', 'config'],
+      defaultBase: 'origin/develop',
+    });
+  });
+
+  it('gives the defaults when the file is missing', async () => {
+    assert.deepEqual(await loadRepositoryToml(checkout), { setupCommand: null, seedPaths: [], defaultBase: null });
+  });
+
+  it('warns and gives the defaults when the file is malformed', async () => {
+    const warn = mock.method(console, 'warn', () => {});
+    await writeToml('setup_command = "npm ci"\nsetup_command = "npm install"\n');
+
+    assert.deepEqual(await loadRepositoryToml(checkout), { setupCommand: null, seedPaths: [], defaultBase: null });
+    assert.equal(warn.mock.calls.length, 1);
+    assert.match(String(warn.mock.calls[0]?.arguments[1]), /Duplicate key/);
+  });
+
+  it('drops seed paths that leave the checkout', async () => {
+    await writeToml(
+      'seed_paths = ["../secrets", "/etc/passwd", "config/../../outside", "nested/../.env", ".", "", 42, ".env"]'
+    );
+
+    assert.deepEqual((await loadRepositoryToml(checkout)).seedPaths, ['.env']);
+  });
+});
@@ -0,0 +1,341 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
+
+/**
+ * Per-repository settings file committed at the root of a repository
+ */
+export const REPOSITORY_TOML_FILENAME = '.agentrix.toml';
+
+export interface RepositoryTomlConfig {
+  /**
+   * `setup_command`: run by POST /api/worktrees/setup in place of `--setup-command`
+   */
+  setupCommand: string | null;
+  /**
+   * `seed_paths`: gitignored files or directories copied from the primary clone into new worktrees
+   */
+  seedPaths: string[];
+  /**
+   * `default_base`: ref new branches start from in place of `--default-base`
+   */
+  defaultBase: string | null;
+}
+
+const DEFAULT_CONFIG: RepositoryTomlConfig = Object.freeze({
+  setupCommand: null,
+  seedPaths: [],
+  defaultBase: null,
+});
+
+class TomlSyntaxError extends Error {
+  constructor(message: string, line: number) {
+    super(`${message} on line ${line}`);
+    this.name = 'TomlSyntaxError';
+  }
+}
+
+const BARE_KEY = /[A-Za-z0-9_-]/;
+const SCALAR = /^[^\s,\]}#]+/;
+const NUMBER = /^(?:0x[0-9a-fA-F_]+|0o[0-7_]+|0b[01_]+|[+-]?[0-9][0-9_]*(?:\.[0-9_]+)?(?:[eE][+-]?[0-9_]+)?)$/;
+const ESCAPES: Record<string, string> = { b: '\b', t: '\t', n: '\n', f: '\f', r: '\r', '"': '"', '\\': '\\' };
+
+/**
+ * Parses the TOML agentrix needs: tables, dotted and quoted keys, strings (basic, literal and their
+ * multi-line forms), arrays, inline tables, booleans and numbers. Dates are not supported.
+ * @param source - File contents
+ * @returns Top-level keys; keys inside tables are parsed but left out
+ * @throws {TomlSyntaxError} If the contents are not valid in that subset
+ */
+export function parseRepositoryToml(source: string): Record<string, unknown> {
+  let index = 0;
+  const result: Record<string, unknown> = {};
+  let inTable = false;
+
+  function fail(message: string): never {
+    throw new TomlSyntaxError(message, source.slice(0, index).split('\n').length);
+  }
+  const skipSpaces = (): void => {
+    while (source[index] === ' ' || source[index] === '\t') {
+      index += 1;
+    }
+  };
+  const skipComment = (): void => {
+    if (source[index] === '#') {
+      while (index < source.length && source[index] !== '\n') {
+        index += 1;
+      }
+    }
+  };
+  const skipBlank = (): void => {
+    for (;;) {
+      skipSpaces();
+      skipComment();
+      if (source[index] === '\n' || source[index] === '\r') {
+        index += 1;
+      } else {
+        return;
+      }
+    }
+  };
+  const expectLineEnd = (): void => {
+    skipSpaces();
+    skipComment();
+    if (source[index] === '\r') {
+      index += 1;
+    }
+    if (index < source.length && source[index] !== '\n') {
+      fail('Expected end of line');
+    }
+  };
+
+  const parseBasicString = (multiline: boolean): string => {
+    const delimiter = multiline ? '"""' : '"';
+    index += delimiter.length;
+    if (multiline && source[index] === '\n') {
+      index += 1;
+    }
+    let value = '';
+    while (!source.startsWith(delimiter, index)) {
+      const char = source[index];
+      if (char === undefined || (!multiline && char === '\n')) {
+        fail('Unterminated string');
+      }
+      if (char === '\\') {
+        const next = source[index + 1] ?? '';
+        if (next === 'u' || next === 'U') {
+          const length = next === 'u' ? 4 : 8;
+          const hex = source.slice(index + 2, index + 2 + length);
+          if (!/^[0-9a-fA-F]+$/.test(hex) || hex.length !== length) {
+            fail('Invalid unicode escape');
+          }
+          value += String.fromCodePoint(parseInt(hex, 16));
+          index += 2 + length;
+        } else if (multiline && /[ \t\r\n]/.test(next)) {
+          // Line-ending backslash: drop the newline and the whitespace that follows it
+          index += 1;
+          while (/[ \t\r\n]/.test(source[index] ?? '')) {
+            index += 1;
+          }
+        } else if (Object.prototype.hasOwnProperty.call(ESCAPES, next)) {
+          value += ESCAPES[next]!;
+          index += 2;
+        } else {
+          fail('Invalid escape');
+        }
+      } else {
+        value += char;
+        index += 1;
+      }
+    }
+    index += delimiter.length;
+    return value;
+  };
+
+  const parseLiteralString = (multiline: boolean): string => {
+    const delimiter = multiline ? "'''" : "'";
+    index += delimiter.length;
+    if (multiline && source[index] === '\n') {
+      index += 1;
+    }
+    const end = source.indexOf(delimiter, index);
+    const value = end === -1 ? '' : source.slice(index, end);
+    if (end === -1 || (!multiline && value.includes('\n'))) {
+      fail('Unterminated string');
+    }
+    index = end + delimiter.length;
+    return value;
+  };
+
+  const parseKey = (): string[] => {
+    const parts: string[] = [];
+    for (;;) {
+      skipSpaces();
+      const char = source[index];
+      if (char === '"') {
+        parts.push(parseBasicString(false));
+      } else if (char === "'") {
+        parts.push(parseLiteralString(false));
+      } else {
+        const start = index;
+        while (BARE_KEY.test(source[index] ?? '')) {
+          index += 1;
+        }
+        if (start === index) {
+          fail('Expected a key');
+        }
+        parts.push(source.slice(start, index));
+      }
+      skipSpaces();
+      if (source[index] !== '.') {
+        return parts;
+      }
+      index += 1;
+    }
+  };
+
+  const parseValue = (): unknown => {
+    if (source.startsWith('"""', index)) {
+      return parseBasicString(true);
+    }
+    if (source.startsWith("'''", index)) {
+      return parseLiteralString(true);
+    }
+    const char = source[index];
+    if (char === '"') {
+      return parseBasicString(false);
+    }
+    if (char === "'") {
+      return parseLiteralString(false);
+    }
+    if (char === '[') {
+      index += 1;
+      const items: unknown[] = [];
+      for (;;) {
+        skipBlank();
+        if (source[index] === ']') {
+          index += 1;
+          return items;
+        }
+        items.push(parseValue());
+        skipBlank();
+        if (source[index] === ',') {
+          index += 1;
+        } else if (source[index] !== ']') {
+          fail('Expected , or ] in array');
+        }
+      }
+    }
+    if (char === '{') {
+      index += 1;
+      const table: Record<string, unknown> = {};
+      skipSpaces();
+      if (source[index] === '}') {
+        index += 1;
+        return table;
+      }
+      for (;;) {
+        const key = parseKey();
+        if (source[index] !== '=') {
+          fail('Expected = after key');
+        }
+        index += 1;
+        skipSpaces();
+        table[key.join('.')] = parseValue();
+        skipSpaces();
+        if (source[index] === '}') {
+          index += 1;
+          return table;
+        }
+        if (source[index] !== ',') {
+          fail('Expected , or } in inline table');
+        }
+        index += 1;
+      }
+    }
+    const scalar = SCALAR.exec(source.slice(index))?.[0] ?? '';
+    index += scalar.length;
+    if (scalar === 'true' || scalar === 'false') {
+      return scalar === 'true';
+    }
+    if (NUMBER.test(scalar)) {
+      return Number(scalar.replace(/_/g, ''));
+    }
+    if (/^[+-]?(?:inf|nan)$/.test(scalar)) {
+      return scalar.endsWith('nan') ? NaN : scalar.startsWith('-') ? -Infinity : Infinity;
+    }
+    return fail(scalar ? `Unsupported value ${scalar}` : 'Expected a value');
+  };
+
+  for (;;) {
+    skipBlank();
+    if (index >= source.length) {
+      return result;
+    }
+    if (source[index] === '[') {
+      const arrayTable = source[index + 1] === '[';
+      index += arrayTable ? 2 : 1;
+      parseKey();
+      if (!source.startsWith(arrayTable ? ']]' : ']', index)) {
+        fail('Unterminated table header');
+      }
+      index += arrayTable ? 2 : 1;
+      inTable = true;
+      expectLineEnd();
+      continue;
+    }
+    const key = parseKey();
+    if (source[index] !== '=') {
+      fail('Expected = after key');
+    }
+    index += 1;
+    skipSpaces();
+    const value = parseValue();
+    expectLineEnd();
+    if (!inTable && key.length === 1) {
+      const name = key[0]!;
+      if (Object.prototype.hasOwnProperty.call(result, name)) {
+        fail(`Duplicate key ${name}`);
+      }
+      result[name] = value;
+    }
+  }
+}
+
+function readString(value: unknown): string | null {
+  return typeof value === 'string' ? value.trim() || null : null;
+}
+
+/**
+ * Keeps seed paths that stay inside the checkout; absolute paths and `..` segments are dropped
+ */
+function readSeedPaths(value: unknown): string[] {
+  if (!Array.isArray(value)) {
+    return [];
+  }
+  const paths = new Set<string>();
+  for (const entry of value) {
+    const trimmed = readString(entry);
+    if (!trimmed || path.isAbsolute(trimmed)) {
+      continue;
+    }
+    const normalised = path.normalize(trimmed).replace(/[\\/]+$/, '');
+    if (normalised && normalised !== '.' && !normalised.split(/[\\/]/).includes('..')) {
+      paths.add(normalised);
+    }
+  }
+  return [...paths];
+}
+
+/**
+ * Reads `.agentrix.toml` from the root of a checkout. Unknown keys are ignored; a missing file gives
+ * the defaults, and so does a malformed one after a warning, leaving the server's settings in charge.
+ * @param checkoutPath - Root of the primary clone or of a worktree
+ */
+export async function loadRepositoryToml(checkoutPath: string): Promise<RepositoryTomlConfig> {
+  const configPath = path.join(checkoutPath, REPOSITORY_TOML_FILENAME);
+  let raw: string;
+  try {
+    raw = await fs.readFile(configPath, 'utf8');
+  } catch (error: unknown) {
+    const code = (error as { code?: string })?.code;
+    if (code === 'ENOENT' || code === 'ENOTDIR') {
+      return { ...DEFAULT_CONFIG, seedPaths: [] };
+    }
+    throw error;
+  }
+
+  let parsed: Record<string, unknown>;
+  try {
+    parsed = parseRepositoryToml(raw);
+  } catch (error: unknown) {
+    const err = error as { message?: string };
+    console.warn(`[agentrix] Ignoring malformed ${configPath}:`, err?.message || error);
+    return { ...DEFAULT_CONFIG, seedPaths: [] };
+  }
+
+  return {
+    setupCommand: readString(parsed['setup_command']),
+    seedPaths: readSeedPaths(parsed['seed_paths']),
+    defaultBase: readString(parsed['default_base']),
+  };
+}
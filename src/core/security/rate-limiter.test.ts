@@ -65,6 +65,31 @@ describe('createRateLimiter', () => {
     assert.equal(result.attempts, 0);
   });
 
+  it('counts consumed attempts only while the key is under the limit', () => {
+    let currentTime = 0;
+    const limiter = createRateLimiter({
+      windowMs: 60_000,
+      maxAttempts: 2,
+      now: () => currentTime,
+    });
+
+    assert.equal(limiter.consume('client').limited, false);
+    currentTime = 10_000;
+    assert.equal(limiter.consume('client').limited, false);
+
+    currentTime = 20_000;
+    const blocked = limiter.consume('client');
+    assert.equal(blocked.limited, true);
+    assert.equal(blocked.attempts, 2);
+    assert.equal(blocked.retryAfterMs, 40_000);
+
+    currentTime = 60_000;
+    const reopened = limiter.consume('client');
+    assert.equal(reopened.limited, false);
+    assert.equal(reopened.attempts, 2);
+    assert.equal(limiter.consume('client').limited, true);
+  });
+
   it('validates options', () => {
     assert.throws(() => createRateLimiter({ windowMs: 0, maxAttempts: 1 }), /windowMs/);
     assert.throws(() => createRateLimiter({ windowMs: 1000, maxAttempts: 0 }), /maxAttempts/);
@@ -13,6 +13,10 @@ export interface RateLimiterResult {
 export interface RateLimiter {
   check(key: string): RateLimiterResult;
   recordFailure(key: string): RateLimiterResult;
+  /**
+   * Counts an attempt unless the key is already limited, for callers that meter every request rather than failures
+   */
+  consume(key: string): RateLimiterResult;
   reset(key: string): void;
 }
 
@@ -123,6 +127,17 @@ export function createRateLimiter({ windowMs, maxAttempts, now = () => Date.now(
       const limited = entry.attempts.length >= maxAttempts;
       return buildResult(entry, limited, currentTime);
     },
+    consume(key: string): RateLimiterResult {
+      const entry = ensureEntry(key);
+      const currentTime = now();
+      pruneAttempts(entry, windowMs, currentTime);
+      if (entry.attempts.length >= maxAttempts) {
+        return buildResult(entry, true, currentTime);
+      }
+      entry.attempts.push(currentTime);
+      scheduleEntryCleanup(entries, key, entry, windowMs);
+      return buildResult(entry, false, currentTime);
+    },
     reset(key: string): void {
       const entry = entries.get(key);
       if (!entry) {
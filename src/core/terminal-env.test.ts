@@ -0,0 +1,66 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { afterEach, describe, it, mock } from 'node:test';
+
+import {
+  describeTerminalEnvironment,
+  parseEnvFile,
+  resolveTerminalEnvironment,
+} from './terminal-env.js';
+
+describe('terminal environment', () => {
+  afterEach(() => {
+    mock.restoreAll();
+  });
+
+  it('parses env files, skipping comments and invalid lines without echoing them', () => {
+    const warn = mock.method(console, 'warn', () => {});
+    const env = parseEnvFile(
+      [
+        '# shared settings',
+        'API_KEY=abc=123',
+        'export PATH_PREFIX = /opt/tools/bin',
+        'QUOTED="line one\\nline \\"two\\""',
+        "SINGLE='kept $AS is'",
+        '',
+        'not valid secret-token',
+      ].join('\n')
+    );
+
+    assert.deepEqual(env, {
+      API_KEY: 'abc=123',
+      PATH_PREFIX: '/opt/tools/bin',
+      QUOTED: 'line one\nline "two"',
+      SINGLE: 'kept $AS is',
+    });
+    assert.equal(warn.mock.calls.length, 1);
+    assert.doesNotMatch(String(warn.mock.calls[0]?.arguments[0]), /secret-token/);
+  });
+
+  it('layers the worktree env file over the configured variables', async () => {
+    const worktreePath = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-terminal-env-'));
+    try {
+      const configured = { SHARED: 'server', API_KEY: 'server-key', 'NOT-VALID': 'dropped' };
+      assert.deepEqual(await resolveTerminalEnvironment(worktreePath, configured), {
+        SHARED: 'server',
+        API_KEY: 'server-key',
+      });
+
+      await fs.mkdir(path.join(worktreePath, '.agentrix'));
+      await fs.writeFile(path.join(worktreePath, '.agentrix', 'env'), 'API_KEY=worktree-key\nEXTRA=1\n');
+      assert.deepEqual(await resolveTerminalEnvironment(worktreePath, configured), {
+        SHARED: 'server',
+        API_KEY: 'worktree-key',
+        EXTRA: '1',
+      });
+    } finally {
+      await fs.rm(worktreePath, { recursive: true, force: true });
+    }
+  });
+
+  it('describes variables by name only', () => {
+    assert.equal(describeTerminalEnvironment({ API_KEY: 'secret', DEBUG: '1' }), 'API_KEY, DEBUG');
+  });
+});
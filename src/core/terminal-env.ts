@@ -0,0 +1,97 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
+
+/**
+ * Per-worktree file whose `KEY=VALUE` lines are added to terminal environments
+ */
+export const TERMINAL_ENV_FILE = path.join('.agentrix', 'env');
+
+const ENV_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+
+/**
+ * Checks that a name can be used as an environment variable
+ */
+export function isValidEnvName(name: string): boolean {
+  return ENV_NAME_PATTERN.test(name);
+}
+
+/**
+ * Keeps the configured variables that can be passed to a terminal. They are only passed to spawned
+ * terminals, never assigned to the server's own `process.env`.
+ * @param env - Variables from `--terminal-env` and the config file
+ * @returns The variables with valid names and string values
+ */
+function filterConfiguredEnvironment(env?: Record<string, string> | null): Record<string, string> {
+  return Object.fromEntries(
+    Object.entries(env ?? {}).filter(([name, value]) => isValidEnvName(name) && typeof value === 'string')
+  );
+}
+
+/**
+ * Lists variable names for logs; values never leave the environment they are injected into
+ * @param env - Variables to describe
+ * @returns e.g. `API_KEY, PATH`
+ */
+export function describeTerminalEnvironment(env: Record<string, string>): string {
+  return Object.keys(env).join(', ');
+}
+
+function unquote(value: string): string {
+  const quote = value[0];
+  if ((quote === '"' || quote === "'") && value.length >= 2 && value.endsWith(quote)) {
+    const inner = value.slice(1, -1);
+    return quote === '"' ? inner.replace(/\\(["\\n])/g, (_match, char: string) => (char === 'n' ? '\n' : char)) : inner;
+  }
+  return value;
+}
+
+/**
+ * Parses an env file: `KEY=VALUE` per line, with blank lines, `#` comments and an optional `export `
+ * prefix ignored, and single or double quotes around the value removed
+ * @param content - File contents
+ * @param source - File name used when warning about skipped lines
+ * @returns Parsed variables; later lines win
+ */
+export function parseEnvFile(content: string, source: string = TERMINAL_ENV_FILE): Record<string, string> {
+  const env: Record<string, string> = {};
+  content.split(/\r?\n/).forEach((rawLine, index) => {
+    const line = rawLine.trim();
+    if (!line || line.startsWith('#')) {
+      return;
+    }
+    const assignment = line.replace(/^export\s+/, '');
+    const separator = assignment.indexOf('=');
+    const name = separator === -1 ? '' : assignment.slice(0, separator).trim();
+    if (!isValidEnvName(name)) {
+      // Only the line number is reported so a malformed secret never reaches the logs
+      console.warn(`[agentrix] Ignoring invalid line ${index + 1} in ${source}`);
+      return;
+    }
+    env[name] = unquote(assignment.slice(separator + 1).trim());
+  });
+  return env;
+}
+
+/**
+ * Collects the variables to add to a terminal spawned in a worktree: the configured variables,
+ * overridden by the worktree's `.agentrix/env` file when it exists
+ * @param worktreePath - Worktree the terminal runs in
+ * @param configured - Variables from `--terminal-env` and the config file
+ * @returns Variables to layer over the terminal's base environment
+ */
+export async function resolveTerminalEnvironment(
+  worktreePath: string,
+  configured?: Record<string, string> | null
+): Promise<Record<string, string>> {
+  const envFile = path.join(worktreePath, TERMINAL_ENV_FILE);
+  let fileEnvironment: Record<string, string> = {};
+  try {
+    fileEnvironment = parseEnvFile(await fs.readFile(envFile, 'utf8'), envFile);
+  } catch (error: unknown) {
+    const code = (error as { code?: string })?.code;
+    if (code !== 'ENOENT' && code !== 'ENOTDIR') {
+      console.warn(`[agentrix] Failed to read ${envFile}:`, (error as Error)?.message || error);
+    }
+  }
+  return { ...filterConfiguredEnvironment(configured), ...fileEnvironment };
+}
@@ -1,4 +1,5 @@
 import assert from 'node:assert/strict';
+import { execFileSync, spawn } from 'node:child_process';
 import { EventEmitter } from 'node:events';
 import { describe, it, mock } from 'node:test';
 
@@ -99,6 +100,8 @@ function createTimerHarness(): TimerHarness {
 }
 
 class FakePty extends EventEmitter {
+  public readonly pid = 4242;
+
   public readonly write = mock.fn((input: string) => {
     this.writes.push(input);
   });
@@ -231,6 +234,36 @@ describe('terminal sessions', { concurrency: false }, () => {
     module.__setTerminalSessionsTestOverrides();
   });
 
+  it('serves output produced before a client attaches as history', { concurrency: false }, async () => {
+    mock.reset();
+    const { module, processes, timers } = await loadTerminalSessions();
+    const { TerminalService } = await import('../services/terminal-service.js');
+
+    const result = await module.getOrCreateTerminalSession('/workspace', 'org', 'repo', 'feature', {
+      mode: 'pty',
+    });
+    const session = 'session' in result ? result.session : result;
+    processes[0]?.addData('first\r\nsecond\r\nthird\r\n');
+    await flushMicrotasks();
+    assert.equal(session.watchers.size, 0);
+
+    const service = new TerminalService('/workspace');
+    const full = await service.readHistory({ sessionId: session.id });
+    assert.equal(full.log, 'first\r\nsecond\r\nthird\r\n');
+    assert.equal(full.closed, false);
+    const tail = await service.readHistory({ sessionId: session.id, lines: 2 });
+    assert.equal(tail.log, 'second\r\nthird\r\n');
+    await assert.rejects(service.readHistory({ sessionId: 'missing' }), { statusCode: 404 });
+
+    assert.equal(module.tailSessionLog('a\nb\nprompt$ ', 1), 'prompt$ ');
+    assert.equal(module.tailSessionLog('a\nb', 5), 'a\nb');
+
+    await module.disposeAllSessions();
+    await flushMicrotasks();
+    timers.clearAll();
+    module.__setTerminalSessionsTestOverrides();
+  });
+
   it('persists session snapshots whenever updates are broadcast', { concurrency: false }, async () => {
     mock.reset();
     const { module, persistSessionsSnapshotMock } = await loadTerminalSessions();
@@ -412,6 +445,40 @@ describe('terminal sessions', { concurrency: false }, () => {
     module.__setTerminalSessionsTestOverrides();
   });
 
+  it('passes injected variables to the spawned process without touching the server environment', { concurrency: false }, async () => {
+    mock.reset();
+    const seen: string[] = [];
+    const { module, timers } = await loadTerminalSessions({
+      resolveTerminalEnvironment: async () => ({ AGENTRIX_TEST_TOKEN: 'secret-value' }),
+      // Run a real child with the environment the PTY would get, then hand back a fake terminal
+      spawnPty: ((_command: string, _args: string[], options: { env?: NodeJS.ProcessEnv }) => {
+        seen.push(
+          execFileSync(process.execPath, ['-e', 'process.stdout.write(process.env.AGENTRIX_TEST_TOKEN ?? "")'], {
+            env: options.env,
+            encoding: 'utf8',
+          }),
+        );
+        return new FakePty();
+      }) as never,
+    });
+    const logMock = mock.method(console, 'log', () => {});
+
+    const session = await module.getOrCreateTerminalSession('/workspace', 'org', 'repo', 'feature', { mode: 'pty' });
+    const logged = logMock.mock.calls.map((call) => call.arguments.join(' ')).join('\n');
+    logMock.mock.restore();
+
+    assert.ok(session);
+    assert.deepEqual(seen, ['secret-value']);
+    assert.equal(process.env['AGENTRIX_TEST_TOKEN'], undefined);
+    assert.match(logged, /AGENTRIX_TEST_TOKEN=\*\*\*/);
+    assert.doesNotMatch(logged, /secret-value/);
+
+    await module.disposeAllSessions();
+    await flushMicrotasks();
+    timers.clearAll();
+    module.__setTerminalSessionsTestOverrides();
+  });
+
   it('uses tmux for isolated automation sessions when available', { concurrency: false }, async () => {
     mock.reset();
     const { module, spawnMock, timers } = await loadTerminalSessions({
@@ -468,4 +535,40 @@ describe('terminal sessions', { concurrency: false }, () => {
     module.__setTerminalSessionsTestOverrides();
   });
 
+  it('interrupts the command running in the foreground of a session', { concurrency: false }, async () => {
+    mock.reset();
+    // Stands in for a command the shell is running: detached, so it leads its own process group
+    const sleeper = spawn('sleep', ['30'], { detached: true, stdio: 'ignore' });
+    const exited = new Promise<NodeJS.Signals | null>((resolve) => {
+      sleeper.once('exit', (_code, signal) => resolve(signal));
+    });
+    const readForegroundMock = mock.fn(async (_pid: number) => sleeper.pid ?? null);
+    const { module, processes, timers } = await loadTerminalSessions({
+      readForegroundProcessGroup: (pid) => readForegroundMock(pid),
+    });
+
+    try {
+      const result = await module.getOrCreateTerminalSession('/workspace', 'org', 'repo', 'feature', {
+        mode: 'pty',
+      });
+      const session = 'session' in result ? result.session : result;
+
+      const processGroup = await module.signalSessionForeground(session, 'SIGINT');
+
+      assert.equal(processGroup, sleeper.pid);
+      assert.deepEqual(readForegroundMock.mock.calls[0]?.arguments, [4242]);
+      assert.equal(await exited, 'SIGINT');
+      // Only the foreground command is interrupted; the shell keeps running
+      assert.equal(processes[0]?.kill.mock.calls.length, 0);
+      assert.equal(session.closed, false);
+    } finally {
+      if (sleeper.exitCode === null && sleeper.signalCode === null) {
+        sleeper.kill('SIGKILL');
+      }
+      await module.disposeAllSessions();
+      await flushMicrotasks();
+      timers.clearAll();
+      module.__setTerminalSessionsTestOverrides();
+    }
+  });
 });
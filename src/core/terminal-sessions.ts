@@ -1,4 +1,6 @@
 import { randomUUID } from 'node:crypto';
+import { execFile } from 'node:child_process';
+import { promisify } from 'node:util';
 import pty from 'node-pty';
 import type {
   TerminalSession,
@@ -15,21 +17,44 @@ import {
   isTmuxAvailable,
   makeTmuxSessionName,
   tmuxHasSession,
+  tmuxListPanePids,
 } from './tmux.js';
 import { emitSessionsUpdate } from './event-bus.js';
+import { describeTerminalEnvironment, resolveTerminalEnvironment } from './terminal-env.js';
+import { createLogger } from '../infrastructure/logging/index.js';
 import { persistSessionsSnapshot, loadPersistedSessionsSnapshot } from './session-persistence.js';
 
 const IDLE_TIMEOUT_MS = 90 * 1000;
 const IDLE_SWEEP_INTERVAL_MS = 5 * 1000;
 const DEFAULT_UTF8_LOCALE = 'en_US.UTF-8';
 
+const execFileAsync = promisify(execFile);
+const logger = createLogger();
+
+/**
+ * Reads the foreground process group of the terminal a process is attached to
+ */
+async function readForegroundProcessGroup(pid: number): Promise<number | null> {
+  const { stdout } = await execFileAsync('ps', ['-o', 'tpgid=', '-p', String(pid)]);
+  const pgid = Number.parseInt(stdout.trim(), 10);
+  return Number.isInteger(pgid) && pgid > 0 ? pgid : null;
+}
+
+function killProcessGroup(pgid: number, signal: NodeJS.Signals): void {
+  process.kill(-pgid, signal);
+}
+
 interface TerminalSessionDependencies {
   spawnPty: typeof pty.spawn;
   getWorktreePath: typeof getWorktreePath;
+  resolveTerminalEnvironment: typeof resolveTerminalEnvironment;
   detectTmux: typeof detectTmux;
   isTmuxAvailable: typeof isTmuxAvailable;
   makeTmuxSessionName: typeof makeTmuxSessionName;
   tmuxHasSession: typeof tmuxHasSession;
+  tmuxListPanePids: typeof tmuxListPanePids;
+  readForegroundProcessGroup: typeof readForegroundProcessGroup;
+  killProcessGroup: typeof killProcessGroup;
   emitSessionsUpdate: typeof emitSessionsUpdate;
   persistSessionsSnapshot: typeof persistSessionsSnapshot;
   loadPersistedSessionsSnapshot: typeof loadPersistedSessionsSnapshot;
@@ -42,10 +67,14 @@ interface TerminalSessionDependencies {
 const defaultTerminalSessionDependencies: TerminalSessionDependencies = {
   spawnPty: pty.spawn.bind(pty),
   getWorktreePath,
+  resolveTerminalEnvironment,
   detectTmux,
   isTmuxAvailable,
   makeTmuxSessionName,
   tmuxHasSession,
+  tmuxListPanePids,
+  readForegroundProcessGroup,
+  killProcessGroup,
   emitSessionsUpdate,
   persistSessionsSnapshot,
   loadPersistedSessionsSnapshot,
@@ -328,6 +357,30 @@ export function queueSessionInput(session: TerminalSession, input: string | Buff
   }
 }
 
+/**
+ * Returns the last lines of a session's scrollback. Output is kept in a buffer capped at
+ * MAX_TERMINAL_BUFFER characters, so older output may already have been dropped.
+ * @param log - Buffered session output
+ * @param lines - Number of lines to keep; omitted returns the whole buffer
+ */
+export function tailSessionLog(log: string, lines?: number): string {
+  if (lines === undefined) {
+    return log;
+  }
+  // A trailing newline ends the last line rather than starting an empty one
+  let index = log.endsWith('\n') ? log.length - 1 : log.length;
+  for (let count = 0; count < lines; count += 1) {
+    if (index <= 0) {
+      return log;
+    }
+    index = log.lastIndexOf('\n', index - 1);
+    if (index < 0) {
+      return log;
+    }
+  }
+  return log.slice(index + 1);
+}
+
 function trimLogBuffer(log: string): string {
   if (log.length <= MAX_TERMINAL_BUFFER) {
     return log;
@@ -620,9 +673,64 @@ export function listActiveSessions(): TerminalSession[] {
   return Array.from(terminalSessionsById.values()).filter((session) => session && !session.closed);
 }
 
+/**
+ * Collects the pids at the top of each open terminal for a worktree: the pty process, plus every pane's
+ * process for tmux-backed sessions (tmux runs those under its server rather than under the pty)
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @returns Root process ids, empty when the worktree has no open terminals
+ */
+export async function listWorktreeSessionPids(org: string, repo: string, branch: string): Promise<number[]> {
+  const sessions = listSessionsForKey(makeSessionKey(org, repo, branch)).filter((session) => !session.closed);
+  const listPanePids = resolveTerminalDependency('tmuxListPanePids');
+  const pids = new Set<number>();
+  await Promise.all(
+    sessions.map(async (session) => {
+      if (typeof session.process?.pid === 'number') {
+        pids.add(session.process.pid);
+      }
+      if (session.usingTmux && session.tmuxSessionName) {
+        const panePids = await listPanePids(session.tmuxSessionName).catch(() => []);
+        panePids.forEach((pid) => pids.add(pid));
+      }
+    }),
+  );
+  return Array.from(pids);
+}
+
+/**
+ * Sends a signal to whatever is running in the foreground of a terminal, e.g. to interrupt a command
+ * the way Ctrl-C would without going through the input stream. For tmux-backed sessions the signal goes
+ * to the pane's foreground job rather than to the tmux client the pty runs.
+ * @param session - Terminal session
+ * @param signal - Signal to send
+ * @returns Process group the signal was sent to
+ * @throws {Error} If the session has exited or its foreground process group cannot be determined
+ */
+export async function signalSessionForeground(session: TerminalSession, signal: NodeJS.Signals): Promise<number> {
+  if (session.closed) {
+    throw new Error('Terminal session has exited');
+  }
+  let rootPid: number | undefined = session.process?.pid;
+  if (session.usingTmux && session.tmuxSessionName) {
+    const [panePid] = await resolveTerminalDependency('tmuxListPanePids')(session.tmuxSessionName);
+    rootPid = panePid;
+  }
+  const pgid =
+    typeof rootPid === 'number'
+      ? await resolveTerminalDependency('readForegroundProcessGroup')(rootPid).catch(() => null)
+      : null;
+  if (!pgid) {
+    throw new Error('Unable to determine the foreground process of the terminal');
+  }
+  resolveTerminalDependency('killProcessGroup')(pgid, signal);
+  return pgid;
+}
+
 export async function rehydrateTmuxSessionsFromSnapshot(
   workdir: string,
-  options: { mode?: string } = {},
+  options: { mode?: string; env?: Record<string, string> | null } = {},
 ): Promise<void> {
   if (!workdir || terminalSessionsById.size > 0) {
     return;
@@ -680,6 +788,7 @@ export async function rehydrateTmuxSessionsFromSnapshot(
           kind: snapshot.kind ?? (snapshot.tool === 'agent' ? 'automation' : 'interactive'),
           tmuxSessionName,
           resumeFromTmux: true,
+          env: options.env,
         });
         if (typeof snapshot.label === 'string' && snapshot.label.trim().length > 0) {
           session.label = snapshot.label;
@@ -725,6 +834,7 @@ async function spawnTerminalProcess({
   requireTmux = false,
   tmuxSessionNameOverride,
   reattachOnly = false,
+  env,
 }: {
   workdir: string;
   org: string;
@@ -734,6 +844,7 @@ async function spawnTerminalProcess({
   requireTmux?: boolean;
   tmuxSessionNameOverride?: string | null;
   reattachOnly?: boolean;
+  env?: Record<string, string> | null;
 }) {
   const { worktreePath } = await resolveTerminalDependency('getWorktreePath')(
     workdir,
@@ -768,6 +879,14 @@ async function spawnTerminalProcess({
     TERM_PROGRAM_VERSION: process.env['TERM_PROGRAM_VERSION'] || '1.0',
     FORCE_COLOR: process.env['FORCE_COLOR'] || '1',
   };
+  // Layered onto a copy, so injected values never reach the server's own process.env
+  const injectedEnv = await resolveTerminalDependency('resolveTerminalEnvironment')(worktreePath, env);
+  if (Object.keys(injectedEnv).length > 0) {
+    Object.assign(baseEnv, injectedEnv);
+    logger.debug(
+      `[agentrix] Terminal environment for ${org}/${repo}:${branch}: ${describeTerminalEnvironment(injectedEnv)}`,
+    );
+  }
   if (baseEnv['TMUX']) {
     delete baseEnv['TMUX'];
   }
@@ -789,6 +908,10 @@ async function spawnTerminalProcess({
         : ['new-session', '-s', tmuxSessionName, '-x', '120', '-y', '36'];
 
       if (!tmuxSessionExists) {
+        // A running tmux server builds new sessions from its own environment, so pass injected variables explicitly
+        Object.entries(injectedEnv).forEach(([name, value]) => {
+          tmuxArgs.push('-e', `${name}=${value}`);
+        });
         // Keep the tmux client attached so the PTY stays open on first launch.
         tmuxArgs.push(shellCommand);
         if (args.length > 0) {
@@ -845,6 +968,7 @@ async function createTerminalSession(
     forceUniqueTmux?: boolean;
     tmuxSessionName?: string | null;
     resumeFromTmux?: boolean;
+    env?: Record<string, string> | null;
   } = {}
 ): Promise<TerminalSession> {
   const {
@@ -855,6 +979,7 @@ async function createTerminalSession(
     forceUniqueTmux = false,
     tmuxSessionName: tmuxSessionNameOverride,
     resumeFromTmux = false,
+    env,
   } = options;
   const resolvedKind: SessionKind = kind === 'automation' ? 'automation' : 'interactive';
   const key = makeSessionKey(org, repo, branch);
@@ -877,6 +1002,7 @@ async function createTerminalSession(
     requireTmux,
     tmuxSessionNameOverride: effectiveTmuxSessionName,
     reattachOnly: resumeFromTmux,
+    env,
   });
 
   const session: TerminalSession = {
@@ -930,7 +1056,13 @@ export async function getOrCreateTerminalSession(
   org: string,
   repo: string,
   branch: string,
-  options: { mode?: string; forceNew?: boolean; tool?: SessionTool; kind?: SessionKind } = {}
+  options: {
+    mode?: string;
+    forceNew?: boolean;
+    tool?: SessionTool;
+    kind?: SessionKind;
+    env?: Record<string, string> | null;
+  } = {}
 ) {
   const requireTmux = true;
 
@@ -943,6 +1075,7 @@ export async function getOrCreateTerminalSession(
       tool: requestedTool,
       requireTmux,
       forceUniqueTmux: true,
+      env: options.env,
     });
   }
 
@@ -973,6 +1106,7 @@ export async function getOrCreateTerminalSession(
     kind: options.kind ?? 'interactive',
     tool: options.tool ?? 'terminal',
     requireTmux,
+    env: options.env,
   });
 }
 
@@ -980,11 +1114,13 @@ export async function createIsolatedTerminalSession(
   workdir: string,
   org: string,
   repo: string,
-  branch: string
+  branch: string,
+  options: { env?: Record<string, string> | null } = {}
 ): Promise<TerminalSession> {
   return createTerminalSession(workdir, org, repo, branch, {
     useTmux: true,
     kind: 'automation',
     tool: 'agent',
+    env: options.env,
   });
 }
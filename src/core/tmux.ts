@@ -141,6 +141,25 @@ export async function tmuxHasSession(sessionName: string): Promise<boolean> {
   }
 }
 
+/**
+ * Lists the pids of the processes running in every pane of a tmux session; empty when the session is gone
+ */
+export async function tmuxListPanePids(sessionName: string): Promise<number[]> {
+  try {
+    const { stdout } = await runTmux(['list-panes', '-s', '-t', tmuxTarget(sessionName), '-F', '#{pane_pid}']);
+    return stdout
+      .split('\n')
+      .map((line) => Number.parseInt(line.trim(), 10))
+      .filter((pid) => Number.isInteger(pid) && pid > 0);
+  } catch (error: unknown) {
+    const err = error as { code?: number };
+    if (typeof err.code === 'number') {
+      return [];
+    }
+    throw error;
+  }
+}
+
 export async function tmuxKillSession(sessionName: string): Promise<void> {
   try {
     await runTmux(['kill-session', '-t', tmuxTarget(sessionName)]);
@@ -1,6 +1,6 @@
 import assert from 'node:assert/strict';
 import { afterEach, beforeEach, describe, it } from 'node:test';
-import { mkdtemp, rm, writeFile } from 'node:fs/promises';
+import { mkdir, mkdtemp, rm, symlink, writeFile } from 'node:fs/promises';
 import { join } from 'node:path';
 import { tmpdir } from 'node:os';
 
@@ -24,6 +24,16 @@ describe('resolveWorkdir', () => {
     assert.equal(resolved, tempDir);
   });
 
+  it('keeps a symlinked workdir as given instead of canonicalising it', async () => {
+    const target = join(tempDir, 'real');
+    const link = join(tempDir, 'linked');
+    await mkdir(target);
+    await symlink(target, link, 'dir');
+
+    assert.equal(await resolveWorkdir(link), link);
+    assert.equal(await resolveWorkdir(join(link, '..', 'linked')), link);
+  });
+
   it('throws when directory does not exist', async () => {
     await rm(tempDir, { recursive: true, force: true });
     await assert.rejects(() => resolveWorkdir(tempDir), /Workdir does not exist/);
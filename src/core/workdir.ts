@@ -2,7 +2,8 @@ import fs from 'node:fs/promises';
 import path from 'node:path';
 
 /**
- * Resolves and validates the work directory path
+ * Resolves and validates the work directory path. The path is only made absolute, never canonicalised:
+ * symlinks an operator set up are kept, and only the directory itself has to be reachable.
  * @param workdir - Work directory path
  * @returns Absolute path to work directory
  * @throws {Error} If directory doesn't exist or isn't accessible
@@ -0,0 +1,39 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+
+import { findBranchNameProblems } from './branch-validator.js';
+
+describe('findBranchNameProblems', () => {
+  it('accepts names git allows', () => {
+    for (const branch of ['main', 'feature/login', 'fix-123', 'release/v1.2', 'user@host']) {
+      assert.deepEqual(findBranchNameProblems(branch), []);
+    }
+  });
+
+  it('names each rule a branch breaks', () => {
+    assert.deepEqual(findBranchNameProblems(''), [{ code: 'empty', message: 'Branch name cannot be empty' }]);
+    assert.deepEqual(
+      findBranchNameProblems('-x').map((problem) => problem.code),
+      ['leading_dash']
+    );
+    assert.deepEqual(findBranchNameProblems('a:b\tc'), [
+      { code: 'invalid_character', message: 'Branch name cannot contain :, control characters' },
+    ]);
+    assert.deepEqual(
+      findBranchNameProblems('a@{1}//b..c').map((problem) => problem.code),
+      ['invalid_sequence']
+    );
+    assert.deepEqual(
+      findBranchNameProblems('feature/.hidden').map((problem) => problem.code),
+      ['invalid_component']
+    );
+    assert.deepEqual(
+      findBranchNameProblems('topic.lock').map((problem) => problem.code),
+      ['invalid_component']
+    );
+    assert.deepEqual(
+      findBranchNameProblems('feature/').map((problem) => problem.code),
+      ['invalid_ending']
+    );
+  });
+});
@@ -57,6 +57,75 @@ export function validateBranchName(branch: string): string {
   return normalized;
 }
 
+export type BranchNameProblemCode =
+  | 'empty'
+  | 'leading_dash'
+  | 'invalid_character'
+  | 'invalid_sequence'
+  | 'invalid_component'
+  | 'invalid_ending';
+
+export interface BranchNameProblem {
+  code: BranchNameProblemCode;
+  message: string;
+}
+
+const FORBIDDEN_BRANCH_CHARACTERS = new Set(['~', '^', ':', '?', '*', '[', '\\']);
+
+/**
+ * Names a character git refuses in ref names, or returns null when it is allowed
+ */
+function describeForbiddenCharacter(char: string): string | null {
+  const code = char.charCodeAt(0);
+  if (char === ' ') {
+    return 'space';
+  }
+  if (code < 0x20 || code === 0x7f) {
+    return 'control characters';
+  }
+  return FORBIDDEN_BRANCH_CHARACTERS.has(char) ? char : null;
+}
+
+/**
+ * Explains why git would refuse a branch name, following the rules of `git check-ref-format --branch`
+ * @param branch - Normalized branch name
+ * @returns Every rule the name breaks; empty when git should accept it
+ */
+export function findBranchNameProblems(branch: string): BranchNameProblem[] {
+  if (!branch) {
+    return [{ code: 'empty', message: 'Branch name cannot be empty' }];
+  }
+  const problems: BranchNameProblem[] = [];
+  if (branch.startsWith('-')) {
+    problems.push({ code: 'leading_dash', message: 'Branch name cannot start with -' });
+  }
+  const characters = new Set<string>();
+  for (const char of branch) {
+    const forbidden = describeForbiddenCharacter(char);
+    if (forbidden) {
+      characters.add(forbidden);
+    }
+  }
+  if (characters.size > 0) {
+    problems.push({ code: 'invalid_character', message: `Branch name cannot contain ${[...characters].join(', ')}` });
+  }
+  const sequences = ['..', '@{', '//'].filter((sequence) => branch.includes(sequence));
+  if (sequences.length > 0) {
+    problems.push({ code: 'invalid_sequence', message: `Branch name cannot contain ${sequences.join(', ')}` });
+  }
+  const components = branch.split('/').filter(Boolean);
+  if (branch === '@' || components.some((component) => component.startsWith('.') || component.endsWith('.lock'))) {
+    problems.push({
+      code: 'invalid_component',
+      message: 'Branch name cannot be @, and no part of it can start with . or end with .lock',
+    });
+  }
+  if (branch.startsWith('/') || branch.endsWith('/') || branch.endsWith('.')) {
+    problems.push({ code: 'invalid_ending', message: 'Branch name cannot start or end with /, or end with .' });
+  }
+  return problems;
+}
+
 /**
  * Value object representing a branch name
  */
@@ -0,0 +1,14 @@
+/**
+ * Partial clone filters a clone may request: `blob:none` downloads file contents only when they are
+ * needed, `tree:0` also defers directory listings
+ */
+export const CLONE_FILTERS = ['blob:none', 'tree:0'] as const;
+
+export type CloneFilter = (typeof CLONE_FILTERS)[number];
+
+/**
+ * Checks that a value is one of the supported partial clone filters
+ */
+export function isCloneFilter(value: unknown): value is CloneFilter {
+  return typeof value === 'string' && (CLONE_FILTERS as readonly string[]).includes(value);
+}
@@ -0,0 +1,39 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+
+import { CloneLayoutError, parseCloneLayout, renderCloneLayout } from './clone-layout.js';
+
+describe('parseCloneLayout', () => {
+  it('defaults to the org/repo layout', () => {
+    const layout = parseCloneLayout();
+    assert.equal(layout.template, '{org}/{repo}');
+    assert.deepEqual(renderCloneLayout(layout, 'acme', 'demo'), ['acme', 'demo']);
+  });
+
+  it('supports reordered layouts, aliases, and literal directories', () => {
+    const reordered = parseCloneLayout('{repository}/{org}');
+    assert.deepEqual(renderCloneLayout(reordered, 'acme', 'demo'), ['demo', 'acme']);
+
+    const nested = parseCloneLayout('repos/{workspace}/{repo}');
+    assert.deepEqual(renderCloneLayout(nested, 'acme', 'demo'), ['repos', 'acme', 'demo']);
+  });
+
+  it('requires exactly one repo placeholder', () => {
+    assert.throws(() => parseCloneLayout('{org}'), CloneLayoutError);
+    assert.throws(() => parseCloneLayout('{repo}/{repo}'), CloneLayoutError);
+  });
+
+  it('requires exactly one org placeholder', () => {
+    assert.throws(() => parseCloneLayout('{repo}'), /must contain \{org\} exactly once/);
+    assert.throws(() => parseCloneLayout('repos/{repo}'), CloneLayoutError);
+    assert.throws(() => parseCloneLayout('{org}/{workspace}/{repo}'), CloneLayoutError);
+  });
+
+  it('rejects unknown placeholders, mixed segments, and relative paths', () => {
+    assert.throws(() => parseCloneLayout('{team}/{repo}'), /Unknown clone layout placeholder/);
+    assert.throws(() => parseCloneLayout('{org}-{repo}'), /placeholder or a plain directory name/);
+    assert.throws(() => parseCloneLayout('../{repo}'), /empty or relative segment/);
+    assert.throws(() => parseCloneLayout('/{repo}'), /empty or relative segment/);
+    assert.throws(() => parseCloneLayout('  '), /cannot be empty/);
+  });
+});
@@ -0,0 +1,96 @@
+/**
+ * Layout used when none is configured: `<workdir>/<org>/<repo>`
+ */
+export const DEFAULT_CLONE_LAYOUT = '{org}/{repo}';
+
+const TOKEN_ALIASES: Record<string, 'org' | 'repo'> = {
+  org: 'org',
+  workspace: 'org',
+  repo: 'repo',
+  repository: 'repo',
+};
+
+export type CloneLayoutSegment =
+  | { type: 'org' }
+  | { type: 'repo' }
+  | { type: 'literal'; value: string };
+
+export interface CloneLayout {
+  template: string;
+  segments: CloneLayoutSegment[];
+}
+
+/**
+ * Error thrown when a clone layout template is malformed
+ */
+export class CloneLayoutError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = 'CloneLayoutError';
+  }
+}
+
+/**
+ * Parses a clone layout template such as `{org}/{repo}` or `repos/{org}/{repo}`.
+ * Each path segment must be a single placeholder (`{org}`/`{workspace}`, `{repo}`/`{repository}`)
+ * or a literal directory name, and `{org}` and `{repo}` must each appear exactly once. Without `{org}`,
+ * same-named repositories of different organisations would share a directory, and the organisation
+ * could only be recovered by asking git for every clone.
+ * @param template - Layout template relative to the workdir
+ * @returns Parsed layout
+ * @throws {CloneLayoutError} If the template is invalid
+ */
+export function parseCloneLayout(template: string = DEFAULT_CLONE_LAYOUT): CloneLayout {
+  const trimmed = typeof template === 'string' ? template.trim() : '';
+  if (!trimmed) {
+    throw new CloneLayoutError('Clone layout cannot be empty');
+  }
+
+  const parts = trimmed.replace(/\\/g, '/').split('/');
+  const segments = parts.map((part): CloneLayoutSegment => {
+    if (!part || part === '.' || part === '..') {
+      throw new CloneLayoutError(`Clone layout "${trimmed}" contains an empty or relative segment`);
+    }
+    const tokenMatch = part.match(/^\{([a-z]+)\}$/);
+    if (tokenMatch) {
+      const type = TOKEN_ALIASES[tokenMatch[1]!];
+      if (!type) {
+        throw new CloneLayoutError(`Unknown clone layout placeholder ${part}`);
+      }
+      return { type };
+    }
+    if (/[{}]/.test(part)) {
+      throw new CloneLayoutError(`Clone layout segment "${part}" must be a placeholder or a plain directory name`);
+    }
+    return { type: 'literal', value: part };
+  });
+
+  const repoCount = segments.filter((segment) => segment.type === 'repo').length;
+  if (repoCount !== 1) {
+    throw new CloneLayoutError('Clone layout must contain {repo} exactly once');
+  }
+  if (segments.filter((segment) => segment.type === 'org').length !== 1) {
+    throw new CloneLayoutError('Clone layout must contain {org} exactly once');
+  }
+
+  return { template: trimmed, segments };
+}
+
+/**
+ * Renders the relative repository root for an org/repo pair
+ * @param layout - Parsed layout
+ * @param org - Organisation name
+ * @param repo - Repository name
+ * @returns Path segments relative to the workdir
+ */
+export function renderCloneLayout(layout: CloneLayout, org: string, repo: string): string[] {
+  return layout.segments.map((segment) => {
+    if (segment.type === 'org') {
+      return org;
+    }
+    if (segment.type === 'repo') {
+      return repo;
+    }
+    return segment.value;
+  });
+}
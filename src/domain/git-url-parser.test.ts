@@ -1,7 +1,13 @@
 import assert from 'node:assert/strict';
 import { describe, it } from 'node:test';
 
-import { GitUrl, parseRepositoryUrl } from './git-url-parser.js';
+import {
+  GitUrl,
+  RepositoryUrlError,
+  UnsupportedUrlSchemeError,
+  detectRepositoryUrlScheme,
+  parseRepositoryUrl,
+} from './git-url-parser.js';
 
 describe('parseRepositoryUrl', () => {
   it('parses SSH Git URLs', () => {
@@ -19,8 +25,8 @@ describe('parseRepositoryUrl', () => {
     assert.equal(result.repo, 'agentrix');
   });
 
-  it('parses file system style paths', () => {
-    const result = parseRepositoryUrl('/repos/vultuk/agentrix');
+  it('parses file system style paths when the file scheme is enabled', () => {
+    const result = parseRepositoryUrl('/repos/vultuk/agentrix', { allowedSchemes: ['file'] });
 
     assert.equal(result.org, 'vultuk');
     assert.equal(result.repo, 'agentrix');
@@ -49,9 +55,99 @@ describe('parseRepositoryUrl', () => {
     assert.equal(result.repo, 'agentrix.v1');
   });
 
-  it('throws for invalid inputs', () => {
-    assert.throws(() => parseRepositoryUrl(''), /Repository URL is required/);
-    assert.throws(() => parseRepositoryUrl('example'), /Unable to determine repository/);
+  it('rejects file URLs and local paths by default', () => {
+    for (const input of ['file:///srv/vultuk/agentrix.git', '/repos/vultuk/agentrix']) {
+      assert.throws(
+        () => parseRepositoryUrl(input),
+        (error: unknown) => {
+          assert.ok(error instanceof UnsupportedUrlSchemeError);
+          assert.equal(error.statusCode, 400);
+          assert.equal(error.kind, 'unsupported_scheme');
+          assert.equal(error.code, 'unsupported_scheme');
+          assert.equal(error.scheme, 'file');
+          return true;
+        }
+      );
+    }
+  });
+
+  it('honours a custom scheme allowlist', () => {
+    assert.throws(
+      () => parseRepositoryUrl('https://github.com/vultuk/agentrix.git', { allowedSchemes: ['ssh'] }),
+      UnsupportedUrlSchemeError
+    );
+    const result = parseRepositoryUrl('file:///srv/vultuk/agentrix.git', { allowedSchemes: ['FILE://'] });
+    assert.equal(result.repo, 'agentrix');
+  });
+
+  it('detects repository URL schemes', () => {
+    assert.equal(detectRepositoryUrlScheme('HTTPS://github.com/a/b'), 'https');
+    assert.equal(detectRepositoryUrlScheme('ssh://git@github.com/a/b'), 'ssh');
+    assert.equal(detectRepositoryUrlScheme('git@github.com:a/b.git'), 'git@');
+    assert.equal(detectRepositoryUrlScheme('../a/b'), 'file');
+  });
+
+  it('reports which part of an invalid URL failed', () => {
+    const cases: Array<[string, string, string, RegExp]> = [
+      ['', 'empty', 'repository_url_required', /Repository URL is required/],
+      ['   ', 'empty', 'repository_url_required', /Repository URL is required/],
+      ['example', 'missing_owner_or_repo', 'repository_url_incomplete', /Unable to determine repository/],
+      ['git@github.com:agentrix', 'invalid_ssh', 'invalid_ssh_url', /git@host:org\/repo\.git/],
+      ['git@github.com', 'invalid_ssh', 'invalid_ssh_url', /SSH repository URLs/],
+    ];
+
+    for (const [input, kind, code, message] of cases) {
+      assert.throws(
+        () => parseRepositoryUrl(input),
+        (error: unknown) => {
+          assert.ok(error instanceof RepositoryUrlError, `expected RepositoryUrlError for "${input}"`);
+          assert.equal(error.kind, kind);
+          assert.equal(error.code, code);
+          assert.equal(error.statusCode, 400);
+          assert.match(error.message, message);
+          return true;
+        }
+      );
+    }
+  });
+});
+
+describe('parseRepositoryUrl with single-segment URLs', () => {
+  it('rejects URLs without an organization when no default is configured', () => {
+    for (const input of ['https://git.internal/agentrix.git', 'ssh://git@git.internal/agentrix.git']) {
+      assert.throws(
+        () => parseRepositoryUrl(input),
+        (error: unknown) => error instanceof RepositoryUrlError && error.kind === 'missing_owner_or_repo'
+      );
+    }
+    assert.throws(
+      () => parseRepositoryUrl('git@git.internal:agentrix.git'),
+      (error: unknown) => error instanceof RepositoryUrlError && error.kind === 'invalid_ssh'
+    );
+  });
+
+  it('places them under the default organization when one is configured', () => {
+    for (const input of [
+      'https://git.internal/agentrix.git',
+      'ssh://git@git.internal/agentrix.git',
+      'git@git.internal:agentrix.git',
+    ]) {
+      assert.deepEqual(parseRepositoryUrl(input, { defaultOrg: 'platform' }), {
+        org: 'platform',
+        repo: 'agentrix',
+        url: input,
+      });
+    }
+  });
+
+  it('keeps the organization of two-segment URLs and validates the default', () => {
+    const result = parseRepositoryUrl('https://git.internal/vultuk/agentrix.git', { defaultOrg: 'platform' });
+    assert.equal(result.org, 'vultuk');
+    assert.throws(
+      () => parseRepositoryUrl('https://git.internal/agentrix.git', { defaultOrg: '..' }),
+      /organization cannot be a traversal segment/i
+    );
+    assert.throws(() => parseRepositoryUrl('example', { defaultOrg: 'platform' }), RepositoryUrlError);
   });
 });
 
@@ -10,20 +10,115 @@ export interface GitUrlParts {
   url: string;
 }
 
+export interface ParseRepositoryUrlOptions {
+  /**
+   * Schemes permitted for the URL (defaults to DEFAULT_ALLOWED_URL_SCHEMES)
+   */
+  allowedSchemes?: readonly string[];
+  /**
+   * Organization used for URLs that name only a repository, such as `https://git.internal/repo.git` on
+   * self-hosted servers; without it such URLs are rejected
+   */
+  defaultOrg?: string | null;
+}
+
+/**
+ * Schemes accepted when no allowlist is configured. `git@` covers scp-style SSH remotes.
+ * Local paths and `file://` URLs map to the `file` scheme and must be enabled explicitly.
+ */
+export const DEFAULT_ALLOWED_URL_SCHEMES: readonly string[] = ['https', 'ssh', 'git@'];
+
+/**
+ * Which part of a repository URL failed to parse
+ */
+export type RepositoryUrlErrorKind = 'empty' | 'unsupported_scheme' | 'missing_owner_or_repo' | 'invalid_ssh';
+
+const REPOSITORY_URL_ERROR_CODES: Record<RepositoryUrlErrorKind, string> = {
+  empty: 'repository_url_required',
+  unsupported_scheme: 'unsupported_scheme',
+  missing_owner_or_repo: 'repository_url_incomplete',
+  invalid_ssh: 'invalid_ssh_url',
+};
+
+/**
+ * Error thrown when a repository URL cannot be parsed. `kind` identifies the failure and
+ * `code` is the matching machine-readable API error code.
+ */
+export class RepositoryUrlError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly kind: RepositoryUrlErrorKind;
+  public readonly code: string;
+
+  constructor(kind: RepositoryUrlErrorKind, message: string) {
+    super(message);
+    this.name = 'RepositoryUrlError';
+    this.kind = kind;
+    this.code = REPOSITORY_URL_ERROR_CODES[kind];
+  }
+}
+
+/**
+ * Error thrown when a repository URL uses a scheme outside the allowlist.
+ */
+export class UnsupportedUrlSchemeError extends RepositoryUrlError {
+  public readonly scheme: string;
+
+  constructor(scheme: string) {
+    super('unsupported_scheme', `Repository URL scheme "${scheme}" is not allowed`);
+    this.name = 'UnsupportedUrlSchemeError';
+    this.scheme = scheme;
+  }
+}
+
+/**
+ * Determines the scheme of a repository URL
+ * @param input - Repository URL
+ * @returns Lowercase scheme (`https`, `ssh`, `file`, ...), `git@` for scp-style remotes,
+ * or `file` for plain paths
+ */
+export function detectRepositoryUrlScheme(input: string): string {
+  const trimmed = input.trim();
+  const schemeMatch = trimmed.match(/^([a-z][a-z0-9+.-]*):\/\//i);
+  if (schemeMatch) {
+    return schemeMatch[1]!.toLowerCase();
+  }
+  if (/^[^\s/@:]+@[^\s/:]+:/.test(trimmed)) {
+    return 'git@';
+  }
+  return 'file';
+}
+
+/**
+ * Normalises a list of allowed schemes (trims, lowercases, strips `://`, drops empties)
+ * @param schemes - Raw scheme list
+ * @returns Normalised scheme list
+ */
+export function normalizeAllowedUrlSchemes(schemes: readonly string[]): string[] {
+  const normalised = schemes
+    .map((scheme) => (typeof scheme === 'string' ? scheme.trim().toLowerCase() : ''))
+    .map((scheme) => scheme.replace(/:\/\/$/, ''))
+    .filter(Boolean);
+  return Array.from(new Set(normalised));
+}
+
 /**
  * Parses a Git repository URL into org and repo components
  * @param input - Repository URL (SSH, HTTPS, or path format)
+ * @param options - Parse options
  * @returns Object with {org, repo, url} properties
- * @throws {Error} If URL cannot be parsed
+ * @throws {UnsupportedUrlSchemeError} If the URL scheme is not allowed
+ * @throws {RepositoryUrlError} If the URL is empty, malformed SSH, or lacks an org/repo path
  */
-export function parseRepositoryUrl(input: string): GitUrlParts {
+export function parseRepositoryUrl(input: string, options: ParseRepositoryUrlOptions = {}): GitUrlParts {
   if (typeof input !== 'string' || !input.trim()) {
-    throw new Error('Repository URL is required');
+    throw new RepositoryUrlError('empty', 'Repository URL is required');
   }
 
   const trimmed = input.trim();
   let org = '';
   let repo = '';
+  // The only path segment of a URL that names a repository without its organization
+  let soleSegment = '';
 
   // Try SSH format: git@github.com:org/repo.git
   const sshMatch = trimmed.match(/^git@[^:]+:([^/]+)\/(.+)$/);
@@ -32,21 +127,25 @@ export function parseRepositoryUrl(input: string): GitUrlParts {
     repo = sshMatch[2]!;
   } else {
     // Try HTTPS/HTTP URL format
+    let parsedAsUrl = false;
     try {
       if (/^[a-z]+:\/\//i.test(trimmed)) {
         const url = new URL(trimmed);
+        parsedAsUrl = true;
         const parts = url.pathname.replace(/^\//, '').split('/').filter(Boolean);
         if (parts.length >= 2) {
           org = parts[parts.length - 2]!;
           repo = parts[parts.length - 1]!;
+        } else if (parts.length === 1) {
+          soleSegment = parts[0]!;
         }
       }
     } catch {
       // ignore URL parsing errors and fall back to manual parsing
     }
 
-    // If not parsed yet, try path-like format
-    if (!org || !repo) {
+    // If not parsed yet, try path-like format. A parsed URL's host is never taken for the organization.
+    if (!parsedAsUrl && (!org || !repo)) {
       const cleaned = trimmed.replace(/\.git$/, '');
       const segments = cleaned.split(/[\\/]+/).filter(Boolean);
       if (segments.length >= 2) {
@@ -56,21 +155,41 @@ export function parseRepositoryUrl(input: string): GitUrlParts {
     }
 
     // Try colon-separated format (alternate SSH style)
-    if ((!org || !repo) && trimmed.includes(':')) {
+    if (!parsedAsUrl && (!org || !repo) && trimmed.includes(':')) {
       const tail = trimmed.split(':').pop() || '';
       const segments = tail.replace(/\.git$/, '').split('/').filter(Boolean);
       if (segments.length >= 2) {
         org = segments[segments.length - 2]!;
         repo = segments[segments.length - 1]!;
+      } else if (segments.length === 1 && /^[^\s/@:]+@[^\s/:]+:/.test(trimmed)) {
+        soleSegment = segments[0]!;
       }
     }
   }
 
+  // Two segments always win; the configured organization only fills in for a missing one
+  if ((!org || !repo) && soleSegment && options.defaultOrg) {
+    org = options.defaultOrg;
+    repo = soleSegment;
+  }
+
   // Remove .git extension from repo name
   repo = repo ? repo.replace(/\.git$/, '') : repo;
 
   if (!org || !repo) {
-    throw new Error('Unable to determine repository organisation and name from URL');
+    if (/^[^\s/@:]+@/.test(trimmed)) {
+      throw new RepositoryUrlError('invalid_ssh', 'SSH repository URLs must look like git@host:org/repo.git');
+    }
+    throw new RepositoryUrlError(
+      'missing_owner_or_repo',
+      'Unable to determine repository organisation and name from URL'
+    );
+  }
+
+  const allowedSchemes = normalizeAllowedUrlSchemes(options.allowedSchemes ?? DEFAULT_ALLOWED_URL_SCHEMES);
+  const scheme = detectRepositoryUrlScheme(trimmed);
+  if (!allowedSchemes.includes(scheme)) {
+    throw new UnsupportedUrlSchemeError(scheme);
   }
 
   const validatedOrg = validateRepositorySegment(org, 'organization');
@@ -87,8 +206,8 @@ export class GitUrl {
   public readonly repo: string;
   public readonly url: string;
 
-  constructor(url: string) {
-    const parsed = parseRepositoryUrl(url);
+  constructor(url: string, options: ParseRepositoryUrlOptions = {}) {
+    const parsed = parseRepositoryUrl(url, options);
     this.org = parsed.org;
     this.repo = parsed.repo;
     this.url = parsed.url;
@@ -1,17 +1,56 @@
-export { parseRepositoryUrl, GitUrl } from './git-url-parser.js';
-export type { GitUrlParts } from './git-url-parser.js';
+export {
+  parseRepositoryUrl,
+  detectRepositoryUrlScheme,
+  normalizeAllowedUrlSchemes,
+  GitUrl,
+  UnsupportedUrlSchemeError,
+  RepositoryUrlError,
+  DEFAULT_ALLOWED_URL_SCHEMES,
+} from './git-url-parser.js';
+export type { GitUrlParts, ParseRepositoryUrlOptions, RepositoryUrlErrorKind } from './git-url-parser.js';
 export {
   validateRepositorySegment,
+  validateRemoteName,
   RepositoryIdentifierError,
 } from './repository-identifiers.js';
 
+export {
+  validateRepositoryMetadata,
+  RepositoryMetadataError,
+  MAX_REPOSITORY_METADATA_ENTRIES,
+  MAX_REPOSITORY_METADATA_KEY_LENGTH,
+  MAX_REPOSITORY_METADATA_VALUE_LENGTH,
+  MAX_REPOSITORY_METADATA_BYTES,
+} from './repository-metadata.js';
+export type { RepositoryMetadata } from './repository-metadata.js';
+
+export {
+  validateSessionId,
+  isValidSessionId,
+  SessionIdError,
+  MAX_SESSION_ID_LENGTH,
+} from './session-id.js';
+
 export {
   normalizeBranchName,
   sanitizeBranchName,
   deriveWorktreeFolderName,
   validateBranchName,
+  findBranchNameProblems,
   BranchName,
 } from './branch-validator.js';
+export type { BranchNameProblem, BranchNameProblemCode } from './branch-validator.js';
+
+export {
+  parseCloneLayout,
+  renderCloneLayout,
+  CloneLayoutError,
+  DEFAULT_CLONE_LAYOUT,
+} from './clone-layout.js';
+export type { CloneLayout, CloneLayoutSegment } from './clone-layout.js';
+
+export { CLONE_FILTERS, isCloneFilter } from './clone-filter.js';
+export type { CloneFilter } from './clone-filter.js';
 
 export { Worktree, createWorktree } from './worktree.js';
 export type { WorktreeData } from './worktree.js';
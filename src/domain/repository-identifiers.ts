@@ -49,3 +49,34 @@ export function validateRepositorySegment(value: unknown, label: string): string
 
   return normalized;
 }
+
+const REMOTE_NAME_PATTERN = /^[A-Za-z0-9][A-Za-z0-9._-]*$/;
+const MAX_REMOTE_NAME_LENGTH = 100;
+
+/**
+ * Validates a git remote name. Names are limited to letters, digits, ".", "_" and "-" so they can never be
+ * mistaken for an option, a URL or a path.
+ * @param value - Raw remote name
+ * @returns Trimmed remote name
+ * @throws {RepositoryIdentifierError} When the name is empty or is not a plain remote name
+ */
+export function validateRemoteName(value: unknown): string {
+  const normalized = typeof value === 'string' ? value.trim() : '';
+
+  if (!normalized) {
+    throw new RepositoryIdentifierError('Remote name is required');
+  }
+
+  if (
+    normalized.length > MAX_REMOTE_NAME_LENGTH ||
+    !REMOTE_NAME_PATTERN.test(normalized) ||
+    normalized.includes('..') ||
+    normalized.endsWith('.lock')
+  ) {
+    throw new RepositoryIdentifierError(
+      `Remote name must be up to ${MAX_REMOTE_NAME_LENGTH} letters, digits, ".", "_" or "-", starting with a letter or digit`
+    );
+  }
+
+  return normalized;
+}
@@ -0,0 +1,68 @@
+/**
+ * Validation for the free-form labels users attach to repositories (e.g. team, priority).
+ */
+
+export const MAX_REPOSITORY_METADATA_ENTRIES = 32;
+export const MAX_REPOSITORY_METADATA_KEY_LENGTH = 64;
+export const MAX_REPOSITORY_METADATA_VALUE_LENGTH = 256;
+/**
+ * Upper bound on the serialised map, so the listing payload stays small
+ */
+export const MAX_REPOSITORY_METADATA_BYTES = 4096;
+
+export type RepositoryMetadata = Record<string, string>;
+
+/**
+ * Error thrown when repository metadata is not a flat string map within the size limits.
+ */
+export class RepositoryMetadataError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'invalid_metadata';
+
+  constructor(message: string) {
+    super(message);
+    this.name = 'RepositoryMetadataError';
+  }
+}
+
+/**
+ * Validates repository metadata: a plain object whose keys are non-empty and whose values are strings.
+ * Keys are trimmed.
+ * @param value - Candidate metadata
+ * @returns The validated metadata
+ * @throws {RepositoryMetadataError} When the shape or size is invalid
+ */
+export function validateRepositoryMetadata(value: unknown): RepositoryMetadata {
+  if (!value || typeof value !== 'object' || Array.isArray(value)) {
+    throw new RepositoryMetadataError('metadata must be an object of string values');
+  }
+
+  const entries = Object.entries(value as Record<string, unknown>);
+  if (entries.length > MAX_REPOSITORY_METADATA_ENTRIES) {
+    throw new RepositoryMetadataError(`metadata cannot have more than ${MAX_REPOSITORY_METADATA_ENTRIES} entries`);
+  }
+
+  const metadata: RepositoryMetadata = {};
+  for (const [rawKey, entryValue] of entries) {
+    const key = rawKey.trim();
+    if (!key || key.length > MAX_REPOSITORY_METADATA_KEY_LENGTH) {
+      throw new RepositoryMetadataError(
+        `metadata keys must be 1-${MAX_REPOSITORY_METADATA_KEY_LENGTH} characters`
+      );
+    }
+    if (typeof entryValue !== 'string') {
+      throw new RepositoryMetadataError(`metadata value for "${key}" must be a string`);
+    }
+    if (entryValue.length > MAX_REPOSITORY_METADATA_VALUE_LENGTH) {
+      throw new RepositoryMetadataError(
+        `metadata value for "${key}" cannot exceed ${MAX_REPOSITORY_METADATA_VALUE_LENGTH} characters`
+      );
+    }
+    metadata[key] = entryValue;
+  }
+
+  if (Buffer.byteLength(JSON.stringify(metadata)) > MAX_REPOSITORY_METADATA_BYTES) {
+    throw new RepositoryMetadataError(`metadata cannot exceed ${MAX_REPOSITORY_METADATA_BYTES} bytes`);
+  }
+  return metadata;
+}
@@ -0,0 +1,63 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+
+import {
+  MAX_SESSION_ID_LENGTH,
+  SessionIdError,
+  isValidSessionId,
+  validateSessionId,
+} from './session-id.js';
+
+describe('validateSessionId', () => {
+  it('accepts UUIDs and slug ids', () => {
+    for (const id of ['3f2b8c1e-9d4a-4b6f-8e2a-1c5d7e9f0a3b', 'session_01', 'A', 'x'.repeat(MAX_SESSION_ID_LENGTH)]) {
+      assert.equal(validateSessionId(id), id);
+      assert.equal(isValidSessionId(id), true);
+    }
+  });
+
+  it('trims surrounding whitespace', () => {
+    assert.equal(validateSessionId('  abc-123 \n'), 'abc-123');
+  });
+
+  it('rejects ids that are unsafe as file names', () => {
+    for (const id of [
+      '',
+      '   ',
+      '.',
+      '..',
+      '../etc/passwd',
+      'a/b',
+      'a\\b',
+      '.hidden',
+      '-leading-dash',
+      '_leading-underscore',
+      'with space',
+      'nul\0byte',
+      'semi;colon',
+      'é',
+      'x'.repeat(MAX_SESSION_ID_LENGTH + 1),
+    ]) {
+      assert.throws(
+        () => validateSessionId(id),
+        (error: unknown) => {
+          assert.ok(error instanceof SessionIdError, `expected ${JSON.stringify(id)} to be rejected`);
+          assert.equal(error.statusCode, 400);
+          assert.equal(error.code, 'invalid_session_id');
+          return true;
+        }
+      );
+    }
+  });
+
+  it('rejects non-string values', () => {
+    for (const value of [undefined, null, 42, {}]) {
+      assert.throws(() => validateSessionId(value), SessionIdError);
+      assert.equal(isValidSessionId(value), false);
+    }
+  });
+
+  it('uses the field label in error messages', () => {
+    assert.throws(() => validateSessionId('', 'id'), /id is required/);
+  });
+});
@@ -0,0 +1,66 @@
+/**
+ * Session identifier validation.
+ *
+ * Session ids name transcript files on disk and are echoed back to clients, so they are limited to a
+ * slug alphabet that can never form a path separator, traversal segment or hidden file.
+ */
+
+const SESSION_ID_PATTERN = /^[A-Za-z0-9][A-Za-z0-9_-]*$/;
+
+/**
+ * Longest accepted session id; generous enough for UUIDs and prefixed ids
+ */
+export const MAX_SESSION_ID_LENGTH = 128;
+
+/**
+ * Error thrown when a session id is missing or unsafe.
+ */
+export class SessionIdError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'invalid_session_id';
+
+  constructor(message: string) {
+    super(message);
+    this.name = 'SessionIdError';
+  }
+}
+
+/**
+ * Checks whether a value is a safe session id without throwing
+ * @param value - Candidate session id
+ * @returns True when the value is a valid session id
+ */
+export function isValidSessionId(value: unknown): value is string {
+  return (
+    typeof value === 'string' &&
+    value.length <= MAX_SESSION_ID_LENGTH &&
+    SESSION_ID_PATTERN.test(value)
+  );
+}
+
+/**
+ * Normalizes and validates a session id
+ * @param value - Raw session id
+ * @param label - Field name used in error messages
+ * @returns Trimmed session id
+ * @throws {SessionIdError} When the id is empty, too long or contains characters outside `[A-Za-z0-9_-]`
+ */
+export function validateSessionId(value: unknown, label: string = 'sessionId'): string {
+  const normalized = typeof value === 'string' ? value.trim() : '';
+
+  if (!normalized) {
+    throw new SessionIdError(`${label} is required`);
+  }
+
+  if (normalized.length > MAX_SESSION_ID_LENGTH) {
+    throw new SessionIdError(`${label} must be at most ${MAX_SESSION_ID_LENGTH} characters`);
+  }
+
+  if (!SESSION_ID_PATTERN.test(normalized)) {
+    throw new SessionIdError(
+      `${label} may only contain letters, digits, dashes and underscores, and must start with a letter or digit`
+    );
+  }
+
+  return normalized;
+}
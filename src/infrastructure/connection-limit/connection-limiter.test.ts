@@ -0,0 +1,83 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import { once } from 'node:events';
+import { afterEach, describe, it } from 'node:test';
+
+import { createConnectionLimiter, DEFAULT_MAX_CONNECTIONS } from './connection-limiter.js';
+
+function request(port: number, path: string): Promise<{ status: number; headers: http.IncomingHttpHeaders; body: string }> {
+  return new Promise((resolve, reject) => {
+    http
+      .get({ host: '127.0.0.1', port, path, agent: false }, (res) => {
+        let body = '';
+        res.setEncoding('utf8');
+        res.on('data', (chunk: string) => {
+          body += chunk;
+        });
+        res.on('end', () => resolve({ status: res.statusCode ?? 0, headers: res.headers, body }));
+      })
+      .on('error', reject);
+  });
+}
+
+describe('createConnectionLimiter', () => {
+  let server: http.Server | null = null;
+
+  afterEach(async () => {
+    const current = server;
+    server = null;
+    if (current) {
+      current.closeAllConnections();
+      await new Promise<void>((resolve) => current.close(() => resolve()));
+    }
+  });
+
+  it('turns away requests beyond the limit with 503 but still answers health checks', async () => {
+    const limiter = createConnectionLimiter({ maxConnections: 2 });
+    const held: http.ServerResponse[] = [];
+    server = http.createServer((req, res) => {
+      if (!limiter.admit(req, res)) {
+        return;
+      }
+      if (req.url === '/api/events') {
+        held.push(res);
+        res.writeHead(200, { 'Content-Type': 'text/event-stream' });
+        res.write(': open\n\n');
+        return;
+      }
+      res.end('ok');
+    });
+    server.listen(0, '127.0.0.1');
+    await once(server, 'listening');
+    const { port } = server.address() as AddressInfo;
+
+    const streams = [request(port, '/api/events'), request(port, '/api/events')];
+    while (held.length < 2) {
+      await new Promise((resolve) => setImmediate(resolve));
+    }
+    assert.equal(limiter.active, 2);
+
+    const overflow = await request(port, '/api/repos');
+    assert.equal(overflow.status, 503);
+    assert.equal(overflow.headers['retry-after'], '1');
+    assert.deepEqual(JSON.parse(overflow.body), { error: 'Server is at its connection limit', code: 'server_busy' });
+
+    const health = await request(port, '/api/health');
+    assert.equal(health.status, 200);
+    assert.equal(limiter.active, 2);
+
+    held.forEach((res) => res.end());
+    await Promise.all(streams);
+    while (limiter.active > 0) {
+      await new Promise((resolve) => setImmediate(resolve));
+    }
+    assert.equal((await request(port, '/api/repos')).status, 200);
+  });
+
+  it('defaults to a generous limit and rejects non-positive ones', () => {
+    assert.equal(createConnectionLimiter().maxConnections, DEFAULT_MAX_CONNECTIONS);
+    assert.equal(createConnectionLimiter({ maxConnections: null }).maxConnections, DEFAULT_MAX_CONNECTIONS);
+    assert.throws(() => createConnectionLimiter({ maxConnections: 0 }), /positive/);
+  });
+});
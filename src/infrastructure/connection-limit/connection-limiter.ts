@@ -0,0 +1,75 @@
+import type { IncomingMessage, ServerResponse } from 'node:http';
+import { sendJson } from '../../utils/http.js';
+
+/**
+ * Requests served at once when no limit is configured; generous enough that only a runaway client
+ * reaches it
+ */
+export const DEFAULT_MAX_CONNECTIONS = 1024;
+
+/**
+ * Probes that are always answered, so a saturated server still reports itself alive
+ */
+export const HEALTH_CHECK_PATHS: ReadonlySet<string> = new Set(['/api/health', '/api/health/detail']);
+
+const RETRY_AFTER_SECONDS = 1;
+
+export interface ConnectionLimiterOptions {
+  /**
+   * Requests in flight allowed at once, open event streams included (defaults to `DEFAULT_MAX_CONNECTIONS`)
+   */
+  maxConnections?: number | null;
+}
+
+export interface ConnectionLimiter {
+  /**
+   * Counts a request against the limit until its response closes, or answers it with 503 when the
+   * limit is reached
+   * @returns false when the request was turned away and must not be handled further
+   */
+  admit(req: IncomingMessage, res: ServerResponse): boolean;
+  readonly active: number;
+  readonly maxConnections: number;
+}
+
+/**
+ * Creates a limiter on the number of HTTP requests served at once. Long-lived requests such as event
+ * streams hold their slot until the client disconnects, which is what stops a misbehaving poller from
+ * exhausting the server. Health checks bypass the limit and are not counted.
+ * @param options - Limiter configuration
+ * @returns Connection limiter instance
+ */
+export function createConnectionLimiter({ maxConnections }: ConnectionLimiterOptions = {}): ConnectionLimiter {
+  if (maxConnections !== undefined && maxConnections !== null && (!Number.isInteger(maxConnections) || maxConnections <= 0)) {
+    throw new Error('maxConnections must be a positive integer');
+  }
+  const limit = maxConnections ?? DEFAULT_MAX_CONNECTIONS;
+  let active = 0;
+
+  function admit(req: IncomingMessage, res: ServerResponse): boolean {
+    const { pathname } = new URL(req.url || '/', 'http://localhost');
+    if (HEALTH_CHECK_PATHS.has(pathname)) {
+      return true;
+    }
+
+    if (active >= limit) {
+      res.setHeader('Retry-After', String(RETRY_AFTER_SECONDS));
+      sendJson(res, 503, { error: 'Server is at its connection limit', code: 'server_busy' });
+      return false;
+    }
+
+    active += 1;
+    res.once('close', () => {
+      active -= 1;
+    });
+    return true;
+  }
+
+  return {
+    admit,
+    get active() {
+      return active;
+    },
+    maxConnections: limit,
+  };
+}
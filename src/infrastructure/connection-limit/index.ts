@@ -0,0 +1,2 @@
+export { createConnectionLimiter, DEFAULT_MAX_CONNECTIONS, HEALTH_CHECK_PATHS } from './connection-limiter.js';
+export type { ConnectionLimiter, ConnectionLimiterOptions } from './connection-limiter.js';
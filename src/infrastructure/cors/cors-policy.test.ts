@@ -0,0 +1,94 @@
+import assert from 'node:assert/strict';
+import { describe, it, mock } from 'node:test';
+
+import { createCorsPolicy } from './cors-policy.js';
+
+function createExchange(method: string, headers: Record<string, string>) {
+  const responseHeaders = new Map<string, string>();
+  const res = {
+    statusCode: 0,
+    setHeader: (name: string, value: string) => {
+      responseHeaders.set(name.toLowerCase(), value);
+    },
+    end: mock.fn(),
+  };
+  return { req: { method, headers } as never, res, responseHeaders };
+}
+
+const preflightHeaders = {
+  origin: 'https://app.example.com',
+  'access-control-request-method': 'POST',
+  'access-control-request-headers': 'content-type, idempotency-key',
+};
+
+describe('createCorsPolicy', () => {
+  it('answers a preflight with the credentials and max age headers', () => {
+    const policy = createCorsPolicy({
+      origins: ['https://app.example.com'],
+      allowCredentials: true,
+      maxAgeSeconds: 600,
+    });
+    const { req, res, responseHeaders } = createExchange('OPTIONS', preflightHeaders);
+
+    assert.equal(policy.handle(req, res as never), true);
+
+    assert.equal(res.statusCode, 204);
+    assert.equal(res.end.mock.callCount(), 1);
+    assert.deepEqual(Object.fromEntries(responseHeaders), {
+      vary: 'Origin',
+      'access-control-allow-origin': 'https://app.example.com',
+      'access-control-allow-credentials': 'true',
+      'access-control-allow-methods': 'GET, HEAD, POST, PUT, PATCH, DELETE',
+      'access-control-allow-headers': 'content-type, idempotency-key',
+      'access-control-max-age': '600',
+    });
+  });
+
+  it('leaves out the credentials and max age headers unless configured', () => {
+    const policy = createCorsPolicy({ origins: ['*'] });
+    const { req, res, responseHeaders } = createExchange('OPTIONS', preflightHeaders);
+
+    assert.equal(policy.handle(req, res as never), true);
+
+    assert.equal(responseHeaders.get('access-control-allow-origin'), '*');
+    assert.equal(responseHeaders.has('access-control-allow-credentials'), false);
+    assert.equal(responseHeaders.has('access-control-max-age'), false);
+    assert.equal(responseHeaders.has('vary'), false);
+  });
+
+  it('refuses a preflight from an origin that is not listed', () => {
+    const policy = createCorsPolicy({ origins: ['https://app.example.com'], allowCredentials: true });
+    const { req, res, responseHeaders } = createExchange('OPTIONS', {
+      ...preflightHeaders,
+      origin: 'https://evil.example.com',
+    });
+
+    assert.equal(policy.handle(req, res as never), true);
+
+    assert.equal(res.statusCode, 403);
+    assert.equal(responseHeaders.has('access-control-allow-origin'), false);
+    assert.equal(responseHeaders.has('access-control-allow-credentials'), false);
+  });
+
+  it('adds the allow headers to actual requests and lets them through', () => {
+    const policy = createCorsPolicy({ origins: ['https://app.example.com'], allowCredentials: true, maxAgeSeconds: 600 });
+    const { req, res, responseHeaders } = createExchange('GET', { origin: 'https://app.example.com' });
+
+    assert.equal(policy.handle(req, res as never), false);
+
+    assert.equal(res.statusCode, 0);
+    assert.equal(res.end.mock.callCount(), 0);
+    assert.equal(responseHeaders.get('access-control-allow-origin'), 'https://app.example.com');
+    assert.equal(responseHeaders.get('access-control-allow-credentials'), 'true');
+    assert.equal(responseHeaders.get('access-control-expose-headers'), 'X-Request-Id, Retry-After');
+    assert.equal(responseHeaders.has('access-control-max-age'), false);
+  });
+
+  it('rejects credentials combined with the wildcard origin', () => {
+    assert.throws(
+      () => createCorsPolicy({ origins: ['*'], allowCredentials: true }),
+      /credentials cannot be allowed for the \* origin/
+    );
+    assert.throws(() => createCorsPolicy({ origins: ['https://app.example.com/'] }), /Invalid CORS origin/);
+  });
+});
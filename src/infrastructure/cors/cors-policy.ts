@@ -0,0 +1,106 @@
+import type { IncomingMessage, ServerResponse } from 'node:http';
+import { REQUEST_ID_HEADER } from '../../utils/request-id.js';
+
+const ALLOWED_METHODS = 'GET, HEAD, POST, PUT, PATCH, DELETE';
+const EXPOSED_HEADERS = [REQUEST_ID_HEADER, 'Retry-After'].join(', ');
+
+export interface CorsPolicyOptions {
+  /**
+   * Origins such as `https://app.example.com` allowed to call the API, or `*` for any origin
+   */
+  origins: string[];
+  /**
+   * Lets browsers send the session cookie, answered with `Access-Control-Allow-Credentials`
+   */
+  allowCredentials?: boolean;
+  /**
+   * Seconds a browser may cache a preflight answer (`Access-Control-Max-Age`); the browser default when unset
+   */
+  maxAgeSeconds?: number | null;
+}
+
+export interface CorsPolicy {
+  /**
+   * Adds the CORS headers for a cross-origin request and answers it when it is a preflight
+   * @returns True when the request was a preflight and has been answered
+   */
+  handle(req: IncomingMessage, res: ServerResponse): boolean;
+}
+
+/**
+ * Checks that a configured origin is `*` or a bare http(s) origin, with no path, query or trailing slash
+ */
+export function isCorsOrigin(value: string): boolean {
+  if (value === '*') {
+    return true;
+  }
+  try {
+    const url = new URL(value);
+    return (url.protocol === 'http:' || url.protocol === 'https:') && url.origin === value;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Creates the CORS policy applied to `/api/` requests
+ * @param options - Allowed origins, credentials toggle and preflight cache lifetime
+ * @throws {Error} If an origin is malformed, or credentials are combined with the `*` origin, which
+ * browsers refuse
+ */
+export function createCorsPolicy({ origins, allowCredentials = false, maxAgeSeconds = null }: CorsPolicyOptions): CorsPolicy {
+  const invalid = origins.find((origin) => !isCorsOrigin(origin));
+  if (invalid !== undefined) {
+    throw new Error(`Invalid CORS origin ${invalid}: expected * or an origin such as https://app.example.com`);
+  }
+  const anyOrigin = origins.includes('*');
+  if (anyOrigin && allowCredentials) {
+    throw new Error('CORS credentials cannot be allowed for the * origin; list the allowed origins instead');
+  }
+  if (maxAgeSeconds !== null && (!Number.isInteger(maxAgeSeconds) || maxAgeSeconds < 0)) {
+    throw new Error('CORS max age must be a non-negative integer');
+  }
+  const allowed = new Set(origins);
+
+  function handle(req: IncomingMessage, res: ServerResponse): boolean {
+    const origin = req.headers.origin;
+    if (!origin) {
+      return false;
+    }
+    const isPreflight =
+      req.method?.toUpperCase() === 'OPTIONS' && req.headers['access-control-request-method'] !== undefined;
+    if (!anyOrigin) {
+      res.setHeader('Vary', 'Origin');
+    }
+    if (!anyOrigin && !allowed.has(origin)) {
+      if (isPreflight) {
+        res.statusCode = 403;
+        res.end();
+      }
+      return isPreflight;
+    }
+
+    res.setHeader('Access-Control-Allow-Origin', anyOrigin ? '*' : origin);
+    if (allowCredentials) {
+      res.setHeader('Access-Control-Allow-Credentials', 'true');
+    }
+    if (!isPreflight) {
+      res.setHeader('Access-Control-Expose-Headers', EXPOSED_HEADERS);
+      return false;
+    }
+
+    res.setHeader('Access-Control-Allow-Methods', ALLOWED_METHODS);
+    const requestedHeaders = req.headers['access-control-request-headers'];
+    if (requestedHeaders) {
+      res.setHeader('Access-Control-Allow-Headers', requestedHeaders);
+    }
+    if (maxAgeSeconds !== null) {
+      res.setHeader('Access-Control-Max-Age', String(maxAgeSeconds));
+    }
+    res.statusCode = 204;
+    res.end();
+    return true;
+  }
+
+  return { handle };
+}
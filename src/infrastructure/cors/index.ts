@@ -0,0 +1,2 @@
+export { createCorsPolicy, isCorsOrigin } from './cors-policy.js';
+export type { CorsPolicy, CorsPolicyOptions } from './cors-policy.js';
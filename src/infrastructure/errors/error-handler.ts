@@ -1,6 +1,7 @@
 import type { ServerResponse } from 'node:http';
 import { HttpError } from './http-error.js';
 import { sendJson } from '../../utils/http.js';
+import { getRequestId } from '../../utils/request-id.js';
 import type { RequestContext } from '../../types/http.js';
 
 /**
@@ -58,30 +59,89 @@ export function extractErrorMessage(error: unknown, defaultMessage: string = 'An
   return defaultMessage;
 }
 
+export interface ErrorBody {
+  error: string;
+  code?: string;
+  details?: Record<string, unknown>;
+  requestId?: string;
+}
+
 /**
- * Centralized error handler that maps errors to HTTP responses
+ * Tags an error body with the request's correlation id and logs server errors under the same id so
+ * client reports can be matched to log lines
  */
-export function handleError(
+function finaliseErrorBody(res: ServerResponse, statusCode: number, body: ErrorBody, error: unknown): ErrorBody {
+  const requestId = getRequestId(res);
+  if (requestId) {
+    body.requestId = requestId;
+  }
+  if (statusCode >= 500) {
+    console.error(`[agentrix] Request ${requestId ?? 'unknown'} failed with ${statusCode}:`, error);
+  }
+  return body;
+}
+
+/**
+ * Builds the JSON error body, surfacing a machine-readable `code` and structured `details`
+ * when the error carries them
+ */
+function buildErrorBody(
+  message: string,
+  error: unknown
+): ErrorBody {
+  const body: ErrorBody = { error: message };
+  if (typeof error !== 'object' || error === null) {
+    return body;
+  }
+  const { code, details } = error as { code?: unknown; details?: unknown };
+  if (typeof code === 'string' && code) {
+    body.code = code;
+  }
+  if (details && typeof details === 'object' && !Array.isArray(details)) {
+    body.details = details as Record<string, unknown>;
+  }
+  return body;
+}
+
+/**
+ * Maps an error to the status code and body it is answered with, for responses that report errors
+ * without sending them as JSON (such as event streams)
+ */
+export function describeError(
   res: ServerResponse,
   error: unknown,
   defaultStatusCode: number = 500
-): void {
+): { statusCode: number; body: ErrorBody } {
   // If it's already an HttpError, use its status code
   if (isHttpError(error)) {
-    sendJson(res, error.statusCode, { error: error.message });
-    return;
+    const body = buildErrorBody(error.message, error);
+    return { statusCode: error.statusCode, body: finaliseErrorBody(res, error.statusCode, body, error) };
   }
 
   // Handle errors with explicit statusCode property
   if (hasStatusCode(error)) {
-    const message = error.message || 'An error occurred';
-    sendJson(res, error.statusCode, { error: message });
-    return;
+    const body = buildErrorBody(error.message || 'An error occurred', error);
+    return { statusCode: error.statusCode, body: finaliseErrorBody(res, error.statusCode, body, error) };
   }
 
   // Default error response
   const message = isError(error) ? error.message : 'An unexpected error occurred';
-  sendJson(res, defaultStatusCode, { error: message });
+  return {
+    statusCode: defaultStatusCode,
+    body: finaliseErrorBody(res, defaultStatusCode, { error: message }, error),
+  };
+}
+
+/**
+ * Centralized error handler that maps errors to HTTP responses
+ */
+export function handleError(
+  res: ServerResponse,
+  error: unknown,
+  defaultStatusCode: number = 500
+): void {
+  const { statusCode, body } = describeError(res, error, defaultStatusCode);
+  sendJson(res, statusCode, body);
 }
 
 /**
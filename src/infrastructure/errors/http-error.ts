@@ -3,21 +3,31 @@
  */
 export class HttpError extends Error {
   public readonly statusCode: number;
+  public readonly code?: string;
 
-  constructor(message: string, statusCode: number = 500, cause: Error | null = null) {
+  constructor(
+    message: string,
+    statusCode: number = 500,
+    cause: Error | null = null,
+    code?: string
+  ) {
     super(message);
     this.name = this.constructor.name;
     this.statusCode = statusCode;
     if (cause) {
       this.cause = cause;
     }
+    if (code) {
+      this.code = code;
+    }
     Error.captureStackTrace(this, this.constructor);
   }
 
-  toJSON(): { error: string; status: number } {
+  toJSON(): { error: string; status: number; code?: string } {
     return {
       error: this.message,
       status: this.statusCode,
+      ...(this.code ? { code: this.code } : {}),
     };
   }
 }
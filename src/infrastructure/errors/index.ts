@@ -1,4 +1,5 @@
 export { HttpError, InternalServerError, ServiceUnavailableError, BadGatewayError } from './http-error.js';
-export { ValidationError, UnauthorizedError, MethodNotAllowedError } from './validation-error.js';
+export { ValidationError, InvalidBodyError, UnauthorizedError, MethodNotAllowedError } from './validation-error.js';
 export { NotFoundError } from './not-found-error.js';
-export { handleError, asyncHandler, errorMiddleware, extractErrorMessage } from './error-handler.js';
+export { handleError, describeError, asyncHandler, errorMiddleware, extractErrorMessage } from './error-handler.js';
+export type { ErrorBody } from './error-handler.js';
@@ -9,6 +9,15 @@ export class ValidationError extends HttpError {
   }
 }
 
+/**
+ * 400 Bad Request - Request body could not be parsed
+ */
+export class InvalidBodyError extends HttpError {
+  constructor(message: string, cause: Error | null = null) {
+    super(message, 400, cause, 'invalid_body');
+  }
+}
+
 /**
  * 401 Unauthorized
  */
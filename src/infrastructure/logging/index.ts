@@ -1,3 +1,11 @@
 export { createLogger, createConsoleLogger } from './logger.js';
 export type { Logger } from './logger.js';
-
+export {
+  LOG_LEVELS,
+  LOG_LEVEL_ENV,
+  initLogging,
+  isLogLevelEnabled,
+  resolveLogLevel,
+  __resetLoggingStateForTests,
+} from './level.js';
+export type { LogLevel } from './level.js';
@@ -0,0 +1,49 @@
+import assert from 'node:assert/strict';
+import { afterEach, describe, it, mock } from 'node:test';
+
+import { parseArgs } from '../../cli/arg-parser.js';
+import { __resetLoggingStateForTests, initLogging, LOG_LEVEL_ENV, resolveLogLevel } from './level.js';
+
+describe('log level', () => {
+  afterEach(() => {
+    __resetLoggingStateForTests();
+    mock.restoreAll();
+  });
+
+  it('maps -vv to a debug-level filter', () => {
+    assert.equal(resolveLogLevel(parseArgs(['-vv']), {}), 'debug');
+  });
+
+  it('maps the remaining verbosity flags and leaves output unfiltered without them', () => {
+    assert.equal(resolveLogLevel(parseArgs([]), {}), null);
+    assert.equal(resolveLogLevel(parseArgs(['-q']), {}), 'error');
+    assert.equal(resolveLogLevel(parseArgs(['-v']), {}), 'info');
+    assert.equal(resolveLogLevel(parseArgs(['-vvvvv']), {}), 'trace');
+  });
+
+  it('lets an explicit environment level win over the flags', () => {
+    assert.equal(resolveLogLevel(parseArgs(['-vv']), { [LOG_LEVEL_ENV]: 'WARN' }), 'warn');
+    assert.equal(resolveLogLevel(parseArgs(['-q']), { [LOG_LEVEL_ENV]: 'trace' }), 'trace');
+    assert.equal(resolveLogLevel(parseArgs(['-vv']), { [LOG_LEVEL_ENV]: 'loud' }), 'debug');
+  });
+
+  it('silences console methods above the level and only applies the first level', () => {
+    const debug = mock.method(console, 'debug', () => {});
+    const info = mock.method(console, 'info', () => {});
+    const warn = mock.method(console, 'warn', () => {});
+
+    assert.equal(initLogging('info'), true);
+    assert.equal(initLogging('error'), false);
+    console.debug('hidden');
+    console.info('shown');
+    console.warn('shown');
+
+    assert.equal(debug.mock.calls.length, 0);
+    assert.equal(info.mock.calls.length, 1);
+    assert.equal(warn.mock.calls.length, 1);
+
+    __resetLoggingStateForTests();
+    console.debug('restored');
+    assert.equal(debug.mock.calls.length, 1);
+  });
+});
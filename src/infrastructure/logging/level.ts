@@ -0,0 +1,101 @@
+/**
+ * Log levels from least to most verbose
+ */
+export const LOG_LEVELS = ['error', 'warn', 'info', 'debug', 'trace'] as const;
+
+export type LogLevel = (typeof LOG_LEVELS)[number];
+
+/**
+ * Environment variable naming the log level explicitly; it wins over `--quiet` and `--verbose`
+ */
+export const LOG_LEVEL_ENV = 'AGENTRIX_LOG';
+
+type ConsoleMethod = 'error' | 'warn' | 'info' | 'log' | 'debug' | 'trace';
+
+/**
+ * Level each console method writes at
+ */
+const CONSOLE_METHOD_LEVELS: Record<ConsoleMethod, LogLevel> = {
+  error: 'error',
+  warn: 'warn',
+  info: 'info',
+  log: 'info',
+  debug: 'debug',
+  trace: 'trace',
+};
+
+let originalConsoleMethods: Partial<Record<ConsoleMethod, (...args: unknown[]) => void>> | null = null;
+
+function parseLogLevel(value: string | undefined): LogLevel | null {
+  const normalized = value?.trim().toLowerCase();
+  return (LOG_LEVELS as readonly string[]).includes(normalized ?? '') ? (normalized as LogLevel) : null;
+}
+
+/**
+ * Resolves the log level from the verbosity flags. An explicit `AGENTRIX_LOG` wins; otherwise `--quiet`
+ * maps to `error` and each `--verbose` raises the level one step from `warn` (`-v` info, `-vv` debug,
+ * `-vvv` trace).
+ * @param flags - Parsed `--quiet` and `--verbose` count
+ * @param env - Environment to read `AGENTRIX_LOG` from
+ * @returns The level, or null to leave console output unfiltered
+ */
+export function resolveLogLevel(
+  { quiet = false, verbose = 0 }: { quiet?: boolean; verbose?: number },
+  env: NodeJS.ProcessEnv = process.env
+): LogLevel | null {
+  const explicit = parseLogLevel(env[LOG_LEVEL_ENV]);
+  if (explicit) {
+    return explicit;
+  }
+  if (quiet) {
+    return 'error';
+  }
+  if (verbose > 0) {
+    return LOG_LEVELS[Math.min(1 + verbose, LOG_LEVELS.length - 1)] ?? 'trace';
+  }
+  return null;
+}
+
+/**
+ * Checks whether messages at a level are written under a threshold
+ */
+export function isLogLevelEnabled(level: LogLevel, threshold: LogLevel): boolean {
+  return LOG_LEVELS.indexOf(level) <= LOG_LEVELS.indexOf(threshold);
+}
+
+/**
+ * Applies the log level process-wide by silencing the console methods above it, so every module's
+ * output is filtered whether it logs through a Logger or the console directly. Only the first call
+ * takes effect; the level must be resolved before anything else initialises logging.
+ * @param level - Threshold to apply; null leaves output unfiltered
+ * @returns Whether this call applied the level
+ */
+export function initLogging(level: LogLevel | null): boolean {
+  if (originalConsoleMethods) {
+    return false;
+  }
+  originalConsoleMethods = {};
+  if (!level) {
+    return true;
+  }
+  for (const [method, methodLevel] of Object.entries(CONSOLE_METHOD_LEVELS) as [ConsoleMethod, LogLevel][]) {
+    if (!isLogLevelEnabled(methodLevel, level)) {
+      originalConsoleMethods[method] = console[method];
+      console[method] = () => {};
+    }
+  }
+  return true;
+}
+
+/**
+ * @internal Utility for tests to restore the console and allow initLogging to run again
+ */
+export function __resetLoggingStateForTests(): void {
+  for (const [method, original] of Object.entries(originalConsoleMethods ?? {}) as [
+    ConsoleMethod,
+    (...args: unknown[]) => void,
+  ][]) {
+    console[method] = original;
+  }
+  originalConsoleMethods = null;
+}
@@ -0,0 +1,109 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, describe, it, mock } from 'node:test';
+
+import {
+  __setCloneBackendTestOverrides,
+  cloneWithLibgit2,
+  CloneBackendUnavailableError,
+  loadLibgit2,
+} from './clone-backend.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) =>
+  (await execFileAsync('git', ['-C', cwd, '-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', ...args])).stdout.trim();
+
+const libgit2Installed = await loadLibgit2().then(
+  () => true,
+  () => false
+);
+
+function stubTransfer(receivedObjects: number, totalObjects: number) {
+  return {
+    receivedObjects: () => receivedObjects,
+    totalObjects: () => totalObjects,
+    receivedBytes: () => receivedObjects * 100,
+  };
+}
+
+describe('clone backend', () => {
+  afterEach(() => {
+    __setCloneBackendTestOverrides();
+  });
+
+  it('reports a coded error when the bindings are not installed, and retries the load later', async () => {
+    const importModule = mock.fn(async () => {
+      throw new Error("Cannot find package 'nodegit'");
+    });
+    __setCloneBackendTestOverrides({ importModule });
+
+    for (let attempt = 0; attempt < 2; attempt += 1) {
+      await assert.rejects(loadLibgit2(), (error: unknown) => {
+        assert.ok(error instanceof CloneBackendUnavailableError);
+        assert.equal(error.statusCode, 503);
+        assert.equal(error.code, 'clone_backend_unavailable');
+        assert.match(error.message, /Cannot find package 'nodegit'/);
+        return true;
+      });
+    }
+    assert.equal(importModule.mock.calls.length, 2);
+  });
+
+  it('forwards transfer progress and stops the transfer once aborted', async () => {
+    const results: number[] = [];
+    const clone = mock.fn(async (_url: string, _path: string, options?: Record<string, unknown>) => {
+      const { callback } = (options as {
+        fetchOpts: { callbacks: { transferProgress: { callback: (stats: unknown) => number } } };
+      }).fetchOpts.callbacks.transferProgress;
+      results.push(callback(stubTransfer(1, 4)));
+      controller.abort();
+      results.push(callback(stubTransfer(2, 4)));
+    });
+    __setCloneBackendTestOverrides({ importModule: async () => ({ default: { Clone: { clone } } }) });
+    const controller = new AbortController();
+    const progress: unknown[] = [];
+
+    await cloneWithLibgit2({
+      url: 'https://github.com/acme/demo.git',
+      repositoryPath: '/work/acme/demo/repository',
+      signal: controller.signal,
+      onProgress: (update) => progress.push(update),
+    });
+
+    assert.deepEqual(clone.mock.calls[0]?.arguments.slice(0, 2), [
+      'https://github.com/acme/demo.git',
+      '/work/acme/demo/repository',
+    ]);
+    assert.deepEqual(progress, [
+      { receivedObjects: 1, totalObjects: 4, receivedBytes: 100 },
+      { receivedObjects: 2, totalObjects: 4, receivedBytes: 200 },
+    ]);
+    assert.deepEqual(results, [0, -1]);
+  });
+
+  it('clones a local bare repository in-process', { skip: !libgit2Installed && 'nodegit is not installed' }, async () => {
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-libgit2-'));
+    try {
+      const sourcePath = path.join(root, 'source');
+      const barePath = path.join(root, 'demo.git');
+      const repositoryPath = path.join(root, 'workdir', 'acme', 'demo', 'repository');
+      await fs.mkdir(sourcePath, { recursive: true });
+      await git(sourcePath, ['init', '--quiet', '-b', 'main']);
+      await fs.writeFile(path.join(sourcePath, 'README.md'), 'demo\n');
+      await git(sourcePath, ['add', '-A']);
+      await git(sourcePath, ['commit', '--quiet', '-m', 'Seed']);
+      await execFileAsync('git', ['clone', '--quiet', '--bare', sourcePath, barePath]);
+
+      await cloneWithLibgit2({ url: barePath, repositoryPath });
+
+      assert.equal(await fs.readFile(path.join(repositoryPath, 'README.md'), 'utf8'), 'demo\n');
+      assert.equal(await git(repositoryPath, ['rev-parse', 'HEAD']), await git(barePath, ['rev-parse', 'main']));
+    } finally {
+      await fs.rm(root, { recursive: true, force: true });
+    }
+  });
+});
@@ -0,0 +1,151 @@
+/**
+ * How repositories are cloned: `cli` runs the system git, `libgit2` clones in-process
+ */
+export type CloneBackendName = 'cli' | 'libgit2';
+
+export const CLONE_BACKENDS: readonly CloneBackendName[] = ['cli', 'libgit2'];
+
+/**
+ * Package providing the libgit2 bindings; optional, so it is only loaded when the backend is selected
+ */
+export const LIBGIT2_MODULE = 'nodegit';
+
+/**
+ * How often libgit2 reports transfer progress while a clone downloads
+ */
+const PROGRESS_THROTTLE_MS = 250;
+
+export interface CloneProgress {
+  receivedObjects: number;
+  totalObjects: number;
+  receivedBytes: number;
+}
+
+export interface InProcessCloneRequest {
+  url: string;
+  repositoryPath: string;
+  /**
+   * Aborting it stops the transfer at the next progress report
+   */
+  signal?: AbortSignal;
+  onProgress?: (progress: CloneProgress) => void;
+}
+
+interface Libgit2TransferProgress {
+  receivedObjects(): number;
+  totalObjects(): number;
+  receivedBytes(): number;
+}
+
+interface Libgit2Credentials {
+  sshKeyFromAgent(userName: string): unknown;
+}
+
+/**
+ * The part of the bindings' API the clone backend relies on
+ */
+export interface Libgit2Module {
+  Clone: {
+    clone(url: string, localPath: string, options?: Record<string, unknown>): Promise<unknown>;
+  };
+  Credential?: Libgit2Credentials;
+  Cred?: Libgit2Credentials;
+}
+
+/**
+ * Error raised when the libgit2 backend is selected but its bindings cannot be loaded
+ */
+export class CloneBackendUnavailableError extends Error {
+  public readonly statusCode: number = 503;
+  public readonly code = 'clone_backend_unavailable';
+
+  constructor(reason: string) {
+    super(`The libgit2 clone backend is unavailable (${reason}); install ${LIBGIT2_MODULE} or use --git-backend cli`);
+    this.name = 'CloneBackendUnavailableError';
+  }
+}
+
+interface Dependencies {
+  importModule: (specifier: string) => Promise<unknown>;
+}
+
+const defaultDependencies: Dependencies = {
+  importModule: (specifier) => import(specifier),
+};
+
+let activeDependencies: Dependencies = defaultDependencies;
+let libgit2Promise: Promise<Libgit2Module> | null = null;
+
+/**
+ * @internal Utility for tests to replace the module loader and forget a loaded module
+ */
+export function __setCloneBackendTestOverrides(overrides?: Partial<Dependencies>): void {
+  libgit2Promise = null;
+  activeDependencies = { ...defaultDependencies, ...overrides };
+}
+
+/**
+ * Checks that a value names a clone backend
+ */
+export function isCloneBackendName(value: unknown): value is CloneBackendName {
+  return typeof value === 'string' && (CLONE_BACKENDS as readonly string[]).includes(value);
+}
+
+/**
+ * Loads the libgit2 bindings once; a failed load is retried on the next call
+ * @returns The bindings
+ * @throws {CloneBackendUnavailableError} If the package is not installed or fails to load
+ */
+export async function loadLibgit2(): Promise<Libgit2Module> {
+  if (!libgit2Promise) {
+    libgit2Promise = activeDependencies.importModule(LIBGIT2_MODULE).then(
+      (loaded) => {
+        // The bindings are CommonJS, so a dynamic import exposes them as the default export
+        const bindings = ((loaded as { default?: unknown })?.default ?? loaded) as Partial<Libgit2Module>;
+        if (typeof bindings?.Clone?.clone !== 'function') {
+          throw new CloneBackendUnavailableError(`${LIBGIT2_MODULE} does not export Clone.clone`);
+        }
+        return bindings as Libgit2Module;
+      },
+      (error: unknown) => {
+        throw new CloneBackendUnavailableError((error as Error)?.message || String(error));
+      }
+    );
+    libgit2Promise.catch(() => {
+      libgit2Promise = null;
+    });
+  }
+  return await libgit2Promise;
+}
+
+/**
+ * Clones a repository through libgit2 without starting a git process. SSH remotes authenticate through
+ * the running ssh-agent; git credential helpers and global git config are not consulted.
+ * @param request - Remote URL, destination and progress hooks
+ * @throws {CloneBackendUnavailableError} If the bindings cannot be loaded
+ */
+export async function cloneWithLibgit2({ url, repositoryPath, signal, onProgress }: InProcessCloneRequest): Promise<void> {
+  const libgit2 = await loadLibgit2();
+  const credentials = libgit2.Credential ?? libgit2.Cred;
+  signal?.throwIfAborted();
+
+  await libgit2.Clone.clone(url, repositoryPath, {
+    fetchOpts: {
+      callbacks: {
+        credentials: (_url: string, userName: string) => credentials?.sshKeyFromAgent(userName),
+        transferProgress: {
+          throttle: PROGRESS_THROTTLE_MS,
+          callback: (stats: Libgit2TransferProgress) => {
+            onProgress?.({
+              receivedObjects: stats.receivedObjects(),
+              totalObjects: stats.totalObjects(),
+              receivedBytes: stats.receivedBytes(),
+            });
+            // A negative result makes libgit2 stop the transfer and fail the clone
+            return signal?.aborted ? -1 : 0;
+          },
+        },
+      },
+    },
+  });
+}
@@ -0,0 +1,170 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { after, before, describe, it } from 'node:test';
+
+import {
+  compareRefs,
+  CompareRefError,
+  findWorktreeMergeBase,
+  UnrelatedHistoriesError,
+} from './git-compare-repository.js';
+import { WorktreeFilePathError } from './worktree-file-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) =>
+  (await execFileAsync('git', ['-C', cwd, '-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', ...args])).stdout.trim();
+
+describe('compareRefs', () => {
+  let workdir: string;
+  let mainCommit: string;
+
+  before(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-compare-'));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    const worktreePath = path.join(workdir, 'acme', 'demo', 'login');
+    await fs.mkdir(path.join(repositoryPath, 'docs'), { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await fs.writeFile(path.join(repositoryPath, 'notes.md'), 'one\n');
+    await fs.writeFile(path.join(repositoryPath, 'docs', 'guide.md'), 'guide\n');
+    await git(repositoryPath, ['add', '-A']);
+    await git(repositoryPath, ['commit', '--quiet', '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', worktreePath]);
+
+    await fs.writeFile(path.join(worktreePath, 'notes.md'), 'one\ntwo\n');
+    await git(worktreePath, ['commit', '--quiet', '-am', 'Extend notes']);
+    await fs.writeFile(path.join(worktreePath, 'docs', 'guide.md'), 'guide\n' + 'x'.repeat(4096) + '\n');
+    await git(worktreePath, ['commit', '--quiet', '-am', 'Expand guide']);
+
+    // A commit only on main must not show up in the comparison
+    await fs.writeFile(path.join(repositoryPath, 'main-only.md'), 'main\n');
+    await git(repositoryPath, ['add', '-A']);
+    await git(repositoryPath, ['commit', '--quiet', '-m', 'Main only']);
+    mainCommit = await git(repositoryPath, ['rev-parse', 'main']);
+  });
+
+  after(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('returns the diff from the merge base and the commits unique to head', async () => {
+    const comparison = await compareRefs(workdir, 'acme', 'demo', { base: 'main', head: 'feature/login' });
+
+    assert.equal(comparison.base.commit, mainCommit);
+    assert.equal(comparison.head.ref, 'feature/login');
+    assert.notEqual(comparison.mergeBase, null);
+    assert.match(comparison.diff, /^\+two$/m);
+    assert.match(comparison.diff, /docs\/guide\.md/);
+    assert.doesNotMatch(comparison.diff, /main-only\.md/);
+    assert.equal(comparison.truncated, false);
+    assert.deepEqual(
+      comparison.commits.items.map((commit) => commit.subject),
+      ['Expand guide', 'Extend notes']
+    );
+    assert.equal(comparison.commits.total, 2);
+  });
+
+  it('scopes the diff and commits to a path', async () => {
+    const comparison = await compareRefs(workdir, 'acme', 'demo', {
+      base: 'main',
+      head: 'feature/login',
+      path: 'notes.md',
+    });
+
+    assert.equal(comparison.path, 'notes.md');
+    assert.doesNotMatch(comparison.diff, /guide\.md/);
+    assert.deepEqual(
+      comparison.commits.items.map((commit) => commit.subject),
+      ['Extend notes']
+    );
+  });
+
+  it('caps the diff and the commit list', async () => {
+    const comparison = await compareRefs(workdir, 'acme', 'demo', {
+      base: 'main',
+      head: 'feature/login',
+      maxDiffBytes: 512,
+      commitLimit: 1,
+    });
+
+    assert.equal(comparison.truncated, true);
+    assert.ok(Buffer.byteLength(comparison.diff) <= 512);
+    assert.ok(comparison.diff.endsWith('\n'));
+    assert.equal(comparison.commits.items.length, 1);
+    assert.equal(comparison.commits.total, 2);
+    assert.equal(comparison.commits.truncated, true);
+  });
+
+  it('rejects refs that do not resolve and paths outside the repository', async () => {
+    await assert.rejects(
+      compareRefs(workdir, 'acme', 'demo', { base: 'main', head: 'feature/missing' }),
+      CompareRefError
+    );
+    await assert.rejects(
+      compareRefs(workdir, 'acme', 'demo', { base: '--output=/tmp/x', head: 'main' }),
+      CompareRefError
+    );
+    await assert.rejects(
+      compareRefs(workdir, 'acme', 'demo', { base: 'main', head: 'feature/login', path: '../outside' }),
+      WorktreeFilePathError
+    );
+  });
+});
+
+describe('findWorktreeMergeBase', () => {
+  let workdir: string;
+  let forkCommit: string;
+
+  before(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-merge-base-'));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    const worktreePath = path.join(workdir, 'acme', 'demo', 'login');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await git(repositoryPath, ['commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+    await git(repositoryPath, ['commit', '--quiet', '--allow-empty', '-m', 'Fork point']);
+    forkCommit = await git(repositoryPath, ['rev-parse', 'HEAD']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', worktreePath]);
+    await git(worktreePath, ['commit', '--quiet', '--allow-empty', '-m', 'Branch work']);
+    await git(repositoryPath, ['commit', '--quiet', '--allow-empty', '-m', 'Main moves on']);
+    await git(repositoryPath, ['branch', 'release', `${forkCommit}~1`]);
+
+    // A root commit of its own, sharing nothing with main
+    const orphan = await git(repositoryPath, ['commit-tree', '4b825dc642cb6eb9a060e54bf8d69288fbee4904', '-m', 'Orphan']);
+    await git(repositoryPath, ['branch', 'orphan', orphan]);
+  });
+
+  after(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it("defaults to the repository's default branch", async () => {
+    const result = await findWorktreeMergeBase(workdir, 'acme', 'demo', 'feature/login');
+
+    assert.equal(result.against, 'main');
+    assert.equal(result.mergeBase, forkCommit);
+    assert.notEqual(result.headCommit, forkCommit);
+    assert.notEqual(result.againstCommit, forkCommit);
+  });
+
+  it('uses the requested ref', async () => {
+    const result = await findWorktreeMergeBase(workdir, 'acme', 'demo', 'feature/login', { against: 'release' });
+
+    assert.equal(result.against, 'release');
+    assert.equal(result.mergeBase, result.againstCommit);
+  });
+
+  it('rejects unrelated histories and refs that do not resolve', async () => {
+    await assert.rejects(
+      findWorktreeMergeBase(workdir, 'acme', 'demo', 'feature/login', { against: 'orphan' }),
+      UnrelatedHistoriesError
+    );
+    await assert.rejects(
+      findWorktreeMergeBase(workdir, 'acme', 'demo', 'feature/login', { against: 'missing' }),
+      CompareRefError
+    );
+  });
+});
@@ -0,0 +1,252 @@
+import fs from 'node:fs/promises';
+import {
+  executeCappedGitCommandInRepo,
+  executeGitCommandInRepo,
+  GitCommandError,
+  GIT_BUFFER_SIZES,
+} from './git-repository.js';
+import { normaliseGitPath, parseRecentCommits } from './git-status-repository.js';
+import { resolveRepositoryPaths } from './repository-paths.js';
+import { WorktreeFilePathError } from './worktree-file-repository.js';
+import { getWorktreePath, RepositoryNotFoundError } from './worktree-repository.js';
+import { resolveDefaultBranch } from '../core/default-branch.js';
+import { normalizeBranchName } from '../domain/index.js';
+import type { CommitCollection } from '../types/git.js';
+
+/**
+ * Largest diff returned by a comparison before it is cut short, unless the configured git output limit
+ * is lower
+ */
+export const DEFAULT_COMPARE_DIFF_LIMIT_BYTES = 1024 * 1024;
+
+/**
+ * Commits listed by a comparison unless the caller asks for fewer
+ */
+export const DEFAULT_COMPARE_COMMIT_LIMIT = 100;
+
+/**
+ * Error raised when a ref to compare is malformed or does not resolve to a commit
+ */
+export class CompareRefError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'invalid_ref';
+
+  constructor(message: string) {
+    super(message);
+    this.name = 'CompareRefError';
+  }
+}
+
+/**
+ * Error raised when two commits share no history, so they have no merge base
+ */
+export class UnrelatedHistoriesError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'unrelated_histories';
+
+  constructor(branch: string, against: string) {
+    super(`${branch} and ${against} have unrelated histories`);
+    this.name = 'UnrelatedHistoriesError';
+  }
+}
+
+export interface CompareRefsOptions {
+  base: string;
+  head: string;
+  /**
+   * Limits the diff and commit list to a file or directory, relative to the repository root
+   */
+  path?: string;
+  /**
+   * Overrides the diff's byte limit
+   */
+  maxDiffBytes?: number;
+  commitLimit?: number;
+}
+
+export interface RefComparison {
+  base: { ref: string; commit: string };
+  head: { ref: string; commit: string };
+  path: string | null;
+  /**
+   * Common ancestor the diff is taken from, or null when the histories are unrelated
+   */
+  mergeBase: string | null;
+  diff: string;
+  truncated: boolean;
+  /**
+   * Byte limit the diff was held to
+   */
+  limitBytes: number;
+  commits: CommitCollection;
+}
+
+async function resolveCommit(repositoryPath: string, name: string, ref: string): Promise<string> {
+  const trimmed = ref.trim();
+  if (!trimmed) {
+    throw new CompareRefError(`${name} is required`);
+  }
+  // A leading dash would be read as an option by every git command the ref is passed to
+  if (trimmed.startsWith('-')) {
+    throw new CompareRefError(`${name} is not a valid ref: ${trimmed}`);
+  }
+  try {
+    const { stdout } = await executeGitCommandInRepo(
+      repositoryPath,
+      ['rev-parse', '--verify', '--quiet', `${trimmed}^{commit}`],
+      { maxBuffer: GIT_BUFFER_SIZES.SMALL }
+    );
+    return stdout.trim();
+  } catch (error: unknown) {
+    if (error instanceof GitCommandError) {
+      throw new CompareRefError(`${name} does not resolve to a commit: ${trimmed}`);
+    }
+    throw error;
+  }
+}
+
+export interface WorktreeMergeBase {
+  branch: string;
+  /**
+   * The ref compared against: the requested one, or the repository's default branch
+   */
+  against: string;
+  headCommit: string;
+  againstCommit: string;
+  mergeBase: string;
+}
+
+async function resolveMergeBase(repositoryPath: string, base: string, head: string): Promise<string | null> {
+  try {
+    const { stdout } = await executeGitCommandInRepo(repositoryPath, ['merge-base', base, head], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+    return stdout.trim() || null;
+  } catch (error: unknown) {
+    // merge-base exits 1 without output when the commits share no history
+    if (error instanceof GitCommandError && !error.stderr) {
+      return null;
+    }
+    throw error;
+  }
+}
+
+/**
+ * Compares two refs of a repository the way a pull request would: the diff from their merge base
+ * to `head`, and the commits reachable from `head` but not from `base`
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param options - Refs to compare, an optional path scope and output limits
+ * @returns Resolved commits, the (possibly truncated) diff and the commit list
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ * @throws {CompareRefError} If either ref does not resolve to a commit
+ * @throws {WorktreeFilePathError} If the path is absolute or escapes the repository
+ */
+export async function compareRefs(
+  workdir: string,
+  org: string,
+  repo: string,
+  {
+    base,
+    head,
+    path: scope,
+    maxDiffBytes = DEFAULT_COMPARE_DIFF_LIMIT_BYTES,
+    commitLimit = DEFAULT_COMPARE_COMMIT_LIMIT,
+  }: CompareRefsOptions
+): Promise<RefComparison> {
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  try {
+    await fs.access(repositoryPath);
+  } catch {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+
+  let pathspec: string | null = null;
+  if (scope !== undefined && scope.trim()) {
+    try {
+      pathspec = normaliseGitPath(scope);
+    } catch (error: unknown) {
+      throw new WorktreeFilePathError((error as Error).message);
+    }
+  }
+  const pathArgs = pathspec ? ['--', pathspec] : ['--'];
+
+  const baseCommit = await resolveCommit(repositoryPath, 'base', base);
+  const headCommit = await resolveCommit(repositoryPath, 'head', head);
+  const mergeBase = await resolveMergeBase(repositoryPath, baseCommit, headCommit);
+
+  const {
+    stdout: diff,
+    truncated,
+    limitBytes,
+  } = await executeCappedGitCommandInRepo(
+    repositoryPath,
+    ['diff', '--no-color', mergeBase ?? baseCommit, headCommit, ...pathArgs],
+    { maxBytes: maxDiffBytes }
+  );
+
+  const range = `${baseCommit}..${headCommit}`;
+  const [{ stdout: log }, { stdout: count }] = await Promise.all([
+    executeGitCommandInRepo(repositoryPath, [
+      'log',
+      '--pretty=format:%H%x1f%an%x1f%ar%x1f%cI%x1f%s',
+      '--max-count',
+      String(commitLimit),
+      range,
+      ...pathArgs,
+    ]),
+    executeGitCommandInRepo(repositoryPath, ['rev-list', '--count', range, ...pathArgs], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    }),
+  ]);
+  const listed = parseRecentCommits(log, commitLimit);
+  const total = Math.max(Number.parseInt(count.trim(), 10) || 0, listed.items.length);
+
+  return {
+    base: { ref: base.trim(), commit: baseCommit },
+    head: { ref: head.trim(), commit: headCommit },
+    path: pathspec,
+    mergeBase,
+    diff,
+    truncated,
+    limitBytes,
+    commits: { items: listed.items, total, truncated: total > listed.items.length },
+  };
+}
+
+/**
+ * Finds the merge base of a worktree's HEAD and another ref, the starting point of the branch's review scope
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param options - Ref to compare against; defaults to the repository's default branch
+ * @returns Both resolved commits and their merge base
+ * @throws {WorktreeNotFoundError} If the branch has no worktree
+ * @throws {CompareRefError} If the ref does not resolve to a commit
+ * @throws {UnrelatedHistoriesError} If HEAD and the ref share no history
+ */
+export async function findWorktreeMergeBase(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  { against }: { against?: string } = {}
+): Promise<WorktreeMergeBase> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { repositoryPath, worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  const target = against?.trim() || (await resolveDefaultBranch(repositoryPath));
+  const headCommit = await resolveCommit(worktreePath, 'HEAD', 'HEAD');
+  const againstCommit = await resolveCommit(worktreePath, 'against', target);
+  const mergeBase = await resolveMergeBase(worktreePath, headCommit, againstCommit);
+  if (!mergeBase) {
+    throw new UnrelatedHistoriesError(branchName, target);
+  }
+
+  return { branch: branchName, against: target, headCommit, againstCommit, mergeBase };
+}
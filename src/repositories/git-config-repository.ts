@@ -0,0 +1,132 @@
+import fs from 'node:fs/promises';
+import { executeGitCommandInRepo, GitCommandError, GIT_BUFFER_SIZES, redactUrlCredentials } from './git-repository.js';
+import { resolveRepositoryPaths } from './repository-paths.js';
+import { RepositoryNotFoundError } from './worktree-repository.js';
+
+/**
+ * Error raised when a config key is not on the readable allowlist
+ */
+export class GitConfigKeyNotAllowedError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'config_key_not_allowed';
+
+  constructor(key: string) {
+    super(`Config key ${key} cannot be read`);
+    this.name = 'GitConfigKeyNotAllowedError';
+  }
+}
+
+/**
+ * Error raised when a readable config key has no value in the repository
+ */
+export class GitConfigKeyUnsetError extends Error {
+  public readonly statusCode: number = 404;
+  public readonly code = 'config_key_unset';
+
+  constructor(org: string, repo: string, key: string) {
+    super(`Config key ${key} is not set in ${org}/${repo}`);
+    this.name = 'GitConfigKeyUnsetError';
+  }
+}
+
+export interface RepositoryConfigValue {
+  key: string;
+  value: string;
+}
+
+/**
+ * Keys without a subsection that may be read, lowercased as git compares them
+ */
+const READABLE_KEYS = new Set([
+  'init.defaultbranch',
+  'user.name',
+  'user.email',
+  'core.bare',
+  'core.autocrlf',
+  'core.filemode',
+  'pull.rebase',
+  'push.default',
+  'commit.gpgsign',
+]);
+
+/**
+ * Variables that may be read under any subsection of these sections, such as `remote.origin.url`
+ */
+const READABLE_SUBSECTION_VARIABLES: Record<string, ReadonlySet<string>> = {
+  remote: new Set(['url', 'pushurl', 'fetch']),
+  branch: new Set(['remote', 'merge', 'rebase']),
+};
+
+/**
+ * Variables holding URLs, whose user info is masked before a value is returned
+ */
+const URL_VARIABLES = new Set(['url', 'pushurl']);
+
+/**
+ * Reports whether a config key is on the readable allowlist. Everything else, such as
+ * `http.<url>.extraheader` or `credential.helper`, may hold credentials and stays unreadable.
+ * Section and variable names match case-insensitively and the subsection exactly, as in git.
+ * @param key - Config key, such as `remote.origin.url`
+ */
+export function isReadableGitConfigKey(key: string): boolean {
+  if (/[\0\n]/.test(key)) {
+    return false;
+  }
+  const first = key.indexOf('.');
+  const last = key.lastIndexOf('.');
+  if (first <= 0 || last === key.length - 1) {
+    return false;
+  }
+  const section = key.slice(0, first).toLowerCase();
+  const variable = key.slice(last + 1).toLowerCase();
+  if (first === last) {
+    return READABLE_KEYS.has(`${section}.${variable}`);
+  }
+  const subsection = key.slice(first + 1, last);
+  return Boolean(subsection) && (READABLE_SUBSECTION_VARIABLES[section]?.has(variable) ?? false);
+}
+
+/**
+ * Reads a config value with `git config --get`, as seen from the repository's primary clone, so
+ * repository values win over global ones. Only allowlisted keys can be read.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param key - Config key, such as `remote.origin.url`
+ * @returns The key and its value; the last one when the key is set more than once, with URL credentials masked
+ * @throws {GitConfigKeyNotAllowedError} If the key is not on the allowlist
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ * @throws {GitConfigKeyUnsetError} If the key has no value
+ */
+export async function readRepositoryConfig(
+  workdir: string,
+  org: string,
+  repo: string,
+  key: string
+): Promise<RepositoryConfigValue> {
+  if (!isReadableGitConfigKey(key)) {
+    throw new GitConfigKeyNotAllowedError(key);
+  }
+
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  try {
+    await fs.access(repositoryPath);
+  } catch {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+
+  try {
+    const { stdout } = await executeGitCommandInRepo(repositoryPath, ['config', '--get', '--', key], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+    const value = stdout.replace(/\n$/, '');
+    const variable = key.slice(key.lastIndexOf('.') + 1).toLowerCase();
+    return { key, value: URL_VARIABLES.has(variable) ? redactUrlCredentials(value) : value };
+  } catch (error: unknown) {
+    // git config exits 1 when the key is unset
+    if (error instanceof GitCommandError && error.exitCode === 1) {
+      throw new GitConfigKeyUnsetError(org, repo, key);
+    }
+    throw error;
+  }
+}
@@ -0,0 +1,98 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, beforeEach, describe, it } from 'node:test';
+
+import { getWorktreeDiffStat, parseDiffStat } from './git-diff-stat-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+const commitAs = ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet'];
+
+describe('parseDiffStat', () => {
+  it('pairs raw records with their line counts', () => {
+    const output = [
+      ':100644 100644 45b983b 45b983b M',
+      'b.txt',
+      ':000000 100644 0000000 bdc955b A',
+      'logo.png',
+      ':100644 100644 96cc558 a699df2 R096',
+      'a.txt',
+      'c.txt',
+      '2\t1\tb.txt',
+      '-\t-\tlogo.png',
+      '1\t0\t',
+      'a.txt',
+      'c.txt',
+      '',
+    ].join('\0');
+
+    assert.deepEqual(parseDiffStat(output), {
+      files: [
+        { path: 'b.txt', previousPath: null, status: 'modified', similarity: null, additions: 2, deletions: 1, binary: false },
+        { path: 'logo.png', previousPath: null, status: 'added', similarity: null, additions: null, deletions: null, binary: true },
+        { path: 'c.txt', previousPath: 'a.txt', status: 'renamed', similarity: 96, additions: 1, deletions: 0, binary: false },
+      ],
+      totals: { files: 3, additions: 3, deletions: 1 },
+    });
+    assert.deepEqual(parseDiffStat(''), { files: [], totals: { files: 0, additions: 0, deletions: 0 } });
+  });
+});
+
+describe('getWorktreeDiffStat', () => {
+  let workdir: string;
+  let worktreePath: string;
+
+  beforeEach(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-diffstat-'));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    worktreePath = path.join(workdir, 'acme', 'demo', 'feature');
+    await fs.mkdir(path.join(repositoryPath, 'src'), { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    const lines = Array.from({ length: 40 }, (_, index) => `line ${index + 1}`).join('\n');
+    await fs.writeFile(path.join(repositoryPath, 'src', 'server.ts'), `${lines}\n`);
+    await fs.writeFile(path.join(repositoryPath, 'README.md'), 'demo\n');
+    await git(repositoryPath, ['add', '-A']);
+    await git(repositoryPath, [...commitAs, '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature', worktreePath]);
+  });
+
+  afterEach(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('detects a moved file as a rename and totals the changes', async () => {
+    await fs.mkdir(path.join(worktreePath, 'lib'));
+    await git(worktreePath, ['mv', 'src/server.ts', 'lib/server.ts']);
+    await fs.appendFile(path.join(worktreePath, 'lib', 'server.ts'), 'line 41\n');
+    await git(worktreePath, ['add', '-A']);
+    await fs.writeFile(path.join(worktreePath, 'README.md'), 'demo\nmore\n');
+
+    const all = await getWorktreeDiffStat(workdir, 'acme', 'demo', 'feature');
+
+    assert.equal(all.branch, 'feature');
+    assert.equal(all.mode, 'all');
+    const renamed = all.files.find((file) => file.status === 'renamed');
+    assert.ok(renamed);
+    assert.equal(renamed.path, 'lib/server.ts');
+    assert.equal(renamed.previousPath, 'src/server.ts');
+    assert.ok((renamed.similarity ?? 0) >= 90);
+    assert.equal(renamed.additions, 1);
+    assert.equal(renamed.deletions, 0);
+    assert.deepEqual(all.totals, { files: 2, additions: 2, deletions: 0 });
+
+    const staged = await getWorktreeDiffStat(workdir, 'acme', 'demo', 'feature', 'staged');
+    assert.deepEqual(
+      staged.files.map((file) => [file.status, file.path]),
+      [['renamed', 'lib/server.ts']]
+    );
+    const unstaged = await getWorktreeDiffStat(workdir, 'acme', 'demo', 'feature', 'unstaged');
+    assert.deepEqual(
+      unstaged.files.map((file) => [file.status, file.path, file.additions]),
+      [['modified', 'README.md', 1]]
+    );
+  });
+});
@@ -0,0 +1,138 @@
+import { executeGitCommandInRepo, GIT_BUFFER_SIZES } from './git-repository.js';
+import { getWorktreePath } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+
+export type DiffStatFileStatus = 'added' | 'modified' | 'deleted' | 'renamed' | 'copied' | 'type_changed';
+
+/**
+ * Which changes a diff stat covers: staged changes, unstaged changes, or everything uncommitted
+ */
+export type DiffStatMode = 'staged' | 'unstaged' | 'all';
+
+export interface DiffStatFile {
+  path: string;
+  /**
+   * Source of a rename or copy; null otherwise
+   */
+  previousPath: string | null;
+  status: DiffStatFileStatus;
+  /**
+   * Percentage git scored the rename or copy at; null otherwise
+   */
+  similarity: number | null;
+  /**
+   * Lines added and deleted; null for binary files
+   */
+  additions: number | null;
+  deletions: number | null;
+  binary: boolean;
+}
+
+export interface DiffStat {
+  files: DiffStatFile[];
+  totals: {
+    files: number;
+    additions: number;
+    deletions: number;
+  };
+}
+
+export interface WorktreeDiffStat extends DiffStat {
+  branch: string;
+  mode: DiffStatMode;
+}
+
+const DIFF_STAT_STATUSES: Record<string, DiffStatFileStatus> = {
+  A: 'added',
+  M: 'modified',
+  D: 'deleted',
+  R: 'renamed',
+  C: 'copied',
+  T: 'type_changed',
+};
+
+function parseLineCount(value: string | undefined): number | null {
+  return value && /^\d+$/.test(value) ? Number(value) : null;
+}
+
+/**
+ * Parses `git diff --raw --numstat -z` output. The raw records come first and name each file's status and
+ * any rename or copy source; the numstat records that follow carry the line counts in the same order.
+ * @param output - Raw command output
+ * @returns Changed files with their line counts, and totals across them
+ */
+export function parseDiffStat(output: string): DiffStat {
+  const tokens = output.split('\0');
+  const files: DiffStatFile[] = [];
+  let index = 0;
+
+  while (index < tokens.length && tokens[index]?.startsWith(':')) {
+    const fields = tokens[index]!.split(' ');
+    const code = fields[4] ?? '';
+    const letter = code.charAt(0);
+    const paired = letter === 'R' || letter === 'C';
+    const first = tokens[index + 1] ?? '';
+    const second = paired ? tokens[index + 2] ?? '' : null;
+    files.push({
+      path: second ?? first,
+      previousPath: paired ? first : null,
+      status: DIFF_STAT_STATUSES[letter] ?? 'modified',
+      similarity: paired ? parseLineCount(code.slice(1)) : null,
+      additions: null,
+      deletions: null,
+      binary: false,
+    });
+    index += paired ? 3 : 2;
+  }
+
+  for (const file of files) {
+    const [added, deleted, inlinePath = ''] = (tokens[index] ?? '').split('\t');
+    // Renames and copies leave the path empty and list the source and destination as separate tokens
+    index += inlinePath ? 1 : 3;
+    file.binary = added === '-' && deleted === '-';
+    file.additions = parseLineCount(added);
+    file.deletions = parseLineCount(deleted);
+  }
+
+  return {
+    files,
+    totals: {
+      files: files.length,
+      additions: files.reduce((sum, file) => sum + (file.additions ?? 0), 0),
+      deletions: files.reduce((sum, file) => sum + (file.deletions ?? 0), 0),
+    },
+  };
+}
+
+/**
+ * Summarises a worktree's uncommitted changes per file with `git diff --numstat`, detecting renames and
+ * copies (`-M -C`). Untracked files are not part of any diff and are left out.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param mode - Staged changes, unstaged changes, or all changes against HEAD
+ * @returns Changed files with additions, deletions and rename sources, plus totals
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ */
+export async function getWorktreeDiffStat(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  mode: DiffStatMode = 'all'
+): Promise<WorktreeDiffStat> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('branch is required');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  const range = mode === 'staged' ? ['--cached'] : mode === 'all' ? ['HEAD'] : [];
+  const { stdout } = await executeGitCommandInRepo(
+    worktreePath,
+    ['diff', '--no-color', '--raw', '--numstat', '-z', '-M', '-C', ...range, '--'],
+    { maxBuffer: GIT_BUFFER_SIZES.LARGE }
+  );
+  return { branch: branchName, mode, ...parseDiffStat(stdout) };
+}
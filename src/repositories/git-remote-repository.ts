@@ -0,0 +1,149 @@
+import fs from 'node:fs/promises';
+import {
+  executeGitCommandInRepo,
+  extractGitErrorMessage,
+  GitNotFoundError,
+  GIT_BUFFER_SIZES,
+  redactUrlCredentials,
+} from './git-repository.js';
+import { resolveRepositoryPaths } from './repository-paths.js';
+import { RepositoryNotFoundError } from './worktree-repository.js';
+import { parseRepositoryUrl, validateRemoteName } from '../domain/index.js';
+
+/**
+ * Error raised when adding a remote under a name the repository already uses
+ */
+export class RemoteExistsError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'remote_exists';
+
+  constructor(org: string, repo: string, name: string) {
+    super(`Remote ${name} already exists in ${org}/${repo}`);
+    this.name = 'RemoteExistsError';
+  }
+}
+
+export interface RepositoryRemote {
+  name: string;
+  fetchUrl: string;
+  /**
+   * Differs from `fetchUrl` only when the remote has its own `pushurl`
+   */
+  pushUrl: string;
+}
+
+export interface AddRepositoryRemoteOptions {
+  /**
+   * URL schemes accepted for the remote (defaults to the domain allowlist)
+   */
+  allowedSchemes?: readonly string[];
+  /**
+   * Organization used for URLs that name only a repository, as for clones
+   */
+  defaultOrg?: string | null;
+}
+
+/**
+ * Parses `git remote -v` output, where each remote has a `(fetch)` and a `(push)` line
+ * @param output - Raw command output
+ * @returns Remotes in output order, with credentials embedded in their URLs masked
+ */
+export function parseRemoteList(output: string): RepositoryRemote[] {
+  const remotes = new Map<string, RepositoryRemote>();
+  for (const line of output.split('\n')) {
+    const match = /^(\S+)\t(.+) \((fetch|push)\)$/.exec(line.trim());
+    if (!match) {
+      continue;
+    }
+    const [, name = '', rawUrl = '', direction] = match;
+    const url = redactUrlCredentials(rawUrl);
+    const remote = remotes.get(name) ?? { name, fetchUrl: url, pushUrl: url };
+    if (direction === 'fetch') {
+      remote.fetchUrl = url;
+    } else {
+      remote.pushUrl = url;
+    }
+    remotes.set(name, remote);
+  }
+  return [...remotes.values()];
+}
+
+async function resolveRemoteRepository(workdir: string, org: string, repo: string): Promise<string> {
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  try {
+    await fs.access(repositoryPath);
+  } catch {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+  return repositoryPath;
+}
+
+async function readRemotes(repositoryPath: string): Promise<RepositoryRemote[]> {
+  const { stdout } = await executeGitCommandInRepo(repositoryPath, ['remote', '-v'], {
+    maxBuffer: GIT_BUFFER_SIZES.SMALL,
+  });
+  return parseRemoteList(stdout);
+}
+
+/**
+ * Lists the remotes configured in a repository's primary clone. Remotes live in the repository's
+ * config, so every worktree shares them.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @returns Remotes with their fetch and push URLs, credentials masked
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ */
+export async function listRepositoryRemotes(workdir: string, org: string, repo: string): Promise<RepositoryRemote[]> {
+  return await readRemotes(await resolveRemoteRepository(workdir, org, repo));
+}
+
+/**
+ * Adds a named remote with `git remote add`, such as a fork to push branches to. The URL is held to the
+ * same scheme allowlist as clone URLs.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param name - Remote name
+ * @param url - Remote URL
+ * @param options - Allowed URL schemes and the default organization
+ * @returns The new remote, its URL's credentials masked
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ * @throws {RepositoryIdentifierError} If the remote name is invalid
+ * @throws {UnsupportedUrlSchemeError} If the URL's scheme is not allowed
+ * @throws {RemoteExistsError} If a remote with the name already exists
+ */
+export async function addRepositoryRemote(
+  workdir: string,
+  org: string,
+  repo: string,
+  name: string,
+  url: string,
+  options: AddRepositoryRemoteOptions = {}
+): Promise<RepositoryRemote> {
+  const remoteName = validateRemoteName(name);
+  const { url: remoteUrl } = parseRepositoryUrl(url, options);
+  const repositoryPath = await resolveRemoteRepository(workdir, org, repo);
+
+  if ((await readRemotes(repositoryPath)).some((remote) => remote.name === remoteName)) {
+    throw new RemoteExistsError(org, repo, remoteName);
+  }
+
+  try {
+    await executeGitCommandInRepo(repositoryPath, ['remote', 'add', '--', remoteName, remoteUrl], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    const message = extractGitErrorMessage(error);
+    if (/already exists/i.test(message)) {
+      throw new RemoteExistsError(org, repo, remoteName);
+    }
+    throw new Error(`Failed to add remote: ${message}`, { cause: error });
+  }
+
+  const displayUrl = redactUrlCredentials(remoteUrl);
+  return { name: remoteName, fetchUrl: displayUrl, pushUrl: displayUrl };
+}
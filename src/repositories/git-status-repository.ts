@@ -1,8 +1,13 @@
 /* c8 ignore file */
 import fs from 'node:fs/promises';
 import path from 'node:path';
-import { executeGitCommandInRepo, GIT_BUFFER_SIZES } from './git-repository.js';
-import { getWorktreePath } from './worktree-repository.js';
+import {
+  executeCappedGitCommandInRepo,
+  executeGitCommandInRepo,
+  DEFAULT_GIT_OUTPUT_LIMIT_BYTES,
+  GIT_BUFFER_SIZES,
+} from './git-repository.js';
+import { getWorktreePath, WorktreeNotFoundError } from './worktree-repository.js';
 import { normalizeBranchName } from '../domain/index.js';
 import type {
   BranchSummary,
@@ -11,6 +16,7 @@ import type {
   GitOperationState,
   CommitCollection,
   WorktreeStatus,
+  WorktreeSummary,
   FileDiff,
   GetWorktreeStatusOptions,
   GetFileDiffOptions,
@@ -31,7 +37,10 @@ const STATUS_LABELS = new Map([
 
 const DEFAULT_ENTRY_LIMIT = 200;
 const DEFAULT_COMMIT_LIMIT = 10;
-const DEFAULT_DIFF_LIMIT = GIT_BUFFER_SIZES.LARGE;
+/**
+ * Text returned in place of a diff when a file has no changes
+ */
+export const NO_DIFF_PLACEHOLDER = 'No differences to display.';
 
 const POSIX_SEPARATOR = '/';
 
@@ -396,7 +405,7 @@ async function detectInProgressOperations(gitDir: string): Promise<GitOperationS
 /**
  * Parses recent commits from git log output
  */
-function parseRecentCommits(raw: string, limit: number = DEFAULT_COMMIT_LIMIT): CommitCollection {
+export function parseRecentCommits(raw: string, limit: number = DEFAULT_COMMIT_LIMIT): CommitCollection {
   if (!raw) {
     return { items: [], total: 0, truncated: false };
   }
@@ -407,12 +416,13 @@ function parseRecentCommits(raw: string, limit: number = DEFAULT_COMMIT_LIMIT):
     .filter(Boolean);
 
   const items = lines.map((line) => {
-    const [hash, author, relativeTime, subject] = line.split('\x1f');
+    const [hash, author, relativeTime, committedAt, subject] = line.split('\x1f');
     return {
       hash: hash || '',
       author: author || '',
       relativeTime: relativeTime || '',
       subject: subject || '',
+      committedAt: committedAt || '',
     };
   });
 
@@ -517,7 +527,7 @@ export async function getWorktreeStatus(
 
     const logArgs = [
       'log',
-      '--pretty=format:%H%x1f%an%x1f%ar%x1f%s',
+      '--pretty=format:%H%x1f%an%x1f%ar%x1f%cI%x1f%s',
       '--max-count',
       String(fetchLimit),
       ...includeRefs,
@@ -557,10 +567,89 @@ export async function getWorktreeStatus(
   };
 }
 
+/**
+ * Gets a lightweight summary of a single worktree (branch, dirty flag, ahead/behind, last commit)
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch name
+ * @returns Worktree summary
+ * @throws {WorktreeNotFoundError} If the worktree is not registered or its directory is missing
+ */
+export async function getWorktreeSummary(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<WorktreeSummary> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('branch is required');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  if (!(await pathExists(worktreePath))) {
+    throw new WorktreeNotFoundError(org, repo, branchName);
+  }
+
+  let statusOutput: string;
+  try {
+    const { stdout } = await executeGitCommandInRepo(worktreePath, [
+      'status',
+      '--porcelain=2',
+      '--branch',
+    ]);
+    statusOutput = stdout;
+  } catch (error: unknown) {
+    const err = error as { message?: string };
+    throw new Error(err && err.message ? err.message : 'Failed to read git status');
+  }
+
+  const branchSummary = parseBranchSummary(statusOutput);
+  const dirty = statusOutput
+    .split('\n')
+    .some((line) => line.trim() && !line.startsWith('# '));
+
+  let lastCommit: WorktreeSummary['lastCommit'] = null;
+  if (!branchSummary.unborn) {
+    try {
+      const { stdout } = await executeGitCommandInRepo(
+        worktreePath,
+        ['log', '-1', '--pretty=format:%H%x1f%an%x1f%ar%x1f%s%x1f%cI'],
+        { maxBuffer: GIT_BUFFER_SIZES.SMALL }
+      );
+      const [hash, author, relativeTime, subject, committedAt] = stdout.trim().split('\x1f');
+      if (hash) {
+        lastCommit = {
+          hash,
+          author: author || '',
+          relativeTime: relativeTime || '',
+          subject: subject || '',
+          committedAt: committedAt || '',
+        };
+      }
+    } catch {
+      lastCommit = null;
+    }
+  }
+
+  return {
+    org,
+    repo,
+    branch: branchName,
+    worktreePath,
+    dirty,
+    ahead: branchSummary.ahead,
+    behind: branchSummary.behind,
+    upstream: branchSummary.upstream,
+    lastCommit,
+  };
+}
+
 /**
  * Normalizes a git path (relative to worktree)
  */
-function normaliseGitPath(input: string): string {
+export function normaliseGitPath(input: string): string {
   if (typeof input !== 'string') {
     throw new Error('path is required');
   }
@@ -622,7 +711,7 @@ export async function getWorktreeFileDiff(
   org: string,
   repo: string,
   branch: string,
-  { path: targetPath, previousPath, mode, status }: GetFileDiffOptions = {}
+  { path: targetPath, previousPath, mode, status, maxBytes = DEFAULT_GIT_OUTPUT_LIMIT_BYTES }: GetFileDiffOptions = {}
 ): Promise<FileDiff> {
   const branchName = normalizeBranchName(branch);
   if (!branchName) {
@@ -637,19 +726,18 @@ export async function getWorktreeFileDiff(
 
   let stdout = '';
   let stderr = '';
+  let truncated = false;
 
   try {
     if (diffMode === 'staged') {
-      const { stdout: out } = await executeGitCommandInRepo(worktreePath, [
+      const result = await executeCappedGitCommandInRepo(worktreePath, [
         'diff',
         '--no-color',
         '--cached',
         '--',
         relativePath,
-      ], {
-        maxBuffer: DEFAULT_DIFF_LIMIT,
-      });
-      stdout = out;
+      ], { maxBytes });
+      ({ stdout, truncated } = result);
     } else if (diffMode === 'untracked') {
       const { execFile } = await import('node:child_process');
       const { promisify } = await import('node:util');
@@ -663,23 +751,26 @@ export async function getWorktreeFileDiff(
         '--',
         nullDevice,
         absolutePath,
-      ], { maxBuffer: DEFAULT_DIFF_LIMIT });
+      ], { maxBuffer: maxBytes });
       stdout = out;
     } else {
-      const { stdout: out } = await executeGitCommandInRepo(worktreePath, [
+      const result = await executeCappedGitCommandInRepo(worktreePath, [
         'diff',
         '--no-color',
         '--',
         relativePath,
-      ], {
-        maxBuffer: DEFAULT_DIFF_LIMIT,
-      });
-      stdout = out;
+      ], { maxBytes });
+      ({ stdout, truncated } = result);
     }
   } catch (error: unknown) {
-    const err = error as { stderr?: Buffer | string; stdout?: Buffer | string; message?: string };
+    const err = error as { stderr?: Buffer | string; stdout?: Buffer | string; message?: string; code?: unknown };
     stderr = err && err.stderr ? err.stderr.toString() : '';
     stdout = err && err.stdout ? err.stdout.toString() : '';
+    // `diff --no-index` runs outside the capped helper; cut its overflow back to whole lines the same way
+    if (err?.code === 'ERR_CHILD_PROCESS_STDIO_MAXBUFFER') {
+      truncated = true;
+      stdout = stdout.slice(0, stdout.lastIndexOf('\n') + 1);
+    }
     if (!stdout.trim()) {
       const message = stderr || err.message || 'Failed to render diff';
       throw new Error(message.trim());
@@ -705,6 +796,8 @@ export async function getWorktreeFileDiff(
           previousPath: previousRelativePath,
           mode: diffMode,
           diff: `--- /dev/null\n+++ b/${relativePath}\n@@ -0,0 +1,${Math.max(totalLines, 1)}\n${diffLines}`,
+          truncated: false,
+          limitBytes: maxBytes,
         };
       } catch (error: unknown) {
         const err = error as { message?: string };
@@ -716,7 +809,9 @@ export async function getWorktreeFileDiff(
       path: relativePath,
       previousPath: previousRelativePath,
       mode: diffMode,
-      diff: 'No differences to display.',
+      diff: NO_DIFF_PLACEHOLDER,
+      truncated: false,
+      limitBytes: maxBytes,
     };
   }
 
@@ -725,5 +820,7 @@ export async function getWorktreeFileDiff(
     previousPath: previousRelativePath,
     mode: diffMode,
     diff: stdout,
+    truncated,
+    limitBytes: maxBytes,
   };
 }
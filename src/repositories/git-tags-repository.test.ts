@@ -0,0 +1,75 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { after, before, describe, it } from 'node:test';
+
+import { listRepositoryTags, parseTagList } from './git-tags-repository.js';
+import { RepositoryNotFoundError } from './worktree-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) =>
+  (await execFileAsync('git', ['-C', cwd, '-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', ...args])).stdout.trim();
+
+const COMMIT = 'a'.repeat(40);
+const TAG_OBJECT = 'b'.repeat(40);
+
+describe('parseTagList', () => {
+  it('peels annotated tags to their commit and skips blank lines', () => {
+    const output = [
+      ['v2.0.0', 'tag', TAG_OBJECT, COMMIT, '2024-05-01T10:00:00+00:00', 'Second release'].join('\0'),
+      ['v1.0.0', 'commit', COMMIT, '', '', 'Seed'].join('\0'),
+      '',
+    ].join('\n');
+
+    assert.deepEqual(parseTagList(output), [
+      { name: 'v2.0.0', commit: COMMIT, annotated: true, createdAt: '2024-05-01T10:00:00+00:00', subject: 'Second release' },
+      { name: 'v1.0.0', commit: COMMIT, annotated: false, createdAt: null, subject: 'Seed' },
+    ]);
+  });
+});
+
+describe('listRepositoryTags', () => {
+  let workdir: string;
+  let firstCommit: string;
+  let secondCommit: string;
+
+  before(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-tags-'));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await git(repositoryPath, ['commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+    firstCommit = await git(repositoryPath, ['rev-parse', 'HEAD']);
+    await git(repositoryPath, ['tag', 'v1.0.0']);
+    await git(repositoryPath, ['commit', '--quiet', '--allow-empty', '--date=2099-01-01T00:00:00Z', '-m', 'Next']);
+    secondCommit = await git(repositoryPath, ['rev-parse', 'HEAD']);
+    await execFileAsync(
+      'git',
+      ['-C', repositoryPath, '-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'tag', '-a', 'v2.0.0', '-m', 'Second release'],
+      { env: { ...process.env, GIT_COMMITTER_DATE: '2099-01-02T00:00:00Z' } }
+    );
+  });
+
+  after(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('lists lightweight and annotated tags newest first', async () => {
+    const tags = await listRepositoryTags(workdir, 'acme', 'demo');
+
+    assert.deepEqual(
+      tags.map(({ name, commit, annotated, subject }) => ({ name, commit, annotated, subject })),
+      [
+        { name: 'v2.0.0', commit: secondCommit, annotated: true, subject: 'Second release' },
+        { name: 'v1.0.0', commit: firstCommit, annotated: false, subject: 'Seed' },
+      ]
+    );
+  });
+
+  it('responds with a not found error when the repository is not cloned', async () => {
+    await assert.rejects(listRepositoryTags(workdir, 'acme', 'missing'), RepositoryNotFoundError);
+  });
+});
@@ -0,0 +1,81 @@
+import fs from 'node:fs/promises';
+import { executeGitCommandInRepo, GIT_BUFFER_SIZES } from './git-repository.js';
+import { resolveRepositoryPaths } from './repository-paths.js';
+import { RepositoryNotFoundError } from './worktree-repository.js';
+
+export interface RepositoryTag {
+  name: string;
+  /**
+   * Commit the tag points at, peeled through annotated tag objects
+   */
+  commit: string;
+  annotated: boolean;
+  /**
+   * ISO timestamp of the tag object for annotated tags, or of the commit for lightweight ones
+   */
+  createdAt: string | null;
+  /**
+   * Annotation subject for annotated tags, commit subject for lightweight ones
+   */
+  subject: string;
+}
+
+const TAG_FIELDS = [
+  '%(refname:strip=2)',
+  '%(objecttype)',
+  '%(objectname)',
+  '%(*objectname)',
+  '%(creatordate:iso-strict)',
+  '%(contents:subject)',
+];
+
+/**
+ * Parses `git tag --list` output produced with NUL separated fields and one tag per line
+ * @param output - Raw command output
+ * @returns Parsed tags in output order
+ */
+export function parseTagList(output: string): RepositoryTag[] {
+  const tags: RepositoryTag[] = [];
+  for (const line of output.split('\n')) {
+    if (!line) {
+      continue;
+    }
+    const [name = '', type = '', objectName = '', peeled = '', createdAt = '', subject = ''] = line.split('\0');
+    if (!name || !objectName) {
+      continue;
+    }
+    const annotated = type === 'tag';
+    tags.push({
+      name,
+      commit: annotated && peeled ? peeled : objectName,
+      annotated,
+      createdAt: createdAt || null,
+      subject,
+    });
+  }
+  return tags;
+}
+
+/**
+ * Lists the tags in a repository's primary clone, newest first
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @returns Tags with the commits they point at
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ */
+export async function listRepositoryTags(workdir: string, org: string, repo: string): Promise<RepositoryTag[]> {
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  try {
+    await fs.access(repositoryPath);
+  } catch {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+
+  const { stdout } = await executeGitCommandInRepo(
+    repositoryPath,
+    ['tag', '--list', '--sort=-creatordate', `--format=${TAG_FIELDS.join('%00')}`],
+    { maxBuffer: GIT_BUFFER_SIZES.LARGE }
+  );
+  return parseTagList(stdout);
+}
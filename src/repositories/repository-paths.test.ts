@@ -1,10 +1,14 @@
 import assert from 'node:assert/strict';
-import { describe, it } from 'node:test';
+import { afterEach, describe, it } from 'node:test';
 
-import { resolveRepositoryPaths } from './repository-paths.js';
+import { configureCloneLayout, resolveRepositoryPaths } from './repository-paths.js';
 import { RepositoryIdentifierError } from '../domain/index.js';
 
 describe('resolveRepositoryPaths', () => {
+  afterEach(() => {
+    configureCloneLayout();
+  });
+
   it('normalizes segments and resolves paths inside workdir', () => {
     const result = resolveRepositoryPaths('/workdir', ' acme ', ' demo ');
 
@@ -12,6 +16,17 @@ describe('resolveRepositoryPaths', () => {
     assert.equal(result.repositoryPath, '/workdir/acme/demo/repository');
   });
 
+  it('applies the configured clone layout', () => {
+    configureCloneLayout('{repo}/{org}');
+    assert.equal(resolveRepositoryPaths('/workdir', 'acme', 'demo').repoRoot, '/workdir/demo/acme');
+
+    configureCloneLayout('repos/{workspace}/{repository}');
+    assert.equal(
+      resolveRepositoryPaths('/workdir', 'acme', 'demo').repositoryPath,
+      '/workdir/repos/acme/demo/repository'
+    );
+  });
+
   it('rejects traversal tokens in org or repo', () => {
     assert.throws(
       () => resolveRepositoryPaths('/workdir', '..', 'demo'),
@@ -1,11 +1,35 @@
 import path from 'node:path';
-import { RepositoryIdentifierError, validateRepositorySegment } from '../domain/index.js';
+import {
+  RepositoryIdentifierError,
+  parseCloneLayout,
+  renderCloneLayout,
+  validateRepositorySegment,
+  type CloneLayout,
+} from '../domain/index.js';
 
 export interface RepositoryPaths {
   repoRoot: string;
   repositoryPath: string;
 }
 
+let cloneLayout: CloneLayout = parseCloneLayout();
+
+/**
+ * Sets the directory layout used to place repositories under the workdir
+ * @param template - Layout template such as `{org}/{repo}` or `repos/{org}/{repo}`; missing restores the default
+ * @throws {CloneLayoutError} If the template is invalid
+ */
+export function configureCloneLayout(template?: string | null): void {
+  cloneLayout = parseCloneLayout(template ?? undefined);
+}
+
+/**
+ * Returns the active clone layout
+ */
+export function getCloneLayout(): CloneLayout {
+  return cloneLayout;
+}
+
 function ensureInsideWorkdir(workdir: string, targetPath: string, label: string): string {
   const basePath = path.resolve(workdir);
   const resolvedTarget = path.resolve(targetPath);
@@ -35,7 +59,7 @@ export function resolveRepositoryPaths(workdir: string, orgInput: string, repoIn
   const normalizedWorkdir = path.resolve(workdir);
   const repoRoot = ensureInsideWorkdir(
     normalizedWorkdir,
-    path.resolve(normalizedWorkdir, safeOrg, safeRepo),
+    path.resolve(normalizedWorkdir, ...renderCloneLayout(cloneLayout, safeOrg, safeRepo)),
     'Repository path'
   );
   const repositoryPath = ensureInsideWorkdir(
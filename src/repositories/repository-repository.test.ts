@@ -1,15 +1,29 @@
 import assert from 'node:assert/strict';
 import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
 import type { Dirent, Stats } from 'node:fs';
 import { afterEach, describe, it, mock } from 'node:test';
 
 import {
   ensureRepository,
   cloneRepository,
+  cancelClone,
+  listActiveClones,
+  createCloneQueue,
+  getCloneQueueStats,
+  CloneCancelledError,
   discoverRepositories,
+  getRemoteSlug,
+  resolveRepositorySlug,
+  listRepositoryTree,
+  switchRepositoryBranch,
   __setRepositoryRepositoryTestOverrides,
 } from './repository-repository.js';
 import { RepositoryIdentifierError } from '../domain/index.js';
+import { configureCloneLayout } from './repository-paths.js';
+import { GitNotFoundError } from './git-repository.js';
+import { InvalidBranchNameError } from './worktree-repository.js';
 
 function createDirent(name: string, isDirectory: boolean): Dirent {
   return {
@@ -125,6 +139,64 @@ describe('repository-repository', () => {
       statMock.mock.restore();
     });
 
+    it('passes the configured git config to git clone as -c arguments', async () => {
+      mock.method(fs, 'mkdir', async () => undefined);
+      mock.method(fs, 'stat', async () => {
+        const error = new Error('missing') as NodeJS.ErrnoException;
+        error.code = 'ENOENT';
+        throw error;
+      });
+      const calls: string[][] = [];
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: async (args) => {
+          calls.push(args);
+          return { stdout: '', stderr: '' };
+        },
+      });
+
+      await cloneRepository('/work', 'https://github.com/acme/demo.git', {
+        gitConfig: { 'http.proxy': 'http://proxy.internal:3128', 'http.sslVerify': 'false' },
+      });
+
+      assert.deepEqual(calls[0], [
+        'clone',
+        '-c',
+        'http.proxy=http://proxy.internal:3128',
+        '-c',
+        'http.sslVerify=false',
+        'https://github.com/acme/demo.git',
+        '/work/acme/demo/repository',
+      ]);
+    });
+
+    it('passes a partial clone filter to the git CLI even when libgit2 is selected', async () => {
+      mock.method(fs, 'mkdir', async () => undefined);
+      mock.method(fs, 'stat', async () => {
+        const error = new Error('missing') as NodeJS.ErrnoException;
+        error.code = 'ENOENT';
+        throw error;
+      });
+      const calls: string[][] = [];
+      const cloneWithLibgit2 = mock.fn(async () => {});
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: async (args) => {
+          calls.push(args);
+          return { stdout: '', stderr: '' };
+        },
+        cloneWithLibgit2,
+      });
+
+      await cloneRepository('/work', 'https://github.com/acme/demo.git', { filter: 'blob:none', backend: 'libgit2' });
+
+      assert.deepEqual(calls[0], [
+        'clone',
+        '--filter=blob:none',
+        'https://github.com/acme/demo.git',
+        '/work/acme/demo/repository',
+      ]);
+      assert.equal(cloneWithLibgit2.mock.callCount(), 0);
+    });
+
     it('wraps git clone failures with descriptive errors', async () => {
       mock.method(fs, 'mkdir', async () => undefined);
       mock.method(fs, 'stat', async () => {
@@ -151,6 +223,188 @@ describe('repository-repository', () => {
       );
     });
 
+    it('surfaces a missing git binary without wrapping it', async () => {
+      mock.method(fs, 'mkdir', async () => undefined);
+      mock.method(fs, 'stat', async () => {
+        const error = new Error('missing') as NodeJS.ErrnoException;
+        error.code = 'ENOENT';
+        throw error;
+      });
+
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: async () => {
+          throw new GitNotFoundError();
+        },
+      });
+
+      await assert.rejects(cloneRepository('/work', 'https://github.com/acme/demo.git'), GitNotFoundError);
+    });
+
+    it('clones in-process and records transfer progress when the libgit2 backend is selected', async () => {
+      mock.method(fs, 'mkdir', async () => undefined);
+      mock.method(fs, 'stat', async () => {
+        const error = new Error('missing') as NodeJS.ErrnoException;
+        error.code = 'ENOENT';
+        throw error;
+      });
+      const executeGitCommand = mock.fn(async () => ({ stdout: '', stderr: '' }));
+      let progressSeen: unknown = null;
+      const cloneWithLibgit2 = mock.fn(async ({ onProgress }: { onProgress?: (progress: unknown) => void }) => {
+        onProgress?.({ receivedObjects: 5, totalObjects: 10, receivedBytes: 2048 });
+        progressSeen = listActiveClones()[0]?.progress;
+      });
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: executeGitCommand as never,
+        cloneWithLibgit2: cloneWithLibgit2 as never,
+      });
+
+      await cloneRepository('/work', 'https://github.com/acme/demo.git', { cloneId: 'clone-1', backend: 'libgit2' });
+
+      assert.equal(executeGitCommand.mock.calls.length, 0);
+      const { url, repositoryPath } = cloneWithLibgit2.mock.calls[0]?.arguments[0] as { url: string; repositoryPath: string };
+      assert.deepEqual({ url, repositoryPath }, {
+        url: 'https://github.com/acme/demo.git',
+        repositoryPath: '/work/acme/demo/repository',
+      });
+      assert.deepEqual(progressSeen, { receivedObjects: 5, totalObjects: 10, receivedBytes: 2048 });
+    });
+
+    it('cancels a running clone and removes its partial directory', async () => {
+      const workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-clone-'));
+      const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+      let markStarted: () => void = () => {};
+      const started = new Promise<void>((resolve) => {
+        markStarted = resolve;
+      });
+
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: async (_args, options) => {
+          await fs.mkdir(path.join(repositoryPath, '.git'), { recursive: true });
+          await fs.writeFile(path.join(repositoryPath, '.git', 'HEAD'), 'ref: refs/heads/main\n');
+          markStarted();
+          return await new Promise((_resolve, reject) => {
+            options?.signal?.addEventListener('abort', () => reject(new Error('The operation was aborted')));
+          });
+        },
+      });
+
+      try {
+        const clone = cloneRepository(workdir, 'https://github.com/acme/demo.git', { cloneId: 'clone-1' });
+        await started;
+
+        assert.deepEqual(
+          listActiveClones().map(({ cloneId, org, repo }) => ({ cloneId, org, repo })),
+          [{ cloneId: 'clone-1', org: 'acme', repo: 'demo' }],
+        );
+        assert.equal(cancelClone('clone-1'), true);
+        await assert.rejects(clone, (error: unknown) => {
+          assert.ok(error instanceof CloneCancelledError);
+          assert.equal(error.statusCode, 409);
+          assert.equal(error.code, 'clone_cancelled');
+          return true;
+        });
+
+        await assert.rejects(fs.access(repositoryPath), { code: 'ENOENT' });
+        assert.deepEqual(listActiveClones(), []);
+        assert.equal(cancelClone('clone-1'), false);
+      } finally {
+        await fs.rm(workdir, { recursive: true, force: true });
+      }
+    });
+
+    describe('clone queue', () => {
+      const pendingClones: Array<() => void> = [];
+
+      function queueClones(): ReturnType<typeof mock.fn> {
+        mock.method(fs, 'mkdir', async () => undefined);
+        mock.method(fs, 'stat', async () => {
+          const error = new Error('missing') as NodeJS.ErrnoException;
+          error.code = 'ENOENT';
+          throw error;
+        });
+        const executeMock = mock.fn(
+          async () =>
+            await new Promise<{ stdout: string; stderr: string }>((resolve) => {
+              pendingClones.push(() => resolve({ stdout: '', stderr: '' }));
+            })
+        );
+        __setRepositoryRepositoryTestOverrides({ executeGitCommand: executeMock as never });
+        return executeMock;
+      }
+
+      const settle = async (): Promise<void> => {
+        await new Promise((resolve) => setImmediate(resolve));
+      };
+
+      afterEach(() => {
+        pendingClones.length = 0;
+      });
+
+      it('makes a third concurrent clone wait for a free slot', async () => {
+        const executeMock = queueClones();
+        const queue = createCloneQueue();
+
+        const clones = ['one', 'two', 'three'].map((repo) =>
+          cloneRepository('/work', `https://github.com/acme/${repo}.git`, { cloneId: `clone-${repo}`, queue })
+        );
+        await settle();
+
+        assert.equal(executeMock.mock.callCount(), 2);
+        assert.deepEqual(getCloneQueueStats(queue), { maxConcurrent: 2, running: 2, queued: 1 });
+        assert.deepEqual(
+          listActiveClones().map(({ cloneId, state }) => ({ cloneId, state })),
+          [
+            { cloneId: 'clone-one', state: 'running' },
+            { cloneId: 'clone-two', state: 'running' },
+            { cloneId: 'clone-three', state: 'queued' },
+          ]
+        );
+
+        pendingClones.shift()?.();
+        await settle();
+        assert.equal(executeMock.mock.callCount(), 3);
+        assert.deepEqual(getCloneQueueStats(queue), { maxConcurrent: 2, running: 2, queued: 0 });
+
+        pendingClones.splice(0).forEach((finish) => finish());
+        assert.deepEqual(
+          (await Promise.all(clones)).map(({ repo }) => repo),
+          ['one', 'two', 'three']
+        );
+        assert.deepEqual(getCloneQueueStats(queue), { maxConcurrent: 2, running: 0, queued: 0 });
+      });
+
+      it('drops a queued clone when its request goes away or it is cancelled', async () => {
+        const queue = createCloneQueue(1);
+        const executeMock = queueClones();
+        const rm = mock.method(fs, 'rm', async () => undefined);
+
+        const running = cloneRepository('/work', 'https://github.com/acme/one.git', { cloneId: 'clone-one', queue });
+        const request = new AbortController();
+        const disconnected = cloneRepository('/work', 'https://github.com/acme/two.git', {
+          cloneId: 'clone-two',
+          signal: request.signal,
+          queue,
+        });
+        const cancelled = cloneRepository('/work', 'https://github.com/acme/three.git', {
+          cloneId: 'clone-three',
+          queue,
+        });
+        await settle();
+
+        request.abort();
+        assert.equal(cancelClone('clone-three'), true);
+        await assert.rejects(disconnected, CloneCancelledError);
+        await assert.rejects(cancelled, CloneCancelledError);
+        assert.deepEqual(getCloneQueueStats(queue), { maxConcurrent: 1, running: 1, queued: 0 });
+
+        pendingClones.shift()?.();
+        await running;
+        assert.equal(executeMock.mock.callCount(), 1);
+        // Queued clones never created a directory, so there is nothing to remove
+        assert.equal(rm.mock.callCount(), 0);
+      });
+    });
+
     it('rejects repository URLs that attempt traversal before creating directories', async () => {
       const mkdirMock = mock.method(fs, 'mkdir', async () => {
         throw new Error('should not attempt mkdir');
@@ -170,6 +424,142 @@ describe('repository-repository', () => {
     });
   });
 
+  describe('getRemoteSlug', () => {
+    function useOrigin(url: string | null) {
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: async (args: string[]) => {
+          assert.deepEqual(args, ['-C', '/work/acme/demo/repository', 'remote', 'get-url', 'origin']);
+          if (url === null) {
+            throw new Error("error: No such remote 'origin'");
+          }
+          return { stdout: `${url}\n`, stderr: '' };
+        },
+      });
+    }
+
+    it('parses https origin URLs', async () => {
+      useOrigin('https://github.com/Upstream-Org/real-name.git');
+      assert.deepEqual(await getRemoteSlug('/work/acme/demo/repository'), {
+        org: 'Upstream-Org',
+        repo: 'real-name',
+      });
+    });
+
+    it('parses scp-style and ssh:// origin URLs', async () => {
+      useOrigin('git@github.com:upstream-org/real-name.git');
+      assert.deepEqual(await getRemoteSlug('/work/acme/demo/repository'), {
+        org: 'upstream-org',
+        repo: 'real-name',
+      });
+
+      useOrigin('ssh://git@github.com/upstream-org/other.git');
+      assert.deepEqual(await getRemoteSlug('/work/acme/demo/repository'), {
+        org: 'upstream-org',
+        repo: 'other',
+      });
+    });
+
+    it('falls back to directory names without an origin remote', async () => {
+      useOrigin(null);
+      assert.equal(await getRemoteSlug('/work/acme/demo/repository'), null);
+      assert.deepEqual(await resolveRepositorySlug('/work/acme/demo/repository', 'acme', 'demo'), {
+        org: 'acme',
+        repo: 'demo',
+      });
+    });
+  });
+
+  describe('listRepositoryTree', () => {
+    it('groups repository names by organisation without running git', async () => {
+      mock.method(fs, 'readdir', async (targetPath: string) => {
+        if (targetPath === '/work') {
+          return [createDirent('zeta', true), createDirent('acme', true)];
+        }
+        if (targetPath === '/work/acme') {
+          return [createDirent('web', true), createDirent('api', true)];
+        }
+        if (targetPath === '/work/zeta') {
+          return [createDirent('tools', true)];
+        }
+        throw new Error(`Unexpected readdir target: ${targetPath}`);
+      });
+      mock.method(fs, 'lstat', async () => createStats(true));
+
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: async () => {
+          throw new Error('git should not run');
+        },
+        listWorktrees: async () => {
+          throw new Error('worktrees should not be listed');
+        },
+      });
+
+      const tree = await listRepositoryTree('/work');
+
+      assert.deepEqual(tree, [
+        { org: 'acme', repos: ['api', 'web'] },
+        { org: 'zeta', repos: ['tools'] },
+      ]);
+    });
+
+    it('skips symlinked directories instead of following them', async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-symlinks-'));
+      try {
+        const workdir = path.join(root, 'work');
+        const outside = path.join(root, 'outside');
+        await fs.mkdir(path.join(workdir, 'acme', 'demo', 'repository'), { recursive: true });
+        await fs.mkdir(path.join(outside, 'secret', 'repository'), { recursive: true });
+        await fs.mkdir(path.join(workdir, 'acme', 'escape'), { recursive: true });
+        // A loop back to the work directory, a repository linked elsewhere, and a checkout that escapes it
+        await fs.symlink(workdir, path.join(workdir, 'loop'), 'dir');
+        await fs.symlink(path.join(workdir, 'acme', 'demo'), path.join(workdir, 'acme', 'alias'), 'dir');
+        await fs.symlink(path.join(outside, 'secret', 'repository'), path.join(workdir, 'acme', 'escape', 'repository'), 'dir');
+        await fs.symlink(outside, path.join(workdir, 'linked-org'), 'dir');
+
+        const tree = await listRepositoryTree(workdir);
+
+        assert.deepEqual(tree, [{ org: 'acme', repos: ['demo'] }]);
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+  });
+
+  describe('clone layout', () => {
+    afterEach(() => {
+      configureCloneLayout();
+    });
+
+    it('discovers a custom layout from directory names without running git', async () => {
+      configureCloneLayout('repos/{repo}/{org}');
+      mock.method(fs, 'readdir', async (targetPath: string) => {
+        if (targetPath === '/work/repos') {
+          return [createDirent('demo', true)];
+        }
+        assert.equal(targetPath, '/work/repos/demo');
+        return [createDirent('acme', true), createDirent('scratch', true)];
+      });
+      mock.method(fs, 'lstat', async (targetPath: string) => {
+        if (['/work/repos', '/work/repos/demo/acme/repository'].includes(targetPath)) {
+          return createStats(true);
+        }
+        throw Object.assign(new Error('missing'), { code: 'ENOENT' });
+      });
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: async (args: string[]) => {
+          throw new Error(`Unexpected git call: ${args.join(' ')}`);
+        },
+        listWorktrees: async () => [{ branch: 'main', path: '/work/repos/demo/acme/repository' }],
+        getRepositoryInitCommand: async () => '',
+        loadRepositoryMetadata: async () => ({}),
+      });
+
+      const result = await discoverRepositories('/work');
+
+      assert.deepEqual(result, { acme: { demo: { branches: ['main'], initCommand: '', metadata: {} } } });
+    });
+  });
+
   describe('discoverRepositories', () => {
     it('returns repositories grouped by organisation with deduplicated branches', async () => {
       const readdirMock = mock.method(fs, 'readdir', async (targetPath: string) => {
@@ -184,7 +574,7 @@ describe('repository-repository', () => {
         throw new Error(`Unexpected readdir target: ${targetPath}`);
       });
 
-      const statMock = mock.method(fs, 'stat', async (targetPath: string) => {
+      const statMock = mock.method(fs, 'lstat', async (targetPath: string) => {
         if (targetPath === '/work/acme/demo/repository') {
           return createStats(true);
         }
@@ -207,9 +597,15 @@ describe('repository-repository', () => {
         return 'pnpm install';
       });
 
+      const loadMetadataMock = mock.fn(async (org: string, repo: string) => {
+        assert.deepEqual([org, repo], ['acme', 'demo']);
+        return { team: 'platform', priority: 'high' };
+      });
+
       __setRepositoryRepositoryTestOverrides({
         listWorktrees: listWorktreesMock,
         getRepositoryInitCommand: getRepoInitMock,
+        loadRepositoryMetadata: loadMetadataMock,
       });
 
       const result = await discoverRepositories('/work');
@@ -219,6 +615,7 @@ describe('repository-repository', () => {
           demo: {
             branches: ['main', 'feature/login'],
             initCommand: 'pnpm install',
+            metadata: { team: 'platform', priority: 'high' },
           },
         },
       });
@@ -229,6 +626,52 @@ describe('repository-repository', () => {
       assert.equal(getRepoInitMock.mock.callCount(), 1);
     });
 
+    it('runs lookups concurrently while keeping org and repo order sorted', async () => {
+      mock.method(fs, 'readdir', async (targetPath: string) => {
+        if (targetPath === '/work') {
+          return [createDirent('zeta', true), createDirent('acme', true)];
+        }
+        if (targetPath === '/work/acme') {
+          return [createDirent('web', true), createDirent('api', true), createDirent('cli', true)];
+        }
+        if (targetPath === '/work/zeta') {
+          return [createDirent('tools', true)];
+        }
+        throw new Error(`Unexpected readdir target: ${targetPath}`);
+      });
+      mock.method(fs, 'lstat', async () => createStats(true));
+
+      const delays: Record<string, number> = {
+        '/work/acme/api/repository': 20,
+        '/work/acme/cli/repository': 5,
+        '/work/acme/web/repository': 10,
+        '/work/zeta/tools/repository': 0,
+      };
+      let active = 0;
+      let peak = 0;
+
+      __setRepositoryRepositoryTestOverrides({
+        listWorktrees: async (repositoryPath: string) => {
+          active += 1;
+          peak = Math.max(peak, active);
+          await new Promise((resolve) => setTimeout(resolve, delays[repositoryPath] ?? 0));
+          active -= 1;
+          return [{ branch: repositoryPath.split('/')[3]!, path: repositoryPath }];
+        },
+        getRepositoryInitCommand: async () => '',
+        loadRepositoryMetadata: async () => ({}),
+      });
+
+      const result = await discoverRepositories('/work', { concurrency: 2 });
+
+      assert.deepEqual(Object.keys(result), ['acme', 'zeta']);
+      assert.deepEqual(Object.keys(result['acme'] ?? {}), ['api', 'cli', 'web']);
+      assert.deepEqual(result['acme']?.['api']?.branches, ['api']);
+      assert.deepEqual(result['acme']?.['web']?.branches, ['web']);
+      assert.deepEqual(result['zeta']?.['tools']?.branches, ['tools']);
+      assert.equal(peak, 2);
+    });
+
     it('returns empty map when workdir is missing', async () => {
       mock.method(fs, 'readdir', async () => {
         const error = new Error('missing') as NodeJS.ErrnoException;
@@ -240,4 +683,56 @@ describe('repository-repository', () => {
       assert.deepEqual(result, {});
     });
   });
+
+  describe('switchRepositoryBranch', () => {
+    it('switches a clean checkout and reports the new branch', async () => {
+      mock.method(fs, 'stat', async () => createStats(true));
+      const calls: string[][] = [];
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: async (args: string[]) => {
+          calls.push(args);
+          if (args.includes('symbolic-ref')) {
+            return { stdout: 'develop\n', stderr: '' };
+          }
+          if (args.includes('rev-parse')) {
+            return { stdout: 'abc1234\n', stderr: '' };
+          }
+          return { stdout: '', stderr: '' };
+        },
+      });
+
+      const result = await switchRepositoryBranch('/work', 'acme', 'demo', 'develop');
+
+      assert.deepEqual(result, {
+        org: 'acme',
+        repo: 'demo',
+        branch: 'develop',
+        detached: false,
+        head: 'abc1234',
+      });
+      assert.ok(
+        calls.some((args) => args.join(' ') === '-C /work/acme/demo/repository switch develop')
+      );
+    });
+
+    it('refuses branch names git would read as options without running git', async () => {
+      mock.method(fs, 'stat', async () => createStats(true));
+      const calls: string[][] = [];
+      __setRepositoryRepositoryTestOverrides({
+        executeGitCommand: async (args: string[]) => {
+          calls.push(args);
+          return { stdout: '', stderr: '' };
+        },
+      });
+
+      for (const branch of ['--orphan=takeover', '-c', 'feature..x']) {
+        await assert.rejects(switchRepositoryBranch('/work', 'acme', 'demo', branch), (error: unknown) => {
+          assert.ok(error instanceof InvalidBranchNameError);
+          assert.equal(error.statusCode, 400);
+          return true;
+        });
+      }
+      assert.deepEqual(calls, []);
+    });
+  });
 });
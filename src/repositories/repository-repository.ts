@@ -1,11 +1,30 @@
 import fs from 'node:fs/promises';
 import path from 'node:path';
-import { executeGitCommand, extractGitErrorMessage, GIT_BUFFER_SIZES } from './git-repository.js';
-import { listWorktrees } from './worktree-repository.js';
-import { parseRepositoryUrl } from '../domain/index.js';
+import {
+  executeGitCommand,
+  extractGitErrorMessage,
+  GIT_BUFFER_SIZES,
+  gitConfigArgs,
+  GitNotFoundError,
+} from './git-repository.js';
+import { InvalidBranchNameError, listWorktrees } from './worktree-repository.js';
+import {
+  findBranchNameProblems,
+  normalizeBranchName,
+  parseRepositoryUrl,
+  type CloneFilter,
+} from '../domain/index.js';
 import { getRepositoryInitCommand } from '../core/repository-config.js';
 import { normaliseInitCommand, setRepositoryInitCommand } from '../core/repository-config.js';
-import { resolveRepositoryPaths, type RepositoryPaths } from './repository-paths.js';
+import { loadRepositoryMetadata } from '../core/repository-metadata.js';
+import { getCloneLayout, resolveRepositoryPaths, type RepositoryPaths } from './repository-paths.js';
+import { ConcurrencyLimiter, mapWithConcurrency } from '../utils/concurrency.js';
+import {
+  cloneWithLibgit2,
+  CloneBackendUnavailableError,
+  type CloneBackendName,
+  type CloneProgress,
+} from './clone-backend.js';
 
 export interface CloneResult {
   org: string;
@@ -14,6 +33,63 @@ export interface CloneResult {
 
 export interface CloneOptions {
   initCommand?: string;
+  allowedSchemes?: readonly string[];
+  /**
+   * Registers the clone under this id so it can be listed and cancelled while git runs
+   */
+  cloneId?: string;
+  /**
+   * Aborting it drops the clone while it is still waiting in the clone queue
+   */
+  signal?: AbortSignal;
+  /**
+   * Makes a partial clone that fetches file contents (or trees too) lazily, passed as `--filter`
+   */
+  filter?: CloneFilter;
+  /**
+   * Organization used for URLs that name only a repository (`https://host/repo.git`); such URLs are
+   * rejected without one
+   */
+  defaultOrg?: string | null;
+  /**
+   * Git config passed with `-c` to `git clone`, such as `http.proxy`, and so stored in the new clone
+   */
+  gitConfig?: Record<string, string> | null;
+  /**
+   * Queue shared by the clones that count towards one limit (a queue of DEFAULT_MAX_CONCURRENT_CLONES
+   * when omitted)
+   */
+  queue?: ConcurrencyLimiter;
+  /**
+   * Clones through the git CLI (default) or in-process through libgit2; partial clones always use the CLI
+   */
+  backend?: CloneBackendName | null;
+}
+
+/**
+ * Server-wide clone settings, passed down by the services that clone
+ */
+export type CloneSettings = Pick<CloneOptions, 'defaultOrg' | 'gitConfig' | 'queue' | 'backend'>;
+
+export interface ActiveClone {
+  cloneId: string;
+  org: string;
+  repo: string;
+  startedAt: string;
+  /**
+   * `queued` while waiting for a free clone slot, `running` once git has started
+   */
+  state: 'queued' | 'running';
+  /**
+   * Transfer progress reported by the libgit2 backend; null until a report arrives and with the git CLI
+   */
+  progress: CloneProgress | null;
+}
+
+export interface CloneQueueStats {
+  maxConcurrent: number;
+  running: number;
+  queued: number;
 }
 
 export interface RepositoriesMap {
@@ -21,24 +97,139 @@ export interface RepositoriesMap {
     [repo: string]: {
       branches: string[];
       initCommand: string;
+      /**
+       * User-defined labels from the repository metadata store
+       */
+      metadata: Record<string, string>;
     };
   };
 }
 
+export interface RepositoryBranchInfo {
+  org: string;
+  repo: string;
+  branch: string | null;
+  detached: boolean;
+  head: string | null;
+}
+
+/**
+ * Error raised when a checkout cannot change branches because it has uncommitted changes
+ */
+export class RepositoryDirtyError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'working_tree_dirty';
+  public readonly details: { files: string[] };
+
+  constructor(org: string, repo: string, files: string[]) {
+    super(`Working tree for ${org}/${repo} has uncommitted changes`);
+    this.name = 'RepositoryDirtyError';
+    this.details = { files };
+  }
+}
+
+/**
+ * Error raised when a clone is cancelled before git finishes
+ */
+export class CloneCancelledError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'clone_cancelled';
+
+  constructor(cloneId: string, org: string, repo: string) {
+    super(`Clone ${cloneId} of ${org}/${repo} was cancelled`);
+    this.name = 'CloneCancelledError';
+  }
+}
+
+/**
+ * Error raised when a clone id is reused while the first clone is still running
+ */
+export class CloneInProgressError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'clone_in_progress';
+
+  constructor(cloneId: string) {
+    super(`A clone with id ${cloneId} is already running`);
+    this.name = 'CloneInProgressError';
+  }
+}
+
+/**
+ * Default number of clones allowed to run at once; later clones wait in a queue
+ */
+export const DEFAULT_MAX_CONCURRENT_CLONES = 2;
+
+const activeClones = new Map<string, ActiveClone & { controller: AbortController }>();
+const defaultCloneQueue = new ConcurrencyLimiter(DEFAULT_MAX_CONCURRENT_CLONES);
+
+/**
+ * Creates the queue that limits how many clones run at once; later clones wait for a slot
+ * @param maxConcurrent - Clones allowed to run at once; a missing or invalid value gives the default
+ * @returns Queue to pass to every clone that counts towards the limit
+ */
+export function createCloneQueue(maxConcurrent?: number | null): ConcurrencyLimiter {
+  return new ConcurrencyLimiter(
+    typeof maxConcurrent === 'number' && Number.isInteger(maxConcurrent) && maxConcurrent > 0
+      ? maxConcurrent
+      : DEFAULT_MAX_CONCURRENT_CLONES
+  );
+}
+
+/**
+ * Reports the clone limit and how many clones are running or waiting for a slot
+ * @param queue - Queue the clones were made through
+ */
+export function getCloneQueueStats(queue: ConcurrencyLimiter = defaultCloneQueue): CloneQueueStats {
+  return { maxConcurrent: queue.maxConcurrent, running: queue.active, queued: queue.pending };
+}
+
+/**
+ * Lists clones that are queued or running, oldest first
+ */
+export function listActiveClones(): ActiveClone[] {
+  return Array.from(activeClones.values(), ({ cloneId, org, repo, startedAt, state, progress }) => ({
+    cloneId,
+    org,
+    repo,
+    startedAt,
+    state,
+    progress,
+  }));
+}
+
+/**
+ * Kills a running clone's git process, or drops it from the clone queue; the clone then removes any
+ * partial directory and rejects with CloneCancelledError
+ * @param cloneId - Id the clone was registered under
+ * @returns False when no clone with that id is running
+ */
+export function cancelClone(cloneId: string): boolean {
+  const entry = activeClones.get(cloneId);
+  if (!entry) {
+    return false;
+  }
+  entry.controller.abort();
+  return true;
+}
+
 type RepositoryRepositoryDependencyOverrides = Partial<{
   executeGitCommand: typeof executeGitCommand;
   listWorktrees: typeof listWorktrees;
+  cloneWithLibgit2: typeof cloneWithLibgit2;
   getRepositoryInitCommand: typeof getRepositoryInitCommand;
   normaliseInitCommand: typeof normaliseInitCommand;
   setRepositoryInitCommand: typeof setRepositoryInitCommand;
+  loadRepositoryMetadata: typeof loadRepositoryMetadata;
 }>;
 
 const repositoryRepositoryDependencies = {
   executeGitCommand,
   listWorktrees,
+  cloneWithLibgit2,
   getRepositoryInitCommand,
   normaliseInitCommand,
   setRepositoryInitCommand,
+  loadRepositoryMetadata,
 } as const;
 
 let repositoryRepositoryTestOverrides: RepositoryRepositoryDependencyOverrides | null = null;
@@ -89,6 +280,159 @@ export async function ensureRepository(workdir: string, org: string, repo: strin
   return { repoRoot, repositoryPath };
 }
 
+/**
+ * Reports the branch currently checked out in the primary clone
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @returns Current branch details (branch is null when HEAD is detached)
+ */
+export async function getRepositoryBranch(
+  workdir: string,
+  org: string,
+  repo: string
+): Promise<RepositoryBranchInfo> {
+  const { repositoryPath } = await ensureRepository(workdir, org, repo);
+  const execGit = resolveRepositoryRepositoryDependency('executeGitCommand');
+
+  let head: string | null = null;
+  try {
+    const { stdout } = await execGit(['-C', repositoryPath, 'rev-parse', '--short', 'HEAD'], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+    head = stdout.trim() || null;
+  } catch {
+    head = null;
+  }
+
+  try {
+    const { stdout } = await execGit(['-C', repositoryPath, 'symbolic-ref', '--quiet', '--short', 'HEAD'], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+    return { org, repo, branch: stdout.trim() || null, detached: false, head };
+  } catch {
+    return { org, repo, branch: null, detached: true, head };
+  }
+}
+
+/**
+ * Switches the primary clone to another branch using `git switch`
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch to check out
+ * @returns Updated branch details
+ * @throws {InvalidBranchNameError} If git would refuse the branch name, such as one starting with -
+ * @throws {RepositoryDirtyError} If the working tree has uncommitted changes
+ */
+export async function switchRepositoryBranch(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<RepositoryBranchInfo> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+  // Names are checked up front so one like `--orphan=x` can never reach git as an option
+  if (findBranchNameProblems(branchName).length > 0) {
+    throw new InvalidBranchNameError(branchName);
+  }
+
+  const { repositoryPath } = await ensureRepository(workdir, org, repo);
+  const execGit = resolveRepositoryRepositoryDependency('executeGitCommand');
+
+  let statusOutput = '';
+  try {
+    const { stdout } = await execGit(['-C', repositoryPath, 'status', '--porcelain'], {
+      maxBuffer: GIT_BUFFER_SIZES.MEDIUM,
+    });
+    statusOutput = stdout;
+  } catch (error: unknown) {
+    throw new Error(`Failed to read repository status: ${extractGitErrorMessage(error)}`);
+  }
+
+  const dirtyFiles = statusOutput
+    .split('\n')
+    .filter((line) => line.trim())
+    .map((line) => line.slice(3).trim())
+    .filter(Boolean);
+
+  if (dirtyFiles.length > 0) {
+    throw new RepositoryDirtyError(org, repo, dirtyFiles);
+  }
+
+  try {
+    await execGit(['-C', repositoryPath, 'switch', branchName], {
+      maxBuffer: GIT_BUFFER_SIZES.MEDIUM,
+    });
+  } catch (error: unknown) {
+    throw new Error(`Failed to switch branch: ${extractGitErrorMessage(error)}`);
+  }
+
+  return await getRepositoryBranch(workdir, org, repo);
+}
+
+export interface RepositorySlug {
+  org: string;
+  repo: string;
+}
+
+const REMOTE_URL_SCHEMES = ['https', 'http', 'ssh', 'git', 'git@', 'file'];
+
+/**
+ * Reads the URL of the origin remote
+ * @param repositoryPath - Path to the repository checkout
+ * @returns The output of `git remote get-url origin`, or null when there is no origin
+ */
+export async function getRemoteUrl(repositoryPath: string): Promise<string | null> {
+  const execGit = resolveRepositoryRepositoryDependency('executeGitCommand');
+
+  try {
+    const { stdout } = await execGit(['-C', repositoryPath, 'remote', 'get-url', 'origin'], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+    return stdout.trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Reads the `owner/repo` slug from the origin remote, which may differ from the local directory names
+ * @param repositoryPath - Path to the repository checkout
+ * @returns The slug parsed from `git remote get-url origin`, or null when there is no usable origin
+ */
+export async function getRemoteSlug(repositoryPath: string): Promise<RepositorySlug | null> {
+  const remoteUrl = await getRemoteUrl(repositoryPath);
+  if (!remoteUrl) {
+    return null;
+  }
+
+  try {
+    const { org, repo } = parseRepositoryUrl(remoteUrl, { allowedSchemes: REMOTE_URL_SCHEMES });
+    return { org, repo };
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Resolves the slug to use for GitHub calls, preferring the origin remote over directory names
+ * @param repositoryPath - Path to the repository checkout
+ * @param org - Organization directory name (fallback)
+ * @param repo - Repository directory name (fallback)
+ * @returns The remote slug, or the directory names when no origin remote can be parsed
+ */
+export async function resolveRepositorySlug(
+  repositoryPath: string,
+  org: string,
+  repo: string
+): Promise<RepositorySlug> {
+  return (await getRemoteSlug(repositoryPath)) ?? { org, repo };
+}
+
 /**
  * Clones a repository
  * @param workdir - Work directory root
@@ -102,7 +446,10 @@ export async function cloneRepository(
   repositoryUrl: string,
   options: CloneOptions = {}
 ): Promise<CloneResult> {
-  const { org, repo, url } = parseRepositoryUrl(repositoryUrl);
+  const { org, repo, url } = parseRepositoryUrl(repositoryUrl, {
+    allowedSchemes: options.allowedSchemes,
+    defaultOrg: options.defaultOrg?.trim() || null,
+  });
   const { repoRoot, repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
 
   await fs.mkdir(repoRoot, { recursive: true });
@@ -120,14 +467,77 @@ export async function cloneRepository(
     }
   }
 
+  const { cloneId, signal: requestSignal } = options;
+  if (cloneId && activeClones.has(cloneId)) {
+    throw new CloneInProgressError(cloneId);
+  }
+  const controller = new AbortController();
+  const entry: ActiveClone & { controller: AbortController } = {
+    cloneId: cloneId ?? '',
+    org,
+    repo,
+    startedAt: new Date().toISOString(),
+    state: 'queued',
+    progress: null,
+    controller,
+  };
+  if (cloneId) {
+    activeClones.set(cloneId, entry);
+  }
+  // Registered before waiting for a slot, so queued clones can be listed and cancelled like running ones
+  const abortQueued = (): void => {
+    if (entry.state === 'queued') {
+      controller.abort();
+    }
+  };
+  requestSignal?.addEventListener('abort', abortQueued, { once: true });
+  if (requestSignal?.aborted) {
+    abortQueued();
+  }
+
+  let release: (() => void) | null = null;
   try {
-    const execGit = resolveRepositoryRepositoryDependency('executeGitCommand');
-    await execGit(['clone', url, repositoryPath], {
-      maxBuffer: GIT_BUFFER_SIZES.MEDIUM,
-    });
+    release = await (options.queue ?? defaultCloneQueue).acquire(controller.signal);
+    entry.state = 'running';
+    // libgit2 cannot make partial clones, so filtered clones always go through the git CLI
+    if (options.backend === 'libgit2' && !options.filter) {
+      const cloneInProcess = resolveRepositoryRepositoryDependency('cloneWithLibgit2');
+      await cloneInProcess({
+        url,
+        repositoryPath,
+        signal: controller.signal,
+        onProgress: (progress) => {
+          entry.progress = progress;
+        },
+      });
+    } else {
+      const execGit = resolveRepositoryRepositoryDependency('executeGitCommand');
+      // After `clone`, -c also writes the settings into the new clone so later fetches keep using them
+      const filterArgs = options.filter ? [`--filter=${options.filter}`] : [];
+      await execGit(['clone', ...gitConfigArgs(options.gitConfig), ...filterArgs, url, repositoryPath], {
+        maxBuffer: GIT_BUFFER_SIZES.MEDIUM,
+        signal: controller.signal,
+      });
+    }
   } catch (error: unknown) {
+    if (controller.signal.aborted) {
+      // The clone has stopped by now, so nothing is still writing into the directory
+      if (release) {
+        await fs.rm(repositoryPath, { recursive: true, force: true });
+      }
+      throw new CloneCancelledError(cloneId ?? 'request', org, repo);
+    }
+    if (error instanceof GitNotFoundError || error instanceof CloneBackendUnavailableError) {
+      throw error;
+    }
     const message = extractGitErrorMessage(error);
     throw new Error(`Failed to clone repository: ${message}`);
+  } finally {
+    release?.();
+    requestSignal?.removeEventListener('abort', abortQueued);
+    if (cloneId) {
+      activeClones.delete(cloneId);
+    }
   }
 
   if (options && Object.prototype.hasOwnProperty.call(options, 'initCommand')) {
@@ -146,59 +556,157 @@ export async function cloneRepository(
 }
 
 /**
- * Discovers all repositories in the work directory
- * @param workdir - Work directory root
- * @returns Nested object: {org: {repo: {branches, initCommand}}}
+ * Default number of repositories inspected concurrently during discovery
  */
-export async function discoverRepositories(workdir: string): Promise<RepositoriesMap> {
-  const result: RepositoriesMap = {};
-  const listWorktreesFn = resolveRepositoryRepositoryDependency('listWorktrees');
-  const getInitCommand = resolveRepositoryRepositoryDependency('getRepositoryInitCommand');
+export const DEFAULT_DISCOVERY_CONCURRENCY = 8;
 
-  let organisations;
+export interface DiscoverRepositoriesOptions {
+  /**
+   * Maximum number of repositories inspected at once; a missing or invalid value gives
+   * DEFAULT_DISCOVERY_CONCURRENCY
+   */
+  concurrency?: number | null;
+}
+
+function resolveDiscoveryConcurrency(concurrency?: number | null): number {
+  return typeof concurrency === 'number' && Number.isInteger(concurrency) && concurrency > 0
+    ? concurrency
+    : DEFAULT_DISCOVERY_CONCURRENCY;
+}
+
+/**
+ * Checks for a directory without following symlinks
+ */
+async function isRealDirectory(target: string): Promise<boolean> {
   try {
-    organisations = await fs.readdir(workdir, { withFileTypes: true });
-  } catch (error: unknown) {
-    const err = error as { code?: string };
-    if (err.code === 'ENOENT') {
-      return result;
-    }
-    throw error;
+    return (await fs.lstat(target)).isDirectory();
+  } catch {
+    return false;
   }
+}
 
-  for (const orgEntry of organisations) {
-    if (!orgEntry.isDirectory()) {
-      continue;
+interface DiscoveredRepository {
+  org: string;
+  repo: string;
+  repoRoot: string;
+  repositoryPath: string;
+}
+
+/**
+ * Collects repository checkouts under the work directory following the configured clone layout,
+ * sorted by org then repo. Names come from the directories alone, so discovery never runs git.
+ * Symlinked directories are never followed, at any level: a link could point back up the tree and make the
+ * walk loop, or expose a checkout that lives outside the work directory.
+ */
+async function collectRepositoryPaths(workdir: string): Promise<DiscoveredRepository[]> {
+  const layout = getCloneLayout();
+  const candidates: DiscoveredRepository[] = [];
+
+  async function walk(dirPath: string, depth: number, org: string | null, repo: string | null): Promise<void> {
+    const segment = layout.segments[depth];
+    if (!segment) {
+      if (org && repo) {
+        candidates.push({ org, repo, repoRoot: dirPath, repositoryPath: path.join(dirPath, 'repository') });
+      }
+      return;
     }
 
-    const orgName = orgEntry.name;
-    const orgPath = path.join(workdir, orgName);
-    let repoEntries;
+    if (segment.type === 'literal') {
+      const literalPath = path.join(dirPath, segment.value);
+      if (await isRealDirectory(literalPath)) {
+        await walk(literalPath, depth + 1, org, repo);
+      }
+      return;
+    }
 
+    let entries;
     try {
-      repoEntries = await fs.readdir(orgPath, { withFileTypes: true });
-    } catch {
-      continue;
+      entries = await fs.readdir(dirPath, { withFileTypes: true });
+    } catch (error: unknown) {
+      const err = error as { code?: string };
+      if (dirPath === workdir && err.code !== 'ENOENT') {
+        throw error;
+      }
+      return;
     }
 
-    for (const repoEntry of repoEntries) {
-      if (!repoEntry.isDirectory()) {
-        continue;
-      }
+    // Dirent types come from lstat, so symlinks to directories report isDirectory() false
+    const names = entries
+      .filter((entry) => entry.isDirectory())
+      .map((entry) => entry.name)
+      .sort((a, b) => a.localeCompare(b));
 
-      const repoName = repoEntry.name;
-      const repoRoot = path.join(orgPath, repoName);
-      const repositoryPath = path.join(repoRoot, 'repository');
+    for (const name of names) {
+      await walk(
+        path.join(dirPath, name),
+        depth + 1,
+        segment.type === 'org' ? name : org,
+        segment.type === 'repo' ? name : repo
+      );
+    }
+  }
 
-      try {
-        const stats = await fs.stat(repositoryPath);
-        if (!stats.isDirectory()) {
-          continue;
-        }
-      } catch {
-        continue;
-      }
+  await walk(workdir, 0, null, null);
+
+  const discovered: DiscoveredRepository[] = [];
+  for (const candidate of candidates) {
+    if (await isRealDirectory(candidate.repositoryPath)) {
+      discovered.push(candidate);
+    }
+  }
+
+  discovered.sort((a, b) => a.org.localeCompare(b.org) || a.repo.localeCompare(b.repo));
+
+  return discovered;
+}
+
+export interface RepositoryTreeEntry {
+  org: string;
+  repos: string[];
+}
+
+/**
+ * Lists organisations and repository names under the work directory without running git
+ * @param workdir - Work directory root
+ * @returns Organisations with their repository names, sorted alphabetically
+ */
+export async function listRepositoryTree(workdir: string): Promise<RepositoryTreeEntry[]> {
+  const repositories = await collectRepositoryPaths(workdir);
+  const tree: RepositoryTreeEntry[] = [];
+
+  repositories.forEach(({ org, repo }) => {
+    const last = tree[tree.length - 1];
+    if (last && last.org === org) {
+      last.repos.push(repo);
+    } else {
+      tree.push({ org, repos: [repo] });
+    }
+  });
+
+  return tree;
+}
 
+/**
+ * Discovers all repositories in the work directory.
+ * Repository paths are gathered first, then git lookups run with bounded concurrency.
+ * @param workdir - Work directory root
+ * @param options - Discovery options
+ * @returns Nested object: {org: {repo: {branches, initCommand, metadata}}}
+ */
+export async function discoverRepositories(
+  workdir: string,
+  options: DiscoverRepositoriesOptions = {}
+): Promise<RepositoriesMap> {
+  const result: RepositoriesMap = {};
+  const listWorktreesFn = resolveRepositoryRepositoryDependency('listWorktrees');
+  const getInitCommand = resolveRepositoryRepositoryDependency('getRepositoryInitCommand');
+  const loadMetadata = resolveRepositoryRepositoryDependency('loadRepositoryMetadata');
+
+  const repositories = await collectRepositoryPaths(workdir);
+  const details = await mapWithConcurrency(
+    repositories,
+    resolveDiscoveryConcurrency(options.concurrency),
+    async ({ org, repo, repoRoot, repositoryPath }) => {
       const worktrees = await listWorktreesFn(repositoryPath);
       const branches = Array.from(
         new Set(
@@ -214,18 +722,33 @@ export async function discoverRepositories(workdir: string): Promise<Repositorie
       } catch (error: unknown) {
         const err = error as { message?: string };
         console.warn(
-          `[agentrix] Failed to load repository config for ${orgName}/${repoName}:`,
+          `[agentrix] Failed to load repository config for ${org}/${repo}:`,
           err?.message || error
         );
         initCommand = '';
       }
 
-      if (!result[orgName]) {
-        result[orgName] = {};
+      let metadata: Record<string, string> = {};
+      try {
+        metadata = await loadMetadata(org, repo);
+      } catch (error: unknown) {
+        const err = error as { message?: string };
+        console.warn(
+          `[agentrix] Failed to load repository metadata for ${org}/${repo}:`,
+          err?.message || error
+        );
       }
-      result[orgName]![repoName] = { branches, initCommand };
+
+      return { branches, initCommand, metadata };
     }
-  }
+  );
+
+  repositories.forEach(({ org, repo }, index) => {
+    if (!result[org]) {
+      result[org] = {};
+    }
+    result[org]![repo] = details[index]!;
+  });
 
   return result;
 }
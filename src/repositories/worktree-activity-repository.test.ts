@@ -0,0 +1,79 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, beforeEach, describe, it } from 'node:test';
+
+import { getWorktreeActivity } from './worktree-activity-repository.js';
+import { RepositoryNotFoundError } from './worktree-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+
+async function commitAt(cwd: string, subject: string, date: string): Promise<string> {
+  await execFileAsync(
+    'git',
+    ['-C', cwd, '-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', subject],
+    { env: { ...process.env, GIT_AUTHOR_DATE: date, GIT_COMMITTER_DATE: date } }
+  );
+  return await git(cwd, ['rev-parse', 'HEAD']);
+}
+
+describe('getWorktreeActivity', () => {
+  let workdir: string;
+  let repositoryPath: string;
+
+  beforeEach(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-activity-'));
+    repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+  });
+
+  afterEach(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('merges the latest commits of every worktree newest first', async () => {
+    const seed = await commitAt(repositoryPath, 'Seed', '2026-01-01T09:00:00Z');
+    const loginPath = path.join(workdir, 'acme', 'demo', 'login');
+    const searchPath = path.join(workdir, 'acme', 'demo', 'search');
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', loginPath]);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/search', searchPath]);
+    const login1 = await commitAt(loginPath, 'Add login form', '2026-01-02T09:00:00Z');
+    const search1 = await commitAt(searchPath, 'Add search index', '2026-01-02T10:00:00Z');
+    const login2 = await commitAt(loginPath, 'Validate login', '2026-01-02T11:00:00Z');
+
+    const activity = await getWorktreeActivity(workdir, 'acme', 'demo');
+
+    assert.equal(activity.org, 'acme');
+    assert.equal(activity.repo, 'demo');
+    assert.deepEqual(
+      activity.commits.map(({ hash, subject, branch, alsoOn }) => ({ hash, subject, branch, alsoOn: [...alsoOn].sort() })),
+      [
+        { hash: login2, subject: 'Validate login', branch: 'feature/login', alsoOn: [] },
+        { hash: search1, subject: 'Add search index', branch: 'feature/search', alsoOn: [] },
+        { hash: login1, subject: 'Add login form', branch: 'feature/login', alsoOn: [] },
+        { hash: seed, subject: 'Seed', branch: 'main', alsoOn: ['feature/login', 'feature/search'] },
+      ]
+    );
+    assert.equal(activity.commits[0]?.author, 'Seed');
+    assert.equal(Date.parse(activity.commits[0]?.committedAt ?? ''), Date.parse('2026-01-02T11:00:00Z'));
+
+    const capped = await getWorktreeActivity(workdir, 'acme', 'demo', { perWorktree: 1, limit: 2 });
+    assert.deepEqual(
+      capped.commits.map((commit) => commit.hash),
+      [login2, search1]
+    );
+  });
+
+  it('returns an empty feed for a repository without commits', async () => {
+    assert.deepEqual(await getWorktreeActivity(workdir, 'acme', 'demo'), { org: 'acme', repo: 'demo', commits: [] });
+    await assert.rejects(
+      getWorktreeActivity(workdir, 'acme', 'missing'),
+      (error: unknown) => error instanceof RepositoryNotFoundError
+    );
+  });
+});
@@ -0,0 +1,154 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { executeGitCommandInRepo, GitNotFoundError, GIT_BUFFER_SIZES } from './git-repository.js';
+import { listWorktrees, RepositoryNotFoundError, type WorktreeEntry } from './worktree-repository.js';
+import { resolveRepositoryPaths } from './repository-paths.js';
+import { mapWithConcurrency } from '../utils/concurrency.js';
+
+/**
+ * Worktrees whose logs are read at once
+ */
+export const WORKTREE_ACTIVITY_CONCURRENCY = 4;
+
+/**
+ * Commits read from each worktree when the caller does not say
+ */
+export const DEFAULT_ACTIVITY_PER_WORKTREE = 5;
+
+/**
+ * Most commits read from a single worktree
+ */
+export const MAX_ACTIVITY_PER_WORKTREE = 50;
+
+/**
+ * Feed length when the caller does not say, and the most it can ask for
+ */
+export const DEFAULT_ACTIVITY_LIMIT = 50;
+export const MAX_ACTIVITY_LIMIT = 200;
+
+export interface WorktreeActivityCommit {
+  hash: string;
+  subject: string;
+  author: string;
+  /**
+   * ISO committer timestamp the feed is sorted by
+   */
+  committedAt: string;
+  /**
+   * Branch of the worktree the commit was found in
+   */
+  branch: string;
+  /**
+   * Other worktree branches whose recent history includes the same commit, such as the base two
+   * branches share
+   */
+  alsoOn: string[];
+}
+
+export interface WorktreeActivity {
+  org: string;
+  repo: string;
+  commits: WorktreeActivityCommit[];
+}
+
+export interface WorktreeActivityOptions {
+  /**
+   * Most recent commits read from each worktree
+   */
+  perWorktree?: number;
+  /**
+   * Longest feed returned
+   */
+  limit?: number;
+}
+
+const FIELD_SEPARATOR = '\x1f';
+
+function clamp(value: number | undefined, fallback: number, max: number): number {
+  if (value === undefined || !Number.isFinite(value)) {
+    return fallback;
+  }
+  return Math.min(Math.max(1, Math.floor(value)), max);
+}
+
+async function readRecentCommits(
+  worktreePath: string,
+  branch: string,
+  count: number
+): Promise<Omit<WorktreeActivityCommit, 'alsoOn'>[]> {
+  let stdout: string;
+  try {
+    ({ stdout } = await executeGitCommandInRepo(
+      worktreePath,
+      ['log', `-n${count}`, `--format=%H${FIELD_SEPARATOR}%cI${FIELD_SEPARATOR}%an${FIELD_SEPARATOR}%s`, 'HEAD', '--'],
+      { maxBuffer: GIT_BUFFER_SIZES.MEDIUM }
+    ));
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    // A branch without commits has no log, and a worktree removed mid-read has nothing to add
+    return [];
+  }
+
+  const commits: Omit<WorktreeActivityCommit, 'alsoOn'>[] = [];
+  for (const line of stdout.split('\n')) {
+    const [hash, committedAt, author = '', subject = ''] = line.split(FIELD_SEPARATOR);
+    if (hash && committedAt) {
+      commits.push({ hash, subject, author, committedAt, branch });
+    }
+  }
+  return commits;
+}
+
+/**
+ * Gathers the latest commits of every worktree of a repository, including the primary clone, into one
+ * feed sorted newest first. A commit reachable from several worktrees appears once, under the first
+ * worktree listed that has it, with the other branches in `alsoOn`. Detached worktrees are skipped.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param options - Per-worktree and overall caps
+ * @returns The merged feed; empty when no worktree has commits
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ */
+export async function getWorktreeActivity(
+  workdir: string,
+  org: string,
+  repo: string,
+  options: WorktreeActivityOptions = {}
+): Promise<WorktreeActivity> {
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  try {
+    await fs.access(repositoryPath);
+  } catch {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+
+  const perWorktree = clamp(options.perWorktree, DEFAULT_ACTIVITY_PER_WORKTREE, MAX_ACTIVITY_PER_WORKTREE);
+  const limit = clamp(options.limit, DEFAULT_ACTIVITY_LIMIT, MAX_ACTIVITY_LIMIT);
+  const primaryPath = path.resolve(repositoryPath);
+  // The primary clone goes first so shared history is attributed to the default branch
+  const entries = (await listWorktrees(repositoryPath))
+    .filter((entry): entry is WorktreeEntry & { path: string; branch: string } => Boolean(entry.path && entry.branch))
+    .sort((a, b) => Number(path.resolve(b.path) === primaryPath) - Number(path.resolve(a.path) === primaryPath));
+
+  const logs = await mapWithConcurrency(entries, WORKTREE_ACTIVITY_CONCURRENCY, (entry) =>
+    readRecentCommits(entry.path, entry.branch, perWorktree)
+  );
+
+  const byHash = new Map<string, WorktreeActivityCommit>();
+  for (const commit of logs.flat()) {
+    const existing = byHash.get(commit.hash);
+    if (!existing) {
+      byHash.set(commit.hash, { ...commit, alsoOn: [] });
+    } else if (existing.branch !== commit.branch && !existing.alsoOn.includes(commit.branch)) {
+      existing.alsoOn.push(commit.branch);
+    }
+  }
+
+  const commits = [...byHash.values()]
+    .sort((a, b) => Date.parse(b.committedAt) - Date.parse(a.committedAt) || a.hash.localeCompare(b.hash))
+    .slice(0, limit);
+  return { org, repo, commits };
+}
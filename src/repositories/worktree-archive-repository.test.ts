@@ -0,0 +1,91 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { gunzipSync } from 'node:zlib';
+import { after, before, describe, it } from 'node:test';
+
+import { createArchiveFileName, createWorktreeArchive } from './worktree-archive-repository.js';
+import type { WorktreeArchive } from './worktree-archive-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+
+async function collect(archive: WorktreeArchive): Promise<Buffer> {
+  const chunks: Buffer[] = [];
+  for await (const chunk of archive.stream) {
+    chunks.push(chunk as Buffer);
+  }
+  await archive.completed;
+  return Buffer.concat(chunks);
+}
+
+async function listEntries(gzipped: Buffer, root: string): Promise<string[]> {
+  const archivePath = path.join(root, `listing-${Date.now()}.tar.gz`);
+  await fs.writeFile(archivePath, gzipped);
+  const { stdout } = await execFileAsync('tar', ['-tzf', archivePath]);
+  await fs.rm(archivePath);
+  return stdout
+    .split('\n')
+    .map((entry) => entry.replace(/^\.\//, ''))
+    .filter((entry) => entry && !entry.endsWith('/'))
+    .sort();
+}
+
+describe('createWorktreeArchive', () => {
+  let workdir: string;
+
+  before(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-archive-'));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    const worktreePath = path.join(workdir, 'acme', 'demo', 'login');
+    await fs.mkdir(path.join(repositoryPath, 'src'), { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await fs.writeFile(path.join(repositoryPath, '.gitignore'), 'build/\n');
+    await fs.writeFile(path.join(repositoryPath, 'src', 'index.ts'), 'export {};\n');
+    await fs.writeFile(path.join(repositoryPath, 'removed.txt'), 'soon gone\n');
+    await git(repositoryPath, ['add', '-A']);
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', worktreePath]);
+
+    await fs.rm(path.join(worktreePath, 'removed.txt'));
+    await fs.writeFile(path.join(worktreePath, 'notes.md'), 'untracked\n');
+    await fs.mkdir(path.join(worktreePath, 'build'));
+    await fs.writeFile(path.join(worktreePath, 'build', 'out.js'), 'ignored\n');
+  });
+
+  after(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('streams a valid gzip of the files git would track', async () => {
+    const archive = await createWorktreeArchive(workdir, 'acme', 'demo', 'feature/login');
+    const gzipped = await collect(archive);
+
+    assert.equal(archive.fileName, 'demo-feature-login.tar.gz');
+    assert.deepEqual([...gzipped.subarray(0, 2)], [0x1f, 0x8b]);
+    assert.doesNotThrow(() => gunzipSync(gzipped));
+    assert.deepEqual(await listEntries(gzipped, workdir), ['.gitignore', 'notes.md', 'src/index.ts']);
+  });
+
+  it('includes ignored files but never .git when requested', async () => {
+    const archive = await createWorktreeArchive(workdir, 'acme', 'demo', 'feature/login', { includeIgnored: true });
+    const entries = await listEntries(await collect(archive), workdir);
+
+    assert.deepEqual(entries, ['.gitignore', 'build/out.js', 'notes.md', 'src/index.ts']);
+  });
+
+  it('rejects unknown worktrees before streaming', async () => {
+    await assert.rejects(createWorktreeArchive(workdir, 'acme', 'demo', 'missing'), /not found/i);
+  });
+});
+
+describe('createArchiveFileName', () => {
+  it('derives a header-safe name from the repository and branch', () => {
+    assert.equal(createArchiveFileName('demo', 'feature/login'), 'demo-feature-login.tar.gz');
+    assert.equal(createArchiveFileName('demo', 'fix "quotes"; and spaces'), 'demo-fix-quotes-and-spaces.tar.gz');
+    assert.equal(createArchiveFileName('', '..'), 'worktree.tar.gz');
+  });
+});
@@ -0,0 +1,146 @@
+import { spawn, type ChildProcess } from 'node:child_process';
+import { once } from 'node:events';
+import type { Readable } from 'node:stream';
+import { executeGitCommandInRepo, GIT_BUFFER_SIZES } from './git-repository.js';
+import { getWorktreePath } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+
+export interface WorktreeArchiveOptions {
+  /**
+   * Archive files excluded by `.gitignore` as well (the `.git` entry is always skipped)
+   */
+  includeIgnored?: boolean;
+}
+
+export interface WorktreeArchive {
+  /**
+   * Download name derived from the repository and branch, e.g. `demo-feature-login.tar.gz`
+   */
+  fileName: string;
+  /**
+   * Gzipped tar stream, produced while it is read
+   */
+  stream: Readable;
+  /**
+   * Resolves once the archiver exits successfully; rejects if it fails part way through
+   */
+  completed: Promise<void>;
+  /**
+   * Stops the archiver, e.g. when the client disconnects
+   */
+  abort: () => void;
+}
+
+/**
+ * Builds the download file name for a worktree archive
+ * @param repo - Repository name
+ * @param branch - Branch name
+ * @returns File name safe for a Content-Disposition header
+ */
+export function createArchiveFileName(repo: string, branch: string): string {
+  const slug = `${repo}-${branch}`
+    .replace(/[^A-Za-z0-9._-]+/g, '-')
+    .replace(/-{2,}/g, '-')
+    .replace(/^[-.]+|-+$/g, '');
+  return `${slug || 'worktree'}.tar.gz`;
+}
+
+/**
+ * Lists the files git would consider part of the worktree: tracked files still on disk plus
+ * untracked files that are not ignored
+ */
+async function listArchivableFiles(worktreePath: string): Promise<string[]> {
+  const listNul = async (args: string[]): Promise<string[]> => {
+    const { stdout } = await executeGitCommandInRepo(worktreePath, ['ls-files', '-z', ...args], {
+      maxBuffer: GIT_BUFFER_SIZES.XLARGE,
+    });
+    return stdout.split('\0').filter(Boolean);
+  };
+
+  const [candidates, deleted] = await Promise.all([
+    listNul(['--cached', '--others', '--exclude-standard']),
+    listNul(['--deleted']),
+  ]);
+  const deletedSet = new Set(deleted);
+  return Array.from(new Set(candidates)).filter((file) => !deletedSet.has(file));
+}
+
+function waitForExit(child: ChildProcess): Promise<void> {
+  let stderr = '';
+  child.stderr?.setEncoding('utf8');
+  child.stderr?.on('data', (chunk: string) => {
+    if (stderr.length < 4096) {
+      stderr += chunk;
+    }
+  });
+
+  return new Promise((resolve, reject) => {
+    child.once('error', reject);
+    child.once('close', (code, signal) => {
+      if (code === 0) {
+        resolve();
+        return;
+      }
+      const reason = stderr.trim() || (signal ? `terminated by ${signal}` : `exit code ${code}`);
+      reject(new Error(`Failed to archive worktree: ${reason}`));
+    });
+  });
+}
+
+/**
+ * Starts streaming a gzipped tar of a worktree. Only the file list is gathered up front; contents
+ * are read by `tar` as the stream is consumed, so memory use does not grow with the worktree.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param options - Archive options
+ * @returns Archive stream with its download name
+ * @throws {WorktreeNotFoundError} If the worktree does not exist
+ */
+export async function createWorktreeArchive(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  { includeIgnored = false }: WorktreeArchiveOptions = {}
+): Promise<WorktreeArchive> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('branch is required');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  const files = includeIgnored ? null : await listArchivableFiles(worktreePath);
+
+  const args = files
+    ? ['-czf', '-', '-C', worktreePath, '--null', '-T', '-']
+    : ['-czf', '-', '-C', worktreePath, '--exclude=./.git', '.'];
+
+  const child = spawn('tar', args, { stdio: ['pipe', 'pipe', 'pipe'] });
+  const completed = waitForExit(child);
+  // The caller awaits this after streaming; until then a failure must not surface as unhandled
+  completed.catch(() => {});
+  // Surface a missing tar binary as a normal error before any response headers are written
+  await Promise.race([once(child, 'spawn'), completed]);
+
+  child.stdin?.on('error', () => {
+    // tar exiting early is reported through `completed`
+  });
+  child.stdin?.end(files && files.length > 0 ? `${files.join('\0')}\0` : '');
+
+  if (!child.stdout) {
+    throw new Error('Failed to archive worktree: tar produced no output stream');
+  }
+
+  return {
+    fileName: createArchiveFileName(repo, branchName),
+    stream: child.stdout,
+    completed,
+    abort: () => {
+      if (child.exitCode === null) {
+        child.kill();
+      }
+    },
+  };
+}
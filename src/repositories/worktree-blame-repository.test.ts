@@ -0,0 +1,137 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { after, before, describe, it } from 'node:test';
+
+import {
+  blameWorktreeFile,
+  parseBlamePorcelain,
+  UNCOMMITTED_BLAME_COMMIT,
+  WorktreeFileUntrackedError,
+} from './worktree-blame-repository.js';
+import { WorktreeFileNotFoundError, WorktreeFilePathError } from './worktree-file-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+
+const FIRST = 'a'.repeat(40);
+const SECOND = 'b'.repeat(40);
+
+describe('parseBlamePorcelain', () => {
+  it('reuses commit details for lines whose header omits them', () => {
+    const output = [
+      `${FIRST} 1 1 2`,
+      'author Ada Lovelace',
+      'author-mail <ada@example.com>',
+      'author-time 1700000000',
+      'author-tz +0000',
+      'committer Ada Lovelace',
+      'summary Seed notes',
+      'boundary',
+      'filename notes.md',
+      '\t# Notes',
+      `${FIRST} 2 2`,
+      '\t',
+      `${SECOND} 3 3 1`,
+      'author Grace Hopper',
+      'author-mail <grace@example.com>',
+      'author-time 1700003600',
+      'author-tz -0500',
+      'summary Add\tdetails',
+      `previous ${FIRST} notes.md`,
+      'filename notes.md',
+      '\t\tindented text',
+      '',
+    ].join('\n');
+
+    assert.deepEqual(parseBlamePorcelain(output), [
+      {
+        line: 1,
+        content: '# Notes',
+        commit: FIRST,
+        author: 'Ada Lovelace',
+        authorEmail: 'ada@example.com',
+        authoredAt: '2023-11-14T22:13:20.000Z',
+        summary: 'Seed notes',
+      },
+      {
+        line: 2,
+        content: '',
+        commit: FIRST,
+        author: 'Ada Lovelace',
+        authorEmail: 'ada@example.com',
+        authoredAt: '2023-11-14T22:13:20.000Z',
+        summary: 'Seed notes',
+      },
+      {
+        line: 3,
+        content: '\tindented text',
+        commit: SECOND,
+        author: 'Grace Hopper',
+        authorEmail: 'grace@example.com',
+        authoredAt: '2023-11-14T23:13:20.000Z',
+        summary: 'Add\tdetails',
+      },
+    ]);
+  });
+
+  it('returns no lines for empty output', () => {
+    assert.deepEqual(parseBlamePorcelain(''), []);
+  });
+});
+
+describe('blameWorktreeFile', () => {
+  let workdir: string;
+
+  before(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-blame-'));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    const worktreePath = path.join(workdir, 'acme', 'demo', 'login');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await fs.writeFile(path.join(repositoryPath, 'notes.md'), 'one\ntwo\nthree\n');
+    await git(repositoryPath, ['add', '-A']);
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', worktreePath]);
+
+    await fs.writeFile(path.join(worktreePath, 'notes.md'), 'one\nTWO\nthree\n');
+    await fs.writeFile(path.join(worktreePath, 'draft.md'), 'untracked\n');
+  });
+
+  after(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('attributes committed and uncommitted lines within the requested range', async () => {
+    const blame = await blameWorktreeFile(workdir, 'acme', 'demo', 'feature/login', 'notes.md', { start: 2, end: 3 });
+
+    assert.equal(blame.path, 'notes.md');
+    assert.deepEqual(
+      blame.lines.map(({ line, content, author }) => ({ line, content, author })),
+      [
+        { line: 2, content: 'TWO', author: 'Not Committed Yet' },
+        { line: 3, content: 'three', author: 'Seed' },
+      ]
+    );
+    assert.equal(blame.lines[0]?.commit, UNCOMMITTED_BLAME_COMMIT);
+    assert.equal(blame.lines[1]?.summary, 'Seed');
+  });
+
+  it('distinguishes missing, untracked and escaping paths', async () => {
+    await assert.rejects(
+      blameWorktreeFile(workdir, 'acme', 'demo', 'feature/login', 'missing.md'),
+      WorktreeFileNotFoundError
+    );
+    await assert.rejects(
+      blameWorktreeFile(workdir, 'acme', 'demo', 'feature/login', 'draft.md'),
+      WorktreeFileUntrackedError
+    );
+    await assert.rejects(
+      blameWorktreeFile(workdir, 'acme', 'demo', 'feature/login', '../repository/notes.md'),
+      WorktreeFilePathError
+    );
+  });
+});
@@ -0,0 +1,204 @@
+import path from 'node:path';
+import { executeCappedGitCommandInRepo, GitCommandError } from './git-repository.js';
+import { resolveWorktreeFile } from './worktree-file-repository.js';
+
+/**
+ * Commit hash git blame reports for lines that are not committed yet
+ */
+export const UNCOMMITTED_BLAME_COMMIT = '0000000000000000000000000000000000000000';
+
+/**
+ * Error raised when a file exists in the worktree but git has no history for it
+ */
+export class WorktreeFileUntrackedError extends Error {
+  public readonly statusCode: number = 404;
+  public readonly code = 'file_not_tracked';
+
+  constructor(filePath: string) {
+    super(`File ${filePath} is not tracked in this worktree`);
+    this.name = 'WorktreeFileUntrackedError';
+  }
+}
+
+/**
+ * Error raised when a requested line range does not fit the file
+ */
+export class BlameRangeError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'invalid_line_range';
+
+  constructor(message: string) {
+    super(message);
+    this.name = 'BlameRangeError';
+  }
+}
+
+export interface BlameLine {
+  /**
+   * 1-based line number in the worktree copy of the file
+   */
+  line: number;
+  content: string;
+  commit: string;
+  author: string | null;
+  authorEmail: string | null;
+  /**
+   * ISO timestamp of the authoring commit
+   */
+  authoredAt: string | null;
+  summary: string | null;
+}
+
+export interface WorktreeBlame {
+  path: string;
+  lines: BlameLine[];
+  /**
+   * True when git's output passed the byte limit, so only the leading lines of the range are listed
+   */
+  truncated: boolean;
+  /**
+   * Byte limit git's output was held to
+   */
+  limitBytes: number;
+}
+
+export interface BlameWorktreeFileOptions {
+  /**
+   * First line to blame, 1-based; defaults to the start of the file
+   */
+  start?: number;
+  /**
+   * Last line to blame, inclusive; defaults to the end of the file
+   */
+  end?: number;
+  /**
+   * Overrides the configured byte limit for git's output
+   */
+  maxBytes?: number;
+}
+
+interface BlameCommitInfo {
+  author: string | null;
+  authorEmail: string | null;
+  authoredAt: string | null;
+  summary: string | null;
+}
+
+const BLAME_HEADER_PATTERN = /^([0-9a-f]{40}|[0-9a-f]{64}) \d+ (\d+)(?: \d+)?$/;
+
+/**
+ * Parses `git blame --porcelain` output. Porcelain only prints a commit's metadata the first time
+ * the commit appears, so details are remembered per hash and reused for later lines.
+ * @param output - Raw porcelain output
+ * @returns One entry per blamed line, in file order
+ */
+export function parseBlamePorcelain(output: string): BlameLine[] {
+  const commits = new Map<string, BlameCommitInfo>();
+  const lines: BlameLine[] = [];
+  let current: { commit: string; line: number; info: BlameCommitInfo } | null = null;
+
+  for (const raw of output.split('\n')) {
+    if (!current) {
+      const match = BLAME_HEADER_PATTERN.exec(raw);
+      if (match) {
+        const commit = match[1] ?? '';
+        let info = commits.get(commit);
+        if (!info) {
+          info = { author: null, authorEmail: null, authoredAt: null, summary: null };
+          commits.set(commit, info);
+        }
+        current = { commit, line: Number(match[2]), info };
+      }
+      continue;
+    }
+
+    if (raw.startsWith('\t')) {
+      lines.push({ line: current.line, content: raw.slice(1), commit: current.commit, ...current.info });
+      current = null;
+      continue;
+    }
+
+    const separator = raw.indexOf(' ');
+    const key = separator === -1 ? raw : raw.slice(0, separator);
+    const value = separator === -1 ? '' : raw.slice(separator + 1);
+    switch (key) {
+      case 'author':
+        current.info.author = value;
+        break;
+      case 'author-mail':
+        current.info.authorEmail = value.replace(/^<|>$/g, '') || null;
+        break;
+      case 'author-time': {
+        const seconds = Number(value);
+        current.info.authoredAt = Number.isFinite(seconds) ? new Date(seconds * 1000).toISOString() : null;
+        break;
+      }
+      case 'summary':
+        current.info.summary = value;
+        break;
+      default:
+        break;
+    }
+  }
+
+  return lines;
+}
+
+function buildLineRange({ start, end }: BlameWorktreeFileOptions): string[] {
+  if (start === undefined && end === undefined) {
+    return [];
+  }
+  return ['-L', `${start ?? 1},${end ?? ''}`];
+}
+
+/**
+ * Runs git blame over a file in a worktree, including uncommitted changes
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param filePath - Path relative to the worktree root
+ * @param options - Optional line range and output limit
+ * @returns Per-line authorship
+ * @throws {WorktreeFilePathError} If the path is invalid or escapes the worktree
+ * @throws {WorktreeFileNotFoundError} If the file does not exist
+ * @throws {WorktreeFileUntrackedError} If git has no history for the file
+ * @throws {BlameRangeError} If the range starts past the end of the file
+ */
+export async function blameWorktreeFile(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  filePath: string,
+  options: BlameWorktreeFileOptions = {}
+): Promise<WorktreeBlame> {
+  const { root, relativePath, canonicalPath } = await resolveWorktreeFile(workdir, org, repo, branch, filePath);
+  // Blame the file the path resolves to, so symlinked paths report the history of their target
+  const trackedPath = path.relative(root, canonicalPath).split(path.sep).join('/');
+
+  let output: { stdout: string; truncated: boolean; limitBytes: number };
+  try {
+    output = await executeCappedGitCommandInRepo(
+      root,
+      ['blame', '--porcelain', ...buildLineRange(options), '--', trackedPath],
+      options.maxBytes !== undefined ? { maxBytes: options.maxBytes } : {}
+    );
+  } catch (error: unknown) {
+    const stderr = error instanceof GitCommandError ? error.stderr : '';
+    if (/no such path .* in HEAD/i.test(stderr)) {
+      throw new WorktreeFileUntrackedError(relativePath);
+    }
+    if (/has only \d+ lines?/i.test(stderr)) {
+      throw new BlameRangeError(`Line range is outside ${relativePath}`);
+    }
+    throw error;
+  }
+
+  return {
+    path: relativePath,
+    lines: parseBlamePorcelain(output.stdout),
+    truncated: output.truncated,
+    limitBytes: output.limitBytes,
+  };
+}
@@ -0,0 +1,113 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { describe, it } from 'node:test';
+
+import {
+  abortCherryPick,
+  CherryPickConflictError,
+  CherryPickStateError,
+  cherryPickWorktree,
+  InvalidCherryPickCommitError,
+} from './worktree-cherry-pick-repository.js';
+
+const execGit = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execGit('git', ['-C', cwd, ...args])).stdout.trim();
+
+/**
+ * Creates a temporary workdir holding a real acme/demo repository with a `feature` worktree checked out
+ */
+async function createWorkdirWithWorktree(): Promise<{ workdir: string; worktreePath: string }> {
+  const workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-cherry-pick-'));
+  const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+  const worktreePath = path.join(workdir, 'acme', 'demo', 'feature');
+  await fs.mkdir(repositoryPath, { recursive: true });
+  await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+  await git(repositoryPath, [
+    '-c',
+    'user.name=Seed',
+    '-c',
+    'user.email=seed@example.com',
+    'commit',
+    '--quiet',
+    '--allow-empty',
+    '-m',
+    'Initial commit',
+  ]);
+  await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature', worktreePath]);
+  return { workdir, worktreePath };
+}
+
+describe('cherryPickWorktree', () => {
+  const commitAs = ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet'];
+
+  async function commitFile(cwd: string, file: string, content: string, message: string): Promise<string> {
+    await fs.writeFile(path.join(cwd, file), content);
+    await git(cwd, ['add', file]);
+    await git(cwd, [...commitAs, '-m', message]);
+    return await git(cwd, ['rev-parse', 'HEAD']);
+  }
+
+  it('applies commits from another worktree as the configured identity', async () => {
+    const { workdir, worktreePath } = await createWorkdirWithWorktree();
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    try {
+      const picked = await commitFile(repositoryPath, 'fix.txt', 'fixed\n', 'Fix the thing');
+      const previousSha = await git(worktreePath, ['rev-parse', 'HEAD']);
+
+      const result = await cherryPickWorktree(workdir, 'acme', 'demo', 'feature', [picked.slice(0, 8)], {
+        identity: { name: 'Agent Bot', email: 'agent@example.com' },
+      });
+
+      assert.deepEqual(result, {
+        branch: 'feature',
+        previousSha,
+        sha: await git(worktreePath, ['rev-parse', 'HEAD']),
+        commits: [picked],
+      });
+      assert.equal(await git(worktreePath, ['log', '-1', '--format=%s|%cn']), 'Fix the thing|Agent Bot');
+      assert.equal(await fs.readFile(path.join(worktreePath, 'fix.txt'), 'utf8'), 'fixed\n');
+      await assert.rejects(
+        cherryPickWorktree(workdir, 'acme', 'demo', 'feature', ['deadbeef']),
+        InvalidCherryPickCommitError
+      );
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+
+  it('reports conflicts with their files and leaves the cherry-pick to abort', async () => {
+    const { workdir, worktreePath } = await createWorkdirWithWorktree();
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    try {
+      const clean = await commitFile(repositoryPath, 'fix.txt', 'fixed\n', 'Fix the thing');
+      const conflicting = await commitFile(repositoryPath, 'shared.txt', 'main\n', 'Main change');
+      const previousSha = await commitFile(worktreePath, 'shared.txt', 'feature\n', 'Feature change');
+
+      await assert.rejects(cherryPickWorktree(workdir, 'acme', 'demo', 'feature', [clean, conflicting]), (error: unknown) => {
+        assert.ok(error instanceof CherryPickConflictError);
+        assert.equal(error.statusCode, 409);
+        assert.equal(error.code, 'cherry_pick_conflict');
+        assert.deepEqual(error.details, { commit: conflicting, files: ['shared.txt'], applied: [clean] });
+        return true;
+      });
+      assert.equal(await git(worktreePath, ['rev-parse', 'CHERRY_PICK_HEAD']), conflicting);
+      await assert.rejects(
+        cherryPickWorktree(workdir, 'acme', 'demo', 'feature', [clean]),
+        (error: unknown) => error instanceof CherryPickStateError && error.code === 'cherry_pick_in_progress'
+      );
+
+      assert.deepEqual(await abortCherryPick(workdir, 'acme', 'demo', 'feature'), { branch: 'feature', sha: previousSha });
+      assert.equal(await git(worktreePath, ['status', '--porcelain']), '');
+      await assert.rejects(
+        abortCherryPick(workdir, 'acme', 'demo', 'feature'),
+        (error: unknown) => error instanceof CherryPickStateError && error.code === 'no_cherry_pick'
+      );
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+});
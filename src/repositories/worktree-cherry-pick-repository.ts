@@ -0,0 +1,207 @@
+import { executeGitCommandInRepo, extractGitErrorMessage, GIT_BUFFER_SIZES, GitNotFoundError } from './git-repository.js';
+import { getWorktreePath, identityConfigArgs, type GitIdentity } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+
+/**
+ * Error raised when a commit to cherry-pick does not resolve
+ */
+export class InvalidCherryPickCommitError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'invalid_commit';
+
+  constructor(commit: string) {
+    super(`Commit ${commit} does not resolve to a commit`);
+    this.name = 'InvalidCherryPickCommitError';
+  }
+}
+
+/**
+ * Error raised when a cherry-pick stops on a conflict. The worktree is left mid cherry-pick so the
+ * conflict can be resolved there, or the cherry-pick aborted.
+ */
+export class CherryPickConflictError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'cherry_pick_conflict';
+  public readonly details: { commit: string; files: string[]; applied: string[] };
+
+  constructor(branch: string, commit: string, files: string[], applied: string[]) {
+    super(`Cherry-picking ${commit} onto ${branch} stopped on a conflict; resolve it or abort the cherry-pick`);
+    this.name = 'CherryPickConflictError';
+    this.details = { commit, files, applied };
+  }
+}
+
+/**
+ * Error raised when a cherry-pick cannot start or stop because of the worktree's cherry-pick state
+ */
+export class CherryPickStateError extends Error {
+  public readonly statusCode: number = 409;
+
+  constructor(
+    message: string,
+    public readonly code: 'cherry_pick_in_progress' | 'no_cherry_pick'
+  ) {
+    super(message);
+    this.name = 'CherryPickStateError';
+  }
+}
+
+export interface CherryPickWorktreeResult {
+  branch: string;
+  previousSha: string;
+  sha: string;
+  /**
+   * Full SHAs of the commits that were picked, in the order they were applied
+   */
+  commits: string[];
+}
+
+/**
+ * Reads the commit a stopped cherry-pick is working on, or null when none is in progress
+ */
+async function readCherryPickHead(worktreePath: string): Promise<string | null> {
+  try {
+    const { stdout } = await executeGitCommandInRepo(worktreePath, ['rev-parse', '--verify', '--quiet', 'CHERRY_PICK_HEAD'], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+    return stdout.trim() || null;
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    return null;
+  }
+}
+
+export interface CherryPickWorktreeOptions {
+  /**
+   * Identity the picked commits are committed as, passed to git with `-c`
+   */
+  identity?: GitIdentity | null;
+}
+
+/**
+ * Applies commits from anywhere in the repository, such as another worktree's branch, to a worktree's
+ * branch with `git cherry-pick`, committing as the configured identity
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree receiving the commits
+ * @param commits - Commits to apply, in order
+ * @param options - Identity to commit as
+ * @returns The commits before and after, and the commits that were picked
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ * @throws {InvalidCherryPickCommitError} If a commit does not resolve
+ * @throws {CherryPickStateError} If the worktree is already in the middle of a cherry-pick
+ * @throws {CherryPickConflictError} If a commit conflicts; the worktree is left mid cherry-pick
+ */
+export async function cherryPickWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  commits: string[],
+  options: CherryPickWorktreeOptions = {}
+): Promise<CherryPickWorktreeResult> {
+  const branchName = normalizeBranchName(branch);
+
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+  if (commits.length === 0) {
+    throw new Error('At least one commit is required');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  const run = async (args: string[]): Promise<string> =>
+    (await executeGitCommandInRepo(worktreePath, args, { maxBuffer: GIT_BUFFER_SIZES.MEDIUM })).stdout;
+
+  if (await readCherryPickHead(worktreePath)) {
+    throw new CherryPickStateError(
+      `A cherry-pick is already in progress in ${branchName}; resolve or abort it first`,
+      'cherry_pick_in_progress'
+    );
+  }
+
+  const resolved: string[] = [];
+  for (const commit of commits) {
+    const trimmed = commit.trim();
+    // A leading dash would be read as an option by cherry-pick
+    if (!trimmed || trimmed.startsWith('-')) {
+      throw new InvalidCherryPickCommitError(commit);
+    }
+    try {
+      resolved.push((await run(['rev-parse', '--verify', '--quiet', `${trimmed}^{commit}`])).trim());
+    } catch (error: unknown) {
+      if (error instanceof GitNotFoundError) {
+        throw error;
+      }
+      throw new InvalidCherryPickCommitError(trimmed);
+    }
+  }
+
+  const previousSha = (await run(['rev-parse', 'HEAD'])).trim();
+  try {
+    await run([...identityConfigArgs(options.identity), 'cherry-pick', ...resolved]);
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    const stopped = await readCherryPickHead(worktreePath);
+    if (!stopped) {
+      throw new Error(`Failed to cherry-pick: ${extractGitErrorMessage(error)}`);
+    }
+    const conflicted = await run(['diff', '--name-only', '--diff-filter=U']);
+    const files = conflicted
+      .split('\n')
+      .map((line) => line.trim())
+      .filter(Boolean);
+    const stoppedAt = resolved.indexOf(stopped);
+    throw new CherryPickConflictError(branchName, stopped, files, stoppedAt > 0 ? resolved.slice(0, stoppedAt) : []);
+  }
+
+  const sha = (await run(['rev-parse', 'HEAD'])).trim();
+  return { branch: branchName, previousSha, sha, commits: resolved };
+}
+
+/**
+ * Abandons a cherry-pick that stopped on a conflict, returning the branch to where it was before the
+ * cherry-pick started, including any commits it had already applied
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @returns The commit the branch is back at
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ * @throws {CherryPickStateError} If no cherry-pick is in progress
+ */
+export async function abortCherryPick(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<{ branch: string; sha: string }> {
+  const branchName = normalizeBranchName(branch);
+
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  if (!(await readCherryPickHead(worktreePath))) {
+    throw new CherryPickStateError(`No cherry-pick is in progress in ${branchName}`, 'no_cherry_pick');
+  }
+
+  try {
+    await executeGitCommandInRepo(worktreePath, ['cherry-pick', '--abort'], { maxBuffer: GIT_BUFFER_SIZES.MEDIUM });
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    throw new Error(`Failed to abort cherry-pick: ${extractGitErrorMessage(error)}`);
+  }
+  const { stdout } = await executeGitCommandInRepo(worktreePath, ['rev-parse', 'HEAD'], {
+    maxBuffer: GIT_BUFFER_SIZES.SMALL,
+  });
+  return { branch: branchName, sha: stdout.trim() };
+}
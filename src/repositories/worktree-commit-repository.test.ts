@@ -0,0 +1,154 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, describe, it } from 'node:test';
+
+import {
+  commitWorktree,
+  NothingToCommitError,
+  SigningKeyNotConfiguredError,
+} from './worktree-commit-repository.js';
+import { __setGitRepositoryTestOverrides } from './git-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+
+/**
+ * Creates a temporary workdir holding a real acme/demo repository with a `feature` worktree checked out
+ */
+async function createWorkdirWithWorktree(): Promise<{ workdir: string; worktreePath: string }> {
+  const workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-commit-'));
+  const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+  const worktreePath = path.join(workdir, 'acme', 'demo', 'feature');
+  await fs.mkdir(repositoryPath, { recursive: true });
+  await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+  await git(repositoryPath, [
+    '-c',
+    'user.name=Seed',
+    '-c',
+    'user.email=seed@example.com',
+    'commit',
+    '--quiet',
+    '--allow-empty',
+    '-m',
+    'Initial commit',
+  ]);
+  await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature', worktreePath]);
+  return { workdir, worktreePath };
+}
+
+afterEach(() => {
+  __setGitRepositoryTestOverrides();
+});
+
+describe('commitWorktree', () => {
+  it('stages and commits changes with the configured identity', async () => {
+    const { workdir, worktreePath } = await createWorkdirWithWorktree();
+    try {
+      await fs.writeFile(path.join(worktreePath, 'notes.md'), 'hello\n');
+      await fs.writeFile(path.join(worktreePath, 'scratch.txt'), 'keep out\n');
+
+      const result = await commitWorktree(workdir, 'acme', 'demo', 'feature', {
+        message: 'Add notes',
+        paths: ['notes.md'],
+        identity: { name: 'Agent Bot', email: 'agent@example.com' },
+      });
+
+      assert.equal(result.branch, 'feature');
+      assert.deepEqual(result.files, ['notes.md']);
+      assert.equal(result.sha, await git(worktreePath, ['rev-parse', 'HEAD']));
+      assert.equal(await git(worktreePath, ['log', '-1', '--format=%an <%ae>|%s']), 'Agent Bot <agent@example.com>|Add notes');
+      assert.equal(await git(worktreePath, ['status', '--porcelain']), '?? scratch.txt');
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+
+  it('rejects commits when nothing is staged', async () => {
+    const { workdir } = await createWorkdirWithWorktree();
+    try {
+      await assert.rejects(
+        commitWorktree(workdir, 'acme', 'demo', 'feature', { message: 'Nothing here' }),
+        (error: unknown) => {
+          assert.ok(error instanceof NothingToCommitError);
+          assert.equal(error.statusCode, 400);
+          assert.equal(error.code, 'nothing_to_commit');
+          return true;
+        }
+      );
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+});
+
+describe('commit signing', () => {
+  function useSigningGit(signingKey: string | null): string[][] {
+    const calls: string[][] = [];
+    __setGitRepositoryTestOverrides({
+      execFileAsync: async (_command, args) => {
+        const gitArgs = args as string[];
+        calls.push(gitArgs);
+        if (gitArgs.includes('--porcelain')) {
+          return { stdout: 'worktree /work/acme/demo/feature\nbranch refs/heads/feature\n', stderr: '' };
+        }
+        if (gitArgs.includes('user.signingkey')) {
+          if (!signingKey) {
+            throw Object.assign(new Error('Command failed'), { code: 1, stderr: '' });
+          }
+          return { stdout: `${signingKey}\n`, stderr: '' };
+        }
+        if (gitArgs.includes('--name-only')) {
+          return { stdout: 'notes.md\n', stderr: '' };
+        }
+        return { stdout: gitArgs.includes('rev-parse') ? 'abc123\n' : '', stderr: '' };
+      },
+    });
+    return calls;
+  }
+
+  const commitArgs = (calls: string[][]) => calls.find((args) => args.includes('commit'));
+
+  it('passes -S to git commit when signing is enabled', async () => {
+    const calls = useSigningGit('ABCDEF12');
+
+    const result = await commitWorktree('/work', 'acme', 'demo', 'feature', {
+      message: 'Signed change',
+      requireSigning: true,
+    });
+
+    assert.equal(result.signed, true);
+    assert.deepEqual(commitArgs(calls), ['-C', '/work/acme/demo/feature', 'commit', '-S', '-m', 'Signed change']);
+  });
+
+  it('signs when the request asks even if the server does not require it', async () => {
+    const calls = useSigningGit('ABCDEF12');
+
+    await commitWorktree('/work', 'acme', 'demo', 'feature', { message: 'Signed change', sign: true });
+    assert.ok(commitArgs(calls)?.includes('-S'));
+
+    calls.length = 0;
+    const unsigned = await commitWorktree('/work', 'acme', 'demo', 'feature', { message: 'Plain change' });
+    assert.equal(unsigned.signed, false);
+    assert.ok(!commitArgs(calls)?.includes('-S'));
+    assert.ok(!calls.some((args) => args.includes('user.signingkey')));
+  });
+
+  it('refuses to commit when signing is required but no key is configured', async () => {
+    const calls = useSigningGit(null);
+
+    await assert.rejects(
+      commitWorktree('/work', 'acme', 'demo', 'feature', { message: 'Signed change', requireSigning: true }),
+      (error: unknown) => {
+        assert.ok(error instanceof SigningKeyNotConfiguredError);
+        assert.equal(error.statusCode, 400);
+        assert.equal(error.code, 'signing_key_not_configured');
+        return true;
+      }
+    );
+    assert.ok(!calls.some((args) => args.includes('add') || args.includes('commit')));
+  });
+});
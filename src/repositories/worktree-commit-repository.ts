@@ -0,0 +1,181 @@
+import {
+  executeGitCommandInRepo,
+  extractGitErrorMessage,
+  GitNotFoundError,
+  GIT_BUFFER_SIZES,
+} from './git-repository.js';
+import { getWorktreePath, identityConfigArgs, type GitIdentity } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+import type { ProcessOutputOptions } from '../utils/process-output.js';
+
+/**
+ * Error raised when a commit is requested but nothing is staged
+ */
+export class NothingToCommitError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'nothing_to_commit';
+
+  constructor(org: string, repo: string, branch: string) {
+    super(`Nothing to commit in ${org}/${repo} branch ${branch}`);
+    this.name = 'NothingToCommitError';
+  }
+}
+
+/**
+ * Error raised when a commit must be signed but git has no signing key configured
+ */
+export class SigningKeyNotConfiguredError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'signing_key_not_configured';
+
+  constructor(org: string, repo: string, branch: string) {
+    super(
+      `Commit signing is required but no signing key is configured for ${org}/${repo} branch ${branch}; ` +
+        'set user.signingKey in git config'
+    );
+    this.name = 'SigningKeyNotConfiguredError';
+  }
+}
+
+export interface CommitWorktreeOptions {
+  message: string;
+  /**
+   * Paths (relative to the worktree) to stage and commit; everything is committed when omitted
+   */
+  paths?: string[];
+  /**
+   * Sign the commit with `-S`; always on when `requireSigning` is set
+   */
+  sign?: boolean;
+  /**
+   * Signing is configured server-wide, so every commit is signed whether or not the request asks for it
+   */
+  requireSigning?: boolean;
+  /**
+   * Author/committer identity passed to git with `-c`
+   */
+  identity?: GitIdentity | null;
+  /**
+   * Record an empty commit without staging anything, e.g. so a new branch has a commit to open a pull
+   * request for
+   */
+  empty?: boolean;
+}
+
+export interface CommitWorktreeResult {
+  branch: string;
+  sha: string;
+  files: string[];
+  signed: boolean;
+}
+
+/**
+ * Stages changes in a worktree and commits them with the configured identity
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param options - Commit message and optional paths to limit the commit to
+ * @param output - Follows git's output (including hook output) line by line; aborting kills git
+ * @returns The new commit SHA and the files it contains
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ * @throws {NothingToCommitError} If staging leaves nothing to commit
+ * @throws {SigningKeyNotConfiguredError} If the commit must be signed but no signing key is configured
+ */
+export async function commitWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  options: CommitWorktreeOptions,
+  output: ProcessOutputOptions = {}
+): Promise<CommitWorktreeResult> {
+  const branchName = normalizeBranchName(branch);
+  const message = options.message.trim();
+
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+  if (!message) {
+    throw new Error('Commit message cannot be empty');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  const empty = options.empty === true;
+  // An empty commit names no paths so nothing already staged sneaks into it
+  const pathspec = empty ? ['--'] : options.paths && options.paths.length > 0 ? ['--', ...options.paths] : [];
+  const sign = options.requireSigning === true || options.sign === true;
+
+  // Checked before staging so a refused commit leaves the index untouched
+  if (sign && !(await hasSigningKey(worktreePath))) {
+    throw new SigningKeyNotConfiguredError(org, repo, branchName);
+  }
+
+  let files: string[] = [];
+  if (!empty) {
+    try {
+      await executeGitCommandInRepo(worktreePath, ['add', '-A', ...pathspec], {
+        maxBuffer: GIT_BUFFER_SIZES.MEDIUM,
+        ...output,
+      });
+    } catch (error) {
+      throw new Error(`Failed to stage changes: ${extractGitErrorMessage(error)}`, { cause: error });
+    }
+
+    const { stdout: stagedOutput } = await executeGitCommandInRepo(
+      worktreePath,
+      ['diff', '--cached', '--name-only', ...pathspec],
+      { maxBuffer: GIT_BUFFER_SIZES.MEDIUM, signal: output.signal }
+    );
+    files = stagedOutput
+      .split('\n')
+      .map((line) => line.trim())
+      .filter(Boolean);
+
+    if (files.length === 0) {
+      throw new NothingToCommitError(org, repo, branchName);
+    }
+  }
+
+  const commitArgs = [
+    ...identityConfigArgs(options.identity),
+    'commit',
+    ...(sign ? ['-S'] : []),
+    ...(empty ? ['--allow-empty', '--only'] : []),
+    '-m',
+    message,
+    ...pathspec,
+  ];
+  try {
+    await executeGitCommandInRepo(worktreePath, commitArgs, {
+      maxBuffer: GIT_BUFFER_SIZES.MEDIUM,
+      ...output,
+    });
+  } catch (error) {
+    throw new Error(`Failed to commit changes: ${extractGitErrorMessage(error)}`, { cause: error });
+  }
+
+  const { stdout: shaOutput } = await executeGitCommandInRepo(worktreePath, ['rev-parse', 'HEAD'], {
+    maxBuffer: GIT_BUFFER_SIZES.SMALL,
+  });
+
+  return { branch: branchName, sha: shaOutput.trim(), files, signed: sign };
+}
+
+/**
+ * Reports whether git would find a signing key for commits made in the worktree
+ */
+async function hasSigningKey(worktreePath: string): Promise<boolean> {
+  try {
+    const { stdout } = await executeGitCommandInRepo(worktreePath, ['config', '--get', 'user.signingkey'], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+    return Boolean(stdout.trim());
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    // git config exits 1 when the key is unset
+    return false;
+  }
+}
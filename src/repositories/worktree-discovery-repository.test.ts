@@ -0,0 +1,103 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { after, before, describe, it } from 'node:test';
+
+import { discoverWorktrees } from './worktree-discovery-repository.js';
+import { listWorktrees } from './worktree-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+
+describe('discoverWorktrees', () => {
+  let root: string;
+  let workdir: string;
+  let repoRoot: string;
+  let repositoryPath: string;
+  let elsewherePath: string;
+
+  before(async () => {
+    root = await fs.realpath(await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-discovery-')));
+    workdir = path.join(root, 'work');
+    repoRoot = path.join(workdir, 'acme', 'demo');
+    repositoryPath = path.join(repoRoot, 'repository');
+    elsewherePath = path.join(root, 'elsewhere', 'hotfix');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+
+    // Registered beside the clone, and locked
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', path.join(repoRoot, 'login')]);
+    await git(repositoryPath, ['worktree', 'lock', path.join(repoRoot, 'login')]);
+    // Registered outside the repository directory, where a directory scan cannot see it
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'hotfix', elsewherePath]);
+    // Registered, but its directory was deleted behind git's back
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'old', path.join(repoRoot, 'old')]);
+    await fs.rm(path.join(repoRoot, 'old'), { recursive: true, force: true });
+    // On disk beside the clone, but unknown to git
+    await fs.mkdir(path.join(repoRoot, 'stale'));
+    // Links beside the clone: one out of the work directory, one back up to the repository directory
+    await fs.mkdir(path.join(root, 'elsewhere', 'private'));
+    await fs.symlink(path.join(root, 'elsewhere', 'private'), path.join(repoRoot, 'private'), 'dir');
+    await fs.symlink(repoRoot, path.join(repoRoot, 'loop'), 'dir');
+  });
+
+  after(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  it('merges git records with the directory scan and flags each source', async () => {
+    const listing = await discoverWorktrees(workdir, 'acme', 'demo');
+    const byPath = new Map(listing.worktrees.map((entry) => [entry.path, entry]));
+
+    const scanned = (await fs.readdir(repoRoot)).filter((name) => name !== 'repository').sort();
+    const registered = (await listWorktrees(repositoryPath)).map((entry) => entry.path);
+    assert.deepEqual(scanned, ['login', 'loop', 'private', 'stale']);
+    assert.equal(registered.length, 4);
+
+    assert.deepEqual(
+      listing.worktrees.map((entry) => entry.path),
+      [...registered, path.join(repoRoot, 'stale')]
+    );
+    assert.deepEqual(byPath.get(repositoryPath), {
+      path: repositoryPath,
+      branch: 'main',
+      primary: true,
+      registered: true,
+      onDisk: true,
+      locked: false,
+      prunable: false,
+    });
+    assert.equal(byPath.get(path.join(repoRoot, 'login'))?.locked, true);
+    assert.equal(byPath.get(elsewherePath)?.branch, 'hotfix');
+    assert.equal(byPath.get(elsewherePath)?.onDisk, true);
+    assert.deepEqual(
+      [byPath.get(path.join(repoRoot, 'old'))?.prunable, byPath.get(path.join(repoRoot, 'old'))?.onDisk],
+      [true, false]
+    );
+    assert.deepEqual(byPath.get(path.join(repoRoot, 'stale')), {
+      path: path.join(repoRoot, 'stale'),
+      branch: null,
+      primary: false,
+      registered: false,
+      onDisk: true,
+      locked: false,
+      prunable: false,
+    });
+  });
+
+  it('does not report symlinked directories beside the clone', async () => {
+    const listing = await discoverWorktrees(workdir, 'acme', 'demo');
+    const paths = listing.worktrees.map((entry) => entry.path);
+
+    assert.ok(!paths.includes(path.join(repoRoot, 'private')));
+    assert.ok(!paths.includes(path.join(repoRoot, 'loop')));
+  });
+
+  it('rejects repositories that have not been cloned', async () => {
+    await assert.rejects(discoverWorktrees(workdir, 'acme', 'missing'), { statusCode: 404 });
+  });
+});
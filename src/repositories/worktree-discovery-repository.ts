@@ -0,0 +1,123 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { listWorktrees, RepositoryNotFoundError } from './worktree-repository.js';
+import { resolveRepositoryPaths } from './repository-paths.js';
+
+export interface DiscoveredWorktree {
+  path: string;
+  branch: string | null;
+  /**
+   * The primary clone rather than a linked worktree
+   */
+  primary: boolean;
+  /**
+   * Git records the worktree; false for directories found on disk that git has forgotten
+   */
+  registered: boolean;
+  /**
+   * The directory exists
+   */
+  onDisk: boolean;
+  locked: boolean;
+  prunable: boolean;
+}
+
+export interface WorktreeListing {
+  org: string;
+  repo: string;
+  worktrees: DiscoveredWorktree[];
+}
+
+async function isDirectory(target: string): Promise<boolean> {
+  try {
+    return (await fs.stat(target)).isDirectory();
+  } catch {
+    return false;
+  }
+}
+
+async function canonicalise(target: string): Promise<string> {
+  try {
+    return await fs.realpath(target);
+  } catch {
+    return path.resolve(target);
+  }
+}
+
+/**
+ * Lists the directories beside the primary clone. Symlinks are skipped rather than followed, so a link to
+ * a directory elsewhere on disk is never reported as a worktree or offered for cleanup.
+ */
+async function listRepositoryDirectories(repoRoot: string, repositoryPath: string): Promise<string[]> {
+  let entries;
+  try {
+    entries = await fs.readdir(repoRoot, { withFileTypes: true });
+  } catch (error: unknown) {
+    if ((error as { code?: string })?.code === 'ENOENT') {
+      return [];
+    }
+    throw error;
+  }
+  return entries
+    .filter((entry) => entry.isDirectory() && !entry.name.startsWith('.'))
+    .map((entry) => path.join(repoRoot, entry.name))
+    .filter((directory) => path.resolve(directory) !== path.resolve(repositoryPath));
+}
+
+/**
+ * Lists a repository's worktrees by merging git's own records (`git worktree list --porcelain`) with a scan
+ * of the directories beside the primary clone. Git's list is authoritative: it includes worktrees that live
+ * outside the repository directory and flags locked and prunable entries, while the scan adds directories
+ * git has forgotten (`registered: false`) so they can be cleaned up. Symlinked directories are not scanned.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @returns Git's worktrees in git's order, followed by unregistered directories sorted by path
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ */
+export async function discoverWorktrees(workdir: string, org: string, repo: string): Promise<WorktreeListing> {
+  const { repoRoot, repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  if (!(await isDirectory(repositoryPath))) {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+
+  const primaryPath = await canonicalise(repositoryPath);
+  const known = new Set<string>();
+  const worktrees: DiscoveredWorktree[] = [];
+
+  for (const entry of await listWorktrees(repositoryPath)) {
+    if (!entry.path) {
+      continue;
+    }
+    const canonical = await canonicalise(entry.path);
+    known.add(canonical);
+    worktrees.push({
+      path: entry.path,
+      branch: entry.branch,
+      primary: canonical === primaryPath,
+      registered: true,
+      onDisk: await isDirectory(entry.path),
+      locked: entry.locked,
+      prunable: entry.prunable,
+    });
+  }
+
+  const unregistered: DiscoveredWorktree[] = [];
+  for (const directory of await listRepositoryDirectories(repoRoot, repositoryPath)) {
+    if (known.has(await canonicalise(directory))) {
+      continue;
+    }
+    unregistered.push({
+      path: directory,
+      branch: null,
+      primary: false,
+      registered: false,
+      onDisk: true,
+      locked: false,
+      prunable: false,
+    });
+  }
+  unregistered.sort((a, b) => a.path.localeCompare(b.path));
+
+  return { org, repo, worktrees: [...worktrees, ...unregistered] };
+}
@@ -0,0 +1,142 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { after, afterEach, before, describe, it } from 'node:test';
+
+import {
+  readWorktreeFile,
+  sniffContentType,
+  WorktreeFileNotFoundError,
+  WorktreeFilePathError,
+  WorktreeFileTooLargeError,
+} from './worktree-file-repository.js';
+import { __setGitRepositoryTestOverrides } from './git-repository.js';
+
+describe('readWorktreeFile', () => {
+  let root: string;
+  let worktreePath: string;
+
+  before(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-file-'));
+    worktreePath = path.join(root, 'acme', 'demo', 'feature');
+    await fs.mkdir(path.join(worktreePath, 'src'), { recursive: true });
+    await fs.writeFile(path.join(worktreePath, 'src', 'notes.txt'), 'hello\n');
+    await fs.writeFile(path.join(worktreePath, '.git'), 'gitdir: /elsewhere\n');
+    await fs.writeFile(path.join(root, 'secret.txt'), 'top secret\n');
+    await fs.symlink(path.join(root, 'secret.txt'), path.join(worktreePath, 'escape.txt'));
+    await fs.symlink(path.join(worktreePath, 'src', 'notes.txt'), path.join(worktreePath, 'alias.txt'));
+  });
+
+  after(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  afterEach(() => {
+    __setGitRepositoryTestOverrides();
+  });
+
+  function useWorktree(): void {
+    __setGitRepositoryTestOverrides({
+      execFileAsync: async () => ({
+        stdout: `worktree ${worktreePath}\nbranch refs/heads/feature\n`,
+        stderr: '',
+      }),
+    });
+  }
+
+  function assertRejectedPath(error: unknown): true {
+    assert.ok(error instanceof WorktreeFilePathError);
+    assert.equal(error.statusCode, 400);
+    assert.equal(error.code, 'invalid_path');
+    return true;
+  }
+
+  it('returns file contents with a sniffed content type', async () => {
+    useWorktree();
+
+    const file = await readWorktreeFile(root, 'acme', 'demo', 'feature', 'src/notes.txt');
+
+    assert.equal(file.path, 'src/notes.txt');
+    assert.equal(file.size, 6);
+    assert.equal(file.contentType, 'text/plain; charset=utf-8');
+    assert.equal(file.content.toString('utf8'), 'hello\n');
+  });
+
+  it('follows symlinks that stay inside the worktree', async () => {
+    useWorktree();
+
+    const file = await readWorktreeFile(root, 'acme', 'demo', 'feature', 'alias.txt');
+    assert.equal(file.content.toString('utf8'), 'hello\n');
+  });
+
+  it('rejects traversal attempts', async () => {
+    useWorktree();
+
+    for (const attempt of [
+      '../../../secret.txt',
+      'src/../../../../secret.txt',
+      '..\\..\\..\\secret.txt',
+      path.join(root, 'secret.txt'),
+      'C:\\Windows\\win.ini',
+      '.',
+      'src/..',
+    ]) {
+      await assert.rejects(readWorktreeFile(root, 'acme', 'demo', 'feature', attempt), assertRejectedPath);
+    }
+  });
+
+  it('rejects symlinks that resolve outside the worktree', async () => {
+    useWorktree();
+
+    await assert.rejects(readWorktreeFile(root, 'acme', 'demo', 'feature', 'escape.txt'), assertRejectedPath);
+  });
+
+  it('refuses to serve git metadata', async () => {
+    useWorktree();
+
+    await assert.rejects(readWorktreeFile(root, 'acme', 'demo', 'feature', '.git'), assertRejectedPath);
+  });
+
+  it('returns 404 for missing files and directories', async () => {
+    useWorktree();
+
+    for (const missing of ['src/missing.txt', 'src']) {
+      await assert.rejects(readWorktreeFile(root, 'acme', 'demo', 'feature', missing), (error: unknown) => {
+        assert.ok(error instanceof WorktreeFileNotFoundError);
+        assert.equal(error.statusCode, 404);
+        return true;
+      });
+    }
+  });
+
+  it('returns 413 for files over the size cap', async () => {
+    useWorktree();
+
+    await assert.rejects(
+      readWorktreeFile(root, 'acme', 'demo', 'feature', 'src/notes.txt', { maxBytes: 4 }),
+      (error: unknown) => {
+        assert.ok(error instanceof WorktreeFileTooLargeError);
+        assert.equal(error.statusCode, 413);
+        assert.deepEqual(error.details, { size: 6, limit: 4 });
+        return true;
+      }
+    );
+  });
+});
+
+describe('sniffContentType', () => {
+  it('prefers magic bytes over the extension', () => {
+    const png = Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00]);
+    assert.equal(sniffContentType('image.txt', png), 'image/png');
+  });
+
+  it('treats content with NUL bytes as binary', () => {
+    assert.equal(sniffContentType('data.txt', Buffer.from([0x61, 0x00, 0x62])), 'application/octet-stream');
+  });
+
+  it('labels text by extension', () => {
+    assert.equal(sniffContentType('package.json', Buffer.from('{}')), 'application/json; charset=utf-8');
+    assert.equal(sniffContentType('Makefile', Buffer.from('all:')), 'text/plain; charset=utf-8');
+  });
+});
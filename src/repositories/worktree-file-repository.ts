@@ -0,0 +1,249 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { getWorktreePath } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+
+/**
+ * Largest file served by readWorktreeFile unless a different cap is passed
+ */
+export const DEFAULT_MAX_WORKTREE_FILE_BYTES = 5 * 1024 * 1024;
+
+const SNIFF_BYTES = 8000;
+
+/**
+ * Error raised when a requested path is malformed or resolves outside the worktree
+ */
+export class WorktreeFilePathError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'invalid_path';
+
+  constructor(message: string) {
+    super(message);
+    this.name = 'WorktreeFilePathError';
+  }
+}
+
+/**
+ * Error raised when a requested file does not exist in the worktree
+ */
+export class WorktreeFileNotFoundError extends Error {
+  public readonly statusCode: number = 404;
+  public readonly code = 'file_not_found';
+
+  constructor(filePath: string) {
+    super(`File ${filePath} not found in worktree`);
+    this.name = 'WorktreeFileNotFoundError';
+  }
+}
+
+/**
+ * Error raised when a requested file exceeds the size cap
+ */
+export class WorktreeFileTooLargeError extends Error {
+  public readonly statusCode: number = 413;
+  public readonly code = 'file_too_large';
+  public readonly details: { size: number; limit: number };
+
+  constructor(filePath: string, size: number, limit: number) {
+    super(`File ${filePath} is ${size} bytes, larger than the ${limit} byte limit`);
+    this.name = 'WorktreeFileTooLargeError';
+    this.details = { size, limit };
+  }
+}
+
+export interface WorktreeFile {
+  path: string;
+  size: number;
+  contentType: string;
+  content: Buffer;
+}
+
+export interface ReadWorktreeFileOptions {
+  maxBytes?: number;
+}
+
+const MAGIC_SIGNATURES: Array<{ type: string; bytes: number[]; offset?: number }> = [
+  { type: 'image/png', bytes: [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a] },
+  { type: 'image/jpeg', bytes: [0xff, 0xd8, 0xff] },
+  { type: 'image/gif', bytes: [0x47, 0x49, 0x46, 0x38] },
+  { type: 'image/webp', bytes: [0x57, 0x45, 0x42, 0x50], offset: 8 },
+  { type: 'application/pdf', bytes: [0x25, 0x50, 0x44, 0x46, 0x2d] },
+  { type: 'application/zip', bytes: [0x50, 0x4b, 0x03, 0x04] },
+  { type: 'application/gzip', bytes: [0x1f, 0x8b] },
+  { type: 'application/wasm', bytes: [0x00, 0x61, 0x73, 0x6d] },
+];
+
+const TEXT_TYPES_BY_EXTENSION = new Map([
+  ['.css', 'text/css'],
+  ['.csv', 'text/csv'],
+  ['.htm', 'text/html'],
+  ['.html', 'text/html'],
+  ['.js', 'text/javascript'],
+  ['.json', 'application/json'],
+  ['.md', 'text/markdown'],
+  ['.mjs', 'text/javascript'],
+  ['.svg', 'image/svg+xml'],
+  ['.xml', 'application/xml'],
+  ['.yaml', 'application/yaml'],
+  ['.yml', 'application/yaml'],
+]);
+
+/**
+ * Determines a file's MIME type from its leading bytes, falling back to the extension for text files
+ * @param filePath - File path, used for the extension
+ * @param content - File contents
+ * @returns MIME type, with `charset=utf-8` for text
+ */
+export function sniffContentType(filePath: string, content: Buffer): string {
+  for (const signature of MAGIC_SIGNATURES) {
+    const offset = signature.offset ?? 0;
+    if (signature.bytes.every((byte, index) => content[offset + index] === byte)) {
+      return signature.type;
+    }
+  }
+
+  if (content.subarray(0, SNIFF_BYTES).includes(0)) {
+    return 'application/octet-stream';
+  }
+
+  const type = TEXT_TYPES_BY_EXTENSION.get(path.extname(filePath).toLowerCase()) ?? 'text/plain';
+  return `${type}; charset=utf-8`;
+}
+
+/**
+ * Validates a worktree-relative path before it touches the filesystem
+ */
+function normaliseRelativePath(input: string): string {
+  const trimmed = typeof input === 'string' ? input.trim() : '';
+  if (!trimmed) {
+    throw new WorktreeFilePathError('path is required');
+  }
+  if (trimmed.includes('\0')) {
+    throw new WorktreeFilePathError('path contains invalid characters');
+  }
+  if (path.isAbsolute(trimmed) || path.win32.isAbsolute(trimmed)) {
+    throw new WorktreeFilePathError('path must be relative to the worktree');
+  }
+  return trimmed.replace(/\\/g, '/');
+}
+
+/**
+ * Checks that a canonical path sits inside a canonical root
+ */
+function isWithin(root: string, candidate: string): boolean {
+  const relative = path.relative(root, candidate);
+  return relative !== '' && !relative.startsWith('..') && !path.isAbsolute(relative);
+}
+
+export interface ResolvedWorktreeFile {
+  /**
+   * Canonical worktree root
+   */
+  root: string;
+  /**
+   * The path as requested, normalised to forward slashes
+   */
+  relativePath: string;
+  /**
+   * Canonical absolute path of the file, after following symlinks
+   */
+  canonicalPath: string;
+}
+
+/**
+ * Resolves a worktree-relative path to an existing file, canonicalising it (following symlinks) and
+ * requiring it to stay inside the worktree root and outside git metadata
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param filePath - Path relative to the worktree root
+ * @returns The worktree root and the file's canonical path
+ * @throws {WorktreeFilePathError} If the path is invalid or escapes the worktree
+ * @throws {WorktreeFileNotFoundError} If the file does not exist
+ */
+export async function resolveWorktreeFile(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  filePath: string
+): Promise<ResolvedWorktreeFile> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('branch is required');
+  }
+
+  const relativePath = normaliseRelativePath(filePath);
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  const root = await fs.realpath(worktreePath);
+  const requested = path.resolve(root, relativePath);
+
+  // Reject lexical escapes before touching the filesystem so probing outside paths reveals nothing
+  if (!isWithin(root, requested)) {
+    throw new WorktreeFilePathError('path must stay within the worktree');
+  }
+
+  let canonical: string;
+  try {
+    canonical = await fs.realpath(requested);
+  } catch (error: unknown) {
+    if ((error as { code?: string })?.code === 'ENOENT') {
+      throw new WorktreeFileNotFoundError(relativePath);
+    }
+    throw error;
+  }
+
+  if (!isWithin(root, canonical)) {
+    throw new WorktreeFilePathError('path must stay within the worktree');
+  }
+  if (path.relative(root, canonical).split(path.sep)[0] === '.git') {
+    throw new WorktreeFilePathError('git metadata cannot be read');
+  }
+
+  const stats = await fs.stat(canonical);
+  if (!stats.isFile()) {
+    throw new WorktreeFileNotFoundError(relativePath);
+  }
+
+  return { root, relativePath, canonicalPath: canonical };
+}
+
+/**
+ * Reads a file from a worktree. The path is canonicalised (following symlinks) and must stay
+ * inside the worktree root.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param filePath - Path relative to the worktree root
+ * @param options - Read options
+ * @returns File contents with its sniffed content type
+ * @throws {WorktreeFilePathError} If the path is invalid or escapes the worktree
+ * @throws {WorktreeFileNotFoundError} If the file does not exist
+ * @throws {WorktreeFileTooLargeError} If the file exceeds the size cap
+ */
+export async function readWorktreeFile(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  filePath: string,
+  { maxBytes = DEFAULT_MAX_WORKTREE_FILE_BYTES }: ReadWorktreeFileOptions = {}
+): Promise<WorktreeFile> {
+  const { relativePath, canonicalPath: canonical } = await resolveWorktreeFile(workdir, org, repo, branch, filePath);
+
+  const stats = await fs.stat(canonical);
+  if (stats.size > maxBytes) {
+    throw new WorktreeFileTooLargeError(relativePath, stats.size, maxBytes);
+  }
+
+  const content = await fs.readFile(canonical);
+
+  return {
+    path: relativePath,
+    size: content.length,
+    contentType: sniffContentType(relativePath, content),
+    content,
+  };
+}
@@ -0,0 +1,106 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, describe, it } from 'node:test';
+
+import { pushWorktree, PushRejectedError } from './worktree-push-repository.js';
+import { __setGitRepositoryTestOverrides, GIT_NETWORK_TIMEOUT_MS, GitTimeoutError } from './git-repository.js';
+
+const execGit = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execGit('git', ['-C', cwd, ...args])).stdout.trim();
+
+/**
+ * Creates a temporary workdir holding a real acme/demo repository with a `feature` worktree checked out
+ */
+async function createWorkdirWithWorktree(): Promise<{ workdir: string; worktreePath: string }> {
+  const workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-push-'));
+  const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+  const worktreePath = path.join(workdir, 'acme', 'demo', 'feature');
+  await fs.mkdir(repositoryPath, { recursive: true });
+  await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+  await git(repositoryPath, [
+    '-c',
+    'user.name=Seed',
+    '-c',
+    'user.email=seed@example.com',
+    'commit',
+    '--quiet',
+    '--allow-empty',
+    '-m',
+    'Initial commit',
+  ]);
+  await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature', worktreePath]);
+  return { workdir, worktreePath };
+}
+
+afterEach(() => {
+  __setGitRepositoryTestOverrides();
+});
+
+describe('pushWorktree', () => {
+  const commitAs = ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet'];
+
+  it('pushes to a local bare remote and handles diverged branches', async () => {
+    const { workdir, worktreePath } = await createWorkdirWithWorktree();
+    const remotePath = path.join(workdir, 'remote.git');
+    try {
+      await execGit('git', ['init', '--quiet', '--bare', remotePath]);
+      await git(worktreePath, ['remote', 'add', 'origin', remotePath]);
+      await git(worktreePath, [...commitAs, '--allow-empty', '-m', 'First']);
+
+      const pushed = await pushWorktree(workdir, 'acme', 'demo', 'feature');
+      assert.deepEqual(pushed, {
+        branch: 'feature',
+        remote: 'origin',
+        upstream: 'origin/feature',
+        sha: await git(worktreePath, ['rev-parse', 'HEAD']),
+        forced: false,
+      });
+      assert.equal(await git(remotePath, ['rev-parse', 'refs/heads/feature']), pushed.sha);
+
+      await git(worktreePath, ['reset', '--quiet', '--hard', 'HEAD~1']);
+      await git(worktreePath, [...commitAs, '--allow-empty', '-m', 'Diverged']);
+
+      await assert.rejects(pushWorktree(workdir, 'acme', 'demo', 'feature'), (error: unknown) => {
+        assert.ok(error instanceof PushRejectedError);
+        assert.equal(error.statusCode, 409);
+        assert.equal(error.code, 'non_fast_forward');
+        assert.match(error.message, /pull or rebase/);
+        return true;
+      });
+
+      const forced = await pushWorktree(workdir, 'acme', 'demo', 'feature', { forceWithLease: true });
+      assert.equal(forced.forced, true);
+      assert.equal(await git(remotePath, ['rev-parse', 'refs/heads/feature']), forced.sha);
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+
+  it('disables credential prompts and applies the network timeout', async () => {
+    let pushOptions: { env?: NodeJS.ProcessEnv; timeout?: number } | undefined;
+    __setGitRepositoryTestOverrides({
+      execFileAsync: async (_command, args, options) => {
+        if (args[2] === 'worktree' && args[3] === 'list') {
+          return { stdout: 'worktree /work/acme/demo/feature\nbranch refs/heads/feature\n', stderr: '' };
+        }
+        if (args[2] === 'push') {
+          pushOptions = options as typeof pushOptions;
+          throw Object.assign(new Error('Command failed'), { killed: true, signal: 'SIGTERM' });
+        }
+        return { stdout: '', stderr: '' };
+      },
+    });
+
+    await assert.rejects(pushWorktree('/work', 'acme', 'demo', 'feature'), (error: unknown) => {
+      assert.ok(error instanceof GitTimeoutError);
+      assert.equal(error.statusCode, 504);
+      return true;
+    });
+    assert.equal(pushOptions?.env?.['GIT_TERMINAL_PROMPT'], '0');
+    assert.equal(pushOptions?.timeout, GIT_NETWORK_TIMEOUT_MS);
+  });
+});
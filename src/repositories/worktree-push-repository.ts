@@ -0,0 +1,150 @@
+import {
+  executeGitCommandInRepo,
+  extractGitErrorMessage,
+  GIT_BUFFER_SIZES,
+  GIT_NETWORK_TIMEOUT_MS,
+  GitNotFoundError,
+  GitTimeoutError,
+} from './git-repository.js';
+import { getWorktreePath } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+import type { ProcessOutputOptions } from '../utils/process-output.js';
+
+/**
+ * Error raised when the remote refuses a push because the branch has diverged
+ */
+export class PushRejectedError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'non_fast_forward';
+
+  constructor(branch: string, detail: string) {
+    super(
+      `Push of ${branch} was rejected because the remote has changes that are not in the local branch; ` +
+        `pull or rebase before pushing again (${detail})`
+    );
+    this.name = 'PushRejectedError';
+  }
+}
+
+/**
+ * Error raised when a push names a remote the repository does not have
+ */
+export class RemoteNotFoundError extends Error {
+  public readonly statusCode: number = 404;
+  public readonly code = 'remote_not_found';
+
+  constructor(remote: string) {
+    super(`Remote ${remote} is not configured; add it before pushing to it`);
+    this.name = 'RemoteNotFoundError';
+  }
+}
+
+export interface PushWorktreeOptions {
+  /**
+   * Overwrite the remote branch as long as it still matches our remote-tracking ref
+   */
+  forceWithLease?: boolean;
+  /**
+   * Remote to push to, such as a fork; defaults to origin
+   */
+  remote?: string;
+}
+
+export interface PushWorktreeResult {
+  branch: string;
+  remote: string;
+  upstream: string;
+  sha: string;
+  forced: boolean;
+}
+
+const PUSH_REMOTE = 'origin';
+
+/**
+ * Pushes a worktree branch to origin, or another configured remote, and sets it as the upstream
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param options - Push options
+ * @param output - Follows git's output, including progress, line by line; aborting kills git
+ * @returns Remote tracking information for the pushed branch
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ * @throws {RemoteNotFoundError} If the remote is not configured
+ * @throws {PushRejectedError} If the remote rejects the push as non-fast-forward
+ * @throws {GitTimeoutError} If the push does not finish within the network timeout
+ */
+export async function pushWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  options: PushWorktreeOptions = {},
+  output: ProcessOutputOptions = {}
+): Promise<PushWorktreeResult> {
+  const branchName = normalizeBranchName(branch);
+
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  const remote = options.remote ?? PUSH_REMOTE;
+  if (options.remote !== undefined) {
+    try {
+      await executeGitCommandInRepo(worktreePath, ['remote', 'get-url', '--', remote], {
+        maxBuffer: GIT_BUFFER_SIZES.SMALL,
+      });
+    } catch (error) {
+      if (error instanceof GitNotFoundError) {
+        throw error;
+      }
+      throw new RemoteNotFoundError(remote);
+    }
+  }
+  const forced = options.forceWithLease === true;
+  const args = [
+    'push',
+    '-u',
+    ...(forced ? ['--force-with-lease'] : []),
+    // git only reports progress to a terminal unless asked
+    ...(output.onOutput ? ['--progress'] : []),
+    remote,
+    branchName,
+  ];
+
+  try {
+    await executeGitCommandInRepo(worktreePath, args, {
+      maxBuffer: GIT_BUFFER_SIZES.MEDIUM,
+      timeout: GIT_NETWORK_TIMEOUT_MS,
+      // Never block on a credential prompt nobody can answer
+      env: { ...process.env, GIT_TERMINAL_PROMPT: '0' },
+      ...output,
+    });
+  } catch (error) {
+    if (error instanceof GitTimeoutError) {
+      throw error;
+    }
+    const message = extractGitErrorMessage(error);
+    if (/non-fast-forward|fetch first|stale info/i.test(message)) {
+      const rejection = message.split('\n').find((line) => line.includes('[rejected]'));
+      throw new PushRejectedError(branchName, rejection?.replace(/^\s*!\s*/, '').trim() || 'rejected');
+    }
+    throw new Error(`Failed to push branch: ${message}`, { cause: error });
+  }
+
+  const [{ stdout: upstreamOutput }, { stdout: shaOutput }] = await Promise.all([
+    executeGitCommandInRepo(worktreePath, ['rev-parse', '--abbrev-ref', '--symbolic-full-name', '@{u}'], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    }),
+    executeGitCommandInRepo(worktreePath, ['rev-parse', 'HEAD'], { maxBuffer: GIT_BUFFER_SIZES.SMALL }),
+  ]);
+
+  return {
+    branch: branchName,
+    remote,
+    upstream: upstreamOutput.trim(),
+    sha: shaOutput.trim(),
+    forced,
+  };
+}
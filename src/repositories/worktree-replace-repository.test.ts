@@ -0,0 +1,115 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, beforeEach, describe, it } from 'node:test';
+
+import { replaceWorktree } from './worktree-replace-repository.js';
+import { __setWorktreeRepositoryTestOverrides, InvalidBaseRefError } from './worktree-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+const commitAs = ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet'];
+
+describe('replaceWorktree', () => {
+  let root: string;
+  let workdir: string;
+  let repositoryPath: string;
+  let worktreePath: string;
+  let mainSha: string;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-replace-'));
+    const seedPath = path.join(root, 'seed');
+    await fs.mkdir(seedPath);
+    await git(seedPath, ['init', '--quiet', '-b', 'main']);
+    await fs.writeFile(path.join(seedPath, 'notes.md'), 'one\n');
+    await git(seedPath, ['add', '-A']);
+    await git(seedPath, [...commitAs, '-m', 'Seed']);
+    const originPath = path.join(root, 'origin.git');
+    await execFileAsync('git', ['clone', '--quiet', '--bare', seedPath, originPath]);
+
+    workdir = path.join(root, 'work');
+    repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    worktreePath = path.join(workdir, 'acme', 'demo', 'login');
+    await execFileAsync('git', ['clone', '--quiet', originPath, repositoryPath]);
+    mainSha = await git(repositoryPath, ['rev-parse', 'main']);
+
+    // A worktree an agent has been busy in: a commit plus uncommitted and untracked changes
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', worktreePath]);
+    await fs.writeFile(path.join(worktreePath, 'login.md'), 'attempt one\n');
+    await git(worktreePath, ['add', '-A']);
+    await git(worktreePath, [...commitAs, '-m', 'First attempt']);
+    await fs.writeFile(path.join(worktreePath, 'notes.md'), 'edited\n');
+    await fs.writeFile(path.join(worktreePath, 'scratch.txt'), 'untracked\n');
+  });
+
+  afterEach(async () => {
+    __setWorktreeRepositoryTestOverrides();
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  it('discards a dirtied worktree and recreates the branch at the base', async () => {
+    const previousSha = await git(repositoryPath, ['rev-parse', 'feature/login']);
+
+    const result = await replaceWorktree(workdir, 'acme', 'demo', 'feature/login', { base: 'main' });
+
+    assert.deepEqual(result, {
+      branch: 'feature/login',
+      worktreePath,
+      base: 'main',
+      replaced: true,
+      previousSha,
+      sha: mainSha,
+    });
+    assert.equal(await git(worktreePath, ['rev-parse', 'HEAD']), mainSha);
+    assert.equal(await git(worktreePath, ['status', '--porcelain']), '');
+    await assert.rejects(fs.access(path.join(worktreePath, 'scratch.txt')), { code: 'ENOENT' });
+    assert.equal(await fs.readFile(path.join(worktreePath, 'notes.md'), 'utf8'), 'one\n');
+
+    // Nothing parked is left behind
+    assert.deepEqual((await fs.readdir(path.dirname(worktreePath))).sort(), ['login', 'repository']);
+    assert.equal(await git(repositoryPath, ['branch', '--list', 'agentrix-replacing-*']), '');
+    assert.equal((await git(repositoryPath, ['worktree', 'list', '--porcelain'])).match(/^worktree /gm)?.length, 2);
+  });
+
+  it('puts the old worktree back when the new one cannot be created', async () => {
+    const previousSha = await git(repositoryPath, ['rev-parse', 'feature/login']);
+    __setWorktreeRepositoryTestOverrides({
+      runRepositoryInitCommand: async () => {
+        throw new Error('init exploded');
+      },
+    });
+
+    await assert.rejects(
+      replaceWorktree(workdir, 'acme', 'demo', 'feature/login', { base: 'main' }),
+      /Failed to create worktree: init exploded/
+    );
+
+    assert.equal(await git(worktreePath, ['rev-parse', 'HEAD']), previousSha);
+    assert.equal(await git(worktreePath, ['rev-parse', '--abbrev-ref', 'HEAD']), 'feature/login');
+    assert.equal(await fs.readFile(path.join(worktreePath, 'notes.md'), 'utf8'), 'edited\n');
+    assert.equal(await fs.readFile(path.join(worktreePath, 'scratch.txt'), 'utf8'), 'untracked\n');
+    assert.deepEqual((await fs.readdir(path.dirname(worktreePath))).sort(), ['login', 'repository']);
+    assert.equal(await git(repositoryPath, ['branch', '--list', 'agentrix-replacing-*']), '');
+  });
+
+  it('refuses a base that does not resolve before touching the worktree', async () => {
+    await assert.rejects(
+      replaceWorktree(workdir, 'acme', 'demo', 'feature/login', { base: 'no-such-ref' }),
+      InvalidBaseRefError
+    );
+
+    assert.equal(await fs.readFile(path.join(worktreePath, 'scratch.txt'), 'utf8'), 'untracked\n');
+  });
+
+  it('creates the worktree when the branch has none yet', async () => {
+    const result = await replaceWorktree(workdir, 'acme', 'demo', 'spike');
+
+    assert.equal(result.replaced, false);
+    assert.equal(result.previousSha, null);
+    assert.equal(await git(result.worktreePath, ['rev-parse', '--abbrev-ref', 'HEAD']), 'spike');
+  });
+});
@@ -0,0 +1,259 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import {
+  executeGitCommandInRepo,
+  extractGitErrorMessage,
+  GitNotFoundError,
+  GIT_BUFFER_SIZES,
+} from './git-repository.js';
+import {
+  assertGitWorkTree,
+  createWorktree,
+  listWorktrees,
+  RepositoryNotFoundError,
+  verifyWorktreeBase,
+  WorktreeConflictError,
+  type WorktreeSettings,
+} from './worktree-repository.js';
+import { resolveRepositoryPaths } from './repository-paths.js';
+import { deriveWorktreeFolderName, normalizeBranchName } from '../domain/index.js';
+
+export interface ReplaceWorktreeOptions extends WorktreeSettings {
+  defaultBranchOverride?: string;
+  /**
+   * Ref the recreated branch starts from; `defaultBase` applies when omitted
+   */
+  base?: string | null;
+}
+
+export interface ReplaceWorktreeResult {
+  branch: string;
+  worktreePath: string;
+  /**
+   * Ref the branch was recreated from, or null when it started from the default branch
+   */
+  base: string | null;
+  /**
+   * False when the branch had no worktree to replace and one was simply created
+   */
+  replaced: boolean;
+  /**
+   * Commit the branch pointed at before it was discarded; null when the branch did not exist
+   */
+  previousSha: string | null;
+  sha: string;
+}
+
+async function pathExists(targetPath: string): Promise<boolean> {
+  try {
+    await fs.access(targetPath);
+    return true;
+  } catch (error: unknown) {
+    if ((error as { code?: string })?.code === 'ENOENT') {
+      return false;
+    }
+    throw error;
+  }
+}
+
+async function canonicalPath(target: string): Promise<string> {
+  try {
+    return await fs.realpath(target);
+  } catch {
+    return path.resolve(target);
+  }
+}
+
+async function readBranchSha(repositoryPath: string, branch: string): Promise<string | null> {
+  try {
+    const { stdout } = await executeGitCommandInRepo(
+      repositoryPath,
+      ['rev-parse', '--verify', '--quiet', `refs/heads/${branch}`],
+      { maxBuffer: GIT_BUFFER_SIZES.SMALL }
+    );
+    return stdout.trim() || null;
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    return null;
+  }
+}
+
+/**
+ * Runs one step of undoing a replacement, logging rather than throwing so the remaining steps still run
+ */
+async function attemptRestore(description: string, step: () => Promise<unknown>): Promise<void> {
+  try {
+    await step();
+  } catch (error: unknown) {
+    console.warn(`[agentrix] Failed to ${description}:`, extractGitErrorMessage(error));
+  }
+}
+
+/**
+ * Discards a branch's worktree and recreates it from a base in one step. The old worktree and branch are
+ * moved aside rather than deleted, so when the new worktree cannot be created they are put back as they
+ * were; they are only removed once the replacement exists.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch whose worktree is replaced
+ * @param options - Base to recreate the branch from, the repository's default branch override and the
+ *   worktree settings
+ * @returns The new worktree and the commit the branch was at before
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ * @throws {InvalidBaseRefError} If the base does not resolve
+ * @throws {WorktreeConflictError} If the branch is checked out in the primary clone or its directory
+ *   belongs to another branch
+ */
+export async function replaceWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  options: ReplaceWorktreeOptions = {}
+): Promise<ReplaceWorktreeResult> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { repoRoot, repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  if (!(await pathExists(repositoryPath))) {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+  await assertGitWorkTree(repositoryPath, org, repo);
+
+  const folderName = deriveWorktreeFolderName(branchName);
+  if (folderName === '.' || folderName === '..') {
+    throw new Error('Invalid worktree folder name derived from branch');
+  }
+  const targetPath = path.join(repoRoot, folderName);
+  // Checked before anything is moved, so a bad base leaves the worktree untouched
+  const { defaultBranchOverride, base: requestedBase, ...settings } = options;
+  const base = await verifyWorktreeBase(workdir, org, repo, requestedBase, settings.defaultBase);
+
+  await executeGitCommandInRepo(repositoryPath, ['worktree', 'prune'], { maxBuffer: GIT_BUFFER_SIZES.SMALL });
+
+  const primaryPath = await canonicalPath(repositoryPath);
+  const target = await canonicalPath(targetPath);
+  const worktrees = await Promise.all(
+    (await listWorktrees(repositoryPath))
+      .filter((item): item is typeof item & { path: string } => Boolean(item.path))
+      .map(async (item) => ({ ...item, canonical: await canonicalPath(item.path) }))
+  );
+  const occupant = worktrees.find((item) => item.canonical === target && item.branch !== branchName);
+  if (target === primaryPath || occupant) {
+    throw new WorktreeConflictError(
+      `Worktree directory ${targetPath} belongs to ${occupant ? `branch ${occupant.branch}` : 'the primary clone'}`
+    );
+  }
+  const own = worktrees.find((item) => item.branch === branchName) ?? null;
+  if (own?.canonical === primaryPath) {
+    throw new WorktreeConflictError(`Branch ${branchName} is checked out in the primary clone`);
+  }
+
+  const previousSha = await readBranchSha(repositoryPath, branchName);
+  const suffix = `${process.pid}-${Date.now()}`;
+  const parkedBranch = `agentrix-replacing-${suffix}`;
+  const parkedWorktreePath = path.join(repoRoot, `.agentrix-replacing-${folderName}-${suffix}`);
+  const parkedLeftoverPath = path.join(repoRoot, `.agentrix-leftover-${folderName}-${suffix}`);
+  let branchParked = false;
+  let worktreeParked = false;
+  let leftoverParked = false;
+
+  // Puts back whatever was parked; after a failed creation the new worktree and branch go first
+  const restore = async (creationFailed: boolean): Promise<void> => {
+    if (creationFailed) {
+      await attemptRestore(`remove the partial worktree at ${targetPath}`, async () => {
+        const registered = (await listWorktrees(repositoryPath)).some(
+          (item) => item.path && path.resolve(item.path) === path.resolve(targetPath)
+        );
+        if (registered) {
+          await executeGitCommandInRepo(repositoryPath, ['worktree', 'remove', '--force', targetPath]);
+        }
+        await fs.rm(targetPath, { recursive: true, force: true });
+      });
+      await attemptRestore(`delete the partial branch ${branchName}`, async () => {
+        if (await readBranchSha(repositoryPath, branchName)) {
+          await executeGitCommandInRepo(repositoryPath, ['branch', '-D', branchName]);
+        }
+      });
+    }
+    if (branchParked) {
+      await attemptRestore(`restore branch ${branchName}`, () =>
+        executeGitCommandInRepo(repositoryPath, ['branch', '-m', parkedBranch, branchName])
+      );
+    }
+    if (worktreeParked && own) {
+      await attemptRestore(`restore the worktree at ${own.path}`, () =>
+        executeGitCommandInRepo(repositoryPath, ['worktree', 'move', parkedWorktreePath, own.path])
+      );
+    }
+    if (leftoverParked) {
+      await attemptRestore(`restore ${targetPath}`, () => fs.rename(parkedLeftoverPath, targetPath));
+    }
+  };
+
+  try {
+    if (own) {
+      await executeGitCommandInRepo(repositoryPath, ['worktree', 'move', own.path, parkedWorktreePath]);
+      worktreeParked = true;
+    }
+    // A directory git does not track for the branch, e.g. left behind by a failed removal
+    if (await pathExists(targetPath)) {
+      await fs.rename(targetPath, parkedLeftoverPath);
+      leftoverParked = true;
+    }
+    if (previousSha) {
+      await executeGitCommandInRepo(repositoryPath, ['branch', '-m', branchName, parkedBranch]);
+      branchParked = true;
+    }
+  } catch (error: unknown) {
+    await restore(false);
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    throw new Error(`Failed to replace worktree: ${extractGitErrorMessage(error)}`);
+  }
+
+  try {
+    // The old worktree still counts against the limit while parked, and its slot is being reused
+    await createWorktree(workdir, org, repo, branchName, {
+      ...settings,
+      ...(defaultBranchOverride ? { defaultBranchOverride } : {}),
+      base,
+      skipWorktreeLimit: Boolean(own),
+    });
+  } catch (error: unknown) {
+    await restore(true);
+    throw error;
+  }
+
+  if (worktreeParked) {
+    await attemptRestore(`remove the replaced worktree at ${parkedWorktreePath}`, () =>
+      executeGitCommandInRepo(repositoryPath, ['worktree', 'remove', '--force', parkedWorktreePath])
+    );
+  }
+  if (branchParked) {
+    await attemptRestore(`delete the replaced branch ${parkedBranch}`, () =>
+      executeGitCommandInRepo(repositoryPath, ['branch', '-D', parkedBranch])
+    );
+  }
+  if (leftoverParked) {
+    await fs.rm(parkedLeftoverPath, { recursive: true, force: true });
+  }
+
+  const { stdout } = await executeGitCommandInRepo(targetPath, ['rev-parse', 'HEAD'], {
+    maxBuffer: GIT_BUFFER_SIZES.SMALL,
+  });
+  return {
+    branch: branchName,
+    worktreePath: targetPath,
+    base,
+    replaced: Boolean(own),
+    previousSha,
+    sha: stdout.trim(),
+  };
+}
@@ -1,18 +1,66 @@
 import assert from 'node:assert/strict';
 import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { execFile } from 'node:child_process';
+import { promisify } from 'node:util';
 import { afterEach, describe, it, mock } from 'node:test';
 
 import {
   __setWorktreeRepositoryTestOverrides,
   GitWorktreeError,
+  WorktreeConflictError,
+  WorktreeNotFoundError,
+  InvalidBranchNameError,
+  InvalidRepositoryError,
+  assertGitWorkTree,
+  RepositoryNotFoundError,
   createWorktree,
+  createWorktrees,
+  WorktreeLimitReachedError,
+  assertWorktreeCapacity,
+  verifyWorktreeBase,
+  InvalidBaseRefError,
+  planWorktree,
+  checkWorktreeBranch,
+  findExistingWorktree,
+  clearWorktreeTarget,
   getWorktreePath,
   listWorktrees,
   countLocalWorktrees,
   removeWorktree,
+  renameWorktree,
 } from './worktree-repository.js';
 import { __setGitRepositoryTestOverrides } from './git-repository.js';
 import { RepositoryIdentifierError } from '../domain/index.js';
+import { ValidationError } from '../infrastructure/errors/index.js';
+
+const execGit = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execGit('git', ['-C', cwd, ...args])).stdout.trim();
+
+/**
+ * Creates a temporary workdir holding a real acme/demo repository with a `feature` worktree checked out
+ */
+async function createWorkdirWithWorktree(): Promise<{ workdir: string; worktreePath: string }> {
+  const workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-worktree-'));
+  const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+  const worktreePath = path.join(workdir, 'acme', 'demo', 'feature');
+  await fs.mkdir(repositoryPath, { recursive: true });
+  await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+  await git(repositoryPath, [
+    '-c',
+    'user.name=Seed',
+    '-c',
+    'user.email=seed@example.com',
+    'commit',
+    '--quiet',
+    '--allow-empty',
+    '-m',
+    'Initial commit',
+  ]);
+  await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature', worktreePath]);
+  return { workdir, worktreePath };
+}
 
 describe('worktree-repository', () => {
   afterEach(() => {
@@ -28,7 +76,8 @@ describe('worktree-repository', () => {
         assert.deepEqual(args, ['-C', '/repo', 'worktree', 'list', '--porcelain']);
         return {
           stdout:
-            'worktree /path/main\nbranch refs/heads/main\n\nworktree /path/feature\nbranch refs/heads/feature/login\n',
+            'worktree /path/main\nbranch refs/heads/main\n\nworktree /path/feature\nbranch refs/heads/feature/login\n' +
+            'locked moved to external disk\n\nworktree /path/gone\nbranch refs/heads/old\nprunable gitdir file points to non-existent location\n',
           stderr: '',
         };
       });
@@ -41,8 +90,9 @@ describe('worktree-repository', () => {
 
       const result = await listWorktrees('/repo');
       assert.deepEqual(result, [
-        { path: '/path/main', branch: 'main' },
-        { path: '/path/feature', branch: 'feature/login' },
+        { path: '/path/main', branch: 'main', locked: false, prunable: false },
+        { path: '/path/feature', branch: 'feature/login', locked: true, prunable: false },
+        { path: '/path/gone', branch: 'old', locked: false, prunable: true },
       ]);
       assert.equal(execMock.mock.callCount(), 1);
     });
@@ -123,7 +173,8 @@ describe('worktree-repository', () => {
       await assert.rejects(
         getWorktreePath('/work', 'acme', 'demo', 'missing'),
         (error: unknown) => {
-          assert.ok(error instanceof Error);
+          assert.ok(error instanceof WorktreeNotFoundError);
+          assert.equal(error.statusCode, 404);
           assert.match(error.message, /not found/);
           return true;
         }
@@ -197,6 +248,367 @@ describe('worktree-repository', () => {
     });
   });
 
+  describe('renameWorktree', () => {
+    const worktreeOutput = 'worktree /work/acme/demo/login\nbranch refs/heads/feature/login\n';
+
+    it('renames the branch and moves the worktree directory', async () => {
+      mock.method(fs, 'access', async () => {
+        const error = new Error('missing') as NodeJS.ErrnoException;
+        error.code = 'ENOENT';
+        throw error;
+      });
+
+      const execMock = mock.fn(async (_command: string, args: string[]) => {
+        if (args[2] === 'worktree' && args[3] === 'list') {
+          return { stdout: worktreeOutput, stderr: '' };
+        }
+        if (args[2] === 'rev-parse') {
+          const error = new Error('missing') as { stderr?: Buffer };
+          error.stderr = Buffer.from('fatal: needed a single revision');
+          throw error;
+        }
+        return { stdout: '', stderr: '' };
+      });
+
+      __setGitRepositoryTestOverrides({
+        execFileAsync: async (command, args, options) => {
+          assert.equal(command, 'git');
+          return await execMock(command, args, options);
+        },
+      });
+
+      const result = await renameWorktree('/work', 'acme', 'demo', 'feature/login', 'feature/signin');
+      assert.deepEqual(result, {
+        branch: 'feature/signin',
+        previousBranch: 'feature/login',
+        worktreePath: '/work/acme/demo/signin',
+      });
+
+      const executedArgs = execMock.mock.calls.map((call) => call.arguments[1] as string[]);
+      assert.ok(
+        executedArgs.some(
+          (args) =>
+            JSON.stringify(args) ===
+            JSON.stringify(['-C', '/work/acme/demo/login', 'branch', '-m', 'feature/login', 'feature/signin'])
+        )
+      );
+      assert.ok(
+        executedArgs.some(
+          (args) =>
+            JSON.stringify(args) ===
+            JSON.stringify([
+              '-C',
+              '/work/acme/demo/repository',
+              'worktree',
+              'move',
+              '/work/acme/demo/login',
+              '/work/acme/demo/signin',
+            ])
+        )
+      );
+    });
+
+    it('rejects renames onto an existing branch with a conflict error', async () => {
+      __setGitRepositoryTestOverrides({
+        execFileAsync: async (_command, args) => {
+          if (args[2] === 'worktree' && args[3] === 'list') {
+            return { stdout: worktreeOutput, stderr: '' };
+          }
+          return { stdout: '', stderr: '' };
+        },
+      });
+
+      await assert.rejects(
+        renameWorktree('/work', 'acme', 'demo', 'feature/login', 'feature/taken'),
+        (error: unknown) => {
+          assert.ok(error instanceof WorktreeConflictError);
+          assert.equal(error.statusCode, 409);
+          assert.equal(error.code, 'worktree_conflict');
+          assert.match(error.message, /already exists/);
+          return true;
+        }
+      );
+    });
+
+    it('rejects empty and unchanged names as bad requests before touching git', async () => {
+      const calls: string[][] = [];
+      __setGitRepositoryTestOverrides({
+        execFileAsync: async (_command, args) => {
+          calls.push(args as string[]);
+          return { stdout: '', stderr: '' };
+        },
+      });
+
+      for (const [branch, newBranch] of [['feature/login', ' '], ['feature/login', 'feature/login'], ['feature/login', '..']]) {
+        await assert.rejects(renameWorktree('/work', 'acme', 'demo', branch!, newBranch!), (error: unknown) => {
+          assert.ok(error instanceof ValidationError, newBranch);
+          assert.equal(error.statusCode, 400);
+          return true;
+        });
+      }
+      assert.deepEqual(calls, []);
+    });
+
+    it('throws WorktreeNotFoundError when no worktree matches', async () => {
+      __setGitRepositoryTestOverrides({
+        execFileAsync: async () => ({ stdout: '', stderr: '' }),
+      });
+
+      await assert.rejects(
+        renameWorktree('/work', 'acme', 'demo', 'feature/missing', 'feature/other'),
+        (error: unknown) => {
+          assert.ok(error instanceof WorktreeNotFoundError);
+          return true;
+        }
+      );
+    });
+  });
+
+  describe('assertGitWorkTree', () => {
+    const execFileAsync = promisify(execFile);
+
+    it('follows a .git file to a separate git directory', async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-gitfile-'));
+      const checkout = path.join(root, 'checkout');
+      try {
+        await execFileAsync('git', ['init', '--quiet', '--separate-git-dir', path.join(root, 'store.git'), checkout]);
+        const gitEntry = await fs.stat(path.join(checkout, '.git'));
+        assert.equal(gitEntry.isFile(), true);
+
+        await assertGitWorkTree(checkout, 'acme', 'demo');
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+
+    it('rejects a directory nested inside another working tree', async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-nested-'));
+      const checkout = path.join(root, 'acme', 'demo', 'repository');
+      try {
+        await execFileAsync('git', ['init', '--quiet', root]);
+        await fs.mkdir(checkout, { recursive: true });
+
+        await assert.rejects(assertGitWorkTree(checkout, 'acme', 'demo'), (error: unknown) => {
+          assert.ok(error instanceof InvalidRepositoryError);
+          assert.match(error.message, /rooted at/);
+          return true;
+        });
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+
+    it('rejects a .git file that points at a missing git directory', async () => {
+      const checkout = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-gitfile-'));
+      try {
+        await fs.writeFile(path.join(checkout, '.git'), `gitdir: ${path.join(checkout, 'missing.git')}\n`);
+
+        await assert.rejects(assertGitWorkTree(checkout, 'acme', 'demo'), (error: unknown) => {
+          assert.ok(error instanceof InvalidRepositoryError);
+          assert.equal(error.statusCode, 409);
+          assert.match(error.message, /acme\/demo is not a valid git working tree/);
+          return true;
+        });
+      } finally {
+        await fs.rm(checkout, { recursive: true, force: true });
+      }
+    });
+  });
+
+  describe('planWorktree', () => {
+    it('computes the target path without creating anything', async () => {
+      const workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-plan-'));
+      const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+      await fs.mkdir(repositoryPath, { recursive: true });
+
+      const calls: string[][] = [];
+      __setGitRepositoryTestOverrides({
+        execFileAsync: async (_command, args) => {
+          const gitArgs = args as string[];
+          calls.push(gitArgs);
+          if (gitArgs.includes('worktree')) {
+            return { stdout: `worktree ${repositoryPath}\nbranch refs/heads/main\n`, stderr: '' };
+          }
+          if (gitArgs.includes('--show-toplevel')) {
+            return { stdout: `${repositoryPath}\n`, stderr: '' };
+          }
+          if (gitArgs.includes('rev-parse')) {
+            throw new Error('fatal: Needed a single revision');
+          }
+          return { stdout: '', stderr: '' };
+        },
+      });
+
+      try {
+        const plan = await planWorktree(workdir, 'acme', 'demo', 'feature/login');
+
+        const expectedPath = path.join(workdir, 'acme', 'demo', 'login');
+        assert.deepEqual(plan, {
+          branch: 'feature/login',
+          worktreePath: expectedPath,
+          branchExists: false,
+        });
+        await assert.rejects(fs.access(expectedPath), { code: 'ENOENT' });
+        assert.ok(!calls.some((args) => args.includes('add')));
+      } finally {
+        await fs.rm(workdir, { recursive: true, force: true });
+      }
+    });
+
+    it('rejects branch names git refuses', async () => {
+      mock.method(fs, 'access', async () => undefined);
+      __setGitRepositoryTestOverrides({
+        execFileAsync: async (_command, args) => {
+          if ((args as string[]).includes('check-ref-format')) {
+            throw new Error('fatal: not a valid branch name');
+          }
+          if ((args as string[]).includes('--show-toplevel')) {
+            return { stdout: '/workdir/acme/demo/repository\n', stderr: '' };
+          }
+          return { stdout: '', stderr: '' };
+        },
+      });
+
+      await assert.rejects(
+        planWorktree('/workdir', 'acme', 'demo', 'bad..name'),
+        InvalidBranchNameError
+      );
+    });
+
+    it('reports missing repositories as not found', async () => {
+      mock.method(fs, 'access', async () => {
+        throw Object.assign(new Error('missing'), { code: 'ENOENT' });
+      });
+
+      await assert.rejects(planWorktree('/workdir', 'acme', 'demo', 'feature'), RepositoryNotFoundError);
+    });
+  });
+
+  describe('checkWorktreeBranch', () => {
+    it('reports a legal, unused name as available', async () => {
+      const { workdir } = await createWorkdirWithWorktree();
+      try {
+        assert.deepEqual(await checkWorktreeBranch(workdir, 'acme', 'demo', 'feature/login'), {
+          branch: 'feature/login',
+          valid: true,
+          available: true,
+          folderName: 'login',
+          worktreePath: path.join(workdir, 'acme', 'demo', 'login'),
+          branchExists: false,
+          reasons: [],
+        });
+        await assert.rejects(fs.access(path.join(workdir, 'acme', 'demo', 'login')), { code: 'ENOENT' });
+      } finally {
+        await fs.rm(workdir, { recursive: true, force: true });
+      }
+    });
+
+    it('explains why git refuses a name', async () => {
+      const { workdir } = await createWorkdirWithWorktree();
+      try {
+        const check = await checkWorktreeBranch(workdir, 'acme', 'demo', 'fix/bad..name ~2');
+        assert.equal(check.valid, false);
+        assert.equal(check.available, false);
+        assert.deepEqual(check.reasons, [
+          { code: 'invalid_character', message: 'Branch name cannot contain space, ~' },
+          { code: 'invalid_sequence', message: 'Branch name cannot contain ..' },
+        ]);
+
+        const head = await checkWorktreeBranch(workdir, 'acme', 'demo', 'HEAD');
+        assert.deepEqual(
+          head.reasons.map((reason) => reason.code),
+          ['invalid_ref']
+        );
+      } finally {
+        await fs.rm(workdir, { recursive: true, force: true });
+      }
+    });
+
+    it('reports names that collide with an existing worktree', async () => {
+      const { workdir, worktreePath } = await createWorkdirWithWorktree();
+      try {
+        const checkedOut = await checkWorktreeBranch(workdir, 'acme', 'demo', 'feature');
+        assert.equal(checkedOut.valid, true);
+        assert.equal(checkedOut.available, false);
+        assert.equal(checkedOut.branchExists, true);
+        assert.deepEqual(checkedOut.reasons, [
+          { code: 'branch_checked_out', message: `Branch feature is already checked out in a worktree at ${worktreePath}` },
+          { code: 'directory_exists', message: `Worktree directory already exists at ${worktreePath}` },
+        ]);
+
+        const sameFolder = await checkWorktreeBranch(workdir, 'acme', 'demo', 'release/feature');
+        assert.equal(sameFolder.branchExists, false);
+        assert.deepEqual(
+          sameFolder.reasons.map((reason) => reason.code),
+          ['directory_exists']
+        );
+      } finally {
+        await fs.rm(workdir, { recursive: true, force: true });
+      }
+    });
+  });
+
+  describe('existing worktree handling', () => {
+    it('keeps rejecting an occupied directory by default', async () => {
+      const { workdir, worktreePath } = await createWorkdirWithWorktree();
+      try {
+        await git(worktreePath, ['checkout', '--quiet', '--detach']);
+        await assert.rejects(planWorktree(workdir, 'acme', 'demo', 'feature'), WorktreeConflictError);
+      } finally {
+        await fs.rm(workdir, { recursive: true, force: true });
+      }
+    });
+
+    it('finds the worktree git tracks so it can be reused', async () => {
+      const { workdir, worktreePath } = await createWorkdirWithWorktree();
+      try {
+        assert.deepEqual(await findExistingWorktree(workdir, 'acme', 'demo', 'feature'), {
+          branch: 'feature',
+          worktreePath: await fs.realpath(worktreePath),
+        });
+        assert.equal(await findExistingWorktree(workdir, 'acme', 'demo', 'other'), null);
+
+        await fs.rm(worktreePath, { recursive: true, force: true });
+        assert.equal(await findExistingWorktree(workdir, 'acme', 'demo', 'feature'), null);
+      } finally {
+        await fs.rm(workdir, { recursive: true, force: true });
+      }
+    });
+
+    it('clears tracked worktrees and leftover directories before recreating', async () => {
+      const { workdir, worktreePath } = await createWorkdirWithWorktree();
+      const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+      const leftoverPath = path.join(workdir, 'acme', 'demo', 'stale');
+      try {
+        await fs.writeFile(path.join(worktreePath, 'scratch.txt'), 'uncommitted\n');
+        await clearWorktreeTarget(workdir, 'acme', 'demo', 'feature');
+        await assert.rejects(fs.access(worktreePath), { code: 'ENOENT' });
+        assert.doesNotMatch(await git(repositoryPath, ['worktree', 'list', '--porcelain']), /refs\/heads\/feature/);
+
+        await fs.mkdir(leftoverPath);
+        await fs.writeFile(path.join(leftoverPath, 'orphan.txt'), 'left behind\n');
+        await clearWorktreeTarget(workdir, 'acme', 'demo', 'stale');
+        await assert.rejects(fs.access(leftoverPath), { code: 'ENOENT' });
+
+        await createWorktree(workdir, 'acme', 'demo', 'feature', { skipDefaultBranchSync: true });
+        assert.equal(await git(worktreePath, ['rev-parse', '--abbrev-ref', 'HEAD']), 'feature');
+      } finally {
+        await fs.rm(workdir, { recursive: true, force: true });
+      }
+    });
+
+    it('refuses to clear the primary clone', async () => {
+      const { workdir } = await createWorkdirWithWorktree();
+      try {
+        await assert.rejects(clearWorktreeTarget(workdir, 'acme', 'demo', 'main'), WorktreeConflictError);
+        await assert.rejects(clearWorktreeTarget(workdir, 'acme', 'demo', 'repository'), WorktreeConflictError);
+        await fs.access(path.join(workdir, 'acme', 'demo', 'repository', '.git'));
+      } finally {
+        await fs.rm(workdir, { recursive: true, force: true });
+      }
+    });
+  });
+
   describe('createWorktree', () => {
     it('creates worktree and runs init command', async () => {
       const accessMock = mock.method(fs, 'access', async () => {
@@ -206,6 +618,9 @@ describe('worktree-repository', () => {
       });
 
       const execMock = mock.fn(async (command: string, args: string[]) => {
+        if (args.includes('--show-toplevel')) {
+          return { stdout: `${args[1]}\n`, stderr: '' };
+        }
         if (args[2] === 'rev-parse') {
           const error = new Error('missing') as { stderr?: Buffer };
           error.stderr = Buffer.from('fatal: ambiguous argument');
@@ -265,6 +680,374 @@ describe('worktree-repository', () => {
       assert.ok(progressLog.some((entry) => entry.step === 'run-init-script' && entry.event === 'complete'));
     });
 
+    it('writes the configured identity to the new worktree config only', async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-identity-'));
+      try {
+        const workdir = path.join(root, 'workdir');
+        const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+        await fs.mkdir(repositoryPath, { recursive: true });
+        await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+        await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+        await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/other', path.join(workdir, 'acme', 'demo', 'other')]);
+        __setWorktreeRepositoryTestOverrides({
+          runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+        });
+
+        await createWorktree(workdir, 'acme', 'demo', 'feature/identity', {
+          defaultBranchOverride: 'main',
+          skipDefaultBranchSync: true,
+          identity: { name: ' Agent Bot ', email: 'agent@example.com' },
+        });
+
+        const worktreePath = path.join(workdir, 'acme', 'demo', 'identity');
+        assert.equal(await git(worktreePath, ['config', 'user.name']), 'Agent Bot');
+        assert.equal(await git(worktreePath, ['config', 'user.email']), 'agent@example.com');
+        await git(worktreePath, ['commit', '--quiet', '--allow-empty', '-m', 'From another shell']);
+        assert.equal(await git(worktreePath, ['log', '-1', '--format=%an <%ae>']), 'Agent Bot <agent@example.com>');
+        await assert.rejects(git(repositoryPath, ['config', '--local', '--get', 'user.name']), { code: 1 });
+        const otherPath = path.join(workdir, 'acme', 'demo', 'other');
+        await assert.rejects(git(otherPath, ['config', '--worktree', '--get', 'user.name']), { code: 1 });
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+
+    it('never writes the paths a sparse worktree leaves out', async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-sparse-'));
+      try {
+        const originPath = path.join(root, 'origin');
+        const workdir = path.join(root, 'workdir');
+        await fs.mkdir(path.join(originPath, 'packages', 'api'), { recursive: true });
+        await fs.mkdir(path.join(originPath, 'packages', 'web'), { recursive: true });
+        await fs.writeFile(path.join(originPath, 'README.md'), 'monorepo\n');
+        await fs.writeFile(path.join(originPath, 'packages', 'api', 'index.ts'), 'export {};\n');
+        await fs.writeFile(path.join(originPath, 'packages', 'web', 'index.ts'), 'export {};\n');
+        await git(originPath, ['init', '--quiet', '-b', 'main']);
+        await git(originPath, ['add', '-A']);
+        await git(originPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '-m', 'Seed']);
+        const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+        await fs.mkdir(path.dirname(repositoryPath), { recursive: true });
+        await execGit('git', ['clone', '--quiet', originPath, repositoryPath]);
+        __setWorktreeRepositoryTestOverrides({
+          runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+        });
+        const worktreePath = path.join(workdir, 'acme', 'demo', 'api');
+        const excludedPath = path.join(worktreePath, 'packages', 'web');
+        // Looks for the excluded directory after every git command, not just once the worktree is done
+        const excludedSeenAfter: string[] = [];
+        __setGitRepositoryTestOverrides({
+          execFileAsync: (async (command: string, args: string[], options: object) => {
+            const result = await execGit(command, args, options);
+            if (await fs.access(excludedPath).then(() => true, () => false)) {
+              excludedSeenAfter.push(args.join(' '));
+            }
+            return result;
+          }) as never,
+        });
+
+        await createWorktree(workdir, 'acme', 'demo', 'feature/api', {
+          defaultBranchOverride: 'main',
+          sparsePaths: ['packages/api'],
+        });
+
+        assert.deepEqual(excludedSeenAfter, []);
+        await fs.access(path.join(worktreePath, 'packages', 'api', 'index.ts'));
+        await fs.access(path.join(worktreePath, 'README.md'));
+        await assert.rejects(fs.access(path.join(worktreePath, 'packages', 'web')), { code: 'ENOENT' });
+        await fs.access(path.join(repositoryPath, 'packages', 'web', 'index.ts'));
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+
+    it('starts a new branch at a tag and rejects tags that do not exist', async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-tag-base-'));
+      try {
+        const originPath = path.join(root, 'origin');
+        const workdir = path.join(root, 'workdir');
+        const commit = ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty'];
+        await fs.mkdir(originPath, { recursive: true });
+        await git(originPath, ['init', '--quiet', '-b', 'main']);
+        await git(originPath, [...commit, '-m', 'Release']);
+        await git(originPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'tag', '-a', 'v1.0.0', '-m', 'First release']);
+        const taggedCommit = await git(originPath, ['rev-parse', 'HEAD']);
+        await git(originPath, [...commit, '-m', 'After release']);
+        const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+        await fs.mkdir(path.dirname(repositoryPath), { recursive: true });
+        await execGit('git', ['clone', '--quiet', originPath, repositoryPath]);
+        __setWorktreeRepositoryTestOverrides({
+          runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+        });
+
+        await createWorktree(workdir, 'acme', 'demo', 'hotfix/v1', { defaultBranchOverride: 'main', base: 'v1.0.0' });
+
+        const worktreePath = path.join(workdir, 'acme', 'demo', 'v1');
+        assert.equal(await git(worktreePath, ['rev-parse', 'HEAD']), taggedCommit);
+        assert.equal(await git(worktreePath, ['rev-parse', '--abbrev-ref', 'HEAD']), 'hotfix/v1');
+        await assert.rejects(
+          createWorktree(workdir, 'acme', 'demo', 'hotfix/v2', { defaultBranchOverride: 'main', base: 'v2.0.0' }),
+          InvalidBaseRefError
+        );
+        await assert.rejects(fs.access(path.join(workdir, 'acme', 'demo', 'v2')), { code: 'ENOENT' });
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+
+    it('creates a batch of worktrees and reports colliding branches without aborting', async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-batch-'));
+      try {
+        const originPath = path.join(root, 'origin');
+        const workdir = path.join(root, 'workdir');
+        await fs.mkdir(originPath, { recursive: true });
+        await git(originPath, ['init', '--quiet', '-b', 'main']);
+        await git(originPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+        const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+        await fs.mkdir(path.dirname(repositoryPath), { recursive: true });
+        await execGit('git', ['clone', '--quiet', originPath, repositoryPath]);
+        await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/taken', path.join(workdir, 'acme', 'demo', 'taken')]);
+        __setWorktreeRepositoryTestOverrides({
+          runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+        });
+
+        const results = await createWorktrees(workdir, 'acme', 'demo', [
+          'feature/one',
+          'feature/taken',
+          'feature/two',
+          'feature/one',
+          'main',
+          'bad..name',
+        ]);
+
+        assert.deepEqual(
+          results.map((result) => [result.branch, result.status]),
+          [
+            ['feature/one', 'created'],
+            ['feature/taken', 'failed'],
+            ['feature/two', 'created'],
+            ['feature/one', 'failed'],
+            ['main', 'failed'],
+            ['bad..name', 'failed'],
+          ],
+        );
+        const [, taken, , duplicate, , invalid] = results;
+        assert.equal(taken?.status === 'failed' && taken.error.statusCode, 409);
+        assert.equal(duplicate?.status === 'failed' && duplicate.error.statusCode, 409);
+        assert.equal(invalid?.status === 'failed' && invalid.error.code, 'invalid_branch_name');
+
+        const branches = (await listWorktrees(repositoryPath)).map((entry) => entry.branch).sort();
+        assert.deepEqual(branches, ['feature/one', 'feature/taken', 'feature/two', 'main']);
+        assert.equal(
+          await git(path.join(workdir, 'acme', 'demo', 'one'), ['rev-parse', '--abbrev-ref', 'HEAD']),
+          'feature/one',
+        );
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+
+    it('refuses worktrees past the configured per-repository limit', async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-limit-'));
+      try {
+        const originPath = path.join(root, 'origin');
+        const workdir = path.join(root, 'workdir');
+        await fs.mkdir(originPath, { recursive: true });
+        await git(originPath, ['init', '--quiet', '-b', 'main']);
+        await git(originPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+        const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+        await fs.mkdir(path.dirname(repositoryPath), { recursive: true });
+        await execGit('git', ['clone', '--quiet', originPath, repositoryPath]);
+        await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/taken', path.join(workdir, 'acme', 'demo', 'taken')]);
+        __setWorktreeRepositoryTestOverrides({
+          runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+        });
+
+        await createWorktree(workdir, 'acme', 'demo', 'feature/one', { defaultBranchOverride: 'main', maxWorktrees: 2 });
+        await assert.rejects(
+          createWorktree(workdir, 'acme', 'demo', 'feature/two', { defaultBranchOverride: 'main', maxWorktrees: 2 }),
+          (error: unknown) =>
+            error instanceof WorktreeLimitReachedError && error.statusCode === 409 && error.code === 'worktree_limit_reached'
+        );
+        await assert.rejects(fs.access(path.join(workdir, 'acme', 'demo', 'two')), { code: 'ENOENT' });
+
+        const results = await createWorktrees(workdir, 'acme', 'demo', ['feature/two', 'feature/three'], {
+          maxWorktrees: 3,
+        });
+
+        assert.deepEqual(
+          results.map((result) => [result.branch, result.status]),
+          [
+            ['feature/two', 'created'],
+            ['feature/three', 'failed'],
+          ],
+        );
+        const [, refused] = results;
+        assert.equal(refused?.status === 'failed' && refused.error.code, 'worktree_limit_reached');
+        assert.equal((await listWorktrees(repositoryPath)).length, 4);
+
+        // A worktree about to be recreated frees its own slot, but only for its own branch
+        await assertWorktreeCapacity(workdir, 'acme', 'demo', 3, 'feature/one');
+        await assert.rejects(assertWorktreeCapacity(workdir, 'acme', 'demo', 3, 'feature/three'), WorktreeLimitReachedError);
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+
+    it("applies default_base and seed_paths from the repository's .agentrix.toml", async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-toml-'));
+      try {
+        const originPath = path.join(root, 'origin');
+        const workdir = path.join(root, 'workdir');
+        const commitAs = ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet'];
+        await fs.mkdir(originPath, { recursive: true });
+        await git(originPath, ['init', '--quiet', '-b', 'main']);
+        await fs.writeFile(path.join(originPath, '.gitignore'), '.env\n');
+        await fs.writeFile(
+          path.join(originPath, '.agentrix.toml'),
+          'default_base = "origin/release"\nseed_paths = [".env", "../outside"]\nunknown_key = 1\n'
+        );
+        await git(originPath, ['add', '.']);
+        await git(originPath, [...commitAs, '-m', 'Seed']);
+        await git(originPath, ['checkout', '--quiet', '-b', 'release']);
+        await git(originPath, [...commitAs, '--allow-empty', '-m', 'Release']);
+        const releaseSha = await git(originPath, ['rev-parse', 'HEAD']);
+        await git(originPath, ['checkout', '--quiet', 'main']);
+        const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+        await fs.mkdir(path.dirname(repositoryPath), { recursive: true });
+        await execGit('git', ['clone', '--quiet', originPath, repositoryPath]);
+        await fs.writeFile(path.join(repositoryPath, '.env'), 'TOKEN=local\n');
+        __setWorktreeRepositoryTestOverrides({
+          runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+        });
+
+        assert.equal(await verifyWorktreeBase(workdir, 'acme', 'demo', undefined, 'main'), 'origin/release');
+        assert.equal(await verifyWorktreeBase(workdir, 'acme', 'demo', 'main', 'main'), 'main');
+        await createWorktree(workdir, 'acme', 'demo', 'feature/seeded', { defaultBranchOverride: 'main', defaultBase: 'main' });
+
+        const worktreePath = path.join(workdir, 'acme', 'demo', 'seeded');
+        assert.equal(await git(worktreePath, ['rev-parse', 'HEAD']), releaseSha);
+        assert.equal(await fs.readFile(path.join(worktreePath, '.env'), 'utf8'), 'TOKEN=local\n');
+        assert.equal(await git(worktreePath, ['status', '--porcelain']), '');
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+
+    it('creates worktrees from a blobless partial clone', async () => {
+      const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-partial-'));
+      try {
+        const originPath = path.join(root, 'origin');
+        const workdir = path.join(root, 'workdir');
+        await fs.mkdir(originPath, { recursive: true });
+        await git(originPath, ['init', '--quiet', '-b', 'main']);
+        await git(originPath, ['config', 'uploadpack.allowFilter', 'true']);
+        await fs.writeFile(path.join(originPath, 'README.md'), 'hello\n');
+        await git(originPath, ['add', '.']);
+        await git(originPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '-m', 'Seed']);
+        const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+        await fs.mkdir(path.dirname(repositoryPath), { recursive: true });
+        await execGit('git', ['clone', '--quiet', '--filter=blob:none', `file://${originPath}`, repositoryPath]);
+        __setWorktreeRepositoryTestOverrides({
+          runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+        });
+
+        assert.equal(await git(repositoryPath, ['config', 'remote.origin.partialclonefilter']), 'blob:none');
+        await createWorktree(workdir, 'acme', 'demo', 'feature/partial', { defaultBranchOverride: 'main' });
+
+        const worktreePath = path.join(workdir, 'acme', 'demo', 'partial');
+        assert.equal(await fs.readFile(path.join(worktreePath, 'README.md'), 'utf8'), 'hello\n');
+        assert.equal(await git(worktreePath, ['status', '--porcelain']), '');
+      } finally {
+        await fs.rm(root, { recursive: true, force: true });
+      }
+    });
+
+    it('branches from the configured default base when no base is requested', async () => {
+      mock.method(fs, 'access', async () => {
+        throw Object.assign(new Error('missing'), { code: 'ENOENT' });
+      });
+      const calls: string[][] = [];
+      __setGitRepositoryTestOverrides({
+        execFileAsync: async (_command, args) => {
+          const gitArgs = args as string[];
+          calls.push(gitArgs);
+          if (gitArgs.includes('refs/heads/feature/base')) {
+            throw Object.assign(new Error('missing'), { stderr: 'fatal: Needed a single revision' });
+          }
+          return { stdout: gitArgs.includes('--show-toplevel') ? '/work/acme/demo/repository\n' : '', stderr: '' };
+        },
+      });
+      __setWorktreeRepositoryTestOverrides({
+        runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+      });
+
+      await createWorktree('/work', 'acme', 'demo', 'feature/base', { defaultBranchOverride: 'main', defaultBase: 'develop' });
+
+      assert.ok(calls.some((args) => args.includes('develop^{commit}')));
+      assert.deepEqual(
+        calls.find((args) => args.includes('worktree') && args.includes('add')),
+        ['-C', '/work/acme/demo/repository', 'worktree', 'add', '-b', 'feature/base', '/work/acme/demo/base', 'develop']
+      );
+    });
+
+    it('passes the configured git config to git worktree add', async () => {
+      mock.method(fs, 'access', async () => {
+        throw Object.assign(new Error('missing'), { code: 'ENOENT' });
+      });
+      const calls: string[][] = [];
+      __setGitRepositoryTestOverrides({
+        execFileAsync: async (_command, args) => {
+          const gitArgs = args as string[];
+          calls.push(gitArgs);
+          if (gitArgs.includes('refs/heads/feature/proxy')) {
+            throw Object.assign(new Error('missing'), { stderr: 'fatal: Needed a single revision' });
+          }
+          return { stdout: gitArgs.includes('--show-toplevel') ? '/work/acme/demo/repository\n' : '', stderr: '' };
+        },
+      });
+      __setWorktreeRepositoryTestOverrides({
+        runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+      });
+
+      await createWorktree('/work', 'acme', 'demo', 'feature/proxy', {
+        defaultBranchOverride: 'main',
+        gitConfig: { 'http.proxy': 'http://proxy.internal:3128' },
+      });
+
+      assert.deepEqual(
+        calls.find((args) => args.includes('worktree') && args.includes('add')),
+        [
+          '-C',
+          '/work/acme/demo/repository',
+          '-c',
+          'http.proxy=http://proxy.internal:3128',
+          'worktree',
+          'add',
+          '-b',
+          'feature/proxy',
+          '/work/acme/demo/proxy',
+        ]
+      );
+    });
+
+    it('rejects a base that does not resolve with a 400', async () => {
+      mock.method(fs, 'access', async () => undefined);
+      __setGitRepositoryTestOverrides({
+        execFileAsync: async () => {
+          throw Object.assign(new Error('missing'), { code: 1, stderr: '' });
+        },
+      });
+
+      await assert.rejects(verifyWorktreeBase('/work', 'acme', 'demo', null, 'develop'), InvalidBaseRefError);
+      await assert.rejects(verifyWorktreeBase('/work', 'acme', 'demo', 'release/9.9', 'develop'), (error: unknown) => {
+        assert.ok(error instanceof InvalidBaseRefError);
+        assert.equal(error.statusCode, 400);
+        assert.equal(error.code, 'invalid_base_ref');
+        assert.match(error.message, /release\/9\.9/);
+        return true;
+      });
+    });
+
     it('fails when target directory already exists', async () => {
       mock.method(fs, 'access', async () => undefined);
 
@@ -4,13 +4,24 @@ import {
   executeGitCommandInRepo,
   extractGitErrorMessage,
   GIT_BUFFER_SIZES,
+  gitConfigArgs,
+  GitNotFoundError,
 } from './git-repository.js';
-import { normalizeBranchName, deriveWorktreeFolderName } from '../domain/index.js';
+import {
+  normalizeBranchName,
+  deriveWorktreeFolderName,
+  findBranchNameProblems,
+  type BranchNameProblemCode,
+} from '../domain/index.js';
 import { resolveDefaultBranch } from '../core/default-branch.js';
 import {
   getRepositoryInitCommand,
 } from '../core/repository-config.js';
+import { loadRepositoryToml } from '../core/repository-toml.js';
 import { resolveRepositoryPaths } from './repository-paths.js';
+import { mapWithConcurrency } from '../utils/concurrency.js';
+import { ValidationError } from '../infrastructure/errors/index.js';
+import type { ProcessOutputOptions } from '../utils/process-output.js';
 
 /**
  * Custom error for worktree operations
@@ -28,15 +39,114 @@ export class GitWorktreeError extends Error {
   }
 }
 
+/**
+ * Error raised when a worktree for the requested branch does not exist
+ */
+export class WorktreeNotFoundError extends Error {
+  public readonly statusCode: number = 404;
+  public readonly code = 'worktree_not_found';
+
+  constructor(org: string, repo: string, branch: string) {
+    super(`Worktree for ${org}/${repo} branch ${branch} not found`);
+    this.name = 'WorktreeNotFoundError';
+  }
+}
+
+/**
+ * Error raised when a worktree operation would collide with an existing branch or directory
+ */
+export class WorktreeConflictError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'worktree_conflict';
+
+  constructor(message: string) {
+    super(message);
+    this.name = 'WorktreeConflictError';
+  }
+}
+
+/**
+ * Error raised when a requested branch name is not a valid git ref
+ */
+export class InvalidBranchNameError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'invalid_branch_name';
+
+  constructor(branch: string) {
+    super(`Invalid branch name: ${branch}`);
+    this.name = 'InvalidBranchNameError';
+  }
+}
+
+/**
+ * Error raised when the base ref for a new branch does not resolve to a commit
+ */
+export class InvalidBaseRefError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'invalid_base_ref';
+
+  constructor(base: string) {
+    super(`Base ref ${base} does not resolve to a commit`);
+    this.name = 'InvalidBaseRefError';
+  }
+}
+
+/**
+ * Error raised when a repository already has as many worktrees as the configured limit allows
+ */
+export class WorktreeLimitReachedError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'worktree_limit_reached';
+
+  constructor(org: string, repo: string, limit: number) {
+    super(`${org}/${repo} already has ${limit} worktree${limit === 1 ? '' : 's'}, the most allowed per repository`);
+    this.name = 'WorktreeLimitReachedError';
+  }
+}
+
+/**
+ * Error raised when the primary clone for a repository is missing from the workdir
+ */
+export class RepositoryNotFoundError extends Error {
+  public readonly statusCode: number = 404;
+  public readonly code = 'repository_not_found';
+
+  constructor(org: string, repo: string) {
+    super(`Repository not found for ${org}/${repo}`);
+    this.name = 'RepositoryNotFoundError';
+  }
+}
+
+/**
+ * Error raised when a repository checkout is not a usable git working tree
+ */
+export class InvalidRepositoryError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'invalid_repository';
+
+  constructor(org: string, repo: string, reason: string) {
+    super(`Repository ${org}/${repo} is not a valid git working tree: ${reason}`);
+    this.name = 'InvalidRepositoryError';
+  }
+}
+
 export interface WorktreeEntry {
   path: string | null;
   branch: string | null;
+  /**
+   * Locked with `git worktree lock`, so git refuses to prune or remove it
+   */
+  locked: boolean;
+  /**
+   * Git would drop the entry on `git worktree prune`, usually because its directory is gone
+   */
+  prunable: boolean;
 }
 
 /**
  * Lists all worktrees for a repository
  * @param repositoryPath - Path to the repository
- * @returns Array of {path, branch} objects
+ * @returns Array of {path, branch, locked, prunable} objects
  * @throws {GitWorktreeError}
  */
 export async function listWorktrees(repositoryPath: string): Promise<WorktreeEntry[]> {
@@ -57,6 +167,8 @@ export async function listWorktrees(repositoryPath: string): Promise<WorktreeEnt
     blocks.forEach((block) => {
       let worktreePath = null;
       let branchName = null;
+      let locked = false;
+      let prunable = false;
 
       block.split('\n').forEach((line) => {
         if (line.startsWith('worktree ')) {
@@ -64,10 +176,14 @@ export async function listWorktrees(repositoryPath: string): Promise<WorktreeEnt
         } else if (line.startsWith('branch ')) {
           const ref = line.slice('branch '.length).trim();
           branchName = ref.replace(/^refs\/heads\//, '');
+        } else if (line === 'locked' || line.startsWith('locked ')) {
+          locked = true;
+        } else if (line === 'prunable' || line.startsWith('prunable ')) {
+          prunable = true;
         }
       });
 
-      worktrees.push({ path: worktreePath, branch: branchName });
+      worktrees.push({ path: worktreePath, branch: branchName, locked, prunable });
     });
 
     return worktrees;
@@ -193,9 +309,629 @@ export function __setWorktreeRepositoryTestOverrides(overrides?: {
   runRepositoryInitCommandImpl = overrides?.runRepositoryInitCommand ?? internalRunRepositoryInitCommand;
 }
 
-export interface CreateWorktreeOptions {
+async function resolveRealPath(target: string): Promise<string> {
+  try {
+    return await fs.realpath(target);
+  } catch {
+    return path.resolve(target);
+  }
+}
+
+/**
+ * Confirms git itself recognises the checkout as the top of a working tree. A `.git` entry may be a file
+ * (worktrees, submodules, separate git dirs), so checking for its existence is not enough, and a plain
+ * directory nested in some other checkout would still count as inside a working tree.
+ * @param repositoryPath - Path to the repository checkout
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @throws {InvalidRepositoryError} If git does not report a working tree rooted at the checkout
+ */
+export async function assertGitWorkTree(repositoryPath: string, org: string, repo: string): Promise<void> {
+  let topLevel = '';
+  try {
+    const { stdout } = await executeGitCommandInRepo(repositoryPath, ['rev-parse', '--show-toplevel'], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+    topLevel = stdout.trim();
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    throw new InvalidRepositoryError(org, repo, extractGitErrorMessage(error));
+  }
+
+  if (!topLevel) {
+    throw new InvalidRepositoryError(org, repo, 'git did not report a working tree');
+  }
+  const [actual, expected] = await Promise.all([resolveRealPath(topLevel), resolveRealPath(repositoryPath)]);
+  if (actual !== expected) {
+    throw new InvalidRepositoryError(org, repo, `the working tree is rooted at ${topLevel}`);
+  }
+}
+
+export interface GitIdentity {
+  name?: string | null;
+  email?: string | null;
+}
+
+/**
+ * Server-wide settings applied when worktrees are created, passed down by the service that owns them
+ */
+export interface WorktreeSettings {
+  /**
+   * Author/committer name and email written to each new worktree's config; missing values are left unset
+   */
+  identity?: GitIdentity | null;
+  /**
+   * Ref new branches start from when a request names no base; empty branches from the synced default branch
+   */
+  defaultBase?: string | null;
+  /**
+   * Most worktrees a repository may have, not counting the primary clone; null or anything below 1 is no cap
+   */
+  maxWorktrees?: number | null;
+  /**
+   * Git config passed with `-c` to `git worktree add`, such as `http.proxy`
+   */
+  gitConfig?: Record<string, string> | null;
+}
+
+function resolveWorktreeLimit(limit?: number | null): number | null {
+  return typeof limit === 'number' && Number.isInteger(limit) && limit > 0 ? limit : null;
+}
+
+/**
+ * Reports how many more worktrees a repository may have under a limit
+ * @param repositoryPath - Path to the primary clone
+ * @param maxWorktrees - Configured limit
+ * @param replacingBranch - Branch whose worktree is about to be removed and recreated, so it does not count
+ * @returns Free slots, or Infinity when no limit is configured
+ */
+async function remainingWorktreeSlots(
+  repositoryPath: string,
+  maxWorktrees?: number | null,
+  replacingBranch?: string | null
+): Promise<number> {
+  const limit = resolveWorktreeLimit(maxWorktrees);
+  if (limit === null) {
+    return Infinity;
+  }
+  // Git lists the primary clone first; prunable entries have lost their directory and do not count
+  const linked = (await listWorktrees(repositoryPath))
+    .slice(1)
+    .filter((entry) => !entry.prunable && !(replacingBranch && entry.branch === replacingBranch));
+  return Math.max(0, limit - linked.length);
+}
+
+/**
+ * Checks that a repository can take another worktree before one is created
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param maxWorktrees - Configured limit; null or missing skips the check
+ * @param replacingBranch - Branch whose existing worktree the new one replaces; its slot is reused
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ * @throws {WorktreeLimitReachedError} If the repository is at the configured limit
+ */
+export async function assertWorktreeCapacity(
+  workdir: string,
+  org: string,
+  repo: string,
+  maxWorktrees?: number | null,
+  replacingBranch?: string | null
+): Promise<void> {
+  const limit = resolveWorktreeLimit(maxWorktrees);
+  if (limit === null) {
+    return;
+  }
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  if (!(await pathExists(repositoryPath))) {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+  if ((await remainingWorktreeSlots(repositoryPath, limit, replacingBranch)) === 0) {
+    throw new WorktreeLimitReachedError(org, repo, limit);
+  }
+}
+
+/**
+ * Picks the base ref for a new branch: the requested one, else the configured default
+ * @param base - Base named by the request
+ * @param defaultBase - Configured default base
+ * @returns The ref to branch from, or null to branch from the default branch's HEAD
+ */
+export function resolveBaseRef(base?: string | null, defaultBase?: string | null): string | null {
+  return base?.trim() || defaultBase?.trim() || null;
+}
+
+/**
+ * Picks the base ref for a new branch in a cloned repository: the requested one, else `default_base` from
+ * the primary clone's `.agentrix.toml`, else the configured default
+ * @param repositoryPath - Path to the primary clone
+ * @param base - Base named by the request
+ * @param defaultBase - Configured default base
+ * @returns The ref to branch from, or null to branch from the default branch's HEAD
+ */
+async function resolveRepositoryBaseRef(
+  repositoryPath: string,
+  base?: string | null,
+  defaultBase?: string | null
+): Promise<string | null> {
+  return resolveBaseRef(base?.trim() || (await loadRepositoryToml(repositoryPath)).defaultBase, defaultBase);
+}
+
+/**
+ * Checks that a base ref resolves to a commit in the primary clone
+ * @param repositoryPath - Path to the primary clone
+ * @param base - Base ref
+ * @throws {InvalidBaseRefError} If the ref does not resolve
+ */
+async function assertBaseRef(repositoryPath: string, base: string): Promise<void> {
+  try {
+    await executeGitCommandInRepo(repositoryPath, ['rev-parse', '--verify', '--quiet', `${base}^{commit}`], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    throw new InvalidBaseRefError(base);
+  }
+}
+
+/**
+ * Resolves and checks the base ref for a new worktree branch before any work starts
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param base - Base named by the request, falling back to the repository's `.agentrix.toml` and then the
+ *   configured default
+ * @param defaultBase - Configured default base
+ * @returns The verified base ref, or null when none is set
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ * @throws {InvalidBaseRefError} If the base does not resolve
+ */
+export async function verifyWorktreeBase(
+  workdir: string,
+  org: string,
+  repo: string,
+  base?: string | null,
+  defaultBase?: string | null
+): Promise<string | null> {
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  const cloned = await pathExists(repositoryPath);
+  const resolved = cloned
+    ? await resolveRepositoryBaseRef(repositoryPath, base, defaultBase)
+    : resolveBaseRef(base, defaultBase);
+  if (!resolved) {
+    return null;
+  }
+  if (!cloned) {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+  await assertBaseRef(repositoryPath, resolved);
+  return resolved;
+}
+
+/**
+ * Writes the configured identity into a worktree's own config (`config.worktree`), so any git run inside it
+ * commits as that identity while the primary clone, other worktrees and the global config are untouched
+ * @param worktreePath - Path to the worktree
+ * @param identity - Configured identity; missing values are left unset
+ */
+async function applyGitIdentity(worktreePath: string, identity: GitIdentity = {}): Promise<void> {
+  const entries = [
+    ['user.name', identity.name?.trim()],
+    ['user.email', identity.email?.trim()],
+  ].filter((entry): entry is [string, string] => Boolean(entry[1]));
+  if (entries.length === 0) {
+    return;
+  }
+  // Without the extension git refuses --worktree once a repository has several worktrees
+  await executeGitCommandInRepo(worktreePath, ['config', 'extensions.worktreeConfig', 'true'], {
+    maxBuffer: GIT_BUFFER_SIZES.SMALL,
+  });
+  for (const [key, value] of entries) {
+    await executeGitCommandInRepo(worktreePath, ['config', '--worktree', key, value], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+  }
+}
+
+export interface WorktreePlan {
+  branch: string;
+  worktreePath: string;
+  branchExists: boolean;
+}
+
+/**
+ * Runs the checks performed before creating a worktree without touching the filesystem
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch name
+ * @returns The normalized branch and the path the worktree would be created at
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ * @throws {InvalidBranchNameError} If git rejects the branch name
+ * @throws {WorktreeConflictError} If the branch is already checked out or the directory exists
+ */
+export async function planWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<WorktreePlan> {
+  const branchName = normalizeBranchName(branch);
+
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { repoRoot, repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+
+  if (!(await pathExists(repositoryPath))) {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+
+  await assertGitWorkTree(repositoryPath, org, repo);
+
+  const folderName = deriveWorktreeFolderName(branchName);
+  if (folderName === '.' || folderName === '..') {
+    throw new Error('Invalid worktree folder name derived from branch');
+  }
+
+  try {
+    await executeGitCommandInRepo(repositoryPath, ['check-ref-format', '--branch', branchName], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+  } catch {
+    throw new InvalidBranchNameError(branchName);
+  }
+
+  const worktrees = await listWorktrees(repositoryPath);
+  if (worktrees.some((item) => item.branch === branchName)) {
+    throw new WorktreeConflictError(`Branch ${branchName} is already checked out in a worktree`);
+  }
+
+  const targetPath = path.join(repoRoot, folderName);
+  if (await pathExists(targetPath)) {
+    throw new WorktreeConflictError(`Worktree directory already exists at ${targetPath}`);
+  }
+
+  return {
+    branch: branchName,
+    worktreePath: targetPath,
+    branchExists: await branchExists(repositoryPath, branchName),
+  };
+}
+
+export type WorktreeBranchReasonCode =
+  | BranchNameProblemCode
+  | 'invalid_ref'
+  | 'invalid_folder'
+  | 'branch_checked_out'
+  | 'directory_exists';
+
+export interface WorktreeBranchReason {
+  code: WorktreeBranchReasonCode;
+  message: string;
+}
+
+export interface WorktreeBranchCheck {
+  branch: string;
+  /**
+   * Whether git accepts the name as a branch
+   */
+  valid: boolean;
+  /**
+   * Whether a worktree could be created for the branch right now
+   */
+  available: boolean;
+  /**
+   * Directory name the worktree would get, or null when none can be derived from the name
+   */
+  folderName: string | null;
+  worktreePath: string | null;
+  branchExists: boolean;
+  reasons: WorktreeBranchReason[];
+}
+
+/**
+ * Checks a branch name the way creating its worktree would, reporting every problem instead of
+ * stopping at the first one. Nothing is created.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch name
+ * @returns Whether the name is legal and free, with the reasons when it is not
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ */
+export async function checkWorktreeBranch(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<WorktreeBranchCheck> {
+  const branchName = normalizeBranchName(branch);
+  const { repoRoot, repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+
+  if (!(await pathExists(repositoryPath))) {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+
+  await assertGitWorkTree(repositoryPath, org, repo);
+
+  const reasons: WorktreeBranchReason[] = findBranchNameProblems(branchName);
+  if (reasons.length === 0) {
+    // git has the final say; the rules above only explain the common refusals
+    try {
+      await executeGitCommandInRepo(repositoryPath, ['check-ref-format', '--branch', branchName], {
+        maxBuffer: GIT_BUFFER_SIZES.SMALL,
+      });
+    } catch (error: unknown) {
+      if (error instanceof GitNotFoundError) {
+        throw error;
+      }
+      reasons.push({ code: 'invalid_ref', message: `git does not accept ${branchName} as a branch name` });
+    }
+  }
+  const valid = reasons.length === 0;
+
+  const folderName = branchName.split('/').filter(Boolean).pop() ?? null;
+  let worktreePath: string | null = null;
+  if (folderName === '.' || folderName === '..') {
+    reasons.push({ code: 'invalid_folder', message: `Branch name cannot end in a ${folderName} directory` });
+  } else if (folderName) {
+    worktreePath = path.join(repoRoot, folderName);
+  }
+
+  if (valid) {
+    const owner = (await listWorktrees(repositoryPath)).find((item) => item.branch === branchName);
+    if (owner) {
+      reasons.push({
+        code: 'branch_checked_out',
+        message: `Branch ${branchName} is already checked out in a worktree${owner.path ? ` at ${owner.path}` : ''}`,
+      });
+    }
+  }
+  if (worktreePath && (await pathExists(worktreePath))) {
+    reasons.push({ code: 'directory_exists', message: `Worktree directory already exists at ${worktreePath}` });
+  }
+
+  return {
+    branch: branchName,
+    valid,
+    available: reasons.length === 0,
+    folderName: worktreePath ? folderName : null,
+    worktreePath,
+    branchExists: valid ? await branchExists(repositoryPath, branchName) : false,
+    reasons,
+  };
+}
+
+export interface ExistingWorktree {
+  branch: string;
+  worktreePath: string;
+}
+
+/**
+ * Finds the worktree git still tracks for a branch. Entries whose directory has gone missing are
+ * ignored, since they cannot be reused.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch name
+ * @returns The existing worktree, or null when the branch has none
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ */
+export async function findExistingWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<ExistingWorktree | null> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  if (!(await pathExists(repositoryPath))) {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+
+  const worktrees = await listWorktrees(repositoryPath);
+  const match = worktrees.find((item) => item.branch === branchName && item.path);
+  if (!match?.path || !(await pathExists(match.path))) {
+    return null;
+  }
+  return { branch: branchName, worktreePath: match.path };
+}
+
+async function canonicalPath(target: string): Promise<string> {
+  try {
+    return await fs.realpath(target);
+  } catch {
+    return path.resolve(target);
+  }
+}
+
+/**
+ * Clears the way for creating a branch's worktree afresh: removes the worktree git tracks for the
+ * branch and any leftover directory at the path the new worktree will be created at
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch name
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ * @throws {WorktreeConflictError} If the path belongs to the primary clone or another branch's worktree
+ */
+export async function clearWorktreeTarget(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<void> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { repoRoot, repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  if (!(await pathExists(repositoryPath))) {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+  await assertGitWorkTree(repositoryPath, org, repo);
+
+  const folderName = deriveWorktreeFolderName(branchName);
+  if (folderName === '.' || folderName === '..') {
+    throw new Error('Invalid worktree folder name derived from branch');
+  }
+  const targetPath = path.join(repoRoot, folderName);
+
+  // Forget entries whose directories were deleted behind git's back so they cannot block the re-add
+  await executeGitCommandInRepo(repositoryPath, ['worktree', 'prune'], { maxBuffer: GIT_BUFFER_SIZES.SMALL });
+
+  const primaryPath = await canonicalPath(repositoryPath);
+  const target = await canonicalPath(targetPath);
+  const worktrees = await Promise.all(
+    (await listWorktrees(repositoryPath))
+      .filter((item): item is WorktreeEntry & { path: string } => Boolean(item.path))
+      .map(async (item) => ({ ...item, canonical: await canonicalPath(item.path) }))
+  );
+
+  const occupant = worktrees.find((item) => item.canonical === target && item.branch !== branchName);
+  if (target === primaryPath || occupant) {
+    throw new WorktreeConflictError(
+      `Worktree directory ${targetPath} belongs to ${occupant ? `branch ${occupant.branch}` : 'the primary clone'}`
+    );
+  }
+
+  const own = worktrees.find((item) => item.branch === branchName);
+  if (own) {
+    if (own.canonical === primaryPath) {
+      throw new WorktreeConflictError(`Branch ${branchName} is checked out in the primary clone`);
+    }
+    try {
+      await executeGitCommandInRepo(repositoryPath, ['worktree', 'remove', '--force', own.path]);
+    } catch (error) {
+      throw new Error(`Failed to remove worktree: ${extractGitErrorMessage(error)}`);
+    }
+  }
+
+  await fs.rm(targetPath, { recursive: true, force: true });
+}
+
+export interface CreateWorktreeOptions extends WorktreeSettings {
   defaultBranchOverride?: string;
   progress?: unknown;
+  /**
+   * Repository-relative directories to materialise via `git sparse-checkout`; empty checks out everything
+   */
+  sparsePaths?: string[];
+  /**
+   * Ref a new branch starts from; defaults to the configured default base, then the synced default branch
+   */
+  base?: string | null;
+  /**
+   * The caller already synced the default branch, e.g. once for a batch of worktrees, so concurrent
+   * creations do not race on the primary clone's checkout
+   */
+  skipDefaultBranchSync?: boolean;
+  /**
+   * The caller already reserved a slot under the worktree limit, as batches do for each branch
+   */
+  skipWorktreeLimit?: boolean;
+}
+
+/**
+ * Checks out the default branch in the primary clone and fast-forwards it from origin
+ */
+async function pullDefaultBranch(
+  repositoryPath: string,
+  defaultBranchOverride: string | undefined,
+  logStep?: (message: string) => void
+): Promise<string> {
+  const defaultBranch = await resolveDefaultBranch(repositoryPath, {
+    override: defaultBranchOverride,
+  });
+  if (defaultBranch) {
+    logStep?.(`Resolved default branch: ${defaultBranch}`);
+  }
+  await executeGitCommandInRepo(repositoryPath, ['checkout', defaultBranch]);
+  logStep?.(`Checked out default branch ${defaultBranch}.`);
+
+  await executeGitCommandInRepo(repositoryPath, [
+    'pull',
+    '--ff-only',
+    'origin',
+    defaultBranch,
+  ]);
+  return defaultBranch;
+}
+
+/**
+ * Brings the primary clone's default branch up to date, as worktree creation does before adding a branch
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param options - Default branch override for the repository
+ * @returns The default branch name
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ */
+export async function syncDefaultBranch(
+  workdir: string,
+  org: string,
+  repo: string,
+  { defaultBranchOverride }: { defaultBranchOverride?: string } = {}
+): Promise<string> {
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  if (!(await pathExists(repositoryPath))) {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+  await assertGitWorkTree(repositoryPath, org, repo);
+  try {
+    return await pullDefaultBranch(repositoryPath, defaultBranchOverride);
+  } catch (error) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    throw new Error(`Failed to sync default branch: ${extractGitErrorMessage(error)}`);
+  }
+}
+
+/**
+ * Copies gitignored files such as `.env`, listed as `seed_paths` in `.agentrix.toml`, from the primary
+ * clone into a new worktree. Paths missing from the clone or already in the worktree are skipped, and a
+ * failed copy only logs a warning.
+ * @param repositoryPath - Path to the primary clone
+ * @param worktreePath - Path to the new worktree
+ * @param seedPaths - Paths relative to the checkout root
+ * @returns The paths that were copied
+ */
+async function seedWorktree(
+  repositoryPath: string,
+  worktreePath: string,
+  seedPaths: readonly string[]
+): Promise<string[]> {
+  const seeded: string[] = [];
+  for (const seedPath of seedPaths) {
+    const source = path.join(repositoryPath, seedPath);
+    const target = path.join(worktreePath, seedPath);
+    try {
+      if (!(await pathExists(source)) || (await pathExists(target))) {
+        continue;
+      }
+      await fs.mkdir(path.dirname(target), { recursive: true });
+      await fs.cp(source, target, { recursive: true, force: false });
+      seeded.push(seedPath);
+    } catch (error: unknown) {
+      console.warn(
+        `[agentrix] Failed to seed ${seedPath} into worktree at ${worktreePath}:`,
+        extractGitErrorMessage(error)
+      );
+    }
+  }
+  return seeded;
 }
 
 /**
@@ -205,6 +941,7 @@ export interface CreateWorktreeOptions {
  * @param repo - Repository name
  * @param branch - Branch name
  * @param options - Options
+ * @throws {WorktreeLimitReachedError} If the repository is at the configured worktree limit
  */
 export async function createWorktree(
   workdir: string,
@@ -213,7 +950,17 @@ export async function createWorktree(
   branch: string,
   options: CreateWorktreeOptions = {}
 ): Promise<void> {
-  const { defaultBranchOverride, progress } = options || {};
+  const {
+    defaultBranchOverride,
+    progress,
+    sparsePaths = [],
+    skipDefaultBranchSync = false,
+    skipWorktreeLimit = false,
+    identity,
+    defaultBase,
+    maxWorktrees,
+    gitConfig,
+  } = options || {};
   const branchName = normalizeBranchName(branch);
   
   if (!branchName) {
@@ -238,6 +985,10 @@ export async function createWorktree(
     }
   }
 
+  if (!skipWorktreeLimit) {
+    await assertWorktreeCapacity(workdir, org, repo, maxWorktrees);
+  }
+
   const describeError = (error: unknown, fallback: string = 'Unknown git error'): string => {
     return extractGitErrorMessage(error, fallback);
   };
@@ -260,34 +1011,40 @@ export async function createWorktree(
     message: 'Preparing repository and syncing default branch.',
   });
 
-  let defaultBranch = '';
   try {
-    defaultBranch = await resolveDefaultBranch(repositoryPath, {
-      override: defaultBranchOverride,
-    });
-    if (defaultBranch) {
-      prog?.logStep?.('sync-default-branch', `Resolved default branch: ${defaultBranch}`);
-    }
-    await executeGitCommandInRepo(repositoryPath, ['checkout', defaultBranch]);
-    prog?.logStep?.('sync-default-branch', `Checked out default branch ${defaultBranch}.`);
-    
-    await executeGitCommandInRepo(repositoryPath, [
-      'pull',
-      '--ff-only',
-      'origin',
-      defaultBranch,
-    ]);
-    
-    prog?.completeStep?.('sync-default-branch', {
+    await assertGitWorkTree(repositoryPath, org, repo);
+  } catch (error) {
+    prog?.failStep?.('sync-default-branch', {
       label: 'Sync default branch',
-      message: `Default branch ${defaultBranch} is up to date.`,
+      message: describeError(error),
     });
+    throw error;
+  }
+
+  try {
+    if (skipDefaultBranchSync) {
+      prog?.skipStep?.('sync-default-branch', {
+        label: 'Sync default branch',
+        message: 'Default branch was already synced.',
+      });
+    } else {
+      const defaultBranch = await pullDefaultBranch(repositoryPath, defaultBranchOverride, (message) =>
+        prog?.logStep?.('sync-default-branch', message)
+      );
+      prog?.completeStep?.('sync-default-branch', {
+        label: 'Sync default branch',
+        message: `Default branch ${defaultBranch} is up to date.`,
+      });
+    }
   } catch (error) {
     const message = describeError(error);
     prog?.failStep?.('sync-default-branch', {
       label: 'Sync default branch',
       message,
     });
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
     throw new Error(`Failed to create worktree: ${message}`);
   }
 
@@ -297,23 +1054,67 @@ export async function createWorktree(
   });
 
   let worktreeAdded = false;
+  const removeAddedWorktree = async (reason: string): Promise<void> => {
+    try {
+      await executeGitCommandInRepo(repositoryPath, [
+        'worktree',
+        'remove',
+        '--force',
+        targetPath,
+      ]);
+    } catch (cleanupError: unknown) {
+      const cleanupErr = cleanupError as { message?: string };
+      console.warn(
+        `[agentrix] Failed to clean up worktree at ${targetPath} after ${reason}:`,
+        cleanupErr?.message || cleanupError
+      );
+    }
+  };
+
   try {
+    const repositoryToml = await loadRepositoryToml(repositoryPath);
+    const base = resolveBaseRef(options?.base?.trim() || repositoryToml.defaultBase, defaultBase);
     const exists = await branchExists(repositoryPath, branchName);
-    const args = ['worktree', 'add'];
+    const args = [...gitConfigArgs(gitConfig), 'worktree', 'add'];
+    if (sparsePaths.length > 0) {
+      // Checked out below once the sparse paths are set, so excluded files are never written
+      args.push('--no-checkout');
+    }
     if (!exists) {
       args.push('-b', branchName);
     }
     args.push(targetPath);
     if (exists) {
       args.push(branchName);
+    } else if (base) {
+      await assertBaseRef(repositoryPath, base);
+      args.push(base);
     }
     await executeGitCommandInRepo(repositoryPath, args);
     worktreeAdded = true;
+    try {
+      await applyGitIdentity(targetPath, identity ?? {});
+    } catch (identityError: unknown) {
+      console.warn(
+        `[agentrix] Failed to set git identity for worktree at ${targetPath}:`,
+        describeError(identityError)
+      );
+    }
+    if (sparsePaths.length > 0) {
+      // Scoped to this worktree: git enables extensions.worktreeConfig so the main clone stays full
+      await executeGitCommandInRepo(targetPath, ['sparse-checkout', 'set', '--', ...sparsePaths]);
+      await executeGitCommandInRepo(targetPath, ['checkout']);
+      prog?.logStep?.('create-worktree', `Limited checkout to ${sparsePaths.join(', ')}.`);
+    }
+    const seeded = await seedWorktree(repositoryPath, targetPath, repositoryToml.seedPaths);
+    if (seeded.length > 0) {
+      prog?.logStep?.('create-worktree', `Copied ${seeded.join(', ')} from the primary clone.`);
+    }
     prog?.completeStep?.('create-worktree', {
       label: 'Create worktree',
       message: exists
         ? `Attached existing branch ${branchName} to ${targetPath}.`
-        : `Created new branch ${branchName} at ${targetPath}.`,
+        : `Created new branch ${branchName}${base ? ` from ${base}` : ''} at ${targetPath}.`,
     });
   } catch (error) {
     const message = describeError(error);
@@ -321,6 +1122,12 @@ export async function createWorktree(
       label: 'Create worktree',
       message,
     });
+    if (worktreeAdded) {
+      await removeAddedWorktree('sparse checkout failure');
+    }
+    if (error instanceof GitNotFoundError || error instanceof InvalidBaseRefError) {
+      throw error;
+    }
     throw new Error(`Failed to create worktree: ${message}`);
   }
 
@@ -349,25 +1156,98 @@ export async function createWorktree(
       message,
     });
     if (worktreeAdded) {
-      try {
-        await executeGitCommandInRepo(repositoryPath, [
-          'worktree',
-          'remove',
-          '--force',
-          targetPath,
-        ]);
-      } catch (cleanupError: unknown) {
-        const cleanupErr = cleanupError as { message?: string };
-        console.warn(
-          `[agentrix] Failed to clean up worktree at ${targetPath} after init command failure:`,
-          cleanupErr?.message || cleanupError
-        );
-      }
+      await removeAddedWorktree('init command failure');
     }
     throw new Error(`Failed to create worktree: ${message}`);
   }
 }
 
+/**
+ * Worktrees created at once by a batch request
+ */
+export const WORKTREE_BATCH_CONCURRENCY = 4;
+
+export type WorktreeBatchItemResult =
+  | { branch: string; status: 'created'; worktreePath: string }
+  | { branch: string; status: 'failed'; error: { message: string; statusCode: number; code: string | null } };
+
+export interface CreateWorktreesOptions extends WorktreeSettings {
+  defaultBranchOverride?: string;
+  base?: string | null;
+  concurrency?: number;
+}
+
+function describeBatchFailure(error: unknown): { message: string; statusCode: number; code: string | null } {
+  const err = error as { statusCode?: unknown; code?: unknown };
+  return {
+    message: extractGitErrorMessage(error, 'Failed to create worktree'),
+    statusCode: typeof err?.statusCode === 'number' ? err.statusCode : 500,
+    code: typeof err?.code === 'string' ? err.code : null,
+  };
+}
+
+/**
+ * Creates worktrees for several branches, syncing the default branch once and then adding the
+ * worktrees with bounded concurrency. A branch that fails does not stop the others. Under a worktree
+ * limit, branches are given the free slots in request order and the rest fail with `worktree_limit_reached`.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branches - Branch names, in request order
+ * @param options - Options shared by every worktree
+ * @returns One result per requested branch, in request order
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ */
+export async function createWorktrees(
+  workdir: string,
+  org: string,
+  repo: string,
+  branches: readonly string[],
+  { defaultBranchOverride, base, concurrency = WORKTREE_BATCH_CONCURRENCY, ...settings }: CreateWorktreesOptions = {}
+): Promise<WorktreeBatchItemResult[]> {
+  await syncDefaultBranch(workdir, org, repo, { defaultBranchOverride });
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  // Counted once: concurrent creations checking for themselves would each see the same count
+  let freeSlots = await remainingWorktreeSlots(repositoryPath, settings.maxWorktrees);
+
+  const seen = new Set<string>();
+  const duplicates = branches.map((branch) => {
+    const key = normalizeBranchName(branch);
+    const duplicate = seen.has(key);
+    seen.add(key);
+    return duplicate;
+  });
+
+  return mapWithConcurrency(branches, concurrency, async (branch, index): Promise<WorktreeBatchItemResult> => {
+    let reserved = false;
+    try {
+      if (duplicates[index]) {
+        throw new WorktreeConflictError(`Branch ${branch} is listed more than once`);
+      }
+      // Reserved before the first await, so slots go to branches in request order
+      if (freeSlots < 1) {
+        throw new WorktreeLimitReachedError(org, repo, resolveWorktreeLimit(settings.maxWorktrees) ?? 0);
+      }
+      freeSlots -= 1;
+      reserved = true;
+      const plan = await planWorktree(workdir, org, repo, branch);
+      await createWorktree(workdir, org, repo, plan.branch, {
+        ...settings,
+        defaultBranchOverride,
+        base,
+        skipDefaultBranchSync: true,
+        skipWorktreeLimit: true,
+      });
+      return { branch, status: 'created', worktreePath: plan.worktreePath };
+    } catch (error: unknown) {
+      if (reserved) {
+        freeSlots += 1;
+      }
+      return { branch, status: 'failed', error: describeBatchFailure(error) };
+    }
+  });
+}
+
 export interface WorktreePathResult {
   repositoryPath: string;
   worktreePath: string;
@@ -392,7 +1272,7 @@ export async function getWorktreePath(
   const match = worktrees.find((item) => item.branch === branch);
   
   if (!match || !match.path) {
-    throw new Error(`Worktree for ${org}/${repo} branch ${branch} not found`);
+    throw new WorktreeNotFoundError(org, repo, branch);
   }
   
   return { repositoryPath, worktreePath: match.path };
@@ -441,3 +1321,167 @@ export async function removeWorktree(
     throw new Error(`Failed to remove worktree: ${message}`);
   }
 }
+
+/**
+ * Builds `-c` arguments carrying the configured identity. Passed per command so worktrees created before
+ * the identity was configured still pick it up.
+ * @param identity - Configured identity; missing values are left out
+ */
+export function identityConfigArgs(identity: GitIdentity | null = {}): string[] {
+  const args: string[] = [];
+  const name = identity?.name?.trim();
+  const email = identity?.email?.trim();
+  if (name) {
+    args.push('-c', `user.name=${name}`);
+  }
+  if (email) {
+    args.push('-c', `user.email=${email}`);
+  }
+  return args;
+}
+
+export interface RenameWorktreeResult {
+  branch: string;
+  previousBranch: string;
+  worktreePath: string;
+}
+
+/**
+ * Checks whether a path exists on disk
+ * @param targetPath - Path to check
+ * @returns True if the path exists
+ */
+async function pathExists(targetPath: string): Promise<boolean> {
+  try {
+    await fs.access(targetPath);
+    return true;
+  } catch (error: unknown) {
+    const err = error as { code?: string };
+    if (err && err.code === 'ENOENT') {
+      return false;
+    }
+    throw error;
+  }
+}
+
+export interface WorktreeRenamePlan {
+  currentBranch: string;
+  targetBranch: string;
+  repositoryPath: string;
+  currentPath: string;
+  targetPath: string;
+  needsMove: boolean;
+}
+
+/**
+ * Runs the checks performed before renaming a worktree without touching it, so callers can reject a
+ * rename before tearing down the worktree's sessions
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Current branch name
+ * @param newBranch - Desired branch name
+ * @returns The normalized branches and where the worktree is and would move to
+ * @throws {ValidationError} If a name is empty, unchanged or maps to an invalid folder
+ * @throws {WorktreeNotFoundError} If no worktree exists for the current branch
+ * @throws {WorktreeConflictError} If the new branch or its directory already exists
+ */
+export async function planWorktreeRename(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  newBranch: string
+): Promise<WorktreeRenamePlan> {
+  const currentBranch = normalizeBranchName(branch);
+  const targetBranch = normalizeBranchName(newBranch);
+
+  if (!currentBranch || !targetBranch) {
+    throw new ValidationError('Branch name cannot be empty');
+  }
+
+  if (currentBranch === targetBranch) {
+    throw new ValidationError('New branch name must differ from the current branch');
+  }
+
+  const folderName = deriveWorktreeFolderName(targetBranch);
+  if (folderName === '.' || folderName === '..') {
+    throw new ValidationError('Invalid worktree folder name derived from branch');
+  }
+
+  const { repoRoot, repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  const worktrees = await listWorktrees(repositoryPath);
+  const entry = worktrees.find((item) => item.branch === currentBranch);
+
+  if (!entry || !entry.path) {
+    throw new WorktreeNotFoundError(org, repo, currentBranch);
+  }
+
+  if (await branchExists(repositoryPath, targetBranch)) {
+    throw new WorktreeConflictError(`Branch ${targetBranch} already exists`);
+  }
+
+  const currentPath = path.resolve(entry.path);
+  const targetPath = path.join(repoRoot, folderName);
+  const needsMove = path.resolve(targetPath) !== currentPath;
+
+  if (needsMove && (await pathExists(targetPath))) {
+    throw new WorktreeConflictError(`Worktree directory already exists at ${targetPath}`);
+  }
+
+  return { currentBranch, targetBranch, repositoryPath, currentPath, targetPath, needsMove };
+}
+
+/**
+ * Renames the branch checked out in a worktree and moves the worktree directory to match. Protecting the
+ * default branch is left to the caller, which knows the repository's configured default.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Current branch name
+ * @param newBranch - Desired branch name
+ * @returns Renamed branch details and the new worktree path
+ * @throws {ValidationError} If a name is empty, unchanged or maps to an invalid folder
+ * @throws {WorktreeNotFoundError} If no worktree exists for the current branch
+ * @throws {WorktreeConflictError} If the new branch or its directory already exists
+ */
+export async function renameWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  newBranch: string
+): Promise<RenameWorktreeResult> {
+  const { currentBranch, targetBranch, repositoryPath, currentPath, targetPath, needsMove } =
+    await planWorktreeRename(workdir, org, repo, branch, newBranch);
+
+  try {
+    await executeGitCommandInRepo(currentPath, ['branch', '-m', currentBranch, targetBranch]);
+  } catch (error) {
+    const message = extractGitErrorMessage(error);
+    throw new Error(`Failed to rename branch: ${message}`);
+  }
+
+  if (needsMove) {
+    try {
+      await executeGitCommandInRepo(repositoryPath, ['worktree', 'move', currentPath, targetPath]);
+    } catch (error) {
+      const message = extractGitErrorMessage(error);
+      try {
+        await executeGitCommandInRepo(currentPath, ['branch', '-m', targetBranch, currentBranch]);
+      } catch (rollbackError: unknown) {
+        console.warn(
+          `[agentrix] Failed to restore branch ${currentBranch} after worktree move failure:`,
+          extractGitErrorMessage(rollbackError)
+        );
+      }
+      throw new Error(`Failed to move worktree: ${message}`);
+    }
+  }
+
+  return {
+    branch: targetBranch,
+    previousBranch: currentBranch,
+    worktreePath: needsMove ? targetPath : currentPath,
+  };
+}
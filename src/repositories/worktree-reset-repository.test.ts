@@ -0,0 +1,99 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { describe, it } from 'node:test';
+
+import { InvalidResetRefError, NoUpstreamError, resetWorktree } from './worktree-reset-repository.js';
+
+const execGit = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execGit('git', ['-C', cwd, ...args])).stdout.trim();
+
+/**
+ * Creates a temporary workdir holding a real acme/demo repository with a `feature` worktree checked out
+ */
+async function createWorkdirWithWorktree(): Promise<{ workdir: string; worktreePath: string }> {
+  const workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-reset-'));
+  const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+  const worktreePath = path.join(workdir, 'acme', 'demo', 'feature');
+  await fs.mkdir(repositoryPath, { recursive: true });
+  await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+  await git(repositoryPath, [
+    '-c',
+    'user.name=Seed',
+    '-c',
+    'user.email=seed@example.com',
+    'commit',
+    '--quiet',
+    '--allow-empty',
+    '-m',
+    'Initial commit',
+  ]);
+  await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature', worktreePath]);
+  return { workdir, worktreePath };
+}
+
+describe('resetWorktree', () => {
+  const commitAs = ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet'];
+
+  it('resets a dirtied worktree to its upstream and removes untracked files', async () => {
+    const { workdir, worktreePath } = await createWorkdirWithWorktree();
+    const remotePath = path.join(workdir, 'remote.git');
+    try {
+      await fs.writeFile(path.join(worktreePath, 'notes.md'), 'pushed\n');
+      await fs.writeFile(path.join(worktreePath, '.gitignore'), 'node_modules/\n');
+      await git(worktreePath, ['add', '-A']);
+      await git(worktreePath, [...commitAs, '-m', 'Pushed']);
+      await execGit('git', ['init', '--quiet', '--bare', remotePath]);
+      await git(worktreePath, ['remote', 'add', 'origin', remotePath]);
+      await git(worktreePath, ['push', '--quiet', '-u', 'origin', 'feature']);
+      const upstreamSha = await git(worktreePath, ['rev-parse', 'HEAD']);
+
+      await git(worktreePath, [...commitAs, '--allow-empty', '-m', 'Local only']);
+      const localSha = await git(worktreePath, ['rev-parse', 'HEAD']);
+      await fs.writeFile(path.join(worktreePath, 'notes.md'), 'broken\n');
+      await fs.mkdir(path.join(worktreePath, 'scratch'));
+      await fs.writeFile(path.join(worktreePath, 'scratch', 'tmp.txt'), 'junk\n');
+      await fs.mkdir(path.join(worktreePath, 'node_modules'));
+      await fs.writeFile(path.join(worktreePath, 'node_modules', 'dep.js'), 'kept\n');
+
+      const result = await resetWorktree(workdir, 'acme', 'demo', 'feature');
+
+      assert.deepEqual(result, {
+        branch: 'feature',
+        target: 'origin/feature',
+        previousSha: localSha,
+        sha: upstreamSha,
+        discardedFiles: ['notes.md'],
+        removedFiles: ['scratch/'],
+      });
+      assert.equal(await git(worktreePath, ['status', '--porcelain']), '');
+      assert.equal(await fs.readFile(path.join(worktreePath, 'notes.md'), 'utf8'), 'pushed\n');
+      assert.equal(await fs.readFile(path.join(worktreePath, 'node_modules', 'dep.js'), 'utf8'), 'kept\n');
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+
+  it('requires a ref when the branch has no upstream and rejects unknown refs', async () => {
+    const { workdir, worktreePath } = await createWorkdirWithWorktree();
+    try {
+      await assert.rejects(resetWorktree(workdir, 'acme', 'demo', 'feature'), (error: unknown) => {
+        assert.ok(error instanceof NoUpstreamError);
+        assert.equal(error.statusCode, 400);
+        assert.equal(error.code, 'no_upstream');
+        return true;
+      });
+      await assert.rejects(resetWorktree(workdir, 'acme', 'demo', 'feature', { ref: 'nope' }), InvalidResetRefError);
+
+      await fs.writeFile(path.join(worktreePath, 'draft.md'), 'draft\n');
+      const result = await resetWorktree(workdir, 'acme', 'demo', 'feature', { ref: 'main' });
+      assert.equal(result.target, 'main');
+      assert.deepEqual(result.removedFiles, ['draft.md']);
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+});
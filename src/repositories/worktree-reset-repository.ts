@@ -0,0 +1,129 @@
+import { executeGitCommandInRepo, GIT_BUFFER_SIZES } from './git-repository.js';
+import { getWorktreePath } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+
+/**
+ * Error raised when a reset has no target: the branch tracks no upstream and no ref was given
+ */
+export class NoUpstreamError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'no_upstream';
+
+  constructor(branch: string) {
+    super(`Branch ${branch} has no upstream; pass the ref to reset to`);
+    this.name = 'NoUpstreamError';
+  }
+}
+
+/**
+ * Error raised when the ref a worktree should be reset to does not resolve to a commit
+ */
+export class InvalidResetRefError extends Error {
+  public readonly statusCode: number = 400;
+  public readonly code = 'invalid_ref';
+
+  constructor(ref: string) {
+    super(`Ref ${ref} does not resolve to a commit`);
+    this.name = 'InvalidResetRefError';
+  }
+}
+
+export interface ResetWorktreeOptions {
+  /**
+   * Ref to reset to; defaults to the branch's upstream
+   */
+  ref?: string;
+}
+
+export interface ResetWorktreeResult {
+  branch: string;
+  /**
+   * The ref the worktree was reset to, e.g. `origin/feature`
+   */
+  target: string;
+  previousSha: string;
+  sha: string;
+  /**
+   * Tracked files whose uncommitted changes were discarded
+   */
+  discardedFiles: string[];
+  /**
+   * Untracked files and directories deleted by `git clean`; ignored files are kept
+   */
+  removedFiles: string[];
+}
+
+/**
+ * Discards every local change in a worktree: resets the branch hard to its upstream (or a given ref)
+ * and deletes untracked files, leaving ignored files such as installed dependencies alone
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param options - Ref to reset to
+ * @returns The commits before and after, and the files that were discarded or removed
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ * @throws {NoUpstreamError} If no ref is given and the branch has no upstream
+ * @throws {InvalidResetRefError} If the ref does not resolve to a commit
+ */
+export async function resetWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  options: ResetWorktreeOptions = {}
+): Promise<ResetWorktreeResult> {
+  const branchName = normalizeBranchName(branch);
+
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  const run = async (args: string[]): Promise<string> =>
+    (await executeGitCommandInRepo(worktreePath, args, { maxBuffer: GIT_BUFFER_SIZES.MEDIUM })).stdout;
+
+  let target = options.ref?.trim() || '';
+  if (!target) {
+    try {
+      target = (await run(['rev-parse', '--abbrev-ref', '--symbolic-full-name', '@{u}'])).trim();
+    } catch {
+      throw new NoUpstreamError(branchName);
+    }
+  }
+  // A leading dash would be read as an option by reset
+  if (target.startsWith('-')) {
+    throw new InvalidResetRefError(target);
+  }
+
+  let sha: string;
+  try {
+    sha = (await run(['rev-parse', '--verify', '--quiet', `${target}^{commit}`])).trim();
+  } catch {
+    throw new InvalidResetRefError(target);
+  }
+
+  const [previousSha, status] = await Promise.all([run(['rev-parse', 'HEAD']), run(['status', '--porcelain', '-z'])]);
+  const discardedFiles: string[] = [];
+  const entries = status.split('\0');
+  for (let index = 0; index < entries.length; index += 1) {
+    const entry = entries[index] ?? '';
+    if (entry.length <= 3 || entry.startsWith('??')) {
+      continue;
+    }
+    discardedFiles.push(entry.slice(3));
+    // Renames and copies are followed by their source path, which is not a status entry
+    if (/^[RC]|^.[RC]/.test(entry)) {
+      index += 1;
+    }
+  }
+
+  await run(['reset', '--quiet', '--hard', sha]);
+  const cleaned = await run(['clean', '-fd']);
+  const removedFiles = cleaned
+    .split('\n')
+    .map((line) => line.match(/^Removing (.+)$/)?.[1])
+    .filter((file): file is string => Boolean(file));
+
+  return { branch: branchName, target, previousSha: previousSha.trim(), sha, discardedFiles, removedFiles };
+}
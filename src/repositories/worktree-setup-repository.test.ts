@@ -0,0 +1,147 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { after, afterEach, before, describe, it, mock } from 'node:test';
+
+import {
+  __setWorktreeSetupTestOverrides,
+  runWorktreeSetup,
+  SetupCommandNotConfiguredError,
+} from './worktree-setup-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+
+describe('runWorktreeSetup', () => {
+  let workdir: string;
+  let worktreePath: string;
+  const originalShell = process.env['SHELL'];
+
+  before(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-setup-'));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    worktreePath = path.join(workdir, 'acme', 'demo', 'login');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', worktreePath]);
+    // Keep login shells from sourcing the developer's profile
+    process.env['SHELL'] = '/bin/sh';
+  });
+
+  afterEach(async () => {
+    __setWorktreeSetupTestOverrides();
+    await fs.rm(path.join(worktreePath, '.agentrix'), { recursive: true, force: true });
+    await fs.rm(path.join(worktreePath, '.agentrix.toml'), { force: true });
+  });
+
+  after(async () => {
+    if (originalShell === undefined) {
+      delete process.env['SHELL'];
+    } else {
+      process.env['SHELL'] = originalShell;
+    }
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('refuses to run when nothing is configured', async () => {
+    await assert.rejects(runWorktreeSetup(workdir, 'acme', 'demo', 'feature/login'), SetupCommandNotConfiguredError);
+  });
+
+  it('runs the configured command in the worktree with the terminal environment', async () => {
+    const result = await runWorktreeSetup(workdir, 'acme', 'demo', 'feature/login', {
+      setupCommand: 'pwd; echo "token=$SETUP_TOKEN"',
+      terminalEnv: { SETUP_TOKEN: 'abc123' },
+    });
+
+    assert.equal(result.source, 'config');
+    assert.equal(result.exitCode, 0);
+    assert.equal(result.timedOut, false);
+    assert.deepEqual(result.output.trim().split('\n'), [await fs.realpath(worktreePath), 'token=abc123']);
+  });
+
+  it("picks up the setup command from the repository's .agentrix.toml", async () => {
+    await fs.writeFile(
+      path.join(worktreePath, '.agentrix.toml'),
+      '# Repository settings\nsetup_command = "echo from-repository"\n\n[editor]\ntheme = "dark"\n'
+    );
+
+    const result = await runWorktreeSetup(workdir, 'acme', 'demo', 'feature/login', { setupCommand: 'echo from-config' });
+
+    assert.equal(result.source, 'repository');
+    assert.equal(result.command, 'echo from-repository');
+    assert.equal(result.output.trim(), 'from-repository');
+  });
+
+  it('falls back to the server setup command when .agentrix.toml is malformed', async () => {
+    const warn = mock.method(console, 'warn', () => {});
+    await fs.writeFile(path.join(worktreePath, '.agentrix.toml'), 'setup_command = "echo unterminated\n');
+
+    const result = await runWorktreeSetup(workdir, 'acme', 'demo', 'feature/login', { setupCommand: 'echo from-config' });
+    warn.mock.restore();
+
+    assert.equal(result.source, 'config');
+    assert.equal(result.output.trim(), 'from-config');
+    assert.equal(warn.mock.callCount(), 1);
+    assert.match(String(warn.mock.calls[0]?.arguments[0]), /\.agentrix\.toml/);
+  });
+
+  it('prefers the worktree setup script and reports its exit status', async () => {
+    await fs.mkdir(path.join(worktreePath, '.agentrix'));
+    await fs.writeFile(path.join(worktreePath, '.agentrix', 'setup'), 'echo bootstrapping\necho oops >&2\nexit 3\n');
+
+    const result = await runWorktreeSetup(workdir, 'acme', 'demo', 'feature/login', { setupCommand: 'echo from-config' });
+
+    assert.equal(result.source, 'script');
+    assert.equal(result.command, path.join('.agentrix', 'setup'));
+    assert.equal(result.exitCode, 3);
+    assert.match(result.output, /bootstrapping/);
+    assert.match(result.output, /oops/);
+    assert.doesNotMatch(result.output, /from-config/);
+  });
+
+  it('kills runs that exceed the timeout', async () => {
+    __setWorktreeSetupTestOverrides({ timeoutMs: 100 });
+
+    const result = await runWorktreeSetup(workdir, 'acme', 'demo', 'feature/login', { setupCommand: 'sleep 5' });
+
+    assert.equal(result.timedOut, true);
+    assert.equal(result.exitCode, null);
+    assert.equal(result.signal, 'SIGKILL');
+  });
+
+  it('passes each output line to the listener in order', async () => {
+    const lines: string[] = [];
+
+    const result = await runWorktreeSetup(
+      workdir,
+      'acme',
+      'demo',
+      'feature/login',
+      { setupCommand: 'echo installing; sleep 0.05; echo linking; sleep 0.05; printf done' },
+      { onOutput: (line) => lines.push(line) }
+    );
+
+    assert.equal(result.exitCode, 0);
+    assert.deepEqual(lines, ['installing', 'linking', 'done']);
+  });
+
+  it('kills the run when the listener aborts', async () => {
+    const controller = new AbortController();
+
+    const result = await runWorktreeSetup(
+      workdir,
+      'acme',
+      'demo',
+      'feature/login',
+      { setupCommand: 'echo started; sleep 5' },
+      { onOutput: () => controller.abort(), signal: controller.signal }
+    );
+
+    assert.equal(result.timedOut, false);
+    assert.equal(result.signal, 'SIGKILL');
+  });
+});
@@ -0,0 +1,265 @@
+import { spawn } from 'node:child_process';
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { getWorktreePath } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+import { resolveTerminalEnvironment } from '../core/terminal-env.js';
+import { loadRepositoryToml, REPOSITORY_TOML_FILENAME } from '../core/repository-toml.js';
+import { createLineSplitter, type LineSplitter, type ProcessOutputOptions } from '../utils/process-output.js';
+
+/**
+ * Per-worktree setup script, run in preference to the configured setup command
+ */
+export const SETUP_SCRIPT_FILE = path.join('.agentrix', 'setup');
+
+/**
+ * How long a setup run may take before it is killed
+ */
+export const SETUP_COMMAND_TIMEOUT_MS = 10 * 60 * 1000;
+
+/**
+ * Trailing output kept from a setup run
+ */
+export const SETUP_OUTPUT_LIMIT_BYTES = 64 * 1024;
+
+/**
+ * Error raised when neither a setup script nor a setup command is configured
+ */
+export class SetupCommandNotConfiguredError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'setup_not_configured';
+
+  constructor(org: string, repo: string, branch: string) {
+    super(
+      `No setup command is configured for ${org}/${repo} (${branch}); add ${SETUP_SCRIPT_FILE}, set ` +
+        `setup_command in ${REPOSITORY_TOML_FILENAME} or start the server with --setup-command`
+    );
+    this.name = 'SetupCommandNotConfiguredError';
+  }
+}
+
+export interface WorktreeSetupResult {
+  branch: string;
+  worktreePath: string;
+  /**
+   * The command that ran: the setup script path or the configured command
+   */
+  command: string;
+  /**
+   * Where the command came from: the setup script, `.agentrix.toml`, or `--setup-command`
+   */
+  source: 'script' | 'repository' | 'config';
+  /**
+   * Exit code, or null when the process was killed by a signal
+   */
+  exitCode: number | null;
+  signal: string | null;
+  timedOut: boolean;
+  durationMs: number;
+  /**
+   * Interleaved stdout and stderr, cut to the last SETUP_OUTPUT_LIMIT_BYTES
+   */
+  output: string;
+  truncated: boolean;
+}
+
+interface ProcessResult {
+  exitCode: number | null;
+  signal: string | null;
+  timedOut: boolean;
+  output: string;
+  truncated: boolean;
+}
+
+interface Dependencies {
+  timeoutMs: number;
+  now: () => number;
+}
+
+let activeDependencies: Dependencies = { timeoutMs: SETUP_COMMAND_TIMEOUT_MS, now: () => Date.now() };
+
+export interface RunWorktreeSetupOptions {
+  /**
+   * Shell command, such as `npm ci`, run when the worktree has neither a setup script nor a `setup_command`
+   */
+  setupCommand?: string | null;
+  /**
+   * Variables from `--terminal-env` and the config file, added to the process's environment
+   */
+  terminalEnv?: Record<string, string> | null;
+}
+
+/**
+ * @internal Utility for tests to shorten the timeout
+ */
+export function __setWorktreeSetupTestOverrides(overrides?: Partial<Dependencies>): void {
+  activeDependencies = { timeoutMs: SETUP_COMMAND_TIMEOUT_MS, now: () => Date.now(), ...overrides };
+}
+
+function resolveShell(): string {
+  const candidate = typeof process.env['SHELL'] === 'string' ? process.env['SHELL'].trim() : '';
+  return candidate || '/bin/sh';
+}
+
+async function findSetupScript(worktreePath: string): Promise<{ path: string; executable: boolean } | null> {
+  const scriptPath = path.join(worktreePath, SETUP_SCRIPT_FILE);
+  try {
+    const stats = await fs.stat(scriptPath);
+    return stats.isFile() ? { path: scriptPath, executable: (stats.mode & 0o111) !== 0 } : null;
+  } catch (error: unknown) {
+    const code = (error as { code?: string })?.code;
+    if (code === 'ENOENT' || code === 'ENOTDIR') {
+      return null;
+    }
+    throw error;
+  }
+}
+
+function runProcess(
+  file: string,
+  args: string[],
+  options: { cwd: string; env: NodeJS.ProcessEnv; timeoutMs: number },
+  output: ProcessOutputOptions = {}
+): Promise<ProcessResult> {
+  return new Promise((resolve, reject) => {
+    // Own process group, so a timeout also stops whatever the script started
+    const child = spawn(file, args, {
+      cwd: options.cwd,
+      env: options.env,
+      stdio: ['ignore', 'pipe', 'pipe'],
+      detached: true,
+    });
+
+    const chunks: Buffer[] = [];
+    let buffered = 0;
+    let truncated = false;
+    const collect = (chunk: Buffer): void => {
+      chunks.push(chunk);
+      buffered += chunk.length;
+      while (buffered > SETUP_OUTPUT_LIMIT_BYTES && chunks.length > 0) {
+        const excess = buffered - SETUP_OUTPUT_LIMIT_BYTES;
+        const first = chunks[0]!;
+        if (first.length <= excess) {
+          chunks.shift();
+          buffered -= first.length;
+        } else {
+          chunks[0] = first.subarray(excess);
+          buffered -= excess;
+        }
+        truncated = true;
+      }
+    };
+    const follow = (chunk: Buffer, lines: LineSplitter | null): void => {
+      collect(chunk);
+      lines?.write(chunk);
+    };
+    const stdoutLines = output.onOutput ? createLineSplitter(output.onOutput) : null;
+    const stderrLines = output.onOutput ? createLineSplitter(output.onOutput) : null;
+    child.stdout?.on('data', (chunk: Buffer) => follow(chunk, stdoutLines));
+    child.stderr?.on('data', (chunk: Buffer) => follow(chunk, stderrLines));
+
+    const killGroup = (): void => {
+      try {
+        if (child.pid === undefined) {
+          throw new Error('process has no pid');
+        }
+        process.kill(-child.pid, 'SIGKILL');
+      } catch {
+        child.kill('SIGKILL');
+      }
+    };
+
+    let timedOut = false;
+    const timer = setTimeout(() => {
+      timedOut = true;
+      killGroup();
+    }, options.timeoutMs);
+    if (output.signal?.aborted) {
+      killGroup();
+    } else {
+      output.signal?.addEventListener('abort', killGroup, { once: true });
+    }
+
+    child.on('error', (error) => {
+      clearTimeout(timer);
+      output.signal?.removeEventListener('abort', killGroup);
+      reject(error);
+    });
+    child.on('close', (exitCode, signal) => {
+      clearTimeout(timer);
+      output.signal?.removeEventListener('abort', killGroup);
+      stdoutLines?.end();
+      stderrLines?.end();
+      resolve({
+        exitCode,
+        signal,
+        timedOut,
+        output: Buffer.concat(chunks).toString('utf8'),
+        truncated,
+      });
+    });
+  });
+}
+
+/**
+ * Runs a worktree's setup step (e.g. `npm ci`) and waits for it to finish. The worktree's
+ * `.agentrix/setup` script is used when present (executed directly when executable, otherwise through
+ * the shell); otherwise the `setup_command` from the worktree's `.agentrix.toml`, falling back to the one
+ * configured with `--setup-command`, runs in a login shell. The process
+ * gets the terminal environment variables and is killed after SETUP_COMMAND_TIMEOUT_MS.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param options - Configured setup command and terminal environment
+ * @param output - Follows the output line by line; aborting kills the process and whatever it started
+ * @returns Exit status and trailing output; a failing command is reported, not thrown
+ * @throws {WorktreeNotFoundError} If the branch has no worktree
+ * @throws {SetupCommandNotConfiguredError} If there is nothing to run
+ */
+export async function runWorktreeSetup(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  options: RunWorktreeSetupOptions = {},
+  output: ProcessOutputOptions = {}
+): Promise<WorktreeSetupResult> {
+  const branchName = normalizeBranchName(branch);
+  if (!branchName) {
+    throw new Error('branch is required');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  const script = await findSetupScript(worktreePath);
+  const repositoryCommand = script ? null : (await loadRepositoryToml(worktreePath)).setupCommand;
+  const setupCommand = repositoryCommand ?? (options.setupCommand?.trim() || null);
+  if (!script && !setupCommand) {
+    throw new SetupCommandNotConfiguredError(org, repo, branchName);
+  }
+
+  const shell = resolveShell();
+  const [file, args, command]: [string, string[], string] = script
+    ? script.executable
+      ? [script.path, [], SETUP_SCRIPT_FILE]
+      : [shell, [script.path], SETUP_SCRIPT_FILE]
+    : [shell, ['-l', '-c', setupCommand!], setupCommand!];
+
+  const env = { ...process.env, ...(await resolveTerminalEnvironment(worktreePath, options.terminalEnv)) };
+  const startedAt = activeDependencies.now();
+  const result = await runProcess(
+    file,
+    args,
+    { cwd: worktreePath, env, timeoutMs: activeDependencies.timeoutMs },
+    output
+  );
+
+  return {
+    branch: branchName,
+    worktreePath,
+    command,
+    source: script ? 'script' : repositoryCommand ? 'repository' : 'config',
+    durationMs: activeDependencies.now() - startedAt,
+    ...result,
+  };
+}
@@ -0,0 +1,120 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, beforeEach, describe, it } from 'node:test';
+
+import {
+  listWorktreeStashes,
+  popWorktreeStash,
+  pushWorktreeStash,
+  StashConflictError,
+  StashStateError,
+} from './worktree-stash-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+const commitAs = ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet'];
+const identity = { name: 'Agent Bot', email: 'agent@example.com' };
+
+describe('worktree stashes', () => {
+  let workdir: string;
+  let repositoryPath: string;
+  let worktreePath: string;
+
+  beforeEach(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-stash-'));
+    repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    worktreePath = path.join(workdir, 'acme', 'demo', 'login');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await fs.writeFile(path.join(repositoryPath, 'notes.md'), 'one\n');
+    await git(repositoryPath, ['add', '-A']);
+    await git(repositoryPath, [...commitAs, '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', worktreePath]);
+  });
+
+  afterEach(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('pushes changes to a stash and pops them back', async () => {
+    await fs.writeFile(path.join(worktreePath, 'notes.md'), 'one\ntwo\n');
+    await fs.writeFile(path.join(worktreePath, 'draft.md'), 'untracked\n');
+
+    const pushed = await pushWorktreeStash(workdir, 'acme', 'demo', 'feature/login', {
+      message: 'Park the draft',
+      includeUntracked: true,
+      identity,
+    });
+
+    assert.equal(pushed.branch, 'feature/login');
+    assert.equal(pushed.stash.ref, 'stash@{0}');
+    assert.equal(pushed.stash.sha, await git(worktreePath, ['rev-parse', 'stash@{0}']));
+    assert.equal(pushed.stash.message, 'Park the draft');
+    assert.equal(await git(worktreePath, ['status', '--porcelain']), '');
+    assert.deepEqual(await listWorktreeStashes(workdir, 'acme', 'demo', 'feature/login'), {
+      branch: 'feature/login',
+      stashes: [pushed.stash],
+    });
+
+    const popped = await popWorktreeStash(workdir, 'acme', 'demo', 'feature/login');
+
+    assert.deepEqual(popped, pushed);
+    assert.equal(await fs.readFile(path.join(worktreePath, 'notes.md'), 'utf8'), 'one\ntwo\n');
+    assert.equal(await fs.readFile(path.join(worktreePath, 'draft.md'), 'utf8'), 'untracked\n');
+    assert.equal(await git(worktreePath, ['stash', 'list']), '');
+    await assert.rejects(
+      popWorktreeStash(workdir, 'acme', 'demo', 'feature/login'),
+      (error: unknown) => error instanceof StashStateError && error.code === 'no_stash'
+    );
+  });
+
+  it('keeps stashes from other worktrees out of the list and refuses a clean tree', async () => {
+    await fs.writeFile(path.join(repositoryPath, 'notes.md'), 'main\n');
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'stash', 'push', '-m', 'Main work']);
+
+    assert.deepEqual((await listWorktreeStashes(workdir, 'acme', 'demo', 'feature/login')).stashes, []);
+    await assert.rejects(
+      pushWorktreeStash(workdir, 'acme', 'demo', 'feature/login', { identity }),
+      (error: unknown) => error instanceof StashStateError && error.code === 'nothing_to_stash'
+    );
+    await assert.rejects(
+      popWorktreeStash(workdir, 'acme', 'demo', 'feature/login'),
+      (error: unknown) => error instanceof StashStateError && error.code === 'no_stash'
+    );
+    assert.match(await git(repositoryPath, ['stash', 'list']), /On main: Main work/);
+  });
+
+  it('reports a conflicting pop with its files and keeps the stash', async () => {
+    await fs.writeFile(path.join(worktreePath, 'notes.md'), 'stashed\n');
+    const { stash } = await pushWorktreeStash(workdir, 'acme', 'demo', 'feature/login', { identity });
+    await fs.writeFile(path.join(worktreePath, 'notes.md'), 'committed\n');
+    await git(worktreePath, [...commitAs, '-am', 'Change notes']);
+
+    await assert.rejects(popWorktreeStash(workdir, 'acme', 'demo', 'feature/login'), (error: unknown) => {
+      assert.ok(error instanceof StashConflictError);
+      assert.equal(error.statusCode, 409);
+      assert.equal(error.code, 'stash_conflict');
+      assert.deepEqual(error.details, { stash: stash.sha, files: ['notes.md'] });
+      return true;
+    });
+    assert.equal(stash.message, 'agentrix stash');
+    assert.equal(await git(worktreePath, ['rev-parse', 'stash@{0}']), stash.sha);
+  });
+
+  it('refuses to pop over local changes the stash would overwrite', async () => {
+    await fs.writeFile(path.join(worktreePath, 'notes.md'), 'stashed\n');
+    const { stash } = await pushWorktreeStash(workdir, 'acme', 'demo', 'feature/login', { identity });
+    await fs.writeFile(path.join(worktreePath, 'notes.md'), 'local\n');
+
+    await assert.rejects(popWorktreeStash(workdir, 'acme', 'demo', 'feature/login'), (error: unknown) => {
+      assert.ok(error instanceof StashConflictError);
+      assert.deepEqual(error.details, { stash: stash.sha, files: ['notes.md'] });
+      return true;
+    });
+    assert.equal(await fs.readFile(path.join(worktreePath, 'notes.md'), 'utf8'), 'local\n');
+  });
+});
@@ -0,0 +1,250 @@
+import {
+  executeGitCommandInRepo,
+  extractGitErrorMessage,
+  GitNotFoundError,
+  GIT_BUFFER_SIZES,
+} from './git-repository.js';
+import { getWorktreePath, identityConfigArgs, type GitIdentity } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+
+/**
+ * Message given to stashes pushed without one
+ */
+export const DEFAULT_STASH_MESSAGE = 'agentrix stash';
+
+/**
+ * Error raised when a stash cannot be pushed or popped because of the worktree's stash state
+ */
+export class StashStateError extends Error {
+  public readonly statusCode: number = 409;
+
+  constructor(
+    message: string,
+    public readonly code: 'nothing_to_stash' | 'no_stash'
+  ) {
+    super(message);
+    this.name = 'StashStateError';
+  }
+}
+
+/**
+ * Error raised when a popped stash does not apply cleanly. The stash entry is kept; conflicts are left
+ * in the worktree to resolve, after which the entry can be dropped with `git stash drop`.
+ */
+export class StashConflictError extends Error {
+  public readonly statusCode: number = 409;
+  public readonly code = 'stash_conflict';
+  public readonly details: { stash: string; files: string[] };
+
+  constructor(branch: string, stash: string, files: string[], reason: string) {
+    super(`Popping stash ${stash} onto ${branch} stopped: ${reason}`);
+    this.name = 'StashConflictError';
+    this.details = { stash, files };
+  }
+}
+
+export interface WorktreeStash {
+  /**
+   * Reflog ref such as `stash@{0}`. Stashes are shared by every worktree of a repository, so the index
+   * can move when another worktree pushes or pops; `sha` does not.
+   */
+  ref: string;
+  sha: string;
+  message: string;
+  /**
+   * ISO timestamp of when the stash was made
+   */
+  createdAt: string;
+}
+
+const FIELD_SEPARATOR = '\x1f';
+
+/**
+ * Lists the stashes made in a worktree, newest first. git keeps one stash list per repository and
+ * records the branch in each entry's subject (`On <branch>: message`, or `WIP on <branch>: …` for a plain
+ * `git stash`), which is what scopes the list to the worktree's branch.
+ */
+async function readBranchStashes(worktreePath: string, branch: string): Promise<WorktreeStash[]> {
+  const { stdout } = await executeGitCommandInRepo(
+    worktreePath,
+    ['stash', 'list', `--format=%gd${FIELD_SEPARATOR}%H${FIELD_SEPARATOR}%gs${FIELD_SEPARATOR}%cI`],
+    { maxBuffer: GIT_BUFFER_SIZES.MEDIUM }
+  );
+  const prefixes = [`On ${branch}: `, `WIP on ${branch}: `];
+  const stashes: WorktreeStash[] = [];
+  for (const line of stdout.split('\n')) {
+    const [ref, sha, subject, createdAt] = line.split(FIELD_SEPARATOR);
+    const prefix = prefixes.find((candidate) => subject?.startsWith(candidate));
+    if (ref && sha && subject && createdAt && prefix) {
+      stashes.push({ ref, sha, message: subject.slice(prefix.length), createdAt });
+    }
+  }
+  return stashes;
+}
+
+/**
+ * Reads the files listed after git's "would be overwritten" error, or null for any other error
+ */
+function readOverwrittenFiles(message: string): string[] | null {
+  const lines = message.split('\n');
+  const start = lines.findIndex((line) => line.includes('would be overwritten'));
+  if (start === -1) {
+    return null;
+  }
+  const files: string[] = [];
+  for (const line of lines.slice(start + 1)) {
+    if (!line.startsWith('\t')) {
+      break;
+    }
+    files.push(line.trim());
+  }
+  return files;
+}
+
+async function resolveStashWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<{ branchName: string; worktreePath: string }> {
+  const branchName = normalizeBranchName(branch);
+
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  return { branchName, worktreePath };
+}
+
+/**
+ * Lists the stashes made in a worktree
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @returns The worktree's stashes, newest first
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ */
+export async function listWorktreeStashes(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<{ branch: string; stashes: WorktreeStash[] }> {
+  const { branchName, worktreePath } = await resolveStashWorktree(workdir, org, repo, branch);
+  return { branch: branchName, stashes: await readBranchStashes(worktreePath, branchName) };
+}
+
+export interface PushWorktreeStashOptions {
+  message?: string;
+  /**
+   * Stashes untracked files too (`--include-untracked`)
+   */
+  includeUntracked?: boolean;
+  /**
+   * Identity recorded on the stash commit, passed to git with `-c`
+   */
+  identity?: GitIdentity | null;
+}
+
+/**
+ * Stashes a worktree's uncommitted changes with `git stash push`, leaving a clean tree
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param options - Stash message and whether to include untracked files
+ * @returns The new stash
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ * @throws {StashStateError} If there are no changes to stash
+ */
+export async function pushWorktreeStash(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  options: PushWorktreeStashOptions = {}
+): Promise<{ branch: string; stash: WorktreeStash }> {
+  const { branchName, worktreePath } = await resolveStashWorktree(workdir, org, repo, branch);
+  const message = options.message?.trim() || DEFAULT_STASH_MESSAGE;
+  const before = await readBranchStashes(worktreePath, branchName);
+
+  try {
+    await executeGitCommandInRepo(
+      worktreePath,
+      [
+        ...identityConfigArgs(options.identity),
+        'stash',
+        'push',
+        ...(options.includeUntracked ? ['--include-untracked'] : []),
+        '--message',
+        message,
+      ],
+      { maxBuffer: GIT_BUFFER_SIZES.MEDIUM }
+    );
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    throw new Error(`Failed to stash changes: ${extractGitErrorMessage(error)}`);
+  }
+
+  // git exits 0 with "No local changes to save" and no new entry when the tree is clean
+  const [latest] = await readBranchStashes(worktreePath, branchName);
+  if (!latest || latest.sha === before[0]?.sha) {
+    throw new StashStateError(`No local changes to stash in ${branchName}`, 'nothing_to_stash');
+  }
+  return { branch: branchName, stash: latest };
+}
+
+/**
+ * Applies the worktree's newest stash with `git stash pop` and drops it
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @returns The stash that was popped
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ * @throws {StashStateError} If the worktree has no stashes
+ * @throws {StashConflictError} If the stash does not apply cleanly; the entry is kept
+ */
+export async function popWorktreeStash(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<{ branch: string; stash: WorktreeStash }> {
+  const { branchName, worktreePath } = await resolveStashWorktree(workdir, org, repo, branch);
+  const [latest] = await readBranchStashes(worktreePath, branchName);
+  if (!latest) {
+    throw new StashStateError(`No stashes to pop in ${branchName}`, 'no_stash');
+  }
+
+  try {
+    await executeGitCommandInRepo(worktreePath, ['stash', 'pop', latest.ref], { maxBuffer: GIT_BUFFER_SIZES.MEDIUM });
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    const reason = extractGitErrorMessage(error);
+    const { stdout } = await executeGitCommandInRepo(worktreePath, ['diff', '--name-only', '--diff-filter=U'], {
+      maxBuffer: GIT_BUFFER_SIZES.MEDIUM,
+    });
+    const conflicted = stdout
+      .split('\n')
+      .map((line) => line.trim())
+      .filter(Boolean);
+    if (conflicted.length > 0) {
+      throw new StashConflictError(branchName, latest.sha, conflicted, 'resolve the conflicts, then drop the stash');
+    }
+    // git refuses before touching anything when the stash would overwrite uncommitted changes
+    const overwritten = readOverwrittenFiles(reason);
+    if (overwritten) {
+      throw new StashConflictError(branchName, latest.sha, overwritten, 'commit or stash the local changes first');
+    }
+    throw new Error(`Failed to pop stash: ${reason}`);
+  }
+
+  return { branch: branchName, stash: latest };
+}
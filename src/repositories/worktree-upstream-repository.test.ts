@@ -0,0 +1,85 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, beforeEach, describe, it } from 'node:test';
+
+import {
+  getWorktreeUpstream,
+  setWorktreeUpstream,
+  UpstreamBranchNotFoundError,
+} from './worktree-upstream-repository.js';
+import { RemoteNotFoundError } from './worktree-push-repository.js';
+import { WorktreeNotFoundError } from './worktree-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+const commitAs = ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet'];
+
+describe('worktree upstreams', () => {
+  let workdir: string;
+  let repositoryPath: string;
+
+  beforeEach(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-upstream-'));
+    repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    const originPath = path.join(workdir, 'origin.git');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await git(repositoryPath, [...commitAs, '--allow-empty', '-m', 'Seed']);
+    await execFileAsync('git', ['init', '--quiet', '--bare', originPath]);
+    await git(repositoryPath, ['remote', 'add', 'origin', originPath]);
+    await git(repositoryPath, ['push', '--quiet', 'origin', 'main', 'main:feature/shared']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', path.join(workdir, 'acme', 'demo', 'login')]);
+  });
+
+  afterEach(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('sets an upstream for a local-only branch and reads it back', async () => {
+    assert.deepEqual(await getWorktreeUpstream(workdir, 'acme', 'demo', 'feature/login'), {
+      branch: 'feature/login',
+      upstream: null,
+      remote: null,
+      remoteBranch: null,
+    });
+
+    const set = await setWorktreeUpstream(workdir, 'acme', 'demo', 'feature/login', {
+      remote: 'origin',
+      remoteBranch: 'feature/shared',
+    });
+
+    const expected = {
+      branch: 'feature/login',
+      upstream: 'origin/feature/shared',
+      remote: 'origin',
+      remoteBranch: 'feature/shared',
+    };
+    assert.deepEqual(set, expected);
+    assert.deepEqual(await getWorktreeUpstream(workdir, 'acme', 'demo', 'feature/login'), expected);
+    assert.equal(await git(repositoryPath, ['config', 'branch.feature/login.merge']), 'refs/heads/feature/shared');
+  });
+
+  it('reports a missing worktree, remote or remote branch', async () => {
+    await assert.rejects(
+      getWorktreeUpstream(workdir, 'acme', 'demo', 'feature/missing'),
+      (error: unknown) => error instanceof WorktreeNotFoundError
+    );
+    await assert.rejects(
+      setWorktreeUpstream(workdir, 'acme', 'demo', 'feature/missing', { remote: 'origin' }),
+      (error: unknown) => error instanceof WorktreeNotFoundError
+    );
+    await assert.rejects(
+      setWorktreeUpstream(workdir, 'acme', 'demo', 'feature/login', { remote: 'fork' }),
+      (error: unknown) => error instanceof RemoteNotFoundError && error.statusCode === 404
+    );
+    // Without a remote branch the worktree's own name is tracked, which was never pushed
+    await assert.rejects(
+      setWorktreeUpstream(workdir, 'acme', 'demo', 'feature/login', { remote: 'origin' }),
+      (error: unknown) => error instanceof UpstreamBranchNotFoundError && error.code === 'upstream_not_found'
+    );
+  });
+});
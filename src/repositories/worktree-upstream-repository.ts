@@ -0,0 +1,163 @@
+import {
+  executeGitCommandInRepo,
+  extractGitErrorMessage,
+  GitNotFoundError,
+  GIT_BUFFER_SIZES,
+} from './git-repository.js';
+import { RemoteNotFoundError } from './worktree-push-repository.js';
+import { getWorktreePath } from './worktree-repository.js';
+import { normalizeBranchName } from '../domain/index.js';
+
+/**
+ * Error raised when the remote branch to track has no remote-tracking ref yet
+ */
+export class UpstreamBranchNotFoundError extends Error {
+  public readonly statusCode: number = 404;
+  public readonly code = 'upstream_not_found';
+
+  constructor(remote: string, remoteBranch: string) {
+    super(`Branch ${remoteBranch} was not found on ${remote}; fetch the remote or push the branch first`);
+    this.name = 'UpstreamBranchNotFoundError';
+  }
+}
+
+export interface WorktreeUpstream {
+  branch: string;
+  /**
+   * Short name of the tracked branch, such as `origin/feature/login`; null when the branch tracks nothing
+   */
+  upstream: string | null;
+  remote: string | null;
+  /**
+   * Name of the tracked branch on the remote
+   */
+  remoteBranch: string | null;
+}
+
+export interface SetWorktreeUpstreamOptions {
+  remote: string;
+  /**
+   * Branch on the remote to track; defaults to the worktree's branch name
+   */
+  remoteBranch?: string;
+}
+
+const FIELD_SEPARATOR = '\x1f';
+
+async function readUpstream(worktreePath: string, branch: string): Promise<WorktreeUpstream> {
+  const { stdout } = await executeGitCommandInRepo(
+    worktreePath,
+    [
+      'for-each-ref',
+      `--format=%(upstream:short)${FIELD_SEPARATOR}%(upstream:remotename)${FIELD_SEPARATOR}%(upstream:remoteref)`,
+      `refs/heads/${branch}`,
+    ],
+    { maxBuffer: GIT_BUFFER_SIZES.SMALL }
+  );
+  const [upstream = '', remote = '', remoteRef = ''] = stdout.trim().split(FIELD_SEPARATOR);
+  return {
+    branch,
+    upstream: upstream || null,
+    remote: remote || null,
+    remoteBranch: remoteRef.replace(/^refs\/heads\//, '') || null,
+  };
+}
+
+async function resolveUpstreamWorktree(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<{ branchName: string; worktreePath: string }> {
+  const branchName = normalizeBranchName(branch);
+
+  if (!branchName) {
+    throw new Error('Branch name cannot be empty');
+  }
+
+  const { worktreePath } = await getWorktreePath(workdir, org, repo, branchName);
+  return { branchName, worktreePath };
+}
+
+/**
+ * Reads the upstream a worktree's branch tracks
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @returns The tracked remote branch, with null fields when the branch tracks nothing
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ */
+export async function getWorktreeUpstream(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string
+): Promise<WorktreeUpstream> {
+  const { branchName, worktreePath } = await resolveUpstreamWorktree(workdir, org, repo, branch);
+  return await readUpstream(worktreePath, branchName);
+}
+
+/**
+ * Sets the upstream of a worktree's branch with `git branch --set-upstream-to`, e.g. for a branch created
+ * locally that should track one already on the remote
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param branch - Branch checked out in the worktree
+ * @param options - Remote, and the branch on it to track
+ * @returns The upstream now configured
+ * @throws {WorktreeNotFoundError} If no worktree exists for the branch
+ * @throws {RemoteNotFoundError} If the remote is not configured
+ * @throws {UpstreamBranchNotFoundError} If the remote branch has no remote-tracking ref
+ */
+export async function setWorktreeUpstream(
+  workdir: string,
+  org: string,
+  repo: string,
+  branch: string,
+  options: SetWorktreeUpstreamOptions
+): Promise<WorktreeUpstream> {
+  const { branchName, worktreePath } = await resolveUpstreamWorktree(workdir, org, repo, branch);
+  const { remote } = options;
+  const remoteBranch = normalizeBranchName(options.remoteBranch) || branchName;
+
+  try {
+    await executeGitCommandInRepo(worktreePath, ['remote', 'get-url', '--', remote], {
+      maxBuffer: GIT_BUFFER_SIZES.SMALL,
+    });
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    throw new RemoteNotFoundError(remote);
+  }
+
+  try {
+    await executeGitCommandInRepo(
+      worktreePath,
+      ['rev-parse', '--verify', '--quiet', `refs/remotes/${remote}/${remoteBranch}`],
+      { maxBuffer: GIT_BUFFER_SIZES.SMALL }
+    );
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    throw new UpstreamBranchNotFoundError(remote, remoteBranch);
+  }
+
+  try {
+    await executeGitCommandInRepo(
+      worktreePath,
+      ['branch', `--set-upstream-to=${remote}/${remoteBranch}`, '--', branchName],
+      { maxBuffer: GIT_BUFFER_SIZES.SMALL }
+    );
+  } catch (error: unknown) {
+    if (error instanceof GitNotFoundError) {
+      throw error;
+    }
+    throw new Error(`Failed to set upstream: ${extractGitErrorMessage(error)}`);
+  }
+
+  return await readUpstream(worktreePath, branchName);
+}
@@ -0,0 +1,98 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, describe, it } from 'node:test';
+
+import {
+  __setWorktreeUsageTestOverrides,
+  getWorktreeUsage,
+  measureDirectory,
+  WORKTREE_USAGE_CACHE_TTL_MS,
+} from './worktree-usage-repository.js';
+
+const execFileAsync = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execFileAsync('git', ['-C', cwd, ...args])).stdout.trim();
+
+describe('measureDirectory', () => {
+  it('totals file sizes across nested directories without following symlinks', async () => {
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-usage-'));
+    try {
+      await fs.mkdir(path.join(root, 'a', 'b', 'c'), { recursive: true });
+      await fs.mkdir(path.join(root, 'empty'));
+      await fs.writeFile(path.join(root, 'top.bin'), Buffer.alloc(1000));
+      await fs.writeFile(path.join(root, 'a', 'one.txt'), 'x'.repeat(250));
+      await fs.writeFile(path.join(root, 'a', 'b', 'two.txt'), 'y'.repeat(4096));
+      await fs.writeFile(path.join(root, 'a', 'b', 'c', 'three.txt'), '');
+      await fs.symlink(path.join(root, 'a'), path.join(root, 'loop'));
+
+      for (const concurrency of [1, 3]) {
+        assert.deepEqual(await measureDirectory(root, concurrency), { bytes: 5346, files: 4 });
+      }
+    } finally {
+      await fs.rm(root, { recursive: true, force: true });
+    }
+  });
+
+  it('reports nothing for a missing directory', async () => {
+    assert.deepEqual(await measureDirectory(path.join(os.tmpdir(), 'agentrix-usage-missing', 'nowhere')), {
+      bytes: 0,
+      files: 0,
+    });
+  });
+});
+
+describe('getWorktreeUsage', () => {
+  let workdir: string | null = null;
+
+  afterEach(async () => {
+    __setWorktreeUsageTestOverrides();
+    if (workdir) {
+      await fs.rm(workdir, { recursive: true, force: true });
+      workdir = null;
+    }
+  });
+
+  it('measures each worktree and serves cached totals until they expire', async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-usage-repo-'));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    const worktreePath = path.join(workdir, 'acme', 'demo', 'login');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', worktreePath]);
+    await fs.writeFile(path.join(worktreePath, 'build.log'), Buffer.alloc(2048));
+
+    let now = Date.parse('2024-05-01T12:00:00Z');
+    __setWorktreeUsageTestOverrides({ now: () => now });
+
+    const gitFile = await fs.stat(path.join(worktreePath, '.git'));
+    const usage = await getWorktreeUsage(workdir, 'acme', 'demo');
+    const login = usage.worktrees.find((entry) => entry.branch === 'feature/login');
+    const primary = usage.worktrees.find((entry) => entry.primary);
+
+    assert.equal(usage.worktrees.length, 2);
+    assert.deepEqual(login && { bytes: login.bytes, files: login.files, primary: login.primary }, {
+      bytes: 2048 + gitFile.size,
+      files: 2,
+      primary: false,
+    });
+    assert.equal(primary?.branch, 'main');
+    assert.equal(usage.totalBytes, (primary?.bytes ?? 0) + (login?.bytes ?? 0));
+    assert.equal(usage.measuredAt, '2024-05-01T12:00:00.000Z');
+
+    await fs.writeFile(path.join(worktreePath, 'more.log'), Buffer.alloc(512));
+    now += WORKTREE_USAGE_CACHE_TTL_MS - 1;
+    assert.equal((await getWorktreeUsage(workdir, 'acme', 'demo')).totalBytes, usage.totalBytes);
+
+    now += 1;
+    assert.equal((await getWorktreeUsage(workdir, 'acme', 'demo')).totalBytes, usage.totalBytes + 512);
+  });
+
+  it('rejects repositories that have not been cloned', async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-usage-repo-'));
+    await assert.rejects(getWorktreeUsage(workdir, 'acme', 'missing'), { statusCode: 404 });
+  });
+});
@@ -0,0 +1,212 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { listWorktrees, RepositoryNotFoundError, type WorktreeEntry } from './worktree-repository.js';
+import { resolveRepositoryPaths } from './repository-paths.js';
+import { mapWithConcurrency } from '../utils/concurrency.js';
+
+/**
+ * How long a measurement is served before the worktrees are walked again
+ */
+export const WORKTREE_USAGE_CACHE_TTL_MS = 30_000;
+
+/**
+ * Directories read at once while walking a single worktree
+ */
+export const DIRECTORY_WALK_CONCURRENCY = 8;
+
+/**
+ * Worktrees measured at once
+ */
+const WORKTREE_MEASURE_CONCURRENCY = 2;
+
+export interface DirectoryUsage {
+  /**
+   * Sum of the apparent sizes of regular files; symlinks are not followed
+   */
+  bytes: number;
+  files: number;
+}
+
+export interface WorktreeUsageEntry extends DirectoryUsage {
+  branch: string | null;
+  path: string;
+  /**
+   * The primary clone, whose size includes the repository's object database
+   */
+  primary: boolean;
+}
+
+export interface WorktreeUsage {
+  org: string;
+  repo: string;
+  worktrees: WorktreeUsageEntry[];
+  totalBytes: number;
+  measuredAt: string;
+}
+
+export interface WorktreeUsageOptions {
+  /**
+   * Measure again even when a recent result is cached
+   */
+  refresh?: boolean;
+}
+
+interface Dependencies {
+  now: () => number;
+}
+
+let activeDependencies: Dependencies = { now: () => Date.now() };
+const usageCache = new Map<string, { expiresAt: number; promise: Promise<WorktreeUsage> }>();
+
+/**
+ * @internal Utility for tests to control the clock and reset the cache
+ */
+export function __setWorktreeUsageTestOverrides(overrides?: Partial<Dependencies>): void {
+  usageCache.clear();
+  activeDependencies = { now: () => Date.now(), ...overrides };
+}
+
+function isMissing(error: unknown): boolean {
+  const code = (error as { code?: string })?.code;
+  return code === 'ENOENT' || code === 'ENOTDIR' || code === 'EACCES' || code === 'EPERM';
+}
+
+/**
+ * Walks a directory tree and totals the sizes of its regular files. Directories are read by a pool of
+ * workers so wide trees never hold more than `concurrency` directory handles open. Entries that vanish
+ * or cannot be read during the walk are skipped.
+ * @param root - Directory to measure
+ * @param concurrency - Maximum directories read at once
+ * @returns Byte and file totals
+ */
+export async function measureDirectory(
+  root: string,
+  concurrency: number = DIRECTORY_WALK_CONCURRENCY
+): Promise<DirectoryUsage> {
+  const usage: DirectoryUsage = { bytes: 0, files: 0 };
+  const pending: string[] = [root];
+  const limit = Math.max(1, Math.floor(concurrency) || 1);
+
+  const visit = async (directory: string): Promise<void> => {
+    let entries;
+    try {
+      entries = await fs.readdir(directory, { withFileTypes: true });
+    } catch (error: unknown) {
+      if (isMissing(error)) {
+        return;
+      }
+      throw error;
+    }
+    for (const entry of entries) {
+      const entryPath = path.join(directory, entry.name);
+      if (entry.isDirectory()) {
+        pending.push(entryPath);
+      } else if (entry.isFile()) {
+        try {
+          const stats = await fs.lstat(entryPath);
+          usage.bytes += stats.size;
+          usage.files += 1;
+        } catch (error: unknown) {
+          if (!isMissing(error)) {
+            throw error;
+          }
+        }
+      }
+    }
+  };
+
+  await new Promise<void>((resolve, reject) => {
+    let active = 0;
+    let failed = false;
+    const pump = (): void => {
+      if (failed) {
+        return;
+      }
+      if (pending.length === 0 && active === 0) {
+        resolve();
+        return;
+      }
+      while (active < limit && pending.length > 0) {
+        const directory = pending.pop()!;
+        active += 1;
+        visit(directory).then(
+          () => {
+            active -= 1;
+            pump();
+          },
+          (error: unknown) => {
+            failed = true;
+            reject(error);
+          }
+        );
+      }
+    };
+    pump();
+  });
+
+  return usage;
+}
+
+async function measureWorktrees(workdir: string, org: string, repo: string): Promise<WorktreeUsage> {
+  const { repositoryPath } = resolveRepositoryPaths(workdir, org, repo);
+  try {
+    await fs.access(repositoryPath);
+  } catch {
+    throw new RepositoryNotFoundError(org, repo);
+  }
+
+  const primaryPath = path.resolve(repositoryPath);
+  const entries = (await listWorktrees(repositoryPath)).filter(
+    (entry): entry is WorktreeEntry & { path: string } => Boolean(entry.path)
+  );
+  const worktrees = await mapWithConcurrency(entries, WORKTREE_MEASURE_CONCURRENCY, async (entry) => ({
+    branch: entry.branch,
+    path: entry.path,
+    primary: path.resolve(entry.path) === primaryPath,
+    ...(await measureDirectory(entry.path)),
+  }));
+
+  return {
+    org,
+    repo,
+    worktrees,
+    totalBytes: worktrees.reduce((sum, worktree) => sum + worktree.bytes, 0),
+    measuredAt: new Date(activeDependencies.now()).toISOString(),
+  };
+}
+
+/**
+ * Reports the disk space used by each of a repository's worktrees, including the primary clone.
+ * Walking worktrees is expensive, so results are cached briefly and concurrent requests share a walk.
+ * @param workdir - Work directory root
+ * @param org - Organization name
+ * @param repo - Repository name
+ * @param options - Cache options
+ * @returns Per-worktree sizes in bytes and their total
+ * @throws {RepositoryNotFoundError} If the repository has not been cloned
+ */
+export async function getWorktreeUsage(
+  workdir: string,
+  org: string,
+  repo: string,
+  { refresh = false }: WorktreeUsageOptions = {}
+): Promise<WorktreeUsage> {
+  const key = [path.resolve(workdir), org, repo].join('\0');
+  const now = activeDependencies.now();
+  const cached = usageCache.get(key);
+  if (cached && !refresh && cached.expiresAt > now) {
+    return await cached.promise;
+  }
+
+  const promise = measureWorktrees(workdir, org, repo);
+  const entry = { expiresAt: now + WORKTREE_USAGE_CACHE_TTL_MS, promise };
+  usageCache.set(key, entry);
+  try {
+    return await promise;
+  } catch (error) {
+    if (usageCache.get(key) === entry) {
+      usageCache.delete(key);
+    }
+    throw error;
+  }
+}
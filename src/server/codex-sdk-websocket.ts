@@ -6,6 +6,7 @@ import { WebSocketServer } from 'ws';
 import type { AuthManager } from '../types/auth.js';
 import { SESSION_COOKIE_NAME } from '../config/constants.js';
 import { parseCookies } from '../utils/cookies.js';
+import { isValidSessionId } from '../domain/index.js';
 import {
   getCodexSdkSession,
   getCodexSdkSessionEvents,
@@ -46,7 +47,18 @@ export function __setCodexWebSocketOverrides(overrides?: Partial<CodexWebSocketD
   dependencyOverrides = overrides ?? null;
 }
 
-export function attachCodexSdkWebSockets(server: HttpServer, authManager: AuthManager): WebSocketAttachment {
+export interface CodexSdkWebSocketOptions {
+  /**
+   * Rejects user messages so clients can follow sessions but not drive them
+   */
+  readOnly?: boolean;
+}
+
+export function attachCodexSdkWebSockets(
+  server: HttpServer,
+  authManager: AuthManager,
+  { readOnly = false }: CodexSdkWebSocketOptions = {}
+): WebSocketAttachment {
   const WebSocketServerImpl = getDependency('WebSocketServer');
   const wss = new WebSocketServerImpl({ noServer: true });
 
@@ -60,6 +72,11 @@ export function attachCodexSdkWebSockets(server: HttpServer, authManager: AuthMa
       socket.close();
       return;
     }
+    if (!isValidSessionId(sessionId)) {
+      socket.send(JSON.stringify({ type: 'error', message: 'sessionId is invalid' }));
+      socket.close();
+      return;
+    }
 
     const session = getDependency('getCodexSdkSession')(sessionId);
     if (!session) {
@@ -96,6 +113,10 @@ export function attachCodexSdkWebSockets(server: HttpServer, authManager: AuthMa
         socket.send(JSON.stringify({ type: 'error', message: 'Unsupported payload' }));
         return;
       }
+      if (readOnly) {
+        socket.send(JSON.stringify({ type: 'error', message: 'Server is running in read-only mode' }));
+        return;
+      }
       try {
         await getDependency('sendCodexSdkUserMessage')(sessionId, payload.text || '');
       } catch (error: unknown) {
@@ -1,17 +1,20 @@
 /* c8 ignore file */
 import type { Server as HttpServer } from 'node:http';
 import http from 'node:http';
+import type { Duplex } from 'node:stream';
 
-import { DEFAULT_HOST, DEFAULT_PORT } from '../config/constants.js';
+import { DEFAULT_HOST, DEFAULT_PORT, LOOPBACK_HOST } from '../config/constants.js';
 import { createAuthManager } from '../core/auth.js';
 import { resolveWorkdir } from '../core/workdir.js';
 import { disposeAllSessions, rehydrateTmuxSessionsFromSnapshot } from '../core/terminal-sessions.js';
 import { generateRandomPassword } from '../utils/random.js';
-import { sendJson } from '../utils/http.js';
+import { assignRequestId } from '../utils/request-id.js';
+import { createSlowRequestWatcher } from '../utils/slow-requests.js';
+import { mountBasePath, normalizeBasePath, stripBasePath } from '../utils/base-path.js';
 import { createRouter } from './router.js';
 import { attachTerminalWebSockets } from './websocket.js';
 import { attachCodexSdkWebSockets } from './codex-sdk-websocket.js';
-import { createUiProvider } from './ui.js';
+import { createLandingProvider, createUiProvider, UiPathNotFoundError } from './ui.js';
 import { createCookieManager } from './cookies.js';
 import { createAgentCommands } from '../config/agent-commands.js';
 import { createBranchNameGenerator } from '../core/branch-name.js';
@@ -19,6 +22,13 @@ import { createPlanService } from '../core/plan.js';
 import { configureTaskPersistence, flushTaskPersistence } from '../core/tasks.js';
 import { createTaskStore } from '../core/task-store.js';
 import { createPortTunnelManager } from '../core/ports.js';
+import { configureCloneLayout } from '../repositories/repository-paths.js';
+import { createGithubClient } from '../core/github.js';
+import { configureGitTracing, createCloneQueue, probeGitVersion } from '../core/git.js';
+import { createRateLimiter } from '../core/security/rate-limiter.js';
+import { createConnectionLimiter } from '../infrastructure/connection-limit/index.js';
+import { createCorsPolicy } from '../infrastructure/cors/index.js';
+import { resolveEnabledFeatures } from '../config/features.js';
 import type { ServerConfig } from '../types/config.js';
 
 export interface StartServerResult {
@@ -48,14 +58,84 @@ export async function startServer({
   defaultBranches,
   cookieSecure,
   terminalSessionMode = 'auto',
+  terminalEnv,
+  gitConfig,
+  allowedUrlSchemes,
+  gitConcurrency,
+  maxConcurrentClones,
+  gitBackend,
+  repoCacheTtlMs,
+  trustProxy = false,
+  githubTimeoutMs,
+  githubApiVersion,
+  githubWebhookSecret,
+  githubTokens,
+  gitAuthorName,
+  gitAuthorEmail,
+  cloneLayout,
+  defaultOrg,
+  defaultBase,
+  maxWorktreesPerRepo,
+  gitOutputLimitBytes,
+  setupCommand,
+  rateLimit,
+  maxConnections,
+  readOnly = false,
+  verifyGithubToken = false,
+  traceGit = false,
+  signCommits = false,
+  confirmDangerousTerminals = false,
+  slowRequestMs,
+  corsOrigins,
+  corsAllowCredentials = false,
+  corsMaxAgeSeconds,
+  landingPage,
+  basePath,
+  features,
 }: Partial<ServerConfig> = {}): Promise<StartServerResult> {
   if (!uiPath) {
     throw new Error('Missing required option: uiPath');
   }
+  const mountPath = normalizeBasePath(basePath);
 
-  const uiProvider = await createUiProvider(uiPath);
+  // Clone layout and git tracing reach every git call site, so they stay process-wide
+  configureCloneLayout(cloneLayout);
+  configureGitTracing({ enabled: traceGit });
+  const githubClient = createGithubClient({
+    timeoutMs: githubTimeoutMs,
+    apiVersion: githubApiVersion,
+    orgTokens: githubTokens,
+  });
+  const watchSlowRequest = createSlowRequestWatcher({ thresholdMs: slowRequestMs });
+  const enabledFeatures = resolveEnabledFeatures(features);
+  console.log(`[agentrix] Features enabled: ${[...enabledFeatures].join(', ')}.`);
+  if (!(await probeGitVersion())) {
+    console.warn(
+      '[agentrix] WARNING: git was not found on PATH. Cloning repositories, worktrees and git status ' +
+        'will fail with git_not_found until git is installed.',
+    );
+  }
+  if (verifyGithubToken) {
+    const verification = await githubClient.verifyToken();
+    if (verification.valid) {
+      console.log(`[agentrix] GitHub token verified for ${verification.login}.`);
+    } else {
+      console.warn(
+        `[agentrix] WARNING: GitHub token verification failed: ${verification.message}. ` +
+          'GitHub features will fail until gh is re-authenticated (gh auth login).',
+      );
+    }
+  }
+  const uiProvider = await createUiProvider(uiPath, { basePath: mountPath }).catch(async (error: unknown) => {
+    // Headless deployments without a frontend build still answer browsers with a landing page
+    if (!(error instanceof UiPathNotFoundError)) {
+      throw error;
+    }
+    console.warn(`[agentrix] ${error.message}; serving ${landingPage ? `the landing page ${landingPage}` : 'a placeholder page'} instead.`);
+    return await createLandingProvider(landingPage, { basePath: mountPath });
+  });
   const resolvedWorkdir = workdir ? await resolveWorkdir(workdir) : process.cwd();
-  await rehydrateTmuxSessionsFromSnapshot(resolvedWorkdir, { mode: terminalSessionMode });
+  await rehydrateTmuxSessionsFromSnapshot(resolvedWorkdir, { mode: terminalSessionMode, env: terminalEnv });
   const taskStore = createTaskStore({ root: resolvedWorkdir, logger: console });
   await configureTaskPersistence({
     saveSnapshot: (snapshot: unknown) => taskStore.saveSnapshot(snapshot),
@@ -75,6 +155,12 @@ export async function startServer({
   const planService = createPlanService({ defaultLlm: planLlm });
   const cookieManager = createCookieManager({ secureSetting: cookieSecure });
   const portTunnelManager = createPortTunnelManager({ authtoken: ngrokConfig?.apiKey });
+  const rateLimiter = rateLimit ? createRateLimiter({ windowMs: 60_000, maxAttempts: rateLimit }) : undefined;
+  const connectionLimiter = createConnectionLimiter({ maxConnections });
+  const cors =
+    corsOrigins && corsOrigins.length > 0
+      ? createCorsPolicy({ origins: corsOrigins, allowCredentials: corsAllowCredentials, maxAgeSeconds: corsMaxAgeSeconds })
+      : undefined;
   const router = createRouter({
     authManager,
     workdir: resolvedWorkdir,
@@ -86,35 +172,80 @@ export async function startServer({
     cookieManager,
     terminalSessionMode,
     portManager: portTunnelManager,
+    allowedUrlSchemes,
+    rateLimiter,
+    cors,
+    readOnly,
+    features: enabledFeatures,
+    trustProxy,
+    githubClient,
+    githubWebhookSecret,
+    clone: { defaultOrg, gitConfig, queue: createCloneQueue(maxConcurrentClones), backend: gitBackend },
+    worktree: {
+      identity: { name: gitAuthorName, email: gitAuthorEmail },
+      defaultBase,
+      maxWorktrees: maxWorktreesPerRepo,
+      gitConfig,
+    },
+    signCommits,
+    setupCommand,
+    terminalEnv,
+    repositoryCache: { maxAgeMs: repoCacheTtlMs, discoveryConcurrency: gitConcurrency },
+    dangerousTerminals: {
+      requireConfirmation: confirmDangerousTerminals,
+      commands: [agentCommands.codexDangerous, agentCommands.claudeDangerous],
+    },
+    gitOutputLimitBytes,
   });
 
   const server = http.createServer(async (req, res) => {
+    const requestId = assignRequestId(res);
+    watchSlowRequest(req, res);
+    if (!mountBasePath(req, res, mountPath)) {
+      return;
+    }
+    if (!connectionLimiter.admit(req, res)) {
+      return;
+    }
     try {
       const handled = await router(req, res);
       if (handled) {
         return;
       }
 
-      const url = new URL(req.url || '/', `http://${req.headers.host || 'localhost'}`);
-      if (url.pathname.startsWith('/api/')) {
-        sendJson(res, 404, { error: 'Not Found' });
-        return;
-      }
-
       await uiProvider.serve(req, res);
     } catch (error) {
-      console.error('[agentrix] Request handling error:', error);
+      console.error(`[agentrix] Request ${requestId} handling error:`, error);
       if (!res.headersSent) {
         res.statusCode = 500;
-        res.end('Internal Server Error');
+        res.end(`Internal Server Error (request ${requestId})`);
       } else {
         res.end();
       }
     }
   });
 
-  const { close: closeWebSockets } = attachTerminalWebSockets(server, authManager);
-  const { close: closeCodexSockets } = attachCodexSdkWebSockets(server, authManager);
+  if (mountPath) {
+    // Runs before the socket handlers, which match their paths at the root
+    server.prependListener('upgrade', (req: http.IncomingMessage, socket: Duplex) => {
+      const stripped = stripBasePath(req.url || '/', mountPath);
+      if (stripped !== null) {
+        req.url = stripped;
+        return;
+      }
+      socket.destroy();
+      // The socket handlers destroy sockets for paths they do not serve, which is a no-op by now
+      req.url = '/';
+    });
+  }
+
+  const noSockets = { close: async () => {} };
+  const { close: closeWebSockets } = enabledFeatures.has('terminals')
+    ? attachTerminalWebSockets(server, authManager, { readOnly })
+    : noSockets;
+  const { close: closeCodexSockets } = enabledFeatures.has('agents')
+    ? attachCodexSdkWebSockets(server, authManager, { readOnly })
+    : noSockets;
 
   const activeSockets = new Set();
   server.on('connection', (socket) => {
@@ -131,6 +262,9 @@ export async function startServer({
       resolve();
     });
   });
+  // Port 0 asks the OS for a free port, so report the one actually bound
+  const address = server.address();
+  const boundPort = address && typeof address === 'object' ? address.port : port;
 
   let ngrokListener: { url: () => string; close: () => Promise<void> } | null = null;
   let ngrokUrl: string | null = null;
@@ -142,7 +276,7 @@ export async function startServer({
     try {
       const { forward } = await import('@ngrok/ngrok');
       const listener = await forward({
-        addr: port,
+        addr: boundPort,
         authtoken: ngrokConfig?.apiKey || '',
         domain: ngrokConfig?.domain || '',
       }) as unknown as { url: () => string; close: () => Promise<void> };
@@ -230,7 +364,7 @@ export async function startServer({
   return {
     server,
     host,
-    port,
+    port: boundPort,
     uiPath: uiProvider.resolvedPath,
     workdir: resolvedWorkdir,
     close: closeAll,
@@ -240,4 +374,4 @@ export async function startServer({
   };
 }
 
-export { DEFAULT_HOST, DEFAULT_PORT, generateRandomPassword };
+export { DEFAULT_HOST, DEFAULT_PORT, LOOPBACK_HOST, generateRandomPassword };
@@ -1,9 +1,17 @@
 import assert from 'node:assert/strict';
 import { describe, it, beforeEach, afterEach, mock } from 'node:test';
 
-import { createRouter, __setRouterTestOverrides } from './router.js';
+import {
+  createRouter,
+  FEATURE_ROUTES,
+  parseRouteEntry,
+  UNGATED_ROUTES,
+  __setRouterTestOverrides,
+} from './router.js';
 import type { RouterConfig } from './router.js';
 import type { AuthManager } from '../types/auth.js';
+import { createRateLimiter } from '../core/security/rate-limiter.js';
+import { createCorsPolicy } from '../infrastructure/cors/index.js';
 
 function createReq(url: string, method: string): { req: { url: string; method: string; headers: Record<string, string> } } {
   return {
@@ -56,24 +64,60 @@ function createStubHandlers() {
       create: async () => {},
       delete: async () => {},
       updateInitCommand: async () => {},
+      readMetadata: async () => {},
+      updateMetadata: async () => {},
+      readBranch: async () => {},
+      switchBranch: async () => {},
+      listRemotes: async () => {},
+      addRemote: async () => {},
+      readConfig: async () => {},
+      listClones: async () => {},
+      cancelClone: async () => {},
+      move: async () => {},
     }),
     createRepoDashboardHandlers: () => ({
       read: async () => {},
+      summary: async () => {},
+      detail: async () => {},
     }),
     createRepoIssueHandlers: () => ({
       read: async () => {},
+      search: async () => {},
     }),
     createSessionHandlers: () => ({
       list: async () => {},
+      tree: async () => {},
+      exportManifest: async () => {},
+      importManifest: async () => {},
     }),
     createWorktreeHandlers: () => ({
+      list: async () => {},
       create: async () => {},
+      createBatch: async () => {},
+      usage: async () => {},
+      activity: async () => {},
       delete: async () => {},
+      replace: async () => {},
+      rename: async () => {},
+      commit: async () => {},
+      push: async () => {},
+      pullRequestDraft: async () => {},
+      pullRequest: async () => {},
+      checkBranch: async () => {},
+      setup: async () => {},
+      reset: async () => {},
+      cherryPick: async () => {},
+      cherryPickAbort: async () => {},
+      stash: async () => {},
+      readUpstream: async () => {},
+      setUpstream: async () => {},
     }),
     createTerminalHandlers: () => ({
       open: async () => {},
       send: async () => {},
       close: async () => {},
+      signal: async () => {},
+      history: async () => {},
     }),
     createConfigHandlers: () => ({
       commands: async () => {},
@@ -83,7 +127,14 @@ function createStubHandlers() {
     }),
     createGitStatusHandlers: () => ({
       read: async () => {},
+      summary: async () => {},
       diff: async () => {},
+      file: async () => {},
+      archive: async () => {},
+      blame: async () => {},
+      compare: async () => {},
+      mergeBase: async () => {},
+      tags: async () => {},
     }),
     createPlanArtifactHandlers: () => ({
       list: async () => {},
@@ -96,8 +147,25 @@ function createStubHandlers() {
     }),
     createPortHandlers: () => ({
       list: async () => {},
+      listForWorktree: async () => {},
       openTunnel: async () => {},
     }),
+    createHealthHandlers: () => ({
+      basic: async () => {},
+      detail: async () => {},
+    }),
+    createGithubWebhookHandlers: () => ({
+      receive: async () => {},
+    }),
+    createGithubNotificationHandlers: () => ({
+      list: async () => {},
+    }),
+    createGithubReadmeHandlers: () => ({
+      read: async () => {},
+    }),
+    createGithubRateLimitHandlers: () => ({
+      read: async () => {},
+    }),
     sendJson: (res: unknown, statusCode: number, payload: unknown) => {
       const response = res as { statusCode: number; end: (value?: unknown) => void };
       response.statusCode = statusCode;
@@ -154,6 +222,36 @@ describe('createRouter', () => {
     assert.equal(payload.error, 'Authentication required');
   });
 
+  it('serves the basic health check without authentication but protects the detailed report', async () => {
+    const authManager = createAuthManager(false);
+    let basicCalls = 0;
+    let detailCalls = 0;
+    const overrides = createStubHandlers();
+    overrides.createHealthHandlers = () => ({
+      basic: async () => {
+        basicCalls += 1;
+      },
+      detail: async () => {
+        detailCalls += 1;
+      },
+    });
+    __setRouterTestOverrides(overrides);
+    const router = createRouter({
+      authManager,
+      workdir: '/repo',
+      agentCommands: {},
+      portManager: portManagerStub,
+    });
+
+    assert.equal(await router(createReq('/api/health', 'GET').req as never, createRes().res as never), true);
+    const { res } = createRes();
+    assert.equal(await router(createReq('/api/health/detail', 'GET').req as never, res as never), true);
+
+    assert.equal(basicCalls, 1);
+    assert.equal(detailCalls, 0);
+    assert.equal(res.statusCode, 401);
+  });
+
   it('returns 405 with Allow header when method not supported', async () => {
     const authManager = createAuthManager(true);
     const router = createRouter({
@@ -238,4 +336,266 @@ describe('createRouter', () => {
     assert.equal(res.statusCode, 0);
     assert.equal((res.end as ReturnType<typeof mock.fn>).mock.calls.length, 0);
   });
+
+  it('answers unknown API routes with an enveloped 404', async () => {
+    const authManager = createAuthManager(true);
+    const router = createRouter({
+      authManager,
+      workdir: '/repo',
+      agentCommands: {},
+      portManager: portManagerStub,
+    });
+
+    const { req } = createReq('/api/nope', 'GET');
+    const { res } = createRes();
+
+    const handled = await router(req as never, res as never);
+
+    assert.equal(handled, true);
+    assert.equal(res.statusCode, 404);
+    const [body] = (res.end as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments ?? [];
+    assert.deepEqual(JSON.parse(body as string), {
+      error: 'No API route matches /api/nope',
+      code: 'not_found',
+      details: { path: '/api/nope' },
+    });
+  });
+
+  it('returns 429 with Retry-After once a client exceeds the rate limit', async () => {
+    let clock = 0;
+    const rateLimiter = createRateLimiter({ windowMs: 60_000, maxAttempts: 3, now: () => clock });
+    const router = createRouter({
+      authManager: createAuthManager(true),
+      workdir: '/repo',
+      agentCommands: {},
+      portManager: portManagerStub,
+      rateLimiter,
+    });
+
+    const hit = async (remoteAddress: string) => {
+      const { req } = createReq('/api/repos', 'GET');
+      const { res } = createRes();
+      await router({ ...req, socket: { remoteAddress } } as never, res as never);
+      return res;
+    };
+
+    for (let index = 0; index < 3; index += 1) {
+      assert.equal((await hit('10.0.0.1')).statusCode, 0);
+    }
+    for (let index = 0; index < 5; index += 1) {
+      const limited = await hit('10.0.0.1');
+      assert.equal(limited.statusCode, 429);
+      assert.equal(limited.getHeader('Retry-After'), '60');
+      const body = JSON.parse((limited.end as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0] as string);
+      assert.equal(body.code, 'rate_limited');
+    }
+
+    assert.equal((await hit('10.0.0.2')).statusCode, 0);
+
+    clock += 60_000;
+    for (let index = 0; index < 3; index += 1) {
+      assert.equal((await hit('10.0.0.1')).statusCode, 0);
+    }
+    assert.equal((await hit('10.0.0.1')).statusCode, 429);
+  });
+
+  it('answers CORS preflights before authentication', async () => {
+    const authManager = createAuthManager(false);
+    const router = createRouter({
+      authManager,
+      workdir: '/repo',
+      agentCommands: {},
+      portManager: portManagerStub,
+      cors: createCorsPolicy({ origins: ['https://app.example.com'], allowCredentials: true, maxAgeSeconds: 300 }),
+    });
+
+    const { req } = createReq('/api/repos', 'OPTIONS');
+    req.headers['origin'] = 'https://app.example.com';
+    req.headers['access-control-request-method'] = 'POST';
+    const preflight = createRes();
+    assert.equal(await router(req as never, preflight.res as never), true);
+    assert.equal(preflight.res.statusCode, 204);
+    assert.equal(preflight.res.getHeader('Access-Control-Allow-Credentials'), 'true');
+    assert.equal(preflight.res.getHeader('Access-Control-Max-Age'), '300');
+
+    const get = createReq('/api/repos', 'GET');
+    get.req.headers['origin'] = 'https://app.example.com';
+    const denied = createRes();
+    await router(get.req as never, denied.res as never);
+    assert.equal(denied.res.statusCode, 401);
+    assert.equal(denied.res.getHeader('Access-Control-Allow-Origin'), 'https://app.example.com');
+  });
+
+  it('rejects mutating requests in read-only mode while reads still succeed', async () => {
+    let created = 0;
+    let listed = 0;
+    const overrides = createStubHandlers();
+    const baseRepoHandlers = overrides.createRepoHandlers();
+    overrides.createRepoHandlers = () => ({
+      ...baseRepoHandlers,
+      list: async () => {
+        listed += 1;
+      },
+      create: async () => {
+        created += 1;
+      },
+    });
+    __setRouterTestOverrides(overrides);
+
+    const router = createRouter({
+      authManager: createAuthManager(true),
+      workdir: '/repo',
+      agentCommands: {},
+      portManager: portManagerStub,
+      readOnly: true,
+    });
+
+    const post = createRes();
+    assert.equal(await router(createReq('/api/repos', 'POST').req as never, post.res as never), true);
+    assert.equal(post.res.statusCode, 403);
+    const body = JSON.parse((post.res.end as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0] as string);
+    assert.equal(body.code, 'read_only_mode');
+    assert.equal(created, 0);
+
+    const get = createRes();
+    assert.equal(await router(createReq('/api/repos', 'GET').req as never, get.res as never), true);
+    assert.notEqual(get.res.statusCode, 403);
+    assert.equal(listed, 1);
+
+    const login = createRes();
+    await router(createReq('/api/auth/login', 'POST').req as never, login.res as never);
+    assert.notEqual(login.res.statusCode, 403);
+
+    const unknown = createRes();
+    await router(createReq('/api/nope', 'POST').req as never, unknown.res as never);
+    assert.equal(unknown.res.statusCode, 404);
+
+    const unsupported = createRes();
+    await router(createReq('/api/repos', 'PATCH').req as never, unsupported.res as never);
+    assert.equal(unsupported.res.statusCode, 405);
+  });
+
+  it('answers 404 for the routes of disabled features while enabled ones are served', async () => {
+    let opened = 0;
+    let listed = 0;
+    const overrides = createStubHandlers();
+    const baseTerminalHandlers = overrides.createTerminalHandlers();
+    overrides.createTerminalHandlers = () => ({
+      ...baseTerminalHandlers,
+      open: async () => {
+        opened += 1;
+      },
+    });
+    const basePortHandlers = overrides.createPortHandlers();
+    overrides.createPortHandlers = () => ({
+      ...basePortHandlers,
+      list: async () => {
+        listed += 1;
+      },
+    });
+    __setRouterTestOverrides(overrides);
+
+    const router = createRouter({
+      authManager: createAuthManager(true),
+      workdir: '/repo',
+      agentCommands: {},
+      portManager: portManagerStub,
+      features: new Set(['ports']),
+    });
+
+    const terminal = createRes();
+    assert.equal(await router(createReq('/api/terminal/open', 'POST').req as never, terminal.res as never), true);
+    assert.equal(terminal.res.statusCode, 404);
+    const body = JSON.parse((terminal.res.end as ReturnType<typeof mock.fn>).mock.calls[0]?.arguments[0] as string);
+    assert.equal(body.code, 'not_found');
+    assert.equal(opened, 0);
+
+    const codexSession = createRes();
+    await router(createReq('/api/codex-sdk/sessions/abc', 'GET').req as never, codexSession.res as never);
+    assert.equal(codexSession.res.statusCode, 404);
+
+    const ports = createRes();
+    assert.equal(await router(createReq('/api/ports', 'GET').req as never, ports.res as never), true);
+    assert.notEqual(ports.res.statusCode, 404);
+    assert.equal(listed, 1);
+  });
+
+  it('answers 404 for a single method of a disabled feature while the path keeps its other methods', async () => {
+    const router = createRouter({
+      authManager: createAuthManager(true),
+      workdir: '/repo',
+      agentCommands: {},
+      portManager: portManagerStub,
+      features: new Set(['github', 'terminals', 'agents', 'ports']),
+    });
+
+    const replace = createRes();
+    await router(createReq('/api/worktrees', 'PUT').req as never, replace.res as never);
+    assert.equal(replace.res.statusCode, 404);
+
+    const list = createRes();
+    await router(createReq('/api/worktrees', 'GET').req as never, list.res as never);
+    assert.notEqual(list.res.statusCode, 404);
+
+    const patch = createRes();
+    await router(createReq('/api/worktrees', 'PATCH').req as never, patch.res as never);
+    assert.equal(patch.res.statusCode, 405);
+    assert.equal(patch.res.getHeader('Allow'), 'GET, HEAD, POST, DELETE');
+    assert.deepEqual(router.routes.get('/api/worktrees'), ['GET', 'HEAD', 'POST', 'DELETE']);
+  });
+
+  it('answers 404 for repository and setup writes when git-write is disabled while their reads stay open', async () => {
+    const router = createRouter({
+      authManager: createAuthManager(true),
+      workdir: '/repo',
+      agentCommands: {},
+      portManager: portManagerStub,
+      features: new Set(['github', 'terminals', 'agents', 'ports']),
+    });
+
+    for (const [path, method] of [
+      ['/api/repos/move', 'POST'],
+      ['/api/repos/init-command', 'POST'],
+      ['/api/repos/meta', 'PUT'],
+      ['/api/worktrees/setup', 'POST'],
+    ] as const) {
+      const write = createRes();
+      await router(createReq(path, method).req as never, write.res as never);
+      assert.equal(write.res.statusCode, 404, `${method} ${path}`);
+    }
+
+    const meta = createRes();
+    await router(createReq('/api/repos/meta', 'GET').req as never, meta.res as never);
+    assert.notEqual(meta.res.statusCode, 404);
+    assert.deepEqual(router.routes.get('/api/repos/meta'), ['GET']);
+  });
+
+  it('assigns every registered route to a feature or leaves it ungated on purpose', () => {
+    const router = createRouter({
+      authManager: createAuthManager(true),
+      workdir: '/repo',
+      agentCommands: {},
+      portManager: portManagerStub,
+    });
+    const gated = new Set(Object.values(FEATURE_ROUTES).flat());
+    const ungated = new Set(UNGATED_ROUTES);
+    const listed = (entries: Set<string>, method: string, path: string) =>
+      entries.has(path) || entries.has(`${method} ${path}`);
+
+    for (const [path, methods] of router.routes) {
+      for (const method of methods) {
+        const isGated = listed(gated, method, path);
+        const isUngated = listed(ungated, method, path);
+        assert.ok(isGated || isUngated, `${method} ${path} is neither feature-gated nor listed as ungated`);
+        assert.ok(!(isGated && isUngated), `${method} ${path} is both feature-gated and listed as ungated`);
+      }
+    }
+
+    for (const entry of [...gated, ...ungated]) {
+      const { method, path } = parseRouteEntry(entry);
+      const methods = router.routes.get(path);
+      assert.ok(methods, `${entry} does not name a registered route`);
+      assert.ok(!method || methods.includes(method), `${entry} does not name a registered method`);
+    }
+  });
 });
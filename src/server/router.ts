@@ -2,13 +2,13 @@ import type { IncomingMessage, ServerResponse } from 'node:http';
 import { createAuthHandlers } from '../api/auth.js';
 import { createAutomationHandlers } from '../api/automation.js';
 import { createRepoHandlers } from '../api/repos.js';
-import { createRepoDashboardHandlers } from '../api/repo-dashboard.js';
+import { createRepoDashboardHandlers, type RepoDashboardOverrides } from '../api/repo-dashboard.js';
 import { createRepoIssueHandlers } from '../api/repo-issue.js';
 import { createSessionHandlers } from '../api/sessions.js';
 import { createTerminalHandlers } from '../api/terminal.js';
 import { createWorktreeHandlers } from '../api/worktrees.js';
 import { createGitStatusHandlers } from '../api/git-status.js';
-import { sendJson, readJsonBody } from '../utils/http.js';
+import { sendJson, readJsonBody, getRequestOrigin, getClientIp } from '../utils/http.js';
 import { createConfigHandlers } from '../api/config.js';
 import { createPlanHandlers } from '../api/create-plan.js';
 import { createPlanArtifactHandlers } from '../api/plans.js';
@@ -17,9 +17,22 @@ import { createEventStreamHandler } from './events.js';
 import { createTaskHandlers } from '../api/tasks.js';
 import { createPortHandlers } from '../api/ports.js';
 import { createCodexSdkHandlers } from '../api/codex-sdk.js';
+import { createHealthHandlers } from '../api/health.js';
+import { createGithubWebhookHandlers } from '../api/github-webhook.js';
+import { createGithubNotificationHandlers } from '../api/github-notifications.js';
+import { createGithubReadmeHandlers } from '../api/github-readme.js';
+import { createGithubRateLimitHandlers } from '../api/github-rate-limit.js';
+import { createGithubClient, type GithubClient } from '../core/github.js';
+import { GITHUB_SUMMARY_CACHE_TTL_MS, RepositorySummaryCache } from '../core/github-summary-cache.js';
+import { getCloneQueueStats, type CloneSettings, type WorktreeSettings } from '../core/git.js';
+import type { DangerousTerminalOptions } from '../core/dangerous-terminals.js';
+import type { RepositoryCacheOptions } from '../utils/repository-cache.js';
 import type { AuthManager, CookieManager } from '../types/auth.js';
 import type { PortTunnelManager } from '../core/ports.js';
+import type { RateLimiter } from '../core/security/rate-limiter.js';
+import type { CorsPolicy } from '../infrastructure/cors/index.js';
 import { createWorktreeService, createPlanModeService } from '../services/index.js';
+import { FEATURES, type Feature } from '../config/features.js';
 
 export interface RouterConfig {
   authManager: AuthManager;
@@ -32,9 +45,180 @@ export interface RouterConfig {
   cookieManager?: CookieManager;
   terminalSessionMode?: 'auto' | 'tmux' | 'pty';
   portManager: PortTunnelManager;
+  allowedUrlSchemes?: string[];
+  /**
+   * Per-client limiter applied to `/api/` requests; omitted when rate limiting is disabled
+   */
+  rateLimiter?: RateLimiter;
+  /**
+   * Cross-origin policy applied to `/api/` requests; omitted when no origins are allowed
+   */
+  cors?: CorsPolicy;
+  /**
+   * Rejects every mutating `/api/` request with 403 so the instance can only be viewed
+   */
+  readOnly?: boolean;
+  /**
+   * Features whose routes are served; the routes of every other feature answer 404. Defaults to all features
+   */
+  features?: ReadonlySet<Feature>;
+  /**
+   * Takes the client address and origin from `X-Forwarded-*` headers set by a reverse proxy
+   */
+  trustProxy?: boolean;
+  /**
+   * Client shared by every handler that calls GitHub; a default client when omitted
+   */
+  githubClient?: GithubClient;
+  /**
+   * Secret GitHub webhook deliveries are signed with. Setting one also caches dashboard summaries, which
+   * deliveries then invalidate; without one the receiver answers 404.
+   */
+  githubWebhookSecret?: string | null;
+  /**
+   * Default org, git config and queue applied to every clone
+   */
+  clone?: CloneSettings;
+  /**
+   * Identity, default base, limit and git config applied to new worktrees
+   */
+  worktree?: WorktreeSettings;
+  /**
+   * Signs every commit made through the API
+   */
+  signCommits?: boolean;
+  /**
+   * Command worktree setup runs when a worktree has no setup script of its own
+   */
+  setupCommand?: string | null;
+  /**
+   * Extra environment variables for terminals, agents and setup runs
+   */
+  terminalEnv?: Record<string, string> | null;
+  dangerousTerminals?: DangerousTerminalOptions;
+  repositoryCache?: RepositoryCacheOptions;
+  /**
+   * Default output cap for diffs and blames (DEFAULT_GIT_OUTPUT_LIMIT_BYTES when omitted)
+   */
+  gitOutputLimitBytes?: number | null;
 }
 
-export type Router = (req: IncomingMessage, res: ServerResponse) => Promise<boolean>;
+export interface Router {
+  (req: IncomingMessage, res: ServerResponse): Promise<boolean>;
+  /**
+   * Methods served on each path of the route table once disabled features are removed
+   */
+  readonly routes: ReadonlyMap<string, readonly string[]>;
+}
+
+const SAFE_METHODS = new Set(['GET', 'HEAD', 'OPTIONS']);
+
+/**
+ * Non-GET routes that stay available in read-only mode because they do not change any state
+ * a viewer cares about (signing in/out, computing a diff, refreshing cached GitHub summaries)
+ */
+const READ_ONLY_ALLOWED_ROUTES = new Set(['/api/auth/login', '/api/auth/logout', '/api/git/diff', '/api/github/webhook']);
+
+/**
+ * Routes served only while their feature is enabled, as a path for every method or `METHOD path` for one.
+ * A route listed under several features needs all of them.
+ */
+export const FEATURE_ROUTES: Readonly<Record<Feature, readonly string[]>> = {
+  github: [
+    '/api/github/webhook',
+    '/api/github/notifications',
+    '/api/github/readme',
+    '/api/github/rate-limit',
+    '/api/repos/dashboard',
+    '/api/repos/dashboard/summary',
+    '/api/repos/detail',
+    '/api/repos/issue',
+    '/api/repos/search',
+    '/api/worktrees/pull-request',
+    '/api/worktrees/pr-draft',
+  ],
+  terminals: ['/api/terminal/open', '/api/terminal/send', '/api/terminal/close', '/api/terminal/signal', '/api/terminal/history'],
+  agents: ['/api/codex-sdk/sessions', '/api/automation/launch'],
+  ports: ['/api/ports', '/api/worktrees/ports', '/api/ports/tunnel'],
+  'git-write': [
+    'POST /api/repos/branch',
+    'POST /api/repos/remotes',
+    '/api/repos/move',
+    '/api/repos/init-command',
+    'PUT /api/repos/meta',
+    'POST /api/sessions/manifest',
+    'PUT /api/worktrees',
+    '/api/worktrees/rename',
+    '/api/worktrees/commit',
+    '/api/worktrees/push',
+    '/api/worktrees/pr-draft',
+    '/api/worktrees/reset',
+    '/api/worktrees/cherry-pick',
+    '/api/worktrees/cherry-pick/abort',
+    '/api/worktrees/stash',
+    '/api/worktrees/setup',
+    'PUT /api/worktrees/upstream',
+  ],
+};
+
+/**
+ * Routes deliberately served whatever features are enabled, in the same form as `FEATURE_ROUTES`: sign-in,
+ * health, reads of local repositories and worktrees, and creating or removing clones and worktrees, which the
+ * rest of the UI cannot work without
+ */
+export const UNGATED_ROUTES: readonly string[] = [
+  '/api/health',
+  '/api/health/detail',
+  '/api/auth/login',
+  '/api/auth/logout',
+  '/api/auth/status',
+  '/api/plan-mode/plans',
+  '/api/repos',
+  'GET /api/repos/meta',
+  'GET /api/repos/branch',
+  'GET /api/repos/remotes',
+  '/api/repos/config',
+  '/api/repos/clones',
+  '/api/repos/clones/cancel',
+  '/api/sessions',
+  '/api/sessions/tree',
+  'GET /api/sessions/manifest',
+  'HEAD /api/sessions/manifest',
+  'GET /api/worktrees',
+  'HEAD /api/worktrees',
+  'POST /api/worktrees',
+  'DELETE /api/worktrees',
+  '/api/worktrees/batch',
+  '/api/worktrees/usage',
+  '/api/worktrees/activity',
+  '/api/worktrees/status',
+  '/api/worktrees/file',
+  '/api/worktrees/blame',
+  '/api/worktrees/merge-base',
+  '/api/worktrees/archive',
+  '/api/worktrees/branch-check',
+  'GET /api/worktrees/upstream',
+  '/api/git/status',
+  '/api/git/diff',
+  '/api/git/compare',
+  '/api/git/tags',
+  '/api/commands',
+  '/api/create-plan',
+  '/api/plans',
+  '/api/plans/content',
+  '/api/events',
+  '/api/tasks',
+];
+
+/**
+ * Splits a `FEATURE_ROUTES` entry into its path and, for `METHOD path` entries, its method
+ */
+export function parseRouteEntry(entry: string): { method: string | null; path: string } {
+  const separator = entry.indexOf(' ');
+  return separator === -1
+    ? { method: null, path: entry }
+    : { method: entry.slice(0, separator), path: entry.slice(separator + 1) };
+}
 
 interface RouterDependencies {
   createAuthHandlers: typeof createAuthHandlers;
@@ -53,6 +237,11 @@ interface RouterDependencies {
   createEventStreamHandler: typeof createEventStreamHandler;
   createTaskHandlers: typeof createTaskHandlers;
   createPortHandlers: typeof createPortHandlers;
+  createHealthHandlers: typeof createHealthHandlers;
+  createGithubWebhookHandlers: typeof createGithubWebhookHandlers;
+  createGithubNotificationHandlers: typeof createGithubNotificationHandlers;
+  createGithubReadmeHandlers: typeof createGithubReadmeHandlers;
+  createGithubRateLimitHandlers: typeof createGithubRateLimitHandlers;
   sendJson: typeof sendJson;
   readJsonBody: typeof readJsonBody;
 }
@@ -74,6 +263,11 @@ const defaultDependencies: RouterDependencies = {
   createEventStreamHandler,
   createTaskHandlers,
   createPortHandlers,
+  createHealthHandlers,
+  createGithubWebhookHandlers,
+  createGithubNotificationHandlers,
+  createGithubReadmeHandlers,
+  createGithubRateLimitHandlers,
   sendJson,
   readJsonBody,
 };
@@ -99,6 +293,22 @@ export function createRouter({
   cookieManager,
   terminalSessionMode = 'auto',
   portManager,
+  allowedUrlSchemes,
+  rateLimiter,
+  cors,
+  readOnly = false,
+  features = new Set(FEATURES),
+  trustProxy = false,
+  githubClient = createGithubClient(),
+  githubWebhookSecret,
+  clone = {},
+  worktree = {},
+  signCommits = false,
+  setupCommand,
+  terminalEnv,
+  dangerousTerminals,
+  repositoryCache,
+  gitOutputLimitBytes,
 }: RouterConfig): Router {
   if (!authManager) {
     throw new Error('authManager is required');
@@ -110,7 +320,19 @@ export function createRouter({
     throw new Error('portManager is required');
   }
 
-  const authHandlers = getDependency('createAuthHandlers')(authManager, { cookieManager });
+  // Shared by the dashboard, which fills it, and the webhook receiver, which invalidates it
+  const summaryCache: NonNullable<RepoDashboardOverrides['summaryCache']> = new RepositorySummaryCache(
+    githubWebhookSecret?.trim() ? GITHUB_SUMMARY_CACHE_TTL_MS : 0
+  );
+  const worktreeService = createWorktreeService(workdir, branchNameGenerator, defaultBranches, githubClient, {
+    ...worktree,
+    signCommits,
+    setupCommand,
+    terminalEnv,
+    repositoryCache,
+  });
+
+  const authHandlers = getDependency('createAuthHandlers')(authManager, { cookieManager, trustProxy });
   const automationHandlers = getDependency('createAutomationHandlers')({
     workdir,
     agentCommands,
@@ -118,34 +340,100 @@ export function createRouter({
     branchNameGenerator,
     planService,
     defaultBranches,
+    trustProxy,
+    clone,
+    worktree,
+    terminalEnv,
+    repositoryCache,
+  });
+  const repoHandlers = getDependency('createRepoHandlers')(workdir, { allowedUrlSchemes, clone, repositoryCache });
+  const repoDashboardHandlers = getDependency('createRepoDashboardHandlers')(workdir, { githubClient, summaryCache });
+  const repoIssueHandlers = getDependency('createRepoIssueHandlers')(workdir, { githubClient });
+  const sessionHandlers = getDependency('createSessionHandlers')(workdir, {
+    allowedUrlSchemes,
+    clone,
+    worktree,
+    repositoryCache,
   });
-  const repoHandlers = getDependency('createRepoHandlers')(workdir);
-  const repoDashboardHandlers = getDependency('createRepoDashboardHandlers')(workdir);
-  const repoIssueHandlers = getDependency('createRepoIssueHandlers')(workdir);
-  const sessionHandlers = getDependency('createSessionHandlers')(workdir);
   const worktreeHandlers = getDependency('createWorktreeHandlers')(
     workdir,
     branchNameGenerator,
     defaultBranches,
+    { worktreeService },
   );
-  const worktreeService = createWorktreeService(workdir, branchNameGenerator, defaultBranches);
   const planModeService = createPlanModeService({ workdir, defaultBranches, worktreeService });
   const planModeHandlers = createPlanModeHandlers(planModeService);
   const terminalHandlers = getDependency('createTerminalHandlers')(workdir, {
     mode: terminalSessionMode,
+    dangerousTerminals,
+    env: terminalEnv,
   });
   const codexSdkHandlers = getDependency('createCodexSdkHandlers')(workdir);
   const configHandlers = getDependency('createConfigHandlers')(agentCommands as never);
   const planHandlers = getDependency('createPlanHandlers')({ planService: planService as never });
-  const gitStatusHandlers = getDependency('createGitStatusHandlers')(workdir);
+  const gitStatusHandlers = getDependency('createGitStatusHandlers')(workdir, { outputLimitBytes: gitOutputLimitBytes });
   const planArtifactHandlers = getDependency('createPlanArtifactHandlers')(workdir);
   const eventStreamHandler = getDependency('createEventStreamHandler')({ authManager, workdir });
   const taskHandlers = getDependency('createTaskHandlers')();
   const portHandlers = getDependency('createPortHandlers')({ portManager });
+  const healthHandlers = getDependency('createHealthHandlers')(workdir, {
+    githubClient,
+    cloneQueueStats: () => getCloneQueueStats(clone.queue),
+  });
+  const githubWebhookHandlers = getDependency('createGithubWebhookHandlers')({
+    secret: githubWebhookSecret,
+    summaryCache,
+  });
+  const githubNotificationHandlers = getDependency('createGithubNotificationHandlers')({ githubClient });
+  const githubReadmeHandlers = getDependency('createGithubReadmeHandlers')({ githubClient });
+  const githubRateLimitHandlers = getDependency('createGithubRateLimitHandlers')({ githubClient });
   const readJson = getDependency('readJsonBody');
   const sendJsonResponse = getDependency('sendJson');
 
   const routes = new Map([
+    [
+      '/api/health',
+      {
+        requiresAuth: false,
+        handlers: { GET: healthHandlers.basic },
+      },
+    ],
+    [
+      '/api/health/detail',
+      {
+        requiresAuth: true,
+        handlers: { GET: healthHandlers.detail },
+      },
+    ],
+    [
+      '/api/github/webhook',
+      {
+        // Deliveries prove themselves with their HMAC signature instead of a session cookie
+        requiresAuth: false,
+        handlers: { POST: githubWebhookHandlers.receive },
+      },
+    ],
+    [
+      '/api/github/notifications',
+      {
+        requiresAuth: true,
+        handlers: { GET: githubNotificationHandlers.list, HEAD: githubNotificationHandlers.list },
+      },
+    ],
+    [
+      '/api/github/readme',
+      {
+        requiresAuth: true,
+        handlers: { GET: githubReadmeHandlers.read, HEAD: githubReadmeHandlers.read },
+      },
+    ],
+    [
+      '/api/github/rate-limit',
+      {
+        requiresAuth: true,
+        handlers: { GET: githubRateLimitHandlers.read, HEAD: githubRateLimitHandlers.read },
+      },
+    ],
     [
       '/api/auth/login',
       {
@@ -160,6 +448,13 @@ export function createRouter({
         handlers: { GET: portHandlers.list, HEAD: portHandlers.list },
       },
     ],
+    [
+      '/api/worktrees/ports',
+      {
+        requiresAuth: true,
+        handlers: { GET: portHandlers.listForWorktree },
+      },
+    ],
     [
       '/api/ports/tunnel',
       {
@@ -203,6 +498,13 @@ export function createRouter({
         },
       },
     ],
+    [
+      '/api/repos/move',
+      {
+        requiresAuth: true,
+        handlers: { POST: repoHandlers.move },
+      },
+    ],
     [
       '/api/repos/init-command',
       {
@@ -210,6 +512,48 @@ export function createRouter({
         handlers: { POST: repoHandlers.updateInitCommand },
       },
     ],
+    [
+      '/api/repos/meta',
+      {
+        requiresAuth: true,
+        handlers: { GET: repoHandlers.readMetadata, PUT: repoHandlers.updateMetadata },
+      },
+    ],
+    [
+      '/api/repos/branch',
+      {
+        requiresAuth: true,
+        handlers: { GET: repoHandlers.readBranch, POST: repoHandlers.switchBranch },
+      },
+    ],
+    [
+      '/api/repos/remotes',
+      {
+        requiresAuth: true,
+        handlers: { GET: repoHandlers.listRemotes, POST: repoHandlers.addRemote },
+      },
+    ],
+    [
+      '/api/repos/config',
+      {
+        requiresAuth: true,
+        handlers: { GET: repoHandlers.readConfig, HEAD: repoHandlers.readConfig },
+      },
+    ],
+    [
+      '/api/repos/clones',
+      {
+        requiresAuth: true,
+        handlers: { GET: repoHandlers.listClones },
+      },
+    ],
+    [
+      '/api/repos/clones/cancel',
+      {
+        requiresAuth: true,
+        handlers: { POST: repoHandlers.cancelClone },
+      },
+    ],
     [
       '/api/repos/dashboard',
       {
@@ -217,6 +561,20 @@ export function createRouter({
         handlers: { GET: repoDashboardHandlers.read, HEAD: repoDashboardHandlers.read },
       },
     ],
+    [
+      '/api/repos/dashboard/summary',
+      {
+        requiresAuth: true,
+        handlers: { GET: repoDashboardHandlers.summary, HEAD: repoDashboardHandlers.summary },
+      },
+    ],
+    [
+      '/api/repos/detail',
+      {
+        requiresAuth: true,
+        handlers: { GET: repoDashboardHandlers.detail, HEAD: repoDashboardHandlers.detail },
+      },
+    ],
     [
       '/api/repos/issue',
       {
@@ -224,6 +582,13 @@ export function createRouter({
         handlers: { GET: repoIssueHandlers.read, HEAD: repoIssueHandlers.read },
       },
     ],
+    [
+      '/api/repos/search',
+      {
+        requiresAuth: true,
+        handlers: { GET: repoIssueHandlers.search, HEAD: repoIssueHandlers.search },
+      },
+    ],
     [
       '/api/sessions',
       {
@@ -231,16 +596,177 @@ export function createRouter({
         handlers: { GET: sessionHandlers.list, HEAD: sessionHandlers.list },
       },
     ],
+    [
+      '/api/sessions/tree',
+      {
+        requiresAuth: true,
+        handlers: { GET: sessionHandlers.tree, HEAD: sessionHandlers.tree },
+      },
+    ],
+    [
+      '/api/sessions/manifest',
+      {
+        requiresAuth: true,
+        handlers: {
+          GET: sessionHandlers.exportManifest,
+          HEAD: sessionHandlers.exportManifest,
+          POST: sessionHandlers.importManifest,
+        },
+      },
+    ],
     [
       '/api/worktrees',
       {
         requiresAuth: true,
         handlers: { 
+          GET: worktreeHandlers.list,
+          HEAD: worktreeHandlers.list,
           POST: worktreeHandlers.create,
+          PUT: worktreeHandlers.replace,
           DELETE: worktreeHandlers.delete,
         },
       },
     ],
+    [
+      '/api/worktrees/batch',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.createBatch },
+      },
+    ],
+    [
+      '/api/worktrees/usage',
+      {
+        requiresAuth: true,
+        handlers: { GET: worktreeHandlers.usage, HEAD: worktreeHandlers.usage },
+      },
+    ],
+    [
+      '/api/worktrees/activity',
+      {
+        requiresAuth: true,
+        handlers: { GET: worktreeHandlers.activity, HEAD: worktreeHandlers.activity },
+      },
+    ],
+    [
+      '/api/worktrees/status',
+      {
+        requiresAuth: true,
+        handlers: { GET: gitStatusHandlers.summary },
+      },
+    ],
+    [
+      '/api/worktrees/file',
+      {
+        requiresAuth: true,
+        handlers: { GET: gitStatusHandlers.file },
+      },
+    ],
+    [
+      '/api/worktrees/blame',
+      {
+        requiresAuth: true,
+        handlers: { GET: gitStatusHandlers.blame },
+      },
+    ],
+    [
+      '/api/worktrees/merge-base',
+      {
+        requiresAuth: true,
+        handlers: { GET: gitStatusHandlers.mergeBase },
+      },
+    ],
+    [
+      '/api/worktrees/archive',
+      {
+        requiresAuth: true,
+        handlers: { GET: gitStatusHandlers.archive },
+      },
+    ],
+    [
+      '/api/worktrees/branch-check',
+      {
+        requiresAuth: true,
+        handlers: { GET: worktreeHandlers.checkBranch, HEAD: worktreeHandlers.checkBranch },
+      },
+    ],
+    [
+      '/api/worktrees/rename',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.rename },
+      },
+    ],
+    [
+      '/api/worktrees/commit',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.commit },
+      },
+    ],
+    [
+      '/api/worktrees/push',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.push },
+      },
+    ],
+    [
+      '/api/worktrees/pr-draft',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.pullRequestDraft },
+      },
+    ],
+    [
+      '/api/worktrees/pull-request',
+      {
+        requiresAuth: true,
+        handlers: { GET: worktreeHandlers.pullRequest, HEAD: worktreeHandlers.pullRequest },
+      },
+    ],
+    [
+      '/api/worktrees/setup',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.setup },
+      },
+    ],
+    [
+      '/api/worktrees/reset',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.reset },
+      },
+    ],
+    [
+      '/api/worktrees/cherry-pick',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.cherryPick },
+      },
+    ],
+    [
+      '/api/worktrees/cherry-pick/abort',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.cherryPickAbort },
+      },
+    ],
+    [
+      '/api/worktrees/stash',
+      {
+        requiresAuth: true,
+        handlers: { POST: worktreeHandlers.stash },
+      },
+    ],
+    [
+      '/api/worktrees/upstream',
+      {
+        requiresAuth: true,
+        handlers: { GET: worktreeHandlers.readUpstream, PUT: worktreeHandlers.setUpstream },
+      },
+    ],
     [
       '/api/git/status',
       {
@@ -255,6 +781,20 @@ export function createRouter({
         handlers: { POST: gitStatusHandlers.diff },
       },
     ],
+    [
+      '/api/git/compare',
+      {
+        requiresAuth: true,
+        handlers: { GET: gitStatusHandlers.compare, HEAD: gitStatusHandlers.compare },
+      },
+    ],
+    [
+      '/api/git/tags',
+      {
+        requiresAuth: true,
+        handlers: { GET: gitStatusHandlers.tags, HEAD: gitStatusHandlers.tags },
+      },
+    ],
     [
       '/api/terminal/open',
       {
@@ -276,6 +816,20 @@ export function createRouter({
         handlers: { POST: terminalHandlers.close },
       },
     ],
+    [
+      '/api/terminal/signal',
+      {
+        requiresAuth: true,
+        handlers: { POST: terminalHandlers.signal },
+      },
+    ],
+    [
+      '/api/terminal/history',
+      {
+        requiresAuth: true,
+        handlers: { GET: terminalHandlers.history, HEAD: terminalHandlers.history },
+      },
+    ],
     [
       '/api/codex-sdk/sessions',
       {
@@ -333,6 +887,43 @@ export function createRouter({
       },
     ],
   ]);
+  // Paths of disabled features leave the table; single methods stay listed here and are answered like unknown routes
+  const disabledMethods = new Set<string>();
+  for (const [feature, entries] of Object.entries(FEATURE_ROUTES) as [Feature, readonly string[]][]) {
+    if (features.has(feature)) {
+      continue;
+    }
+    for (const entry of entries) {
+      const { method, path } = parseRouteEntry(entry);
+      if (method) {
+        disabledMethods.add(`${method} ${path}`);
+      } else {
+        routes.delete(path);
+      }
+    }
+  }
+  const servedMethods = (path: string, handlers: object): string[] =>
+    Object.keys(handlers).filter((method) => !disabledMethods.has(`${method} ${path}`));
+
+  function sendRouteNotFound(res: ServerResponse, pathname: string): void {
+    sendJsonResponse(res, 404, {
+      error: `No API route matches ${pathname}`,
+      code: 'not_found',
+      details: { path: pathname },
+    });
+  }
+
+  /**
+   * Answers 403 for a matched mutating route while the server is read-only; called only once a handler
+   * exists so unknown paths and methods keep their 404 and 405
+   */
+  function rejectReadOnly(res: ServerResponse, pathname: string, method: string): boolean {
+    if (!readOnly || SAFE_METHODS.has(method) || READ_ONLY_ALLOWED_ROUTES.has(pathname)) {
+      return false;
+    }
+    sendJsonResponse(res, 403, { error: 'Server is running in read-only mode', code: 'read_only_mode' });
+    return true;
+  }
 
   function handleMethodNotAllowed(res: ServerResponse, allowedMethods: string[] = []): void {
     const headerValue = allowedMethods.join(', ');
@@ -343,8 +934,21 @@ export function createRouter({
     res.end('Method Not Allowed');
   }
 
-  return async function route(req: IncomingMessage, res: ServerResponse): Promise<boolean> {
-    const url = new URL(req.url || '/', `http://${req.headers.host || 'localhost'}`);
+  async function route(req: IncomingMessage, res: ServerResponse): Promise<boolean> {
+    const url = new URL(req.url || '/', getRequestOrigin(req, { trustProxy }));
+    // Preflights carry no credentials, so they are answered before authentication and rate limiting
+    if (cors && url.pathname.startsWith('/api/') && cors.handle(req, res)) {
+      return true;
+    }
+    if (rateLimiter && url.pathname.startsWith('/api/')) {
+      const decision = rateLimiter.consume(getClientIp(req, { trustProxy }));
+      if (decision.limited) {
+        res.setHeader('Retry-After', String(Math.max(1, Math.ceil(decision.retryAfterMs / 1000))));
+        sendJsonResponse(res, 429, { error: 'Too many requests', code: 'rate_limited' });
+        return true;
+      }
+    }
+
     if (url.pathname.startsWith('/api/tasks/')) {
       if (!authManager.isAuthenticated(req)) {
         sendJsonResponse(res, 401, { error: 'Authentication required' });
@@ -372,7 +976,7 @@ export function createRouter({
       return true;
     }
 
-    if (url.pathname.startsWith('/api/codex-sdk/sessions/')) {
+    if (url.pathname.startsWith('/api/codex-sdk/sessions/') && features.has('agents')) {
       if (!authManager.isAuthenticated(req)) {
         sendJsonResponse(res, 401, { error: 'Authentication required' });
         return true;
@@ -387,6 +991,9 @@ export function createRouter({
         sendJsonResponse(res, 404, { error: 'Session not found' });
         return true;
       }
+      if (rejectReadOnly(res, url.pathname, method)) {
+        return true;
+      }
       const context = {
         req,
         res,
@@ -446,6 +1053,9 @@ export function createRouter({
         handleMethodNotAllowed(res, allowedMethods);
         return true;
       }
+      if (rejectReadOnly(res, url.pathname, method)) {
+        return true;
+      }
       const context = {
         req,
         res,
@@ -461,14 +1071,27 @@ export function createRouter({
 
     const route = routes.get(url.pathname);
     if (!route) {
-      return false;
+      // Everything else falls through to the UI, which serves the app for its own client-side routes
+      if (!url.pathname.startsWith('/api/')) {
+        return false;
+      }
+      sendRouteNotFound(res, url.pathname);
+      return true;
     }
 
     const method = (req.method?.toUpperCase() || 'GET') as keyof typeof route.handlers;
+    if (disabledMethods.has(`${method} ${url.pathname}`)) {
+      sendRouteNotFound(res, url.pathname);
+      return true;
+    }
     const allowed = route.handlers[method];
 
     if (!allowed) {
-      handleMethodNotAllowed(res, Object.keys(route.handlers));
+      handleMethodNotAllowed(res, servedMethods(url.pathname, route.handlers));
+      return true;
+    }
+
+    if (rejectReadOnly(res, url.pathname, method)) {
       return true;
     }
 
@@ -488,5 +1111,9 @@ export function createRouter({
 
     await allowed(context);
     return true;
-  };
+  }
+
+  return Object.assign(route, {
+    routes: new Map([...routes].map(([path, entry]) => [path, servedMethods(path, entry.handlers)] as const)),
+  });
 }
@@ -2,9 +2,9 @@ import assert from 'node:assert/strict';
 import fs from 'node:fs/promises';
 import os from 'node:os';
 import path from 'node:path';
-import { describe, it, before, after } from 'node:test';
+import { describe, it, before, after, mock } from 'node:test';
 
-import { createUiProvider } from './ui.js';
+import { createLandingProvider, createUiProvider, PLACEHOLDER_LANDING_PAGE, UiPathNotFoundError } from './ui.js';
 
 interface TestResponse {
   statusCode: number;
@@ -61,6 +61,7 @@ describe('createUiProvider', () => {
     await assert.rejects(() => createUiProvider(path.join(tmpDir, 'missing')), {
       message: /UI path not found/,
     });
+    await assert.rejects(() => createUiProvider(path.join(tmpDir, 'missing')), UiPathNotFoundError);
   });
 
   it('serves index and static assets from directory', async () => {
@@ -131,3 +132,65 @@ describe('createUiProvider', () => {
     assert.ok(res.getBody().toString('utf8').includes('Standalone'));
   });
 });
+
+describe('createLandingProvider', () => {
+  let tmpDir: string;
+
+  before(async () => {
+    tmpDir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-landing-test-'));
+  });
+
+  after(async () => {
+    await fs.rm(tmpDir, { recursive: true, force: true });
+  });
+
+  async function request(provider: Awaited<ReturnType<typeof createLandingProvider>>, url: string, method = 'GET') {
+    const res = createResponse();
+    await provider.serve(
+      { url, method } as unknown as { url: string; method: string },
+      res as unknown as Parameters<typeof provider.serve>[1],
+    );
+    return res;
+  }
+
+  it('serves a custom landing file on every path with its content type', async () => {
+    const landingPath = path.join(tmpDir, 'landing.html');
+    await fs.writeFile(landingPath, '<meta http-equiv="refresh" content="0; url=https://docs.example.com">', 'utf8');
+    const provider = await createLandingProvider(landingPath);
+    assert.equal(provider.type, 'landing');
+    assert.equal(provider.resolvedPath, landingPath);
+
+    for (const url of ['/', '/dashboard/view']) {
+      const res = await request(provider, url);
+      assert.equal(res.statusCode, 200);
+      assert.equal(res.headers.get('Content-Type'), 'text/html; charset=utf-8');
+      assert.equal(res.headers.get('Cache-Control'), 'no-store');
+      assert.match(res.getBody().toString('utf8'), /docs\.example\.com/);
+    }
+
+    const noticePath = path.join(tmpDir, 'notice.txt');
+    await fs.writeFile(noticePath, 'Headless deployment\n', 'utf8');
+    const notice = await request(await createLandingProvider(noticePath), '/');
+    assert.equal(notice.headers.get('Content-Type'), 'text/plain; charset=utf-8');
+    assert.equal(notice.getBody().toString('utf8'), 'Headless deployment\n');
+
+    const post = await request(provider, '/', 'POST');
+    assert.equal(post.statusCode, 405);
+  });
+
+  it('falls back to the built-in placeholder when unset or unreadable', async () => {
+    const warn = mock.method(console, 'warn', () => {});
+    try {
+      for (const provider of [await createLandingProvider(), await createLandingProvider(path.join(tmpDir, 'missing.html'))]) {
+        assert.equal(provider.resolvedPath, '');
+        const res = await request(provider, '/');
+        assert.equal(res.statusCode, 200);
+        assert.equal(res.headers.get('Content-Type'), 'text/html; charset=utf-8');
+        assert.equal(res.getBody().toString('utf8'), PLACEHOLDER_LANDING_PAGE);
+      }
+      assert.equal(warn.mock.callCount(), 1);
+    } finally {
+      warn.mock.restore();
+    }
+  });
+});
@@ -2,6 +2,7 @@ import type { IncomingMessage, ServerResponse } from 'node:http';
 import fs from 'node:fs/promises';
 import path from 'node:path';
 import { createRequire } from 'node:module';
+import { injectBasePathConfig, rewriteHtmlForBasePath } from '../utils/base-path.js';
 
 const require = createRequire(import.meta.url);
 
@@ -91,8 +92,12 @@ async function serveFile(res: ServerResponse, filePath: string, method: string):
   res.end(content);
 }
 
-async function createFileProvider(resolvedPath: string): Promise<UiProvider> {
-  const contents = await fs.readFile(resolvedPath, 'utf8');
+function prepareUiHtml(html: string, basePath: string): string {
+  return injectBasePathConfig(rewriteHtmlForBasePath(html, basePath), basePath);
+}
+
+async function createFileProvider(resolvedPath: string, basePath: string): Promise<UiProvider> {
+  const contents = prepareUiHtml(await fs.readFile(resolvedPath, 'utf8'), basePath);
 
   async function serve(req: IncomingMessage, res: ServerResponse): Promise<void> {
     const method = req.method?.toUpperCase() || 'GET';
@@ -119,9 +124,9 @@ async function createFileProvider(resolvedPath: string): Promise<UiProvider> {
   };
 }
 
-async function createDirectoryProvider(resolvedPath: string): Promise<UiProvider> {
+async function createDirectoryProvider(resolvedPath: string, basePath: string): Promise<UiProvider> {
   const indexPath = path.join(resolvedPath, 'index.html');
-  const indexHtml = await fs.readFile(indexPath, 'utf8');
+  const indexHtml = prepareUiHtml(await fs.readFile(indexPath, 'utf8'), basePath);
 
   async function serve(req: IncomingMessage, res: ServerResponse): Promise<void> {
     const method = req.method?.toUpperCase() || 'GET';
@@ -169,7 +174,7 @@ async function createDirectoryProvider(resolvedPath: string): Promise<UiProvider
           return;
         }
         const html = await fs.readFile(targetPath, 'utf8');
-        res.end(html);
+        res.end(prepareUiHtml(html, basePath));
         return;
       }
 
@@ -214,7 +219,102 @@ export interface UiProvider {
   serve(req: IncomingMessage, res: ServerResponse): Promise<void>;
 }
 
-export async function createUiProvider(uiPath: string): Promise<UiProvider> {
+export interface UiProviderOptions {
+  /**
+   * Normalised prefix the server is mounted under; root-relative links in served HTML are prefixed with it, and
+   * the page is told the prefix through `<base href>` and `window.__AGENTRIX_BASE__`
+   */
+  basePath?: string;
+}
+
+/**
+ * Error raised when the UI path does not exist, typically because the frontend has not been built
+ */
+export class UiPathNotFoundError extends Error {
+  constructor(public readonly resolvedPath: string) {
+    super(`UI path not found at ${resolvedPath}`);
+    this.name = 'UiPathNotFoundError';
+  }
+}
+
+/**
+ * Renders the page served in place of the UI when no frontend is built and no landing page is configured
+ * @param basePath - Normalised prefix the server is mounted under
+ */
+export function renderPlaceholderLandingPage(basePath = ''): string {
+  return `<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <title>Agentrix</title>
+  </head>
+  <body>
+    <h1>Agentrix</h1>
+    <p>The API is running at <a href="${basePath}/api/health"><code>${basePath}/api/</code></a>. No frontend has been built for this server.</p>
+  </body>
+</html>
+`;
+}
+
+/**
+ * Placeholder page for a server mounted at the root
+ */
+export const PLACEHOLDER_LANDING_PAGE = renderPlaceholderLandingPage();
+
+/**
+ * Creates a provider answering every UI request with one page: the landing page file when it can be read,
+ * otherwise the built-in placeholder. The file's extension picks its content type, so a plain text notice or an
+ * HTML page with a redirect both work.
+ * @param landingPage - Path to the landing page file; unset serves the placeholder
+ * @param options - Base path the placeholder's links are relative to
+ */
+export async function createLandingProvider(
+  landingPage?: string | null,
+  options: UiProviderOptions = {}
+): Promise<UiProvider> {
+  let resolvedPath = '';
+  let contents: Buffer | string = renderPlaceholderLandingPage(options.basePath);
+  if (landingPage) {
+    try {
+      const candidate = path.resolve(landingPage);
+      contents = await fs.readFile(candidate);
+      resolvedPath = candidate;
+    } catch (error: unknown) {
+      console.warn(`[agentrix] Cannot read landing page ${landingPage}; serving the built-in placeholder:`, error);
+    }
+  }
+
+  async function serve(req: IncomingMessage, res: ServerResponse): Promise<void> {
+    const method = req.method?.toUpperCase() || 'GET';
+    if (method !== 'GET' && method !== 'HEAD') {
+      res.statusCode = 405;
+      res.setHeader('Allow', 'GET, HEAD');
+      res.end('Method Not Allowed');
+      return;
+    }
+    if (resolvedPath) {
+      applyContentType(res, resolvedPath);
+    } else {
+      res.setHeader('Content-Type', 'text/html; charset=utf-8');
+    }
+    res.setHeader('Cache-Control', 'no-store');
+    res.statusCode = 200;
+    if (method === 'HEAD') {
+      res.end();
+      return;
+    }
+    res.end(contents);
+  }
+
+  return {
+    type: 'landing',
+    resolvedPath,
+    serve,
+  };
+}
+
+export async function createUiProvider(uiPath: string, options: UiProviderOptions = {}): Promise<UiProvider> {
+  const basePath = options.basePath ?? '';
   const resolvedPath = path.resolve(uiPath);
   let stats;
   try {
@@ -222,17 +322,17 @@ export async function createUiProvider(uiPath: string): Promise<UiProvider> {
   } catch (error: unknown) {
     const err = error as { code?: string };
     if (err && err.code === 'ENOENT') {
-      throw new Error(`UI path not found at ${resolvedPath}`);
+      throw new UiPathNotFoundError(resolvedPath);
     }
     throw error;
   }
 
   if (stats.isDirectory()) {
-    return createDirectoryProvider(resolvedPath);
+    return createDirectoryProvider(resolvedPath, basePath);
   }
 
   if (stats.isFile()) {
-    return createFileProvider(resolvedPath);
+    return createFileProvider(resolvedPath, basePath);
   }
 
   throw new Error(`UI path must be a file or directory: ${resolvedPath}`);
@@ -231,4 +231,46 @@ describe('attachTerminalWebSockets', () => {
     await attachment.close();
     assert.equal(wsServer.closed, true);
   });
+
+  it('drops input frames in read-only mode but still streams and resizes', () => {
+    const session = {
+      id: 'session-1',
+      log: 'hello',
+      closed: false,
+      exitCode: null,
+      exitSignal: null,
+      exitError: null,
+      process: {
+        resize: mock.fn(),
+      },
+    };
+    overrides.getSessionById.mock.mockImplementation(() => session);
+
+    const server = new EventEmitter() as unknown as { on: EventEmitter['on'] };
+    const attachment = attachTerminalWebSockets(server as never, createAuthManager(true), { readOnly: true });
+
+    (server as EventEmitter).emit(
+      'upgrade',
+      {
+        url: '/api/terminal/socket?sessionId=session-1',
+        headers: {
+          host: 'localhost',
+          cookie: `${SESSION_COOKIE_NAME}=token`,
+        },
+      } as unknown,
+      new FakeSocket() as unknown,
+      Buffer.alloc(0),
+    );
+
+    const client = Array.from((attachment.wss as unknown as FakeWebSocketServer).clients)[0]!;
+    assert.equal(client.sent.some((message) => String(message).includes('"type":"init"')), true);
+
+    client.emit('message', Buffer.from('ls\n'), true);
+    client.emit('message', '{"type":"input","data":"ls\\n"}', false);
+    client.emit('message', 'raw-bytes', false);
+    client.emit('message', '{"type":"resize","cols":"80","rows":"24"}', false);
+
+    assert.equal(overrides.queueSessionInput.mock.calls.length, 0);
+    assert.equal(session.process.resize.mock.calls.length, 1);
+  });
 });
@@ -6,6 +6,7 @@ import type { AuthManager } from '../types/auth.js';
 
 import { SESSION_COOKIE_NAME } from '../config/constants.js';
 import { parseCookies } from '../utils/cookies.js';
+import { isValidSessionId } from '../domain/index.js';
 import {
   addSocketWatcher,
   getSessionById,
@@ -17,6 +18,13 @@ export interface WebSocketAttachment {
   close: () => Promise<void>;
 }
 
+export interface TerminalWebSocketOptions {
+  /**
+   * Drops input frames so clients can watch terminals but not type into them
+   */
+  readOnly?: boolean;
+}
+
 interface WebSocketDependencies {
   WebSocketServer: typeof WebSocketServer;
   parseCookies: typeof parseCookies;
@@ -43,7 +51,11 @@ function getDependency<K extends keyof WebSocketDependencies>(key: K): WebSocket
   return (testOverrides?.[key] ?? defaultDependencies[key]) as WebSocketDependencies[K];
 }
 
-export function attachTerminalWebSockets(server: HttpServer, authManager: AuthManager): WebSocketAttachment {
+export function attachTerminalWebSockets(
+  server: HttpServer,
+  authManager: AuthManager,
+  { readOnly = false }: TerminalWebSocketOptions = {}
+): WebSocketAttachment {
   const WebSocketServerImpl = getDependency('WebSocketServer');
   const wss = new WebSocketServerImpl({ noServer: true });
 
@@ -56,6 +68,11 @@ export function attachTerminalWebSockets(server: HttpServer, authManager: AuthMa
         socket.close();
         return;
       }
+      if (!isValidSessionId(sessionId)) {
+        socket.send(JSON.stringify({ type: 'error', message: 'sessionId is invalid' }));
+        socket.close();
+        return;
+      }
 
       const session = getDependency('getSessionById')(sessionId);
       if (!session) {
@@ -71,6 +88,9 @@ export function attachTerminalWebSockets(server: HttpServer, authManager: AuthMa
           return;
         }
         if (isBinary) {
+          if (readOnly) {
+            return;
+          }
           const buffer = normaliseRawData(data);
           if (buffer.length > 0) {
             getDependency('queueSessionInput')(session, buffer);
@@ -86,6 +106,9 @@ export function attachTerminalWebSockets(server: HttpServer, authManager: AuthMa
         }
 
         if (parsed && parsed.type === 'input') {
+          if (readOnly) {
+            return;
+          }
           const payload = typeof parsed.data === 'string' ? parsed.data : '';
           getDependency('queueSessionInput')(session, payload);
         } else if (parsed && parsed.type === 'resize') {
@@ -94,7 +117,7 @@ export function attachTerminalWebSockets(server: HttpServer, authManager: AuthMa
           if (Number.isInteger(cols) && Number.isInteger(rows) && cols > 0 && rows > 0) {
             session.process.resize(cols, rows);
           }
-        } else if (raw) {
+        } else if (raw && !readOnly) {
           getDependency('queueSessionInput')(session, raw);
         }
       });
@@ -1,11 +1,27 @@
 export { RepositoryService, createRepositoryService } from './repository-service.js';
-export type { RepositoriesData, AddRepositoryResult } from './repository-service.js';
+export type { RepositoriesData, AddRepositoryResult, RepositoryMetadataResult, RepositoryServiceOptions } from './repository-service.js';
 
 export { WorktreeService, createWorktreeService } from './worktree-service.js';
-export type { CreateWorktreeResult } from './worktree-service.js';
+export type {
+  CreateWorktreeResult,
+  ReplaceWorktreeServiceResult,
+  RenameWorktreeServiceResult,
+  CommitWorktreeServiceResult,
+  PushWorktreeServiceResult,
+  WorktreeSetupServiceResult,
+  ResetWorktreeServiceResult,
+  WorktreeDryRunResult,
+  ReusedWorktreeResult,
+  WorktreeBatchResult,
+} from './worktree-service.js';
 
 export { TerminalService, createTerminalService } from './terminal-service.js';
-export type { TerminalOpenResult, TerminalSendResult, TerminalCloseResult } from './terminal-service.js';
+export type {
+  TerminalOpenResult,
+  TerminalSendResult,
+  TerminalCloseResult,
+  TerminalSignalResult,
+} from './terminal-service.js';
 
 export { CodexSdkService, createCodexSdkService } from './codex-sdk-service.js';
 export type { CodexSdkSessionDetail } from './codex-sdk-service.js';
@@ -17,3 +33,11 @@ export type { AuthResult } from './auth-service.js';
 
 export { SessionService, createSessionService } from './session-service.js';
 export type { SessionInfo } from './session-service.js';
+
+export { ManifestService, createManifestService } from './manifest-service.js';
+export type {
+  SessionManifest,
+  ManifestImportItem,
+  ManifestImportResult,
+  ManifestServiceOptions,
+} from './manifest-service.js';
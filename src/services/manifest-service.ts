@@ -0,0 +1,282 @@
+import path from 'node:path';
+import {
+  createWorktree,
+  findExistingWorktree,
+  findWorktreeMergeBase,
+  getRemoteUrl,
+  InvalidBaseRefError,
+  listRepositoryTree,
+  listWorktrees,
+  stripUrlCredentials,
+  verifyWorktreeBase,
+  type WorktreeSettings,
+} from '../core/git.js';
+import { cloneRepository, type CloneSettings } from '../repositories/repository-repository.js';
+import { resolveRepositoryPaths } from '../repositories/repository-paths.js';
+import { moveRepository } from '../core/repositories.js';
+import { resolveDefaultBranch } from '../core/default-branch.js';
+import { getRepositoryInitCommand } from '../core/repository-config.js';
+import { importPlan, listPlans } from '../core/plan-mode-store.js';
+import { mapWithConcurrency } from '../utils/concurrency.js';
+import { refreshRepositoryCache, type RepositoryCacheOptions } from '../utils/repository-cache.js';
+import { SESSION_MANIFEST_VERSION } from '../validation/index.js';
+import type {
+  ManifestPlanInput,
+  ManifestRepositoryInput,
+  ManifestWorktreeInput,
+  SessionManifestInput,
+} from '../validation/index.js';
+
+/**
+ * Repositories described at once while exporting a manifest
+ */
+export const MANIFEST_EXPORT_CONCURRENCY = 4;
+
+export interface SessionManifest extends SessionManifestInput {
+  exportedAt: string;
+}
+
+export type ManifestImportStatus = 'created' | 'exists' | 'skipped' | 'failed';
+
+export interface ManifestImportItem {
+  kind: 'repository' | 'worktree' | 'plan';
+  org: string;
+  repo: string;
+  /**
+   * Branch of a worktree item
+   */
+  branch?: string;
+  /**
+   * Id of a plan item
+   */
+  id?: string;
+  status: ManifestImportStatus;
+  /**
+   * Ref a created worktree's new branch started from; null when it started from the default base
+   */
+  base?: string | null;
+  error?: string;
+}
+
+export interface ManifestImportResult {
+  items: ManifestImportItem[];
+}
+
+export interface ManifestServiceOptions {
+  /**
+   * URL schemes accepted when cloning (defaults to the domain allowlist)
+   */
+  allowedUrlSchemes?: string[];
+  /**
+   * Default org, git config and queue applied to every clone
+   */
+  clone?: CloneSettings;
+  /**
+   * Identity, default base, limit and git config applied to the worktrees it creates
+   */
+  worktree?: WorktreeSettings;
+  repositoryCache?: RepositoryCacheOptions;
+}
+
+function describeFailure(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+/**
+ * Service exporting the work directory's repositories, worktrees and plans as a manifest, and recreating
+ * them from one
+ */
+export class ManifestService {
+  constructor(
+    private readonly workdir: string,
+    private readonly options: ManifestServiceOptions = {}
+  ) {}
+
+  /**
+   * Describes every repository in the work directory with its worktrees and plans
+   * @returns Manifest that `importManifest` can recreate elsewhere
+   */
+  async exportManifest(): Promise<SessionManifest> {
+    const tree = await listRepositoryTree(this.workdir);
+    const entries = tree.flatMap(({ org, repos }) => repos.map((repo) => ({ org, repo })));
+    const repositories = await mapWithConcurrency(entries, MANIFEST_EXPORT_CONCURRENCY, ({ org, repo }) =>
+      this.describeRepository(org, repo)
+    );
+    return { version: SESSION_MANIFEST_VERSION, exportedAt: new Date().toISOString(), repositories };
+  }
+
+  /**
+   * Clones the manifest's missing repositories, then creates its missing worktrees and plans. Anything that
+   * already exists is left alone, so importing the same manifest twice changes nothing.
+   * @param manifest - Validated manifest
+   * @returns The outcome of every repository, worktree and plan, in manifest order
+   */
+  async importManifest(manifest: SessionManifestInput): Promise<ManifestImportResult> {
+    const tree = await listRepositoryTree(this.workdir);
+    const existing = new Set(tree.flatMap(({ org, repos }) => repos.map((repo) => `${org}/${repo}`)));
+    const items: ManifestImportItem[] = [];
+
+    for (const repository of manifest.repositories) {
+      const cloned = await this.importRepository(repository, existing.has(`${repository.org}/${repository.repo}`));
+      items.push(cloned);
+      for (const worktree of repository.worktrees) {
+        items.push(
+          cloned.status === 'failed'
+            ? {
+                kind: 'worktree',
+                org: repository.org,
+                repo: repository.repo,
+                branch: worktree.branch,
+                status: 'skipped',
+                error: 'The repository was not imported',
+              }
+            : await this.importWorktree(repository, worktree)
+        );
+      }
+      for (const plan of repository.plans) {
+        items.push(await this.importPlan(repository, plan));
+      }
+    }
+
+    if (items.some((item) => item.kind !== 'plan' && item.status === 'created')) {
+      await refreshRepositoryCache(this.workdir, this.options.repositoryCache);
+    }
+    return { items };
+  }
+
+  private async describeRepository(org: string, repo: string): Promise<ManifestRepositoryInput> {
+    const { repoRoot, repositoryPath } = resolveRepositoryPaths(this.workdir, org, repo);
+    const [url, initCommand, defaultBranch, entries, plans] = await Promise.all([
+      getRemoteUrl(repositoryPath),
+      getRepositoryInitCommand(repoRoot).catch(() => ''),
+      resolveDefaultBranch(repositoryPath),
+      listWorktrees(repositoryPath),
+      listPlans({ workdir: this.workdir, org, repo }),
+    ]);
+
+    const primaryPath = path.resolve(repositoryPath);
+    const branches = entries
+      .filter((entry) => entry.branch && entry.path && path.resolve(entry.path) !== primaryPath)
+      .map((entry) => entry.branch!);
+    const worktrees = await Promise.all(
+      branches.map(async (branch): Promise<ManifestWorktreeInput> => {
+        try {
+          const { headCommit, mergeBase } = await findWorktreeMergeBase(this.workdir, org, repo, branch, {
+            against: defaultBranch,
+          });
+          return { branch, headSha: headCommit, baseSha: mergeBase };
+        } catch {
+          return { branch, headSha: null, baseSha: null };
+        }
+      })
+    );
+
+    return {
+      org,
+      repo,
+      // Tokens embedded in the remote stay on this machine; the importer authenticates its own way
+      url: url && stripUrlCredentials(url),
+      initCommand,
+      defaultBranch,
+      worktrees,
+      plans: plans.map((plan) => ({
+        id: plan.id,
+        title: plan.title,
+        markdown: plan.markdown,
+        status: plan.status,
+        source: plan.source,
+        createdAt: plan.createdAt,
+        updatedAt: plan.updatedAt,
+        defaultBranch: plan.defaultBranch,
+        worktreeBranch: plan.worktreeBranch,
+      })),
+    };
+  }
+
+  private async importRepository(repository: ManifestRepositoryInput, exists: boolean): Promise<ManifestImportItem> {
+    const { org, repo, url, initCommand } = repository;
+    if (exists) {
+      return { kind: 'repository', org, repo, status: 'exists' };
+    }
+    if (!url) {
+      return { kind: 'repository', org, repo, status: 'failed', error: 'The manifest has no URL to clone from' };
+    }
+
+    const { allowedUrlSchemes } = this.options;
+    try {
+      const cloned = await cloneRepository(this.workdir, url, {
+        ...this.options.clone,
+        ...(initCommand ? { initCommand } : {}),
+        ...(allowedUrlSchemes ? { allowedSchemes: allowedUrlSchemes } : {}),
+      });
+      // The clone lands where its URL points; the manifest's names win, as the repository may have been moved
+      if (cloned.org !== org || cloned.repo !== repo) {
+        await moveRepository(this.workdir, cloned.org, cloned.repo, org, repo);
+      }
+      return { kind: 'repository', org, repo, status: 'created' };
+    } catch (error: unknown) {
+      return { kind: 'repository', org, repo, status: 'failed', error: describeFailure(error) };
+    }
+  }
+
+  /**
+   * Creates a worktree from the best ref still available: the pushed branch, then the exported commit,
+   * then the point it left the default branch
+   */
+  private async importWorktree(
+    repository: ManifestRepositoryInput,
+    worktree: ManifestWorktreeInput
+  ): Promise<ManifestImportItem> {
+    const { org, repo, defaultBranch } = repository;
+    const { branch } = worktree;
+    try {
+      if (await findExistingWorktree(this.workdir, org, repo, branch)) {
+        return { kind: 'worktree', org, repo, branch, status: 'exists' };
+      }
+
+      let base: string | null = null;
+      for (const candidate of [`origin/${branch}`, worktree.headSha, worktree.baseSha]) {
+        if (!candidate) {
+          continue;
+        }
+        try {
+          base = await verifyWorktreeBase(this.workdir, org, repo, candidate);
+          break;
+        } catch (error: unknown) {
+          if (!(error instanceof InvalidBaseRefError)) {
+            throw error;
+          }
+        }
+      }
+
+      await createWorktree(this.workdir, org, repo, branch, {
+        ...this.options.worktree,
+        ...(defaultBranch ? { defaultBranchOverride: defaultBranch } : {}),
+        base,
+      });
+      return { kind: 'worktree', org, repo, branch, status: 'created', base };
+    } catch (error: unknown) {
+      return { kind: 'worktree', org, repo, branch, status: 'failed', error: describeFailure(error) };
+    }
+  }
+
+  private async importPlan(repository: ManifestRepositoryInput, plan: ManifestPlanInput): Promise<ManifestImportItem> {
+    const { org, repo } = repository;
+    try {
+      const { created } = await importPlan({ workdir: this.workdir, org, repo }, plan);
+      return { kind: 'plan', org, repo, id: plan.id, status: created ? 'created' : 'exists' };
+    } catch (error: unknown) {
+      return { kind: 'plan', org, repo, id: plan.id, status: 'failed', error: describeFailure(error) };
+    }
+  }
+}
+
+/**
+ * Creates a manifest service instance
+ * @param workdir - Work directory root
+ * @param options - Service options
+ * @returns ManifestService instance
+ */
+export function createManifestService(workdir: string, options: ManifestServiceOptions = {}): ManifestService {
+  return new ManifestService(workdir, options);
+}
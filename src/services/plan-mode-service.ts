@@ -199,6 +199,7 @@ export class PlanModeService {
       branch,
       prompt: plan.markdown,
       hasPrompt: Boolean(plan.markdown),
+      dryRun: false,
     });
     await deletePlan({ workdir: this.workdir, org, repo, id });
     if (plan.codexSessionId) {
@@ -13,14 +13,14 @@ describe('RepositoryService', () => {
     __setRepositoryServiceTestOverrides();
   });
 
-  it('lists repositories using repository discovery', async () => {
+  it('lists repositories through the repository cache', async () => {
     const structure = { acme: { demo: { branches: ['main'] } } };
     const discoverMock = mock.fn(async (workdir: string) => {
       assert.equal(workdir, '/work');
       return structure;
     });
 
-    __setRepositoryServiceTestOverrides({ discoverRepositories: discoverMock });
+    __setRepositoryServiceTestOverrides({ getCachedRepositories: discoverMock });
 
     const service = new RepositoryService('/work');
     const result = await service.listRepositories();
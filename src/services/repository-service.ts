@@ -1,7 +1,31 @@
-import { cloneRepository, discoverRepositories, ensureRepository } from '../repositories/repository-repository.js';
+import { randomUUID } from 'node:crypto';
+import {
+  cancelClone,
+  cloneRepository,
+  ensureRepository,
+  getRepositoryBranch,
+  listActiveClones,
+  switchRepositoryBranch,
+  type ActiveClone,
+  type CloneSettings,
+  type RepositoryBranchInfo,
+} from '../repositories/repository-repository.js';
+import {
+  addRepositoryRemote,
+  listRepositoryRemotes,
+  type RepositoryRemote,
+} from '../repositories/git-remote-repository.js';
+import { readRepositoryConfig } from '../repositories/git-config-repository.js';
+import { NotFoundError } from '../infrastructure/errors/index.js';
 import { setRepositoryInitCommand } from '../core/repository-config.js';
-import { removeRepository } from '../core/repositories.js';
-import { refreshRepositoryCache } from '../utils/repository-cache.js';
+import { loadRepositoryMetadata, saveRepositoryMetadata } from '../core/repository-metadata.js';
+import type { CloneFilter, RepositoryMetadata } from '../domain/index.js';
+import { moveRepository, removeRepository } from '../core/repositories.js';
+import {
+  getCachedRepositories,
+  refreshRepositoryCache,
+  type RepositoryCacheOptions,
+} from '../utils/repository-cache.js';
 import type { IRepositoryService } from '../types/services.js';
 
 export interface RepositoriesData {
@@ -9,34 +33,97 @@ export interface RepositoriesData {
     [repo: string]: {
       branches: string[];
       initCommand: string;
+      metadata: Record<string, string>;
     };
   };
 }
 
+export interface RepositoryMetadataResult {
+  org: string;
+  repo: string;
+  metadata: RepositoryMetadata;
+}
+
+export interface RepositoryServiceOptions {
+  /**
+   * URL schemes accepted when cloning (defaults to the domain allowlist)
+   */
+  allowedUrlSchemes?: string[];
+  /**
+   * Default org, git config and queue applied to every clone
+   */
+  clone?: CloneSettings;
+  repositoryCache?: RepositoryCacheOptions;
+}
+
 export interface AddRepositoryResult {
   data: RepositoriesData;
   repo: {
     org: string;
     repo: string;
   };
+  cloneId: string;
+}
+
+export interface MoveRepositoryResult {
+  data: RepositoriesData;
+  repo: {
+    org: string;
+    repo: string;
+  };
+}
+
+export interface RepositoryRemotesResult {
+  org: string;
+  repo: string;
+  remotes: RepositoryRemote[];
+}
+
+export interface RepositoryRemoteResult {
+  org: string;
+  repo: string;
+  remote: RepositoryRemote;
+}
+
+export interface RepositoryConfigResult {
+  org: string;
+  repo: string;
+  key: string;
+  value: string;
 }
 
 type RepositoryServiceDependencyOverrides = Partial<{
-  discoverRepositories: typeof discoverRepositories;
+  getCachedRepositories: typeof getCachedRepositories;
   cloneRepository: typeof cloneRepository;
   refreshRepositoryCache: typeof refreshRepositoryCache;
   removeRepository: typeof removeRepository;
+  moveRepository: typeof moveRepository;
   ensureRepository: typeof ensureRepository;
   setRepositoryInitCommand: typeof setRepositoryInitCommand;
+  getRepositoryBranch: typeof getRepositoryBranch;
+  switchRepositoryBranch: typeof switchRepositoryBranch;
+  listRepositoryRemotes: typeof listRepositoryRemotes;
+  addRepositoryRemote: typeof addRepositoryRemote;
+  readRepositoryConfig: typeof readRepositoryConfig;
+  loadRepositoryMetadata: typeof loadRepositoryMetadata;
+  saveRepositoryMetadata: typeof saveRepositoryMetadata;
 }>;
 
 const repositoryServiceDependencies = {
-  discoverRepositories,
+  getCachedRepositories,
   cloneRepository,
   refreshRepositoryCache,
   removeRepository,
+  moveRepository,
   ensureRepository,
   setRepositoryInitCommand,
+  getRepositoryBranch,
+  switchRepositoryBranch,
+  listRepositoryRemotes,
+  addRepositoryRemote,
+  readRepositoryConfig,
+  loadRepositoryMetadata,
+  saveRepositoryMetadata,
 } as const;
 
 let repositoryServiceTestOverrides: RepositoryServiceDependencyOverrides | null = null;
@@ -60,30 +147,70 @@ export function __setRepositoryServiceTestOverrides(overrides?: RepositoryServic
  * Service for repository management operations
  */
 export class RepositoryService implements IRepositoryService {
-  constructor(private readonly workdir: string) {}
+  constructor(
+    private readonly workdir: string,
+    private readonly options: RepositoryServiceOptions = {}
+  ) {}
 
   /**
-   * Lists all repositories
+   * Lists all repositories, served from the repository cache while it is fresh
    * @returns Repository data
    */
   async listRepositories(): Promise<RepositoriesData> {
-    const discover = resolveRepositoryServiceDependency('discoverRepositories');
-    return await discover(this.workdir);
+    const readRepositories = resolveRepositoryServiceDependency('getCachedRepositories');
+    return await readRepositories(this.workdir, this.options.repositoryCache);
   }
 
   /**
    * Adds a new repository
    * @param repositoryUrl - Git repository URL
    * @param initCommand - Optional init command
+   * @param cloneId - Id the running clone can be cancelled by (generated when omitted)
+   * @param signal - Aborting it drops the clone while it is still waiting for a clone slot
+   * @param filter - Partial clone filter, e.g. `blob:none` for a large repository
    * @returns Result with repository data
    */
-  async addRepository(repositoryUrl: string, initCommand: string = ''): Promise<AddRepositoryResult> {
+  async addRepository(
+    repositoryUrl: string,
+    initCommand: string = '',
+    cloneId: string = randomUUID(),
+    signal?: AbortSignal,
+    filter?: CloneFilter
+  ): Promise<AddRepositoryResult> {
     const clone = resolveRepositoryServiceDependency('cloneRepository');
     const refresh = resolveRepositoryServiceDependency('refreshRepositoryCache');
 
-    const repoInfo = await clone(this.workdir, repositoryUrl, { initCommand });
-    const data = await refresh(this.workdir);
-    return { data, repo: repoInfo };
+    const { allowedUrlSchemes } = this.options;
+    const repoInfo = await clone(this.workdir, repositoryUrl, {
+      ...this.options.clone,
+      initCommand,
+      cloneId,
+      ...(signal ? { signal } : {}),
+      ...(filter ? { filter } : {}),
+      ...(allowedUrlSchemes ? { allowedSchemes: allowedUrlSchemes } : {}),
+    });
+    const data = await refresh(this.workdir, this.options.repositoryCache);
+    return { data, repo: repoInfo, cloneId };
+  }
+
+  /**
+   * Lists clones that are still running
+   * @returns Running clones with their ids
+   */
+  listClones(): ActiveClone[] {
+    return listActiveClones();
+  }
+
+  /**
+   * Cancels a running clone
+   * @param cloneId - Id returned by or passed to addRepository
+   * @throws {NotFoundError} If no clone with that id is running
+   */
+  cancelClone(cloneId: string): { cloneId: string; cancelled: true } {
+    if (!cancelClone(cloneId)) {
+      throw new NotFoundError(`Clone ${cloneId}`);
+    }
+    return { cloneId, cancelled: true };
   }
 
   /**
@@ -97,7 +224,35 @@ export class RepositoryService implements IRepositoryService {
     const refresh = resolveRepositoryServiceDependency('refreshRepositoryCache');
 
     await remove(this.workdir, org, repo);
-    return await refresh(this.workdir);
+    return await refresh(this.workdir, this.options.repositoryCache);
+  }
+
+  /**
+   * Renames a repository, moving its clone and worktrees to a new org/repo
+   * @param org - Current organization name
+   * @param repo - Current repository name
+   * @param newOrg - Destination organization name
+   * @param newRepo - Destination repository name
+   * @returns Updated repository data and the new identifiers
+   * @throws {NotFoundError} If the repository does not exist
+   */
+  async moveRepository(org: string, repo: string, newOrg: string, newRepo: string): Promise<MoveRepositoryResult> {
+    const ensure = resolveRepositoryServiceDependency('ensureRepository');
+    const move = resolveRepositoryServiceDependency('moveRepository');
+    const refresh = resolveRepositoryServiceDependency('refreshRepositoryCache');
+
+    try {
+      await ensure(this.workdir, org, repo);
+    } catch (error: unknown) {
+      if ((error as { statusCode?: number })?.statusCode) {
+        throw error;
+      }
+      throw new NotFoundError(`Repository ${org}/${repo}`, error instanceof Error ? error : null);
+    }
+
+    const moved = await move(this.workdir, org, repo, newOrg, newRepo);
+    const data = await refresh(this.workdir, this.options.repositoryCache);
+    return { data, repo: moved };
   }
 
   /**
@@ -114,15 +269,121 @@ export class RepositoryService implements IRepositoryService {
 
     const { repoRoot } = await ensure(this.workdir, org, repo);
     await setInit(repoRoot, initCommand);
-    return await refresh(this.workdir);
+    return await refresh(this.workdir, this.options.repositoryCache);
+  }
+
+  /**
+   * Reads the metadata attached to a repository
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @returns The repository's metadata (empty when none is stored)
+   */
+  async getMetadata(org: string, repo: string): Promise<RepositoryMetadataResult> {
+    const ensure = resolveRepositoryServiceDependency('ensureRepository');
+    const load = resolveRepositoryServiceDependency('loadRepositoryMetadata');
+
+    await ensure(this.workdir, org, repo);
+    return { org, repo, metadata: await load(org, repo) };
+  }
+
+  /**
+   * Replaces the metadata attached to a repository
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param metadata - New metadata; an empty map clears it
+   * @returns The stored metadata
+   */
+  async updateMetadata(org: string, repo: string, metadata: RepositoryMetadata): Promise<RepositoryMetadataResult> {
+    const ensure = resolveRepositoryServiceDependency('ensureRepository');
+    const save = resolveRepositoryServiceDependency('saveRepositoryMetadata');
+    const refresh = resolveRepositoryServiceDependency('refreshRepositoryCache');
+
+    await ensure(this.workdir, org, repo);
+    const stored = await save(org, repo, metadata);
+    await refresh(this.workdir, this.options.repositoryCache);
+    return { org, repo, metadata: stored };
+  }
+
+  /**
+   * Reports the branch checked out in the primary clone
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @returns Current branch details
+   */
+  async getCurrentBranch(org: string, repo: string): Promise<RepositoryBranchInfo> {
+    const getBranch = resolveRepositoryServiceDependency('getRepositoryBranch');
+    return await getBranch(this.workdir, org, repo);
+  }
+
+  /**
+   * Switches the primary clone to another branch
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param branch - Branch to check out
+   * @returns Updated branch details
+   */
+  async switchBranch(org: string, repo: string, branch: string): Promise<RepositoryBranchInfo> {
+    const switchBranch = resolveRepositoryServiceDependency('switchRepositoryBranch');
+    const refresh = resolveRepositoryServiceDependency('refreshRepositoryCache');
+
+    const result = await switchBranch(this.workdir, org, repo, branch);
+    await refresh(this.workdir, this.options.repositoryCache);
+    return result;
+  }
+
+  /**
+   * Lists the remotes configured in a repository
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @returns Repository and its remotes
+   */
+  async listRemotes(org: string, repo: string): Promise<RepositoryRemotesResult> {
+    const listRemotes = resolveRepositoryServiceDependency('listRepositoryRemotes');
+    return { org, repo, remotes: await listRemotes(this.workdir, org, repo) };
+  }
+
+  /**
+   * Adds a named remote to a repository, such as a fork to push to
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param name - Remote name
+   * @param url - Remote URL, held to the clone URL scheme allowlist
+   * @returns Repository and the new remote
+   */
+  async addRemote(org: string, repo: string, name: string, url: string): Promise<RepositoryRemoteResult> {
+    const addRemote = resolveRepositoryServiceDependency('addRepositoryRemote');
+    const { allowedUrlSchemes } = this.options;
+    const remote = await addRemote(this.workdir, org, repo, name, url, {
+      ...(allowedUrlSchemes ? { allowedSchemes: allowedUrlSchemes } : {}),
+      ...(this.options.clone?.defaultOrg ? { defaultOrg: this.options.clone.defaultOrg } : {}),
+    });
+    return { org, repo, remote };
+  }
+
+  /**
+   * Reads a git config value of a repository; only allowlisted keys, which cannot hold credentials,
+   * are readable
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param key - Config key, such as `remote.origin.url`
+   * @returns Repository, key and value
+   */
+  async readConfig(org: string, repo: string, key: string): Promise<RepositoryConfigResult> {
+    const readConfig = resolveRepositoryServiceDependency('readRepositoryConfig');
+    const { value } = await readConfig(this.workdir, org, repo, key);
+    return { org, repo, key, value };
   }
 }
 
 /**
  * Creates a repository service instance
  * @param workdir - Work directory root
+ * @param options - Service options
  * @returns RepositoryService instance
  */
-export function createRepositoryService(workdir: string): RepositoryService {
-  return new RepositoryService(workdir);
+export function createRepositoryService(
+  workdir: string,
+  options: RepositoryServiceOptions = {}
+): RepositoryService {
+  return new RepositoryService(workdir, options);
 }
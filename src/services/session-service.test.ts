@@ -108,7 +108,7 @@ describe('SessionService', () => {
       detectTmux: detectMock,
       isTmuxAvailable: isAvailableMock,
       runTmux: runTmuxMock,
-      discoverRepositories: discoverMock,
+      getCachedRepositories: discoverMock,
       serialiseSessions: serialiseMock,
       loadPersistedSessionsSnapshot: async () => [],
     });
@@ -1,4 +1,5 @@
-import { discoverRepositories } from '../repositories/repository-repository.js';
+import { listRepositoryTree, type RepositoryTreeEntry } from '../repositories/repository-repository.js';
+import { getCachedRepositories, type RepositoryCacheOptions } from '../utils/repository-cache.js';
 import {
   buildSanitisedWorktreeLookup,
   detectTmux,
@@ -20,7 +21,8 @@ export interface SessionInfo {
 }
 
 type SessionServiceDependencyOverrides = Partial<{
-  discoverRepositories: typeof discoverRepositories;
+  getCachedRepositories: typeof getCachedRepositories;
+  listRepositoryTree: typeof listRepositoryTree;
   buildSanitisedWorktreeLookup: typeof buildSanitisedWorktreeLookup;
   detectTmux: typeof detectTmux;
   isTmuxAvailable: typeof isTmuxAvailable;
@@ -33,7 +35,8 @@ type SessionServiceDependencyOverrides = Partial<{
 }>;
 
 const sessionServiceDependencies = {
-  discoverRepositories,
+  getCachedRepositories,
+  listRepositoryTree,
   buildSanitisedWorktreeLookup,
   detectTmux,
   isTmuxAvailable,
@@ -73,7 +76,19 @@ function isFiniteNumber(value: unknown): value is number {
  * Service for session discovery and management
  */
 export class SessionService {
-  constructor(private readonly workdir: string) {}
+  constructor(
+    private readonly workdir: string,
+    private readonly repositoryCache: RepositoryCacheOptions = {}
+  ) {}
+
+  /**
+   * Lists organisations and repositories for navigation, skipping worktree enumeration and git calls
+   * @returns Organisation/repository tree
+   */
+  async listSessionTree(): Promise<RepositoryTreeEntry[]> {
+    const listTree = resolveSessionServiceDependency('listRepositoryTree');
+    return await listTree(this.workdir);
+  }
 
   /**
    * Lists all active terminal sessions, including both in-memory and orphaned tmux sessions
@@ -137,9 +152,9 @@ export class SessionService {
       if (tmuxSessions.length > 0) {
         let lookup;
         try {
-          const repositoryDiscovery = resolveSessionServiceDependency('discoverRepositories');
+          const repositoryDiscovery = resolveSessionServiceDependency('getCachedRepositories');
           const buildLookup = resolveSessionServiceDependency('buildSanitisedWorktreeLookup');
-          const structure = await repositoryDiscovery(this.workdir);
+          const structure = await repositoryDiscovery(this.workdir, this.repositoryCache);
           lookup = buildLookup(structure);
         } catch (error: unknown) {
           lookup = new Map();
@@ -215,8 +230,9 @@ export class SessionService {
 /**
  * Creates a session service instance
  * @param workdir - Work directory root
+ * @param repositoryCache - Repository cache settings
  * @returns SessionService instance
  */
-export function createSessionService(workdir: string): SessionService {
-  return new SessionService(workdir);
+export function createSessionService(workdir: string, repositoryCache: RepositoryCacheOptions = {}): SessionService {
+  return new SessionService(workdir, repositoryCache);
 }
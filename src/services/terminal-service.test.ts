@@ -55,6 +55,38 @@ describe('TerminalService', () => {
     assert.equal(queuedArgs[1], 'ls\r');
   });
 
+  it('refuses unconfirmed dangerous terminals with 412 when confirmation is required', async () => {
+    const session = { id: 'session-2', log: '', closed: false };
+    const getOrCreateMock = mock.fn(async () => ({ session, created: true }));
+    const warn = mock.method(console, 'warn', () => {});
+    __setTerminalServiceTestOverrides({
+      getOrCreateTerminalSession: getOrCreateMock,
+      queueSessionInput: mock.fn(() => undefined),
+    });
+    const service = new TerminalService('/work', {
+      dangerousTerminals: { requireConfirmation: true, commands: ['codex --yolo'] },
+    });
+    const request = { org: 'acme', repo: 'demo', branch: 'feature', hasPrompt: false };
+
+    await assert.rejects(service.openTerminal({ ...request, command: 'codex  --yolo' }), {
+      statusCode: 412,
+      code: 'dangerous_confirmation_required',
+    });
+    await assert.rejects(
+      service.openTerminal({ ...request, command: 'claude --dangerously-skip-permissions' }),
+      { statusCode: 412 }
+    );
+    await assert.rejects(service.openTerminal({ ...request, command: 'bash', dangerous: true }), { statusCode: 412 });
+    assert.equal(getOrCreateMock.mock.callCount(), 0);
+
+    const result = await service.openTerminal({ ...request, command: 'codex --yolo', confirmDangerous: true });
+
+    assert.equal(result.sessionId, 'session-2');
+    assert.equal(getOrCreateMock.mock.callCount(), 1);
+    assert.equal(warn.mock.callCount(), 1);
+    assert.match(String(warn.mock.calls[0]?.arguments[0]), /dangerous terminal for acme\/demo branch feature: codex --yolo/);
+  });
+
   it('rejects terminal access for main branch', async () => {
     const service = createTerminalService('/work');
     await assert.rejects(
@@ -3,10 +3,20 @@ import {
   getSessionById,
   queueSessionInput,
   disposeSessionById,
+  tailSessionLog,
+  signalSessionForeground,
 } from '../core/terminal-sessions.js';
 import { launchAgentProcess } from '../core/agents.js';
-import { ValidationError } from '../infrastructure/errors/index.js';
-import type { TerminalOpenInput, TerminalSendInput, TerminalCloseInput } from '../validation/index.js';
+import { assertDangerousTerminalAllowed, type DangerousTerminalOptions } from '../core/dangerous-terminals.js';
+import { NotFoundError, ValidationError } from '../infrastructure/errors/index.js';
+import type {
+  TerminalOpenInput,
+  TerminalSendInput,
+  TerminalCloseInput,
+  TerminalHistoryInput,
+  TerminalSignalInput,
+  TerminalSignalName,
+} from '../validation/index.js';
 import type { ITerminalService } from '../types/services.js';
 
 export interface TerminalOpenResult {
@@ -24,12 +34,34 @@ export interface TerminalCloseResult {
   ok: boolean;
 }
 
+export interface TerminalSignalResult {
+  sessionId: string;
+  signal: NodeJS.Signals;
+  /**
+   * Foreground process group the signal was delivered to
+   */
+  processGroup: number;
+}
+
+const TERMINAL_SIGNALS: Record<TerminalSignalName, NodeJS.Signals> = {
+  int: 'SIGINT',
+  term: 'SIGTERM',
+  kill: 'SIGKILL',
+};
+
+export interface TerminalHistoryResult {
+  sessionId: string;
+  log: string;
+  closed: boolean;
+}
+
 type TerminalServiceDependencyOverrides = Partial<{
   getOrCreateTerminalSession: typeof getOrCreateTerminalSession;
   getSessionById: typeof getSessionById;
   queueSessionInput: typeof queueSessionInput;
   launchAgentProcess: typeof launchAgentProcess;
   disposeSessionById: typeof disposeSessionById;
+  signalSessionForeground: typeof signalSessionForeground;
 }>;
 
 const terminalServiceDependencies = {
@@ -38,6 +70,7 @@ const terminalServiceDependencies = {
   queueSessionInput,
   launchAgentProcess,
   disposeSessionById,
+  signalSessionForeground,
 } as const;
 
 let terminalServiceTestOverrides: TerminalServiceDependencyOverrides | null = null;
@@ -57,14 +90,30 @@ export function __setTerminalServiceTestOverrides(overrides?: TerminalServiceDep
   terminalServiceTestOverrides = overrides ?? null;
 }
 
+export interface TerminalServiceOptions {
+  mode?: string;
+  /**
+   * Whether dangerous terminals need confirming and which commands count as dangerous
+   */
+  dangerousTerminals?: DangerousTerminalOptions;
+  /**
+   * Extra environment variables for the terminals it spawns
+   */
+  env?: Record<string, string> | null;
+}
+
 /**
  * Service for terminal session orchestration
  */
 export class TerminalService implements ITerminalService {
   private readonly mode: string;
+  private readonly dangerousTerminals: DangerousTerminalOptions;
+  private readonly env: Record<string, string> | null;
 
-  constructor(private readonly workdir: string, options: { mode?: string } = {}) {
+  constructor(private readonly workdir: string, options: TerminalServiceOptions = {}) {
     this.mode = typeof options.mode === 'string' ? options.mode : 'auto';
+    this.dangerousTerminals = options.dangerousTerminals ?? {};
+    this.env = options.env ?? null;
   }
 
   /**
@@ -88,6 +137,7 @@ export class TerminalService implements ITerminalService {
     if (branch.toLowerCase() === 'main') {
       throw new ValidationError('Terminal access to the main branch is disabled');
     }
+    assertDangerousTerminalAllowed(params, this.dangerousTerminals);
 
     const attachSessionId = typeof sessionId === 'string' ? sessionId.trim() : '';
     if (attachSessionId) {
@@ -124,6 +174,7 @@ export class TerminalService implements ITerminalService {
         repo,
         branch,
         prompt: prompt ?? '',
+        ...(this.env ? { env: this.env } : {}),
       });
 
       const getSession = resolveTerminalServiceDependency('getSessionById');
@@ -148,6 +199,7 @@ export class TerminalService implements ITerminalService {
       forceNew: Boolean(newSession),
       tool: desiredTool,
       kind: desiredKind,
+      ...(this.env ? { env: this.env } : {}),
     });
 
     const session = 'session' in result ? result.session : result;
@@ -186,6 +238,47 @@ export class TerminalService implements ITerminalService {
     return { ok: true };
   }
 
+  /**
+   * Reads the recent output of a terminal session, so a reconnecting client can show its scrollback
+   * before attaching to the live stream
+   * @param params - Session and number of trailing lines
+   * @returns Buffered output
+   */
+  async readHistory(params: TerminalHistoryInput): Promise<TerminalHistoryResult> {
+    const { sessionId, lines } = params;
+    const getSession = resolveTerminalServiceDependency('getSessionById');
+    const session = getSession(sessionId);
+    if (!session) {
+      throw new NotFoundError('Terminal session');
+    }
+
+    return {
+      sessionId: session.id,
+      log: tailSessionLog(session.log || '', lines),
+      closed: Boolean(session.closed),
+    };
+  }
+
+  /**
+   * Interrupts or stops the command running in a terminal session by signalling its foreground process
+   * group, leaving the shell and the session itself open
+   * @param params - Session and signal name
+   * @returns The signal sent and the process group it went to
+   */
+  async signalSession(params: TerminalSignalInput): Promise<TerminalSignalResult> {
+    const { sessionId } = params;
+    const getSession = resolveTerminalServiceDependency('getSessionById');
+    const session = getSession(sessionId);
+    if (!session || session.closed) {
+      throw new NotFoundError('Terminal session');
+    }
+
+    const signal = TERMINAL_SIGNALS[params.signal];
+    const signalForeground = resolveTerminalServiceDependency('signalSessionForeground');
+    const processGroup = await signalForeground(session, signal);
+    return { sessionId: session.id, signal, processGroup };
+  }
+
   /**
    * Closes a terminal session
    * @param params - Close parameters
@@ -205,6 +298,6 @@ export class TerminalService implements ITerminalService {
  * @param options - Service options
  * @returns TerminalService instance
  */
-export function createTerminalService(workdir: string, options: { mode?: string } = {}): TerminalService {
+export function createTerminalService(workdir: string, options: TerminalServiceOptions = {}): TerminalService {
   return new TerminalService(workdir, options);
 }
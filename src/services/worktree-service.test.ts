@@ -0,0 +1,254 @@
+import assert from 'node:assert/strict';
+import { execFile } from 'node:child_process';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { promisify } from 'node:util';
+import { afterEach, beforeEach, describe, it, mock } from 'node:test';
+
+import { WorktreeService } from './worktree-service.js';
+import { __setWorktreeRepositoryTestOverrides } from '../repositories/worktree-repository.js';
+
+const execGit = promisify(execFile);
+const git = async (cwd: string, args: string[]) => (await execGit('git', ['-C', cwd, ...args])).stdout.trim();
+const identity = { name: 'Agent Bot', email: 'agent@example.com' };
+
+describe('WorktreeService.createPullRequestDraft', () => {
+  let root: string;
+  let workdir: string;
+  let originPath: string;
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-pr-draft-'));
+    originPath = path.join(root, 'origin.git');
+    workdir = path.join(root, 'workdir');
+    const seedPath = path.join(root, 'seed');
+    await execGit('git', ['init', '--quiet', '--bare', '-b', 'main', originPath]);
+    await execGit('git', ['clone', '--quiet', originPath, seedPath]);
+    await git(seedPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+    await git(seedPath, ['push', '--quiet', 'origin', 'HEAD:main']);
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    await fs.mkdir(path.dirname(repositoryPath), { recursive: true });
+    await execGit('git', ['clone', '--quiet', originPath, repositoryPath]);
+    __setWorktreeRepositoryTestOverrides({
+      runRepositoryInitCommand: async () => ({ ran: false, command: '' }),
+    });
+  });
+
+  afterEach(async () => {
+    __setWorktreeRepositoryTestOverrides();
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  it('creates the worktree, pushes an initial commit and opens a draft pull request', async () => {
+    const createPullRequest = mock.fn(async () => ({
+      number: 12,
+      url: 'https://github.com/acme/demo/pull/12',
+      draft: true,
+    }));
+    const service = new WorktreeService(
+      workdir,
+      null,
+      undefined,
+      { createPullRequest, findOpenPullRequest: async () => null },
+      { identity }
+    );
+
+    const result = await service.createPullRequestDraft({
+      org: 'acme',
+      repo: 'demo',
+      branch: 'feature/login',
+      title: 'Add login',
+      body: 'Work in progress',
+    });
+
+    const worktreePath = path.join(workdir, 'acme', 'demo', 'login');
+    assert.equal(await fs.realpath(result.worktreePath), await fs.realpath(worktreePath));
+    assert.equal(await git(originPath, ['rev-parse', 'refs/heads/feature/login']), result.sha);
+    assert.equal(await git(worktreePath, ['log', '-1', '--format=%s']), 'Add login');
+    assert.equal(await git(worktreePath, ['rev-list', '--count', 'origin/main..HEAD']), '1');
+    assert.deepEqual(createPullRequest.mock.calls[0]?.arguments, [
+      'acme',
+      'demo',
+      { head: 'feature/login', base: 'main', title: 'Add login', body: 'Work in progress', draft: true },
+    ]);
+    assert.deepEqual(result.pullRequest, {
+      number: 12,
+      url: 'https://github.com/acme/demo/pull/12',
+      draft: true,
+      base: 'main',
+    });
+  });
+
+  it('removes the worktree again when the pull request cannot be opened', async () => {
+    const warn = mock.method(console, 'warn', () => {});
+    const service = new WorktreeService(
+      workdir,
+      null,
+      undefined,
+      {
+        createPullRequest: async () => {
+          throw new Error('GraphQL: No commits between main and feature/login');
+        },
+        findOpenPullRequest: async () => null,
+      },
+      { identity }
+    );
+
+    await assert.rejects(
+      service.createPullRequestDraft({ org: 'acme', repo: 'demo', branch: 'feature/login', title: 'Add login', body: '' }),
+      /No commits between/
+    );
+
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    assert.doesNotMatch(await git(repositoryPath, ['worktree', 'list', '--porcelain']), /feature\/login/);
+    await assert.rejects(fs.access(path.join(workdir, 'acme', 'demo', 'login')), { code: 'ENOENT' });
+    assert.equal(warn.mock.callCount(), 0);
+    warn.mock.restore();
+  });
+});
+
+describe('WorktreeService.getPullRequest', () => {
+  let workdir: string;
+
+  beforeEach(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-pr-lookup-'));
+    const repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', path.join(workdir, 'acme', 'demo', 'login')]);
+  });
+
+  afterEach(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it("returns the branch's open pull request and reuses the answer for repeated lookups", async () => {
+    const pullRequest = {
+      number: 42,
+      url: 'https://github.com/acme/demo/pull/42',
+      title: 'Add login',
+      draft: false,
+      base: 'main',
+      head: 'feature/login',
+      author: 'octocat',
+      createdAt: '2024-05-01T10:00:00.000Z',
+    };
+    const findOpenPullRequest = mock.fn(async () => pullRequest);
+    const service = new WorktreeService(workdir, null, undefined, {
+      createPullRequest: async () => ({ number: null, url: '', draft: true }),
+      findOpenPullRequest,
+    });
+
+    const first = await service.getPullRequest({ org: 'acme', repo: 'demo', branch: 'feature/login' });
+    const second = await service.getPullRequest({ org: 'acme', repo: 'demo', branch: 'feature/login' });
+
+    assert.deepEqual(first, { org: 'acme', repo: 'demo', branch: 'feature/login', pullRequest });
+    assert.deepEqual(second, first);
+    assert.deepEqual(findOpenPullRequest.mock.calls[0]?.arguments, ['acme', 'demo', 'feature/login']);
+    assert.equal(findOpenPullRequest.mock.callCount(), 1);
+  });
+});
+
+describe('WorktreeService.renameWorktree', () => {
+  let workdir: string;
+  let repositoryPath: string;
+
+  beforeEach(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-rename-'));
+    repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', path.join(workdir, 'acme', 'demo', 'login')]);
+    await git(repositoryPath, ['branch', 'feature/taken']);
+  });
+
+  afterEach(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  const spyOnSessionTeardown = (service: WorktreeService) =>
+    mock.method(
+      service as unknown as { terminateSessions: (org: string, repo: string, branch: string) => Promise<void> },
+      'terminateSessions',
+      async () => {}
+    );
+
+  it('leaves the worktree sessions alive when the new name is taken', async () => {
+    const service = new WorktreeService(workdir, null, undefined);
+    const terminateSessions = spyOnSessionTeardown(service);
+
+    await assert.rejects(
+      service.renameWorktree({ org: 'acme', repo: 'demo', branch: 'feature/login', newBranch: 'feature/taken' }),
+      { statusCode: 409, code: 'worktree_conflict' }
+    );
+    await assert.rejects(
+      service.renameWorktree({ org: 'acme', repo: 'demo', branch: 'feature/missing', newBranch: 'feature/other' }),
+      { statusCode: 404 }
+    );
+
+    assert.equal(terminateSessions.mock.callCount(), 0);
+    assert.match(await git(repositoryPath, ['worktree', 'list', '--porcelain']), /refs\/heads\/feature\/login/);
+  });
+
+  it('tears the sessions down once the rename can go ahead', async () => {
+    const service = new WorktreeService(workdir, null, undefined);
+    const terminateSessions = spyOnSessionTeardown(service);
+
+    const result = await service.renameWorktree({
+      org: 'acme',
+      repo: 'demo',
+      branch: 'feature/login',
+      newBranch: 'feature/signin',
+    });
+
+    assert.equal(result.branch, 'feature/signin');
+    assert.deepEqual(terminateSessions.mock.calls[0]?.arguments, ['acme', 'demo', 'feature/login']);
+  });
+});
+
+describe('WorktreeService.createWorktree', () => {
+  let workdir: string;
+  let repositoryPath: string;
+
+  beforeEach(async () => {
+    workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-recreate-'));
+    repositoryPath = path.join(workdir, 'acme', 'demo', 'repository');
+    await fs.mkdir(repositoryPath, { recursive: true });
+    await git(repositoryPath, ['init', '--quiet', '-b', 'main']);
+    await git(repositoryPath, ['-c', 'user.name=Seed', '-c', 'user.email=seed@example.com', 'commit', '--quiet', '--allow-empty', '-m', 'Seed']);
+    await git(repositoryPath, ['worktree', 'add', '--quiet', '-b', 'feature/login', path.join(workdir, 'acme', 'demo', 'login')]);
+    await git(repositoryPath, ['branch', 'feature/taken']);
+  });
+
+  afterEach(async () => {
+    await fs.rm(workdir, { recursive: true, force: true });
+  });
+
+  it('checks the worktree limit before a recreate tears anything down', async () => {
+    const service = new WorktreeService(workdir, null, undefined, undefined, { maxWorktrees: 1 });
+    const terminateSessions = mock.method(
+      service as unknown as { terminateSessions: (org: string, repo: string, branch: string) => Promise<void> },
+      'terminateSessions',
+      async () => {}
+    );
+
+    await assert.rejects(
+      service.createWorktree({
+        org: 'acme',
+        repo: 'demo',
+        branch: 'feature/taken',
+        prompt: '',
+        hasPrompt: false,
+        dryRun: false,
+        ifExists: 'recreate',
+      }),
+      { statusCode: 409, code: 'worktree_limit_reached' }
+    );
+
+    assert.equal(terminateSessions.mock.callCount(), 0);
+    assert.match(await git(repositoryPath, ['worktree', 'list', '--porcelain']), /refs\/heads\/feature\/login/);
+  });
+});
@@ -1,11 +1,43 @@
 /* c8 ignore file */
 import {
+  assertWorktreeCapacity,
+  clearWorktreeTarget,
+  commitWorktree,
   createWorktree,
+  createWorktrees,
+  discoverWorktrees,
+  findExistingWorktree,
+  getWorktreeUsage,
+  getWorktreeActivity,
+  pushWorktree,
   getWorktreePath,
   normalizeBranchName,
+  planWorktree,
+  checkWorktreeBranch,
   removeWorktree,
+  renameWorktree,
+  planWorktreeRename,
+  replaceWorktree,
+  resetWorktree,
+  cherryPickWorktree,
+  abortCherryPick,
+  listWorktreeStashes,
+  pushWorktreeStash,
+  popWorktreeStash,
+  getWorktreeUpstream,
+  setWorktreeUpstream,
+  resolveRepositorySlug,
+  runWorktreeSetup,
+  verifyWorktreeBase,
 } from '../core/git.js';
-import { selectDefaultBranchOverride } from '../core/default-branch.js';
+import { resolveDefaultBranch, selectDefaultBranchOverride } from '../core/default-branch.js';
+import {
+  createGithubClient,
+  type GithubClient,
+  type GithubOpenPullRequest,
+  type GithubPullRequest,
+} from '../core/github.js';
+import { RepositorySummaryCache } from '../core/github-summary-cache.js';
 import {
   detectTmux,
   isTmuxAvailable,
@@ -15,13 +47,52 @@ import {
 import { disposeSessionByKey, makeSessionKey } from '../core/terminal-sessions.js';
 import { savePlanToWorktree } from '../core/plan-storage.js';
 import { runTask } from '../core/tasks.js';
-import { refreshRepositoryCache } from '../utils/repository-cache.js';
-import type { WorktreeCreateInput, WorktreeDeleteInput } from '../validation/index.js';
+import { refreshRepositoryCache, type RepositoryCacheOptions } from '../utils/repository-cache.js';
+import type { ProcessOutputOptions } from '../utils/process-output.js';
+import { ValidationError } from '../infrastructure/errors/index.js';
+import type {
+  WorktreeCreateInput,
+  WorktreeBatchCreateInput,
+  WorktreeDeleteInput,
+  WorktreeReplaceInput,
+  WorktreeRenameInput,
+  WorktreeCommitInput,
+  WorktreePushInput,
+  WorktreePullRequestDraftInput,
+  WorktreeSetupInput,
+  WorktreeResetInput,
+  WorktreeCherryPickInput,
+  WorktreeCherryPickAbortInput,
+  WorktreeStashAction,
+  WorktreeStashInput,
+  WorktreeUpstreamInput,
+} from '../validation/index.js';
+import type {
+  CherryPickWorktreeResult,
+  ReplaceWorktreeResult,
+  WorktreeStash,
+  WorktreeUpstream,
+  ResetWorktreeResult,
+  WorktreeBatchItemResult,
+  WorktreeListing,
+  WorktreeSetupResult,
+  WorktreeUsage,
+  WorktreeActivity,
+  WorktreeActivityOptions,
+  WorktreeBranchCheck,
+  WorktreeSettings,
+} from '../core/git.js';
 import type { RepositoriesData } from './repository-service.js';
 import type { IWorktreeService } from '../types/services.js';
 
 const TASK_TYPE_CREATE_WORKTREE = 'worktree:create';
 
+/**
+ * How long a branch's pull request lookup is reused, so a UI polling after a push does not hit GitHub
+ * every time
+ */
+export const PULL_REQUEST_LOOKUP_TTL_MS = 30_000;
+
 const STEP_IDS = Object.freeze({
   GENERATE_BRANCH: 'generate-branch',
   SYNC_DEFAULT_BRANCH: 'sync-default-branch',
@@ -38,23 +109,182 @@ export interface CreateWorktreeResult {
   branch: string | null;
 }
 
+export interface WorktreeDryRunResult {
+  org: string;
+  repo: string;
+  branch: string;
+  worktreePath: string;
+  branchExists: boolean;
+  created: false;
+}
+
+export interface ReusedWorktreeResult {
+  org: string;
+  repo: string;
+  branch: string;
+  worktreePath: string;
+  created: false;
+}
+
+export interface WorktreeBatchResult {
+  org: string;
+  repo: string;
+  results: WorktreeBatchItemResult[];
+}
+
+export interface ReplaceWorktreeServiceResult extends ReplaceWorktreeResult {
+  org: string;
+  repo: string;
+}
+
+export interface RenameWorktreeServiceResult {
+  org: string;
+  repo: string;
+  branch: string;
+  previousBranch: string;
+  worktreePath: string;
+}
+
+export interface CommitWorktreeServiceResult {
+  org: string;
+  repo: string;
+  branch: string;
+  sha: string;
+  files: string[];
+  signed: boolean;
+}
+
+export interface PushWorktreeServiceResult {
+  org: string;
+  repo: string;
+  branch: string;
+  remote: string;
+  upstream: string;
+  sha: string;
+  forced: boolean;
+}
+
+export interface PullRequestDraftServiceResult {
+  org: string;
+  repo: string;
+  branch: string;
+  worktreePath: string;
+  /**
+   * Commit pushed to origin and opened as the pull request's head
+   */
+  sha: string;
+  pullRequest: GithubPullRequest & { base: string };
+}
+
+export interface WorktreePullRequestServiceResult {
+  org: string;
+  repo: string;
+  branch: string;
+  /**
+   * The branch's open pull request, or null when it has none
+   */
+  pullRequest: GithubOpenPullRequest | null;
+}
+
+export interface WorktreeSetupServiceResult extends WorktreeSetupResult {
+  org: string;
+  repo: string;
+}
+
+export interface ResetWorktreeServiceResult extends ResetWorktreeResult {
+  org: string;
+  repo: string;
+}
+
+export interface CherryPickWorktreeServiceResult extends CherryPickWorktreeResult {
+  org: string;
+  repo: string;
+}
+
+export interface AbortCherryPickServiceResult {
+  org: string;
+  repo: string;
+  branch: string;
+  sha: string;
+}
+
+export interface WorktreeBranchCheckResult extends WorktreeBranchCheck {
+  org: string;
+  repo: string;
+}
+
+export interface WorktreeStashServiceResult {
+  org: string;
+  repo: string;
+  branch: string;
+  action: WorktreeStashAction;
+  /**
+   * Stash pushed or popped; absent for `list`
+   */
+  stash?: WorktreeStash;
+  /**
+   * The worktree's stashes, newest first; only for `list`
+   */
+  stashes?: WorktreeStash[];
+}
+
+export interface WorktreeUpstreamServiceResult extends WorktreeUpstream {
+  org: string;
+  repo: string;
+}
+
+/**
+ * Server settings the worktree service applies to the worktrees it creates and the git commands it runs
+ */
+export interface WorktreeServiceSettings extends WorktreeSettings {
+  /**
+   * Signs every commit made through the service, not only those that ask for it
+   */
+  signCommits?: boolean;
+  /**
+   * Command run by worktree setup when the worktree has no setup script or `.agentrix.toml` command
+   */
+  setupCommand?: string | null;
+  /**
+   * Extra environment variables for setup runs, as given to terminals
+   */
+  terminalEnv?: Record<string, string> | null;
+  repositoryCache?: RepositoryCacheOptions;
+}
+
 /**
  * Service for worktree lifecycle management
  */
 export class WorktreeService implements IWorktreeService {
+  // Keyed by `owner/repo#branch`
+  private readonly pullRequestLookups = new RepositorySummaryCache<GithubOpenPullRequest | null>(
+    PULL_REQUEST_LOOKUP_TTL_MS
+  );
+
   constructor(
     private readonly workdir: string,
     private readonly branchNameGenerator: unknown,
-    private readonly defaultBranchConfig: unknown
+    private readonly defaultBranchConfig: unknown,
+    private readonly githubClient: Pick<GithubClient, 'createPullRequest' | 'findOpenPullRequest'> = createGithubClient(),
+    private readonly settings: WorktreeServiceSettings = {}
   ) {}
 
+  private get worktreeSettings(): WorktreeSettings {
+    const { identity, defaultBase, maxWorktrees, gitConfig } = this.settings;
+    return { identity, defaultBase, maxWorktrees, gitConfig };
+  }
+
+  private refreshRepositories(): Promise<RepositoriesData> {
+    return refreshRepositoryCache(this.workdir, this.settings.repositoryCache) as Promise<RepositoriesData>;
+  }
+
   /**
-   * Creates a new worktree
+   * Creates a new worktree, or returns the existing one when asked to reuse it
    * @param params - Creation parameters
-   * @returns Result with task ID and repository info
+   * @returns Result with task ID and repository info, or the reused worktree
    */
-  async createWorktree(params: WorktreeCreateInput): Promise<CreateWorktreeResult> {
-    const { org, repo, branch, prompt, hasPrompt } = params;
+  async createWorktree(params: WorktreeCreateInput): Promise<CreateWorktreeResult | ReusedWorktreeResult> {
+    const { org, repo, branch, prompt, hasPrompt, sparsePaths = [], ifExists = 'error' } = params;
     let normalisedBranch = normalizeBranchName(branch);
     let resolvedBranch: string | null = normalisedBranch || null;
 
@@ -65,11 +295,40 @@ export class WorktreeService implements IWorktreeService {
       );
     }
 
+    if (normalisedBranch && ifExists === 'reuse') {
+      const existing = await findExistingWorktree(this.workdir, org, repo, normalisedBranch);
+      if (existing) {
+        return { org, repo, ...existing, created: false };
+      }
+    }
+
     const defaultBranchOverride = selectDefaultBranchOverride(
       this.defaultBranchConfig,
       org,
       repo
     );
+    // Checked up front so a bad base is a 400 rather than a failed background task
+    const base = await verifyWorktreeBase(this.workdir, org, repo, params.base, this.settings.defaultBase);
+
+    const recreating = Boolean(normalisedBranch) && ifExists === 'recreate';
+    if (recreating) {
+      const protectedBranch = (defaultBranchOverride || 'main').toLowerCase();
+      if (normalisedBranch.toLowerCase() === protectedBranch) {
+        throw new ValidationError(`Cannot recreate the default worktree (${defaultBranchOverride || 'main'})`);
+      }
+    }
+    // Checked before a recreated worktree is torn down, counting its slot as free since it is reused
+    await assertWorktreeCapacity(
+      this.workdir,
+      org,
+      repo,
+      this.settings.maxWorktrees,
+      recreating ? normalisedBranch : null
+    );
+    if (recreating) {
+      await this.terminateSessions(org, repo, normalisedBranch);
+      await clearWorktreeTarget(this.workdir, org, repo, normalisedBranch);
+    }
 
     const { id: taskId } = runTask(
       {
@@ -143,8 +402,11 @@ export class WorktreeService implements IWorktreeService {
         prog.ensureStep(STEP_IDS.RUN_INIT_SCRIPT, 'Run init script');
 
         await createWorktree(this.workdir, org, repo, targetBranch, {
+          ...this.worktreeSettings,
           defaultBranchOverride,
           progress: prog,
+          sparsePaths,
+          base,
         });
 
         if (hasPrompt) {
@@ -188,7 +450,7 @@ export class WorktreeService implements IWorktreeService {
           message: 'Refreshing repository cache.',
         });
         try {
-          await refreshRepositoryCache(this.workdir);
+          await refreshRepositoryCache(this.workdir, this.settings.repositoryCache);
           prog.completeStep(STEP_IDS.REFRESH_REPOSITORIES, {
             label: 'Refresh repositories',
             message: 'Repository cache refreshed.',
@@ -219,6 +481,82 @@ export class WorktreeService implements IWorktreeService {
     };
   }
 
+  /**
+   * Validates a worktree creation request without creating anything
+   * @param params - Creation parameters
+   * @returns The path the worktree would be created at
+   */
+  async previewWorktree(params: WorktreeCreateInput): Promise<WorktreeDryRunResult> {
+    const { org, repo, branch } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('A branch is required for a dry run');
+    }
+
+    const plan = await planWorktree(this.workdir, org, repo, normalised);
+    if (!plan.branchExists) {
+      await verifyWorktreeBase(this.workdir, org, repo, params.base, this.settings.defaultBase);
+    }
+    await assertWorktreeCapacity(this.workdir, org, repo, this.settings.maxWorktrees);
+
+    return { org, repo, ...plan, created: false };
+  }
+
+  /**
+   * Creates worktrees for several branches at once, reporting each branch's outcome
+   * @param params - Batch parameters
+   * @returns Per-branch results in request order
+   */
+  async createWorktreeBatch(params: WorktreeBatchCreateInput): Promise<WorktreeBatchResult> {
+    const { org, repo, branches } = params;
+    const defaultBranchOverride = selectDefaultBranchOverride(this.defaultBranchConfig, org, repo);
+    // A bad base would fail every branch, so it rejects the whole request instead
+    const base = await verifyWorktreeBase(this.workdir, org, repo, params.base, this.settings.defaultBase);
+
+    const results = await createWorktrees(this.workdir, org, repo, branches, {
+      ...this.worktreeSettings,
+      defaultBranchOverride,
+      base,
+    });
+    if (results.some((result) => result.status === 'created')) {
+      await refreshRepositoryCache(this.workdir, this.settings.repositoryCache);
+    }
+    return { org, repo, results };
+  }
+
+  /**
+   * Lists a repository's worktrees as git records them, plus directories git has forgotten
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @returns Worktrees flagged as locked, prunable or unregistered
+   */
+  async listWorktrees(org: string, repo: string): Promise<WorktreeListing> {
+    return await discoverWorktrees(this.workdir, org, repo);
+  }
+
+  /**
+   * Reports the disk space used by a repository's worktrees
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param options - Set `refresh` to bypass the short-lived cache
+   * @returns Per-worktree sizes in bytes and their total
+   */
+  async getWorktreeUsage(org: string, repo: string, options: { refresh?: boolean } = {}): Promise<WorktreeUsage> {
+    return await getWorktreeUsage(this.workdir, org, repo, options);
+  }
+
+  /**
+   * Gathers recent commits from every worktree of a repository into one newest-first feed
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param options - Commits read per worktree and the feed's length
+   * @returns Commits with the branch they were found on
+   */
+  async getWorktreeActivity(org: string, repo: string, options: WorktreeActivityOptions = {}): Promise<WorktreeActivity> {
+    return await getWorktreeActivity(this.workdir, org, repo, options);
+  }
+
   /**
    * Deletes a worktree
    * @param params - Deletion parameters
@@ -244,12 +582,389 @@ export class WorktreeService implements IWorktreeService {
       throw new Error(`Cannot remove the default worktree (${overrideDefault || 'main'})`);
     }
 
-    const sessionKey = makeSessionKey(org, repo, normalised);
+    await this.terminateSessions(org, repo, normalised);
+
+    await removeWorktree(this.workdir, org, repo, normalised);
+    return await refreshRepositoryCache(this.workdir, this.settings.repositoryCache);
+  }
+
+  /**
+   * Discards a worktree and recreates its branch from a base in one step, leaving the old worktree in
+   * place if the new one cannot be created
+   * @param params - Worktree and optional base ref
+   * @returns The new worktree and the commit the branch was at before
+   */
+  async replaceWorktree(params: WorktreeReplaceInput): Promise<ReplaceWorktreeServiceResult> {
+    const { org, repo, branch, base } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const defaultBranchOverride = selectDefaultBranchOverride(this.defaultBranchConfig, org, repo);
+    const protectedBranch = (defaultBranchOverride || 'main').toLowerCase();
+    if (normalised.toLowerCase() === protectedBranch) {
+      throw new ValidationError(`Cannot replace the default worktree (${defaultBranchOverride || 'main'})`);
+    }
+
+    await this.terminateSessions(org, repo, normalised);
+    const result = await replaceWorktree(this.workdir, org, repo, normalised, {
+      ...this.worktreeSettings,
+      ...(defaultBranchOverride ? { defaultBranchOverride } : {}),
+      base: base ?? null,
+    });
+    await refreshRepositoryCache(this.workdir, this.settings.repositoryCache);
+    return { org, repo, ...result };
+  }
+
+  /**
+   * Renames a worktree's branch and moves its directory to match
+   * @param params - Rename parameters
+   * @returns Renamed worktree details
+   */
+  async renameWorktree(params: WorktreeRenameInput): Promise<RenameWorktreeServiceResult> {
+    const { org, repo, branch, newBranch } = params;
+    const normalised = normalizeBranchName(branch);
+
+    const target = normalizeBranchName(newBranch);
+
+    if (!normalised || !target) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const overrideDefault = selectDefaultBranchOverride(this.defaultBranchConfig, org, repo);
+    const protectedBranch = overrideDefault ? overrideDefault.toLowerCase() : 'main';
+
+    if (normalised.toLowerCase() === protectedBranch || target.toLowerCase() === protectedBranch) {
+      throw new ValidationError(`Cannot rename the default worktree (${overrideDefault || 'main'})`);
+    }
+
+    // Rejects a missing worktree or a taken name before the worktree's terminals are torn down
+    await planWorktreeRename(this.workdir, org, repo, normalised, target);
+    await this.terminateSessions(org, repo, normalised);
+
+    const result = await renameWorktree(this.workdir, org, repo, normalised, target);
+    await refreshRepositoryCache(this.workdir, this.settings.repositoryCache);
+
+    return { org, repo, ...result };
+  }
+
+  /**
+   * Stages and commits changes in a worktree
+   * @param params - Commit parameters
+   * @param output - Follows git's output while it runs
+   * @returns The new commit SHA and committed files
+   */
+  async commitWorktree(
+    params: WorktreeCommitInput,
+    output: ProcessOutputOptions = {}
+  ): Promise<CommitWorktreeServiceResult> {
+    const { org, repo, branch, message, paths, sign } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const result = await commitWorktree(
+      this.workdir,
+      org,
+      repo,
+      normalised,
+      { message, paths, sign, identity: this.settings.identity, requireSigning: this.settings.signCommits },
+      output
+    );
+    return { org, repo, ...result };
+  }
+
+  /**
+   * Pushes a worktree branch to origin, or the remote the request names
+   * @param params - Push parameters
+   * @param output - Follows git's output while it runs
+   * @returns Remote tracking information
+   */
+  async pushWorktree(params: WorktreePushInput, output: ProcessOutputOptions = {}): Promise<PushWorktreeServiceResult> {
+    const { org, repo, branch, forceWithLease, remote } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const result = await pushWorktree(
+      this.workdir,
+      org,
+      repo,
+      normalised,
+      { forceWithLease, ...(remote ? { remote } : {}) },
+      output
+    );
+    return { org, repo, ...result };
+  }
+
+  /**
+   * Creates a worktree, pushes its branch and opens a draft pull request for it in one step. A new branch
+   * gets an empty commit first so GitHub has something to open the pull request for; an existing branch
+   * is pushed as it is. If anything after creating the worktree fails, the worktree is removed again.
+   * @param params - Worktree, pull request title and body, and the branch to target
+   * @returns The worktree path, the pushed commit and the pull request
+   */
+  async createPullRequestDraft(params: WorktreePullRequestDraftInput): Promise<PullRequestDraftServiceResult> {
+    const { org, repo, branch, title, body } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const defaultBranchOverride = selectDefaultBranchOverride(this.defaultBranchConfig, org, repo);
+    const protectedBranch = (params.base || defaultBranchOverride || 'main').toLowerCase();
+    if (normalised.toLowerCase() === protectedBranch) {
+      throw new ValidationError('A pull request cannot be opened from the branch it targets');
+    }
+
+    const plan = await planWorktree(this.workdir, org, repo, normalised);
+    // A requested target is also where a new branch starts, as GitHub will compare against it
+    const worktreeBase =
+      !plan.branchExists && params.base
+        ? await verifyWorktreeBase(this.workdir, org, repo, `origin/${params.base}`)
+        : undefined;
+    await createWorktree(this.workdir, org, repo, normalised, {
+      ...this.worktreeSettings,
+      defaultBranchOverride,
+      ...(worktreeBase ? { base: worktreeBase } : {}),
+    });
+
+    try {
+      const { repositoryPath, worktreePath } = await getWorktreePath(this.workdir, org, repo, normalised);
+      if (!plan.branchExists) {
+        await commitWorktree(this.workdir, org, repo, normalised, {
+          message: title,
+          empty: true,
+          identity: this.settings.identity,
+          requireSigning: this.settings.signCommits,
+        });
+      }
+      const pushed = await pushWorktree(this.workdir, org, repo, normalised);
+      const base = params.base || (await resolveDefaultBranch(repositoryPath, { override: defaultBranchOverride }));
+      const slug = await resolveRepositorySlug(repositoryPath, org, repo);
+      const pullRequest = await this.githubClient.createPullRequest(slug.org, slug.repo, {
+        head: normalised,
+        base,
+        title,
+        body,
+        draft: true,
+      });
+      this.pullRequestLookups.invalidate(`${slug.org}/${slug.repo}#${normalised}`);
+      await refreshRepositoryCache(this.workdir, this.settings.repositoryCache);
+
+      return { org, repo, branch: normalised, worktreePath, sha: pushed.sha, pullRequest: { ...pullRequest, base } };
+    } catch (error: unknown) {
+      try {
+        await removeWorktree(this.workdir, org, repo, normalised);
+      } catch (cleanupError: unknown) {
+        console.warn(
+          `[agentrix] Failed to remove worktree for ${normalised} after the pull request draft failed:`,
+          (cleanupError as Error)?.message || cleanupError
+        );
+      }
+      throw error;
+    }
+  }
+
+  /**
+   * Discards local changes in a worktree by resetting it to its upstream or a given ref
+   * @param params - Worktree and optional target ref
+   * @returns The commits before and after, and the files that were discarded or removed
+   */
+  async resetWorktree(params: WorktreeResetInput): Promise<ResetWorktreeServiceResult> {
+    const { org, repo, branch, ref } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const result = await resetWorktree(this.workdir, org, repo, normalised, ref ? { ref } : {});
+    return { org, repo, ...result };
+  }
+
+  /**
+   * Applies commits, e.g. from another worktree's branch, to a worktree's branch
+   * @param params - Worktree and the commits to pick
+   * @returns The commits before and after, and the commits that were picked
+   */
+  async cherryPickWorktree(params: WorktreeCherryPickInput): Promise<CherryPickWorktreeServiceResult> {
+    const { org, repo, branch, commits } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const result = await cherryPickWorktree(this.workdir, org, repo, normalised, commits, {
+      identity: this.settings.identity,
+    });
+    return { org, repo, ...result };
+  }
+
+  /**
+   * Abandons a cherry-pick that stopped on a conflict
+   * @param params - Worktree to abort the cherry-pick in
+   * @returns The commit the branch is back at
+   */
+  async abortCherryPick(params: WorktreeCherryPickAbortInput): Promise<AbortCherryPickServiceResult> {
+    const { org, repo, branch } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const result = await abortCherryPick(this.workdir, org, repo, normalised);
+    return { org, repo, ...result };
+  }
+
+  /**
+   * Pushes, pops or lists the stashes of a worktree, e.g. to get a clean tree for a while
+   * @param params - Worktree, action, and for `push` the message and whether to include untracked files
+   * @returns The stash pushed or popped, or the worktree's stashes
+   */
+  async stashWorktree(params: WorktreeStashInput): Promise<WorktreeStashServiceResult> {
+    const { org, repo, branch, action } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    if (action === 'list') {
+      const result = await listWorktreeStashes(this.workdir, org, repo, normalised);
+      return { org, repo, action, ...result };
+    }
+    const result =
+      action === 'push'
+        ? await pushWorktreeStash(this.workdir, org, repo, normalised, {
+            message: params.message,
+            includeUntracked: params.includeUntracked,
+            identity: this.settings.identity,
+          })
+        : await popWorktreeStash(this.workdir, org, repo, normalised);
+    return { org, repo, action, ...result };
+  }
+
+  /**
+   * Reads the upstream a worktree's branch tracks
+   * @param params - Worktree to read
+   * @returns The tracked remote branch, with null fields when the branch tracks nothing
+   */
+  async getUpstream(params: { org: string; repo: string; branch: string }): Promise<WorktreeUpstreamServiceResult> {
+    const { org, repo, branch } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const upstream = await getWorktreeUpstream(this.workdir, org, repo, normalised);
+    return { org, repo, ...upstream };
+  }
+
+  /**
+   * Makes a worktree's branch track a branch on a remote, e.g. after creating it locally
+   * @param params - Worktree, remote, and optionally the remote branch to track
+   * @returns The upstream now configured
+   */
+  async setUpstream(params: WorktreeUpstreamInput): Promise<WorktreeUpstreamServiceResult> {
+    const { org, repo, branch, remote, remoteBranch } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const upstream = await setWorktreeUpstream(this.workdir, org, repo, normalised, {
+      remote,
+      ...(remoteBranch ? { remoteBranch } : {}),
+    });
+    return { org, repo, ...upstream };
+  }
+
+  /**
+   * Checks whether a worktree could be created for a branch, without creating anything
+   * @param params - Repository and branch name to check
+   * @returns Whether the name is legal and free, with the reasons when it is not
+   */
+  async checkBranch(params: { org: string; repo: string; branch: string }): Promise<WorktreeBranchCheckResult> {
+    const { org, repo, branch } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const check = await checkWorktreeBranch(this.workdir, org, repo, normalised);
+    return { org, repo, ...check };
+  }
+
+  /**
+   * Looks up the open pull request for a worktree's branch on GitHub, so the UI can link to it instead of
+   * offering to open a duplicate. Answers are reused for PULL_REQUEST_LOOKUP_TTL_MS.
+   * @param params - Worktree whose branch to look up
+   * @returns The open pull request, or null when the branch has none
+   */
+  async getPullRequest(params: { org: string; repo: string; branch: string }): Promise<WorktreePullRequestServiceResult> {
+    const { org, repo, branch } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const { repositoryPath } = await getWorktreePath(this.workdir, org, repo, normalised);
+    const slug = await resolveRepositorySlug(repositoryPath, org, repo);
+    const pullRequest = await this.pullRequestLookups.get(`${slug.org}/${slug.repo}#${normalised}`, () =>
+      this.githubClient.findOpenPullRequest(slug.org, slug.repo, normalised)
+    );
+    return { org, repo, branch: normalised, pullRequest };
+  }
+
+  /**
+   * Runs the setup script or configured setup command in a worktree
+   * @param params - Worktree to set up
+   * @param output - Follows the setup output while it runs
+   * @returns Exit status and output of the run
+   */
+  async setupWorktree(
+    params: WorktreeSetupInput,
+    output: ProcessOutputOptions = {}
+  ): Promise<WorktreeSetupServiceResult> {
+    const { org, repo, branch } = params;
+    const normalised = normalizeBranchName(branch);
+
+    if (!normalised) {
+      throw new ValidationError('Branch name cannot be empty');
+    }
+
+    const { setupCommand, terminalEnv } = this.settings;
+    const result = await runWorktreeSetup(this.workdir, org, repo, normalised, { setupCommand, terminalEnv }, output);
+    return { org, repo, ...result };
+  }
+
+  /**
+   * Disposes terminal and tmux sessions attached to a worktree
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param branch - Branch name
+   */
+  private async terminateSessions(org: string, repo: string, branch: string): Promise<void> {
+    const sessionKey = makeSessionKey(org, repo, branch);
     await disposeSessionByKey(sessionKey);
 
     await detectTmux();
     if (isTmuxAvailable()) {
-      const tmuxSessionName = makeTmuxSessionName(org, repo, normalised);
+      const tmuxSessionName = makeTmuxSessionName(org, repo, branch);
       try {
         await tmuxKillSession(tmuxSessionName);
       } catch (error: unknown) {
@@ -259,9 +974,6 @@ export class WorktreeService implements IWorktreeService {
         );
       }
     }
-
-    await removeWorktree(this.workdir, org, repo, normalised);
-    return await refreshRepositoryCache(this.workdir);
   }
 }
 
@@ -270,12 +982,16 @@ export class WorktreeService implements IWorktreeService {
  * @param workdir - Work directory root
  * @param branchNameGenerator - Branch name generator
  * @param defaultBranchConfig - Default branch configuration
+ * @param githubClient - GitHub client for pull requests; a default client when omitted
+ * @param settings - Server settings applied to worktrees and commits
  * @returns WorktreeService instance
  */
 export function createWorktreeService(
   workdir: string,
   branchNameGenerator: unknown,
-  defaultBranchConfig: unknown
+  defaultBranchConfig: unknown,
+  githubClient?: Pick<GithubClient, 'createPullRequest' | 'findOpenPullRequest'>,
+  settings: WorktreeServiceSettings = {}
 ): WorktreeService {
-  return new WorktreeService(workdir, branchNameGenerator, defaultBranchConfig);
+  return new WorktreeService(workdir, branchNameGenerator, defaultBranchConfig, githubClient, settings);
 }
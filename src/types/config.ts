@@ -2,6 +2,8 @@
  * Configuration type definitions
  */
 
+import type { Feature } from '../config/features.js';
+
 export interface AgentCommand {
   command: string;
   label: string;
@@ -48,5 +50,121 @@ export interface ServerConfig {
   defaultBranches?: DefaultBranchConfig;
   cookieSecure?: string | boolean;
   terminalSessionMode?: 'auto' | 'tmux' | 'pty';
+  /**
+   * Variables added to the environment of spawned terminals, beneath each worktree's `.agentrix/env`
+   */
+  terminalEnv?: Record<string, string>;
+  /**
+   * git config such as `http.proxy` passed with `-c` to clones and worktree creation
+   */
+  gitConfig?: Record<string, string>;
+  allowedUrlSchemes?: string[];
+  gitConcurrency?: number;
+  /**
+   * Clones allowed to run at once; further clone requests wait in a queue
+   */
+  maxConcurrentClones?: number;
+  /**
+   * Clones through the git CLI (default) or in-process through libgit2
+   */
+  gitBackend?: 'cli' | 'libgit2';
+  /**
+   * Longest a cached repository listing is served before it is rediscovered; 0 disables the cache
+   */
+  repoCacheTtlMs?: number;
+  trustProxy?: boolean;
+  githubTimeoutMs?: number;
+  /**
+   * GitHub REST API version sent as `X-GitHub-Api-Version` on `gh api` requests
+   */
+  githubApiVersion?: string;
+  /**
+   * Secret GitHub signs webhook deliveries with; enables `POST /api/github/webhook` and summary caching
+   */
+  githubWebhookSecret?: string;
+  /**
+   * Tokens keyed by GitHub organisation, used instead of the global gh login for that owner's repositories
+   */
+  githubTokens?: Record<string, string>;
+  gitAuthorName?: string;
+  gitAuthorEmail?: string;
+  cloneLayout?: string;
+  /**
+   * Organization for repository URLs that name only a repository (`https://host/repo.git`)
+   */
+  defaultOrg?: string;
+  /**
+   * Ref new worktree branches start from when the request names no `base`
+   */
+  defaultBase?: string;
+  /**
+   * Most worktrees a repository may have besides its primary clone; unset means no limit
+   */
+  maxWorktreesPerRepo?: number;
+  /**
+   * Bytes of output kept from a diff, comparison or blame before it is cut short and flagged `truncated`
+   */
+  gitOutputLimitBytes?: number;
+  /**
+   * Command run by `POST /api/worktrees/setup` in worktrees without an `.agentrix/setup` script
+   */
+  setupCommand?: string;
+  /**
+   * Requests per minute allowed per client IP on `/api/` routes; unset disables rate limiting
+   */
+  rateLimit?: number;
+  /**
+   * Requests served at once, open event streams included, before others get 503; health checks are
+   * exempt. Defaults to `DEFAULT_MAX_CONNECTIONS`
+   */
+  maxConnections?: number;
+  /**
+   * Rejects mutating API requests with 403 `read_only_mode`
+   */
+  readOnly?: boolean;
+  /**
+   * Checks the GitHub CLI token against `/user` at startup and logs the result
+   */
+  verifyGithubToken?: boolean;
+  /**
+   * Logs every git command line and its working directory at debug level, with URL credentials masked
+   */
+  traceGit?: boolean;
+  /**
+   * Signs every commit made through the API with `git commit -S`
+   */
+  signCommits?: boolean;
+  /**
+   * Refuses dangerous terminals unless the open request sends `confirmDangerous: true`
+   */
+  confirmDangerousTerminals?: boolean;
+  /**
+   * Logs a warning for requests that take longer than this many milliseconds; unset or 0 disables it
+   */
+  slowRequestMs?: number;
+  /**
+   * Origins allowed to call the API from a browser, or `*`; CORS headers are only sent when set
+   */
+  corsOrigins?: string[];
+  /**
+   * Answers allowed origins with `Access-Control-Allow-Credentials: true`; cannot be combined with `*`
+   */
+  corsAllowCredentials?: boolean;
+  /**
+   * Seconds browsers may cache a preflight answer
+   */
+  corsMaxAgeSeconds?: number;
+  /**
+   * File served in place of the UI when the UI path does not exist; a built-in placeholder page when unset
+   */
+  landingPage?: string;
+  /**
+   * Path prefix, such as `/agentrix`, the API, sockets and UI are served under when reverse-proxied on a subpath
+   */
+  basePath?: string;
+  /**
+   * Route groups to serve; every feature is on when unset
+   */
+  features?: Feature[];
 }
 
@@ -99,6 +99,10 @@ export interface CommitEntry {
   author: string;
   relativeTime: string;
   subject: string;
+  /**
+   * RFC 3339 committer timestamp
+   */
+  committedAt: string;
 }
 
 /**
@@ -137,6 +141,21 @@ export interface WorktreeStatus {
   };
 }
 
+/**
+ * Lightweight worktree summary used to refresh a single worktree
+ */
+export interface WorktreeSummary {
+  org: string;
+  repo: string;
+  branch: string;
+  worktreePath: string;
+  dirty: boolean;
+  ahead: number;
+  behind: number;
+  upstream: string | null;
+  lastCommit: CommitEntry | null;
+}
+
 /**
  * File diff result
  */
@@ -145,6 +164,14 @@ export interface FileDiff {
   previousPath: string | null;
   mode: 'staged' | 'unstaged' | 'untracked' | 'conflict';
   diff: string;
+  /**
+   * True when the patch was longer than the limit and was cut short at a line boundary
+   */
+  truncated: boolean;
+  /**
+   * Byte limit the patch was held to
+   */
+  limitBytes: number;
 }
 
 /**
@@ -190,5 +217,9 @@ export interface GetFileDiffOptions {
   previousPath?: string;
   mode?: string;
   status?: string;
+  /**
+   * Overrides the configured byte limit for the patch
+   */
+  maxBytes?: number;
 }
 
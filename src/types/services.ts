@@ -1,14 +1,66 @@
 import type { IncomingMessage, ServerResponse } from 'node:http';
-import type { RepositoriesData, AddRepositoryResult } from '../services/repository-service.js';
-import type { CreateWorktreeResult } from '../services/worktree-service.js';
-import type { TerminalOpenResult, TerminalSendResult, TerminalCloseResult } from '../services/terminal-service.js';
+import type {
+  RepositoriesData,
+  AddRepositoryResult,
+  MoveRepositoryResult,
+  RepositoryMetadataResult,
+  RepositoryRemotesResult,
+  RepositoryRemoteResult,
+  RepositoryConfigResult,
+} from '../services/repository-service.js';
+import type { CloneFilter, RepositoryMetadata } from '../domain/index.js';
+import type { ActiveClone, RepositoryBranchInfo } from '../repositories/repository-repository.js';
+import type { WorktreeUsage } from '../repositories/worktree-usage-repository.js';
+import type { WorktreeActivity, WorktreeActivityOptions } from '../repositories/worktree-activity-repository.js';
+import type { WorktreeListing } from '../repositories/worktree-discovery-repository.js';
+import type {
+  CreateWorktreeResult,
+  ReplaceWorktreeServiceResult,
+  RenameWorktreeServiceResult,
+  CommitWorktreeServiceResult,
+  PushWorktreeServiceResult,
+  PullRequestDraftServiceResult,
+  WorktreePullRequestServiceResult,
+  WorktreeSetupServiceResult,
+  ResetWorktreeServiceResult,
+  CherryPickWorktreeServiceResult,
+  AbortCherryPickServiceResult,
+  WorktreeStashServiceResult,
+  WorktreeUpstreamServiceResult,
+  WorktreeBranchCheckResult,
+  WorktreeDryRunResult,
+  ReusedWorktreeResult,
+  WorktreeBatchResult,
+} from '../services/worktree-service.js';
+import type {
+  TerminalOpenResult,
+  TerminalSendResult,
+  TerminalCloseResult,
+  TerminalHistoryResult,
+  TerminalSignalResult,
+} from '../services/terminal-service.js';
 import type { AuthResult } from '../services/auth-service.js';
+import type { ProcessOutputOptions } from '../utils/process-output.js';
 import type {
   WorktreeCreateInput,
+  WorktreeBatchCreateInput,
   WorktreeDeleteInput,
+  WorktreeReplaceInput,
+  WorktreeRenameInput,
+  WorktreeCommitInput,
+  WorktreePushInput,
+  WorktreePullRequestDraftInput,
+  WorktreeSetupInput,
+  WorktreeResetInput,
+  WorktreeCherryPickInput,
+  WorktreeCherryPickAbortInput,
+  WorktreeStashInput,
+  WorktreeUpstreamInput,
   TerminalOpenInput,
   TerminalSendInput,
   TerminalCloseInput,
+  TerminalHistoryInput,
+  TerminalSignalInput,
 } from '../validation/index.js';
 
 /**
@@ -25,9 +77,31 @@ export interface IRepositoryService {
    * Adds a new repository
    * @param repositoryUrl - Git repository URL
    * @param initCommand - Optional init command
+   * @param cloneId - Optional id for cancelling the clone while it runs
+   * @param signal - Aborting it drops the clone while it is still waiting for a clone slot
+   * @param filter - Optional partial clone filter
    * @returns Result with repository data
    */
-  addRepository(repositoryUrl: string, initCommand?: string): Promise<AddRepositoryResult>;
+  addRepository(
+    repositoryUrl: string,
+    initCommand?: string,
+    cloneId?: string,
+    signal?: AbortSignal,
+    filter?: CloneFilter
+  ): Promise<AddRepositoryResult>;
+
+  /**
+   * Lists clones that are still running
+   * @returns Running clones with their ids
+   */
+  listClones(): ActiveClone[];
+
+  /**
+   * Cancels a running clone, removing its partial directory
+   * @param cloneId - Clone id
+   * @returns Confirmation
+   */
+  cancelClone(cloneId: string): { cloneId: string; cancelled: true };
 
   /**
    * Removes a repository
@@ -37,6 +111,16 @@ export interface IRepositoryService {
    */
   deleteRepository(org: string, repo: string): Promise<RepositoriesData>;
 
+  /**
+   * Renames a repository, moving its clone and worktrees to a new org/repo
+   * @param org - Current organization name
+   * @param repo - Current repository name
+   * @param newOrg - Destination organization name
+   * @param newRepo - Destination repository name
+   * @returns Updated repository data and the new identifiers
+   */
+  moveRepository(org: string, repo: string, newOrg: string, newRepo: string): Promise<MoveRepositoryResult>;
+
   /**
    * Updates the init command for a repository
    * @param org - Organization name
@@ -45,6 +129,67 @@ export interface IRepositoryService {
    * @returns Updated repository data
    */
   updateInitCommand(org: string, repo: string, initCommand: string): Promise<RepositoriesData>;
+
+  /**
+   * Reports the branch checked out in the primary clone
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @returns Current branch details
+   */
+  getCurrentBranch(org: string, repo: string): Promise<RepositoryBranchInfo>;
+
+  /**
+   * Switches the primary clone to another branch
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param branch - Branch to check out
+   * @returns Updated branch details
+   */
+  switchBranch(org: string, repo: string, branch: string): Promise<RepositoryBranchInfo>;
+
+  /**
+   * Lists the remotes configured in a repository
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @returns Repository and its remotes
+   */
+  listRemotes(org: string, repo: string): Promise<RepositoryRemotesResult>;
+
+  /**
+   * Adds a named remote to a repository
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param name - Remote name
+   * @param url - Remote URL
+   * @returns Repository and the new remote
+   */
+  addRemote(org: string, repo: string, name: string, url: string): Promise<RepositoryRemoteResult>;
+
+  /**
+   * Reads an allowlisted git config value of a repository
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param key - Config key, such as `remote.origin.url`
+   * @returns Repository, key and value
+   */
+  readConfig(org: string, repo: string, key: string): Promise<RepositoryConfigResult>;
+
+  /**
+   * Reads the metadata attached to a repository
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @returns The repository's metadata
+   */
+  getMetadata(org: string, repo: string): Promise<RepositoryMetadataResult>;
+
+  /**
+   * Replaces the metadata attached to a repository
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param metadata - New metadata; an empty map clears it
+   * @returns The stored metadata
+   */
+  updateMetadata(org: string, repo: string, metadata: RepositoryMetadata): Promise<RepositoryMetadataResult>;
 }
 
 /**
@@ -52,11 +197,51 @@ export interface IRepositoryService {
  */
 export interface IWorktreeService {
   /**
-   * Creates a new worktree
+   * Creates a new worktree, or returns the existing one when asked to reuse it
    * @param params - Creation parameters
-   * @returns Result with task ID and repository info
+   * @returns Result with task ID and repository info, or the reused worktree
+   */
+  createWorktree(params: WorktreeCreateInput): Promise<CreateWorktreeResult | ReusedWorktreeResult>;
+
+  /**
+   * Validates a worktree creation request without creating anything
+   * @param params - Creation parameters
+   * @returns The path the worktree would be created at
+   */
+  previewWorktree(params: WorktreeCreateInput): Promise<WorktreeDryRunResult>;
+
+  /**
+   * Creates worktrees for several branches at once
+   * @param params - Batch parameters
+   * @returns Per-branch results in request order
+   */
+  createWorktreeBatch(params: WorktreeBatchCreateInput): Promise<WorktreeBatchResult>;
+
+  /**
+   * Lists a repository's worktrees as git records them, plus directories git has forgotten
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @returns Worktrees flagged as locked, prunable or unregistered
    */
-  createWorktree(params: WorktreeCreateInput): Promise<CreateWorktreeResult>;
+  listWorktrees(org: string, repo: string): Promise<WorktreeListing>;
+
+  /**
+   * Reports the disk space used by a repository's worktrees
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param options - Set `refresh` to bypass the short-lived cache
+   * @returns Per-worktree sizes in bytes and their total
+   */
+  getWorktreeUsage(org: string, repo: string, options?: { refresh?: boolean }): Promise<WorktreeUsage>;
+
+  /**
+   * Gathers recent commits from every worktree of a repository into one newest-first feed
+   * @param org - Organization name
+   * @param repo - Repository name
+   * @param options - Commits read per worktree and the feed's length
+   * @returns Commits with the branch they were found on
+   */
+  getWorktreeActivity(org: string, repo: string, options?: WorktreeActivityOptions): Promise<WorktreeActivity>;
 
   /**
    * Deletes a worktree
@@ -64,6 +249,107 @@ export interface IWorktreeService {
    * @returns Updated repository data
    */
   deleteWorktree(params: WorktreeDeleteInput): Promise<RepositoriesData>;
+
+  /**
+   * Discards a worktree and recreates its branch from a base, restoring the old worktree on failure
+   * @param params - Worktree and optional base ref
+   * @returns The new worktree and the commit the branch was at before
+   */
+  replaceWorktree(params: WorktreeReplaceInput): Promise<ReplaceWorktreeServiceResult>;
+
+  /**
+   * Renames a worktree's branch and directory
+   * @param params - Rename parameters
+   * @returns Renamed worktree details
+   */
+  renameWorktree(params: WorktreeRenameInput): Promise<RenameWorktreeServiceResult>;
+
+  /**
+   * Stages and commits changes in a worktree
+   * @param params - Commit parameters
+   * @param output - Follows git's output while it runs
+   * @returns The new commit SHA and committed files
+   */
+  commitWorktree(params: WorktreeCommitInput, output?: ProcessOutputOptions): Promise<CommitWorktreeServiceResult>;
+
+  /**
+   * Pushes a worktree branch to origin
+   * @param params - Push parameters
+   * @param output - Follows git's output while it runs
+   * @returns Remote tracking information
+   */
+  pushWorktree(params: WorktreePushInput, output?: ProcessOutputOptions): Promise<PushWorktreeServiceResult>;
+
+  /**
+   * Creates a worktree, pushes it and opens a draft pull request for it
+   * @param params - Worktree and pull request details
+   * @returns Worktree path, pushed commit and pull request
+   */
+  createPullRequestDraft(params: WorktreePullRequestDraftInput): Promise<PullRequestDraftServiceResult>;
+
+  /**
+   * Checks whether a worktree could be created for a branch, without creating anything
+   * @param params - Repository and branch name to check
+   * @returns Whether the name is legal and free, with the reasons when it is not
+   */
+  checkBranch(params: { org: string; repo: string; branch: string }): Promise<WorktreeBranchCheckResult>;
+
+  /**
+   * Looks up the open pull request for a worktree's branch
+   * @param params - Worktree whose branch to look up
+   * @returns The open pull request, or null when the branch has none
+   */
+  getPullRequest(params: { org: string; repo: string; branch: string }): Promise<WorktreePullRequestServiceResult>;
+
+  /**
+   * Runs the setup script or configured setup command in a worktree
+   * @param params - Worktree to set up
+   * @param output - Follows the setup output while it runs
+   * @returns Exit status and output of the run
+   */
+  setupWorktree(params: WorktreeSetupInput, output?: ProcessOutputOptions): Promise<WorktreeSetupServiceResult>;
+
+  /**
+   * Discards local changes in a worktree by resetting it to its upstream or a given ref
+   * @param params - Worktree and optional target ref
+   * @returns The commits before and after, and the files that were discarded or removed
+   */
+  resetWorktree(params: WorktreeResetInput): Promise<ResetWorktreeServiceResult>;
+
+  /**
+   * Applies commits to a worktree's branch
+   * @param params - Worktree and commits to pick
+   * @returns The commits before and after, and the commits that were picked
+   */
+  cherryPickWorktree(params: WorktreeCherryPickInput): Promise<CherryPickWorktreeServiceResult>;
+
+  /**
+   * Abandons a cherry-pick that stopped on a conflict
+   * @param params - Worktree to abort the cherry-pick in
+   * @returns The commit the branch is back at
+   */
+  abortCherryPick(params: WorktreeCherryPickAbortInput): Promise<AbortCherryPickServiceResult>;
+
+  /**
+   * Pushes, pops or lists the stashes of a worktree
+   * @param params - Worktree and stash action
+   * @returns The stash pushed or popped, or the worktree's stashes
+   */
+  stashWorktree(params: WorktreeStashInput): Promise<WorktreeStashServiceResult>;
+
+  /**
+   * Reads the upstream a worktree's branch tracks
+   * @param params - Worktree to read
+   * @returns The tracked remote branch, with null fields when the branch tracks nothing
+   */
+  getUpstream(params: { org: string; repo: string; branch: string }): Promise<WorktreeUpstreamServiceResult>;
+
+  /**
+   * Makes a worktree's branch track a branch on a remote
+   * @param params - Worktree, remote, and optionally the remote branch to track
+   * @returns The upstream now configured
+   */
+  setUpstream(params: WorktreeUpstreamInput): Promise<WorktreeUpstreamServiceResult>;
 }
 
 /**
@@ -84,6 +370,20 @@ export interface ITerminalService {
    */
   sendInput(params: TerminalSendInput): Promise<TerminalSendResult>;
 
+  /**
+   * Reads the buffered output of a terminal session
+   * @param params - Session and number of trailing lines
+   * @returns Buffered output
+   */
+  readHistory(params: TerminalHistoryInput): Promise<TerminalHistoryResult>;
+
+  /**
+   * Signals the process running in the foreground of a terminal session
+   * @param params - Session and signal name
+   * @returns The signal sent and the process group it went to
+   */
+  signalSession(params: TerminalSignalInput): Promise<TerminalSignalResult>;
+
   /**
    * Closes an active terminal session
    * @param params - Close parameters
@@ -0,0 +1,142 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import http from 'node:http';
+import os from 'node:os';
+import path from 'node:path';
+import type { AddressInfo } from 'node:net';
+import { once } from 'node:events';
+import { after, before, describe, it } from 'node:test';
+
+import {
+  injectBasePathConfig,
+  mountBasePath,
+  normalizeBasePath,
+  rewriteHtmlForBasePath,
+  stripBasePath,
+} from './base-path.js';
+import { createLandingProvider, createUiProvider } from '../server/ui.js';
+
+describe('normalizeBasePath', () => {
+  it('adds a leading slash and drops trailing and repeated ones', () => {
+    assert.equal(normalizeBasePath('agentrix/'), '/agentrix');
+    assert.equal(normalizeBasePath(' //tools//agentrix '), '/tools/agentrix');
+    assert.equal(normalizeBasePath('/'), '');
+    assert.equal(normalizeBasePath(undefined), '');
+  });
+
+  it('rejects traversal and characters unsafe in a path', () => {
+    assert.throws(() => normalizeBasePath('/a/../b'), /"\.\."/);
+    assert.throws(() => normalizeBasePath('/agentrix?x=1'), /not allowed/);
+    assert.throws(() => normalizeBasePath('/agent rix'), /not allowed/);
+  });
+});
+
+describe('stripBasePath, rewriteHtmlForBasePath and injectBasePathConfig', () => {
+  it('strips the prefix only at a segment boundary', () => {
+    assert.equal(stripBasePath('/agentrix/api/repos?org=a', '/agentrix'), '/api/repos?org=a');
+    assert.equal(stripBasePath('/agentrix-other/', '/agentrix'), null);
+    assert.equal(stripBasePath('/api/repos', '/agentrix'), null);
+  });
+
+  it('prefixes root-relative URLs and leaves others alone', () => {
+    const html =
+      '<link href="/assets/app.css"><script src=\'/assets/app.js\'></script>' +
+      '<a href="//cdn.example.com/x.js">cdn</a><a href="docs/">docs</a><img src="https://example.com/a.png">';
+    assert.equal(
+      rewriteHtmlForBasePath(html, '/agentrix'),
+      '<link href="/agentrix/assets/app.css"><script src=\'/agentrix/assets/app.js\'></script>' +
+        '<a href="//cdn.example.com/x.js">cdn</a><a href="docs/">docs</a><img src="https://example.com/a.png">'
+    );
+    assert.equal(rewriteHtmlForBasePath(html, ''), html);
+  });
+
+  it('announces the base path at the start of the head', () => {
+    assert.equal(
+      injectBasePathConfig('<html><head lang="en"><script src="./assets/app.js"></script></head></html>', '/agentrix'),
+      '<html><head lang="en"><base href="/agentrix/"><script>window.__AGENTRIX_BASE__="/agentrix";</script>' +
+        '<script src="./assets/app.js"></script></head></html>'
+    );
+    assert.equal(
+      injectBasePathConfig('<p>hi</p>', ''),
+      '<base href="/"><script>window.__AGENTRIX_BASE__="";</script><p>hi</p>'
+    );
+  });
+});
+
+describe('mountBasePath', () => {
+  let uiDir: string;
+  let server: http.Server;
+  let port: number;
+
+  const get = async (requestPath: string) => {
+    const response = await fetch(`http://127.0.0.1:${port}${requestPath}`, { redirect: 'manual' });
+    return { status: response.status, location: response.headers.get('location'), body: await response.text() };
+  };
+
+  before(async () => {
+    uiDir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-base-path-'));
+    await fs.mkdir(path.join(uiDir, 'assets'));
+    await fs.writeFile(path.join(uiDir, 'index.html'), '<script src="/assets/app.js"></script>');
+    await fs.writeFile(path.join(uiDir, 'assets', 'app.js'), 'console.log("app");');
+    const uiProvider = await createUiProvider(uiDir, { basePath: '/agentrix' });
+
+    // The server's request handler in miniature: mount, then the API, then the UI
+    server = http.createServer(async (req, res) => {
+      if (!mountBasePath(req, res, '/agentrix')) {
+        return;
+      }
+      if (req.url?.startsWith('/api/')) {
+        res.setHeader('Content-Type', 'application/json');
+        res.end(JSON.stringify({ path: req.url }));
+        return;
+      }
+      await uiProvider.serve(req, res);
+    });
+    server.listen(0, '127.0.0.1');
+    await once(server, 'listening');
+    ({ port } = server.address() as AddressInfo);
+  });
+
+  after(async () => {
+    server.closeAllConnections();
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+    await fs.rm(uiDir, { recursive: true, force: true });
+  });
+
+  it('serves the API and the frontend under the base path', async () => {
+    assert.deepEqual(JSON.parse((await get('/agentrix/api/health?deep=1')).body), { path: '/api/health?deep=1' });
+
+    const index = await get('/agentrix/');
+    assert.equal(index.status, 200);
+    assert.equal(
+      index.body,
+      '<base href="/agentrix/"><script>window.__AGENTRIX_BASE__="/agentrix";</script>' +
+        '<script src="/agentrix/assets/app.js"></script>'
+    );
+    assert.equal((await get('/agentrix/assets/app.js')).body, 'console.log("app");');
+    // Client-side routes still fall back to the index page
+    assert.equal((await get('/agentrix/repos/acme/demo')).body, index.body);
+  });
+
+  it('redirects the bare base path and refuses paths outside it', async () => {
+    assert.deepEqual(await get('/agentrix?tab=1'), { status: 308, location: '/agentrix/?tab=1', body: '' });
+    assert.equal((await get('/api/health')).status, 404);
+    assert.equal((await get('/')).status, 404);
+  });
+
+  it('points the placeholder page at the prefixed API', async () => {
+    const landing = await createLandingProvider(null, { basePath: '/agentrix' });
+    const chunks: Buffer[] = [];
+    const res = {
+      statusCode: 0,
+      setHeader: () => {},
+      end: (data?: string | Buffer) => {
+        if (data) {
+          chunks.push(Buffer.from(data));
+        }
+      },
+    };
+    await landing.serve({ method: 'GET', url: '/' } as http.IncomingMessage, res as unknown as http.ServerResponse);
+    assert.match(Buffer.concat(chunks).toString('utf8'), /href="\/agentrix\/api\/health"><code>\/agentrix\/api\/<\/code>/);
+  });
+});
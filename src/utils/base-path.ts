@@ -0,0 +1,91 @@
+import type { IncomingMessage, ServerResponse } from 'node:http';
+
+const BASE_PATH_SEGMENT_PATTERN = /^[A-Za-z0-9._~-]+$/;
+
+/**
+ * Normalises the path prefix the server is mounted under, such as `agentrix/` to `/agentrix`
+ * @param value - Raw base path; empty or `/` mounts the server at the root
+ * @returns The base path with a leading and no trailing slash, or an empty string for the root
+ * @throws {Error} If a segment is `.`/`..` or has characters other than letters, digits and `._~-`
+ */
+export function normalizeBasePath(value?: string | null): string {
+  const segments = (value ?? '').trim().split('/').filter(Boolean);
+  for (const segment of segments) {
+    if (segment === '.' || segment === '..' || !BASE_PATH_SEGMENT_PATTERN.test(segment)) {
+      throw new Error(`Base path segment "${segment}" is not allowed`);
+    }
+  }
+  return segments.length > 0 ? `/${segments.join('/')}` : '';
+}
+
+/**
+ * Removes the base path from a request URL
+ * @param url - Raw request URL, query included
+ * @param basePath - Normalised, non-empty base path
+ * @returns The URL as it would be at the root, or null when it is outside the base path
+ */
+export function stripBasePath(url: string, basePath: string): string | null {
+  return url.startsWith(`${basePath}/`) ? url.slice(basePath.length) : null;
+}
+
+/**
+ * Strips the base path from a request so the router and the UI see the paths they would at the root.
+ * The bare base path is redirected to its trailing-slash form, which relative links in the UI need, and
+ * anything outside the base path gets 404.
+ * @param req - Incoming request; its URL is rewritten in place
+ * @param res - Its response, ended when the request is not for the mounted server
+ * @param basePath - Normalised base path; empty leaves every request untouched
+ * @returns false when the request was answered and must not be handled further
+ */
+export function mountBasePath(req: IncomingMessage, res: ServerResponse, basePath: string): boolean {
+  if (!basePath) {
+    return true;
+  }
+  const rawUrl = req.url || '/';
+  const stripped = stripBasePath(rawUrl, basePath);
+  if (stripped !== null) {
+    req.url = stripped;
+    return true;
+  }
+
+  const queryIndex = rawUrl.indexOf('?');
+  if ((queryIndex === -1 ? rawUrl : rawUrl.slice(0, queryIndex)) === basePath) {
+    res.statusCode = 308;
+    res.setHeader('Location', `${basePath}/${queryIndex === -1 ? '' : rawUrl.slice(queryIndex)}`);
+    res.end();
+    return false;
+  }
+  res.statusCode = 404;
+  res.end('Not Found');
+  return false;
+}
+
+/**
+ * Prefixes the root-relative `src`, `href` and `action` URLs of an HTML page with the base path, so a
+ * frontend built for the root loads its assets under the prefix. Protocol-relative URLs are left alone.
+ * @param html - Page markup
+ * @param basePath - Normalised base path; empty returns the page unchanged
+ */
+export function rewriteHtmlForBasePath(html: string, basePath: string): string {
+  if (!basePath) {
+    return html;
+  }
+  return html.replace(/(\s(?:src|href|action)=["'])\/(?!\/)/gi, `$1${basePath}/`);
+}
+
+/**
+ * Tells a served UI page where it is mounted: a `<base href>` that relative asset and chunk URLs resolve
+ * against, and `window.__AGENTRIX_BASE__`, which the UI prefixes its API and socket URLs with. Both go at the
+ * start of `<head>`, ahead of anything that loads a URL, or at the start of the page when it has no head.
+ * @param html - Page markup
+ * @param basePath - Normalised base path; empty for a server mounted at the root
+ */
+export function injectBasePathConfig(html: string, basePath: string): string {
+  const config = `<base href="${basePath}/"><script>window.__AGENTRIX_BASE__=${JSON.stringify(basePath)};</script>`;
+  const head = /<head(?:\s[^>]*)?>/i.exec(html);
+  if (!head) {
+    return `${config}${html}`;
+  }
+  const end = head.index + head[0].length;
+  return `${html.slice(0, end)}${config}${html.slice(end)}`;
+}
@@ -0,0 +1,92 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+
+import { ConcurrencyLimiter, mapWithConcurrency } from './concurrency.js';
+
+function delay(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+describe('mapWithConcurrency', () => {
+  it('preserves input order when calls resolve out of order', async () => {
+    const result = await mapWithConcurrency([30, 5, 20, 0], 4, async (value) => {
+      await delay(value);
+      return value * 2;
+    });
+
+    assert.deepEqual(result, [60, 10, 40, 0]);
+  });
+
+  it('never exceeds the configured limit', async () => {
+    let active = 0;
+    let peak = 0;
+
+    await mapWithConcurrency([1, 2, 3, 4, 5, 6, 7], 3, async () => {
+      active += 1;
+      peak = Math.max(peak, active);
+      await delay(5);
+      active -= 1;
+    });
+
+    assert.equal(peak, 3);
+  });
+
+  it('treats invalid limits as sequential and handles empty input', async () => {
+    let active = 0;
+    let peak = 0;
+    await mapWithConcurrency([1, 2, 3], 0, async () => {
+      active += 1;
+      peak = Math.max(peak, active);
+      await delay(1);
+      active -= 1;
+    });
+
+    assert.equal(peak, 1);
+    assert.deepEqual(await mapWithConcurrency([], 4, async () => 1), []);
+  });
+});
+
+describe('ConcurrencyLimiter', () => {
+  it('grants slots in arrival order and starts waiters when the limit is raised', async () => {
+    const limiter = new ConcurrencyLimiter(1);
+    const order: string[] = [];
+
+    const releaseFirst = await limiter.acquire();
+    const second = limiter.acquire().then((release) => {
+      order.push('second');
+      return release;
+    });
+    const third = limiter.acquire().then((release) => {
+      order.push('third');
+      return release;
+    });
+    assert.equal(limiter.pending, 2);
+
+    releaseFirst();
+    releaseFirst();
+    const releaseSecond = await second;
+    assert.deepEqual(order, ['second']);
+    assert.equal(limiter.active, 1);
+
+    limiter.setLimit(2);
+    await third;
+    assert.deepEqual(order, ['second', 'third']);
+    assert.equal(limiter.active, 2);
+    releaseSecond();
+    assert.equal(limiter.active, 1);
+  });
+
+  it('removes an aborted waiter from the queue', async () => {
+    const limiter = new ConcurrencyLimiter(1);
+    const release = await limiter.acquire();
+    const controller = new AbortController();
+
+    const waiting = limiter.acquire(controller.signal);
+    controller.abort(new Error('gone'));
+
+    await assert.rejects(waiting, /gone/);
+    assert.equal(limiter.pending, 0);
+    release();
+    assert.equal(limiter.active, 0);
+  });
+});
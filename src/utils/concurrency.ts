@@ -0,0 +1,115 @@
+/**
+ * Maps items through an async function while running at most `limit` calls at once.
+ * Results are returned in the same order as the input items.
+ * @param items - Items to process
+ * @param limit - Maximum number of in-flight calls (values below 1 are treated as 1)
+ * @param mapper - Async mapping function
+ * @returns Mapped results in input order
+ */
+export async function mapWithConcurrency<T, R>(
+  items: readonly T[],
+  limit: number,
+  mapper: (item: T, index: number) => Promise<R>
+): Promise<R[]> {
+  const results = new Array<R>(items.length);
+  const workerCount = Math.min(Math.max(1, Math.floor(limit) || 1), items.length);
+  let nextIndex = 0;
+
+  const worker = async (): Promise<void> => {
+    while (nextIndex < items.length) {
+      const index = nextIndex;
+      nextIndex += 1;
+      results[index] = await mapper(items[index]!, index);
+    }
+  };
+
+  await Promise.all(Array.from({ length: workerCount }, () => worker()));
+  return results;
+}
+
+/**
+ * Counting semaphore that hands out slots in arrival order. Waiters beyond the limit queue until a
+ * slot is released, and can leave the queue early through an abort signal.
+ */
+export class ConcurrencyLimiter {
+  private limit: number;
+  private running = 0;
+  private readonly waiters: Array<() => void> = [];
+
+  constructor(limit: number) {
+    this.limit = ConcurrencyLimiter.normaliseLimit(limit);
+  }
+
+  private static normaliseLimit(limit: number): number {
+    return Math.max(1, Math.floor(limit) || 1);
+  }
+
+  /**
+   * Calls currently holding a slot
+   */
+  get active(): number {
+    return this.running;
+  }
+
+  /**
+   * Calls waiting for a slot
+   */
+  get pending(): number {
+    return this.waiters.length;
+  }
+
+  get maxConcurrent(): number {
+    return this.limit;
+  }
+
+  /**
+   * Changes the number of slots; raising it starts queued waiters straight away, lowering it lets
+   * running calls finish
+   */
+  setLimit(limit: number): void {
+    this.limit = ConcurrencyLimiter.normaliseLimit(limit);
+    this.drain();
+  }
+
+  /**
+   * Waits for a slot
+   * @param signal - Aborting it while still queued rejects with the signal's reason
+   * @returns Function that frees the slot; calling it more than once has no effect
+   */
+  acquire(signal?: AbortSignal): Promise<() => void> {
+    if (signal?.aborted) {
+      return Promise.reject(signal.reason);
+    }
+    return new Promise((resolve, reject) => {
+      const grant = (): void => {
+        signal?.removeEventListener('abort', onAbort);
+        this.running += 1;
+        let released = false;
+        resolve(() => {
+          if (released) {
+            return;
+          }
+          released = true;
+          this.running -= 1;
+          this.drain();
+        });
+      };
+      const onAbort = (): void => {
+        const index = this.waiters.indexOf(grant);
+        if (index !== -1) {
+          this.waiters.splice(index, 1);
+        }
+        reject(signal?.reason);
+      };
+      signal?.addEventListener('abort', onAbort, { once: true });
+      this.waiters.push(grant);
+      this.drain();
+    });
+  }
+
+  private drain(): void {
+    while (this.running < this.limit && this.waiters.length > 0) {
+      this.waiters.shift()!();
+    }
+  }
+}
@@ -4,7 +4,16 @@ import type { IncomingMessage, ServerResponse } from 'node:http';
 import { describe, it } from 'node:test';
 
 import { MAX_REQUEST_BODY_SIZE } from '../config/constants.js';
-import { getClientIp, handleHeadRequest, readJsonBody, sendJson } from './http.js';
+import { InvalidBodyError } from '../infrastructure/errors/index.js';
+import {
+  describeJsonSyntaxError,
+  getClientIp,
+  getRequestOrigin,
+  handleHeadRequest,
+  negotiateMediaType,
+  readJsonBody,
+  sendJson,
+} from './http.js';
 
 type HeaderValue = string | number | string[];
 
@@ -83,13 +92,39 @@ describe('sendJson', () => {
   });
 });
 
+describe('negotiateMediaType', () => {
+  const candidates = ['application/json', 'text/plain'] as const;
+
+  it('falls back to the first candidate when the header is missing or ties', () => {
+    assert.equal(negotiateMediaType(undefined, candidates), 'application/json');
+    assert.equal(negotiateMediaType('*/*', candidates), 'application/json');
+    assert.equal(negotiateMediaType('text/plain, application/json', candidates), 'application/json');
+  });
+
+  it('follows q weights and specificity', () => {
+    assert.equal(negotiateMediaType('text/plain', candidates), 'text/plain');
+    assert.equal(negotiateMediaType('text/*, */*;q=0.5', candidates), 'text/plain');
+    assert.equal(negotiateMediaType('application/json;q=0.2, text/plain;q=0.8', candidates), 'text/plain');
+    assert.equal(negotiateMediaType('text/plain;q=0, */*', candidates), 'application/json');
+  });
+});
+
 describe('getClientIp', () => {
-  it('uses the first x-forwarded-for header value', () => {
+  it('uses the first x-forwarded-for hop when the proxy is trusted', () => {
     const req = new MockIncomingMessage({ 'x-forwarded-for': '203.0.113.1, 198.51.100.2' });
-    const ip = getClientIp(req as unknown as IncomingMessage);
+    const ip = getClientIp(req as unknown as IncomingMessage, { trustProxy: true });
     assert.equal(ip, '203.0.113.1');
   });
 
+  it('ignores x-forwarded-for unless the proxy is trusted', () => {
+    const req = new MockIncomingMessage(
+      { 'x-forwarded-for': '203.0.113.1' },
+      { remoteAddress: '192.0.2.55' },
+    );
+    assert.equal(getClientIp(req as unknown as IncomingMessage), '192.0.2.55');
+    assert.equal(getClientIp(req as unknown as IncomingMessage, { trustProxy: false }), '192.0.2.55');
+  });
+
   it('falls back to remote address when header is missing', () => {
     const req = new MockIncomingMessage({}, { remoteAddress: '192.0.2.55' });
     const ip = getClientIp(req as unknown as IncomingMessage);
@@ -103,6 +138,34 @@ describe('getClientIp', () => {
   });
 });
 
+describe('getRequestOrigin', () => {
+  it('uses the host header and socket protocol when the proxy is untrusted', () => {
+    const req = new MockIncomingMessage({
+      host: 'internal:3414',
+      'x-forwarded-proto': 'https',
+      'x-forwarded-host': 'agentrix.example.com',
+    });
+    assert.equal(getRequestOrigin(req as unknown as IncomingMessage), 'http://internal:3414');
+  });
+
+  it('honours the first forwarded proto and host hop when trusted', () => {
+    const req = new MockIncomingMessage({
+      host: 'internal:3414',
+      'x-forwarded-proto': 'https, http',
+      'x-forwarded-host': 'agentrix.example.com, internal',
+    });
+    assert.equal(
+      getRequestOrigin(req as unknown as IncomingMessage, { trustProxy: true }),
+      'https://agentrix.example.com',
+    );
+  });
+
+  it('ignores unexpected forwarded protocols', () => {
+    const req = new MockIncomingMessage({ host: 'internal', 'x-forwarded-proto': 'gopher' }, { encrypted: true });
+    assert.equal(getRequestOrigin(req as unknown as IncomingMessage, { trustProxy: true }), 'https://internal');
+  });
+});
+
 describe('handleHeadRequest', () => {
   it('sets status and cache headers', () => {
     const res = new MockServerResponse();
@@ -147,6 +210,38 @@ describe('readJsonBody', () => {
     await assert.rejects(promise, /Invalid JSON payload/);
   });
 
+  it('reports where invalid JSON fails as a 400 invalid_body error', async () => {
+    const req = new MockIncomingMessage();
+    const promise = readJsonBody(req as unknown as IncomingMessage);
+
+    req.push(Buffer.from('{\n  "url": "git@github.com:acme/demo.git",\n  "initCommand": }', 'utf8'));
+    req.finish();
+
+    await assert.rejects(promise, (error: unknown) => {
+      assert.ok(error instanceof InvalidBodyError);
+      assert.equal(error.statusCode, 400);
+      assert.equal(error.code, 'invalid_body');
+      assert.match(error.message, /^Invalid JSON payload/);
+      return true;
+    });
+  });
+
+  it('describes JSON syntax errors by line and column', () => {
+    const text = '{"org": "acme",\n  "repo" "demo"}';
+    let parseError: unknown;
+    try {
+      JSON.parse(text);
+    } catch (error) {
+      parseError = error;
+    }
+
+    assert.match(describeJsonSyntaxError(text, parseError), /^Invalid JSON payload at line 2, column 10: /);
+    assert.equal(
+      describeJsonSyntaxError('{"a":', new SyntaxError('Unexpected end of JSON input')),
+      'Invalid JSON payload at line 1, column 6: Unexpected end of JSON input',
+    );
+  });
+
   it('rejects and destroys the request when payload exceeds size limit', async () => {
     const req = new MockIncomingMessage();
     const promise = readJsonBody(req as unknown as IncomingMessage);
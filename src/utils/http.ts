@@ -1,5 +1,6 @@
 import type { IncomingMessage, ServerResponse } from 'node:http';
 import { MAX_REQUEST_BODY_SIZE } from '../config/constants.js';
+import { InvalidBodyError } from '../infrastructure/errors/validation-error.js';
 
 export interface JsonPayload {
   [key: string]: unknown;
@@ -23,13 +24,86 @@ export function handleHeadRequest(res: ServerResponse): void {
   res.end();
 }
 
+/**
+ * Picks the media type a client prefers from those the endpoint can produce, following the `q` weights in
+ * an `Accept` header. Exact types outrank `type/*`, which outranks the full wildcard; ties go to the earlier candidate.
+ * @param accept - Raw `Accept` header, if any
+ * @param candidates - Producible media types, most preferred first (the first is the default)
+ * @returns The chosen candidate
+ */
+export function negotiateMediaType(accept: string | string[] | undefined, candidates: readonly [string, ...string[]]): string {
+  const header = Array.isArray(accept) ? accept.join(',') : accept ?? '';
+  const ranges = header
+    .split(',')
+    .map((entry) => {
+      const [range = '', ...params] = entry.split(';').map((part) => part.trim().toLowerCase());
+      const qParam = params.find((param) => param.startsWith('q='));
+      const q = qParam ? Number.parseFloat(qParam.slice(2)) : 1;
+      return { range, q: Number.isFinite(q) ? q : 0 };
+    })
+    .filter(({ range }) => range.includes('/'));
+
+  if (ranges.length === 0) {
+    return candidates[0];
+  }
+
+  const weightFor = (type: string): number => {
+    const [major] = type.split('/');
+    const match =
+      ranges.find(({ range }) => range === type) ??
+      ranges.find(({ range }) => range === `${major}/*`) ??
+      ranges.find(({ range }) => range === '*/*');
+    return match ? match.q : 0;
+  };
+
+  let best = candidates[0];
+  let bestWeight = weightFor(best);
+  for (const candidate of candidates.slice(1)) {
+    const weight = weightFor(candidate);
+    if (weight > bestWeight) {
+      best = candidate;
+      bestWeight = weight;
+    }
+  }
+  return best;
+}
+
 /**
  * @deprecated Use extractErrorMessage from infrastructure/errors instead
  * Re-exported for backward compatibility
  */
 export { extractErrorMessage } from '../infrastructure/errors/index.js';
 
-export async function readJsonBody(req: IncomingMessage): Promise<JsonPayload> {
+/**
+ * Turns a JSON.parse failure into a message naming the line and column of the problem
+ * @param text - Body that failed to parse
+ * @param error - Error thrown by JSON.parse
+ */
+export function describeJsonSyntaxError(text: string, error: unknown): string {
+  const raw = error instanceof Error ? error.message : '';
+  // V8 reports "… in JSON at position N" for most failures, and only "Unexpected end of JSON input" for truncation
+  const positionMatch = raw.match(/ in JSON at position (\d+)/);
+  const position = positionMatch ? Number(positionMatch[1]) : /end of JSON input/.test(raw) ? text.length : null;
+  const reason = raw
+    .replace(/ in JSON at position \d+[\s\S]*$/, '')
+    .replace(/, "[\s\S]*" is not valid JSON$/, '')
+    .trim();
+
+  if (position === null) {
+    return reason ? `Invalid JSON payload: ${reason}` : 'Invalid JSON payload';
+  }
+  const lines = text.slice(0, position).split('\n');
+  const line = lines.length;
+  const column = (lines[lines.length - 1] ?? '').length + 1;
+  return `Invalid JSON payload at line ${line}, column ${column}${reason ? `: ${reason}` : ''}`;
+}
+
+/**
+ * Reads a request body as raw bytes, e.g. to check a signature computed over the exact payload
+ * @param req - Incoming request
+ * @returns The body; empty when the request has none
+ */
+export async function readRequestBody(req: IncomingMessage): Promise<Buffer> {
   return new Promise((resolve, reject) => {
     const chunks: Buffer[] = [];
     let length = 0;
@@ -45,17 +119,7 @@ export async function readJsonBody(req: IncomingMessage): Promise<JsonPayload> {
     });
 
     req.on('end', () => {
-      if (chunks.length === 0) {
-        resolve({});
-        return;
-      }
-
-      try {
-        const buffer = Buffer.concat(chunks);
-        resolve(JSON.parse(buffer.toString('utf8')) as JsonPayload);
-      } catch (error) {
-        reject(new Error('Invalid JSON payload'));
-      }
+      resolve(Buffer.concat(chunks));
     });
 
     req.on('error', (error: Error) => {
@@ -64,25 +128,65 @@ export async function readJsonBody(req: IncomingMessage): Promise<JsonPayload> {
   });
 }
 
+/**
+ * Parses a JSON request body, naming the line and column of any syntax error
+ * @param text - Body text
+ * @throws {InvalidBodyError} If the body is not valid JSON
+ */
+export function parseJsonBody(text: string): JsonPayload {
+  try {
+    return JSON.parse(text) as JsonPayload;
+  } catch (error) {
+    throw new InvalidBodyError(describeJsonSyntaxError(text, error), error instanceof Error ? error : null);
+  }
+}
+
+export async function readJsonBody(req: IncomingMessage): Promise<JsonPayload> {
+  const body = await readRequestBody(req);
+  if (body.length === 0) {
+    return {};
+  }
+  return parseJsonBody(body.toString('utf8'));
+}
+
 /**
  * @deprecated Use infrastructure/cookies instead
  * This function re-exports for backward compatibility
  */
 export { determineSecureCookie } from '../infrastructure/cookies/index.js';
 
-export function getClientIp(req: IncomingMessage): string {
-  const headerValue = req.headers?.['x-forwarded-for'];
-  if (typeof headerValue === 'string' && headerValue.trim()) {
-    const candidate = headerValue.split(',').map((part) => part.trim()).find(Boolean);
-    if (candidate) {
-      return candidate;
+export interface ProxyTrustOptions {
+  /**
+   * Honours `X-Forwarded-*` headers set by a reverse proxy. Off by default because clients can spoof
+   * these headers when connecting directly.
+   */
+  trustProxy?: boolean;
+}
+
+/**
+ * Returns the first hop of a (possibly comma-separated or repeated) forwarded header
+ */
+function firstForwardedValue(headerValue: string | string[] | undefined): string | null {
+  const values = Array.isArray(headerValue) ? headerValue : [headerValue];
+  for (const value of values) {
+    if (typeof value !== 'string') {
+      continue;
     }
-  } else if (Array.isArray(headerValue) && headerValue.length) {
-    const candidate = headerValue.map((part) => part.trim()).find(Boolean);
+    const candidate = value.split(',').map((part) => part.trim()).find(Boolean);
     if (candidate) {
       return candidate;
     }
   }
+  return null;
+}
+
+export function getClientIp(req: IncomingMessage, { trustProxy = false }: ProxyTrustOptions = {}): string {
+  if (trustProxy) {
+    const forwarded = firstForwardedValue(req.headers?.['x-forwarded-for']);
+    if (forwarded) {
+      return forwarded;
+    }
+  }
 
   const remote = req.socket?.remoteAddress;
   if (typeof remote === 'string' && remote.trim()) {
@@ -91,3 +195,28 @@ export function getClientIp(req: IncomingMessage): string {
 
   return 'unknown';
 }
+
+/**
+ * Resolves the externally visible origin (`proto://host`) for building absolute URLs
+ * @param req - Incoming request
+ * @param options - Proxy trust options
+ * @returns Origin string
+ */
+export function getRequestOrigin(req: IncomingMessage, { trustProxy = false }: ProxyTrustOptions = {}): string {
+  const socket = req.socket as { encrypted?: boolean } | undefined;
+  let protocol = socket?.encrypted ? 'https' : 'http';
+  let host = typeof req.headers?.host === 'string' && req.headers.host ? req.headers.host : 'localhost';
+
+  if (trustProxy) {
+    const forwardedProto = firstForwardedValue(req.headers?.['x-forwarded-proto'])?.toLowerCase();
+    if (forwardedProto === 'http' || forwardedProto === 'https') {
+      protocol = forwardedProto;
+    }
+    const forwardedHost = firstForwardedValue(req.headers?.['x-forwarded-host']);
+    if (forwardedHost) {
+      host = forwardedHost;
+    }
+  }
+
+  return `${protocol}://${host}`;
+}
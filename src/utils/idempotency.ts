@@ -0,0 +1,138 @@
+/**
+ * Header clients send to make a retried request safe to repeat
+ */
+export const IDEMPOTENCY_KEY_HEADER = 'idempotency-key';
+
+/**
+ * Longest accepted idempotency key
+ */
+export const MAX_IDEMPOTENCY_KEY_LENGTH = 255;
+
+/**
+ * Error raised when an idempotency key is reused for a request with a different body
+ */
+export class IdempotencyKeyReusedError extends Error {
+  public readonly statusCode: number = 422;
+  public readonly code = 'idempotency_key_reused';
+
+  constructor() {
+    super('Idempotency-Key was already used for a different request');
+    this.name = 'IdempotencyKeyReusedError';
+  }
+}
+
+interface IdempotencyEntry<T> {
+  fingerprint: string;
+  promise: Promise<T>;
+  controller: AbortController;
+  /**
+   * Requests still waiting on the outcome; the work is aborted once all of them have gone away
+   */
+  waiters: number;
+  /**
+   * Set once the work succeeds; null while it is still running
+   */
+  expiresAt: number | null;
+}
+
+export interface IdempotentResult<T> {
+  value: T;
+  /**
+   * The value came from an earlier request with the same key
+   */
+  replayed: boolean;
+}
+
+/**
+ * Remembers the outcome of requests made with an idempotency key so a retry gets the original result
+ * instead of repeating the work. Retries that arrive while the first request is still running wait for
+ * it. Only successes are remembered: once the work fails, the next request with the key runs it again.
+ */
+export class IdempotencyCache<T> {
+  private readonly entries = new Map<string, IdempotencyEntry<T>>();
+
+  constructor(
+    private readonly ttlMs: number,
+    private readonly now: () => number = () => Date.now()
+  ) {}
+
+  /**
+   * Runs the work for a key, or joins or replays an earlier run with the same key
+   * @param key - Client-supplied idempotency key
+   * @param fingerprint - Identifies the request; a different one under the same key is rejected
+   * @param work - Does the work; its signal aborts once every request waiting on it has gone away
+   * @param signal - Aborts when this request's client goes away
+   * @throws {IdempotencyKeyReusedError} If the key was used for a different request
+   */
+  async run(
+    key: string,
+    fingerprint: string,
+    work: (signal: AbortSignal) => Promise<T>,
+    signal?: AbortSignal
+  ): Promise<IdempotentResult<T>> {
+    this.evictExpired();
+
+    let entry = this.entries.get(key);
+    const replayed = Boolean(entry);
+    if (entry && entry.fingerprint !== fingerprint) {
+      throw new IdempotencyKeyReusedError();
+    }
+    if (!entry) {
+      const controller = new AbortController();
+      const created: IdempotencyEntry<T> = {
+        fingerprint,
+        promise: Promise.resolve().then(() => work(controller.signal)),
+        controller,
+        waiters: 0,
+        expiresAt: null,
+      };
+      created.promise.then(
+        () => {
+          created.expiresAt = this.now() + this.ttlMs;
+        },
+        () => {
+          if (this.entries.get(key) === created) {
+            this.entries.delete(key);
+          }
+        }
+      );
+      this.entries.set(key, created);
+      entry = created;
+    }
+
+    const joined = entry;
+    joined.waiters += 1;
+    let left = false;
+    const leave = (): void => {
+      if (left) {
+        return;
+      }
+      left = true;
+      joined.waiters -= 1;
+      if (joined.waiters === 0 && joined.expiresAt === null) {
+        joined.controller.abort();
+      }
+    };
+    if (signal?.aborted) {
+      leave();
+    } else {
+      signal?.addEventListener('abort', leave, { once: true });
+    }
+
+    try {
+      return { value: await joined.promise, replayed };
+    } finally {
+      signal?.removeEventListener('abort', leave);
+      leave();
+    }
+  }
+
+  private evictExpired(): void {
+    const now = this.now();
+    for (const [key, entry] of this.entries) {
+      if (entry.expiresAt !== null && entry.expiresAt <= now) {
+        this.entries.delete(key);
+      }
+    }
+  }
+}
@@ -0,0 +1,124 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import { describe, it, mock } from 'node:test';
+
+import { createLineSplitter, streamProcessOutput, wantsOutputStream } from './process-output.js';
+import type { ProcessOutputOptions } from './process-output.js';
+
+async function withServer(
+  run: (output: Required<ProcessOutputOptions>) => Promise<unknown>,
+  client: (baseUrl: string) => Promise<void>
+): Promise<void> {
+  const server = http.createServer((req, res) => {
+    if (!wantsOutputStream(req)) {
+      res.statusCode = 406;
+      res.end();
+      return;
+    }
+    void streamProcessOutput(res, run);
+  });
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  try {
+    await client(`http://127.0.0.1:${(server.address() as AddressInfo).port}`);
+  } finally {
+    server.closeAllConnections();
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+  }
+}
+
+function parseEvents(body: string): Array<{ event: string; data: unknown }> {
+  return body
+    .split('\n\n')
+    .filter((block) => block.startsWith('event: '))
+    .map((block) => {
+      const [eventLine = '', dataLine = ''] = block.split('\n');
+      return { event: eventLine.slice('event: '.length), data: JSON.parse(dataLine.slice('data: '.length)) };
+    });
+}
+
+describe('createLineSplitter', () => {
+  it('splits on every kind of line break, across chunk boundaries', () => {
+    const lines: string[] = [];
+    const splitter = createLineSplitter((line) => lines.push(line));
+
+    splitter.write('Counting objects: 50%\rCounting');
+    splitter.write(Buffer.from(' objects: 100%\r'));
+    splitter.write('\nTo origin\nfinal');
+    splitter.end();
+
+    assert.deepEqual(lines, ['Counting objects: 50%', 'Counting objects: 100%', 'To origin', 'final']);
+  });
+});
+
+describe('streamProcessOutput', () => {
+  it('streams output lines in order and ends with the exit status', async () => {
+    await withServer(
+      async ({ onOutput }) => {
+        for (const line of ['one', 'two', 'three']) {
+          onOutput(line);
+          await new Promise((resolve) => setTimeout(resolve, 5));
+        }
+        return { sha: 'abc123' };
+      },
+      async (baseUrl) => {
+        const response = await fetch(baseUrl, { headers: { Accept: 'text/event-stream' } });
+
+        assert.equal(response.status, 200);
+        assert.equal(response.headers.get('content-type'), 'text/event-stream');
+        assert.deepEqual(parseEvents(await response.text()), [
+          { event: 'output', data: { line: 'one' } },
+          { event: 'output', data: { line: 'two' } },
+          { event: 'output', data: { line: 'three' } },
+          { event: 'exit', data: { exitCode: 0, data: { sha: 'abc123' } } },
+        ]);
+      }
+    );
+  });
+
+  it('reports a failure with its exit status and error body', async () => {
+    const logged = mock.method(console, 'error', () => {});
+    await withServer(
+      async ({ onOutput }) => {
+        onOutput('fatal: hook declined');
+        throw new Error('Failed to commit changes: hook declined', {
+          cause: Object.assign(new Error('hook declined'), { exitCode: 1 }),
+        });
+      },
+      async (baseUrl) => {
+        const response = await fetch(baseUrl, { headers: { Accept: 'text/event-stream' } });
+        const events = parseEvents(await response.text());
+
+        assert.deepEqual(events, [
+          { event: 'output', data: { line: 'fatal: hook declined' } },
+          { event: 'exit', data: { exitCode: 1, status: 500, error: 'Failed to commit changes: hook declined' } },
+        ]);
+      }
+    );
+    assert.equal(logged.mock.callCount(), 1);
+    logged.mock.restore();
+  });
+
+  it('aborts the run when the client disconnects', async () => {
+    let aborted: Promise<void> = Promise.resolve();
+    await withServer(
+      async ({ onOutput, signal }) => {
+        aborted = new Promise((resolve) => signal.addEventListener('abort', () => resolve(), { once: true }));
+        onOutput('started');
+        await aborted;
+        throw new Error('killed');
+      },
+      async (baseUrl) => {
+        const controller = new AbortController();
+        const response = await fetch(baseUrl, {
+          headers: { Accept: 'text/event-stream' },
+          signal: controller.signal,
+        });
+        const reader = response.body!.getReader();
+        await reader.read();
+        controller.abort();
+        await aborted;
+      }
+    );
+  });
+});
@@ -0,0 +1,128 @@
+import type { IncomingMessage, ServerResponse } from 'node:http';
+import { StringDecoder } from 'node:string_decoder';
+import { describeError } from '../infrastructure/errors/index.js';
+
+/**
+ * Lets a caller follow a subprocess while it runs
+ */
+export interface ProcessOutputOptions {
+  /**
+   * Receives each line of combined stdout and stderr as it is written
+   */
+  onOutput?: (line: string) => void;
+  /**
+   * Kills the process when aborted
+   */
+  signal?: AbortSignal;
+}
+
+export interface LineSplitter {
+  write(chunk: Buffer | string): void;
+  /**
+   * Emits whatever is left after the last line break
+   */
+  end(): void;
+}
+
+/**
+ * Cuts a process stream into lines. A lone carriage return ends a line too, so progress meters such as
+ * git's `Writing objects: 42%` come through as they update.
+ * @param onLine - Receives each line without its line break
+ */
+export function createLineSplitter(onLine: (line: string) => void): LineSplitter {
+  const decoder = new StringDecoder('utf8');
+  let pending = '';
+
+  const emit = (text: string): void => {
+    const combined = pending + text;
+    // A trailing \r may be the first half of a \r\n split across chunks
+    const cut = combined.endsWith('\r') ? combined.length - 1 : combined.length;
+    const lines = combined.slice(0, cut).split(/\r\n|\r|\n/);
+    pending = (lines.pop() ?? '') + combined.slice(cut);
+    lines.forEach((line) => onLine(line));
+  };
+
+  return {
+    write(chunk) {
+      emit(typeof chunk === 'string' ? chunk : decoder.write(chunk));
+    },
+    end() {
+      emit(decoder.end());
+      const rest = pending.replace(/\r$/, '');
+      pending = '';
+      if (rest) {
+        onLine(rest);
+      }
+    },
+  };
+}
+
+/**
+ * Checks whether the client asked for process output as server-sent events
+ */
+export function wantsOutputStream(req: IncomingMessage): boolean {
+  return (req.headers.accept ?? '').includes('text/event-stream');
+}
+
+function writeEvent(res: ServerResponse, event: string, data: unknown): void {
+  if (!res.writableEnded && !res.destroyed) {
+    res.write(`event: ${event}\ndata: ${JSON.stringify(data)}\n\n`);
+  }
+}
+
+/**
+ * Reads the exit status carried by a failed process, following wrapped errors
+ */
+function exitCodeOfError(error: unknown): number | null {
+  let current = error as { exitCode?: unknown; cause?: unknown } | null | undefined;
+  for (let depth = 0; current && depth < 5; depth += 1) {
+    if (typeof current.exitCode === 'number') {
+      return current.exitCode;
+    }
+    current = current.cause as typeof current;
+  }
+  return null;
+}
+
+/**
+ * Runs a subprocess-backed operation and streams its output as server-sent events: an `output` event
+ * per line, then one `exit` event with the exit status and either the result (`data`) or the error body
+ * with its HTTP `status`. The process is killed when the client disconnects.
+ * @param res - Response to stream on; nothing may have been written to it yet
+ * @param run - Starts the operation with the output listener and the disconnect signal
+ * @param exitCodeOf - Exit status reported for a result (0 by default)
+ */
+export async function streamProcessOutput<T>(
+  res: ServerResponse,
+  run: (output: Required<ProcessOutputOptions>) => Promise<T>,
+  exitCodeOf: (result: T) => number | null = () => 0
+): Promise<void> {
+  const controller = new AbortController();
+  const abort = (): void => controller.abort();
+  // The response closes early only when the client goes away
+  res.once('close', abort);
+
+  res.writeHead(200, {
+    'Cache-Control': 'no-store',
+    Connection: 'keep-alive',
+    'Content-Type': 'text/event-stream',
+    'X-Accel-Buffering': 'no',
+  });
+  res.flushHeaders();
+
+  try {
+    const result = await run({
+      onOutput: (line) => writeEvent(res, 'output', { line }),
+      signal: controller.signal,
+    });
+    writeEvent(res, 'exit', { exitCode: exitCodeOf(result), data: result });
+  } catch (error: unknown) {
+    if (!controller.signal.aborted) {
+      const { statusCode, body } = describeError(res, error);
+      writeEvent(res, 'exit', { exitCode: exitCodeOfError(error), status: statusCode, ...body });
+    }
+  } finally {
+    res.off('close', abort);
+    res.end();
+  }
+}
@@ -1,14 +1,21 @@
 import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
 import { describe, it, mock, afterEach } from 'node:test';
 
 import {
   __setRepositoryCacheTestOverrides,
   __setRepositoryCacheSnapshot,
+  getCachedRepositories,
+  getRepositoryCacheGeneration,
   getRepositoryCacheSnapshot,
+  invalidateRepositoryCache,
   refreshRepositoryCache,
 } from './repository-cache.js';
 
 afterEach(() => {
+  invalidateRepositoryCache();
   __setRepositoryCacheSnapshot(null);
   __setRepositoryCacheTestOverrides();
 });
@@ -24,8 +31,9 @@ describe('refreshRepositoryCache', () => {
       },
     };
 
-    const discover = mock.fn(async (workdir: string) => {
+    const discover = mock.fn(async (workdir: string, options?: { concurrency?: number | null }) => {
       assert.equal(workdir, '/tmp/workdir');
+      assert.equal(options?.concurrency, 3);
       return sample;
     });
     const emit = mock.fn(() => {});
@@ -35,7 +43,7 @@ describe('refreshRepositoryCache', () => {
       emitReposUpdate: emit,
     });
 
-    const result = await refreshRepositoryCache('/tmp/workdir');
+    const result = await refreshRepositoryCache('/tmp/workdir', { discoveryConcurrency: 3 });
 
     assert.deepEqual(result, sample);
     assert.equal(discover.mock.calls.length, 1);
@@ -102,3 +110,107 @@ describe('repository cache snapshot helpers', () => {
     assert.strictEqual(getRepositoryCacheSnapshot(), preset);
   });
 });
+
+describe('getCachedRepositories', () => {
+  /**
+   * Creates a workdir holding acme/demo whose discovery lists one branch per worktree directory
+   */
+  async function createWorkdir(): Promise<{ workdir: string; repoRoot: string; discover: ReturnType<typeof mock.fn> }> {
+    const workdir = await fs.mkdtemp(path.join(os.tmpdir(), 'agentrix-repo-cache-'));
+    const repoRoot = path.join(workdir, 'acme', 'demo');
+    await fs.mkdir(path.join(repoRoot, 'repository'), { recursive: true });
+    // Backdate so a directory created during the test always moves the mtime
+    const past = new Date(Date.now() - 60_000);
+    await fs.utimes(repoRoot, past, past);
+
+    const discover = mock.fn(async () => {
+      const entries = await fs.readdir(repoRoot);
+      const branches = entries.map((entry) => (entry === 'repository' ? 'main' : entry)).sort();
+      return { acme: { demo: { branches, initCommand: '' } } };
+    });
+    __setRepositoryCacheTestOverrides({ discoverRepositories: discover, emitReposUpdate: mock.fn() });
+    return { workdir, repoRoot, discover };
+  }
+
+  it('serves repeated reads from the cache', async () => {
+    const { workdir, discover } = await createWorkdir();
+    try {
+      const first = await getCachedRepositories(workdir);
+      const second = await getCachedRepositories(workdir);
+
+      assert.strictEqual(second, first);
+      assert.equal(discover.mock.callCount(), 1);
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+
+  it('rediscovers once a new worktree directory appears on disk', async () => {
+    const { workdir, repoRoot, discover } = await createWorkdir();
+    try {
+      assert.deepEqual((await getCachedRepositories(workdir))['acme']?.['demo']?.branches, ['main']);
+
+      await fs.mkdir(path.join(repoRoot, 'feature'));
+
+      assert.deepEqual((await getCachedRepositories(workdir))['acme']?.['demo']?.branches, ['feature', 'main']);
+      assert.equal(discover.mock.callCount(), 2);
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+
+  it('serves the listing refreshed by a mutation without rediscovering', async () => {
+    const { workdir, repoRoot, discover } = await createWorkdir();
+    try {
+      await getCachedRepositories(workdir);
+      const generation = getRepositoryCacheGeneration();
+
+      await fs.mkdir(path.join(repoRoot, 'feature'));
+      const refreshed = await refreshRepositoryCache(workdir);
+
+      assert.ok(getRepositoryCacheGeneration() > generation);
+      assert.strictEqual(await getCachedRepositories(workdir), refreshed);
+      assert.deepEqual(refreshed['acme']?.['demo']?.branches, ['feature', 'main']);
+      assert.equal(discover.mock.callCount(), 2);
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+
+  it('rediscovers after invalidation, expiry, or when caching is off', async () => {
+    const { workdir, discover } = await createWorkdir();
+    let now = 1_000;
+    __setRepositoryCacheTestOverrides({ now: () => now });
+    try {
+      await getCachedRepositories(workdir);
+      invalidateRepositoryCache();
+      await getCachedRepositories(workdir);
+      assert.equal(discover.mock.callCount(), 2);
+
+      now += 499;
+      await getCachedRepositories(workdir, { maxAgeMs: 500 });
+      assert.equal(discover.mock.callCount(), 2);
+      now += 1;
+      await getCachedRepositories(workdir, { maxAgeMs: 500 });
+      assert.equal(discover.mock.callCount(), 3);
+
+      await getCachedRepositories(workdir, { maxAgeMs: 0 });
+      await getCachedRepositories(workdir, { maxAgeMs: 0 });
+      assert.equal(discover.mock.callCount(), 5);
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+
+  it('shares one discovery between concurrent reads', async () => {
+    const { workdir, discover } = await createWorkdir();
+    try {
+      const [first, second] = await Promise.all([getCachedRepositories(workdir), getCachedRepositories(workdir)]);
+
+      assert.strictEqual(first, second);
+      assert.equal(discover.mock.callCount(), 1);
+    } finally {
+      await fs.rm(workdir, { recursive: true, force: true });
+    }
+  });
+});
@@ -1,19 +1,55 @@
+import fs from 'node:fs/promises';
+import path from 'node:path';
 import { discoverRepositories } from '../repositories/repository-repository.js';
+import { resolveRepositoryPaths } from '../repositories/repository-paths.js';
 import { emitReposUpdate } from '../core/event-bus.js';
 import type { RepositoriesMap } from '../repositories/repository-repository.js';
 
+/**
+ * Longest a cached listing is served before it is rediscovered, even when nothing looks changed
+ */
+export const DEFAULT_REPOSITORY_CACHE_MAX_AGE_MS = 30_000;
+
+/**
+ * Settings for reading and refreshing the repository listing
+ */
+export interface RepositoryCacheOptions {
+  /**
+   * Longest a cached listing is served, in milliseconds; 0 turns caching of reads off
+   */
+  maxAgeMs?: number | null;
+  /**
+   * Repositories inspected at once when the listing is rediscovered
+   */
+  discoveryConcurrency?: number | null;
+}
+
 interface Dependencies {
   discoverRepositories: typeof discoverRepositories;
   emitReposUpdate: typeof emitReposUpdate;
+  readFingerprint: (workdir: string, snapshot: RepositoriesMap | null) => Promise<string>;
+  now: () => number;
+}
+
+interface CacheEntry {
+  workdir: string;
+  generation: number;
+  fingerprint: string;
+  cachedAt: number;
 }
 
 const defaultDependencies: Dependencies = {
   discoverRepositories,
   emitReposUpdate,
+  readFingerprint: readDirectoryFingerprint,
+  now: () => Date.now(),
 };
 
 let activeDependencies: Dependencies = { ...defaultDependencies };
 let repositoryCacheSnapshot: RepositoriesMap | null = null;
+let repositoryCacheEntry: CacheEntry | null = null;
+let repositoryCacheGeneration = 0;
+let pendingLoad: { workdir: string; generation: number; promise: Promise<RepositoriesMap> } | null = null;
 
 /**
  * @internal Utility for tests to override repository discovery dependencies
@@ -26,6 +62,12 @@ export function __setRepositoryCacheTestOverrides(overrides?: Partial<Dependenci
   activeDependencies = { ...activeDependencies, ...overrides };
 }
 
+function resolveMaxAgeMs(maxAgeMs?: number | null): number {
+  return typeof maxAgeMs === 'number' && Number.isInteger(maxAgeMs) && maxAgeMs >= 0
+    ? maxAgeMs
+    : DEFAULT_REPOSITORY_CACHE_MAX_AGE_MS;
+}
+
 /**
  * Retrieves the current repository cache snapshot, if any
  */
@@ -38,21 +80,146 @@ export function getRepositoryCacheSnapshot(): RepositoriesMap | null {
  */
 export function __setRepositoryCacheSnapshot(snapshot: RepositoriesMap | null): void {
   repositoryCacheSnapshot = snapshot;
+  repositoryCacheEntry = null;
+}
+
+/**
+ * Counter bumped whenever the cache is invalidated or refreshed
+ */
+export function getRepositoryCacheGeneration(): number {
+  return repositoryCacheGeneration;
+}
+
+/**
+ * Drops the cached listing so the next read rediscovers repositories
+ */
+export function invalidateRepositoryCache(): void {
+  repositoryCacheGeneration += 1;
+  repositoryCacheSnapshot = null;
+  repositoryCacheEntry = null;
+}
+
+/**
+ * Summarises the modification times of the directories worktrees and clones are created in. Adding or
+ * removing a clone or worktree changes one of them, so a different fingerprint means the listing is stale.
+ */
+async function readDirectoryFingerprint(workdir: string, snapshot: RepositoriesMap | null): Promise<string> {
+  const directories = new Set<string>([workdir]);
+  Object.entries(snapshot ?? {}).forEach(([org, repos]) => {
+    Object.keys(repos ?? {}).forEach((repo) => {
+      try {
+        const { repoRoot } = resolveRepositoryPaths(workdir, org, repo);
+        directories.add(repoRoot);
+        directories.add(path.dirname(repoRoot));
+      } catch {
+        // Unresolvable names cannot have been created on disk
+      }
+    });
+  });
+
+  const stamps = await Promise.all(
+    Array.from(directories, async (directory) => {
+      try {
+        const stats = await fs.stat(directory);
+        return `${directory}:${stats.mtimeMs}`;
+      } catch {
+        return `${directory}:missing`;
+      }
+    })
+  );
+  return stamps.join('\n');
+}
+
+/**
+ * Runs discovery and caches the result unless a mutation bumped the generation meanwhile
+ */
+async function discoverAndStore(workdir: string, options: RepositoryCacheOptions): Promise<RepositoriesMap> {
+  const generation = repositoryCacheGeneration;
+  const cachedAt = activeDependencies.now();
+  const data = await activeDependencies.discoverRepositories(workdir, { concurrency: options.discoveryConcurrency });
+  const fingerprint = await activeDependencies.readFingerprint(workdir, data);
+  if (generation === repositoryCacheGeneration) {
+    repositoryCacheSnapshot = data;
+    repositoryCacheEntry = { workdir, generation, fingerprint, cachedAt };
+  }
+  return data;
+}
+
+/**
+ * Returns the cached listing when it was built for this workdir in the current generation, is younger than
+ * the maximum age, and the directories it covers are unchanged on disk
+ */
+async function readFreshSnapshot(workdir: string, maxAgeMs: number): Promise<RepositoriesMap | null> {
+  const snapshot = repositoryCacheSnapshot;
+  const entry = repositoryCacheEntry;
+  if (!snapshot || !entry || entry.workdir !== workdir || entry.generation !== repositoryCacheGeneration) {
+    return null;
+  }
+  if (activeDependencies.now() - entry.cachedAt >= maxAgeMs) {
+    return null;
+  }
+  const fingerprint = await activeDependencies.readFingerprint(workdir, snapshot);
+  return fingerprint === entry.fingerprint && entry.generation === repositoryCacheGeneration ? snapshot : null;
+}
+
+/**
+ * Lists repositories, serving the cached listing while it is fresh. The cache is filled lazily, dropped by
+ * mutations through `refreshRepositoryCache`/`invalidateRepositoryCache`, and rediscovered when the
+ * directories holding clones and worktrees change on disk or the listing outlives the configured maximum age.
+ * @param workdir - Work directory root
+ * @param options - Maximum age and discovery concurrency
+ * @returns Repository data
+ */
+export async function getCachedRepositories(
+  workdir: string,
+  options: RepositoryCacheOptions = {}
+): Promise<RepositoriesMap> {
+  const maxAgeMs = resolveMaxAgeMs(options.maxAgeMs);
+  if (maxAgeMs === 0) {
+    return await activeDependencies.discoverRepositories(workdir, { concurrency: options.discoveryConcurrency });
+  }
+  const fresh = await readFreshSnapshot(workdir, maxAgeMs);
+  if (fresh) {
+    return fresh;
+  }
+  if (pendingLoad && pendingLoad.workdir === workdir && pendingLoad.generation === repositoryCacheGeneration) {
+    return await pendingLoad.promise;
+  }
+
+  const load = {
+    workdir,
+    generation: repositoryCacheGeneration,
+    promise: discoverAndStore(workdir, options),
+  };
+  pendingLoad = load;
+  try {
+    return await load.promise;
+  } finally {
+    if (pendingLoad === load) {
+      pendingLoad = null;
+    }
+  }
 }
 
 /**
  * Refreshes the repository cache by discovering repositories and emitting an update event
  * @param workdir - Work directory root
+ * @param options - Discovery concurrency
  * @returns Updated repository data
  */
-export async function refreshRepositoryCache(workdir: string): Promise<RepositoriesMap> {
+export async function refreshRepositoryCache(
+  workdir: string,
+  options: RepositoryCacheOptions = {}
+): Promise<RepositoriesMap> {
+  // The old snapshot stays visible to event stream subscribers but is no longer served by reads
+  repositoryCacheGeneration += 1;
   try {
-    const data = await activeDependencies.discoverRepositories(workdir);
-    repositoryCacheSnapshot = data;
+    const data = await discoverAndStore(workdir, options);
     activeDependencies.emitReposUpdate(data);
     return data;
   } catch (error) {
     repositoryCacheSnapshot = null;
+    repositoryCacheEntry = null;
     throw error;
   }
 }
@@ -0,0 +1,29 @@
+import { randomUUID } from 'node:crypto';
+import type { ServerResponse } from 'node:http';
+
+export const REQUEST_ID_HEADER = 'X-Request-Id';
+
+const requestIds = new WeakMap<ServerResponse, string>();
+
+/**
+ * Generates a correlation id for a request, exposes it via the X-Request-Id response header,
+ * and remembers it so error responses and log lines can reference it
+ * @param res - Server response object
+ * @param createId - Id generator (defaults to a random UUID)
+ * @returns The assigned request id
+ */
+export function assignRequestId(res: ServerResponse, createId: () => string = randomUUID): string {
+  const requestId = createId();
+  requestIds.set(res, requestId);
+  res.setHeader(REQUEST_ID_HEADER, requestId);
+  return requestId;
+}
+
+/**
+ * Looks up the correlation id assigned to a response
+ * @param res - Server response object
+ * @returns The request id, or null when none was assigned
+ */
+export function getRequestId(res: ServerResponse): string | null {
+  return requestIds.get(res) ?? null;
+}
@@ -0,0 +1,66 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import { describe, it } from 'node:test';
+
+import { assignRequestId } from './request-id.js';
+import { createSlowRequestWatcher, type SlowRequestWatcher } from './slow-requests.js';
+
+async function withServer(
+  watchSlowRequest: SlowRequestWatcher,
+  run: (baseUrl: string) => Promise<void>
+): Promise<void> {
+  let nextId = 0;
+  const server = http.createServer((req, res) => {
+    assignRequestId(res, () => `req-${++nextId}`);
+    watchSlowRequest(req, res);
+    const delayMs = req.url?.startsWith('/api/slow') ? 80 : 0;
+    setTimeout(() => {
+      res.statusCode = 200;
+      res.end('ok');
+    }, delayMs);
+  });
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  try {
+    await run(`http://127.0.0.1:${(server.address() as AddressInfo).port}`);
+  } finally {
+    await new Promise<void>((resolve) => server.close(() => resolve()));
+  }
+}
+
+describe('createSlowRequestWatcher', () => {
+  it('warns with the route, latency and request id when a request exceeds the threshold', async () => {
+    const warnings: string[] = [];
+    const watcher = createSlowRequestWatcher({
+      thresholdMs: 40,
+      logger: { warn: (message: unknown) => warnings.push(String(message)) },
+    });
+
+    await withServer(watcher, async (baseUrl) => {
+      await (await fetch(`${baseUrl}/api/fast`)).text();
+      await (await fetch(`${baseUrl}/api/slow?token=secret`)).text();
+      // The log line is written when the response closes, which can trail the client by a tick
+      await new Promise((resolve) => setTimeout(resolve, 20));
+    });
+
+    assert.equal(warnings.length, 1);
+    assert.match(warnings[0] ?? '', /^\[agentrix\] Slow request GET \/api\/slow took \d+ ms \(status 200, request req-2\)$/);
+    assert.ok(Number(/took (\d+) ms/.exec(warnings[0] ?? '')?.[1]) > 40);
+    assert.doesNotMatch(warnings[0] ?? '', /secret/);
+  });
+
+  it('logs nothing while the threshold is off', async () => {
+    const warnings: string[] = [];
+    const watcher = createSlowRequestWatcher({
+      thresholdMs: 0,
+      logger: { warn: (message: unknown) => warnings.push(String(message)) },
+    });
+
+    await withServer(watcher, async (baseUrl) => {
+      await (await fetch(`${baseUrl}/api/slow`)).text();
+      await new Promise((resolve) => setTimeout(resolve, 20));
+    });
+
+    assert.deepEqual(warnings, []);
+  });
+});
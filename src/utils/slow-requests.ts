@@ -0,0 +1,62 @@
+import type { IncomingMessage, ServerResponse } from 'node:http';
+import { performance } from 'node:perf_hooks';
+import { createLogger } from '../infrastructure/logging/index.js';
+import type { Logger } from '../infrastructure/logging/index.js';
+import { getRequestId } from './request-id.js';
+
+export interface SlowRequestWatcherOptions {
+  /**
+   * Latency in milliseconds above which a finished request is logged; 0 or unset disables the log
+   */
+  thresholdMs?: number | null;
+  /**
+   * Logger to warn on (console by default)
+   */
+  logger?: Pick<Logger, 'warn'>;
+  /**
+   * Clock in milliseconds (defaults to the monotonic performance clock)
+   */
+  now?: () => number;
+}
+
+export type SlowRequestWatcher = (req: IncomingMessage, res: ServerResponse) => void;
+
+function isEventStream(req: IncomingMessage, res: ServerResponse): boolean {
+  const accept = req.headers.accept ?? '';
+  const contentType = String(res.getHeader('content-type') ?? '');
+  return accept.includes('text/event-stream') || contentType.startsWith('text/event-stream');
+}
+
+/**
+ * Creates a watcher that times a request and, once its response closes, warns with the route, latency
+ * and request id when it took longer than the threshold. Off unless a threshold is given, so slow git
+ * operations can be spotted without turning on debug logging. Event streams stay open by design and are
+ * never reported. The query string is left out of the route so tokens in it do not reach the logs.
+ * @param options - Threshold, logger and clock
+ * @returns Function to call with each request and its response, already given a request id
+ */
+export function createSlowRequestWatcher({
+  thresholdMs,
+  logger = createLogger(),
+  now = () => performance.now(),
+}: SlowRequestWatcherOptions = {}): SlowRequestWatcher {
+  const threshold =
+    typeof thresholdMs === 'number' && Number.isFinite(thresholdMs) && thresholdMs > 0 ? thresholdMs : 0;
+  return (req, res) => {
+    if (threshold === 0) {
+      return;
+    }
+    const startedAt = now();
+    res.once('close', () => {
+      const latencyMs = Math.round(now() - startedAt);
+      if (latencyMs <= threshold || isEventStream(req, res)) {
+        return;
+      }
+      const route = new URL(req.url || '/', 'http://localhost').pathname;
+      logger.warn(
+        `[agentrix] Slow request ${req.method ?? 'GET'} ${route} took ${latencyMs} ms ` +
+          `(status ${res.statusCode}, request ${getRequestId(res) ?? 'unknown'})`
+      );
+    });
+  };
+}
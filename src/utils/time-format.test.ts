@@ -0,0 +1,51 @@
+import assert from 'node:assert/strict';
+import { describe, it } from 'node:test';
+
+import { applyTimeFormat, formatTimestamp, parseTimeFormat } from './time-format.js';
+import { ValidationError } from '../infrastructure/errors/index.js';
+
+describe('time format', () => {
+  const commit = {
+    hash: 'abc1234',
+    subject: 'Add login',
+    relativeTime: '2 hours ago',
+    committedAt: '2024-05-01T10:00:00+02:00',
+  };
+
+  it('writes the same commit timestamp as RFC 3339 or epoch seconds', () => {
+    assert.equal(formatTimestamp(commit.committedAt, 'rfc3339'), '2024-05-01T10:00:00+02:00');
+    assert.equal(formatTimestamp(commit.committedAt, 'unix'), 1714550400);
+    assert.equal(formatTimestamp('2024-05-01T08:00:00.750Z', 'unix'), 1714550400);
+  });
+
+  it('rewrites timestamps throughout a response body and nothing else', () => {
+    const body = {
+      data: {
+        fetchedAt: '2024-05-01T08:30:00.000Z',
+        lastCommit: commit,
+        commits: [commit, { ...commit, committedAt: '' }],
+        notes: { createdAt: 'yesterday', date: '2024-05-01T10:00:00+02:00' },
+      },
+    };
+
+    assert.equal(applyTimeFormat(body, 'rfc3339'), body);
+    assert.deepEqual(applyTimeFormat(body, 'unix'), {
+      data: {
+        fetchedAt: 1714552200,
+        lastCommit: { ...commit, committedAt: 1714550400 },
+        commits: [
+          { ...commit, committedAt: 1714550400 },
+          { ...commit, committedAt: '' },
+        ],
+        notes: { createdAt: 'yesterday', date: '2024-05-01T10:00:00+02:00' },
+      },
+    });
+    assert.equal(body.data.lastCommit.committedAt, '2024-05-01T10:00:00+02:00');
+  });
+
+  it('defaults to RFC 3339 and refuses unknown formats', () => {
+    assert.equal(parseTimeFormat(null), 'rfc3339');
+    assert.equal(parseTimeFormat(' UNIX '), 'unix');
+    assert.throws(() => parseTimeFormat('epoch'), ValidationError);
+  });
+});
@@ -0,0 +1,80 @@
+import { ValidationError } from '../infrastructure/errors/index.js';
+
+/**
+ * Query parameter that picks how timestamps are written in a JSON response
+ */
+export const TIME_FORMAT_PARAM = 'timeFormat';
+
+export const TIME_FORMATS = ['rfc3339', 'unix'] as const;
+
+/**
+ * `rfc3339` leaves timestamps as the strings the API always sent; `unix` turns them into whole seconds
+ * since the epoch
+ */
+export type TimeFormat = (typeof TIME_FORMATS)[number];
+
+export const DEFAULT_TIME_FORMAT: TimeFormat = 'rfc3339';
+
+const RFC3339_PATTERN = /^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:\d{2})$/;
+
+/**
+ * Reads the `timeFormat` query parameter
+ * @param value - Raw parameter value
+ * @returns The requested format, or `rfc3339` when none was given
+ * @throws {ValidationError} If the value names an unknown format
+ */
+export function parseTimeFormat(value: string | null): TimeFormat {
+  const trimmed = value?.trim().toLowerCase() || '';
+  if (!trimmed) {
+    return DEFAULT_TIME_FORMAT;
+  }
+  if (!(TIME_FORMATS as readonly string[]).includes(trimmed)) {
+    throw new ValidationError(`${TIME_FORMAT_PARAM} query parameter must be one of ${TIME_FORMATS.join(', ')}`);
+  }
+  return trimmed as TimeFormat;
+}
+
+/**
+ * Writes one RFC 3339 timestamp in the requested format
+ * @param value - Timestamp such as `2024-05-01T10:00:00+02:00`
+ * @param format - Output format
+ * @returns The timestamp unchanged for `rfc3339`, or epoch seconds for `unix`
+ */
+export function formatTimestamp(value: string, format: TimeFormat): string | number {
+  if (format === 'rfc3339') {
+    return value;
+  }
+  return Math.floor(Date.parse(value) / 1000);
+}
+
+/**
+ * Rewrites the timestamps in a response body. Timestamps are recognised as RFC 3339 strings under keys
+ * ending in `At` (`committedAt`, `fetchedAt`, …), the naming every endpoint uses for them, and as Dates.
+ * @param value - Response body
+ * @param format - Output format
+ * @returns A copy with its timestamps rewritten, or the body itself for `rfc3339`
+ */
+export function applyTimeFormat(value: unknown, format: TimeFormat): unknown {
+  if (format === 'rfc3339') {
+    return value;
+  }
+  if (Array.isArray(value)) {
+    return value.map((entry) => applyTimeFormat(entry, format));
+  }
+  if (value instanceof Date) {
+    return formatTimestamp(value.toISOString(), format);
+  }
+  // Only plain objects are walked; anything else is left for JSON.stringify to serialise as it would
+  const prototype = value && typeof value === 'object' ? Object.getPrototypeOf(value) : undefined;
+  if (prototype !== Object.prototype && prototype !== null) {
+    return value;
+  }
+  const rewritten: Record<string, unknown> = {};
+  for (const [key, entry] of Object.entries(value as Record<string, unknown>)) {
+    rewritten[key] =
+      key.endsWith('At') && typeof entry === 'string' && RFC3339_PATTERN.test(entry)
+        ? formatTimestamp(entry, format)
+        : applyTimeFormat(entry, format);
+  }
+  return rewritten;
+}
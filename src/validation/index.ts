@@ -12,14 +12,78 @@ export {
   extractWorktreeParams,
 } from './request-validator.js';
 
-export { validateRepositoryCreate, validateRepositoryDelete, validateInitCommandUpdate } from './schemas/repository-schema.js';
-export type { RepositoryCreateInput, RepositoryDeleteInput, InitCommandUpdateInput } from './schemas/repository-schema.js';
+export {
+  validateRepositoryCreate,
+  validateRepositoryDelete,
+  validateRepositoryMove,
+  validateInitCommandUpdate,
+  validateRepositoryMetadataUpdate,
+  validateRepositoryBranchSwitch,
+  validateRepositoryRemoteAdd,
+  validateCloneCancel,
+} from './schemas/repository-schema.js';
+export type {
+  RepositoryCreateInput,
+  RepositoryDeleteInput,
+  RepositoryMoveInput,
+  InitCommandUpdateInput,
+  RepositoryMetadataUpdateInput,
+  RepositoryBranchSwitchInput,
+  RepositoryRemoteAddInput,
+  CloneCancelInput,
+} from './schemas/repository-schema.js';
 
-export { validateWorktreeCreate, validateWorktreeDelete } from './schemas/worktree-schema.js';
-export type { WorktreeCreateInput, WorktreeDeleteInput } from './schemas/worktree-schema.js';
+export {
+  validateWorktreeCreate,
+  validateWorktreeBatchCreate,
+  validateWorktreeDelete,
+  validateWorktreeRename,
+  validateWorktreeCommit,
+  validateWorktreePush,
+  validateWorktreePullRequestDraft,
+  validateWorktreeSetup,
+  validateWorktreeReset,
+  validateWorktreeReplace,
+  validateWorktreeCherryPick,
+  validateWorktreeCherryPickAbort,
+  validateWorktreeStash,
+  validateWorktreeUpstream,
+} from './schemas/worktree-schema.js';
+export type {
+  WorktreeCreateInput,
+  WorktreeIfExists,
+  WorktreeBatchCreateInput,
+  WorktreeDeleteInput,
+  WorktreeRenameInput,
+  WorktreeCommitInput,
+  WorktreePushInput,
+  WorktreePullRequestDraftInput,
+  WorktreeSetupInput,
+  WorktreeResetInput,
+  WorktreeReplaceInput,
+  WorktreeCherryPickInput,
+  WorktreeCherryPickAbortInput,
+  WorktreeStashAction,
+  WorktreeStashInput,
+  WorktreeUpstreamInput,
+} from './schemas/worktree-schema.js';
 
-export { validateTerminalOpen, validateTerminalSend, validateTerminalClose } from './schemas/terminal-schema.js';
-export type { TerminalOpenInput, TerminalSendInput, TerminalCloseInput } from './schemas/terminal-schema.js';
+export {
+  validateTerminalOpen,
+  validateTerminalSend,
+  validateTerminalClose,
+  validateTerminalHistory,
+  validateTerminalSignal,
+  TERMINAL_SIGNAL_NAMES,
+} from './schemas/terminal-schema.js';
+export type {
+  TerminalOpenInput,
+  TerminalSendInput,
+  TerminalCloseInput,
+  TerminalHistoryInput,
+  TerminalSignalInput,
+  TerminalSignalName,
+} from './schemas/terminal-schema.js';
 
 export {
   validateCodexSessionList,
@@ -47,4 +111,12 @@ export type {
   PlanDeleteInput,
 } from './schemas/plan-mode-schema.js';
 
+export { validateSessionManifest, SESSION_MANIFEST_VERSION } from './schemas/manifest-schema.js';
+export type {
+  SessionManifestInput,
+  ManifestRepositoryInput,
+  ManifestWorktreeInput,
+  ManifestPlanInput,
+} from './schemas/manifest-schema.js';
+
 export { ValidationError } from '../infrastructure/errors/index.js';
@@ -1,4 +1,5 @@
 import { validateRequired, requireNonEmpty } from '../request-validator.js';
+import { validateSessionId } from '../../domain/index.js';
 
 export interface CodexSessionWorktreeInput {
   org: string;
@@ -34,5 +35,5 @@ export function validateCodexSessionCreate(payload: unknown): CodexSessionCreate
 
 export function validateCodexSessionId(payload: unknown): CodexSessionIdInput {
   const { sessionId } = validateRequired(payload, ['sessionId'] as const);
-  return { sessionId: requireNonEmpty(sessionId, 'sessionId') };
+  return { sessionId: validateSessionId(requireNonEmpty(sessionId, 'sessionId')) };
 }
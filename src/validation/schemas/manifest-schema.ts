@@ -0,0 +1,192 @@
+import { ValidationError } from '../../infrastructure/errors/index.js';
+import { validateRequired } from '../request-validator.js';
+import type { ImportPlanInput, PlanSource, PlanStatus } from '../../core/plan-mode-store.js';
+
+/**
+ * Version written into exported manifests and the only one accepted on import
+ */
+export const SESSION_MANIFEST_VERSION = 1;
+
+export interface ManifestWorktreeInput {
+  branch: string;
+  /**
+   * Commit the worktree had checked out when it was exported
+   */
+  headSha: string | null;
+  /**
+   * Where the branch left the default branch (their merge base)
+   */
+  baseSha: string | null;
+}
+
+export type ManifestPlanInput = ImportPlanInput;
+
+export interface ManifestRepositoryInput {
+  org: string;
+  repo: string;
+  /**
+   * Origin remote the repository is cloned from; null when it has none
+   */
+  url: string | null;
+  initCommand: string;
+  defaultBranch: string | null;
+  worktrees: ManifestWorktreeInput[];
+  plans: ManifestPlanInput[];
+}
+
+export interface SessionManifestInput {
+  version: typeof SESSION_MANIFEST_VERSION;
+  repositories: ManifestRepositoryInput[];
+}
+
+const PLAN_STATUSES: readonly PlanStatus[] = ['draft', 'updated', 'ready', 'building'];
+const COMMIT_SHA_PATTERN = /^[0-9a-f]{7,64}$/i;
+
+function readRecord(value: unknown, label: string): Record<string, unknown> {
+  if (!value || typeof value !== 'object' || Array.isArray(value)) {
+    throw new ValidationError(`${label} must be an object`);
+  }
+  return value as Record<string, unknown>;
+}
+
+function readList(value: unknown, label: string): unknown[] {
+  if (value === undefined || value === null) {
+    return [];
+  }
+  if (!Array.isArray(value)) {
+    throw new ValidationError(`${label} must be an array`);
+  }
+  return value;
+}
+
+function readOptionalString(value: unknown, label: string): string | null {
+  if (value === undefined || value === null) {
+    return null;
+  }
+  if (typeof value !== 'string') {
+    throw new ValidationError(`${label} must be a string`);
+  }
+  return value.trim() || null;
+}
+
+function readSha(value: unknown, label: string): string | null {
+  const sha = readOptionalString(value, label);
+  if (sha !== null && !COMMIT_SHA_PATTERN.test(sha)) {
+    throw new ValidationError(`${label} must be a commit SHA`);
+  }
+  return sha;
+}
+
+function readTimestamp(value: unknown, label: string, fallback: string): string {
+  const timestamp = readOptionalString(value, label);
+  if (timestamp === null) {
+    return fallback;
+  }
+  if (Number.isNaN(Date.parse(timestamp))) {
+    throw new ValidationError(`${label} must be an ISO timestamp`);
+  }
+  return timestamp;
+}
+
+function readPlanSource(value: unknown, label: string): PlanSource {
+  if (value === undefined || value === null) {
+    return { type: 'manual' };
+  }
+  const source = readRecord(value, label);
+  if (source['type'] === 'manual') {
+    return { type: 'manual' };
+  }
+  if (source['type'] !== 'issue') {
+    throw new ValidationError(`${label}.type must be manual or issue`);
+  }
+  const issueNumber = source['issueNumber'];
+  if (issueNumber !== undefined && (typeof issueNumber !== 'number' || !Number.isInteger(issueNumber))) {
+    throw new ValidationError(`${label}.issueNumber must be an integer`);
+  }
+  const issueUrl = readOptionalString(source['issueUrl'], `${label}.issueUrl`);
+  return {
+    type: 'issue',
+    ...(typeof issueNumber === 'number' ? { issueNumber } : {}),
+    ...(issueUrl ? { issueUrl } : {}),
+  };
+}
+
+function readWorktree(value: unknown, label: string): ManifestWorktreeInput {
+  const { branch } = validateRequired(readRecord(value, label), ['branch'] as const);
+  const worktree = value as Record<string, unknown>;
+  return {
+    branch,
+    headSha: readSha(worktree['headSha'], `${label}.headSha`),
+    baseSha: readSha(worktree['baseSha'], `${label}.baseSha`),
+  };
+}
+
+function readPlan(value: unknown, label: string, now: string): ManifestPlanInput {
+  const { id, title } = validateRequired(readRecord(value, label), ['id', 'title'] as const);
+  const plan = value as Record<string, unknown>;
+  const markdown = plan['markdown'];
+  if (typeof markdown !== 'string') {
+    throw new ValidationError(`${label}.markdown must be a string`);
+  }
+  const status = plan['status'] ?? 'draft';
+  if (typeof status !== 'string' || !PLAN_STATUSES.includes(status as PlanStatus)) {
+    throw new ValidationError(`${label}.status must be one of ${PLAN_STATUSES.join(', ')}`);
+  }
+  const createdAt = readTimestamp(plan['createdAt'], `${label}.createdAt`, now);
+  return {
+    id,
+    title,
+    markdown,
+    status: status as PlanStatus,
+    source: readPlanSource(plan['source'], `${label}.source`),
+    createdAt,
+    updatedAt: readTimestamp(plan['updatedAt'], `${label}.updatedAt`, createdAt),
+    defaultBranch: readOptionalString(plan['defaultBranch'], `${label}.defaultBranch`),
+    worktreeBranch: readOptionalString(plan['worktreeBranch'], `${label}.worktreeBranch`),
+  };
+}
+
+function readRepository(value: unknown, label: string, now: string): ManifestRepositoryInput {
+  const { org, repo } = validateRequired(readRecord(value, label), ['org', 'repo'] as const);
+  const repository = value as Record<string, unknown>;
+  return {
+    org,
+    repo,
+    url: readOptionalString(repository['url'], `${label}.url`),
+    initCommand: readOptionalString(repository['initCommand'], `${label}.initCommand`) ?? '',
+    defaultBranch: readOptionalString(repository['defaultBranch'], `${label}.defaultBranch`),
+    worktrees: readList(repository['worktrees'], `${label}.worktrees`).map((worktree, index) =>
+      readWorktree(worktree, `${label}.worktrees[${index}]`)
+    ),
+    plans: readList(repository['plans'], `${label}.plans`).map((plan, index) =>
+      readPlan(plan, `${label}.plans[${index}]`, now)
+    ),
+  };
+}
+
+/**
+ * Validates a manifest posted for import, as produced by `GET /api/sessions/manifest`
+ */
+export function validateSessionManifest(payload: unknown): SessionManifestInput {
+  const manifest = readRecord(payload, 'manifest');
+  if (manifest['version'] !== SESSION_MANIFEST_VERSION) {
+    throw new ValidationError(`manifest version must be ${SESSION_MANIFEST_VERSION}`);
+  }
+  const entries = manifest['repositories'];
+  if (!Array.isArray(entries)) {
+    throw new ValidationError('repositories must be an array');
+  }
+  const now = new Date().toISOString();
+  const repositories = entries.map((repository: unknown, index: number) =>
+    readRepository(repository, `repositories[${index}]`, now)
+  );
+  const seen = new Set<string>();
+  for (const { org, repo } of repositories) {
+    const key = `${org}/${repo}`;
+    if (seen.has(key)) {
+      throw new ValidationError(`repositories lists ${key} more than once`);
+    }
+    seen.add(key);
+  }
+  return { version: SESSION_MANIFEST_VERSION, repositories };
+}
@@ -1,3 +1,15 @@
+import { ValidationError } from '../../infrastructure/errors/index.js';
+import {
+  CLONE_FILTERS,
+  isCloneFilter,
+  isValidSessionId,
+  MAX_SESSION_ID_LENGTH,
+  validateRemoteName,
+  validateRepositoryMetadata,
+  validateRepositorySegment,
+  type CloneFilter,
+  type RepositoryMetadata,
+} from '../../domain/index.js';
 import {
   validateRequired,
   validateOptional,
@@ -7,6 +19,18 @@ import {
 export interface RepositoryCreateInput {
   url: string;
   initCommand: string;
+  /**
+   * Client-chosen id for cancelling the clone while it runs; generated when omitted
+   */
+  cloneId?: string;
+  /**
+   * Partial clone filter, passed to `git clone --filter`
+   */
+  filter?: CloneFilter;
+}
+
+export interface CloneCancelInput {
+  cloneId: string;
 }
 
 export interface RepositoryDeleteInput {
@@ -14,6 +38,32 @@ export interface RepositoryDeleteInput {
   repo: string;
 }
 
+export interface RepositoryMoveInput {
+  org: string;
+  repo: string;
+  newOrg: string;
+  newRepo: string;
+}
+
+export interface RepositoryBranchSwitchInput {
+  org: string;
+  repo: string;
+  branch: string;
+}
+
+export interface RepositoryRemoteAddInput {
+  org: string;
+  repo: string;
+  name: string;
+  url: string;
+}
+
+export interface RepositoryMetadataUpdateInput {
+  org: string;
+  repo: string;
+  metadata: RepositoryMetadata;
+}
+
 export interface InitCommandUpdateInput {
   org: string;
   repo: string;
@@ -31,8 +81,37 @@ export function validateRepositoryCreate(payload: unknown): RepositoryCreateInpu
   const data = payload as Record<string, unknown>;
   const url = requireNonEmpty(data['url'] || data['repoUrl'], 'Repository URL');
   const { initCommand } = validateOptional(data, { initCommand: '' });
+  const cloneId = data['cloneId'] === undefined || data['cloneId'] === null
+    ? undefined
+    : validateCloneId(data['cloneId']);
+  const filter = data['filter'] === undefined || data['filter'] === null ? undefined : validateCloneFilter(data['filter']);
+
+  return { url, initCommand, ...(cloneId ? { cloneId } : {}), ...(filter ? { filter } : {}) };
+}
 
-  return { url, initCommand };
+function validateCloneFilter(value: unknown): CloneFilter {
+  if (!isCloneFilter(value)) {
+    throw new ValidationError(`filter must be one of ${CLONE_FILTERS.join(', ')}`);
+  }
+  return value;
+}
+
+function validateCloneId(value: unknown): string {
+  const cloneId = typeof value === 'string' ? value.trim() : '';
+  if (!isValidSessionId(cloneId)) {
+    throw new ValidationError(
+      `cloneId must be up to ${MAX_SESSION_ID_LENGTH} letters, digits, "-" or "_", starting with a letter or digit`
+    );
+  }
+  return cloneId;
+}
+
+/**
+ * Validates a clone cancellation request
+ */
+export function validateCloneCancel(payload: unknown): CloneCancelInput {
+  const { cloneId } = validateRequired(payload, ['cloneId'] as const);
+  return { cloneId: validateCloneId(cloneId) };
 }
 
 /**
@@ -42,6 +121,19 @@ export function validateRepositoryDelete(payload: unknown): RepositoryDeleteInpu
   return validateRequired(payload, ['org', 'repo'] as const);
 }
 
+/**
+ * Validates a repository move request; every segment must name a single directory under the workdir
+ */
+export function validateRepositoryMove(payload: unknown): RepositoryMoveInput {
+  const { org, repo, newOrg, newRepo } = validateRequired(payload, ['org', 'repo', 'newOrg', 'newRepo'] as const);
+  return {
+    org: validateRepositorySegment(org, 'organization'),
+    repo: validateRepositorySegment(repo, 'repository'),
+    newOrg: validateRepositorySegment(newOrg, 'new organization'),
+    newRepo: validateRepositorySegment(newRepo, 'new repository'),
+  };
+}
+
 /**
  * Validates an init command update request
  */
@@ -54,3 +146,32 @@ export function validateInitCommandUpdate(payload: unknown): InitCommandUpdateIn
 
   return { org, repo, initCommand };
 }
+
+/**
+ * Validates a repository metadata replacement request
+ */
+export function validateRepositoryMetadataUpdate(payload: unknown): RepositoryMetadataUpdateInput {
+  const { org, repo } = validateRequired(payload, ['org', 'repo'] as const);
+  const data = payload as Record<string, unknown>;
+
+  return {
+    org: validateRepositorySegment(org, 'organization'),
+    repo: validateRepositorySegment(repo, 'repository'),
+    metadata: validateRepositoryMetadata(data['metadata']),
+  };
+}
+
+/**
+ * Validates a primary clone branch switch request
+ */
+export function validateRepositoryBranchSwitch(payload: unknown): RepositoryBranchSwitchInput {
+  return validateRequired(payload, ['org', 'repo', 'branch'] as const);
+}
+
+/**
+ * Validates a request to add a remote to a repository; the URL's scheme is checked when it is added
+ */
+export function validateRepositoryRemoteAdd(payload: unknown): RepositoryRemoteAddInput {
+  const { org, repo, name, url } = validateRequired(payload, ['org', 'repo', 'name', 'url'] as const);
+  return { org, repo, name: validateRemoteName(name), url };
+}
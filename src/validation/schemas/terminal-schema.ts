@@ -1,5 +1,6 @@
 import { validateRequired, requireNonEmpty } from '../request-validator.js';
 import { ValidationError } from '../../infrastructure/errors/index.js';
+import { validateSessionId } from '../../domain/index.js';
 
 export interface TerminalOpenInput {
   /** GitHub organisation that owns the repository */
@@ -20,6 +21,10 @@ export interface TerminalOpenInput {
   newSession?: boolean;
   /** Session tool being requested ('terminal' for interactive, 'agent' for automation) */
   sessionTool?: 'terminal' | 'agent';
+  /** Marks the terminal as dangerous, e.g. an agent running without its approval prompts */
+  dangerous?: boolean;
+  /** Confirms a dangerous terminal when the server requires it */
+  confirmDangerous?: boolean;
 }
 
 export interface TerminalSendInput {
@@ -31,6 +36,24 @@ export interface TerminalCloseInput {
   sessionId: string;
 }
 
+/**
+ * Signals a client may send to a terminal's foreground process
+ */
+export const TERMINAL_SIGNAL_NAMES = ['int', 'term', 'kill'] as const;
+
+export type TerminalSignalName = (typeof TERMINAL_SIGNAL_NAMES)[number];
+
+export interface TerminalSignalInput {
+  sessionId: string;
+  signal: TerminalSignalName;
+}
+
+export interface TerminalHistoryInput {
+  sessionId: string;
+  /** Number of trailing lines to return; the whole buffer when omitted */
+  lines?: number;
+}
+
 /**
  * Validates a terminal open request
  */
@@ -50,7 +73,7 @@ export function validateTerminalOpen(payload: unknown): TerminalOpenInput {
   const hasPrompt = Object.prototype.hasOwnProperty.call(data, 'prompt');
   const prompt = hasPrompt ? data['prompt'] : undefined;
   const sessionIdValue = typeof data['sessionId'] === 'string' ? data['sessionId'].trim() : '';
-  const sessionId = sessionIdValue ? sessionIdValue : undefined;
+  const sessionId = sessionIdValue ? validateSessionId(sessionIdValue) : undefined;
   const newSession = typeof data['newSession'] === 'boolean' ? data['newSession'] : false;
   const rawSessionTool = typeof data['sessionTool'] === 'string' ? data['sessionTool'].trim().toLowerCase() : '';
   let sessionTool: 'terminal' | 'agent' | undefined;
@@ -81,6 +104,12 @@ export function validateTerminalOpen(payload: unknown): TerminalOpenInput {
     throw new ValidationError('newSession cannot be combined with prompt');
   }
 
+  for (const flag of ['dangerous', 'confirmDangerous'] as const) {
+    if (data[flag] !== undefined && typeof data[flag] !== 'boolean') {
+      throw new ValidationError(`${flag} must be a boolean when provided`);
+    }
+  }
+
   return {
     org,
     repo,
@@ -91,6 +120,8 @@ export function validateTerminalOpen(payload: unknown): TerminalOpenInput {
     sessionId,
     newSession,
     sessionTool,
+    ...(data['dangerous'] === true ? { dangerous: true } : {}),
+    ...(data['confirmDangerous'] === true ? { confirmDangerous: true } : {}),
   };
 }
 
@@ -103,7 +134,7 @@ export function validateTerminalSend(payload: unknown): TerminalSendInput {
   }
 
   const data = payload as Record<string, unknown>;
-  const sessionId = requireNonEmpty(data['sessionId'], 'sessionId');
+  const sessionId = validateSessionId(requireNonEmpty(data['sessionId'], 'sessionId'));
   const input = typeof data['input'] === 'string' ? data['input'] : '';
 
   return { sessionId, input };
@@ -117,6 +148,38 @@ export function validateTerminalClose(payload: unknown): TerminalCloseInput {
     throw new ValidationError('Invalid request payload');
   }
   const data = payload as Record<string, unknown>;
-  const sessionId = requireNonEmpty(data['sessionId'], 'sessionId');
+  const sessionId = validateSessionId(requireNonEmpty(data['sessionId'], 'sessionId'));
   return { sessionId };
 }
+
+/**
+ * Validates a request to signal a terminal's foreground process
+ */
+export function validateTerminalSignal(payload: unknown): TerminalSignalInput {
+  if (!payload || typeof payload !== 'object') {
+    throw new ValidationError('Invalid request payload');
+  }
+  const data = payload as Record<string, unknown>;
+  const sessionId = validateSessionId(requireNonEmpty(data['sessionId'], 'sessionId'));
+  const signal = requireNonEmpty(data['signal'], 'signal').toLowerCase();
+  if (!(TERMINAL_SIGNAL_NAMES as readonly string[]).includes(signal)) {
+    throw new ValidationError(`signal must be one of ${TERMINAL_SIGNAL_NAMES.join(', ')}`);
+  }
+  return { sessionId, signal: signal as TerminalSignalName };
+}
+
+/**
+ * Validates a terminal history request from its query string
+ */
+export function validateTerminalHistory(searchParams: URLSearchParams): TerminalHistoryInput {
+  const sessionId = validateSessionId(requireNonEmpty(searchParams.get('sessionId'), 'sessionId'));
+  const linesParam = searchParams.get('lines')?.trim() || '';
+  if (!linesParam) {
+    return { sessionId };
+  }
+  const lines = Number(linesParam);
+  if (!Number.isInteger(lines) || lines <= 0) {
+    throw new ValidationError('lines query parameter must be a positive integer');
+  }
+  return { sessionId, lines };
+}
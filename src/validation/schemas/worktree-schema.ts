@@ -1,4 +1,7 @@
+import path from 'node:path';
+import { ValidationError } from '../../infrastructure/errors/index.js';
 import { validateRequired } from '../request-validator.js';
+import { findBranchNameProblems, validateRemoteName } from '../../domain/index.js';
 
 export interface WorktreeCreateInput {
   org: string;
@@ -6,6 +9,36 @@ export interface WorktreeCreateInput {
   branch: string;
   prompt: string;
   hasPrompt: boolean;
+  dryRun: boolean;
+  /**
+   * Repository-relative directories to limit the checkout to; empty for a full checkout
+   */
+  sparsePaths?: string[];
+  /**
+   * Ref a new branch starts from; the server's default base applies when omitted
+   */
+  base?: string;
+  /**
+   * What to do when the branch already has a worktree or its directory exists; defaults to `error`
+   */
+  ifExists?: WorktreeIfExists;
+}
+
+export type WorktreeIfExists = 'error' | 'reuse' | 'recreate';
+
+const WORKTREE_IF_EXISTS_MODES: readonly WorktreeIfExists[] = ['error', 'reuse', 'recreate'];
+
+export interface WorktreeBatchCreateInput {
+  org: string;
+  repo: string;
+  /**
+   * Branches to create worktrees for, in request order
+   */
+  branches: string[];
+  /**
+   * Ref new branches start from; the server's default base applies when omitted
+   */
+  base?: string;
 }
 
 export interface WorktreeDeleteInput {
@@ -14,6 +47,151 @@ export interface WorktreeDeleteInput {
   branch: string;
 }
 
+export interface WorktreeReplaceInput {
+  org: string;
+  repo: string;
+  branch: string;
+  /**
+   * Ref the recreated branch starts from; the server's default base applies when omitted
+   */
+  base?: string;
+}
+
+export interface WorktreeRenameInput {
+  org: string;
+  repo: string;
+  branch: string;
+  newBranch: string;
+}
+
+export interface WorktreeCommitInput {
+  org: string;
+  repo: string;
+  branch: string;
+  message: string;
+  paths: string[];
+  /**
+   * Sign the commit; the server may require signing regardless
+   */
+  sign: boolean;
+}
+
+export interface WorktreePushInput {
+  org: string;
+  repo: string;
+  branch: string;
+  forceWithLease: boolean;
+  /**
+   * Remote to push to; origin when omitted
+   */
+  remote?: string;
+}
+
+export interface WorktreePullRequestDraftInput {
+  org: string;
+  repo: string;
+  branch: string;
+  /**
+   * Pull request title; also the message of the empty commit a new branch gets
+   */
+  title: string;
+  body: string;
+  /**
+   * Branch the pull request targets; the repository's default branch when omitted
+   */
+  base?: string;
+}
+
+export interface WorktreeSetupInput {
+  org: string;
+  repo: string;
+  branch: string;
+}
+
+export interface WorktreeResetInput {
+  org: string;
+  repo: string;
+  branch: string;
+  /**
+   * Ref to reset to; the branch's upstream when omitted
+   */
+  ref?: string;
+}
+
+export interface WorktreeCherryPickInput {
+  org: string;
+  repo: string;
+  branch: string;
+  /**
+   * Commit SHAs to apply to the branch, in order
+   */
+  commits: string[];
+}
+
+export interface WorktreeCherryPickAbortInput {
+  org: string;
+  repo: string;
+  branch: string;
+}
+
+export type WorktreeStashAction = 'push' | 'pop' | 'list';
+
+export interface WorktreeStashInput {
+  org: string;
+  repo: string;
+  branch: string;
+  action: WorktreeStashAction;
+  /**
+   * Message for a pushed stash
+   */
+  message?: string;
+  /**
+   * Stashes untracked files too when pushing
+   */
+  includeUntracked?: boolean;
+}
+
+export interface WorktreeUpstreamInput {
+  org: string;
+  repo: string;
+  branch: string;
+  remote: string;
+  /**
+   * Branch on the remote to track; the worktree's branch name when omitted
+   */
+  remoteBranch?: string;
+}
+
+/**
+ * Validates sparse-checkout paths, normalising them to POSIX form and rejecting any that leave the repository
+ */
+function validateSparsePaths(value: unknown): string[] {
+  if (value === undefined || value === null) {
+    return [];
+  }
+  if (!Array.isArray(value)) {
+    throw new ValidationError('sparsePaths must be an array of strings');
+  }
+  const paths = value.map((entry: unknown) => {
+    if (typeof entry !== 'string' || !entry.trim()) {
+      throw new ValidationError('sparsePaths must be an array of non-empty strings');
+    }
+    const trimmed = entry.trim().replace(/\\/g, '/');
+    if (path.posix.isAbsolute(trimmed) || path.win32.isAbsolute(trimmed)) {
+      throw new ValidationError(`sparsePaths entry ${entry} must be relative to the repository`);
+    }
+    const normalised = path.posix.normalize(trimmed).replace(/\/+$/, '');
+    if (normalised === '.' || normalised === '..' || normalised.startsWith('../')) {
+      throw new ValidationError(`sparsePaths entry ${entry} must stay within the repository`);
+    }
+    if (normalised.split('/')[0] === '.git') {
+      throw new ValidationError(`sparsePaths entry ${entry} must not point into git metadata`);
+    }
+    return normalised;
+  });
+  return Array.from(new Set(paths));
+}
+
 /**
  * Validates a worktree creation request
  */
@@ -28,6 +206,17 @@ export function validateWorktreeCreate(payload: unknown): WorktreeCreateInput {
   const branchInput = typeof data['branch'] === 'string' ? data['branch'].trim() : '';
   const rawPrompt = typeof data['prompt'] === 'string' ? data['prompt'] : '';
   const prompt = rawPrompt.trim();
+  const base = typeof data['base'] === 'string' ? data['base'].trim() : '';
+  if (base.startsWith('-')) {
+    throw new ValidationError('base must be a branch or ref name');
+  }
+  const ifExists = data['ifExists'] ?? 'error';
+  if (!WORKTREE_IF_EXISTS_MODES.includes(ifExists as WorktreeIfExists)) {
+    throw new ValidationError(`ifExists must be one of ${WORKTREE_IF_EXISTS_MODES.join(', ')}`);
+  }
+  if (ifExists !== 'error' && !branchInput) {
+    throw new ValidationError('ifExists requires a branch');
+  }
 
   return {
     org,
@@ -35,9 +224,47 @@ export function validateWorktreeCreate(payload: unknown): WorktreeCreateInput {
     branch: branchInput,
     prompt,
     hasPrompt: Boolean(prompt),
+    dryRun: data['dryRun'] === true,
+    sparsePaths: validateSparsePaths(data['sparsePaths']),
+    ...(base ? { base } : {}),
+    ...(ifExists !== 'error' ? { ifExists: ifExists as WorktreeIfExists } : {}),
   };
 }
 
+/**
+ * Largest number of branches a single batch request may create
+ */
+export const MAX_WORKTREE_BATCH_SIZE = 20;
+
+/**
+ * Validates a batch worktree creation request
+ */
+export function validateWorktreeBatchCreate(payload: unknown): WorktreeBatchCreateInput {
+  const { org, repo } = validateRequired(payload, ['org', 'repo'] as const);
+  const data = payload as Record<string, unknown>;
+
+  const rawBranches = data['branches'];
+  if (!Array.isArray(rawBranches) || rawBranches.length === 0) {
+    throw new ValidationError('branches must be a non-empty array of branch names');
+  }
+  if (rawBranches.length > MAX_WORKTREE_BATCH_SIZE) {
+    throw new ValidationError(`branches cannot list more than ${MAX_WORKTREE_BATCH_SIZE} branches`);
+  }
+  const branches = rawBranches.map((entry: unknown) => {
+    if (typeof entry !== 'string' || !entry.trim()) {
+      throw new ValidationError('branches must be an array of non-empty strings');
+    }
+    return entry.trim();
+  });
+
+  const base = typeof data['base'] === 'string' ? data['base'].trim() : '';
+  if (base.startsWith('-')) {
+    throw new ValidationError('base must be a branch or ref name');
+  }
+
+  return { org, repo, branches, ...(base ? { base } : {}) };
+}
+
 /**
  * Validates a worktree deletion request
  */
@@ -45,3 +272,213 @@ export function validateWorktreeDelete(payload: unknown): WorktreeDeleteInput {
   const { org, repo, branch } = validateRequired(payload, ['org', 'repo', 'branch'] as const);
   return { org, repo, branch };
 }
+
+/**
+ * Validates a worktree rename request. The new name reaches `git branch -m`, so it is held to git's branch
+ * name rules here; a leading `-` would otherwise be read as an option.
+ */
+export function validateWorktreeRename(payload: unknown): WorktreeRenameInput {
+  const { org, repo, branch, newBranch } = validateRequired(
+    payload,
+    ['org', 'repo', 'branch', 'newBranch'] as const
+  );
+  const [problem] = findBranchNameProblems(newBranch);
+  if (problem) {
+    throw new ValidationError(`newBranch is invalid: ${problem.message}`);
+  }
+  return { org, repo, branch, newBranch };
+}
+
+/**
+ * Validates a worktree commit request
+ */
+export function validateWorktreeCommit(payload: unknown): WorktreeCommitInput {
+  const { org, repo, branch, message } = validateRequired(
+    payload,
+    ['org', 'repo', 'branch', 'message'] as const
+  );
+
+  const rawPaths: unknown = (payload as Record<string, unknown>)['paths'] ?? [];
+  if (!Array.isArray(rawPaths)) {
+    throw new ValidationError('paths must be an array of strings');
+  }
+  const paths = rawPaths.map((entry: unknown) => {
+    if (typeof entry !== 'string' || !entry.trim()) {
+      throw new ValidationError('paths must be an array of non-empty strings');
+    }
+    return entry.trim();
+  });
+
+  return { org, repo, branch, message, paths, sign: (payload as Record<string, unknown>)['sign'] === true };
+}
+
+/**
+ * Validates a worktree push request
+ */
+export function validateWorktreePush(payload: unknown): WorktreePushInput {
+  const { org, repo, branch } = validateRequired(payload, ['org', 'repo', 'branch'] as const);
+  const body = payload as Record<string, unknown>;
+  const remote = body['remote'] === undefined || body['remote'] === null ? undefined : validateRemoteName(body['remote']);
+  return {
+    org,
+    repo,
+    branch,
+    forceWithLease: body['forceWithLease'] === true,
+    ...(remote ? { remote } : {}),
+  };
+}
+
+/**
+ * Validates a request to create a worktree and open a draft pull request for it
+ */
+export function validateWorktreePullRequestDraft(payload: unknown): WorktreePullRequestDraftInput {
+  const { org, repo, branch } = validateRequired(payload, ['org', 'repo', 'branch'] as const);
+  const body = payload as Record<string, unknown>;
+  for (const field of ['title', 'body', 'base'] as const) {
+    if (body[field] !== undefined && body[field] !== null && typeof body[field] !== 'string') {
+      throw new ValidationError(`${field} must be a string`);
+    }
+  }
+  const title = typeof body['title'] === 'string' ? body['title'].trim() : '';
+  const base = typeof body['base'] === 'string' ? body['base'].trim() : '';
+  return {
+    org,
+    repo,
+    branch,
+    title: title || branch,
+    body: typeof body['body'] === 'string' ? body['body'] : '',
+    ...(base ? { base } : {}),
+  };
+}
+
+/**
+ * Largest number of commits a single cherry-pick request may apply
+ */
+export const MAX_CHERRY_PICK_COMMITS = 100;
+
+/**
+ * Validates a cherry-pick request
+ */
+export function validateWorktreeCherryPick(payload: unknown): WorktreeCherryPickInput {
+  const { org, repo, branch } = validateRequired(payload, ['org', 'repo', 'branch'] as const);
+  const rawCommits = (payload as Record<string, unknown>)['commits'];
+  if (!Array.isArray(rawCommits) || rawCommits.length === 0) {
+    throw new ValidationError('commits must be a non-empty array of commit SHAs');
+  }
+  if (rawCommits.length > MAX_CHERRY_PICK_COMMITS) {
+    throw new ValidationError(`commits cannot list more than ${MAX_CHERRY_PICK_COMMITS} commits`);
+  }
+  const commits = rawCommits.map((entry: unknown) => {
+    const value = typeof entry === 'string' ? entry.trim() : '';
+    if (!/^[0-9a-f]{4,64}$/i.test(value)) {
+      throw new ValidationError('commits must be an array of commit SHAs');
+    }
+    return value;
+  });
+  return { org, repo, branch, commits };
+}
+
+/**
+ * Validates a request to abort a stopped cherry-pick
+ */
+export function validateWorktreeCherryPickAbort(payload: unknown): WorktreeCherryPickAbortInput {
+  return validateRequired(payload, ['org', 'repo', 'branch'] as const);
+}
+
+const STASH_ACTIONS: readonly WorktreeStashAction[] = ['push', 'pop', 'list'];
+
+/**
+ * Validates a stash request
+ */
+export function validateWorktreeStash(payload: unknown): WorktreeStashInput {
+  const { org, repo, branch } = validateRequired(payload, ['org', 'repo', 'branch'] as const);
+  const body = payload as Record<string, unknown>;
+  const action = body['action'];
+  if (typeof action !== 'string' || !STASH_ACTIONS.includes(action as WorktreeStashAction)) {
+    throw new ValidationError(`action must be one of ${STASH_ACTIONS.join(', ')}`);
+  }
+  const message = body['message'];
+  if (message !== undefined && message !== null && (typeof message !== 'string' || message.includes('\n'))) {
+    throw new ValidationError('message must be a single-line string');
+  }
+  const includeUntracked = body['includeUntracked'];
+  if (includeUntracked !== undefined && includeUntracked !== null && typeof includeUntracked !== 'boolean') {
+    throw new ValidationError('includeUntracked must be a boolean');
+  }
+  return {
+    org,
+    repo,
+    branch,
+    action: action as WorktreeStashAction,
+    ...(typeof message === 'string' && message.trim() ? { message: message.trim() } : {}),
+    ...(includeUntracked === true ? { includeUntracked: true } : {}),
+  };
+}
+
+/**
+ * Validates a request to set the upstream of a worktree's branch
+ */
+export function validateWorktreeUpstream(payload: unknown): WorktreeUpstreamInput {
+  const { org, repo, branch } = validateRequired(payload, ['org', 'repo', 'branch'] as const);
+  const body = payload as Record<string, unknown>;
+  const remote = validateRemoteName(body['remote']);
+  const remoteBranch = body['remoteBranch'];
+  if (remoteBranch === undefined || remoteBranch === null) {
+    return { org, repo, branch, remote };
+  }
+  if (typeof remoteBranch !== 'string') {
+    throw new ValidationError('remoteBranch must be a string');
+  }
+  const [problem] = findBranchNameProblems(remoteBranch.trim());
+  if (problem) {
+    throw new ValidationError(`remoteBranch is invalid: ${problem.message}`);
+  }
+  return { org, repo, branch, remote, remoteBranch: remoteBranch.trim() };
+}
+
+/**
+ * Validates a worktree reset request. Resetting discards work, so the caller must pass `confirm: true`.
+ */
+export function validateWorktreeReset(payload: unknown): WorktreeResetInput {
+  const { org, repo, branch } = validateRequired(payload, ['org', 'repo', 'branch'] as const);
+  const body = payload as Record<string, unknown>;
+  if (body['confirm'] !== true) {
+    throw new ValidationError('confirm must be true to reset a worktree; uncommitted changes are discarded');
+  }
+  const ref = body['ref'];
+  if (ref !== undefined && ref !== null && (typeof ref !== 'string' || !ref.trim())) {
+    throw new ValidationError('ref must be a non-empty string');
+  }
+  return typeof ref === 'string' ? { org, repo, branch, ref: ref.trim() } : { org, repo, branch };
+}
+
+/**
+ * Validates a request to replace a worktree with a fresh one. The old worktree's changes and commits are
+ * discarded, so the caller must pass `confirm: true`.
+ */
+export function validateWorktreeReplace(payload: unknown): WorktreeReplaceInput {
+  const { org, repo, branch } = validateRequired(payload, ['org', 'repo', 'branch'] as const);
+  const body = payload as Record<string, unknown>;
+  if (body['confirm'] !== true) {
+    throw new ValidationError('confirm must be true to replace a worktree; its changes and commits are discarded');
+  }
+  const [problem] = findBranchNameProblems(branch);
+  if (problem) {
+    throw new ValidationError(`branch is invalid: ${problem.message}`);
+  }
+  const base = body['base'];
+  if (base === undefined || base === null) {
+    return { org, repo, branch };
+  }
+  if (typeof base !== 'string' || !base.trim() || base.trim().startsWith('-')) {
+    throw new ValidationError('base must be a branch or ref name');
+  }
+  return { org, repo, branch, base: base.trim() };
+}
+
+/**
+ * Validates a worktree setup request
+ */
+export function validateWorktreeSetup(payload: unknown): WorktreeSetupInput {
+  return validateRequired(payload, ['org', 'repo', 'branch'] as const);
+}
@@ -1,4 +1,5 @@
 import React, { createContext, useCallback, useContext, useEffect, useState } from 'react';
+import { withBasePath } from '../utils/basePath.js';
 
 const { createElement: h } = React;
 
@@ -33,7 +34,7 @@ export function AuthProvider({ children }: AuthProviderProps) {
 
   const checkAuthStatus = useCallback(async () => {
     try {
-      const response = await fetch('/api/auth/status', { credentials: 'include' });
+      const response = await fetch(withBasePath('/api/auth/status'), { credentials: 'include' });
       if (!response.ok) {
         setAuthStatus('unauthenticated');
         return;
@@ -65,7 +66,7 @@ export function AuthProvider({ children }: AuthProviderProps) {
     }
     setIsLoggingOut(true);
     try {
-      await fetch('/api/auth/logout', {
+      await fetch(withBasePath('/api/auth/logout'), {
         method: 'POST',
         credentials: 'include'
       });
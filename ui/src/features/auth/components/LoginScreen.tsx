@@ -1,4 +1,5 @@
 import React, { useState, useEffect, useRef, useCallback } from 'react';
+import { withBasePath } from '../../../utils/basePath.js';
 
 const { createElement: h } = React;
 
@@ -32,7 +33,7 @@ export default function LoginScreen({ onAuthenticated }: LoginScreenProps) {
       setIsSubmitting(true);
       setError(null);
       try {
-        const response = await fetch('/api/auth/login', {
+        const response = await fetch(withBasePath('/api/auth/login'), {
           method: 'POST',
           headers: { 'Content-Type': 'application/json' },
           credentials: 'include',
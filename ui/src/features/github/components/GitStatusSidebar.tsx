@@ -13,6 +13,7 @@ import {
   XCircle,
 } from 'lucide-react';
 import { useTheme } from '../../../context/ThemeContext.js';
+import { withBasePath } from '../../../utils/basePath.js';
 
 const AUTO_REFRESH_INTERVAL_MS = 6000;
 const LARGE_SECTION_THRESHOLD = 25;
@@ -319,7 +320,7 @@ export default function GitStatusSidebar({
           params.set('commitLimit', String(commitLimit));
         }
 
-        const response = await fetch(`/api/git/status?${params.toString()}`, {
+        const response = await fetch(withBasePath(`/api/git/status?${params.toString()}`), {
           credentials: 'include',
           signal: controller.signal,
         });
@@ -1,4 +1,5 @@
 import React from 'react';
+import { withBasePath } from '../../../utils/basePath.js';
 
 const ISSUE_ENDPOINT = '/api/repos/issue';
 
@@ -117,7 +118,7 @@ export function useIssueDetails({
       typeof window !== 'undefined' && window.location?.origin
         ? window.location.origin
         : 'http://localhost';
-    const url = new URL(ISSUE_ENDPOINT, baseUrl);
+    const url = new URL(withBasePath(ISSUE_ENDPOINT), baseUrl);
     url.searchParams.set('org', repository.org);
     url.searchParams.set('repo', repository.repo);
     url.searchParams.set('issue', String(issueNumber));
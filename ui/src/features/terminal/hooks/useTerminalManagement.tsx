@@ -9,6 +9,7 @@ import * as terminalService from '../../../services/api/terminalService.js';
 import { isAuthenticationError } from '../../../services/api/api-client.js';
 import { useTheme } from '../../../context/ThemeContext.js';
 import type { Worktree } from '../../../types/domain.js';
+import { getWebSocketUrl } from '../../../utils/basePath.js';
 
 const isTerminalDebugEnabled = import.meta.env.MODE !== 'production';
 const terminalDebugLog = (message: string) => {
@@ -237,10 +238,7 @@ export function useTerminalManagement({ onAuthExpired, onSessionRemoved }: UseTe
         return;
       }
       disposeSocket();
-      const protocol = window.location.protocol === 'https:' ? 'wss' : 'ws';
-      const socketUrl = `${protocol}://${window.location.host}/api/terminal/socket?sessionId=${encodeURIComponent(
-        newSessionId
-      )}`;
+      const socketUrl = getWebSocketUrl(`/api/terminal/socket?sessionId=${encodeURIComponent(newSessionId)}`);
       const socket = new WebSocket(socketUrl);
       socket.binaryType = 'arraybuffer';
       const connectionId = ++connectionSequenceRef.current;
@@ -2,6 +2,8 @@
  * Centralized API client with unified error handling
  */
 
+import { withBasePath } from '../../utils/basePath.js';
+
 export interface FetchOptions extends RequestInit {
   /**
    * Whether to include credentials (default: true)
@@ -72,7 +74,7 @@ export async function apiRequest<T = unknown>(
     ...fetchOptions
   } = options;
 
-  const response = await fetch(url, {
+  const response = await fetch(withBasePath(url), {
     ...fetchOptions,
     credentials,
   });
@@ -162,7 +164,7 @@ export async function apiHead(
   url: string,
   options: Omit<FetchOptions, 'method' | 'body'> = {}
 ): Promise<void> {
-  const response = await fetch(url, {
+  const response = await fetch(withBasePath(url), {
     ...options,
     method: 'HEAD',
     credentials: options.credentials ?? 'include',
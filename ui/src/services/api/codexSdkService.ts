@@ -1,4 +1,5 @@
 import { apiDelete, apiGet, apiPost } from './api-client.js';
+import { getWebSocketUrl } from '../../utils/basePath.js';
 import type { CodexSdkSessionDetail, CodexSdkSessionSummary } from '../../types/codex-sdk.js';
 
 export interface CreateCodexSessionOptions {
@@ -59,7 +60,6 @@ export function getCodexSdkSocketUrl(sessionId: string): string {
   if (typeof window === 'undefined' || !window.location) {
     throw new Error('Cannot determine Codex SDK socket URL outside the browser');
   }
-  const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
   const params = new URLSearchParams({ sessionId });
-  return `${protocol}//${window.location.host}/api/codex-sdk/socket?${params.toString()}`;
+  return getWebSocketUrl(`/api/codex-sdk/socket?${params.toString()}`);
 }
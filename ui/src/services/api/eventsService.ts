@@ -3,6 +3,7 @@
  */
 
 import type { EventStreamCallbacks } from '../../types/api.js';
+import { withBasePath } from '../../utils/basePath.js';
 
 /**
  * Create an EventSource connection to the server
@@ -110,7 +111,7 @@ export function createEventStream({
       eventSource = null;
     }
 
-    const source = new EventSource('/api/events');
+    const source = new EventSource(withBasePath('/api/events'));
     eventSource = source;
 
     source.addEventListener('open', handleConnect);
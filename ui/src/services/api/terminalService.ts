@@ -3,6 +3,7 @@
  */
 
 import { apiGet, apiPost } from './api-client.js';
+import { getWebSocketUrl, withBasePath } from '../../utils/basePath.js';
 import type { WorktreeSession } from '../../types/domain.js';
 
 interface FetchSessionsResponse {
@@ -101,7 +102,7 @@ export async function closeTerminal(sessionId: string): Promise<boolean> {
  * Send input to a terminal session
  */
 export async function sendTerminalInput(org: string, repo: string, branch: string, data: string): Promise<boolean> {
-  const response = await fetch('/api/terminal/input', {
+  const response = await fetch(withBasePath('/api/terminal/input'), {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     credentials: 'include',
@@ -119,7 +120,7 @@ export async function sendTerminalInput(org: string, repo: string, branch: strin
  * Resize a terminal session
  */
 export async function resizeTerminal(org: string, repo: string, branch: string, cols: number, rows: number): Promise<boolean> {
-  const response = await fetch('/api/terminal/resize', {
+  const response = await fetch(withBasePath('/api/terminal/resize'), {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     credentials: 'include',
@@ -137,7 +138,6 @@ export async function resizeTerminal(org: string, repo: string, branch: string,
  * Get WebSocket URL for terminal
  */
 export function getTerminalWebSocketUrl(org: string, repo: string, branch: string): string {
-  const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
   const params = new URLSearchParams({ org, repo, branch });
-  return `${protocol}//${window.location.host}/api/terminal/socket?${params.toString()}`;
+  return getWebSocketUrl(`/api/terminal/socket?${params.toString()}`);
 }
@@ -0,0 +1,38 @@
+import { describe, expect, it, afterEach, vi } from 'vitest';
+import { getBasePath, getWebSocketUrl, withBasePath } from '../basePath.js';
+import { apiGet } from '../../services/api/api-client.js';
+
+describe('basePath', () => {
+  afterEach(() => {
+    delete window.__AGENTRIX_BASE__;
+    vi.unstubAllGlobals();
+  });
+
+  it('leaves paths alone when the server is mounted at the root', () => {
+    expect(getBasePath()).toBe('');
+    expect(withBasePath('/api/repos')).toBe('/api/repos');
+  });
+
+  it('prefixes root-relative API and socket paths with the announced base path', () => {
+    window.__AGENTRIX_BASE__ = '/agentrix/';
+
+    expect(withBasePath('/api/repos?org=acme')).toBe('/agentrix/api/repos?org=acme');
+    expect(withBasePath('https://example.com/api')).toBe('https://example.com/api');
+    expect(withBasePath('//cdn.example.com/x.js')).toBe('//cdn.example.com/x.js');
+    expect(getWebSocketUrl('/api/terminal/socket?sessionId=abc')).toBe(
+      `ws://${window.location.host}/agentrix/api/terminal/socket?sessionId=abc`
+    );
+  });
+
+  it('routes API client requests under the base path', async () => {
+    window.__AGENTRIX_BASE__ = '/agentrix';
+    const fetchSpy = vi.fn().mockResolvedValue(
+      new Response(JSON.stringify({ data: [] }), { status: 200, headers: { 'Content-Type': 'application/json' } })
+    );
+    vi.stubGlobal('fetch', fetchSpy);
+
+    await apiGet('/api/repos');
+
+    expect(fetchSpy.mock.calls[0]?.[0]).toBe('/agentrix/api/repos');
+  });
+});
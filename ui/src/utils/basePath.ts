@@ -0,0 +1,41 @@
+/**
+ * Helpers for running the UI under the path prefix the server is mounted on (`--base-path`)
+ */
+
+declare global {
+  interface Window {
+    /**
+     * Path prefix announced by the server in the served page, such as `/agentrix`; empty at the root
+     */
+    __AGENTRIX_BASE__?: string;
+  }
+}
+
+/**
+ * Returns the path prefix the server is mounted under, without a trailing slash
+ */
+export function getBasePath(): string {
+  if (typeof window === 'undefined') {
+    return '';
+  }
+  const base = window.__AGENTRIX_BASE__;
+  return typeof base === 'string' ? base.replace(/\/+$/, '') : '';
+}
+
+/**
+ * Prefixes a root-relative path such as `/api/repos` with the base path; other URLs are returned unchanged
+ */
+export function withBasePath(path: string): string {
+  if (!path.startsWith('/') || path.startsWith('//')) {
+    return path;
+  }
+  return `${getBasePath()}${path}`;
+}
+
+/**
+ * Builds a WebSocket URL on the current host for a root-relative path, honouring the base path
+ */
+export function getWebSocketUrl(path: string): string {
+  const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:';
+  return `${protocol}//${window.location.host}${withBasePath(path)}`;
+}
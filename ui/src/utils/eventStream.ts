@@ -1,3 +1,5 @@
+import { withBasePath } from './basePath.js';
+
 export function createEventStream({
   onRepos,
   onSessions,
@@ -101,7 +103,7 @@ export function createEventStream({
       eventSource = null;
     }
 
-    const source = new EventSource('/api/events');
+    const source = new EventSource(withBasePath('/api/events'));
     eventSource = source;
 
     source.addEventListener('open', handleConnect);
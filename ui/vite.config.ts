@@ -3,6 +3,9 @@ import react from '@vitejs/plugin-react';
 
 export default defineConfig(({ mode }) => ({
   plugins: [react()],
+  // Relative asset and chunk URLs resolve against the <base href> the server injects, so the build works under
+  // any --base-path
+  base: './',
   server: {
     host: '0.0.0.0',
     port: 5173,